@@ -0,0 +1,212 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::json;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Field, FormContext, InputType};
+use pwt::widget::{Button, Column, Dialog, InputPanel, TabBarItem, TabPanel, Toolbar};
+
+use proxmox_schema::api_types::PASSWORD_SCHEMA;
+
+use pwt_macros::builder;
+
+use crate::user_panel::check_confirm_password;
+use crate::{EditWindow, PermissionPanel, SchemaValidation, TokenPanel};
+
+/// Account dialog for the logged-in user.
+///
+/// Lets the user change their password, view the granted permissions and manage their own
+/// API tokens. Meant to be opened from a user menu.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct AccountDialog {
+    /// The user ID, defaults to the logged-in user.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub userid: Option<AttrValue>,
+
+    /// The URL used to change the password.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or("/access/password".into())]
+    pub password_url: AttrValue,
+
+    /// Show the API token tab.
+    #[builder]
+    #[prop_or(true)]
+    pub show_tokens: bool,
+
+    /// Close callback.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for AccountDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountDialog {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+pub enum Msg {
+    ChangePassword(bool),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxAccountDialog {
+    change_password: bool,
+}
+
+fn password_input_panel(_form_ctx: &FormContext, userid: &str) -> Html {
+    InputPanel::new()
+        .padding(4)
+        .with_field(
+            tr!("User name"),
+            Field::new()
+                .name("userid")
+                .default(userid.to_string())
+                .disabled(true)
+                .submit(false),
+        )
+        .with_field(
+            tr!("Current password"),
+            Field::new()
+                .name("confirmation-password")
+                .required(true)
+                .autofocus(true)
+                .input_type(InputType::Password),
+        )
+        .with_field(
+            tr!("New password"),
+            Field::new()
+                .name("password")
+                .required(true)
+                .schema(&PASSWORD_SCHEMA)
+                .input_type(InputType::Password),
+        )
+        .with_field(
+            tr!("Confirm password"),
+            Field::new()
+                .name("confirm_password")
+                .required(true)
+                .submit(false)
+                .input_type(InputType::Password),
+        )
+        .into()
+}
+
+async fn change_password(
+    form_ctx: FormContext,
+    url: AttrValue,
+    userid: String,
+) -> Result<(), Error> {
+    let data = form_ctx.get_submit_data();
+    let param = json!({
+        "userid": userid,
+        "password": data["password"],
+        "confirmation-password": data["confirmation-password"],
+    });
+    crate::http_put(url.as_str(), Some(param)).await
+}
+
+impl ProxmoxAccountDialog {
+    fn password_dialog(&self, ctx: &Context<Self>, userid: &str) -> Html {
+        let props = ctx.props();
+        let url = props.password_url.clone();
+        let userid = userid.to_string();
+
+        EditWindow::new(tr!("Change Password"))
+            .renderer({
+                let userid = userid.clone();
+                move |form_ctx: &FormContext| password_input_panel(form_ctx, &userid)
+            })
+            .on_change(check_confirm_password)
+            .on_submit(move |form_ctx| change_password(form_ctx, url.clone(), userid.clone()))
+            .on_done(ctx.link().callback(|_| Msg::ChangePassword(false)))
+            .into()
+    }
+}
+
+impl Component for ProxmoxAccountDialog {
+    type Message = Msg;
+    type Properties = AccountDialog;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            change_password: false,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ChangePassword(show) => self.change_password = show,
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let userid = match &props.userid {
+            Some(userid) => userid.to_string(),
+            None => crate::http_get_auth()
+                .map(|auth| auth.userid)
+                .unwrap_or_default(),
+        };
+
+        let toolbar = Toolbar::new().class("pwt-border-bottom").with_child(
+            Button::new(tr!("Change Password"))
+                .icon_class("fa fa-fw fa-key")
+                .disabled(userid.is_empty())
+                .onclick(ctx.link().callback(|_| Msg::ChangePassword(true))),
+        );
+
+        let mut tabs = TabPanel::new().class("pwt-flex-fit").with_item(
+            TabBarItem::new()
+                .key("permissions")
+                .label(tr!("Permissions")),
+            PermissionPanel::new().auth_id(userid.clone()),
+        );
+
+        if props.show_tokens {
+            tabs.add_item(
+                TabBarItem::new().key("tokens").label(tr!("API Tokens")),
+                TokenPanel::new().userid(userid.clone()),
+            );
+        }
+
+        let dialog = Dialog::new(tr!("My Account") + ": " + &userid)
+            .resizable(true)
+            .width(840)
+            .height(600)
+            .on_close(props.on_close.clone())
+            .with_child(
+                Column::new()
+                    .class("pwt-flex-fit")
+                    .with_child(toolbar)
+                    .with_child(tabs),
+            );
+
+        let password_dialog = self
+            .change_password
+            .then(|| self.password_dialog(ctx, &userid));
+
+        html! {<>{dialog}{password_dialog}</>}
+    }
+}
+
+impl From<AccountDialog> for VNode {
+    fn from(val: AccountDialog) -> Self {
+        let comp = VComp::new::<ProxmoxAccountDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
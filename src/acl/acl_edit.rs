@@ -1,13 +1,14 @@
+use serde_json::Value;
 use yew::html::IntoPropValue;
 
 use pwt::prelude::*;
-use pwt::widget::form::{Checkbox, FormContext};
-use pwt::widget::{FieldLabel, InputPanel};
+use pwt::widget::form::{Checkbox, FormContext, RadioButton};
+use pwt::widget::{FieldLabel, InputPanel, Row};
 
 use pwt_macros::builder;
 
 use crate::EditWindow;
-use crate::{AuthidSelector, RoleSelector};
+use crate::{AuthidSelector, GroupSelector, RoleSelector};
 
 pub trait AclEditWindow: Into<EditWindow> {}
 
@@ -19,6 +20,14 @@ pub struct AclEdit {
     #[builder]
     use_tokens: bool,
 
+    /// Show a toggle to switch between users, groups and API tokens in the dialog.
+    ///
+    /// The initial selection is defined by `use_tokens`. Groups only exist on Proxmox VE, group
+    /// entries are submitted with its `groups` and `roles` parameters.
+    #[prop_or_default]
+    #[builder]
+    ugid_type_toggle: bool,
+
     /// The endpoint which will be used to create new ACL entries via a PUT request.
     #[prop_or(String::from("/access/acl"))]
     #[builder(IntoPropValue, into_prop_value)]
@@ -51,22 +60,32 @@ impl AclEdit {
     }
 }
 
+const UGID_TYPE: &str = "_ugid_type";
+
+fn render_ugid_type_toggle(default: &str) -> Row {
+    let radio = |value: &str, label: String| {
+        RadioButton::new(value.to_string())
+            .name(UGID_TYPE)
+            .key(format!("ugid-type-{value}"))
+            .default(default == value)
+            .box_label(label)
+            .submit(false)
+    };
+    Row::new()
+        .gap(2)
+        .with_child(radio("user", tr!("User")))
+        .with_child(radio("group", tr!("Group")))
+        .with_child(radio("token", tr!("API Token")))
+}
+
 impl From<AclEdit> for EditWindow {
     fn from(value: AclEdit) -> Self {
-        let field = AuthidSelector::new().name("auth-id").required(true);
-
-        let (title, authid_label, authid_field) = if value.use_tokens {
-            (
-                tr!("API Token Permission"),
-                tr!("API Token"),
-                field.include_users(false),
-            )
-        } else {
-            (
-                tr!("User Permission"),
-                tr!("User"),
-                field.include_tokens(false),
-            )
+        let default_ugid_type = if value.use_tokens { "token" } else { "user" };
+
+        let title = match (value.ugid_type_toggle, value.use_tokens) {
+            (true, _) => tr!("Permission"),
+            (false, true) => tr!("API Token Permission"),
+            (false, false) => tr!("User Permission"),
         };
 
         let mut role_selector = RoleSelector::new().name("role").required(true);
@@ -79,16 +98,44 @@ impl From<AclEdit> for EditWindow {
             role_selector.set_default_role(default_role.clone());
         }
 
-        let input_panel = value
-            .input_panel
-            .clone()
-            .padding(4)
-            .with_field(authid_label, authid_field)
-            .with_field(tr!("Role"), role_selector)
-            .with_field(
-                tr!("Propagate"),
-                Checkbox::new().name("propagate").default(true),
-            );
+        let ugid_type = move |form_ctx: &FormContext| match value.ugid_type_toggle {
+            true => match form_ctx.read().get_field_text(UGID_TYPE) {
+                ty if ty.is_empty() => default_ugid_type.to_string(),
+                ty => ty,
+            },
+            false => default_ugid_type.to_string(),
+        };
+
+        let renderer = {
+            let input_panel = value.input_panel.clone();
+            let ugid_type = ugid_type.clone();
+            move |form_ctx: &FormContext| {
+                let mut panel = input_panel.clone().padding(4);
+
+                if value.ugid_type_toggle {
+                    panel =
+                        panel.with_large_custom_child(render_ugid_type_toggle(default_ugid_type));
+                }
+
+                let field = AuthidSelector::new().name("auth-id").required(true);
+                panel = match ugid_type(form_ctx).as_str() {
+                    "group" => panel.with_field(
+                        tr!("Group"),
+                        GroupSelector::new().name("group").required(true),
+                    ),
+                    "token" => panel.with_field(tr!("API Token"), field.include_users(false)),
+                    _ => panel.with_field(tr!("User"), field.include_tokens(false)),
+                };
+
+                panel
+                    .with_field(tr!("Role"), role_selector.clone())
+                    .with_field(
+                        tr!("Propagate"),
+                        Checkbox::new().name("propagate").default(true),
+                    )
+                    .into()
+            }
+        };
 
         let url = value.acl_api_endpoint.to_owned();
 
@@ -96,15 +143,40 @@ impl From<AclEdit> for EditWindow {
             let url = url.clone();
             move |form_ctx: FormContext| {
                 let url = url.clone();
+                let ugid_type = ugid_type(&form_ctx);
                 async move {
-                    let data = form_ctx.get_submit_data();
+                    let mut data = form_ctx.get_submit_data();
+
+                    // only send the identity matching the selected type, groups use the
+                    // Proxmox VE parameter names
+                    if let Some(map) = data.as_object_mut() {
+                        match ugid_type.as_str() {
+                            "group" => {
+                                map.remove("auth-id");
+                                if let Some(group) = map.remove("group") {
+                                    map.insert("groups".into(), group);
+                                }
+                                if let Some(role) = map.remove("role") {
+                                    map.insert("roles".into(), role);
+                                }
+                            }
+                            _ => {
+                                map.remove("group");
+                            }
+                        }
+                    }
+
+                    // always send the flag, an unchecked checkbox must not fall back to the
+                    // server side default
+                    data["propagate"] = Value::Bool(form_ctx.read().get_field_checked("propagate"));
+
                     crate::http_put(url.as_str(), Some(data)).await
                 }
             }
         };
 
         EditWindow::new(title)
-            .renderer(move |_form_ctx: &FormContext| input_panel.clone().into())
+            .renderer(renderer)
             .on_submit(on_submit)
     }
 }
@@ -0,0 +1,475 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::css;
+use pwt::prelude::*;
+use pwt::props::ExtractPrimaryKey;
+use pwt::state::{Selection, SlabTree, SlabTreeNodeMut, Store, TreeStore};
+use pwt::widget::data_table::{
+    DataTable, DataTableCellRenderArgs, DataTableColumn, DataTableHeader,
+};
+use pwt::widget::form::Field;
+use pwt::widget::{ActionIcon, Button, ConfirmDialog, Fa, Row, Toolbar};
+
+use pwt_macros::builder;
+
+use proxmox_access_control::types::AclListItem;
+
+use crate::{
+    LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState,
+};
+
+use super::acl_view::remove_acl_entry;
+
+/// ACL overview for installations with many entries.
+///
+/// The left pane groups the ACL entries either as tree of paths or by
+/// user/group/token, the right pane lists the entries of the selected group.
+#[derive(PartialEq, Properties)]
+#[builder]
+pub struct AclOverview {
+    /// Specifies the endpoint from which to fetch the ACL entries from via GET and to remove them
+    /// via PUT requests.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(String::from("/access/acl"))]
+    acl_api_endpoint: String,
+
+    /// Read-only view - hide the remove buttons.
+    #[builder]
+    #[prop_or_default]
+    readonly: bool,
+}
+
+impl AclOverview {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+impl Default for AclOverview {
+    fn default() -> Self {
+        AclOverview::new()
+    }
+}
+
+impl From<AclOverview> for VNode {
+    fn from(value: AclOverview) -> Self {
+        VComp::new::<LoadableComponentMaster<ProxmoxAclOverview>>(Rc::new(value), None).into()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum GroupBy {
+    Path,
+    Ugid,
+}
+
+/// A node in the group tree.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+struct AclGroup {
+    /// The path or ugid, empty for the "all" node.
+    key: String,
+    label: String,
+    count: usize,
+}
+
+impl ExtractPrimaryKey for AclGroup {
+    fn extract_key(&self) -> Key {
+        Key::from(self.key.as_str())
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum ViewState {
+    ConfirmRemove(AclListItem),
+}
+
+enum Msg {
+    DataChange,
+    ToggleGroupBy,
+    Filter(String),
+    SelectGroup,
+    Remove(AclListItem),
+}
+
+/// Returns true if an entry matches the (lower case) search text.
+fn acl_entry_matches(item: &AclListItem, filter: &str) -> bool {
+    filter.is_empty()
+        || item.path.to_lowercase().contains(filter)
+        || item.ugid.to_lowercase().contains(filter)
+        || item.roleid.to_lowercase().contains(filter)
+}
+
+/// Returns true if `path` is `base` or below it.
+fn path_in_subtree(path: &str, base: &str) -> bool {
+    let base = base.trim_end_matches('/');
+    base.is_empty() || path == base || path.starts_with(&format!("{base}/"))
+}
+
+fn insert_path(mut node: SlabTreeNodeMut<'_, AclGroup>, components: &[&str]) {
+    node.record_mut().count += 1;
+
+    let Some((component, components)) = components.split_first() else {
+        return;
+    };
+
+    let child_path = match node.record().key.as_str() {
+        "/" => format!("/{component}"),
+        path => format!("{path}/{component}"),
+    };
+
+    match node
+        .children_mut()
+        .find(|child| child.record().key == child_path)
+    {
+        Some(child) => insert_path(child, components),
+        None => {
+            let mut child = node.append(AclGroup {
+                key: child_path,
+                label: component.to_string(),
+                count: 0,
+            });
+            child.set_expanded(true);
+            insert_path(child, components);
+        }
+    }
+}
+
+struct ProxmoxAclOverview {
+    state: LoadableComponentState<ViewState>,
+    store: Store<AclListItem>,
+    group_by: GroupBy,
+    filter: String,
+    group_store: TreeStore<AclGroup>,
+    group_selection: Selection,
+    entry_store: Store<AclListItem>,
+}
+
+pwt::impl_deref_mut_property!(ProxmoxAclOverview, state, LoadableComponentState<ViewState>);
+
+impl ProxmoxAclOverview {
+    fn filtered_entries(&self) -> Vec<AclListItem> {
+        self.store
+            .read()
+            .data()
+            .iter()
+            .filter(|item| acl_entry_matches(item, &self.filter))
+            .cloned()
+            .collect()
+    }
+
+    fn update_groups(&mut self) {
+        let entries = self.filtered_entries();
+
+        let mut tree = SlabTree::new();
+        match self.group_by {
+            GroupBy::Path => {
+                let mut root = tree.set_root(AclGroup {
+                    key: String::from("/"),
+                    label: String::from("/"),
+                    count: 0,
+                });
+                root.set_expanded(true);
+                for item in &entries {
+                    let components: Vec<&str> =
+                        item.path.split('/').filter(|c| !c.is_empty()).collect();
+                    insert_path(tree.root_mut().unwrap(), &components);
+                }
+            }
+            GroupBy::Ugid => {
+                let mut root = tree.set_root(AclGroup {
+                    key: String::new(),
+                    label: tr!("All"),
+                    count: entries.len(),
+                });
+                root.set_expanded(true);
+                let mut ugids: Vec<&str> = entries.iter().map(|item| item.ugid.as_str()).collect();
+                ugids.sort();
+                ugids.dedup();
+                for ugid in ugids {
+                    root.append(AclGroup {
+                        key: ugid.to_string(),
+                        label: ugid.to_string(),
+                        count: entries.iter().filter(|item| item.ugid == ugid).count(),
+                    });
+                }
+            }
+        }
+        tree.sort(true);
+        self.group_store.set_data(tree);
+
+        self.update_entries();
+    }
+
+    fn update_entries(&mut self) {
+        let selected = self
+            .group_selection
+            .selected_key()
+            .map(|key| key.to_string())
+            .unwrap_or_default();
+
+        let entries: Vec<AclListItem> = self
+            .filtered_entries()
+            .into_iter()
+            .filter(|item| match self.group_by {
+                GroupBy::Path => path_in_subtree(&item.path, &selected),
+                GroupBy::Ugid => selected.is_empty() || item.ugid == selected,
+            })
+            .collect();
+
+        self.entry_store.set_data(entries);
+    }
+
+    fn group_columns(&self) -> Rc<Vec<DataTableHeader<AclGroup>>> {
+        let icon_class = match self.group_by {
+            GroupBy::Path => "folder-o",
+            GroupBy::Ugid => "user",
+        };
+        Rc::new(vec![
+            DataTableColumn::new(match self.group_by {
+                GroupBy::Path => tr!("Path"),
+                GroupBy::Ugid => tr!("User/Group/API Token"),
+            })
+            .flex(1)
+            .tree_column(self.group_store.clone())
+            .render_cell(move |args: &mut DataTableCellRenderArgs<AclGroup>| {
+                let icon = Fa::new(icon_class).fixed_width().padding_end(2);
+                html! {<>{icon}{&args.record().label}</>}
+            })
+            .into(),
+            DataTableColumn::new(tr!("Entries"))
+                .justify("right")
+                .render(|group: &AclGroup| group.count.into())
+                .into(),
+        ])
+    }
+
+    fn entry_columns(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Rc<Vec<DataTableHeader<AclListItem>>> {
+        let mut columns = vec![
+            DataTableColumn::new(tr!("Path"))
+                .flex(1)
+                .render(|item: &AclListItem| item.path.as_str().into())
+                .sorter(|a: &AclListItem, b: &AclListItem| a.path.cmp(&b.path))
+                .sort_order(true)
+                .into(),
+            DataTableColumn::new(tr!("User/Group/API Token"))
+                .flex(1)
+                .render(|item: &AclListItem| item.ugid.as_str().into())
+                .sorter(|a: &AclListItem, b: &AclListItem| a.ugid.cmp(&b.ugid))
+                .into(),
+            DataTableColumn::new(tr!("Role"))
+                .flex(1)
+                .render(|item: &AclListItem| item.roleid.as_str().into())
+                .sorter(|a: &AclListItem, b: &AclListItem| a.roleid.cmp(&b.roleid))
+                .into(),
+            DataTableColumn::new(tr!("Propagate"))
+                .render(|item: &AclListItem| {
+                    if item.propagate {
+                        tr!("Yes").into()
+                    } else {
+                        tr!("No").into()
+                    }
+                })
+                .into(),
+        ];
+
+        if !ctx.props().readonly {
+            let link = ctx.link().clone();
+            columns.push(
+                DataTableColumn::new(tr!("Actions"))
+                    .width("70px")
+                    .justify("center")
+                    .render(move |item: &AclListItem| {
+                        let item = item.clone();
+                        ActionIcon::new("fa fa-trash-o")
+                            .tabindex(0)
+                            .on_activate(link.change_view_callback(move |_| {
+                                Some(ViewState::ConfirmRemove(item.clone()))
+                            }))
+                            .into()
+                    })
+                    .into(),
+            );
+        }
+
+        Rc::new(columns)
+    }
+}
+
+impl LoadableComponent for ProxmoxAclOverview {
+    type Properties = AclOverview;
+    type Message = Msg;
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let store = Store::with_extract_key(|record: &AclListItem| {
+            Key::from(format!(
+                "{} for {} - {}",
+                record.path, record.ugid, record.roleid
+            ))
+        })
+        .on_change(ctx.link().callback(|_| Msg::DataChange));
+
+        let entry_store = Store::with_extract_key(|record: &AclListItem| {
+            Key::from(format!(
+                "{} for {} - {}",
+                record.path, record.ugid, record.roleid
+            ))
+        });
+
+        let group_selection = Selection::new().on_select(ctx.link().callback(|_| Msg::SelectGroup));
+
+        Self {
+            state: LoadableComponentState::new(),
+            store,
+            group_by: GroupBy::Path,
+            filter: String::new(),
+            group_store: TreeStore::new(),
+            group_selection,
+            entry_store,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let store = self.store.clone();
+        let url = ctx.props().acl_api_endpoint.clone();
+
+        Box::pin(async move {
+            let data = crate::http_get(&url, None).await?;
+            store.write().set_data(data);
+            Ok(())
+        })
+    }
+
+    fn update(&mut self, ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DataChange => self.update_groups(),
+            Msg::ToggleGroupBy => {
+                self.group_by = match self.group_by {
+                    GroupBy::Path => GroupBy::Ugid,
+                    GroupBy::Ugid => GroupBy::Path,
+                };
+                self.group_selection.clear();
+                self.update_groups();
+            }
+            Msg::Filter(filter) => {
+                self.filter = filter.trim().to_lowercase();
+                self.update_groups();
+            }
+            Msg::SelectGroup => self.update_entries(),
+            Msg::Remove(record) => {
+                let link = ctx.link().clone();
+                let url = ctx.props().acl_api_endpoint.clone();
+                self.spawn(async move {
+                    match remove_acl_entry(url, &record).await {
+                        Ok(()) => link.send_reload(),
+                        Err(err) => link.show_error(tr!("Removing ACL failed"), err, true),
+                    }
+                });
+            }
+        }
+        true
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let toggle_text = match self.group_by {
+            GroupBy::Path => tr!("Group by User"),
+            GroupBy::Ugid => tr!("Group by Path"),
+        };
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .border_bottom(true)
+            .with_child(
+                Button::new(toggle_text).onclick(ctx.link().callback(|_| Msg::ToggleGroupBy)),
+            )
+            .with_child(
+                Field::new()
+                    .placeholder(tr!("Search"))
+                    .on_change(ctx.link().callback(Msg::Filter)),
+            )
+            .with_flex_spacer()
+            .with_child({
+                let loading = self.loading();
+                let link = ctx.link().clone();
+                Button::refresh(loading).onclick(move |_| link.send_reload())
+            });
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let groups = DataTable::new(self.group_columns(), self.group_store.clone())
+            .selection(self.group_selection.clone())
+            .class("pwt-border-end")
+            .style("flex", "0 0 350px");
+
+        let entries = DataTable::new(self.entry_columns(ctx), self.entry_store.clone())
+            .class(css::FlexFit)
+            .striped(true);
+
+        Row::new()
+            .class(css::FlexFit)
+            .with_child(groups)
+            .with_child(entries)
+            .into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        match view_state {
+            ViewState::ConfirmRemove(item) => {
+                let item = item.clone();
+                Some(
+                    ConfirmDialog::default()
+                        .confirm_message(tr!(
+                            "Are you sure you want to remove the role '{0}' of '{1}' on '{2}'?",
+                            item.roleid,
+                            item.ugid,
+                            item.path
+                        ))
+                        .on_confirm({
+                            let link = ctx.link().clone();
+                            move |_| {
+                                link.send_message(Msg::Remove(item.clone()));
+                                link.change_view(None);
+                            }
+                        })
+                        .on_close(ctx.link().change_view_callback(|_| None))
+                        .into(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_in_subtree() {
+        assert!(path_in_subtree("/datastore/store1", "/"));
+        assert!(path_in_subtree("/datastore/store1", "/datastore"));
+        assert!(path_in_subtree("/datastore", "/datastore"));
+        assert!(!path_in_subtree("/datastore2", "/datastore"));
+        assert!(!path_in_subtree("/system", "/datastore"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
 use serde_json::Value;
@@ -6,18 +7,20 @@ use yew::html::IntoPropValue;
 use yew::virtual_dom::Key;
 
 use pwt::prelude::*;
-use pwt::state::Store;
-use pwt::widget::data_table::{DataTable, DataTableColumn};
+use pwt::props::ExtractPrimaryKey;
+use pwt::state::{SlabTree, SlabTreeNodeMut, TreeStore};
+use pwt::widget::data_table::{DataTable, DataTableCellRenderArgs, DataTableColumn};
 use pwt::widget::form::{Selector, SelectorRenderArgs};
-use pwt::widget::GridPicker;
+use pwt::widget::{Fa, GridPicker};
 
 use pwt_macros::{builder, widget};
 
 /// Selector for ACL paths, offering the paths on which permissions can be granted.
 ///
-/// The candidates are the object keys of the configured permissions listing endpoint. The field
-/// stays editable, so a path missing from the listing, or one that could not be loaded, can
-/// still be entered by hand.
+/// The candidates are the object keys of the configured permissions listing endpoint, the paths
+/// of existing ACL entries and, if configured, the paths of the entries returned by a resource
+/// listing endpoint. They are presented as a tree. The field stays editable, so a path missing
+/// from the listings, or one that could not be loaded, can still be entered by hand.
 #[widget(comp=ProxmoxAclPathSelector, @input, @element)]
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
@@ -26,6 +29,20 @@ pub struct AclPathSelector {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or(AttrValue::Static("/access/permissions"))]
     pub permissions_api_endpoint: AttrValue,
+
+    /// Endpoint returning the existing ACL entries, their paths are offered too.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(Some(AttrValue::Static("/access/acl")))]
+    pub acl_api_endpoint: Option<AttrValue>,
+
+    /// Resource listing endpoint (for example `/cluster/resources`).
+    ///
+    /// Guests, nodes, storages, pools and SDN zones listed there are offered with their ACL
+    /// path (`/vms/{vmid}`, `/nodes/{node}`, `/storage/{storage}`, `/pool/{pool}` and
+    /// `/sdn/zones/{zone}`).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub resources_api_endpoint: Option<AttrValue>,
 }
 
 impl AclPathSelector {
@@ -41,14 +58,90 @@ impl Default for AclPathSelector {
     }
 }
 
+#[doc(hidden)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AclPathEntry {
+    path: String,
+    name: String,
+}
+
+impl ExtractPrimaryKey for AclPathEntry {
+    fn extract_key(&self) -> Key {
+        Key::from(self.path.as_str())
+    }
+}
+
+/// Map a resource listing entry to its ACL path.
+fn resource_acl_path(item: &Value) -> Option<String> {
+    let value = |name| item[name].as_str();
+    match value("type")? {
+        "qemu" | "lxc" => item["vmid"].as_u64().map(|vmid| format!("/vms/{vmid}")),
+        "node" => value("node").map(|node| format!("/nodes/{node}")),
+        "storage" => value("storage").map(|storage| format!("/storage/{storage}")),
+        "pool" => value("pool").map(|pool| format!("/pool/{pool}")),
+        "sdn" => value("sdn").map(|zone| format!("/sdn/zones/{zone}")),
+        _ => None,
+    }
+}
+
+async fn load_paths(url: &str, extract: impl Fn(Value) -> Vec<String>) -> Vec<String> {
+    match crate::http_get::<Value>(url, None).await {
+        Ok(data) => extract(data),
+        Err(err) => {
+            // manual path entry still works, so just log the miss
+            log::error!("loading ACL paths from {url} failed: {err}");
+            Vec::new()
+        }
+    }
+}
+
+fn insert_path(mut node: SlabTreeNodeMut<'_, AclPathEntry>, components: &[&str]) {
+    let Some((component, components)) = components.split_first() else {
+        return;
+    };
+
+    if let Some(child) = node.children_mut().find(|c| c.record().name == *component) {
+        insert_path(child, components);
+    } else {
+        let parent = &node.record().path;
+        let path = if parent == "/" {
+            format!("/{component}")
+        } else {
+            format!("{parent}/{component}")
+        };
+        let child = node.append(AclPathEntry {
+            path,
+            name: component.to_string(),
+        });
+        insert_path(child, components);
+    }
+}
+
+fn paths_to_tree(paths: BTreeSet<String>) -> SlabTree<AclPathEntry> {
+    let mut tree = SlabTree::new();
+    tree.set_root(AclPathEntry {
+        path: String::from("/"),
+        name: String::from("/"),
+    });
+    tree.root_mut().unwrap().set_expanded(true);
+
+    for path in paths {
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        insert_path(tree.root_mut().unwrap(), &components);
+    }
+
+    tree.sort(true);
+    tree
+}
+
 #[doc(hidden)]
 pub enum Msg {
-    Loaded(Vec<String>),
+    Loaded(SlabTree<AclPathEntry>),
 }
 
 #[doc(hidden)]
 pub struct ProxmoxAclPathSelector {
-    store: Store<String>,
+    store: TreeStore<AclPathEntry>,
 }
 
 impl Component for ProxmoxAclPathSelector {
@@ -56,30 +149,50 @@ impl Component for ProxmoxAclPathSelector {
     type Properties = AclPathSelector;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let endpoint = ctx.props().permissions_api_endpoint.to_string();
+        let props = ctx.props();
+        let permissions_endpoint = props.permissions_api_endpoint.to_string();
+        let acl_endpoint = props.acl_api_endpoint.clone();
+        let resources_endpoint = props.resources_api_endpoint.clone();
+
         ctx.link().send_future(async move {
-            let mut paths: Vec<String> = match crate::http_get::<Value>(&endpoint, None).await {
-                Ok(Value::Object(map)) => map.keys().cloned().collect(),
-                Ok(_) => Vec::new(),
-                Err(err) => {
-                    // manual path entry still works, so just log the miss
-                    log::error!("loading ACL paths from {endpoint} failed: {err}");
-                    Vec::new()
+            let mut paths = BTreeSet::new();
+
+            paths.extend(
+                load_paths(&permissions_endpoint, |data| match data {
+                    Value::Object(map) => map.keys().cloned().collect(),
+                    _ => Vec::new(),
+                })
+                .await,
+            );
+
+            let list_paths = |extract: fn(&Value) -> Option<String>| {
+                move |data: Value| match data {
+                    Value::Array(list) => list.iter().filter_map(extract).collect(),
+                    _ => Vec::new(),
                 }
             };
-            paths.sort();
-            Msg::Loaded(paths)
+
+            if let Some(url) = acl_endpoint {
+                let acl_path = |item: &Value| item["path"].as_str().map(String::from);
+                paths.extend(load_paths(&url, list_paths(acl_path)).await);
+            }
+
+            if let Some(url) = resources_endpoint {
+                paths.extend(load_paths(&url, list_paths(resource_acl_path)).await);
+            }
+
+            Msg::Loaded(paths_to_tree(paths))
         });
 
         Self {
-            store: Store::with_extract_key(|item: &String| Key::from(item.as_str())),
+            store: TreeStore::new(),
         }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::Loaded(paths) => {
-                self.store.write().set_data(paths);
+            Msg::Loaded(tree) => {
+                self.store.set_data(tree);
                 true
             }
         }
@@ -88,13 +201,15 @@ impl Component for ProxmoxAclPathSelector {
     fn view(&self, ctx: &Context<Self>) -> Html {
         Selector::new(
             self.store.clone(),
-            |args: &SelectorRenderArgs<Store<String>>| {
-                let column = Rc::new(vec![
-                    DataTableColumn::new("Path")
-                        .show_menu(false)
-                        .render(|v: &String| html! {v})
-                        .into(),
-                ]);
+            |args: &SelectorRenderArgs<TreeStore<AclPathEntry>>| {
+                let column = Rc::new(vec![DataTableColumn::new(tr!("Path"))
+                    .show_menu(false)
+                    .tree_column(args.store.clone())
+                    .render_cell(|args: &mut DataTableCellRenderArgs<AclPathEntry>| {
+                        let icon = Fa::new("folder-o").fixed_width().padding_end(2);
+                        html! {<>{icon}{&args.record().name}</>}
+                    })
+                    .into()]);
 
                 let table = DataTable::new(column, args.store.clone())
                     .striped(true)
@@ -55,6 +55,11 @@ pub struct AclView {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or(Some(5000))]
     auto_reload_interval: Option<u32>,
+
+    /// Read-only view - hide toolbar and all buttons/menus to edit content.
+    #[builder]
+    #[prop_or_default]
+    readonly: bool,
 }
 
 impl AclView {
@@ -96,6 +101,26 @@ impl From<AclView> for VNode {
     }
 }
 
+/// Remove a single ACL entry via the ACL API endpoint.
+pub(super) async fn remove_acl_entry(url: String, record: &AclListItem) -> Result<(), Error> {
+    let data = match record.ugid_type {
+        AclUgidType::User => json!({
+            "delete": true,
+            "path": record.path,
+            "role": record.roleid,
+            "auth-id": record.ugid,
+        }),
+        AclUgidType::Group => json!({
+            "delete": true,
+            "path": record.path,
+            "role": record.roleid,
+            "group": record.ugid,
+        }),
+    };
+
+    crate::http_put(url, Some(data)).await
+}
+
 #[derive(Clone, PartialEq)]
 enum ViewState {
     AddAcl(AttrValue),
@@ -209,6 +234,9 @@ impl LoadableComponent for ProxmoxAclView {
     }
 
     fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        if ctx.props().readonly {
+            return None;
+        }
         let selected_id = self.selection.selected_key().map(|k| k.to_string());
         let disabled = selected_id.is_none();
 
@@ -256,22 +284,7 @@ impl LoadableComponent for ProxmoxAclView {
                         let url = ctx.props().acl_api_endpoint.to_owned();
 
                         self.spawn(async move {
-                            let data = match record.ugid_type {
-                                AclUgidType::User => json!({
-                                    "delete": true,
-                                    "path": record.path,
-                                    "role": record.roleid,
-                                    "auth-id": record.ugid,
-                                }),
-                                AclUgidType::Group => json!({
-                                    "delete": true,
-                                    "path": record.path,
-                                    "role": record.roleid,
-                                    "group": record.ugid,
-                                }),
-                            };
-
-                            match crate::http_put(url, Some(data)).await {
+                            match remove_acl_entry(url, &record).await {
                                 Ok(()) => link.send_reload(),
                                 Err(err) => link.show_error("Removing ACL failed", err, true),
                             }
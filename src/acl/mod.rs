@@ -1,6 +1,9 @@
 pub(crate) mod acl_edit;
 pub use acl_edit::AclEdit;
 
+pub(crate) mod acl_overview;
+pub use acl_overview::AclOverview;
+
 pub(crate) mod acl_path_selector;
 pub use acl_path_selector::AclPathSelector;
 
@@ -5,7 +5,7 @@ use std::rc::Rc;
 use anyhow::{bail, Error};
 use proxmox_client::ApiResponseData;
 use serde_json::{json, Value};
-use yew::html::IntoPropValue;
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
@@ -19,7 +19,7 @@ use pwt_macros::builder;
 use crate::common_api_types::{create_acme_config_string, parse_acme_config_string, AcmeConfig};
 use crate::common_api_types::{create_acme_domain_string, parse_acme_domain_string, AcmeDomain};
 use crate::percent_encoding::percent_encode_component;
-use crate::{ConfirmButton, EditWindow, LoadableComponentState};
+use crate::{ConfirmButton, EditWindow, LoadableComponentState, TaskProgress};
 use crate::{
     LoadableComponent, LoadableComponentContext, LoadableComponentMaster, LoadableComponentScopeExt,
 };
@@ -40,6 +40,23 @@ pub struct AcmeDomainsPanel {
     #[builder(IntoPropValue, into_prop_value)]
     /// The base url for
     pub url: AttrValue,
+
+    /// The certificate order endpoint.
+    #[prop_or("/nodes/localhost/certificates/acme/certificate".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub order_url: AttrValue,
+
+    /// The task base url used to track the order task.
+    #[prop_or("/nodes/localhost/tasks".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub task_base_url: AttrValue,
+
+    /// Called after a certificate was ordered successfully.
+    ///
+    /// Use this to reload the [CertificateList](super::CertificateList).
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_certificate_ordered: Option<Callback<()>>,
 }
 
 impl Default for AcmeDomainsPanel {
@@ -78,6 +95,7 @@ pub enum ViewState {
     Add,
     Edit(Key),
     EditAccount,
+    OrderCertificate(String),
 }
 
 impl LoadableComponent for ProxmoxAcmeDomainsPanel {
@@ -250,9 +268,20 @@ impl LoadableComponent for ProxmoxAcmeDomainsPanel {
             })
             .with_child(Button::new(tr!("Order Certificate Now")).onclick({
                 let link = ctx.link().clone();
+                let order_url = ctx.props().order_url.clone();
                 move |_| {
-                    let command_path = "/nodes/localhost/certificates/acme/certificate";
-                    link.start_task(command_path, None, false);
+                    let link = link.clone();
+                    let command_future = crate::http_post::<String>(order_url.to_string(), None);
+                    link.clone().spawn(async move {
+                        match command_future.await {
+                            Ok(task_id) => {
+                                link.change_view(Some(ViewState::OrderCertificate(task_id)));
+                            }
+                            Err(err) => {
+                                link.show_error(tr!("Error"), err, false);
+                            }
+                        }
+                    });
                 }
             }));
 
@@ -284,6 +313,9 @@ impl LoadableComponent for ProxmoxAcmeDomainsPanel {
                 Some(self.create_edit_acme_domain_dialog(ctx, domain_name))
             }
             ViewState::EditAccount => Some(self.create_edit_acme_account_dialog(ctx)),
+            ViewState::OrderCertificate(task_id) => {
+                Some(self.create_order_certificate_dialog(ctx, task_id))
+            }
         }
     }
 }
@@ -297,6 +329,29 @@ impl From<AcmeDomainsPanel> for VNode {
 }
 
 impl ProxmoxAcmeDomainsPanel {
+    fn create_order_certificate_dialog(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        task_id: &str,
+    ) -> Html {
+        let props = ctx.props();
+        TaskProgress::new(task_id)
+            .base_url(props.task_base_url.clone())
+            .on_close(ctx.link().change_view_callback(|_| None))
+            .on_finish({
+                let on_certificate_ordered = props.on_certificate_ordered.clone();
+                move |task_ok: bool| {
+                    if !task_ok {
+                        return;
+                    }
+                    if let Some(on_certificate_ordered) = &on_certificate_ordered {
+                        on_certificate_ordered.emit(());
+                    }
+                }
+            })
+            .into()
+    }
+
     fn acme_domain_input_panel(form_ctx: &FormContext) -> InputPanel {
         let challenge_type = form_ctx.read().get_field_text("type");
 
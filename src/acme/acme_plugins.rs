@@ -13,7 +13,7 @@ use pwt::prelude::*;
 use pwt::state::{Selection, Store};
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader, DataTableMouseEvent};
 use pwt::widget::form::{DisplayField, Field, FormContext, Number, TextArea};
-use pwt::widget::{Button, InputPanel, Toolbar};
+use pwt::widget::{Button, Container, InputPanel, Toolbar};
 
 use pwt_macros::builder;
 
@@ -25,7 +25,10 @@ use crate::{
     LoadableComponentState,
 };
 
-use super::{AcmeChallengeSchemaItem, AcmeChallengeSelector};
+use super::dns_plugin_data_editor::parse_plugin_data;
+use super::{
+    validate_dns_plugin_data, AcmeChallengeSchemaItem, AcmeChallengeSelector, DnsPluginDataEditor,
+};
 
 pub(crate) async fn load_acme_plugin_list(url: AttrValue) -> Result<Vec<PluginConfig>, Error> {
     let data: Vec<PluginConfig> = crate::http_get(&*url, None).await?;
@@ -108,7 +111,6 @@ pub enum Msg {
     Edit(Key),
     Delete(Option<Key>),
     ChallengeSchema(Option<AcmeChallengeSchemaItem>),
-    ApiDataChange(FormContext, String, String),
     LoadChallengeSchemaList,
     UpdateChallengeSchemaList(Result<Vec<AcmeChallengeSchemaItem>, Error>),
 }
@@ -209,14 +211,6 @@ impl LoadableComponent for ProxmoxAcmePluginsPanel {
                 self.challenge_schema = schema;
                 true
             }
-            Msg::ApiDataChange(form_ctx, name, value) => {
-                Self::update_api_data(
-                    &form_ctx,
-                    self.challenge_schema.as_ref(),
-                    Some((name, value)),
-                );
-                true
-            }
             Msg::CloseDialog => {
                 self.challenge_schema = None;
                 ctx.link().change_view(None);
@@ -338,45 +332,24 @@ impl From<AcmePluginsPanel> for VNode {
 }
 
 impl ProxmoxAcmePluginsPanel {
-    fn update_api_data(
-        form_ctx: &FormContext,
-        challenge_schema: Option<&AcmeChallengeSchemaItem>,
-        insert: Option<(String, String)>,
-    ) {
-        let api_data = form_ctx.read().get_field_text("data");
-        let mut parsed_data = Self::parse_plugin_data(&api_data);
-
-        if let Some((name, value)) = insert {
-            // just add the new data
-            parsed_data.insert(name, value);
+    /// Drop data of fields unknown to the challenge schema.
+    fn update_api_data(form_ctx: &FormContext, challenge_schema: Option<&AcmeChallengeSchemaItem>) {
+        let field_list = challenge_schema
+            .and_then(|challenge_schema| challenge_schema.schema["fields"].as_object());
+
+        if let Some(field_list) = field_list {
+            let parsed_data = parse_plugin_data(&form_ctx.read().get_field_text("data"));
             let mut api_data = Vec::new();
-            for (field_name, value) in parsed_data {
-                api_data.push(format!("{field_name}={value}"));
-            }
-            let api_data = api_data.join("\n");
-            form_ctx
-                .write()
-                .set_field_value("data", api_data.clone().into());
-        } else {
-            let field_list = challenge_schema
-                .and_then(|challenge_schema| challenge_schema.schema["fields"].as_object());
-
-            // only add data from known fields
-            if let Some(field_list) = field_list {
-                let mut api_data = Vec::new();
-                for (field_name, _field_schema) in field_list {
-                    if let Some(value) = parsed_data.get(field_name) {
-                        let value = value.trim();
-                        if !value.is_empty() {
-                            api_data.push(format!("{field_name}={value}"))
-                        }
+            for (field_name, _field_schema) in field_list {
+                if let Some((_, value)) = parsed_data.iter().find(|(key, _)| key == field_name) {
+                    let value = value.trim();
+                    if !value.is_empty() {
+                        api_data.push(format!("{field_name}={value}"))
                     }
                 }
-                let api_data = api_data.join("\n");
-                form_ctx
-                    .write()
-                    .set_field_value("data", api_data.clone().into());
             }
+            let api_data = api_data.join("\n");
+            form_ctx.write().set_field_value("data", api_data.into());
         }
     }
 
@@ -387,8 +360,6 @@ impl ProxmoxAcmePluginsPanel {
         challenge_schema: Option<&AcmeChallengeSchemaItem>,
         challenge_store: Store<AcmeChallengeSchemaItem>,
     ) -> InputPanel {
-        let api_data = form_ctx.read().get_field_text("data");
-
         let mut panel = InputPanel::new()
             .width(600)
             .class("pwt-flex-fit")
@@ -434,65 +405,36 @@ impl ProxmoxAcmePluginsPanel {
             );
         }
 
-        let field_list = challenge_schema
-            .and_then(|challenge_schema| challenge_schema.schema["fields"].as_object());
+        let has_fields = challenge_schema
+            .and_then(|challenge_schema| challenge_schema.schema["fields"].as_object())
+            .is_some();
 
         panel.add_field_with_options(
             pwt::widget::FieldPosition::Left,
             false,
-            field_list.is_some(),
+            has_fields,
             tr!("API Data"),
             TextArea::new()
                 .name("data")
                 .class("pwt-w-100")
                 .submit_empty(true)
                 .submit(false)
+                .validate({
+                    let challenge_schema = challenge_schema.cloned();
+                    move |value: &String| validate_dns_plugin_data(challenge_schema.as_ref(), value)
+                })
                 .attribute("rows", "4"),
         );
 
-        if let Some(field_list) = field_list {
-            for (field_name, field_schema) in field_list {
-                let parsed_data = Self::parse_plugin_data(&api_data);
-                let value = parsed_data
-                    .get(field_name)
-                    .map(|s| s.to_owned())
-                    .unwrap_or_default();
-                let description: Option<String> =
-                    field_schema["description"].as_str().map(|s| s.to_owned());
-                let placeholder = field_schema["default"].as_str().map(|s| s.to_owned());
-
-                panel.add_field(
-                    format!("{}=", field_name),
-                    Field::new()
-                        .key(format!("data_{}", field_name))
-                        .tip(description)
-                        .submit(false)
-                        .placeholder(placeholder)
-                        .value(value)
-                        .on_change({
-                            let field_name = field_name.clone();
-                            let form_ctx = form_ctx.clone();
-                            link.callback(move |v| {
-                                Msg::ApiDataChange(form_ctx.clone(), field_name.clone(), v)
-                            })
-                        }),
-                )
-            }
+        if has_fields {
+            panel.add_large_custom_child(Container::new().key("__plugin_data__").with_child(
+                DnsPluginDataEditor::new(form_ctx.clone()).schema(challenge_schema.cloned()),
+            ));
         }
 
         panel
     }
 
-    fn parse_plugin_data(data: &str) -> HashMap<String, String> {
-        let mut map = HashMap::new();
-        for line in data.lines() {
-            if let Some((key, value)) = line.split_once('=') {
-                map.insert(key.to_owned(), value.to_owned());
-            }
-        }
-        map
-    }
-
     fn create_edit_dns_plugin_dialog(
         &self,
         ctx: &crate::LoadableComponentContext<Self>,
@@ -527,7 +469,7 @@ impl ProxmoxAcmePluginsPanel {
                     let mut data = form_ctx.get_submit_data();
                     let url = url.clone();
 
-                    Self::update_api_data(&form_ctx, challenge_schema.as_ref(), None);
+                    Self::update_api_data(&form_ctx, challenge_schema.as_ref());
                     data["data"] =
                         proxmox_base64::encode(form_ctx.read().get_field_text("data")).into();
 
@@ -560,7 +502,7 @@ impl ProxmoxAcmePluginsPanel {
             .on_submit({
                 let challenge_schema = self.challenge_schema.clone();
                 move |form_ctx: FormContext| {
-                    Self::update_api_data(&form_ctx, challenge_schema.as_ref(), None);
+                    Self::update_api_data(&form_ctx, challenge_schema.as_ref());
 
                     let mut data = form_ctx.get_submit_data();
                     data["type"] = "dns".into();
@@ -5,6 +5,7 @@ use std::rc::Rc;
 use anyhow::Error;
 use serde_json::{json, Value};
 
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
@@ -14,25 +15,43 @@ use pwt::widget::data_table::{
 };
 use pwt::widget::form::{Form, FormContext, TextArea};
 use pwt::widget::{Button, Container, Dialog, FileButton, MessageBox, Toolbar};
+use pwt::AsyncPool;
+
+use pwt_macros::builder;
 
 use crate::common_api_types::CertificateInfo;
+use crate::grid_state::{GridState, PersistentGridState};
 use crate::utils::render_epoch;
 use crate::{
     ConfirmButton, EditWindow, KVGrid, KVGridRow, LoadableComponent, LoadableComponentContext,
-    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState,
+    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState, Status,
 };
 
-async fn upload_custom_certificate(form_ctx: FormContext) -> Result<(), Error> {
+async fn upload_custom_certificate(node: &str, form_ctx: FormContext) -> Result<(), Error> {
     let mut data = form_ctx.get_submit_data();
     data["force"] = true.into();
     data["restart"] = true.into();
-    let _certs: Vec<CertificateInfo> =
-        crate::http_post("/nodes/localhost/certificates/custom", Some(data)).await?;
+    let url = format!("/nodes/{node}/certificates/custom");
+    let _certs: Vec<CertificateInfo> = crate::http_post(&url, Some(data)).await?;
     Ok(())
 }
 
 #[derive(PartialEq, Properties)]
-pub struct CertificateList {}
+#[builder]
+pub struct CertificateList {
+    /// Changing this value reloads the list.
+    ///
+    /// Useful to show a certificate which was ordered by the
+    /// [AcmeDomainsPanel](super::AcmeDomainsPanel).
+    #[prop_or_default]
+    #[builder]
+    pub reload_generation: usize,
+
+    /// Save column adjustments in the browser's local storage, using this id.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub state_id: Option<AttrValue>,
+}
 
 impl Default for CertificateList {
     fn default() -> Self {
@@ -42,24 +61,28 @@ impl Default for CertificateList {
 
 impl CertificateList {
     pub fn new() -> Self {
-        Self {}
+        yew::props!(Self {})
     }
 }
 
 #[derive(PartialEq)]
 pub enum ViewState {
-    CertificateView(Rc<Value>),
+    CertificateView(CertificateInfo),
     UploadCustomCertificate,
     PleaseReload,
 }
 
+pub enum Msg {
+    UpdateGridState(GridState),
+}
+
 #[doc(hidden)]
 pub struct ProxmoxCertificateList {
     state: LoadableComponentState<ViewState>,
     selection: Selection,
     store: Store<CertificateInfo>,
     columns: Rc<Vec<DataTableHeader<CertificateInfo>>>,
-    rows: Rc<Vec<KVGridRow>>,
+    grid_state: Option<PersistentGridState<CertificateInfo>>,
 }
 
 pwt::impl_deref_mut_property!(
@@ -70,7 +93,7 @@ pwt::impl_deref_mut_property!(
 
 impl LoadableComponent for ProxmoxCertificateList {
     type Properties = CertificateList;
-    type Message = ();
+    type Message = Msg;
     type ViewState = ViewState;
 
     fn create(ctx: &LoadableComponentContext<Self>) -> Self {
@@ -81,13 +104,17 @@ impl LoadableComponent for ProxmoxCertificateList {
         let store =
             Store::with_extract_key(|record: &CertificateInfo| Key::from(record.filename.clone()));
         let columns = Rc::new(columns());
-        let rows = Rc::new(rows());
+        let grid_state = ctx
+            .props()
+            .state_id
+            .as_deref()
+            .map(|state_id| PersistentGridState::new(state_id, columns.clone()));
         Self {
             state: LoadableComponentState::new(),
             selection,
             store,
             columns,
-            rows,
+            grid_state,
         }
     }
 
@@ -104,6 +131,35 @@ impl LoadableComponent for ProxmoxCertificateList {
         })
     }
 
+    fn changed(
+        &mut self,
+        ctx: &LoadableComponentContext<Self>,
+        old_props: &Self::Properties,
+    ) -> bool {
+        if ctx.props().reload_generation != old_props.reload_generation {
+            ctx.link().send_reload();
+        }
+        if ctx.props().state_id != old_props.state_id {
+            self.grid_state = ctx
+                .props()
+                .state_id
+                .as_deref()
+                .map(|state_id| PersistentGridState::new(state_id, self.columns.clone()));
+        }
+        true
+    }
+
+    fn update(&mut self, _ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::UpdateGridState(state) => {
+                if let Some(grid_state) = &mut self.grid_state {
+                    grid_state.update(state);
+                }
+                true
+            }
+        }
+    }
+
     fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
         let selected_key = self.selection.selected_key();
         let selected_cert = match &selected_key {
@@ -160,21 +216,28 @@ impl LoadableComponent for ProxmoxCertificateList {
                         let link = ctx.link().clone();
                         move |_| {
                             if let Some(selected_cert) = &selected_cert {
-                                let cert_data: Value =
-                                    serde_json::to_value(selected_cert.clone()).unwrap();
-                                link.change_view(Some(ViewState::CertificateView(Rc::new(
-                                    cert_data,
-                                ))));
+                                link.change_view(Some(ViewState::CertificateView(
+                                    selected_cert.clone(),
+                                )));
                             }
                         }
                     }),
-            );
+            )
+            .with_flex_spacer()
+            .with_optional_child(self.grid_state.as_ref().map(|grid_state| {
+                grid_state.columns_menu(ctx.link().callback(Msg::UpdateGridState))
+            }));
 
         Some(toolbar.into())
     }
 
     fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        DataTable::new(self.columns.clone(), self.store.clone())
+        let columns = match &self.grid_state {
+            Some(grid_state) => grid_state.columns(),
+            None => self.columns.clone(),
+        };
+
+        let table = DataTable::new(columns, self.store.clone())
             .class("pwt-flex-fit")
             .selection(self.selection.clone())
             .on_row_dblclick({
@@ -183,12 +246,17 @@ impl LoadableComponent for ProxmoxCertificateList {
                 move |event: &mut DataTableMouseEvent| {
                     let key = &event.record_key;
                     if let Some(selected_cert) = store.read().lookup_record(key).cloned() {
-                        let cert_data: Value = serde_json::to_value(selected_cert.clone()).unwrap();
-                        link.change_view(Some(ViewState::CertificateView(Rc::new(cert_data))));
+                        link.change_view(Some(ViewState::CertificateView(selected_cert)));
                     };
                 }
-            })
-            .into()
+            });
+
+        match &self.grid_state {
+            Some(grid_state) => grid_state
+                .track(table, ctx.link().callback(Msg::UpdateGridState))
+                .into(),
+            None => table.into(),
+        }
     }
 
     fn dialog_view(
@@ -238,10 +306,106 @@ async fn update_field_from_file(
 
 impl ProxmoxCertificateList {
     fn create_upload_custom_certificate(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        let link = ctx.link().clone();
+        CertificateUploadDialog::new()
+            .on_close(ctx.link().change_view_callback(|_| None))
+            .on_upload(
+                ctx.link()
+                    .change_view_callback(|_| Some(ViewState::PleaseReload)),
+            )
+            .into()
+    }
+
+    fn create_please_reload_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let msg = tr!(
+            "API server will be restarted to use new certificates, please reload web-interface!"
+        );
+
+        MessageBox::new(tr!("Please Reload"), msg)
+            .on_close(ctx.link().change_view_callback(|_| None))
+            .into()
+    }
+
+    fn create_certificate_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        info: &CertificateInfo,
+    ) -> Html {
+        CertificateDetailsDialog::new(info.clone())
+            .on_close(ctx.link().change_view_callback(|_| None))
+            .into()
+    }
+}
+
+/// Dialog to upload a custom certificate chain and private key.
+///
+/// Both can be pasted or loaded from a file. The certificate is uploaded to
+/// `/nodes/{node}/certificates/custom`, which also restarts the API server.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct CertificateUploadDialog {
+    /// The node name.
+    #[prop_or(AttrValue::Static("localhost"))]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub node: AttrValue,
+
+    /// Close/Abort callback.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+
+    /// Called after a successful upload.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_upload: Option<Callback<()>>,
+}
+
+impl Default for CertificateUploadDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CertificateUploadDialog {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxCertificateUploadDialog {
+    async_pool: AsyncPool,
+}
+
+impl Component for ProxmoxCertificateUploadDialog {
+    type Message = ();
+    type Properties = CertificateUploadDialog;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            async_pool: AsyncPool::new(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let file_button = |field_name: &'static str, form_ctx: &FormContext| {
+            let form_ctx = form_ctx.clone();
+            let async_pool = self.async_pool.clone();
+            FileButton::new(tr!("From File"))
+                .class("pwt-align-self-flex-start pwt-scheme-primary")
+                .on_change(move |file_list: Option<web_sys::FileList>| {
+                    async_pool.spawn(update_field_from_file(
+                        form_ctx.clone(),
+                        field_name,
+                        file_list,
+                    ));
+                })
+        };
+
         EditWindow::new(tr!("Upload Custom Certificate"))
             .width(600)
-            .on_close(link.change_view_callback(|_| None))
+            .on_close(props.on_close.clone())
             .submit_text(tr!("Upload"))
             .renderer(move |form_ctx: &FormContext| {
                 Form::new()
@@ -255,21 +419,7 @@ impl ProxmoxCertificateList {
                             .name("key")
                             .placeholder(tr!("No change")),
                     )
-                    .with_child(
-                        FileButton::new(tr!("From File"))
-                            .class("pwt-align-self-flex-start pwt-scheme-primary")
-                            .on_change({
-                                let form_ctx = form_ctx.clone();
-                                let link = link.clone();
-                                move |file_list: Option<web_sys::FileList>| {
-                                    link.spawn(update_field_from_file(
-                                        form_ctx.clone(),
-                                        "key",
-                                        file_list,
-                                    ));
-                                }
-                            }),
-                    )
+                    .with_child(file_button("key", form_ctx))
                     .with_child(
                         Container::from_tag("span")
                             .padding_top(4)
@@ -281,72 +431,163 @@ impl ProxmoxCertificateList {
                             .attribute("rows", "4")
                             .name("certificates"),
                     )
-                    .with_child(
-                        FileButton::new(tr!("From File"))
-                            .class("pwt-align-self-flex-start pwt-scheme-primary")
-                            .on_change({
-                                let form_ctx = form_ctx.clone();
-                                let link = link.clone();
-                                move |file_list: Option<web_sys::FileList>| {
-                                    link.spawn(update_field_from_file(
-                                        form_ctx.clone(),
-                                        "certificates",
-                                        file_list,
-                                    ));
-                                }
-                            }),
-                    )
+                    .with_child(file_button("certificates", form_ctx))
                     .into()
             })
             .on_submit({
-                let link = ctx.link().clone();
+                let node = props.node.clone();
+                let on_upload = props.on_upload.clone();
                 move |form_ctx: FormContext| {
-                    let link = link.clone();
+                    let node = node.clone();
+                    let on_upload = on_upload.clone();
                     async move {
-                        upload_custom_certificate(form_ctx).await?;
-                        link.change_view(Some(ViewState::PleaseReload));
+                        upload_custom_certificate(&node, form_ctx).await?;
+                        if let Some(on_upload) = &on_upload {
+                            on_upload.emit(());
+                        }
                         Ok(())
                     }
                 }
             })
             .into()
     }
+}
 
-    fn create_please_reload_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        let msg = tr!(
-            "API server will be restarted to use new certificates, please reload web-interface!"
-        );
+impl From<CertificateUploadDialog> for VNode {
+    fn from(val: CertificateUploadDialog) -> Self {
+        let comp = VComp::new::<ProxmoxCertificateUploadDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
 
-        MessageBox::new(tr!("Please Reload"), msg)
-            .on_close(ctx.link().change_view_callback(|_| None))
-            .into()
+/// Returns whether a certificate expiring at `notafter` (UNIX epoch) needs attention.
+///
+/// Expired certificates are an error, certificates expiring within `warning_days` a warning.
+fn expiry_status(notafter: i64, warning_days: u32) -> Option<Status> {
+    let now = (js_sys::Date::now() / 1000.0) as i64;
+    if notafter <= now {
+        Some(Status::Error)
+    } else if notafter - now < i64::from(warning_days) * 24 * 3600 {
+        Some(Status::Warning)
+    } else {
+        None
     }
+}
+
+fn expiry_color(status: Status) -> &'static str {
+    match status {
+        Status::Error => "pwt-color-error",
+        _ => "pwt-color-warning",
+    }
+}
+
+fn render_expiry(notafter: i64, warning_days: u32) -> Html {
+    let class = expiry_status(notafter, warning_days).map(expiry_color);
+    html! {<span {class}>{render_epoch(notafter)}</span>}
+}
+
+/// Dialog showing the details of a certificate.
+///
+/// Shows subject alternative names, fingerprint, issuer and validity. The expiry date is
+/// colored when the certificate is expired or about to expire.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct CertificateDetailsDialog {
+    /// The certificate to show.
+    pub info: CertificateInfo,
+
+    /// Certificates expiring within this number of days are flagged with a warning.
+    #[prop_or(30)]
+    #[builder]
+    pub expiry_warning_days: u32,
+
+    /// Close callback.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl CertificateDetailsDialog {
+    pub fn new(info: CertificateInfo) -> Self {
+        yew::props!(Self { info })
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxCertificateDetailsDialog {
+    rows: Rc<Vec<KVGridRow>>,
+    data: Rc<Value>,
+}
+
+impl Component for ProxmoxCertificateDetailsDialog {
+    type Message = ();
+    type Properties = CertificateDetailsDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        Self {
+            rows: Rc::new(rows(props.expiry_warning_days)),
+            data: Rc::new(serde_json::to_value(&props.info).unwrap_or_default()),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.expiry_warning_days != old_props.expiry_warning_days {
+            self.rows = Rc::new(rows(props.expiry_warning_days));
+        }
+        if props.info != old_props.info {
+            self.data = Rc::new(serde_json::to_value(&props.info).unwrap_or_default());
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
 
-    fn create_certificate_view(
-        &self,
-        ctx: &LoadableComponentContext<Self>,
-        info: &Rc<Value>,
-    ) -> Html {
         let grid = KVGrid::new()
             .class("pwt-flex-fit")
             .borderless(true)
             .striped(false)
             .rows(self.rows.clone())
-            .data(Rc::clone(info))
+            .data(self.data.clone())
             .cell_configuration(
                 CellConfiguration::new()
                     .class("pwt-datatable-cell pwt-user-select-text")
                     .padding(2),
             );
 
+        let expiry_note = props.info.notafter.and_then(|notafter| {
+            let status = expiry_status(notafter, props.expiry_warning_days)?;
+            let note = match status {
+                Status::Error => tr!("The certificate has expired."),
+                _ => tr!("The certificate will expire soon."),
+            };
+            Some((expiry_color(status), note))
+        });
+
         Dialog::new(tr!("Certificate"))
             .with_child(grid)
-            .on_close(ctx.link().change_view_callback(|_| None))
+            .with_optional_child(expiry_note.map(|(class, note)| {
+                Container::new()
+                    .padding(2)
+                    .class("pwt-border-top")
+                    .class(class)
+                    .with_child(note)
+            }))
+            .on_close(props.on_close.clone())
             .into()
     }
 }
 
-fn rows() -> Vec<KVGridRow> {
+impl From<CertificateDetailsDialog> for VNode {
+    fn from(val: CertificateDetailsDialog) -> Self {
+        let comp = VComp::new::<ProxmoxCertificateDetailsDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+fn rows(expiry_warning_days: u32) -> Vec<KVGridRow> {
     let render_date = |_name: &str, value: &Value, _record: &Value| match value.as_i64() {
         Some(value) => html! {render_epoch(value)},
         None => html! {value.to_string()},
@@ -360,7 +601,12 @@ fn rows() -> Vec<KVGridRow> {
         KVGridRow::new("public-key-type", tr!("Public Key Algorithm")),
         KVGridRow::new("public-key-bits", tr!("Public Key Size")),
         KVGridRow::new("notbefore", tr!("Valid Since")).renderer(render_date),
-        KVGridRow::new("notafter", tr!("Expires")).renderer(render_date),
+        KVGridRow::new("notafter", tr!("Expires")).renderer(
+            move |_name, value, _record| match value.as_i64() {
+                Some(value) => render_expiry(value, expiry_warning_days),
+                None => html! {value.to_string()},
+            },
+        ),
         KVGridRow::new("san", tr!("Subject Alternative Names")).renderer(
             |_name, value, _record| {
                 let list: Result<Vec<String>, _> = serde_json::from_value(value.clone());
@@ -382,22 +628,27 @@ fn rows() -> Vec<KVGridRow> {
 fn columns() -> Vec<DataTableHeader<CertificateInfo>> {
     vec![
         DataTableColumn::new(tr!("File"))
+            .key("filename")
             .width("150px")
             .render(|item: &CertificateInfo| html! { &item.filename })
             .into(),
         DataTableColumn::new(tr!("Issuer"))
+            .key("issuer")
             .flex(1)
             .render(|item: &CertificateInfo| html! {&item.issuer})
             .into(),
         DataTableColumn::new(tr!("Subject"))
+            .key("subject")
             .flex(1)
             .render(|item: &CertificateInfo| html! {&item.subject})
             .into(),
         DataTableColumn::new(tr!("Public Key Algorithm"))
+            .key("public-key-type")
             .hidden(true)
             .render(|item: &CertificateInfo| html! {&item.public_key_type})
             .into(),
         DataTableColumn::new(tr!("Public Key Size"))
+            .key("public-key-bits")
             .hidden(true)
             .render(|item: &CertificateInfo| match item.public_key_bits {
                 Some(bits) => html! {bits},
@@ -405,6 +656,7 @@ fn columns() -> Vec<DataTableHeader<CertificateInfo>> {
             })
             .into(),
         DataTableColumn::new(tr!("Valid Since"))
+            .key("notbefore")
             .width("150px")
             .render(|item: &CertificateInfo| match item.notbefore {
                 Some(notbefore) => html! {render_epoch(notbefore)},
@@ -412,19 +664,22 @@ fn columns() -> Vec<DataTableHeader<CertificateInfo>> {
             })
             .into(),
         DataTableColumn::new(tr!("Expires"))
+            .key("notafter")
             .width("150px")
             .render(|item: &CertificateInfo| match item.notafter {
-                Some(notafter) => html! {render_epoch(notafter)},
+                Some(notafter) => render_expiry(notafter, 30),
                 None => html! {"-"},
             })
             .into(),
         DataTableColumn::new(tr!("Subject Alternative Names"))
+            .key("san")
             .flex(1)
             .render(|item: &CertificateInfo| {
                 html! {<pre>{&item.san.join("\n")}</pre>}
             })
             .into(),
         DataTableColumn::new(tr!("Fingerprint"))
+            .key("fingerprint")
             .hidden(true)
             .render(|item: &CertificateInfo| match &item.fingerprint {
                 Some(fingerprint) => html! {fingerprint},
@@ -0,0 +1,252 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+use serde_json::{Map, Value};
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Field, FormContext};
+use pwt::widget::InputPanel;
+
+use pwt_macros::builder;
+
+use super::AcmeChallengeSchemaItem;
+
+/// Parse DNS plugin data (`key=value` lines), preserving the order.
+pub(crate) fn parse_plugin_data(data: &str) -> Vec<(String, String)> {
+    data.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+fn format_plugin_data(data: &[(String, String)]) -> String {
+    data.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn schema_fields(schema: Option<&AcmeChallengeSchemaItem>) -> Option<&Map<String, Value>> {
+    schema.and_then(|schema| schema.schema["fields"].as_object())
+}
+
+fn field_is_optional(field_schema: &Value) -> bool {
+    match &field_schema["optional"] {
+        Value::Bool(false) => false,
+        Value::Number(n) => n.as_u64() != Some(0),
+        _ => true,
+    }
+}
+
+fn validate_field_value(field_schema: &Value, value: &str) -> Result<(), Error> {
+    match field_schema["type"].as_str() {
+        Some("integer") => {
+            if value.parse::<i64>().is_err() {
+                bail!(tr!("Value must be an integer."));
+            }
+        }
+        Some("number") => {
+            if value.parse::<f64>().is_err() {
+                bail!(tr!("Value must be a number."));
+            }
+        }
+        Some("boolean") => {
+            if !matches!(value, "0" | "1") {
+                bail!(tr!("Value must be 0 or 1."));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Validate DNS plugin data against the fields of a challenge schema.
+///
+/// Checks that all mandatory fields are set and the values match their type.
+pub fn validate_dns_plugin_data(
+    schema: Option<&AcmeChallengeSchemaItem>,
+    data: &str,
+) -> Result<(), Error> {
+    let Some(fields) = schema_fields(schema) else {
+        return Ok(());
+    };
+    let data = parse_plugin_data(data);
+    let lookup = |name: &str| {
+        data.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+
+    for (name, field_schema) in fields {
+        match lookup(name) {
+            Some(value) => {
+                if let Err(err) = validate_field_value(field_schema, value) {
+                    bail!("{name}: {err}");
+                }
+            }
+            None if !field_is_optional(field_schema) => {
+                bail!(tr!("Missing value for '{0}'.", name));
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Schema driven editor for the API data of ACME DNS plugins.
+///
+/// Renders an input field for each field of the selected challenge schema, using the field
+/// type for the widget and its validation. The values are stored as `key=value` lines in a
+/// field of the surrounding form (`data` by default), so that field can still be submitted
+/// (and edited) as plain text.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct DnsPluginDataEditor {
+    /// The form context holding the plugin data field.
+    pub form_ctx: FormContext,
+
+    /// The challenge schema of the selected DNS API.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub schema: Option<AcmeChallengeSchemaItem>,
+
+    /// Name of the form field holding the plugin data.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(AttrValue::Static("data"))]
+    pub data_field: AttrValue,
+}
+
+impl DnsPluginDataEditor {
+    pub fn new(form_ctx: FormContext) -> Self {
+        yew::props!(Self { form_ctx })
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Change(String, String),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxDnsPluginDataEditor {}
+
+impl Component for ProxmoxDnsPluginDataEditor {
+    type Message = Msg;
+    type Properties = DnsPluginDataEditor;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {}
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Change(name, value) => {
+                let text = props.form_ctx.read().get_field_text(&props.data_field);
+                let mut data = parse_plugin_data(&text);
+                data.retain(|(key, _)| *key != name);
+
+                if !value.trim().is_empty() {
+                    data.push((name, value));
+                }
+                // keep the order of the schema, unknown fields go last
+                if let Some(fields) = schema_fields(props.schema.as_ref()) {
+                    data.sort_by_key(|(key, _)| {
+                        fields.keys().position(|k| k == key).unwrap_or(usize::MAX)
+                    });
+                }
+
+                props
+                    .form_ctx
+                    .write()
+                    .set_field_value(&*props.data_field, format_plugin_data(&data).into());
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let Some(fields) = schema_fields(props.schema.as_ref()) else {
+            return html! {};
+        };
+
+        let text = props.form_ctx.read().get_field_text(&props.data_field);
+        let data = parse_plugin_data(&text);
+
+        let mut panel = InputPanel::new().class("pwt-flex-fit");
+
+        for (name, field_schema) in fields {
+            let value = data
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default();
+            let description = field_schema["description"].as_str().map(String::from);
+            let on_change = {
+                let name = name.clone();
+                ctx.link()
+                    .callback(move |value: String| Msg::Change(name.clone(), value))
+            };
+
+            if field_schema["type"].as_str() == Some("boolean") {
+                let default = match &field_schema["default"] {
+                    Value::Bool(b) => *b,
+                    Value::Number(n) => n.as_u64() == Some(1),
+                    _ => false,
+                };
+                let checked = match value.as_str() {
+                    "" => default,
+                    value => value == "1",
+                };
+                panel.add_field(
+                    name.clone(),
+                    Checkbox::new()
+                        .key(format!("data_{name}"))
+                        .tip(description)
+                        .checked(checked)
+                        .on_change({
+                            let on_change = on_change.clone();
+                            move |checked: bool| {
+                                on_change.emit(if checked { "1" } else { "0" }.to_string())
+                            }
+                        }),
+                );
+                continue;
+            }
+
+            let placeholder = match &field_schema["default"] {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            };
+
+            let field_schema = field_schema.clone();
+            panel.add_field(
+                name.clone(),
+                Field::new()
+                    .key(format!("data_{name}"))
+                    .tip(description)
+                    .placeholder(placeholder)
+                    .required(!field_is_optional(&field_schema))
+                    .validate(move |value: &String| validate_field_value(&field_schema, value))
+                    .value(value)
+                    .on_change(on_change),
+            );
+        }
+
+        panel.into()
+    }
+}
+
+impl From<DnsPluginDataEditor> for VNode {
+    fn from(val: DnsPluginDataEditor) -> Self {
+        let comp = VComp::new::<ProxmoxDnsPluginDataEditor>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
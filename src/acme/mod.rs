@@ -19,6 +19,11 @@ pub use acme_challenge_selector::{
     AcmeChallengeSchemaItem, AcmeChallengeSelector, ProxmoxAcmeChallengeSelector,
 };
 
+mod dns_plugin_data_editor;
+pub use dns_plugin_data_editor::{
+    validate_dns_plugin_data, DnsPluginDataEditor, ProxmoxDnsPluginDataEditor,
+};
+
 mod acme_register_account;
 pub use acme_register_account::{AcmeRegisterAccount, ProxmoxAcmeRegisterAccount};
 
@@ -32,4 +37,7 @@ mod acme_plugins;
 pub use acme_plugins::{AcmePluginsPanel, ProxmoxAcmePluginsPanel};
 
 mod certificate_list;
-pub use certificate_list::CertificateList;
+pub use certificate_list::{
+    CertificateDetailsDialog, CertificateList, CertificateUploadDialog,
+    ProxmoxCertificateDetailsDialog, ProxmoxCertificateUploadDialog,
+};
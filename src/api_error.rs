@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde_json::Value;
+
+use pwt::widget::form::FormContext;
+
+/// Error reported by the Proxmox API.
+///
+/// Failed API calls return the HTTP status, a message and, for parameter verification
+/// errors, a message for each invalid parameter. The HTTP helpers ([crate::http_get],
+/// [crate::http_post], ...) return this inside the [anyhow::Error], use
+/// [ApiError::from_error] to access it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiError {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The error message.
+    pub message: String,
+    /// Error messages for single parameters (field name => message).
+    pub errors: BTreeMap<String, String>,
+}
+
+impl ApiError {
+    /// Create a new instance without parameter errors.
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            errors: BTreeMap::new(),
+        }
+    }
+
+    /// Access the [ApiError] contained in an [anyhow::Error], if any.
+    pub fn from_error(err: &anyhow::Error) -> Option<&Self> {
+        err.downcast_ref::<Self>()
+    }
+
+    /// Parse the API response (status and body).
+    ///
+    /// Returns `None` for successful responses.
+    pub fn from_response(status: u16, body: &[u8]) -> Option<Self> {
+        let data = serde_json::from_slice::<Value>(body).ok();
+        Self::from_parsed_response(status, body, data.as_ref())
+    }
+
+    /// Like [Self::from_response], with the body already parsed as JSON (if possible).
+    pub(crate) fn from_parsed_response(
+        status: u16,
+        body: &[u8],
+        data: Option<&Value>,
+    ) -> Option<Self> {
+        let status_ok = (200..300).contains(&status);
+
+        let Some(Value::Object(data)) = data else {
+            if status_ok {
+                return None;
+            }
+            let message = match std::str::from_utf8(body).map(str::trim) {
+                Ok(text) if !text.is_empty() => text.to_string(),
+                _ => http::StatusCode::from_u16(status)
+                    .ok()
+                    .and_then(|status| status.canonical_reason())
+                    .unwrap_or("unknown error")
+                    .to_string(),
+            };
+            return Some(Self::new(status, message));
+        };
+
+        let success = match data.get("success") {
+            Some(Value::Bool(success)) => *success,
+            Some(Value::Number(n)) => n.as_u64() == Some(1),
+            Some(Value::String(s)) => s == "1" || s == "true",
+            _ => status_ok,
+        };
+        if success && status_ok {
+            return None;
+        }
+
+        // the extjs formatter always replies with 200, the real status is in the body
+        let status = match data.get("status").and_then(Value::as_u64) {
+            Some(status) => status as u16,
+            None if status_ok => 400,
+            None => status,
+        };
+
+        let message = match data.get("message") {
+            Some(Value::String(message)) => message.trim().to_string(),
+            _ => String::from("no message provided"),
+        };
+
+        let errors = match data.get("errors") {
+            Some(Value::Object(errors)) => errors
+                .iter()
+                .map(|(name, msg)| {
+                    let msg = match msg {
+                        Value::String(msg) => msg.trim().to_string(),
+                        other => other.to_string(),
+                    };
+                    (name.clone(), msg)
+                })
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+
+        Some(Self {
+            status,
+            message,
+            errors,
+        })
+    }
+
+    /// The error message for parameter `name`.
+    pub fn field_error(&self, name: &str) -> Option<&str> {
+        self.errors.get(name).map(String::as_str)
+    }
+
+    /// Mark the form fields with parameter errors as invalid.
+    ///
+    /// Returns true if at least one field was marked.
+    pub fn mark_form_fields(&self, form_ctx: &FormContext) -> bool {
+        let mut marked = false;
+        let mut form_state = form_ctx.write();
+        for (name, msg) in &self.errors {
+            if form_state.get_field_data(name.clone()).is_some() {
+                form_state.set_field_valid(name.clone(), Err(msg.clone()));
+                marked = true;
+            }
+        }
+        marked
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for (name, msg) in &self.errors {
+            write!(f, "\n{name}: {msg}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_response() {
+        let body = br#"{"success":1,"data":null}"#;
+        assert_eq!(ApiError::from_response(200, body), None);
+
+        let body = br#"{"success":0,"status":400,"message":"parameter verification failed\n","errors":{"name":"value too long\n"},"data":null}"#;
+        let err = ApiError::from_response(200, body).unwrap();
+        assert_eq!(err.status, 400);
+        assert_eq!(err.message, "parameter verification failed");
+        assert_eq!(err.field_error("name"), Some("value too long"));
+        assert_eq!(
+            err.to_string(),
+            "parameter verification failed\nname: value too long"
+        );
+
+        let err = ApiError::from_response(500, b"internal error").unwrap();
+        assert_eq!(err.status, 500);
+        assert_eq!(err.message, "internal error");
+        assert!(err.errors.is_empty());
+    }
+}
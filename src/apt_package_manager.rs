@@ -22,6 +22,7 @@ use pwt::widget::{AlertDialog, Button, Container, Toolbar, Tooltip};
 use crate::percent_encoding::percent_encode_component;
 use crate::subscription_alert::subscription_is_active;
 use crate::LoadableComponentState;
+use crate::Markdown;
 use crate::SubscriptionAlert;
 use crate::{
     DataViewWindow, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
@@ -31,8 +32,6 @@ use proxmox_apt_api_types::APTUpdateInfo;
 
 use pwt_macros::builder;
 
-// fixme: add Upgrade button (opens xtermjs)
-
 async fn list_updates(base_url: AttrValue) -> Result<Vec<APTUpdateInfo>, Error> {
     let url = format!("{base_url}/update");
     crate::http_get(url, None).await
@@ -56,12 +55,19 @@ pub struct AptPackageManager {
     #[builder]
     pub enable_upgrade: bool,
 
-    /// What happens when the 'Upgrade' button is clicked, by default opens the XTermJs upgrade
-    /// console for 'localhost'
+    /// What happens when the 'Upgrade all' button is clicked, by default opens the XTermJs
+    /// upgrade console for 'localhost'
     #[prop_or_default]
     #[builder_cb(IntoEventCallback, into_event_callback, ())]
     pub on_upgrade: Option<Callback<()>>,
 
+    /// Upgrade only the selected packages.
+    ///
+    /// The 'Upgrade selected' button is only shown if this is set.
+    #[prop_or_default]
+    #[builder_cb(IntoEventCallback, into_event_callback, Vec<String>)]
+    pub on_upgrade_packages: Option<Callback<Vec<String>>>,
+
     // todo refactor url+message into a struct like EditableProperty
     #[prop_or_default]
     #[builder(IntoPropValue, into_prop_value)]
@@ -157,7 +163,8 @@ fn update_list_to_tree(updates: &[APTUpdateInfo]) -> SlabTree<TreeEntry> {
 
 #[derive(Clone, PartialEq)]
 pub enum ViewState {
-    ShowChangelog(String),
+    /// Show the changelog of a package (name, version).
+    ShowChangelog(String, String),
     ShowSubscriptionPopup,
 }
 
@@ -188,7 +195,7 @@ impl LoadableComponent for ProxmoxAptPackageManager {
         let props = ctx.props();
         let tree_store = TreeStore::new().view_root(false);
         let columns = Self::columns(ctx, tree_store.clone());
-        let selection = Selection::new().on_select({
+        let selection = Selection::new().multiselect(true).on_select({
             let link = ctx.link().clone();
             move |_| link.send_redraw()
         });
@@ -247,17 +254,12 @@ impl LoadableComponent for ProxmoxAptPackageManager {
     fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
         let props = ctx.props();
 
-        let selected_key = self.selection.selected_key();
-        let selected_record = match selected_key.as_ref() {
-            Some(key) => self
-                .tree_store
-                .read()
-                .lookup_node(key)
-                .map(|r| r.record().clone()),
-            None => None,
-        };
-        let selected_package = match selected_record {
-            Some(TreeEntry::Package(_, info)) => Some(info.package.clone()),
+        let selected_packages = self.selected_packages();
+        let changelog_view = match selected_packages.as_slice() {
+            [info] => Some(ViewState::ShowChangelog(
+                info.package.clone(),
+                info.version.clone(),
+            )),
             _ => None,
         };
 
@@ -288,19 +290,30 @@ impl LoadableComponent for ProxmoxAptPackageManager {
                 }
             }))
             .with_child(
-                Button::new(tr!("Upgrade"))
+                Button::new(tr!("Upgrade all"))
                     .disabled(!props.enable_upgrade)
                     .onclick(on_upgrade),
             )
+            .with_optional_child(
+                props
+                    .on_upgrade_packages
+                    .clone()
+                    .map(|on_upgrade_packages| {
+                        let packages: Vec<String> = selected_packages
+                            .iter()
+                            .map(|info| info.package.clone())
+                            .collect();
+                        Button::new(tr!("Upgrade selected"))
+                            .disabled(!props.enable_upgrade || packages.is_empty())
+                            .onclick(move |_| on_upgrade_packages.emit(packages.clone()))
+                    }),
+            )
             .with_child(
                 Button::new(tr!("Changelog"))
-                    .disabled(selected_package.is_none())
+                    .disabled(changelog_view.is_none())
                     .onclick({
                         let link = ctx.link().clone();
-                        let view = selected_package
-                            .as_ref()
-                            .map(|p| ViewState::ShowChangelog(p.clone()));
-                        move |_| link.change_view(view.clone())
+                        move |_| link.change_view(changelog_view.clone())
                     }),
             )
             .with_flex_spacer()
@@ -328,8 +341,8 @@ impl LoadableComponent for ProxmoxAptPackageManager {
         view_state: &Self::ViewState,
     ) -> Option<Html> {
         match view_state {
-            ViewState::ShowChangelog(package) => {
-                Some(self.create_show_changelog_dialog(ctx, package))
+            ViewState::ShowChangelog(package, version) => {
+                Some(self.create_show_changelog_dialog(ctx, package, version))
             }
             ViewState::ShowSubscriptionPopup => {
                 let link = ctx.link().clone();
@@ -378,16 +391,33 @@ impl From<AptPackageManager> for VNode {
 }
 
 impl ProxmoxAptPackageManager {
+    /// Returns the selected packages (selected origins are ignored).
+    fn selected_packages(&self) -> Vec<APTUpdateInfo> {
+        let tree = self.tree_store.read();
+        self.selection
+            .selected_keys()
+            .iter()
+            .filter_map(
+                |key| match tree.lookup_node(key).map(|n| n.record().clone()) {
+                    Some(TreeEntry::Package(_, info)) => Some(*info),
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
     fn create_show_changelog_dialog(
         &self,
         ctx: &LoadableComponentContext<Self>,
         package: &str,
+        version: &str,
     ) -> Html {
         let props = ctx.props().clone();
         let url = format!(
-            "{}/changelog?name={}",
+            "{}/changelog?name={}&version={}",
             props.base_url,
             percent_encode_component(package),
+            percent_encode_component(version),
         );
 
         DataViewWindow::<String>::new(tr!("Changelog") + ": " + package)
@@ -396,18 +426,13 @@ impl ProxmoxAptPackageManager {
             .resizable(true)
             .on_done(ctx.link().change_view_callback(|_| None))
             .loader(url)
-            .renderer(|description: &String| {
-                let mut panel = Container::from_tag("pre")
+            .renderer(|changelog: &String| {
+                Container::new()
                     .padding(2)
-                    .class("pwt-flex-fit pwt-font-monospace");
-
-                if let Some((title, body)) = description.split_once("\n") {
-                    panel.add_child(html! {<h6>{title}</h6>});
-                    panel.add_child(body);
-                } else {
-                    panel.add_child(description);
-                }
-                panel.into()
+                    .class("pwt-flex-fit")
+                    .class("pwt-embedded-html")
+                    .with_child(Markdown::new().text(changelog.clone()))
+                    .into()
             })
             .into()
     }
@@ -417,6 +442,7 @@ impl ProxmoxAptPackageManager {
         store: TreeStore<TreeEntry>,
     ) -> Rc<Vec<DataTableHeader<TreeEntry>>> {
         Rc::new(vec![
+            DataTableColumn::selection_indicator().into(),
             DataTableColumn::new(tr!("Package"))
                 .width("350px")
                 .render_cell(render_tree_node)
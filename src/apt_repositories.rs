@@ -1,11 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use pwt::css::AlignItems;
-use pwt::widget::form::{Combobox, FormContext, ValidateFn};
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, ValidateFn};
+use pwt::widget::menu::{Menu, MenuButton, MenuItem};
 use serde_json::{json, Value};
 
 use yew::html::IntoPropValue;
@@ -17,20 +18,22 @@ use pwt::state::{Selection, SlabTree, Store, TreeStore};
 use pwt::widget::data_table::{
     DataTable, DataTableCellRenderArgs, DataTableColumn, DataTableHeader,
 };
-use pwt::widget::{Button, Column, Container, Fa, Row, Toolbar, Tooltip};
+use pwt::widget::{Button, Column, Container, InputPanel, Row, Toolbar, Tooltip};
 
+use crate::grid_state::{GridState, PersistentGridState};
+use crate::percent_encoding::percent_encode_component;
+use crate::repository_status::{RepositoryStatus, RepositoryStatusLine};
 use crate::subscription_alert::subscription_is_active;
+use crate::utils::render_boolean_icon;
 use crate::{
-    EditWindow, ExistingProduct, LoadableComponent, LoadableComponentContext,
-    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState, ProjectInfo,
-    SubscriptionAlert,
+    ConfirmButton, EditWindow, ExistingProduct, LoadableComponent, LoadableComponentContext,
+    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState, SubscriptionAlert,
 };
 
 use pwt_macros::builder;
 
 use proxmox_apt_api_types::{
-    APTRepositoriesResult, APTRepository, APTRepositoryInfo, APTRepositoryPackageType,
-    APTStandardRepoSummary, APTStandardRepository,
+    APTRepositoriesResult, APTRepository, APTRepositoryInfo, APTStandardRepository,
 };
 
 async fn apt_configuration(base_url: AttrValue) -> Result<APTRepositoriesResult, Error> {
@@ -38,6 +41,63 @@ async fn apt_configuration(base_url: AttrValue) -> Result<APTRepositoriesResult,
     crate::http_get(url, None).await
 }
 
+const SIGNED_BY: &str = "Signed-By";
+
+fn split_list(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+}
+
+fn validate_types(value: &String) -> Result<(), Error> {
+    let types = split_list(value);
+    if types.is_empty() {
+        bail!(tr!("Field may not be empty."));
+    }
+    for ty in types {
+        if ty != "deb" && ty != "deb-src" {
+            bail!(tr!("Invalid package type '{0}'.", ty));
+        }
+    }
+    Ok(())
+}
+
+fn validate_repository_path(value: &String) -> Result<(), Error> {
+    if !value.starts_with('/') || !(value.ends_with(".list") || value.ends_with(".sources")) {
+        bail!(tr!(
+            "Expected an absolute path to a '.list' or '.sources' file."
+        ));
+    }
+    Ok(())
+}
+
+/// Convert the repository form data into a repository entry, using the same format as the
+/// `repositories` API endpoint.
+///
+/// Lists are entered space-separated. Options other than `Signed-By` are not editable, so
+/// they are passed through from `options` (the options of the edited repository).
+fn repository_from_form(data: &Value, options: &[Value]) -> Value {
+    let text = |name: &str| data[name].as_str().unwrap_or("").trim().to_string();
+
+    let mut options: Vec<Value> = options
+        .iter()
+        .filter(|option| option["Key"].as_str() != Some(SIGNED_BY))
+        .cloned()
+        .collect();
+    let signed_by = split_list(&text("signed-by"));
+    if !signed_by.is_empty() {
+        options.push(json!({ "Key": SIGNED_BY, "Values": signed_by }));
+    }
+
+    json!({
+        "Types": split_list(&text("types")),
+        "URIs": split_list(&text("uris")),
+        "Suites": split_list(&text("suites")),
+        "Components": split_list(&text("components")),
+        "Options": options,
+        "Comment": text("comment"),
+        "Enabled": data["enabled"].as_bool().unwrap_or(true),
+    })
+}
+
 #[derive(Properties, PartialEq, Clone)]
 #[builder]
 pub struct AptRepositories {
@@ -61,6 +121,31 @@ pub struct AptRepositories {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or_default]
     pub status_only: bool,
+
+    /// Read-only view - hide all buttons/menus to edit content.
+    #[builder]
+    #[prop_or_default]
+    pub readonly: bool,
+
+    /// Save column adjustments of the repository table in the browser's local storage,
+    /// using this id.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub state_id: Option<AttrValue>,
+
+    /// Cluster nodes, for datacenter level views.
+    ///
+    /// If set, a node selector is shown and the `apt` and `subscription` URLs below
+    /// `node_url` of the selected node are used instead of `base_url` and `subscription_url`.
+    #[prop_or_default]
+    pub nodes: Option<Rc<Vec<AttrValue>>>,
+
+    /// The node URL template used with `nodes`, `{node}` is replaced by the node name.
+    ///
+    /// For example `/pve/remotes/<remote>/nodes/{node}` on the Proxmox Datacenter Manager.
+    #[prop_or("/nodes/{node}".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub node_url: AttrValue,
 }
 
 impl Default for AptRepositories {
@@ -73,223 +158,19 @@ impl AptRepositories {
     pub fn new() -> Self {
         yew::props!(Self {})
     }
-}
-
-#[derive(Copy, Clone, PartialEq)]
-enum Status {
-    Ok,
-    Error,
-    Warning,
-}
-
-#[derive(Clone, PartialEq)]
-struct StatusLine {
-    status: Status,
-    message: Key,
-}
 
-impl StatusLine {
-    fn ok(msg: impl Into<String>) -> Self {
-        StatusLine {
-            status: Status::Ok,
-            message: Key::from(msg.into()),
-        }
-    }
-    fn warning(msg: impl Into<String>) -> Self {
-        StatusLine {
-            status: Status::Warning,
-            message: Key::from(msg.into()),
-        }
+    /// Builder style method to set the cluster nodes.
+    pub fn nodes(mut self, nodes: impl IntoIterator<Item = impl Into<AttrValue>>) -> Self {
+        self.set_nodes(nodes);
+        self
     }
-    fn error(msg: impl Into<String>) -> Self {
-        StatusLine {
-            status: Status::Error,
-            message: Key::from(msg.into()),
-        }
-    }
-}
 
-impl ExtractPrimaryKey for StatusLine {
-    fn extract_key(&self) -> Key {
-        self.message.clone()
+    /// Method to set the cluster nodes.
+    pub fn set_nodes(&mut self, nodes: impl IntoIterator<Item = impl Into<AttrValue>>) {
+        self.nodes = Some(Rc::new(nodes.into_iter().map(Into::into).collect()));
     }
 }
 
-// Note: this should implement the same logic we have in APTRepositories.js
-fn update_status_store(
-    product: Option<ExistingProduct>,
-    status_store: &Store<StatusLine>,
-    config: &APTRepositoriesResult,
-    standard_repos: &HashMap<String, APTStandardRepository>,
-    active_subscription: bool,
-) {
-    let mut list = Vec::new();
-
-    for error in &config.errors {
-        list.push(StatusLine::error(format!(
-            "{} - {}",
-            error.path, error.error
-        )));
-    }
-
-    let product = match product {
-        Some(product) => product,
-        None => return,
-    };
-
-    let summary = APTStandardRepoSummary::from_repos(standard_repos.values());
-    for handle in &summary.unrecognized {
-        log::warn!("encountered unknown APT repository handle variant '{handle}'");
-    }
-    let has_enterprise = summary.has_enterprise;
-    let has_no_subscription = summary.has_no_subscription;
-    let has_test = summary.has_test;
-    let has_ceph_enterprise = summary.has_ceph_enterprise;
-    let has_ceph_no_subscription = summary.has_ceph_no_subscription;
-    let has_ceph_test = summary.has_ceph_test;
-
-    if !(has_enterprise | has_no_subscription | has_test) {
-        list.push(StatusLine::error(tr!(
-            "No {0} repository is enabled, you do not get any updates!",
-            product.project_text()
-        )));
-    } else if config.errors.is_empty() {
-        // just avoid that we show "get updates"
-        if has_test || has_no_subscription {
-            list.push(StatusLine::ok(tr!(
-                "You get updates for {0}",
-                product.project_text()
-            )));
-        } else if has_enterprise && active_subscription {
-            list.push(StatusLine::ok(tr!(
-                "You get supported updates for {0}",
-                product.project_text()
-            )));
-        }
-    }
-
-    let mut enabled_repos: HashSet<(&str, usize)> = HashSet::new();
-    for file in &config.files {
-        for (index, repo) in file.repositories.iter().enumerate() {
-            if repo.enabled {
-                if let Some(path) = &file.path {
-                    enabled_repos.insert((path, index));
-                }
-            }
-        }
-    }
-
-    let mut mixed_suites = false;
-    let mut check_mixed_suites = false;
-
-    let mut ignore_pre_upgrade_warning: HashSet<(&str, usize)> = HashSet::new();
-    let mut controlled_origin: HashSet<(&str, usize)> = HashSet::new();
-    for info in &config.infos {
-        if info.kind == "ignore-pre-upgrade-warning" {
-            ignore_pre_upgrade_warning.insert((&info.path, info.index));
-            check_mixed_suites = true;
-        }
-        if info.kind == "origin" && (info.message == "Debian" || info.message == "Proxmox") {
-            controlled_origin.insert((&info.path, info.index));
-        }
-    }
-
-    let mut suites_warning = false;
-    for info in &config.infos {
-        if info.kind == "warning"
-            && info.property.as_deref() == Some("Suites")
-            && enabled_repos.contains(&(&info.path, info.index))
-        {
-            suites_warning = true;
-            break;
-        }
-    }
-
-    if suites_warning {
-        list.push(StatusLine::warning(tr!("Some suites are misconfigured")));
-    }
-
-    for file in &config.files {
-        if let Some(path) = &file.path {
-            for (index, repo) in file.repositories.iter().enumerate() {
-                if check_mixed_suites
-                    && repo.enabled
-                    && repo.types.contains(&APTRepositoryPackageType::Deb)
-                    && controlled_origin.contains(&(path, index))
-                {
-                    mixed_suites = true;
-                }
-            }
-        }
-    }
-
-    if mixed_suites {
-        list.push(StatusLine::warning(tr!(
-            "Detected mixed suites before upgrade"
-        )));
-    }
-
-    // production ready check
-    if has_enterprise && !active_subscription {
-        list.push(StatusLine::warning(tr!(
-            "The {0}enterprise repository is enabled, but there is no active subscription!",
-            product.project_text() + " ",
-        )));
-    }
-
-    if has_no_subscription {
-        list.push(StatusLine::warning(tr!(
-            "The {0}no-subscription{1} repository is not recommended for production use!",
-            product.project_text() + " ",
-            "",
-        )));
-    }
-
-    if has_test {
-        list.push(StatusLine::warning(tr!(
-            "The {0}test repository may pull in unstable updates and is not recommended for production use!",
-            product.project_text() + " ",
-        )));
-    }
-
-    // check Ceph repositories
-    if has_ceph_enterprise && !active_subscription {
-        list.push(StatusLine::warning(tr!(
-            "The {0}enterprise repository is enabled, but there is no active subscription!",
-            "Ceph ",
-        )));
-    }
-
-    if has_ceph_no_subscription {
-        list.push(StatusLine::warning(tr!(
-            "The {0}no-subscription{1} repository is not recommended for production use!",
-            "Ceph ",
-            "/main", // TODO drop alternate 'main' name when no longer relevant
-        )));
-    }
-
-    if has_ceph_test {
-        list.push(StatusLine::warning(tr!(
-            "The {0}test repository may pull in unstable updates and is not recommended for production use!",
-            "Ceph ",
-        )));
-    }
-
-    if !config.errors.is_empty() {
-        list.push(StatusLine::error(tr!(
-            "Fatal parsing error for at least one repository"
-        )));
-    }
-
-    if !list.iter().any(|l| l.status != Status::Ok) {
-        list.push(StatusLine::ok(tr!(
-            "All OK, you have production-ready repositories configured!"
-        )));
-    }
-
-    status_store.write().set_data(list);
-}
-
 #[derive(Copy, Clone, PartialEq)]
 enum Origin {
     Debian,
@@ -394,13 +275,20 @@ fn apt_configuration_to_tree(config: &APTRepositoriesResult) -> SlabTree<TreeEnt
 
 pub enum Msg {
     ToggleEnable,
+    Remove,
     UpdateStatus(APTRepositoriesResult),
-    SubscriptionInfo(Result<Value, Error>),
+    /// Subscription status of the node (if the `nodes` property is set).
+    SubscriptionInfo(Option<AttrValue>, Result<Value, Error>),
+    UpdateGridState(GridState),
+    SelectNode(String),
 }
 
 #[derive(Clone, PartialEq)]
 pub enum ViewState {
     AddRespository,
+    AddCustomRepository,
+    /// Edit the repository entry (path, index).
+    EditRepository(String, usize),
     ShowSubscription, // show subscription dialog
 }
 
@@ -413,8 +301,11 @@ pub struct ProxmoxAptRepositories {
     standard_repos: HashMap<String, APTStandardRepository>,
     validate_standard_repo: ValidateFn<(String, Store<AttrValue>)>,
     subscription_status: Option<Result<Value, Error>>,
-    status_store: Store<StatusLine>,
-    status_columns: Rc<Vec<DataTableHeader<StatusLine>>>,
+    status_store: Store<RepositoryStatusLine>,
+    status_columns: Rc<Vec<DataTableHeader<RepositoryStatusLine>>>,
+    grid_state: Option<PersistentGridState<TreeEntry>>,
+    // selected node, if the `nodes` property is set
+    node: Option<AttrValue>,
 }
 
 pwt::impl_deref_mut_property!(
@@ -437,21 +328,20 @@ impl LoadableComponent for ProxmoxAptRepositories {
         });
         let status_columns = Self::status_columns(ctx);
 
-        let subscription_url = ctx.props().subscription_url.clone();
-
-        let state = LoadableComponentState::new();
+        let node = ctx
+            .props()
+            .nodes
+            .as_ref()
+            .and_then(|nodes| nodes.first().cloned());
 
-        state.spawn({
-            let link = ctx.link().clone();
-            async move {
-                // TODO: also reload this in load, not only create?!
-                let data = crate::http_get(subscription_url.to_string(), None).await;
-                link.send_message(Msg::SubscriptionInfo(data));
-            }
-        });
+        let grid_state = ctx
+            .props()
+            .state_id
+            .as_deref()
+            .map(|state_id| PersistentGridState::new(state_id, columns.clone()));
 
-        Self {
-            state,
+        let this = Self {
+            state: LoadableComponentState::new(),
             tree_store,
             selection,
             columns,
@@ -461,7 +351,11 @@ impl LoadableComponent for ProxmoxAptRepositories {
             subscription_status: None,
             status_store: Store::new(),
             status_columns,
-        }
+            grid_state,
+            node,
+        };
+        this.load_subscription(ctx);
+        this
     }
 
     fn load(
@@ -469,7 +363,7 @@ impl LoadableComponent for ProxmoxAptRepositories {
         ctx: &LoadableComponentContext<Self>,
     ) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>>>> {
         let props = ctx.props();
-        let base_url = props.base_url.clone();
+        let base_url = self.base_url(props);
         let tree_store = self.tree_store.clone();
         let link = ctx.link().clone();
 
@@ -485,17 +379,40 @@ impl LoadableComponent for ProxmoxAptRepositories {
     fn update(&mut self, ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
         let props = ctx.props();
         match msg {
-            Msg::SubscriptionInfo(status) => {
+            Msg::SelectNode(node) => {
+                let node = AttrValue::from(node);
+                if self.node.as_ref() == Some(&node) {
+                    return false;
+                }
+                self.node = Some(node);
+                self.config = None;
+                self.subscription_status = None;
+                self.status_store.clear();
+                self.load_subscription(ctx);
+                ctx.link().send_reload();
+                true
+            }
+            Msg::UpdateGridState(state) => {
+                if let Some(grid_state) = &mut self.grid_state {
+                    grid_state.update(state);
+                }
+                true
+            }
+            Msg::SubscriptionInfo(node, status) => {
+                // ignore replies for a previously selected node
+                if node != self.node {
+                    return false;
+                }
                 self.subscription_status = Some(status);
                 if let Some(config) = &self.config {
                     let active_subscription = self.active_subscription();
-                    update_status_store(
+                    let status = RepositoryStatus::compute(
                         props.product,
-                        &self.status_store,
                         config,
                         &self.standard_repos,
                         active_subscription,
                     );
+                    self.status_store.write().set_data(status.lines);
                 } else {
                     self.status_store.clear();
                 }
@@ -509,13 +426,13 @@ impl LoadableComponent for ProxmoxAptRepositories {
                     .collect();
 
                 let active_subscription = self.active_subscription();
-                update_status_store(
+                let status = RepositoryStatus::compute(
                     props.product,
-                    &self.status_store,
                     &config,
                     &standard_repos,
                     active_subscription,
                 );
+                self.status_store.write().set_data(status.lines);
 
                 self.config = Some(config);
                 self.standard_repos = standard_repos.clone();
@@ -540,13 +457,15 @@ impl LoadableComponent for ProxmoxAptRepositories {
                     path, index, repo, ..
                 } = selected_record
                 {
-                    let param = json!({
+                    let mut param = json!({
                         "path": path,
                         "index": index,
                         "enabled": !repo.enabled,
                     });
-                    // fixme: add digest to protect against concurrent changes
-                    let url = format!("{}/repositories", props.base_url);
+                    if let Some(digest) = self.digest() {
+                        param["digest"] = digest;
+                    }
+                    let url = format!("{}/repositories", self.base_url(props));
                     let link = ctx.link().clone();
                     link.clone().spawn(async move {
                         match crate::http_post(url, Some(param)).await {
@@ -561,6 +480,27 @@ impl LoadableComponent for ProxmoxAptRepositories {
                 }
                 false
             }
+            Msg::Remove => {
+                let Some(TreeEntry::Repository { path, index, .. }) = self.selected_record() else {
+                    return false;
+                };
+                let mut param = json!({
+                    "path": path,
+                    "index": index,
+                });
+                if let Some(digest) = self.digest() {
+                    param["digest"] = digest;
+                }
+                let url = format!("{}/repositories", self.base_url(props));
+                let link = ctx.link().clone();
+                link.clone().spawn(async move {
+                    if let Err(err) = crate::http_delete(url, Some(param)).await {
+                        link.show_error(tr!("Unable to remove repository"), err, true);
+                    }
+                    link.send_reload();
+                });
+                false
+            }
         }
     }
 
@@ -571,35 +511,128 @@ impl LoadableComponent for ProxmoxAptRepositories {
     ) -> bool {
         let props = ctx.props();
 
+        if props.state_id != old_props.state_id {
+            self.grid_state = props
+                .state_id
+                .as_deref()
+                .map(|state_id| PersistentGridState::new(state_id, self.columns.clone()));
+        }
+
+        if props.nodes != old_props.nodes {
+            let nodes = props.nodes.as_deref().map(|nodes| nodes.as_slice());
+            let known = match (nodes, &self.node) {
+                (Some(nodes), Some(node)) => nodes.contains(node),
+                (None, None) => true,
+                _ => false,
+            };
+            if !known {
+                self.node = nodes.and_then(|nodes| nodes.first().cloned());
+                self.subscription_status = None;
+                self.load_subscription(ctx);
+                ctx.link().send_reload();
+                return true;
+            }
+        }
+
+        if props.node_url != old_props.node_url && self.node.is_some() {
+            self.subscription_status = None;
+            self.load_subscription(ctx);
+            ctx.link().send_reload();
+            return true;
+        }
+
         if props.base_url != old_props.base_url || props.product != old_props.product {
             ctx.link().send_reload();
             true
         } else {
-            false
+            props.state_id != old_props.state_id
         }
     }
 
     fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
-        if ctx.props().status_only {
-            return None;
+        let props = ctx.props();
+
+        let columns_menu = self
+            .grid_state
+            .as_ref()
+            .map(|grid_state| grid_state.columns_menu(ctx.link().callback(Msg::UpdateGridState)));
+        let refresh_button = {
+            let loading = self.loading();
+            let link = ctx.link().clone();
+            Button::refresh(loading).onclick(move |_| link.send_reload())
+        };
+
+        if props.readonly && !props.status_only {
+            let toolbar = Toolbar::new()
+                .class("pwt-w-100")
+                .class("pwt-overflow-hidden")
+                .class("pwt-border-bottom")
+                .with_optional_child(self.node_selector(ctx))
+                .with_flex_spacer()
+                .with_optional_child(columns_menu)
+                .with_child(refresh_button);
+            return Some(toolbar.into());
+        }
+
+        if props.status_only {
+            return self.node_selector(ctx).map(|selector| {
+                Toolbar::new()
+                    .class("pwt-w-100")
+                    .class("pwt-border-bottom")
+                    .with_child(selector)
+                    .into()
+            });
         }
         let selected_record = self.selected_record();
+        let selected_repo = match &selected_record {
+            Some(TreeEntry::Repository {
+                path, index, repo, ..
+            }) => Some((path.clone(), *index, repo.enabled)),
+            _ => None,
+        };
+
+        let add_menu = Menu::new()
+            .with_item(
+                MenuItem::new(tr!("Standard Repository")).on_select(
+                    ctx.link()
+                        .change_view_callback(|_| Some(ViewState::ShowSubscription)),
+                ),
+            )
+            .with_item(
+                MenuItem::new(tr!("Custom Repository")).on_select(
+                    ctx.link()
+                        .change_view_callback(|_| Some(ViewState::AddCustomRepository)),
+                ),
+            );
 
         let toolbar = Toolbar::new()
             .class("pwt-w-100")
             .class("pwt-overflow-hidden")
             .class("pwt-border-bottom")
+            .with_optional_child(self.node_selector(ctx))
+            .with_child(MenuButton::new(tr!("Add")).show_arrow(true).menu(add_menu))
             .with_child(
-                Button::new(tr!("Add")).onclick(
-                    ctx.link()
-                        .change_view_callback(|_| Some(ViewState::ShowSubscription)),
-                ),
+                Button::new(tr!("Edit"))
+                    .disabled(selected_repo.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        let view = selected_repo
+                            .clone()
+                            .map(|(path, index, _)| ViewState::EditRepository(path, index));
+                        move |_| link.change_view(view.clone())
+                    }),
+            )
+            .with_child(
+                ConfirmButton::new(tr!("Remove"))
+                    .confirm_message(tr!(
+                        "Are you sure you want to remove the selected repository entry?"
+                    ))
+                    .dangerous(true)
+                    .disabled(selected_repo.is_none())
+                    .on_activate(ctx.link().callback(|_| Msg::Remove)),
             )
             .with_child({
-                let enabled = match selected_record {
-                    Some(TreeEntry::Repository { repo, .. }) => Some(repo.enabled),
-                    _ => None,
-                };
+                let enabled = selected_repo.as_ref().map(|(_, _, enabled)| *enabled);
                 Button::new(if enabled.unwrap_or(false) {
                     tr!("Disable")
                 } else {
@@ -609,11 +642,8 @@ impl LoadableComponent for ProxmoxAptRepositories {
                 .onclick(ctx.link().callback(|_| Msg::ToggleEnable))
             })
             .with_flex_spacer()
-            .with_child({
-                let loading = self.loading();
-                let link = ctx.link().clone();
-                Button::refresh(loading).onclick(move |_| link.send_reload())
-            });
+            .with_optional_child(columns_menu)
+            .with_child(refresh_button);
 
         Some(toolbar.into())
     }
@@ -629,11 +659,20 @@ impl LoadableComponent for ProxmoxAptRepositories {
             return status.into();
         }
 
-        let table = DataTable::new(self.columns.clone(), self.tree_store.clone())
+        let columns = match &self.grid_state {
+            Some(grid_state) => grid_state.columns(),
+            None => self.columns.clone(),
+        };
+
+        let mut table = DataTable::new(columns, self.tree_store.clone())
             .selection(self.selection.clone())
             .class("pwt-flex-fit pwt-border-top")
             .striped(false);
 
+        if let Some(grid_state) = &self.grid_state {
+            table = grid_state.track(table, ctx.link().callback(Msg::UpdateGridState));
+        }
+
         let mut panel = Column::new().class("pwt-flex-fit");
 
         panel.add_child(Row::new().padding(4).with_child(status));
@@ -648,6 +687,21 @@ impl LoadableComponent for ProxmoxAptRepositories {
     ) -> Option<Html> {
         match view_state {
             ViewState::AddRespository => Some(self.create_add_dialog(ctx)),
+            ViewState::AddCustomRepository => Some(self.create_edit_dialog(ctx, None)),
+            ViewState::EditRepository(path, index) => {
+                let key = Key::from(format!("repo:{path}:{index}"));
+                let record = self
+                    .tree_store
+                    .read()
+                    .lookup_node(&key)
+                    .map(|node| node.record().clone());
+                match record {
+                    Some(TreeEntry::Repository { repo, .. }) => {
+                        Some(self.create_edit_dialog(ctx, Some((path.clone(), *index, repo))))
+                    }
+                    _ => None,
+                }
+            }
             ViewState::ShowSubscription => {
                 let (status, url) = match &self.subscription_status {
                     Some(Ok(data)) => (
@@ -704,10 +758,209 @@ fn standard_repo_info(
 }
 
 impl ProxmoxAptRepositories {
+    fn node_url(props: &AptRepositories, node: &str) -> String {
+        props
+            .node_url
+            .replace("{node}", &percent_encode_component(node))
+    }
+
+    fn base_url(&self, props: &AptRepositories) -> AttrValue {
+        match &self.node {
+            Some(node) => format!("{}/apt", Self::node_url(props, node)).into(),
+            None => props.base_url.clone(),
+        }
+    }
+
+    fn subscription_url(&self, props: &AptRepositories) -> AttrValue {
+        match &self.node {
+            Some(node) => format!("{}/subscription", Self::node_url(props, node)).into(),
+            None => props.subscription_url.clone(),
+        }
+    }
+
+    // the subscription status of the (selected) node
+    fn load_subscription(&self, ctx: &LoadableComponentContext<Self>) {
+        let subscription_url = self.subscription_url(ctx.props());
+        let node = self.node.clone();
+        self.spawn({
+            let link = ctx.link().clone();
+            async move {
+                // TODO: also reload this in load, not only on create and node change?!
+                let data = crate::http_get(subscription_url.to_string(), None).await;
+                link.send_message(Msg::SubscriptionInfo(node, data));
+            }
+        });
+    }
+
+    fn node_selector(&self, ctx: &LoadableComponentContext<Self>) -> Option<Combobox> {
+        let nodes = ctx.props().nodes.as_ref()?;
+        Some(
+            Combobox::new()
+                .width(200)
+                .items(nodes.clone())
+                .value(self.node.clone().unwrap_or_default())
+                .on_change(ctx.link().callback(Msg::SelectNode)),
+        )
+    }
+
     fn active_subscription(&self) -> bool {
         subscription_is_active(self.subscription_status.as_ref())
     }
 
+    /// Digest of the loaded configuration, used to detect concurrent modifications.
+    fn digest(&self) -> Option<Value> {
+        let config = self.config.as_ref()?;
+        serde_json::to_value(&config.digest).ok()
+    }
+
+    fn create_edit_dialog(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        entry: Option<(String, usize, APTRepository)>,
+    ) -> Html {
+        let url = format!("{}/repositories", self.base_url(ctx.props()));
+        let digest = self.digest();
+
+        let is_create = entry.is_none();
+        let (path, index, repo) = match entry {
+            Some((path, index, repo)) => (path, Some(index), Some(repo)),
+            None => (
+                String::from("/etc/apt/sources.list.d/custom.sources"),
+                None,
+                None,
+            ),
+        };
+        let repo = repo.map(|repo| serde_json::to_value(repo).unwrap_or_default());
+        let record = repo.clone().unwrap_or_default();
+        let options: Vec<Value> = record["Options"].as_array().cloned().unwrap_or_default();
+
+        let list = |name: &str| match record[name].as_array() {
+            Some(list) => list
+                .iter()
+                .filter_map(|item| item.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => String::new(),
+        };
+        let signed_by = options
+            .iter()
+            .find(|option| option["Key"].as_str() == Some(SIGNED_BY))
+            .map(|option| {
+                option["Values"]
+                    .as_array()
+                    .map(|values| {
+                        values
+                            .iter()
+                            .filter_map(|v| v.as_str())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default();
+
+        let types = if is_create {
+            String::from("deb")
+        } else {
+            list("Types")
+        };
+        let uris = list("URIs");
+        let suites = list("Suites");
+        let components = list("Components");
+        let comment = record["Comment"].as_str().unwrap_or("").to_string();
+        let enabled = record["Enabled"].as_bool().unwrap_or(true);
+
+        let title = if is_create {
+            tr!("Add") + ": " + &tr!("Repository")
+        } else {
+            tr!("Edit") + ": " + &tr!("Repository")
+        };
+
+        EditWindow::new(title)
+            .on_done(ctx.link().change_view_callback(|_| None))
+            .renderer({
+                let path = path.clone();
+                move |_form_ctx: &FormContext| {
+                    InputPanel::new()
+                        .min_width(600)
+                        .padding(4)
+                        .with_field(
+                            tr!("File"),
+                            Field::new()
+                                .name("path")
+                                .default(path.clone())
+                                .disabled(!is_create)
+                                .required(true)
+                                .validate(validate_repository_path),
+                        )
+                        .with_field(
+                            tr!("Types"),
+                            Field::new()
+                                .name("types")
+                                .default(types.clone())
+                                .required(true)
+                                .validate(validate_types),
+                        )
+                        .with_field(
+                            tr!("URIs"),
+                            Field::new()
+                                .name("uris")
+                                .default(uris.clone())
+                                .required(true)
+                                .placeholder("http://download.example.com/debian"),
+                        )
+                        .with_field(
+                            tr!("Suites"),
+                            Field::new()
+                                .name("suites")
+                                .default(suites.clone())
+                                .required(true)
+                                .placeholder("trixie"),
+                        )
+                        .with_field(
+                            tr!("Components"),
+                            Field::new()
+                                .name("components")
+                                .default(components.clone())
+                                .placeholder("main"),
+                        )
+                        .with_field(
+                            tr!("Signed-By"),
+                            Field::new()
+                                .name("signed-by")
+                                .default(signed_by.clone())
+                                .placeholder("/usr/share/keyrings/example.gpg"),
+                        )
+                        .with_field(
+                            tr!("Comment"),
+                            Field::new().name("comment").default(comment.clone()),
+                        )
+                        .with_field(
+                            tr!("Enabled"),
+                            Checkbox::new().name("enabled").default(enabled),
+                        )
+                        .into()
+                }
+            })
+            .on_submit(move |form_ctx: FormContext| {
+                let data = form_ctx.get_submit_data();
+                let mut param = json!({
+                    "path": path.clone(),
+                    "repository": repository_from_form(&data, &options),
+                });
+                // a missing index adds a new entry to the file
+                if let Some(index) = index {
+                    param["index"] = index.into();
+                }
+                if let Some(digest) = digest.clone() {
+                    param["digest"] = digest;
+                }
+                let url = url.clone();
+                async move { crate::http_post(&url, Some(param)).await }
+            })
+            .into()
+    }
+
     fn create_show_subscription_dialog(
         &self,
         ctx: &LoadableComponentContext<Self>,
@@ -728,7 +981,7 @@ impl ProxmoxAptRepositories {
         let standard_repos = self.standard_repos.clone();
         let validate_standard_repo = self.validate_standard_repo.clone();
 
-        let url = format!("{}/repositories", props.base_url);
+        let url = format!("{}/repositories", self.base_url(props));
 
         EditWindow::new(tr!("Add") + ": " + &tr!("Repository"))
             .on_done(ctx.link().change_view_callback(|_| None))
@@ -794,30 +1047,37 @@ impl ProxmoxAptRepositories {
     ) -> Rc<Vec<DataTableHeader<TreeEntry>>> {
         Rc::new(vec![
             DataTableColumn::new(tr!("Enabled"))
+                .key("enabled")
                 .render_cell(render_enabled_or_group)
                 .tree_column(store.clone())
                 .into(),
             DataTableColumn::new(tr!("Types"))
+                .key("types")
                 .width("100px")
                 .render(render_types)
                 .into(),
             DataTableColumn::new(tr!("URIs"))
+                .key("uris")
                 .width("400px")
                 .render(render_uris)
                 .into(),
             DataTableColumn::new(tr!("Suites"))
+                .key("suites")
                 .width("150px")
                 .render(render_suites)
                 .into(),
             DataTableColumn::new(tr!("Components"))
+                .key("components")
                 .width("200px")
                 .render(render_components)
                 .into(),
             DataTableColumn::new(tr!("Origin"))
+                .key("origin")
                 .width("140px")
                 .render(render_origin)
                 .into(),
             DataTableColumn::new(tr!("Comment"))
+                .key("comment")
                 .flex(1)
                 .render(render_comment)
                 .into(),
@@ -826,19 +1086,11 @@ impl ProxmoxAptRepositories {
 
     fn status_columns(
         _ctx: &LoadableComponentContext<Self>,
-    ) -> Rc<Vec<DataTableHeader<StatusLine>>> {
+    ) -> Rc<Vec<DataTableHeader<RepositoryStatusLine>>> {
         Rc::new(vec![DataTableColumn::new("Status") // not visible
             .flex(1)
             .show_menu(false)
-            .render(|record: &StatusLine| {
-                let (icon, color_class) = match record.status {
-                    Status::Ok => ("check", ""),
-                    Status::Warning => ("exclamation", "pwt-color-warning"),
-                    Status::Error => ("times", "pwt-color-error"),
-                };
-                let icon = Fa::new(icon).fixed_width().padding_end(2);
-                html! {<span class={color_class}>{icon}{&record.message}</span>}
-            })
+            .render(|record: &RepositoryStatusLine| record.render())
             .into()])
     }
 }
@@ -857,13 +1109,7 @@ fn render_enabled_or_group(args: &mut DataTableCellRenderArgs<TreeEntry>) -> Htm
             args.add_class("pwt-bg-color-surface");
             html! {text}
         }
-        TreeEntry::Repository { repo, .. } => {
-            let icon_class = match repo.enabled {
-                true => "fa fa-check",
-                false => "fa fa-minus",
-            };
-            html! {<i class={icon_class}/>}
-        }
+        TreeEntry::Repository { repo, .. } => render_boolean_icon(Some(repo.enabled)),
         _ => html! {},
     }
 }
@@ -971,3 +1217,44 @@ fn render_types(record: &TreeEntry) -> Html {
         _ => html! {},
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repository_from_form() {
+        let data = json!({
+            "types": "deb deb-src",
+            "uris": " http://deb.debian.org/debian ",
+            "suites": "trixie",
+            "components": "main contrib",
+            "signed-by": "/usr/share/keyrings/debian.gpg",
+            "comment": "",
+            "enabled": false,
+        });
+        let options = [
+            json!({ "Key": "Signed-By", "Values": ["/old.gpg"] }),
+            json!({ "Key": "Architectures", "Values": ["amd64"] }),
+        ];
+        let repo = repository_from_form(&data, &options);
+        assert_eq!(repo["Types"], json!(["deb", "deb-src"]));
+        assert_eq!(repo["URIs"], json!(["http://deb.debian.org/debian"]));
+        assert_eq!(repo["Components"], json!(["main", "contrib"]));
+        assert_eq!(
+            repo["Options"],
+            json!([
+                { "Key": "Architectures", "Values": ["amd64"] },
+                { "Key": "Signed-By", "Values": ["/usr/share/keyrings/debian.gpg"] },
+            ])
+        );
+        assert_eq!(repo["Enabled"], json!(false));
+    }
+
+    #[test]
+    fn test_validate_types() {
+        assert!(validate_types(&"deb deb-src".to_string()).is_ok());
+        assert!(validate_types(&"".to_string()).is_err());
+        assert!(validate_types(&"rpm".to_string()).is_err());
+    }
+}
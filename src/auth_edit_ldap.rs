@@ -11,14 +11,15 @@ use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::widget::form::{Field, TristateBoolean};
-use pwt::widget::{Container, InputPanel, TabBarItem, TabPanel};
+use pwt::widget::{Button, Container, Fa, InputPanel, Row, TabBarItem, TabPanel};
+use pwt::AsyncPool;
 
 use crate::form::delete_empty_values;
 use crate::percent_encoding::percent_encode_component;
 
 use pwt_macros::builder;
 
-use crate::EditWindow;
+use crate::{EditWindow, ExistingProduct, Status};
 
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
@@ -42,6 +43,19 @@ pub struct AuthEditLDAP {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or_default]
     pub ad_realm: Option<bool>,
+
+    /// URL to check the connection with the entered values.
+    ///
+    /// The server is expected to connect, bind and search the user base. If set, a "Check
+    /// connection" button is shown.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub check_url: Option<AttrValue>,
+
+    /// The Product. PBS only syncs users, so the sync scope is not shown there.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub product: Option<ExistingProduct>,
 }
 
 impl Default for AuthEditLDAP {
@@ -56,58 +70,53 @@ impl AuthEditLDAP {
     }
 }
 
-async fn load_realm(url: impl Into<String>) -> Result<ApiResponseData<Value>, Error> {
-    let mut response: ApiResponseData<Value> = crate::http_get_full(url, None).await?;
-
-    response.data["anonymous_search"] = Value::Bool(!response.data["bind-dn"].is_string());
+const REMOVE_VANISHED_OPTIONS: [&str; 3] = ["acl", "entry", "properties"];
 
-    if let Value::String(sync_default_options) = response.data["sync-defaults-options"].take() {
-        let split = sync_default_options.split(",");
+/// Split the `sync-defaults-options` property string into the separate sync option fields
+/// (`scope`, `enable-new` and `remove-vanished-{acl,entry,properties}`).
+pub(crate) fn parse_sync_defaults_options(data: &mut Value) {
+    let Value::String(options) = data["sync-defaults-options"].take() else {
+        return;
+    };
 
-        for part in split {
-            let mut part = part.split("=");
-
-            match part.next() {
-                Some("enable-new") => {
-                    response.data["enable-new"] = Value::Bool(part.next() == Some("true"))
-                }
-                Some("remove-vanished") => {
-                    if let Some(part) = part.next() {
-                        for vanished_opt in part.split(";") {
-                            response.data[&format!("remove-vanished-{vanished_opt}")] =
-                                Value::Bool(true)
-                        }
-                    }
+    for part in options.split(',') {
+        let Some((name, value)) = part.split_once('=') else {
+            continue;
+        };
+        match name.trim() {
+            "enable-new" => {
+                data["enable-new"] = Value::Bool(matches!(value, "1" | "true" | "yes" | "on"))
+            }
+            "remove-vanished" => {
+                for vanished_opt in value.split(';').filter(|opt| !opt.is_empty()) {
+                    data[&format!("remove-vanished-{vanished_opt}")] = Value::Bool(true)
                 }
-                _ => {}
             }
+            "scope" => data["scope"] = Value::String(value.to_string()),
+            _ => {}
         }
     }
+}
 
-    if let Value::String(sync_attributes) = response.data["sync-attributes"].take() {
-        let split = sync_attributes.split(",");
+/// Join the sync option fields into a `sync-defaults-options` property string.
+///
+/// The fields are removed from `data`. Returns `None` if no option is set.
+pub(crate) fn format_sync_defaults_options(data: &mut Value) -> Option<String> {
+    let mut options = Vec::new();
 
-        for opt in split {
-            let mut opt = opt.split("=");
-            if let (Some(name), Some(val)) = (opt.next(), opt.next()) {
-                response.data[name] = Value::String(val.to_string());
-            }
+    if let Value::String(scope) = data["scope"].take() {
+        if !scope.is_empty() {
+            options.push(format!("scope={scope}"));
         }
     }
 
-    Ok(response)
-}
-
-fn format_sync_and_default_options(data: &mut Value) -> Value {
-    let mut sync_default_options: Option<String> = None;
-
     if let Value::Bool(val) = data["enable-new"].take() {
-        sync_default_options = Some(format!("enable-new={val}"))
+        options.push(format!("enable-new={val}"));
     }
 
     let mut remove_vanished: Vec<&str> = Vec::new();
 
-    for prop in ["acl", "entry", "properties"] {
+    for prop in REMOVE_VANISHED_OPTIONS {
         let prop_name = format!("remove-vanished-{prop}");
         if data[&prop_name].take() == Value::Bool(true) {
             remove_vanished.push(prop);
@@ -115,14 +124,100 @@ fn format_sync_and_default_options(data: &mut Value) -> Value {
     }
 
     if !remove_vanished.is_empty() {
-        let vanished = format!("remove-vanished={}", remove_vanished.join(";"));
+        options.push(format!("remove-vanished={}", remove_vanished.join(";")));
+    }
+
+    (!options.is_empty()).then(|| options.join(","))
+}
+
+/// Add the sync option fields (scope, enable new users and remove vanished options).
+///
+/// Used for the default sync options of a realm and when starting a sync manually.
+pub(crate) fn add_sync_option_fields(
+    mut panel: InputPanel,
+    product: Option<ExistingProduct>,
+) -> InputPanel {
+    // only products with groups support syncing them
+    if product != Some(ExistingProduct::PBS) {
+        panel.add_field(
+            tr!("Scope"),
+            Combobox::new()
+                .name("scope")
+                .placeholder(tr!("Default"))
+                .items(Rc::new(vec![
+                    "users".into(),
+                    "groups".into(),
+                    "both".into(),
+                ]))
+                .render_value(|scope: &AttrValue| {
+                    let text = match scope.as_str() {
+                        "users" => tr!("Users"),
+                        "groups" => tr!("Groups"),
+                        "both" => tr!("Users and Groups"),
+                        unknown => unknown.to_string(),
+                    };
+                    html! {text}
+                }),
+        );
+    }
+
+    panel
+        .with_right_field(
+            tr!("Enable new users"),
+            TristateBoolean::new()
+                .name("enable-new")
+                .null_text(tr!("Default") + " (" + &tr!("Yes") + ")"),
+        )
+        .with_large_custom_child(
+            Container::new()
+                .key("remove-vanished-options")
+                .class("pwt-font-title-medium")
+                .padding_top(2)
+                .with_child(tr!("Remove Vanished Options")),
+        )
+        .with_large_field(
+            tr!("ACLs"),
+            Checkbox::new()
+                .name("remove-vanished-acl")
+                .box_label(tr!("Remove ACLs of vanished users.")),
+        )
+        .with_large_field(
+            tr!("Entries"),
+            Checkbox::new()
+                .name("remove-vanished-entry")
+                .box_label(tr!("Remove vanished user")),
+        )
+        .with_large_field(
+            tr!("Properties"),
+            Checkbox::new()
+                .name("remove-vanished-properties")
+                .box_label(tr!("Remove vanished properties")),
+        )
+}
+
+async fn load_realm(url: impl Into<String>) -> Result<ApiResponseData<Value>, Error> {
+    let mut response: ApiResponseData<Value> = crate::http_get_full(url, None).await?;
+
+    response.data["anonymous_search"] = Value::Bool(!response.data["bind-dn"].is_string());
+
+    parse_sync_defaults_options(&mut response.data);
 
-        sync_default_options = sync_default_options
-            .map(|f| format!("{f},{vanished}"))
-            .or(Some(vanished));
+    if let Value::String(sync_attributes) = response.data["sync-attributes"].take() {
+        let split = sync_attributes.split(",");
+
+        for opt in split {
+            let mut opt = opt.split("=");
+            if let (Some(name), Some(val)) = (opt.next(), opt.next()) {
+                response.data[name] = Value::String(val.to_string());
+            }
+        }
     }
 
-    if let Some(defaults) = sync_default_options {
+    Ok(response)
+}
+
+fn format_sync_and_default_options(data: &mut Value) -> Value {
+    if let Some(defaults) = format_sync_defaults_options(data) {
         data["sync-defaults-options"] = Value::String(defaults);
     }
 
@@ -149,6 +244,35 @@ fn format_sync_and_default_options(data: &mut Value) -> Value {
     new
 }
 
+async fn check_connection(
+    form_ctx: FormContext,
+    check_url: AttrValue,
+    realm: Option<AttrValue>,
+) -> Result<(), Error> {
+    let mut data = form_ctx.get_submit_data();
+    let mut data = format_sync_and_default_options(&mut data);
+
+    // the realm field is not submitted when editing, but needed to use the stored password
+    if let Some(realm) = realm {
+        data["realm"] = realm.to_string().into();
+    }
+
+    let anonymous_search = form_ctx
+        .read()
+        .get_field_value("anonymous_search")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if anonymous_search {
+        if let Some(data) = data.as_object_mut() {
+            data.remove("bind-dn");
+            data.remove("password");
+        }
+    }
+
+    crate::http_post(check_url.as_str(), Some(data)).await
+}
+
 async fn create_item(form_ctx: FormContext, base_url: String) -> Result<(), Error> {
     let mut data = form_ctx.get_submit_data();
     let data = format_sync_and_default_options(&mut data);
@@ -203,15 +327,71 @@ async fn update_item(form_ctx: FormContext, base_url: String) -> Result<(), Erro
     crate::http_put(&url, Some(data)).await
 }
 
+pub enum Msg {
+    Check(FormContext),
+    CheckResult(Result<(), String>),
+}
+
+/// State of the connection check.
+#[derive(Clone, PartialEq)]
+enum CheckState {
+    None,
+    Running,
+    Done(Result<(), String>),
+}
+
 #[doc(hidden)]
-pub struct ProxmoxAuthEditLDAP {}
+pub struct ProxmoxAuthEditLDAP {
+    check_state: CheckState,
+    async_pool: AsyncPool,
+}
 
-fn render_panel(form_ctx: FormContext, props: AuthEditLDAP) -> Html {
+fn render_check_result(check_state: &CheckState) -> Html {
+    let (status, text) = match check_state {
+        CheckState::None => return html! {},
+        CheckState::Running => return html! {tr!("Checking connection...")},
+        CheckState::Done(Ok(())) => (
+            Status::Success,
+            tr!("Server reachable, bind and user search successful."),
+        ),
+        CheckState::Done(Err(err)) => (Status::Error, err.clone()),
+    };
+
+    Row::new()
+        .class(pwt::css::AlignItems::Center)
+        .gap(2)
+        .with_child(Fa::from(status))
+        .with_child(text)
+        .into()
+}
+
+fn render_panel(
+    form_ctx: FormContext,
+    props: AuthEditLDAP,
+    check_state: CheckState,
+    on_check: Callback<FormContext>,
+) -> Html {
     TabPanel::new()
         .with_item(TabBarItem::new().key("general").label(tr!("General")), {
-            let props = props.clone();
-            let form_ctx = form_ctx.clone();
-            render_general_form(form_ctx.clone(), props.clone())
+            let mut panel = render_general_form(form_ctx.clone(), props.clone());
+            if props.check_url.is_some() {
+                panel = panel.with_large_custom_child(
+                    Row::new()
+                        .key("check-connection")
+                        .class(pwt::css::AlignItems::Center)
+                        .gap(2)
+                        .with_child(
+                            Button::new(tr!("Check connection"))
+                                .disabled(check_state == CheckState::Running)
+                                .onclick({
+                                    let form_ctx = form_ctx.clone();
+                                    move |_| on_check.emit(form_ctx.clone())
+                                }),
+                        )
+                        .with_child(render_check_result(&check_state)),
+                );
+            }
+            panel
         })
         .with_item(
             TabBarItem::new().key("sync").label(tr!("Sync Options")),
@@ -221,10 +401,10 @@ fn render_panel(form_ctx: FormContext, props: AuthEditLDAP) -> Html {
         .into()
 }
 
-fn render_sync_form(_form_ctx: FormContext, _props: AuthEditLDAP) -> Html {
+fn render_sync_form(_form_ctx: FormContext, props: AuthEditLDAP) -> Html {
     //let is_edit = props.realm.is_some();
 
-    InputPanel::new()
+    let panel = InputPanel::new()
         .class(Flex::Fill)
         .class(Overflow::Auto)
         .padding(4)
@@ -243,42 +423,12 @@ fn render_sync_form(_form_ctx: FormContext, _props: AuthEditLDAP) -> Html {
                 .class("pwt-font-title-medium")
                 .padding_top(2)
                 .with_child(tr!("Default Sync Options")),
-        )
-        .with_field(
-            tr!("Enable new users"),
-            TristateBoolean::new()
-                .name("enable-new")
-                .submit_empty(true)
-                .null_text(tr!("Default") + " (" + &tr!("Yes") + ")"),
-        )
-        .with_large_custom_child(
-            Container::new()
-                .class("pwt-font-title-medium")
-                .padding_top(2)
-                .with_child(tr!("Remove Vanished Options")),
-        )
-        .with_large_field(
-            tr!("ACLs"),
-            Checkbox::new()
-                .name("remove-vanished-acl")
-                .box_label(tr!("Remove ACLs of vanished users.")),
-        )
-        .with_large_field(
-            tr!("Entries"),
-            Checkbox::new()
-                .name("remove-vanished-entry")
-                .box_label(tr!("Remove vanished user")),
-        )
-        .with_large_field(
-            tr!("Properties"),
-            Checkbox::new()
-                .name("remove-vanished-properties")
-                .box_label(tr!("Remove vanished properties")),
-        )
-        .into()
+        );
+
+    add_sync_option_fields(panel, props.product).into()
 }
 
-fn render_general_form(form_ctx: FormContext, props: AuthEditLDAP) -> Html {
+fn render_general_form(form_ctx: FormContext, props: AuthEditLDAP) -> InputPanel {
     let is_edit = props.realm.is_some();
 
     let mode_items = Rc::new(vec!["ldap".into(), "ldap+starttls".into(), "ldaps".into()]);
@@ -388,15 +538,39 @@ fn render_general_form(form_ctx: FormContext, props: AuthEditLDAP) -> Html {
                 .show_peek_icon(true),
         )
         .with_large_field(tr!("Comment"), Field::new().name("comment"))
-        .into()
 }
 
 impl Component for ProxmoxAuthEditLDAP {
-    type Message = ();
+    type Message = Msg;
     type Properties = AuthEditLDAP;
 
     fn create(_ctx: &Context<Self>) -> Self {
-        Self {}
+        Self {
+            check_state: CheckState::None,
+            async_pool: AsyncPool::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Check(form_ctx) => {
+                let Some(check_url) = props.check_url.clone() else {
+                    return false;
+                };
+                let realm = props.realm.clone();
+                let link = ctx.link().clone();
+                self.async_pool.spawn(async move {
+                    let result = check_connection(form_ctx, check_url, realm).await;
+                    link.send_message(Msg::CheckResult(result.map_err(|err| err.to_string())));
+                });
+                self.check_state = CheckState::Running;
+            }
+            Msg::CheckResult(result) => {
+                self.check_state = CheckState::Done(result);
+            }
+        }
+        true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -434,7 +608,16 @@ impl Component for ProxmoxAuthEditLDAP {
             )
             .renderer({
                 let props = props.clone();
-                move |form_ctx: &FormContext| render_panel(form_ctx.clone(), props.clone())
+                let check_state = self.check_state.clone();
+                let on_check = ctx.link().callback(Msg::Check);
+                move |form_ctx: &FormContext| {
+                    render_panel(
+                        form_ctx.clone(),
+                        props.clone(),
+                        check_state.clone(),
+                        on_check.clone(),
+                    )
+                }
             })
             .on_done(props.on_close.clone())
             .on_submit(on_submit)
@@ -448,3 +631,87 @@ impl From<AuthEditLDAP> for VNode {
         VNode::from(comp)
     }
 }
+
+/// Add/Edit dialog for Active Directory realms.
+///
+/// Same as [`AuthEditLDAP`] with `ad_realm` set, so base DN and user attribute are not
+/// required.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct AuthEditAD {
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+
+    #[prop_or("/access/domains".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url for
+    pub base_url: AttrValue,
+
+    /// Edit existing realm
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub realm: Option<AttrValue>,
+
+    /// URL to check the connection with the entered values, see [`AuthEditLDAP::check_url`].
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub check_url: Option<AttrValue>,
+
+    /// The Product, see [`AuthEditLDAP::product`].
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub product: Option<ExistingProduct>,
+}
+
+impl Default for AuthEditAD {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthEditAD {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+impl From<AuthEditAD> for VNode {
+    fn from(val: AuthEditAD) -> Self {
+        AuthEditLDAP::new()
+            .base_url(val.base_url)
+            .realm(val.realm)
+            .on_close(val.on_close)
+            .check_url(val.check_url)
+            .product(val.product)
+            .ad_realm(true)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_sync_defaults_options() {
+        let mut data = json!({
+            "sync-defaults-options": "enable-new=1,remove-vanished=acl;entry,scope=users",
+        });
+        parse_sync_defaults_options(&mut data);
+        assert_eq!(data["enable-new"], json!(true));
+        assert_eq!(data["remove-vanished-acl"], json!(true));
+        assert_eq!(data["remove-vanished-entry"], json!(true));
+        assert_eq!(data["remove-vanished-properties"], Value::Null);
+        assert_eq!(data["scope"], json!("users"));
+
+        assert_eq!(
+            format_sync_defaults_options(&mut data).as_deref(),
+            Some("scope=users,enable-new=true,remove-vanished=acl;entry")
+        );
+        assert_eq!(format_sync_defaults_options(&mut json!({})), None);
+    }
+}
@@ -5,7 +5,7 @@ use std::rc::Rc;
 use anyhow::Error;
 
 use proxmox_client::ApiResponseData;
-use pwt::widget::form::{Checkbox, DisplayField, Field, FormContext, TristateBoolean};
+use pwt::widget::form::{Checkbox, DisplayField, Field, FormContext};
 use serde_json::Value;
 use yew::html::IntoPropValue;
 use yew::virtual_dom::{VComp, VNode};
@@ -15,14 +15,15 @@ use pwt::state::{Selection, Store};
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
 use pwt::widget::menu::{Menu, MenuButton, MenuItem};
 
-use pwt::widget::{Button, Container, Fa, InputPanel, Toolbar};
+use pwt::widget::{Button, Fa, InputPanel, Toolbar};
 
 use pwt_macros::builder;
 
+use crate::auth_edit_ldap::{add_sync_option_fields, parse_sync_defaults_options};
 use crate::form::delete_empty_values;
 use crate::{
-    AuthEditLDAP, AuthEditOpenID, ConfirmButton, EditWindow, LoadableComponent,
-    LoadableComponentContext, LoadableComponentMaster, LoadableComponentScope,
+    AuthEditAD, AuthEditLDAP, AuthEditOpenID, ConfirmButton, EditWindow, ExistingProduct,
+    LoadableComponent, LoadableComponentContext, LoadableComponentMaster, LoadableComponentScope,
     LoadableComponentScopeExt, LoadableComponentState,
 };
 
@@ -57,6 +58,21 @@ pub struct AuthView {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or_default]
     ad_base_url: Option<AttrValue>,
+
+    /// URL to check the connection of LDAP realms before saving.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    ldap_check_url: Option<AttrValue>,
+
+    /// URL to check the connection of Active Directory realms before saving.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    ad_check_url: Option<AttrValue>,
+
+    /// The Product, used to show the sync options it supports.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    product: Option<ExistingProduct>,
 }
 
 impl Default for AuthView {
@@ -87,6 +103,8 @@ pub enum Msg {
     Edit,
     Remove,
     Sync,
+    /// Sync the selected realm using its default sync options.
+    SyncNow,
 }
 
 #[doc(hidden)]
@@ -144,30 +162,7 @@ async fn sync_realm(
 
 async fn load_realm(url: impl Into<String>) -> Result<ApiResponseData<Value>, Error> {
     let mut response: ApiResponseData<Value> = crate::http_get_full(url, None).await?;
-
-    if let Value::String(sync_default_options) = response.data["sync-defaults-options"].take() {
-        let split = sync_default_options.split(",");
-
-        for part in split {
-            let mut part = part.split("=");
-
-            match part.next() {
-                Some("enable-new") => {
-                    response.data["enable-new"] = Value::Bool(part.next() == Some("true"))
-                }
-                Some("remove-vanished") => {
-                    if let Some(part) = part.next() {
-                        for vanished_opt in part.split(";") {
-                            response.data[&format!("remove-vanished-{vanished_opt}")] =
-                                Value::Bool(true)
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
+    parse_sync_defaults_options(&mut response.data);
     Ok(response)
 }
 
@@ -308,6 +303,25 @@ impl LoadableComponent for ProxmoxAuthView {
                 ctx.link().change_view(Some(ViewState::Sync(info)));
                 true
             }
+            Msg::SyncNow => {
+                let Some(info) = self.get_selected_record() else {
+                    return true;
+                };
+
+                let url = format!(
+                    "{}/{}/sync",
+                    props.base_url,
+                    percent_encode_component(&info.realm)
+                );
+                let link = ctx.link().clone();
+                link.clone().spawn(async move {
+                    match crate::http_post::<String>(url, None).await {
+                        Ok(upid) => link.show_task_log(upid, None),
+                        Err(err) => link.show_error(tr!("Sync Failed"), err, true),
+                    }
+                });
+                false
+            }
         }
     }
 
@@ -405,6 +419,18 @@ impl LoadableComponent for ProxmoxAuthView {
                 Button::new(tr!("Sync"))
                     .disabled(sync_disabled)
                     .onclick(ctx.link().callback(|_| Msg::Sync)),
+            )
+            .with_child(
+                ConfirmButton::new(tr!("Sync now"))
+                    .disabled(sync_disabled)
+                    .confirm_message(match &selected_record {
+                        Some(info) => tr!(
+                            "Sync realm '{}' using its default sync options?",
+                            info.realm
+                        ),
+                        None => String::new(),
+                    })
+                    .on_activate(ctx.link().callback(|_| Msg::SyncNow)),
             );
 
         Some(toolbar.into())
@@ -431,30 +457,36 @@ impl LoadableComponent for ProxmoxAuthView {
 
         match view_state {
             ViewState::AddAd => Some(
-                AuthEditLDAP::new()
+                AuthEditAD::new()
                     .base_url(props.ad_base_url.clone().unwrap())
+                    .product(props.product)
+                    .check_url(props.ad_check_url.clone())
                     .on_close(ctx.link().change_view_callback(|_| None))
-                    .ad_realm(true)
                     .into(),
             ),
             ViewState::EditAd(realm) => Some(
-                AuthEditLDAP::new()
+                AuthEditAD::new()
                     .base_url(props.ad_base_url.clone().unwrap())
+                    .product(props.product)
+                    .check_url(props.ad_check_url.clone())
                     .realm(realm.clone())
                     .on_close(ctx.link().change_view_callback(|_| None))
-                    .ad_realm(true)
                     .into(),
             ),
 
             ViewState::AddLDAP => Some(
                 AuthEditLDAP::new()
                     .base_url(props.ldap_base_url.clone().unwrap())
+                    .product(props.product)
+                    .check_url(props.ldap_check_url.clone())
                     .on_close(ctx.link().change_view_callback(|_| None))
                     .into(),
             ),
             ViewState::EditLDAP(realm) => Some(
                 AuthEditLDAP::new()
                     .base_url(props.ldap_base_url.clone().unwrap())
+                    .product(props.product)
+                    .check_url(props.ldap_check_url.clone())
                     .realm(realm.clone())
                     .on_close(ctx.link().change_view_callback(|_| None))
                     .into(),
@@ -499,42 +531,15 @@ impl LoadableComponent for ProxmoxAuthView {
 
                 Some(
                     EditWindow::new(tr!("Realm Sync"))
-                        .renderer(|_form_ctx| {
-                            InputPanel::new()
-                                .padding(4)
-                                .with_field(tr!("Preview Only"), Checkbox::new().name("dry-run"))
-                                .with_field(
-                                    tr!("Enable new users"),
-                                    TristateBoolean::new()
-                                        .name("enable-new")
-                                        .null_text(tr!("Default") + " (" + &tr!("Yes") + ")"),
-                                )
-                                .with_large_custom_child(
-                                    Container::new()
-                                        .key("remove-vanished-options")
-                                        .class("pwt-font-title-medium")
-                                        .padding_top(2)
-                                        .with_child(tr!("Remove Vanished Options")),
-                                )
-                                .with_large_field(
-                                    tr!("ACLs"),
-                                    Checkbox::new()
-                                        .name("remove-vanished-acl")
-                                        .box_label(tr!("Remove ACLs of vanished users.")),
-                                )
-                                .with_large_field(
-                                    tr!("Entries"),
-                                    Checkbox::new()
-                                        .name("remove-vanished-entry")
-                                        .box_label(tr!("Remove vanished user")),
-                                )
-                                .with_large_field(
-                                    tr!("Properties"),
-                                    Checkbox::new()
-                                        .name("remove-vanished-properties")
-                                        .box_label(tr!("Remove vanished properties")),
-                                )
-                                .into()
+                        .renderer({
+                            let product = props.product;
+                            move |_form_ctx: &FormContext| {
+                                let panel = InputPanel::new().padding(4).with_large_field(
+                                    tr!("Preview Only"),
+                                    Checkbox::new().name("dry-run"),
+                                );
+                                add_sync_option_fields(panel, product).into()
+                            }
                         })
                         .loader({
                             let url =
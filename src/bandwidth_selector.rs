@@ -1,15 +1,17 @@
 use std::str::FromStr;
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use serde_json::{json, Value};
 
+use proxmox_schema::Schema;
+
 use pwt::widget::menu::{Menu, MenuButton, MenuItem};
 use yew::html::IntoPropValue;
 
 use pwt::prelude::*;
 use pwt::widget::form::{
-    ManagedField, ManagedFieldContext, ManagedFieldMaster, ManagedFieldScopeExt, ManagedFieldState,
-    Number,
+    Checkbox, ManagedField, ManagedFieldContext, ManagedFieldMaster, ManagedFieldScopeExt,
+    ManagedFieldState, Number,
 };
 use pwt::widget::Container;
 
@@ -34,6 +36,31 @@ pub struct BandwidthSelector {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or(SizeUnit::Mebi)]
     pub default_unit: SizeUnit,
+
+    /// Unit of plain number values and of the `min`/`max` bounds.
+    ///
+    /// For example, PVE bandwidth limits are specified in KiB/s.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(SizeUnit::Byte)]
+    pub base_unit: SizeUnit,
+
+    /// Minimum value (in `base_unit`).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub min: Option<f64>,
+
+    /// Maximum value (in `base_unit`).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub max: Option<f64>,
+
+    /// Show an "Unlimited" checkbox.
+    ///
+    /// If checked, the field value is empty, so that submit hooks using
+    /// [delete_empty_values](crate::form::delete_empty_values) delete the key.
+    #[builder]
+    #[prop_or_default]
+    pub show_unlimited: bool,
 }
 
 impl Default for BandwidthSelector {
@@ -47,21 +74,86 @@ impl BandwidthSelector {
     pub fn new() -> Self {
         yew::props!(Self {})
     }
+
+    /// Builder style method to set `min` and `max` from an integer or number schema.
+    pub fn schema(mut self, schema: &'static Schema) -> Self {
+        self.set_schema(schema);
+        self
+    }
+
+    /// Method to set `min` and `max` from an integer or number schema.
+    pub fn set_schema(&mut self, schema: &'static Schema) {
+        match schema {
+            Schema::Integer(s) => {
+                self.min = s.minimum.map(|v| v as f64);
+                self.max = s.maximum.map(|v| v as f64);
+            }
+            Schema::Number(s) => {
+                self.min = s.minimum;
+                self.max = s.maximum;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Units available in the unit menu.
+const UNITS: [SizeUnit; 3] = [SizeUnit::Kibi, SizeUnit::Mebi, SizeUnit::Gibi];
+
+/// Select a sensible display unit for `bytes`.
+///
+/// Uses the largest of KiB, MiB and GiB where the value is at least 1.
+fn auto_scale(bytes: f64) -> HumanByte {
+    let unit = [SizeUnit::Gibi, SizeUnit::Mebi]
+        .into_iter()
+        .find(|unit| bytes >= unit.factor())
+        .unwrap_or(SizeUnit::Kibi);
+    HumanByte {
+        size: bytes / unit.factor(),
+        unit,
+    }
+}
+
+fn unit_label(unit: &str) -> String {
+    format!("{unit}/s")
 }
 
 pub enum Msg {
     SelectUnit(SizeUnit),
     ChangeSize((String, Option<f64>)),
+    ToggleUnlimited(bool),
 }
 
 pub struct ProxmoxBandwidthField {
     state: ManagedFieldState,
     current_size: String,
     current_unit: String,
+    unlimited: bool,
 }
 #[derive(PartialEq)]
 pub struct ValidateClosure {
     required: bool,
+    show_unlimited: bool,
+    base_unit: SizeUnit,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl ValidateClosure {
+    fn check_bounds(&self, bytes: f64) -> Result<(), Error> {
+        let factor = self.base_unit.factor();
+        if let Some(min) = self.min {
+            if bytes < min * factor {
+                bail!(tr!("Minimum value is {0}/s", auto_scale(min * factor)));
+            }
+        }
+        if let Some(max) = self.max {
+            if bytes > max * factor {
+                bail!(tr!("Maximum value is {0}/s", auto_scale(max * factor)));
+            }
+        }
+        Ok(())
+    }
 }
 
 pwt::impl_deref_mut_property!(ProxmoxBandwidthField, state, ManagedFieldState);
@@ -74,23 +166,37 @@ impl ManagedField for ProxmoxBandwidthField {
     fn validation_args(props: &Self::Properties) -> Self::ValidateClosure {
         ValidateClosure {
             required: props.input_props.required,
+            show_unlimited: props.show_unlimited,
+            base_unit: props.base_unit,
+            min: props.min,
+            max: props.max,
         }
     }
 
     fn validator(props: &Self::ValidateClosure, value: &Value) -> Result<Value, Error> {
-        let is_empty = match value {
-            Value::Null => true,
+        // with the unlimited checkbox, an empty value means "unlimited", while an empty input
+        // (object value) means "Unlimited" is unchecked
+        let required = match value {
+            Value::Null => props.required && !props.show_unlimited,
             Value::Number(_) => false,
-            Value::String(v) => v.is_empty(),
+            Value::String(v) if v.is_empty() => props.required && !props.show_unlimited,
+            Value::String(_) => false,
             Value::Object(map) => match &map["size"] {
-                Value::String(v) => v.is_empty(),
+                Value::String(v) if v.is_empty() => props.required || props.show_unlimited,
+                Value::String(_) => false,
                 _ => return Err(Error::msg(tr!("Got wrong data type!"))),
             },
             _ => return Err(Error::msg(tr!("Got wrong data type!"))),
         };
+        let is_empty = match value {
+            Value::Null => true,
+            Value::String(v) => v.is_empty(),
+            Value::Object(map) => map["size"].as_str() == Some(""),
+            _ => false,
+        };
 
         if is_empty {
-            if props.required {
+            if required {
                 return Err(Error::msg(tr!("Field may not be empty.")));
             } else {
                 return Ok(Value::String(String::new()));
@@ -99,24 +205,25 @@ impl ManagedField for ProxmoxBandwidthField {
 
         match value {
             Value::Number(n) => {
-                if n.as_f64().is_none() {
+                let Some(number) = n.as_f64() else {
                     return Err(Error::msg(tr!("unable to parse number")));
-                }
+                };
+                props.check_bounds(number * props.base_unit.factor())?;
                 Ok(Value::Number(n.clone()))
             }
             Value::String(v) => {
-                if let Err(err) = HumanByte::from_str(v) {
-                    return Err(Error::msg(tr!("unable to parse value: {}", err)));
-                }
+                let hb = HumanByte::from_str(v)
+                    .map_err(|err| Error::msg(tr!("unable to parse value: {}", err)))?;
+                props.check_bounds(hb.as_f64())?;
                 Ok(Value::String(v.to_string()))
             }
             Value::Object(map) => match (&map["size"], &map["unit"]) {
                 (Value::String(size), Value::String(unit)) => {
                     let size = pwt::dom::parse_float(size).map_err(Error::msg)?;
                     let hb_str = format!("{} {}", size, unit);
-                    if let Err(err) = HumanByte::from_str(&hb_str) {
-                        return Err(Error::msg(tr!("unable to parse value: {}", err)));
-                    }
+                    let hb = HumanByte::from_str(&hb_str)
+                        .map_err(|err| Error::msg(tr!("unable to parse value: {}", err)))?;
+                    props.check_bounds(hb.as_f64())?;
                     Ok(Value::String(hb_str))
                 }
                 _ => Err(Error::msg(tr!("Got wrong data type!"))),
@@ -139,6 +246,7 @@ impl ManagedField for ProxmoxBandwidthField {
             state: ManagedFieldState::new(value, default),
             current_size: "".into(),
             current_unit: props.default_unit.to_string(),
+            unlimited: false,
         };
         me.value_changed(ctx);
         me
@@ -147,10 +255,17 @@ impl ManagedField for ProxmoxBandwidthField {
     fn value_changed(&mut self, ctx: &ManagedFieldContext<Self>) {
         let props = ctx.props();
 
+        self.unlimited = props.show_unlimited
+            && match &self.state.value {
+                Value::Null => true,
+                Value::String(v) => v.is_empty(),
+                _ => false,
+            };
+
         match &self.state.value {
             Value::Number(n) => {
                 if let Some(n) = n.as_f64() {
-                    let hb = HumanByte::new_binary(n);
+                    let hb = auto_scale(n * props.base_unit.factor());
                     self.current_size = pwt::dom::format_float(hb.size);
                     self.current_unit = hb.unit.to_string();
                 } else {
@@ -159,7 +274,7 @@ impl ManagedField for ProxmoxBandwidthField {
                 }
             }
             Value::String(v) => {
-                if let Ok(hb) = HumanByte::from_str(v) {
+                if let Ok(hb) = HumanByte::from_str(v).map(|hb| auto_scale(hb.as_f64())) {
                     self.current_size = pwt::dom::format_float(hb.size);
                     self.current_unit = hb.unit.to_string();
                 } else {
@@ -186,6 +301,13 @@ impl ManagedField for ProxmoxBandwidthField {
         let (size, unit) = match msg {
             Msg::SelectUnit(unit) => (self.current_size.to_string(), unit.to_string()),
             Msg::ChangeSize((size_text, _size)) => (size_text, self.current_unit.to_string()),
+            Msg::ToggleUnlimited(true) => {
+                ctx.link().update_value(Value::String(String::new()));
+                return false;
+            }
+            Msg::ToggleUnlimited(false) => {
+                (self.current_size.to_string(), self.current_unit.to_string())
+            }
         };
 
         // Note: we cannot store as valid HumanByte, so we store as Object.
@@ -204,39 +326,63 @@ impl ManagedField for ProxmoxBandwidthField {
         let mut input_props = props.input_props.clone();
         input_props.name = None;
 
+        let disabled = input_props.disabled || self.unlimited;
+
         let input = Number::<f64>::new()
             .with_input_props(&input_props)
+            .disabled(disabled)
             .min_width(40)
             .min(0.0)
-            .value(self.current_size.clone())
+            .value(if self.unlimited {
+                String::new()
+            } else {
+                self.current_size.clone()
+            })
             .on_input(ctx.link().callback(Msg::ChangeSize));
 
         let mut menu = Menu::new();
 
-        for unit in [
-            SizeUnit::Byte,
-            SizeUnit::KByte,
-            SizeUnit::Kibi,
-            SizeUnit::MByte,
-            SizeUnit::Mebi,
-            SizeUnit::GByte,
-            SizeUnit::Gibi,
-        ] {
+        for unit in UNITS {
             menu.add_item(
-                MenuItem::new(unit.to_string())
+                MenuItem::new(unit_label(&unit.to_string()))
                     .on_select(ctx.link().callback(move |_| Msg::SelectUnit(unit))),
             )
         }
 
-        let unit_selector = MenuButton::new(self.current_unit.to_string())
+        let unit_selector = MenuButton::new(unit_label(&self.current_unit))
+            .disabled(disabled)
             .show_arrow(true)
             .menu(menu);
 
         Container::new()
             .with_std_props(&props.std_props)
-            .class("pwt-d-flex pwt-gap-2")
+            .class("pwt-d-flex pwt-gap-2 pwt-align-items-center")
             .with_child(input)
             .with_child(unit_selector)
+            .with_optional_child(props.show_unlimited.then(|| {
+                Checkbox::new()
+                    .disabled(input_props.disabled)
+                    .checked(self.unlimited)
+                    .box_label(tr!("Unlimited"))
+                    .on_change(ctx.link().callback(Msg::ToggleUnlimited))
+            }))
             .into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_auto_scale() {
+        let check = |bytes: f64, size: f64, unit: SizeUnit| {
+            let hb = auto_scale(bytes);
+            assert_eq!((hb.size, hb.unit), (size, unit));
+        };
+        check(512.0, 0.5, SizeUnit::Kibi);
+        check(10.0 * 1024.0, 10.0, SizeUnit::Kibi);
+        check(1024.0 * 1024.0, 1.0, SizeUnit::Mebi);
+        check(1536.0 * 1024.0 * 1024.0, 1.5, SizeUnit::Gibi);
+    }
+}
@@ -1,54 +1,93 @@
 use std::rc::Rc;
 
+use anyhow::Error;
+
+use pwt::prelude::*;
+use yew::context::ContextHandle;
 use yew::html::IntoPropValue;
-use yew::prelude::*;
 use yew::virtual_dom::Key;
 
 use pwt::props::RenderFn;
 use pwt::state::Store;
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
-use pwt::widget::GridPicker;
+use pwt::widget::{Column, Container, GridPicker};
 
-use pwt::widget::form::{Selector, SelectorRenderArgs, ValidateFn};
+use pwt::widget::form::{FormContext, Selector, SelectorRenderArgs, ValidateFn};
 
-type Record = (&'static str, &'static str);
+/// (frequency, value, comment)
+type Record = (&'static str, &'static str, &'static str);
 
 static EXAMPLES: &[Record] = &[
-    ("*:0/30", "Every 30 minutes"),
-    ("hourly", "Every hour"),
-    ("0/2:00", "Every two hours"),
-    ("2,22:30", "Every day 02:30, 22:30"),
-    ("21:00", "Every day 21:00"),
-    ("daily", "Every day 00:00"),
-    ("mon..fri 00:00", "Monday to Friday 00:00"),
-    ("mon..fri *:00", "Monday to Friday, hourly"),
-    ("sat 18:15", "Every Saturday 18:15"),
-    ("monthly", "Every first day of the Month 00:00"),
+    ("Hourly", "*:0/15", "Every 15 minutes"),
+    ("Hourly", "*:0/30", "Every 30 minutes"),
+    ("Hourly", "hourly", "Every hour"),
+    ("Hourly", "0/2:00", "Every two hours"),
+    ("Hourly", "mon..fri *:00", "Monday to Friday, hourly"),
+    ("Daily", "2,22:30", "Every day 02:30, 22:30"),
+    ("Daily", "21:00", "Every day 21:00"),
+    ("Daily", "daily", "Every day 00:00"),
+    ("Daily", "mon..fri 00:00", "Monday to Friday 00:00"),
+    ("Weekly", "sat 18:15", "Every Saturday 18:15"),
+    ("Weekly", "weekly", "Every Monday 00:00"),
+    ("Monthly", "monthly", "Every first day of the Month 00:00"),
     (
+        "Monthly",
         "sat *-1..7 02:00",
         "Every first Saturday of the month 02:00",
     ),
-    ("yearly", "First day of the year 00:00"),
+    ("Yearly", "yearly", "First day of the year 00:00"),
 ];
 
+/// Number of upcoming runs shown in the preview.
+const PREVIEW_COUNT: usize = 3;
+
 thread_local! {
     static COLUMNS: Rc<Vec<DataTableHeader<Record>>> = Rc::new(vec![
-        DataTableColumn::new("Value")
+        DataTableColumn::new("Frequency")
             .width("100px")
             .show_menu(false)
             .render(|item: &Record| html!{item.0})
             .into(),
+        DataTableColumn::new("Value")
+            .width("150px")
+            .show_menu(false)
+            .render(|item: &Record| html!{item.1})
+            .into(),
         DataTableColumn::new("Comment")
             .width("300px")
             .show_menu(false)
-            .render(|item: &Record| html!{item.1})
+            .render(|item: &Record| html!{item.2})
             .into(),
     ]);
 }
 
+/// Compute the next `count` run times of a calendar event, starting after `start`.
+pub fn next_calendar_events(
+    event: &str,
+    start: i64,
+    count: usize,
+    utc: bool,
+) -> Result<Vec<i64>, Error> {
+    let event = proxmox_time::parse_calendar_event(event)?;
+    let mut list = Vec::new();
+    let mut last = start;
+    while list.len() < count {
+        match event.compute_next_event(last, utc)? {
+            Some(next) => {
+                list.push(next);
+                last = next;
+            }
+            None => break,
+        }
+    }
+    Ok(list)
+}
+
 use pwt::props::{FieldBuilder, WidgetBuilder};
 use pwt_macros::{builder, widget};
 
+use crate::utils::render_epoch;
+
 #[widget(comp=ProxmoxCalendarEventSelector, @input)]
 #[derive(Clone, Properties, PartialEq)]
 #[builder]
@@ -57,6 +96,11 @@ pub struct CalendarEventSelector {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or_default]
     pub default: Option<AttrValue>,
+
+    /// Show a preview of the next runs below the field.
+    #[builder]
+    #[prop_or_default]
+    pub show_preview: bool,
 }
 
 impl Default for CalendarEventSelector {
@@ -72,18 +116,27 @@ impl CalendarEventSelector {
     }
 }
 
+pub enum Msg {
+    Change(String),
+    FormCtxUpdate(FormContext),
+}
+
 pub struct ProxmoxCalendarEventSelector {
+    // current value, used if there is no form context
+    value: String,
+    form_ctx: Option<FormContext>,
+    _form_ctx_handle: Option<ContextHandle<FormContext>>,
     store: Store<Record>,
     validate: ValidateFn<(String, Store<Record>)>,
     picker: RenderFn<SelectorRenderArgs<Store<Record>>>,
 }
 
 impl Component for ProxmoxCalendarEventSelector {
-    type Message = ();
+    type Message = Msg;
     type Properties = CalendarEventSelector;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        let store = Store::with_extract_key(|item: &Record| Key::from(item.0));
+    fn create(ctx: &Context<Self>) -> Self {
+        let store = Store::with_extract_key(|item: &Record| Key::from(item.1));
         store.set_data(Vec::from(EXAMPLES));
 
         let validate = ValidateFn::new(|(value, _store): &(String, Store<Record>)| {
@@ -101,22 +154,113 @@ impl Component for ProxmoxCalendarEventSelector {
                 .into()
         });
 
+        // the preview needs to follow values set by the form, for example when loaded
+        let (form_ctx, form_ctx_handle) = if ctx.props().show_preview {
+            ctx.link()
+                .context::<FormContext>(ctx.link().callback(Msg::FormCtxUpdate))
+                .unzip()
+        } else {
+            (None, None)
+        };
+
         Self {
+            value: ctx.props().default.as_deref().unwrap_or("").to_string(),
+            form_ctx,
+            _form_ctx_handle: form_ctx_handle,
             store,
             validate,
             picker,
         }
     }
 
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Change(value) => {
+                self.value = value;
+                true
+            }
+            Msg::FormCtxUpdate(form_ctx) => {
+                self.form_ctx = Some(form_ctx);
+                true
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
 
-        Selector::new(self.store.clone(), self.picker.clone())
-            .with_std_props(&props.std_props)
-            .with_input_props(&props.input_props)
-            .default(&props.default)
+        let selector = Selector::new(self.store.clone(), self.picker.clone())
             .editable(true)
             .validate(self.validate.clone())
+            .on_change(ctx.link().callback(|key: Key| Msg::Change(key.to_string())));
+
+        if !props.show_preview {
+            return selector
+                .with_std_props(&props.std_props)
+                .with_input_props(&props.input_props)
+                .default(&props.default)
+                .into();
+        }
+
+        Column::new()
+            .with_std_props(&props.std_props)
+            .gap(1)
+            .with_child(
+                selector
+                    .with_input_props(&props.input_props)
+                    .default(&props.default),
+            )
+            .with_child(self.render_preview(ctx))
             .into()
     }
 }
+
+impl ProxmoxCalendarEventSelector {
+    fn current_value(&self, ctx: &Context<Self>) -> String {
+        match (&self.form_ctx, &ctx.props().input_props.name) {
+            (Some(form_ctx), Some(name)) => form_ctx.read().get_field_text(name.as_str()),
+            _ => self.value.clone(),
+        }
+    }
+
+    fn render_preview(&self, ctx: &Context<Self>) -> Html {
+        let value = self.current_value(ctx);
+        if value.is_empty() {
+            return html! {};
+        }
+        let now = proxmox_time::epoch_i64();
+        match next_calendar_events(&value, now, PREVIEW_COUNT, false) {
+            Ok(list) if list.is_empty() => Container::new()
+                .class("pwt-color-warning")
+                .with_child(tr!("The event never runs."))
+                .into(),
+            Ok(list) => {
+                let list: Vec<String> = list.into_iter().map(render_epoch).collect();
+                Container::new()
+                    .class("pwt-opacity-50")
+                    .with_child(tr!("Next runs: {0}", list.join(", ")))
+                    .into()
+            }
+            // the validator already shows the error
+            Err(_) => html! {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_next_calendar_events() {
+        assert_eq!(
+            next_calendar_events("daily", 0, 3, true).unwrap(),
+            vec![86400, 2 * 86400, 3 * 86400],
+        );
+        assert_eq!(
+            next_calendar_events("*:0/30", 0, 2, true).unwrap(),
+            vec![1800, 3600],
+        );
+        assert!(next_calendar_events("no valid event", 0, 3, true).is_err());
+    }
+}
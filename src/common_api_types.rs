@@ -32,8 +32,13 @@ impl ExtractPrimaryKey for BasicRealmInfo {
 ///
 /// Different products use different UPID formats. This type can parse
 /// all of them and provides enough information to nicely display task list/info.
+///
+/// Use [FromStr](std::str::FromStr) to create instances.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct ProxmoxUpid {
+    /// The Unix PID of the worker process
+    pub pid: i32,
     /// The task start time (Epoch)
     pub starttime: i64,
     /// Worker type (arbitrary ASCII string)
@@ -63,6 +68,7 @@ impl std::str::FromStr for ProxmoxUpid {
             // assume PBS
             let upid = proxmox_schema::upid::UPID::from_str(upid_part)?;
             Ok(Self {
+                pid: upid.pid,
                 starttime: upid.starttime,
                 worker_id: upid.worker_id,
                 worker_type: upid.worker_type,
@@ -74,6 +80,7 @@ impl std::str::FromStr for ProxmoxUpid {
             // assume PVE
             let upid = PveUpid::from_str(upid_part)?;
             Ok(Self {
+                pid: upid.pid,
                 starttime: upid.starttime,
                 worker_id: upid.worker_id,
                 worker_type: upid.worker_type,
@@ -87,6 +94,44 @@ impl std::str::FromStr for ProxmoxUpid {
     }
 }
 
+impl ProxmoxUpid {
+    /// The user who started the task, without the API token name.
+    pub fn user(&self) -> &str {
+        match self.auth_id.split_once('!') {
+            Some((user, _token)) => user,
+            None => &self.auth_id,
+        }
+    }
+
+    /// The name of the API token which started the task, if any.
+    pub fn token_name(&self) -> Option<&str> {
+        self.auth_id.split_once('!').map(|(_user, token)| token)
+    }
+
+    /// Human readable task description, see
+    /// [register_task_description](crate::utils::register_task_description).
+    pub fn description(&self) -> String {
+        crate::utils::format_task_description(&self.worker_type, self.worker_id.as_deref())
+    }
+
+    /// A file name safe identifier, for example to name downloaded task logs.
+    pub fn file_name_stem(&self) -> String {
+        let mut stem = format!("task-{}-{}", self.node, self.worker_type);
+        if let Some(worker_id) = &self.worker_id {
+            stem.push('-');
+            stem.push_str(worker_id);
+        }
+        stem.push_str(&format!("-{}", self.starttime));
+        stem.replace(
+            |c: char| !(c.is_ascii_alphanumeric() || "-_.".contains(c)),
+            "_",
+        )
+    }
+}
+
+/// Short name for [ProxmoxUpid].
+pub type Upid = ProxmoxUpid;
+
 /// Clasify task status.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum TaskStatusClass {
@@ -108,6 +153,49 @@ impl<T: AsRef<str>> From<T> for TaskStatusClass {
     }
 }
 
+/// Parsed task status.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TaskState {
+    /// The task is still running (no status yet).
+    Running,
+    Ok,
+    /// Finished with the given number of warnings.
+    Warnings(u64),
+    /// Failed, with the error message.
+    Error(String),
+}
+
+impl TaskState {
+    /// Parse the `status` of a task list item, for example `pbs_api_types::TaskListItem`
+    /// (`None` or `RUNNING` for running tasks).
+    ///
+    /// Fails if the warning count is not a number.
+    pub fn parse(status: Option<&str>) -> Result<Self, Error> {
+        let state = match status {
+            None | Some("" | "RUNNING") => TaskState::Running,
+            Some("OK") => TaskState::Ok,
+            Some(status) => match status.strip_prefix("WARNINGS:") {
+                Some(count) => match count.trim().parse() {
+                    Ok(count) => TaskState::Warnings(count),
+                    Err(_) => bail!("invalid task status '{status}'"),
+                },
+                None => TaskState::Error(status.to_string()),
+            },
+        };
+        Ok(state)
+    }
+
+    /// The status class, `None` while the task is running.
+    pub fn class(&self) -> Option<TaskStatusClass> {
+        match self {
+            TaskState::Running => None,
+            TaskState::Ok => Some(TaskStatusClass::Ok),
+            TaskState::Warnings(_) => Some(TaskStatusClass::Warning),
+            TaskState::Error(_) => Some(TaskStatusClass::Error),
+        }
+    }
+}
+
 /// Certificate information.
 #[derive(PartialEq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -331,3 +419,49 @@ fn unescape_id(text: &str) -> Result<String, Error> {
 
     Ok(text)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_upid() {
+        let upid: Upid = "UPID:pve1:0000C530:0001D0F1:66D5A8B3:qmstart:100:root@pam!automation:"
+            .parse()
+            .unwrap();
+        assert_eq!(upid.node, "pve1");
+        assert_eq!(upid.pid, 0xC530);
+        assert_eq!(upid.starttime, 0x66D5A8B3);
+        assert_eq!(upid.worker_type, "qmstart");
+        assert_eq!(upid.worker_id.as_deref(), Some("100"));
+        assert_eq!(upid.user(), "root@pam");
+        assert_eq!(upid.token_name(), Some("automation"));
+        assert_eq!(upid.remote, None);
+        assert_eq!(upid.file_name_stem(), "task-pve1-qmstart-100-1725278387");
+
+        let upid: Upid =
+            "remote1!UPID:pbs:000004B5:00000A8C:00000000:66D5A8B3:garbage_collection:store1:root@pam:"
+                .parse()
+                .unwrap();
+        assert_eq!(upid.remote.as_deref(), Some("remote1"));
+        assert_eq!(upid.worker_type, "garbage_collection");
+        assert_eq!(upid.token_name(), None);
+
+        assert!("UPID:invalid".parse::<Upid>().is_err());
+    }
+
+    #[test]
+    fn test_task_state() {
+        assert_eq!(TaskState::parse(None).unwrap(), TaskState::Running);
+        assert_eq!(TaskState::parse(Some("OK")).unwrap(), TaskState::Ok);
+        assert_eq!(
+            TaskState::parse(Some("WARNINGS: 3")).unwrap(),
+            TaskState::Warnings(3)
+        );
+        assert_eq!(
+            TaskState::parse(Some("command failed")).unwrap().class(),
+            Some(TaskStatusClass::Error)
+        );
+        assert!(TaskState::parse(Some("WARNINGS: x")).is_err());
+    }
+}
@@ -1,56 +1,110 @@
+use std::net::IpAddr;
 use std::rc::Rc;
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use serde_json::Value;
 
 use crate::form::delete_empty_values;
-use crate::{ObjectGrid, ObjectGridRow, SchemaValidation};
+use crate::{ObjectGrid, ObjectGridRow};
 
 use pwt::prelude::*;
 use pwt::widget::form::{Field, FormContext};
-use pwt::widget::InputPanel;
+use pwt::widget::{Column, Container, InputPanel};
+
+use super::validate_dns_domain;
+
+const DNS_SERVERS: [&str; 3] = ["dns1", "dns2", "dns3"];
 
 async fn store_dns(form_ctx: FormContext) -> Result<(), Error> {
+    // Each editor only submits its own field, together with the digest loaded when the
+    // editor was opened. So the API rejects the update if the configuration was modified
+    // elsewhere in the meantime.
     let data = form_ctx.get_submit_data();
-    let data = delete_empty_values(&data, &["dns1", "dns2", "dns3"], true);
+    let data = delete_empty_values(&data, &DNS_SERVERS, false);
     crate::http_put("/nodes/localhost/dns", Some(data)).await
 }
 
-fn dns_editor(_form_ctx: &FormContext, _name: &str, _value: &Value, record: &Value) -> Html {
-    InputPanel::new()
-        .padding(4)
-        .with_field(
+/// Validate a DNS server address (IPv4 or IPv6).
+///
+/// IPv6 link-local addresses may include a zone index, e.g. `fe80::1%eth0`.
+fn validate_dns_server(value: &str) -> Result<(), Error> {
+    let valid = match value.split_once('%') {
+        Some((address, zone)) => !zone.is_empty() && address.parse::<std::net::Ipv6Addr>().is_ok(),
+        None => value.parse::<IpAddr>().is_ok(),
+    };
+    if !valid {
+        bail!(tr!("Invalid IPv4 or IPv6 address."));
+    }
+    Ok(())
+}
+
+/// Generate the `resolv.conf` content for the given search domain and DNS servers.
+fn resolv_conf_preview(search: &str, servers: &[String]) -> String {
+    let mut text = String::new();
+    if !search.is_empty() {
+        text.push_str(&format!("search {search}\n"));
+    }
+    for server in servers.iter().filter(|server| !server.is_empty()) {
+        text.push_str(&format!("nameserver {server}\n"));
+    }
+    text
+}
+
+fn dns_editor(form_ctx: &FormContext, name: &str, _value: &Value, record: &Value) -> Html {
+    let default = record[name].as_str().unwrap_or("").to_string();
+
+    let (label, field) = match name {
+        "search" => (
             tr!("Search domain"),
             Field::new()
                 .name("search")
                 .required(true)
-                .default(record["search"].as_str().unwrap_or("").to_string())
-                .schema(&proxmox_dns_api::SEARCH_DOMAIN_SCHEMA)
-                .autofocus(true),
-        )
-        .with_field(
-            tr!("DNS server 1"),
-            Field::new()
-                .name("dns1")
-                .required(true)
-                .default(record["dns1"].as_str().unwrap_or("").to_string())
-                .schema(&proxmox_dns_api::FIRST_DNS_SERVER_SCHEMA),
-        )
-        .with_field(
-            tr!("DNS server 2"),
-            Field::new()
-                .name("dns2")
-                .default(record["dns2"].as_str().unwrap_or("").to_string())
-                .schema(&proxmox_dns_api::SECOND_DNS_SERVER_SCHEMA),
-            //.validate(validate_ip.clone()),
+                .validate(|value: &String| validate_dns_domain(value)),
+        ),
+        _ => {
+            let index = DNS_SERVERS.iter().position(|n| *n == name).unwrap_or(0);
+            (
+                tr!("DNS server {0}", index + 1),
+                Field::new()
+                    .name(name.to_string())
+                    .required(index == 0)
+                    // submit cleared servers, so that they get deleted
+                    .submit_empty(true)
+                    .validate(|value: &String| validate_dns_server(value)),
+            )
+        }
+    };
+
+    // use the current form value for the edited field, the loaded values otherwise
+    let current = |field: &str| {
+        if field == name {
+            form_ctx.read().get_field_text(field)
+        } else {
+            record[field].as_str().unwrap_or("").to_string()
+        }
+    };
+    let servers: Vec<String> = DNS_SERVERS.into_iter().map(&current).collect();
+    let preview = resolv_conf_preview(&current("search"), &servers);
+
+    Column::new()
+        .with_child(
+            InputPanel::new()
+                .padding(4)
+                .with_field(label, field.default(default).autofocus(true)),
         )
-        .with_field(
-            tr!("DNS server 3"),
-            Field::new()
-                .name("dns3")
-                .default(record["dns3"].as_str().unwrap_or("").to_string())
-                .schema(&proxmox_dns_api::THIRD_DNS_SERVER_SCHEMA),
-            //.validate(validate_ip),
+        .with_child(
+            Column::new()
+                .padding_x(4)
+                .padding_bottom(4)
+                .gap(2)
+                .class("pwt-font-size-body-medium")
+                .with_child(tr!("Resulting resolv.conf"))
+                .with_child(
+                    Container::from_tag("pre")
+                        .padding(2)
+                        .class("pwt-border pwt-font-monospace")
+                        .with_child(preview),
+                ),
         )
         .into()
 }
@@ -75,3 +129,27 @@ pub fn dns_panel() -> Html {
         .rows(rows)
         .into()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_dns_server() {
+        assert!(validate_dns_server("192.168.1.1").is_ok());
+        assert!(validate_dns_server("2001:db8::53").is_ok());
+        assert!(validate_dns_server("fe80::1%eth0").is_ok());
+        assert!(validate_dns_server("192.168.1.1%eth0").is_err());
+        assert!(validate_dns_server("fe80::1%").is_err());
+        assert!(validate_dns_server("dns.example.com").is_err());
+    }
+
+    #[test]
+    fn test_resolv_conf_preview() {
+        let servers = ["192.168.1.1".to_string(), String::new(), "::1".to_string()];
+        assert_eq!(
+            resolv_conf_preview("example.com", &servers),
+            "search example.com\nnameserver 192.168.1.1\nnameserver ::1\n"
+        );
+    }
+}
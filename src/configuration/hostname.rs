@@ -0,0 +1,228 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, DisplayField, Field, FormContext, ValidateFn};
+use pwt::widget::{Column, InputPanel};
+
+use pwt_macros::builder;
+
+use crate::form::delete_empty_values;
+use crate::EditWindow;
+
+/// Check that `name` is a valid DNS label (a hostname without domain).
+pub fn validate_hostname(name: &str) -> Result<(), Error> {
+    if name.is_empty() || name.len() > 63 {
+        bail!(tr!("Hostname must be between 1 and 63 characters long."));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        bail!(tr!(
+            "Hostname may only contain letters, digits and hyphens."
+        ));
+    }
+    if name.starts_with('-') || name.ends_with('-') {
+        bail!(tr!("Hostname must not start or end with a hyphen."));
+    }
+    if name.chars().all(|c| c.is_ascii_digit()) {
+        bail!(tr!("Hostname must not consist of digits only."));
+    }
+    Ok(())
+}
+
+/// Check that `domain` is a valid DNS domain name.
+pub fn validate_dns_domain(domain: &str) -> Result<(), Error> {
+    let domain = domain.strip_suffix('.').unwrap_or(domain);
+    if domain.len() > 253 {
+        bail!(tr!("Domain name must not be longer than 253 characters."));
+    }
+    for label in domain.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            bail!(tr!("Invalid domain name label '{0}'.", label));
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            || label.starts_with('-')
+            || label.ends_with('-')
+        {
+            bail!(tr!("Invalid domain name label '{0}'.", label));
+        }
+    }
+    Ok(())
+}
+
+/// Dialog to change the hostname and domain of a node.
+///
+/// Only use this with products whose API supports changing the hostname. The current values
+/// are loaded from, and the new `hostname` and `domain` are submitted (`PUT`) to the configured
+/// url. As a renamed node invalidates its certificates, and renaming a cluster member breaks the
+/// cluster, the user has to acknowledge these implications before submitting.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct HostnameEditDialog {
+    /// The hostname API endpoint.
+    #[builder(IntoPropValue, into_prop_value)]
+    pub url: AttrValue,
+
+    /// Whether the node is part of a cluster, shows an additional warning.
+    #[builder]
+    #[prop_or_default]
+    pub cluster_member: bool,
+
+    /// The configuration files the product updates when changing the hostname.
+    #[builder]
+    #[prop_or(vec![AttrValue::Static("/etc/hostname"), AttrValue::Static("/etc/hosts")])]
+    pub affected_files: Vec<AttrValue>,
+
+    /// Done callback, called after Close, Abort or Submit.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_done: Option<Callback<()>>,
+}
+
+impl HostnameEditDialog {
+    pub fn new(url: impl Into<AttrValue>) -> Self {
+        yew::props!(Self { url: url.into() })
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxHostnameEditDialog {
+    validate_confirm: ValidateFn<bool>,
+}
+
+impl Component for ProxmoxHostnameEditDialog {
+    type Message = ();
+    type Properties = HostnameEditDialog;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            validate_confirm: ValidateFn::new(|value: &bool| {
+                if !value {
+                    bail!(tr!("Please confirm the change."))
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let validate_confirm = self.validate_confirm.clone();
+        let cluster_member = props.cluster_member;
+        let affected_files = Rc::new(props.affected_files.clone());
+
+        EditWindow::new(tr!("Change Hostname"))
+            .width(500)
+            .loader(props.url.clone())
+            .on_done(props.on_done.clone())
+            .renderer(move |form_ctx: &FormContext| {
+                let hostname = form_ctx.read().get_field_text("hostname");
+                let domain = form_ctx.read().get_field_text("domain");
+                let fqdn = match (hostname.is_empty(), domain.is_empty()) {
+                    (false, false) => format!("{hostname}.{domain}"),
+                    (false, true) => hostname,
+                    _ => String::from("-"),
+                };
+
+                let mut warnings = Column::new()
+                    .key("warnings")
+                    .class("pwt-color-warning")
+                    .gap(2)
+                    .with_child(tr!(
+                        "Certificates issued for the old name will no longer match the node \
+                        and need to be renewed or ordered again."
+                    ));
+
+                if cluster_member {
+                    warnings.add_child(tr!(
+                        "This node is part of a cluster. Changing its name breaks the cluster \
+                        communication, please remove the node from the cluster first."
+                    ));
+                }
+
+                let mut files = Column::new()
+                    .key("affected-files")
+                    .gap(1)
+                    .with_child(tr!("The following files will be modified:"));
+                for file in affected_files.iter() {
+                    files.add_child(html! {<code>{file}</code>});
+                }
+
+                InputPanel::new()
+                    .padding(4)
+                    .class("pwt-flex-fit")
+                    .with_field(
+                        tr!("Hostname"),
+                        Field::new()
+                            .name("hostname")
+                            .required(true)
+                            .autofocus(true)
+                            .validate(|value: &String| validate_hostname(value)),
+                    )
+                    .with_field(
+                        tr!("Domain"),
+                        Field::new()
+                            .name("domain")
+                            .validate(|value: &String| validate_dns_domain(value)),
+                    )
+                    .with_field(tr!("FQDN"), DisplayField::new().value(fqdn))
+                    .with_large_custom_child(warnings)
+                    .with_large_custom_child(files)
+                    .with_field(
+                        tr!("I understand the implications"),
+                        Checkbox::new()
+                            .name("_confirm")
+                            .submit(false)
+                            .validate(validate_confirm.clone()),
+                    )
+                    .into()
+            })
+            .on_submit({
+                let url = props.url.clone();
+                move |form_ctx: FormContext| {
+                    let url = url.clone();
+                    async move {
+                        let data = form_ctx.get_submit_data();
+                        let data = delete_empty_values(&data, &["domain"], false);
+                        crate::http_put(&*url, Some(data)).await
+                    }
+                }
+            })
+            .into()
+    }
+}
+
+impl From<HostnameEditDialog> for VNode {
+    fn from(val: HostnameEditDialog) -> Self {
+        let comp = VComp::new::<ProxmoxHostnameEditDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hostname_validation() {
+        assert!(validate_hostname("pve1").is_ok());
+        assert!(validate_hostname("node-a").is_ok());
+        assert!(validate_hostname("").is_err());
+        assert!(validate_hostname("-node").is_err());
+        assert!(validate_hostname("node.example").is_err());
+        assert!(validate_hostname("1234").is_err());
+        assert!(validate_hostname(&"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn domain_validation() {
+        assert!(validate_dns_domain("example.com").is_ok());
+        assert!(validate_dns_domain("example.com.").is_ok());
+        assert!(validate_dns_domain("a..b").is_err());
+        assert!(validate_dns_domain("-bad.example").is_err());
+        assert!(validate_dns_domain("under_score.example").is_err());
+    }
+}
@@ -6,6 +6,11 @@ pub use dns::DnsPanel;
 mod time;
 pub use time::TimePanel;
 
+mod hostname;
+pub use hostname::{
+    validate_dns_domain, validate_hostname, HostnameEditDialog, ProxmoxHostnameEditDialog,
+};
+
 pub mod pve;
 
 #[cfg(feature = "network")]
@@ -22,6 +27,47 @@ pub use network_edit::{NetworkEdit, ProxmoxNetworkEdit};
 use proxmox_network_api::NetworkInterfaceType;
 #[cfg(feature = "network")]
 use pwt::tr;
+#[cfg(feature = "network")]
+use serde::{Deserialize, Serialize};
+
+/// Open vSwitch interface types (only available on Proxmox VE).
+#[cfg(feature = "network")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OvsInterfaceType {
+    #[serde(rename = "OVSBridge")]
+    Bridge,
+    #[serde(rename = "OVSBond")]
+    Bond,
+    #[serde(rename = "OVSIntPort")]
+    IntPort,
+    #[serde(rename = "OVSPort")]
+    Port,
+}
+
+/// Network interface type, including the Open vSwitch types.
+///
+/// Serializes to the `type` value used by the network API.
+#[cfg(feature = "network")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NetworkInterfaceKind {
+    Linux(NetworkInterfaceType),
+    Ovs(OvsInterfaceType),
+}
+
+#[cfg(feature = "network")]
+impl From<NetworkInterfaceType> for NetworkInterfaceKind {
+    fn from(interface_type: NetworkInterfaceType) -> Self {
+        Self::Linux(interface_type)
+    }
+}
+
+#[cfg(feature = "network")]
+impl From<OvsInterfaceType> for NetworkInterfaceKind {
+    fn from(interface_type: OvsInterfaceType) -> Self {
+        Self::Ovs(interface_type)
+    }
+}
 
 #[cfg(feature = "network")]
 pub fn format_network_interface_type(interface_type: NetworkInterfaceType) -> String {
@@ -36,6 +82,26 @@ pub fn format_network_interface_type(interface_type: NetworkInterfaceType) -> St
     }
 }
 
+#[cfg(feature = "network")]
+pub fn format_ovs_interface_type(interface_type: OvsInterfaceType) -> String {
+    match interface_type {
+        OvsInterfaceType::Bridge => tr!("OVS Bridge"),
+        OvsInterfaceType::Bond => tr!("OVS Bond"),
+        OvsInterfaceType::IntPort => tr!("OVS IntPort"),
+        OvsInterfaceType::Port => tr!("OVS Port"),
+    }
+}
+
+#[cfg(feature = "network")]
+pub fn format_network_interface_kind(kind: NetworkInterfaceKind) -> String {
+    match kind {
+        NetworkInterfaceKind::Linux(interface_type) => {
+            format_network_interface_type(interface_type)
+        }
+        NetworkInterfaceKind::Ovs(interface_type) => format_ovs_interface_type(interface_type),
+    }
+}
+
 use yew::AttrValue;
 
 use crate::form::pve::PveGuestType;
@@ -132,3 +198,38 @@ pub fn guest_move_disk_url(
     let base_url = guest_base_url(vmid, node, remote, guest_type);
     format!("{base_url}/{name}")
 }
+
+pub fn guest_snapshot_url(
+    vmid: u32,
+    node: &AttrValue,
+    remote: &Option<AttrValue>,
+    guest_type: PveGuestType,
+) -> String {
+    let base_url = guest_base_url(vmid, node, remote, guest_type);
+    format!("{base_url}/snapshot")
+}
+
+pub fn guest_status_command_url(
+    vmid: u32,
+    node: &AttrValue,
+    remote: &Option<AttrValue>,
+    guest_type: PveGuestType,
+    command: &str,
+) -> String {
+    let base_url = guest_base_url(vmid, node, remote, guest_type);
+    if remote.is_some() {
+        format!("{base_url}/{command}")
+    } else {
+        format!("{base_url}/status/{command}")
+    }
+}
+
+pub fn guest_migrate_url(
+    vmid: u32,
+    node: &AttrValue,
+    remote: &Option<AttrValue>,
+    guest_type: PveGuestType,
+) -> String {
+    let base_url = guest_base_url(vmid, node, remote, guest_type);
+    format!("{base_url}/migrate")
+}
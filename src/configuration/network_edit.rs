@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
-use anyhow::Error;
+use anyhow::{bail, format_err, Error};
 use proxmox_client::ApiResponseData;
 use serde_json::Value;
 
@@ -8,22 +9,87 @@ use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
-use pwt::widget::form::{Checkbox, Field, FormContext, Number};
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, Number};
 use pwt::widget::InputPanel;
+use pwt::AsyncPool;
 
 use crate::form::delete_empty_values;
 use crate::utils::json_array_to_flat_string;
 use crate::{
-    ApiLoadCallback, BondModeSelector, BondXmitHashPolicySelector, EditWindow, SchemaValidation,
+    ApiLoadCallback, BondModeSelector, BondXmitHashPolicySelector, EditWindow, ExistingProduct,
+    SchemaValidation,
 };
 
 use proxmox_network_api::NetworkInterfaceType;
 use proxmox_schema::api_types::{CIDR_V4_SCHEMA, CIDR_V6_SCHEMA, IP_V4_SCHEMA, IP_V6_SCHEMA};
+use proxmox_schema::{ApiStringFormat, IntegerSchema, Schema, StringSchema};
 
 use crate::percent_encoding::percent_encode_component;
 use pwt_macros::builder;
 
-use super::format_network_interface_type;
+use super::{format_network_interface_kind, NetworkInterfaceKind, OvsInterfaceType};
+
+const MTU_SCHEMA: Schema = IntegerSchema::new("Maximum Transmission Unit.")
+    .minimum(1280)
+    .maximum(65520)
+    .default(1500)
+    .schema();
+
+const BRIDGE_VIDS_SCHEMA: Schema = StringSchema::new("List of VLAN IDs and ranges.")
+    .format(&ApiStringFormat::VerifyFn(verify_vlan_id_list))
+    .schema();
+
+/// Verify a list of VLAN IDs and ranges, for example `2 10-20 100`.
+fn verify_vlan_id_list(value: &str) -> Result<(), Error> {
+    for item in value.split([' ', ',']).filter(|item| !item.is_empty()) {
+        let (start, end) = item.split_once('-').unwrap_or((item, item));
+        let parse = |id: &str| {
+            id.parse::<u16>()
+                .ok()
+                .filter(|id| (2..=4094).contains(id))
+                .ok_or_else(|| format_err!("invalid VLAN ID '{id}' (must be in the range 2-4094)"))
+        };
+        if parse(start)? > parse(end)? {
+            bail!("invalid VLAN ID range '{item}'");
+        }
+    }
+    Ok(())
+}
+
+/// Returns the smallest configured MTU of the lower interfaces, which limits the MTU of the
+/// upper interface (a VLAN cannot use a larger MTU than its parent, and a bridge cannot use
+/// a larger MTU than its ports).
+fn mtu_limit<'a>(
+    mtus: &HashMap<String, u64>,
+    lower: impl IntoIterator<Item = &'a str>,
+) -> Option<u64> {
+    lower
+        .into_iter()
+        .filter_map(|name| mtus.get(name).copied())
+        .min()
+}
+
+/// The parent of a VLAN interface, derived from the name (e.g. `eno1.50`).
+fn vlan_parent(name: &str) -> Option<&str> {
+    name.rsplit_once('.').map(|(parent, _)| parent)
+}
+
+fn mtu_field(limit: Option<u64>) -> Number<u64> {
+    let field = Number::new().name("mtu").schema(&MTU_SCHEMA);
+    match limit {
+        // the default MTU is inherited from the lower interface
+        Some(mtu) => field.max(mtu).placeholder(mtu.to_string()),
+        None => field,
+    }
+}
+
+async fn load_mtus() -> Result<HashMap<String, u64>, Error> {
+    let list: Vec<Value> = crate::http_get("/nodes/localhost/network", None).await?;
+    Ok(list
+        .iter()
+        .filter_map(|item| Some((item["iface"].as_str()?.to_string(), item["mtu"].as_u64()?)))
+        .collect())
+}
 
 async fn load_item(name: AttrValue) -> Result<ApiResponseData<Value>, Error> {
     let url = format!(
@@ -51,7 +117,7 @@ async fn load_item(name: AttrValue) -> Result<ApiResponseData<Value>, Error> {
 
 async fn create_item(
     form_ctx: FormContext,
-    interface_type: NetworkInterfaceType,
+    interface_type: NetworkInterfaceKind,
 ) -> Result<(), Error> {
     let mut data = form_ctx.get_submit_data();
 
@@ -66,21 +132,32 @@ async fn create_item(
     crate::http_post("/nodes/localhost/network", Some(data)).await
 }
 
-async fn update_item(form_ctx: FormContext) -> Result<(), Error> {
+async fn update_item(
+    form_ctx: FormContext,
+    interface_type: NetworkInterfaceKind,
+    product: Option<ExistingProduct>,
+) -> Result<(), Error> {
     let data = form_ctx.get_submit_data();
-    let data = delete_empty_values(
-        &data,
-        &[
-            "bridge_vlan_aware",
-            "bond_xmit_hash_policy",
-            "cidr",
-            "cidr6",
-            "gateway",
-            "gateway6",
-            "mtu",
-        ],
-        true,
-    );
+
+    let mut delete_list = vec![
+        "bridge_vlan_aware",
+        "bond_xmit_hash_policy",
+        "bond-primary",
+        "cidr",
+        "cidr6",
+        "gateway",
+        "gateway6",
+        "mtu",
+    ];
+    // only known by the Proxmox VE API
+    if let NetworkInterfaceKind::Ovs(_) = interface_type {
+        delete_list.extend(["ovs_options", "ovs_tag"]);
+    }
+    if supports_bridge_vids(product) {
+        delete_list.push("bridge_vids");
+    }
+
+    let data = delete_empty_values(&data, &delete_list, true);
 
     let name = form_ctx.read().get_field_text("name");
     let url = format!(
@@ -94,7 +171,7 @@ async fn update_item(form_ctx: FormContext) -> Result<(), Error> {
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
 pub struct NetworkEdit {
-    pub interface_type: NetworkInterfaceType,
+    pub interface_type: NetworkInterfaceKind,
     /// Close/Abort callback
     #[builder_cb(IntoEventCallback, into_event_callback, ())]
     #[prop_or_default]
@@ -109,22 +186,60 @@ pub struct NetworkEdit {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or_default]
     pub default_name: Option<AttrValue>,
+
+    /// The Product. Only PVE supports the bridge VLAN IDs option.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub product: Option<ExistingProduct>,
 }
 
 impl NetworkEdit {
-    pub fn new(interface_type: NetworkInterfaceType) -> Self {
-        yew::props!(Self { interface_type })
+    pub fn new(interface_type: impl Into<NetworkInterfaceKind>) -> Self {
+        yew::props!(Self {
+            interface_type: interface_type.into()
+        })
     }
 }
 
 pub struct ProxmoxNetworkEdit {
     loader: Option<ApiLoadCallback<Value>>,
+    mtus: Rc<HashMap<String, u64>>,
+    _async_pool: AsyncPool,
+}
+
+// bridge VLAN IDs and Open vSwitch interfaces are only supported by Proxmox VE
+fn is_pve(product: Option<ExistingProduct>) -> bool {
+    matches!(
+        product,
+        Some(ExistingProduct::PVE | ExistingProduct::PVEMobile)
+    )
 }
 
-fn render_bridge_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
+fn supports_bridge_vids(product: Option<ExistingProduct>) -> bool {
+    is_pve(product)
+}
+
+pub(super) fn supports_ovs(product: Option<ExistingProduct>) -> bool {
+    is_pve(product)
+}
+
+fn render_bridge_form(
+    form_ctx: FormContext,
+    props: &NetworkEdit,
+    mtus: &HashMap<String, u64>,
+) -> Html {
     let is_edit = props.name.is_some();
 
-    InputPanel::new()
+    let (vlan_aware, bridge_ports) = {
+        let form = form_ctx.read();
+        (
+            form.get_field_checked("bridge_vlan_aware"),
+            form.get_field_text("bridge_ports"),
+        )
+    };
+    let mtu_limit = mtu_limit(mtus, bridge_ports.split_whitespace());
+
+    let mut panel = InputPanel::new()
         .show_advanced(form_ctx.get_show_advanced())
         .padding(4)
         .with_field(
@@ -145,7 +260,22 @@ fn render_bridge_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
             tr!("IPv4/CIDR"),
             Field::new().name("cidr").schema(&CIDR_V4_SCHEMA),
         )
-        .with_right_field(tr!("VLAN aware"), Checkbox::new().name("bridge_vlan_aware"))
+        .with_right_field(tr!("VLAN aware"), Checkbox::new().name("bridge_vlan_aware"));
+
+    if vlan_aware && supports_bridge_vids(props.product) {
+        panel.add_right_field(
+            tr!("VLAN IDs"),
+            Field::new()
+                .name("bridge_vids")
+                .schema(&BRIDGE_VIDS_SCHEMA)
+                .placeholder("2-4094")
+                .tip(tr!(
+                    "Space-separated list of VLAN IDs and ranges, for example: 2 10-20 100"
+                )),
+        );
+    }
+
+    panel
         .with_field(
             tr!("Gateway") + " (IPv4)",
             Field::new().name("gateway").schema(&IP_V4_SCHEMA),
@@ -169,36 +299,54 @@ fn render_bridge_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
             Field::new().name("gateway6").schema(&IP_V6_SCHEMA),
         )
         .with_advanced_spacer()
-        .with_advanced_field(
-            tr!("MTU"),
-            Number::new().min(1).name("mtu").placeholder("1500"),
-        )
+        .with_advanced_field(tr!("MTU"), mtu_field(mtu_limit))
         .into()
 }
 
-fn render_bond_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
-    let is_edit = props.name.is_some();
-
-    let mode = form_ctx
+fn bond_mode(form_ctx: &FormContext) -> String {
+    form_ctx
         .read()
         .get_field_value("bond_mode")
         .and_then(|v| v.as_str().map(String::from))
-        .unwrap_or_default();
+        .unwrap_or_default()
+}
 
-    let allow_xmit_hash_policy = mode == "balance-xor" || mode == "802.3ad";
+fn bond_uses_xmit_hash_policy(mode: &str) -> bool {
+    mode == "balance-xor" || mode == "802.3ad"
+}
+
+fn bond_uses_primary(mode: &str) -> bool {
+    mode == "active-backup"
+}
+
+// clear the bond options not used by the selected mode
+fn clear_unused_bond_options(form_ctx: FormContext) {
+    let mode = bond_mode(&form_ctx);
 
-    if !allow_xmit_hash_policy {
-        form_ctx
-            .write()
-            .set_field_value("bond_xmit_hash_policy", "".into())
+    let mut unused = Vec::new();
+    if !bond_uses_xmit_hash_policy(&mode) {
+        unused.push("bond_xmit_hash_policy");
+    }
+    if !bond_uses_primary(&mode) {
+        unused.push("bond-primary");
     }
 
-    let allow_bond_primary = mode == "active-backup";
-    if !allow_bond_primary {
-        form_ctx.write().set_field_value("bond-primary", "".into())
+    for name in unused {
+        // only write if needed, writing triggers another change event
+        if !form_ctx.read().get_field_text(name).is_empty() {
+            form_ctx.write().set_field_value(name, "".into());
+        }
     }
+}
 
-    InputPanel::new()
+fn render_bond_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
+    let is_edit = props.name.is_some();
+
+    let mode = bond_mode(&form_ctx);
+    let allow_xmit_hash_policy = bond_uses_xmit_hash_policy(&mode);
+    let allow_bond_primary = bond_uses_primary(&mode);
+
+    let mut panel = InputPanel::new()
         .show_advanced(form_ctx.get_show_advanced())
         .padding(4)
         .with_field(
@@ -240,37 +388,172 @@ fn render_bond_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
             tr!("IPv6/CIDR"),
             Field::new().name("cidr6").schema(&CIDR_V6_SCHEMA),
         )
-        .with_right_field(
+        .with_field(
+            tr!("Gateway") + " (IPv6)",
+            Field::new().name("gateway6").schema(&IP_V6_SCHEMA),
+        );
+
+    // only show the options used by the selected mode
+    if allow_xmit_hash_policy {
+        panel.add_right_field(
             tr!("Hash policy"),
-            BondXmitHashPolicySelector::new()
-                .name("bond_xmit_hash_policy")
-                .disabled(!allow_xmit_hash_policy),
+            BondXmitHashPolicySelector::new().name("bond_xmit_hash_policy"),
+        );
+    }
+    if allow_bond_primary {
+        panel.add_right_field(
+            tr!("Bond Primary"),
+            Field::new().name("bond-primary").tip(tr!(
+                "The slave used while it is available, for example: enp0s0"
+            )),
+        );
+    }
+
+    panel
+        .with_right_field(
+            tr!("Comment"),
+            Field::new().name("comments").submit_empty(true),
+        )
+        .with_advanced_spacer()
+        .with_advanced_field(tr!("MTU"), mtu_field(None))
+        .into()
+}
+
+fn render_common_form(
+    form_ctx: FormContext,
+    props: &NetworkEdit,
+    mtus: &HashMap<String, u64>,
+) -> Html {
+    let is_edit = props.name.is_some();
+
+    let name = match &props.name {
+        Some(name) => name.to_string(),
+        None => form_ctx.read().get_field_text("name"),
+    };
+    let mtu_limit = mtu_limit(mtus, vlan_parent(&name));
+
+    InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_field(
+            tr!("Name"),
+            Field::new()
+                .name("name")
+                .default(&props.default_name)
+                .tip(tr!(
+                    "Format: [interface].[vlan-id] (e.g., eno1.50, bond1.30)"
+                ))
+                .required(true)
+                .disabled(is_edit)
+                .submit(!is_edit),
+        )
+        .with_right_field(
+            tr!("Autostart"),
+            Checkbox::new().name("autostart").default(true),
+        )
+        .with_field(
+            tr!("IPv4/CIDR"),
+            Field::new().name("cidr").schema(&CIDR_V4_SCHEMA),
+        )
+        .with_right_field(
+            tr!("Comment"),
+            Field::new().name("comments").submit_empty(true),
+        )
+        .with_field(
+            tr!("Gateway") + " (IPv4)",
+            Field::new().name("gateway").schema(&IP_V4_SCHEMA),
+        )
+        .with_field(
+            tr!("IPv6/CIDR"),
+            Field::new().name("cidr6").schema(&CIDR_V6_SCHEMA),
         )
         .with_field(
             tr!("Gateway") + " (IPv6)",
             Field::new().name("gateway6").schema(&IP_V6_SCHEMA),
         )
-        .with_right_field(
-            "bond-primary",
+        .with_advanced_spacer()
+        .with_advanced_field(tr!("MTU"), mtu_field(mtu_limit))
+        .into()
+}
+
+fn ovs_bridge_field() -> Field {
+    Field::new().name("ovs_bridge").required(true).tip(tr!(
+        "The OVS bridge this interface is attached to, for example: vmbr0"
+    ))
+}
+
+fn ovs_tag_field() -> Number<u16> {
+    Number::new().name("ovs_tag").min(1).max(4094)
+}
+
+fn ovs_options_field() -> Field {
+    Field::new().name("ovs_options").tip(tr!(
+        "Additional OVS options, for example: tag=10 vlan_mode=native-untagged"
+    ))
+}
+
+fn render_ovs_bridge_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
+    let is_edit = props.name.is_some();
+
+    InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_field(
+            tr!("Name"),
             Field::new()
-                .name("bond-primary")
-                .disabled(!allow_bond_primary),
+                .name("name")
+                .tip(tr!("Commonly: vmbr[N] (e.g., vmbr0, vmbr1). Must start with a character, max 10 alphanumeric characters."))
+                .required(true)
+                .default(&props.default_name)
+                .disabled(is_edit)
+                .submit(!is_edit),
+        )
+        .with_right_field(
+            tr!("Autostart"),
+            Checkbox::new().name("autostart").default(true),
+        )
+        .with_field(
+            tr!("IPv4/CIDR"),
+            Field::new().name("cidr").schema(&CIDR_V4_SCHEMA),
+        )
+        .with_right_field(
+            tr!("Bridge ports"),
+            Field::new().name("ovs_ports").tip(tr!(
+                "Space-separated list of interfaces, for example: enp0s0 enp1s0"
+            )),
+        )
+        .with_field(
+            tr!("Gateway") + " (IPv4)",
+            Field::new().name("gateway").schema(&IP_V4_SCHEMA),
+        )
+        .with_right_field(tr!("OVS options"), ovs_options_field())
+        .with_field(
+            tr!("IPv6/CIDR"),
+            Field::new().name("cidr6").schema(&CIDR_V6_SCHEMA),
         )
         .with_right_field(
             tr!("Comment"),
             Field::new().name("comments").submit_empty(true),
         )
-        .with_advanced_spacer()
-        .with_advanced_field(
-            tr!("MTU"),
-            Number::new().min(1).name("mtu").placeholder("1500"),
+        .with_field(
+            tr!("Gateway") + " (IPv6)",
+            Field::new().name("gateway6").schema(&IP_V6_SCHEMA),
         )
+        .with_advanced_spacer()
+        .with_advanced_field(tr!("MTU"), mtu_field(None))
         .into()
 }
 
-fn render_common_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
+fn render_ovs_bond_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
     let is_edit = props.name.is_some();
 
+    let bond_modes = Rc::new(vec![
+        AttrValue::Static("active-backup"),
+        AttrValue::Static("balance-slb"),
+        AttrValue::Static("lacp-balance-slb"),
+        AttrValue::Static("lacp-balance-tcp"),
+    ]);
+
     InputPanel::new()
         .show_advanced(form_ctx.get_show_advanced())
         .padding(4)
@@ -279,7 +562,7 @@ fn render_common_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
             Field::new()
                 .name("name")
                 .default(&props.default_name)
-                .tip(tr!("Format: [interface].[vlan-id] (e.g., eno1.50, bond1.30)"))
+                .tip(tr!("Format: bond[N] (e.g., bond0, bond1)"))
                 .required(true)
                 .disabled(is_edit)
                 .submit(!is_edit),
@@ -289,17 +572,65 @@ fn render_common_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
             Checkbox::new().name("autostart").default(true),
         )
         .with_field(
-            tr!("IPv4/CIDR"),
-            Field::new().name("cidr").schema(&CIDR_V4_SCHEMA),
+            tr!("Slaves"),
+            Field::new().name("ovs_bonds").required(true).tip(tr!(
+                "Space-separated list of interfaces, for example: enp0s0 enp1s0"
+            )),
         )
+        .with_right_field(
+            tr!("Mode"),
+            Combobox::new()
+                .name("bond_mode")
+                .items(bond_modes)
+                .required(true)
+                .default("active-backup"),
+        )
+        .with_field(tr!("OVS Bridge"), ovs_bridge_field())
+        .with_right_field(tr!("VLAN Tag"), ovs_tag_field())
+        .with_field(tr!("OVS options"), ovs_options_field())
         .with_right_field(
             tr!("Comment"),
             Field::new().name("comments").submit_empty(true),
         )
+        .with_advanced_spacer()
+        .with_advanced_field(tr!("MTU"), mtu_field(None))
+        .into()
+}
+
+fn render_ovs_int_port_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
+    let is_edit = props.name.is_some();
+
+    InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_field(
+            tr!("Name"),
+            Field::new()
+                .name("name")
+                .default(&props.default_name)
+                .required(true)
+                .disabled(is_edit)
+                .submit(!is_edit),
+        )
+        .with_right_field(
+            tr!("Autostart"),
+            Checkbox::new().name("autostart").default(true),
+        )
+        .with_field(tr!("OVS Bridge"), ovs_bridge_field())
+        .with_right_field(tr!("VLAN Tag"), ovs_tag_field())
+        .with_field(
+            tr!("IPv4/CIDR"),
+            Field::new().name("cidr").schema(&CIDR_V4_SCHEMA),
+        )
+        .with_right_field(tr!("OVS options"), ovs_options_field())
         .with_field(
             tr!("Gateway") + " (IPv4)",
             Field::new().name("gateway").schema(&IP_V4_SCHEMA),
         )
+        .with_right_field(
+            tr!("Comment"),
+            Field::new().name("comments").submit_empty(true),
+        )
         .with_field(
             tr!("IPv6/CIDR"),
             Field::new().name("cidr6").schema(&CIDR_V6_SCHEMA),
@@ -309,23 +640,35 @@ fn render_common_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
             Field::new().name("gateway6").schema(&IP_V6_SCHEMA),
         )
         .with_advanced_spacer()
-        .with_advanced_field(
-            tr!("MTU"),
-            Number::new().min(1).name("mtu").placeholder("1500"),
-        )
+        .with_advanced_field(tr!("MTU"), mtu_field(None))
         .into()
 }
 
-fn render_input_form(form_ctx: FormContext, props: &NetworkEdit) -> Html {
+fn render_input_form(
+    form_ctx: FormContext,
+    props: &NetworkEdit,
+    mtus: &HashMap<String, u64>,
+) -> Html {
     match props.interface_type {
-        NetworkInterfaceType::Bridge => render_bridge_form(form_ctx, props),
-        NetworkInterfaceType::Bond => render_bond_form(form_ctx, props),
-        _ => render_common_form(form_ctx, props),
+        NetworkInterfaceKind::Linux(NetworkInterfaceType::Bridge) => {
+            render_bridge_form(form_ctx, props, mtus)
+        }
+        NetworkInterfaceKind::Linux(NetworkInterfaceType::Bond) => {
+            render_bond_form(form_ctx, props)
+        }
+        NetworkInterfaceKind::Ovs(OvsInterfaceType::Bridge) => {
+            render_ovs_bridge_form(form_ctx, props)
+        }
+        NetworkInterfaceKind::Ovs(OvsInterfaceType::Bond) => render_ovs_bond_form(form_ctx, props),
+        NetworkInterfaceKind::Ovs(OvsInterfaceType::IntPort) => {
+            render_ovs_int_port_form(form_ctx, props)
+        }
+        _ => render_common_form(form_ctx, props, mtus),
     }
 }
 
 impl Component for ProxmoxNetworkEdit {
-    type Message = ();
+    type Message = HashMap<String, u64>;
     type Properties = NetworkEdit;
 
     fn create(ctx: &Context<Self>) -> Self {
@@ -336,7 +679,26 @@ impl Component for ProxmoxNetworkEdit {
             ApiLoadCallback::new(move || load_item(name.clone()))
         });
 
-        Self { loader }
+        // the configured MTUs are used to validate the MTU against the lower interfaces
+        let async_pool = AsyncPool::new();
+        let link = ctx.link().clone();
+        async_pool.spawn(async move {
+            match load_mtus().await {
+                Ok(mtus) => link.send_message(mtus),
+                Err(err) => log::error!("loading network interfaces failed: {err}"),
+            }
+        });
+
+        Self {
+            loader,
+            mtus: Rc::new(HashMap::new()),
+            _async_pool: async_pool,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, mtus: Self::Message) -> bool {
+        self.mtus = Rc::new(mtus);
+        true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -347,22 +709,31 @@ impl Component for ProxmoxNetworkEdit {
         let action = if is_edit { tr!("Edit") } else { tr!("Create") };
 
         let interface_type = props.interface_type;
+        let product = props.product;
         let on_submit = move |form_context| async move {
             if is_edit {
-                update_item(form_context).await
+                update_item(form_context, interface_type, product).await
             } else {
                 create_item(form_context, interface_type).await
             }
         };
 
-        let interface_type = format_network_interface_type(props.interface_type);
+        let on_change = matches!(
+            props.interface_type,
+            NetworkInterfaceKind::Linux(NetworkInterfaceType::Bond)
+        )
+        .then(|| Callback::from(clear_unused_bond_options));
+
+        let interface_type = format_network_interface_kind(props.interface_type);
 
         EditWindow::new(action + ": " + &interface_type)
             .advanced_checkbox(true)
+            .on_change(on_change)
             .loader(self.loader.clone())
             .renderer({
                 let props = props.clone();
-                move |form_ctx: &FormContext| render_input_form(form_ctx.clone(), &props)
+                let mtus = Rc::clone(&self.mtus);
+                move |form_ctx: &FormContext| render_input_form(form_ctx.clone(), &props, &mtus)
             })
             .on_done(props.on_close.clone())
             .on_submit(on_submit)
@@ -376,3 +747,29 @@ impl From<NetworkEdit> for VNode {
         VNode::from(comp)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verify_vlan_id_list() {
+        assert!(verify_vlan_id_list("2-4094").is_ok());
+        assert!(verify_vlan_id_list("2 10-20,100").is_ok());
+        assert!(verify_vlan_id_list("1").is_err());
+        assert!(verify_vlan_id_list("20-10").is_err());
+        assert!(verify_vlan_id_list("10-abc").is_err());
+    }
+
+    #[test]
+    fn test_mtu_limit() {
+        let mtus = HashMap::from([("eno1".to_string(), 9000), ("eno2".to_string(), 1500)]);
+
+        assert_eq!(mtu_limit(&mtus, vlan_parent("eno1.50")), Some(9000));
+        assert_eq!(
+            mtu_limit(&mtus, "eno1 eno2 eno3".split_whitespace()),
+            Some(1500)
+        );
+        assert_eq!(mtu_limit(&mtus, vlan_parent("vmbr0")), None);
+    }
+}
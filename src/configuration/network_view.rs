@@ -1,32 +1,93 @@
 use std::future::Future;
+use std::ops::Deref;
 use std::pin::Pin;
 use std::rc::Rc;
 
 use anyhow::Error;
+use serde_json::Value;
 
+use yew::html::IntoPropValue;
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::state::{Selection, Store};
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
 use pwt::widget::menu::{Menu, MenuButton, MenuItem};
-use pwt::widget::{Button, Column, Container, SplitPane, Toolbar};
+use pwt::widget::{Button, Column, Container, Dialog, SplitPane, Toolbar};
 
 use crate::{
-    LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    ExistingProduct, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
     LoadableComponentScopeExt, LoadableComponentState, TaskProgress,
 };
 use proxmox_client::ApiResponseData;
 
+use pwt_macros::builder;
+
 use crate::percent_encoding::percent_encode_component;
 use proxmox_network_api::{BondXmitHashPolicy, Interface, LinuxBondMode, NetworkInterfaceType};
 
-use super::{format_network_interface_type, NetworkEdit};
+use super::network_edit::supports_ovs;
+use super::{format_network_interface_kind, NetworkEdit, NetworkInterfaceKind, OvsInterfaceType};
+
+/// Interface with the Open vSwitch specific properties, which [Interface] does not cover.
+#[derive(Clone)]
+struct NetworkInterface {
+    kind: NetworkInterfaceKind,
+    interface: Interface,
+    /// Ports of an OVS bridge, or slaves of an OVS bond.
+    ovs_ports: Option<String>,
+    ovs_bond_mode: Option<String>,
+}
+
+impl Deref for NetworkInterface {
+    type Target = Interface;
+
+    fn deref(&self) -> &Self::Target {
+        &self.interface
+    }
+}
+
+fn parse_interface(mut data: Value) -> Result<NetworkInterface, Error> {
+    let ovs_type: Option<OvsInterfaceType> = serde_json::from_value(data["type"].clone()).ok();
+
+    let mut ovs_bond_mode = None;
+    if ovs_type.is_some() {
+        // OVS types and bond modes are unknown to the Linux interface types
+        data["type"] = serde_json::to_value(NetworkInterfaceType::Unknown)?;
+        if let Some(map) = data.as_object_mut() {
+            ovs_bond_mode = map
+                .remove("bond_mode")
+                .and_then(|mode| mode.as_str().map(String::from));
+        }
+    }
+
+    let ovs_ports = data["ovs_ports"]
+        .as_str()
+        .or_else(|| data["ovs_bonds"].as_str())
+        .map(String::from);
+
+    let interface: Interface = serde_json::from_value(data)?;
+    let kind = match ovs_type {
+        Some(ovs_type) => ovs_type.into(),
+        None => interface.interface_type.into(),
+    };
 
-async fn load_interfaces() -> Result<(Vec<Interface>, String), Error> {
-    let resp: ApiResponseData<Vec<Interface>> =
+    Ok(NetworkInterface {
+        kind,
+        interface,
+        ovs_ports,
+        ovs_bond_mode,
+    })
+}
+
+async fn load_interfaces() -> Result<(Vec<NetworkInterface>, String), Error> {
+    let resp: ApiResponseData<Vec<Value>> =
         crate::http_get_full("/nodes/localhost/network", None).await?;
-    let data = resp.data;
+    let data = resp
+        .data
+        .into_iter()
+        .map(parse_interface)
+        .collect::<Result<Vec<_>, Error>>()?;
     let changes = resp
         .attribs
         .get("changes")
@@ -53,7 +114,19 @@ async fn apply_changes() -> Result<String, Error> {
 }
 
 #[derive(PartialEq, Properties)]
-pub struct NetworkView {}
+#[builder]
+pub struct NetworkView {
+    /// Read-only view - hide all buttons/menus to edit content.
+    #[builder]
+    #[prop_or_default]
+    pub readonly: bool,
+
+    /// The Product, used to show product specific interface options (for example Open
+    /// vSwitch interfaces on Proxmox VE).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub product: Option<ExistingProduct>,
+}
 
 impl Default for NetworkView {
     fn default() -> Self {
@@ -63,15 +136,15 @@ impl Default for NetworkView {
 
 impl NetworkView {
     pub fn new() -> Self {
-        Self {}
+        yew::props!(Self {})
     }
 }
 
 #[doc(hidden)]
 pub struct ProxmoxNetworkView {
     state: LoadableComponentState<ViewState>,
-    columns: Rc<Vec<DataTableHeader<Interface>>>,
-    store: Store<Interface>,
+    columns: Rc<Vec<DataTableHeader<NetworkInterface>>>,
+    store: Store<NetworkInterface>,
     changes: String,
     selection: Selection,
 }
@@ -82,7 +155,9 @@ pwt::impl_deref_mut_property!(ProxmoxNetworkView, state, LoadableComponentState<
 pub enum ViewState {
     AddBridge,
     AddBond,
+    AddOvs(OvsInterfaceType),
     Edit,
+    ConfirmApply(String),
     ApplyChanges(String),
 }
 
@@ -90,11 +165,12 @@ pub enum Msg {
     RemoveItem,
     Changes(String),
     RevertChanges,
+    ConfirmApply,
     ApplyChanges,
 }
 
 impl ProxmoxNetworkView {
-    fn get_selected_record(&self) -> Option<Interface> {
+    fn get_selected_record(&self) -> Option<NetworkInterface> {
         let selected_key = self.selection.selected_key();
         let mut selected_record = None;
         if let Some(key) = &selected_key {
@@ -104,7 +180,7 @@ impl ProxmoxNetworkView {
     }
 }
 
-fn find_next_free_interface_id(prefix: &str, list: &[Interface]) -> Option<String> {
+fn find_next_free_interface_id(prefix: &str, list: &[NetworkInterface]) -> Option<String> {
     for next in 0..9999 {
         let id = format!("{prefix}{next}");
         if !list.iter().any(|item| item.name == id) {
@@ -134,7 +210,8 @@ impl LoadableComponent for ProxmoxNetworkView {
     }
 
     fn create(ctx: &LoadableComponentContext<Self>) -> Self {
-        let store = Store::with_extract_key(|record: &Interface| Key::from(record.name.as_str()));
+        let store =
+            Store::with_extract_key(|record: &NetworkInterface| Key::from(record.name.as_str()));
         let selection = Selection::new().on_select({
             let link = ctx.link().clone();
             move |_| link.send_redraw()
@@ -176,6 +253,20 @@ impl LoadableComponent for ProxmoxNetworkView {
                 });
                 false
             }
+            Msg::ConfirmApply => {
+                // show the current state of the pending changes before applying them
+                let link = ctx.link().clone();
+                link.clone().spawn(async move {
+                    match load_interfaces().await {
+                        Err(err) => link.show_error(tr!("Unable to load changes"), err, true),
+                        Ok((_, changes)) => {
+                            link.send_message(Msg::Changes(changes.clone()));
+                            link.change_view(Some(ViewState::ConfirmApply(changes)));
+                        }
+                    }
+                });
+                false
+            }
             Msg::ApplyChanges => {
                 let link = ctx.link().clone();
                 link.clone().spawn(async move {
@@ -197,11 +288,26 @@ impl LoadableComponent for ProxmoxNetworkView {
     fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
         let link = ctx.link();
 
+        let refresh_button = {
+            let loading = self.loading();
+            let link = ctx.link().clone();
+            Button::refresh(loading).onclick(move |_| link.send_reload())
+        };
+
+        if ctx.props().readonly {
+            let toolbar = Toolbar::new()
+                .class("pwt-overflow-hidden")
+                .class("pwt-border-bottom")
+                .with_flex_spacer()
+                .with_child(refresh_button);
+            return Some(toolbar.into());
+        }
+
         let disabled = self.selection.is_empty();
 
         let no_changes = self.changes.is_empty();
 
-        let add_menu = Menu::new()
+        let mut add_menu = Menu::new()
             .with_item(
                 MenuItem::new(tr!("Linux Bridge")).on_select(
                     ctx.link()
@@ -215,6 +321,21 @@ impl LoadableComponent for ProxmoxNetworkView {
                 ),
             );
 
+        if supports_ovs(ctx.props().product) {
+            for ovs_type in [
+                OvsInterfaceType::Bridge,
+                OvsInterfaceType::Bond,
+                OvsInterfaceType::IntPort,
+            ] {
+                add_menu.add_item(
+                    MenuItem::new(format_network_interface_kind(ovs_type.into())).on_select(
+                        ctx.link()
+                            .change_view_callback(move |_| Some(ViewState::AddOvs(ovs_type))),
+                    ),
+                );
+            }
+        }
+
         let toolbar = Toolbar::new()
             .class("pwt-overflow-hidden")
             .class("pwt-border-bottom")
@@ -243,28 +364,26 @@ impl LoadableComponent for ProxmoxNetworkView {
             .with_child(
                 Button::new(tr!("Apply Configuration"))
                     .disabled(no_changes)
-                    .onclick(link.callback(|_| Msg::ApplyChanges)),
+                    .onclick(link.callback(|_| Msg::ConfirmApply)),
             )
             .with_flex_spacer()
-            .with_child({
-                let loading = self.loading();
-                let link = ctx.link().clone();
-                Button::refresh(loading).onclick(move |_| link.send_reload())
-            });
+            .with_child(refresh_button);
 
         Some(toolbar.into())
     }
 
     fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        let link = ctx.link().clone();
-
-        let table = DataTable::new(Rc::clone(&self.columns), self.store.clone())
+        let mut table = DataTable::new(Rc::clone(&self.columns), self.store.clone())
             .class("pwt-flex-fit")
             .selection(self.selection.clone())
-            .striped(true)
-            .on_row_dblclick(move |_: &mut _| {
+            .striped(true);
+
+        if !ctx.props().readonly {
+            let link = ctx.link().clone();
+            table = table.on_row_dblclick(move |_: &mut _| {
                 link.change_view(Some(ViewState::Edit));
             });
+        }
 
         let changes = (!self.changes.is_empty()).then(|| {
             Column::new()
@@ -305,6 +424,7 @@ impl LoadableComponent for ProxmoxNetworkView {
         match view_state {
             ViewState::AddBridge => Some(
                 NetworkEdit::new(NetworkInterfaceType::Bridge)
+                    .product(ctx.props().product)
                     .default_name(find_next_free_interface_id("vmbr", list))
                     .on_close(ctx.link().change_view_callback(|_| None))
                     .into(),
@@ -315,15 +435,69 @@ impl LoadableComponent for ProxmoxNetworkView {
                     .on_close(ctx.link().change_view_callback(|_| None))
                     .into(),
             ),
+            ViewState::AddOvs(ovs_type) => {
+                let prefix = match ovs_type {
+                    OvsInterfaceType::Bridge => Some("vmbr"),
+                    OvsInterfaceType::Bond => Some("bond"),
+                    OvsInterfaceType::IntPort | OvsInterfaceType::Port => None,
+                };
+                Some(
+                    NetworkEdit::new(*ovs_type)
+                        .default_name(
+                            prefix.and_then(|prefix| find_next_free_interface_id(prefix, list)),
+                        )
+                        .on_close(ctx.link().change_view_callback(|_| None))
+                        .into(),
+                )
+            }
             ViewState::Edit => match self.get_selected_record() {
                 None => None,
                 Some(record) => Some(
-                    NetworkEdit::new(record.interface_type)
+                    NetworkEdit::new(record.kind)
+                        .product(ctx.props().product)
                         .name(AttrValue::from(record.name.clone()))
                         .on_close(ctx.link().change_view_callback(|_| None))
                         .into(),
                 ),
             },
+            ViewState::ConfirmApply(changes) => Some(
+                Dialog::new(tr!("Apply Configuration"))
+                    .min_width(600)
+                    .on_close(ctx.link().change_view_callback(|_| None))
+                    .with_child(
+                        Container::new()
+                            .padding(2)
+                            .class("pwt-border-bottom pwt-font-size-body-medium")
+                            .with_child(if changes.is_empty() {
+                                tr!("There are no pending changes.")
+                            } else {
+                                tr!("The following changes will be activated (needs ifupdown2).")
+                            }),
+                    )
+                    .with_child(
+                        Container::from_tag("pre")
+                            .padding(2)
+                            .class("pwt-font-monospace pwt-font-size-body-medium pwt-line-height-body-medium")
+                            .style("max-height", "60vh")
+                            .class("pwt-overflow-auto")
+                            .with_child(changes),
+                    )
+                    .with_child(
+                        Toolbar::new()
+                            .class("pwt-border-top")
+                            .with_flex_spacer()
+                            .with_child(
+                                Button::new(tr!("Cancel"))
+                                    .onclick(ctx.link().change_view_callback(|_| None)),
+                            )
+                            .with_child(
+                                Button::new(tr!("Apply"))
+                                    .disabled(changes.is_empty())
+                                    .onclick(ctx.link().callback(|_| Msg::ApplyChanges)),
+                            ),
+                    )
+                    .into(),
+            ),
             ViewState::ApplyChanges(task_id) => Some(
                 TaskProgress::new(task_id)
                     .on_close(ctx.link().change_view_callback(|_| None))
@@ -340,7 +514,10 @@ impl From<NetworkView> for VNode {
     }
 }
 
-fn format_ports_slaves(interface: &Interface) -> String {
+fn format_ports_slaves(interface: &NetworkInterface) -> String {
+    if let Some(ports) = &interface.ovs_ports {
+        return ports.clone();
+    }
     match interface.interface_type {
         NetworkInterfaceType::Bridge => interface
             .bridge_ports
@@ -399,25 +576,25 @@ fn render_two_lines(
     }
 }
 
-fn columns() -> Rc<Vec<DataTableHeader<Interface>>> {
+fn columns() -> Rc<Vec<DataTableHeader<NetworkInterface>>> {
     Rc::new(vec![
         DataTableColumn::new(tr!("Name"))
             .width("120px")
-            .render(|item: &Interface| html!{
+            .render(|item: &NetworkInterface| html!{
                 item.name.clone()
             })
-            .sorter(|a: &Interface, b: &Interface| {
+            .sorter(|a: &NetworkInterface, b: &NetworkInterface| {
                 a.name.cmp(&b.name)
             })
             .into(),
         DataTableColumn::new(tr!("Type"))
             .width("120px")
-            .render(|item: &Interface| html!{
-                format_network_interface_type(item.interface_type)
+            .render(|item: &NetworkInterface| html!{
+                format_network_interface_kind(item.kind)
             })
-            .sorter(|a: &Interface, b: &Interface| {
-                let a =  format_network_interface_type(a.interface_type);
-                let b =  format_network_interface_type(b.interface_type);
+            .sorter(|a: &NetworkInterface, b: &NetworkInterface| {
+                let a =  format_network_interface_kind(a.kind);
+                let b =  format_network_interface_kind(b.kind);
                 a.cmp(&b)
             })
             .into(),
@@ -427,14 +604,14 @@ fn columns() -> Rc<Vec<DataTableHeader<Interface>>> {
                 let yes_text = tr!("Yes");
                 let no_text = tr!("No");
 
-                move |item: &Interface| html!{{
+                move |item: &NetworkInterface| html!{{
                     match item.active {
                         true => &yes_text,
                         false => &no_text,
                     }
                 }}
             })
-            .sorter(|a: &Interface, b: &Interface| {
+            .sorter(|a: &NetworkInterface, b: &NetworkInterface| {
                 a.active.cmp(&b.active)
             })
             .into(),
@@ -444,14 +621,14 @@ fn columns() -> Rc<Vec<DataTableHeader<Interface>>> {
                 let yes_text = tr!("Yes");
                 let no_text = tr!("No");
 
-                move |item: &Interface| html!{{
+                move |item: &NetworkInterface| html!{{
                     match item.autostart {
                         true => &yes_text,
                         false => &no_text,
                     }
                 }}
             })
-            .sorter(|a: &Interface, b: &Interface| {
+            .sorter(|a: &NetworkInterface, b: &NetworkInterface| {
                 a.autostart.cmp(&b.autostart)
             })
             .into(),
@@ -462,47 +639,52 @@ fn columns() -> Rc<Vec<DataTableHeader<Interface>>> {
                 let yes_text = tr!("Yes");
                 let no_text = tr!("No");
 
-                move |item: &Interface| html!{{
+                move |item: &NetworkInterface| html!{{
                     match item.bridge_vlan_aware {
                         Some(true) => &yes_text,
                         _ => &no_text,
                     }
                 }}
             })
-            .sorter(|a: &Interface, b: &Interface| {
+            .sorter(|a: &NetworkInterface, b: &NetworkInterface| {
                 a.bridge_vlan_aware.cmp(&b.bridge_vlan_aware)
             })
             .into(),
 
         DataTableColumn::new(tr!("Ports/Slaves"))
             .width("120px")
-            .render(move |item: &Interface| html!{format_ports_slaves(item)})
+            .render(move |item: &NetworkInterface| html!{format_ports_slaves(item)})
             .into(),
         DataTableColumn::new(tr!("Bond Mode"))
             .width("120px")
-            .render(move |item: &Interface| html!{format_bond_mode(item.bond_mode)})
+            .render(move |item: &NetworkInterface| html!{
+                match &item.ovs_bond_mode {
+                    Some(mode) => mode.clone(),
+                    None => format_bond_mode(item.bond_mode),
+                }
+            })
             .into(),
         DataTableColumn::new(tr!("Hash policy"))
             .width("120px")
             .hidden(true)
-            .render(move |item: &Interface| html!{format_bond_xmit_hash_policy(item.bond_xmit_hash_policy)})
+            .render(move |item: &NetworkInterface| html!{format_bond_xmit_hash_policy(item.bond_xmit_hash_policy)})
             .into(),
         DataTableColumn::new(tr!("CIDR"))
             .width("150px")
-            .render(move |item: &Interface| {
+            .render(move |item: &NetworkInterface| {
                 render_two_lines(item.cidr.as_ref(), item.cidr6.as_ref())
             })
             .into(),
         DataTableColumn::new(tr!("Gateway"))
             .width("150px")
-            .render(move |item: &Interface| {
+            .render(move |item: &NetworkInterface| {
                 render_two_lines(item.gateway.as_ref(), item.gateway6.as_ref())
             })
             .into(),
         DataTableColumn::new(tr!("MTU"))
             .width("100px")
             .hidden(true)
-            .render(move |item: &Interface| {
+            .render(move |item: &NetworkInterface| {
                 let text = match item.mtu {
                     Some(mtu) => mtu.to_string(),
                     None => String::new(),
@@ -512,7 +694,7 @@ fn columns() -> Rc<Vec<DataTableHeader<Interface>>> {
             .into(),
         DataTableColumn::new("Comment")
             .flex(1)
-            .render(|item: &Interface| html!{
+            .render(|item: &NetworkInterface| html!{
                 item.comments.clone().unwrap_or_default()
             })
             .into()
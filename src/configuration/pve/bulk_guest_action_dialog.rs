@@ -0,0 +1,812 @@
+use std::collections::{BTreeMap, HashSet};
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde_json::{json, Value};
+
+use pve_api_types::{ClusterResource, ClusterResourceKind, ClusterResourceType};
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::css::{AlignItems, FlexFit};
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Combobox, Field, Form, FormContext, Number, RadioButton};
+use pwt::widget::{Button, Column, Dialog, Fa, InputPanel, Progress, Row, Toolbar};
+use pwt::AsyncPool;
+
+use pwt_macros::builder;
+
+use super::bulk_snapshot_dialog::{
+    guest_row, guest_title, guest_type, task_status_url, MAX_POLL_ERRORS,
+};
+use crate::configuration::{guest_migrate_url, guest_status_command_url};
+use crate::form::pve::PveGuestType;
+use crate::percent_encoding::percent_encode_component;
+use crate::Status;
+
+const MODE: &str = "_mode";
+const FILTER: &str = "_filter";
+const DEFAULT_MAX_WORKERS: u64 = 4;
+
+/// Action executed by the [BulkGuestActionDialog].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkGuestAction {
+    Start,
+    Shutdown,
+    Suspend,
+    Migrate,
+}
+
+impl BulkGuestAction {
+    fn title(&self) -> String {
+        match self {
+            Self::Start => tr!("Bulk Start"),
+            Self::Shutdown => tr!("Bulk Shutdown"),
+            Self::Suspend => tr!("Bulk Suspend"),
+            Self::Migrate => tr!("Bulk Migrate"),
+        }
+    }
+
+    fn submit_text(&self) -> String {
+        match self {
+            Self::Start => tr!("Start"),
+            Self::Shutdown => tr!("Shutdown"),
+            Self::Suspend => tr!("Suspend"),
+            Self::Migrate => tr!("Migrate"),
+        }
+    }
+
+    /// Returns if the action can be done on the guest in its current state.
+    fn applies_to(&self, guest: &ClusterResource) -> bool {
+        let running = guest.status.as_deref() == Some("running");
+        let template = guest.template == Some(true);
+        match self {
+            Self::Start => !running && !template,
+            Self::Shutdown => running,
+            // only supported for virtual machines
+            Self::Suspend => running && guest.ty == ClusterResourceType::Qemu,
+            Self::Migrate => true,
+        }
+    }
+
+    /// The command of the node `bulk` API endpoint.
+    fn node_command(&self) -> &'static str {
+        match self {
+            Self::Start => "startall",
+            Self::Shutdown => "stopall",
+            Self::Suspend => "suspendall",
+            Self::Migrate => "migrateall",
+        }
+    }
+
+    /// The command of the guest `status` API endpoint.
+    fn guest_command(&self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Shutdown => "shutdown",
+            Self::Suspend => "suspend",
+            Self::Migrate => "migrate",
+        }
+    }
+}
+
+/// Start, shutdown, suspend or migrate several guests at once.
+///
+/// The dialog lists all guests the action can be done on, which can be filtered and
+/// selected. The actions are either executed per guest with a limited number of parallel
+/// jobs, or by the `bulk` endpoints of the nodes, which respect the configured startup
+/// order. After submitting, the spawned tasks are tracked and the overall progress is
+/// shown.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct BulkGuestActionDialog {
+    /// The action to execute.
+    pub action: BulkGuestAction,
+
+    /// Only list the guests of this node.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+
+    /// Use Proxmox Datacenter Manager API endpoints
+    ///
+    /// There are no `bulk` endpoints, so all actions are executed per guest.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub remote: Option<AttrValue>,
+
+    /// Close callback.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl BulkGuestActionDialog {
+    pub fn new(action: BulkGuestAction) -> Self {
+        yew::props!(Self { action })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum TaskState {
+    Queued,
+    Starting,
+    Running(String),
+    Finished(Result<(), String>),
+}
+
+/// A task spawned for a single guest, or for all guests of a node (`bulk` endpoints).
+#[derive(Clone, PartialEq)]
+struct ActionTask {
+    node: String,
+    guests: Vec<ClusterResource>,
+    bulk: bool,
+    state: TaskState,
+    poll_errors: usize,
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    FormUpdate,
+    Loaded(Result<Vec<ClusterResource>, Error>),
+    NodesLoaded(Result<Vec<AttrValue>, Error>),
+    Select(u32, bool),
+    SelectAll(bool),
+    Submit,
+    TaskStarted(usize, Result<String, Error>),
+    TaskStatus(usize, Result<Value, Error>),
+    Poll,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxBulkGuestActionDialog {
+    form_ctx: FormContext,
+    guests: Option<Result<Vec<ClusterResource>, String>>,
+    // online nodes, the possible migration targets
+    nodes: Rc<Vec<AttrValue>>,
+    selection: HashSet<u32>,
+    tasks: Option<Vec<ActionTask>>,
+    max_workers: usize,
+    async_pool: AsyncPool,
+    poll_timeout: Option<Timeout>,
+}
+
+fn matches_filter(guest: &ClusterResource, filter: &str) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+    let filter = filter.to_lowercase();
+    guest_title(guest).to_lowercase().contains(&filter)
+        || guest
+            .node
+            .as_deref()
+            .is_some_and(|node| node.to_lowercase().contains(&filter))
+}
+
+impl ProxmoxBulkGuestActionDialog {
+    async fn load_resources(
+        remote: &Option<AttrValue>,
+        kind: ClusterResourceKind,
+    ) -> Result<Vec<ClusterResource>, Error> {
+        let (url, param) = match remote {
+            Some(remote) => (
+                format!(
+                    "/pve/remotes/{}/resources",
+                    percent_encode_component(remote),
+                ),
+                json!({ "kind": kind }),
+            ),
+            None => ("/cluster/resources".to_string(), json!({ "type": kind })),
+        };
+        crate::http_get(url, Some(param)).await
+    }
+
+    async fn load_guests(
+        remote: Option<AttrValue>,
+        node: Option<AttrValue>,
+        action: BulkGuestAction,
+    ) -> Result<Vec<ClusterResource>, Error> {
+        let mut guests = Self::load_resources(&remote, ClusterResourceKind::Vm).await?;
+        guests.retain(|guest| {
+            guest.vmid.is_some()
+                && guest_type(guest).is_some()
+                && action.applies_to(guest)
+                && node
+                    .as_ref()
+                    .is_none_or(|node| guest.node.as_deref() == Some(node.as_str()))
+        });
+        guests.sort_by_key(|guest| guest.vmid);
+        Ok(guests)
+    }
+
+    async fn load_nodes(remote: Option<AttrValue>) -> Result<Vec<AttrValue>, Error> {
+        let resources = Self::load_resources(&remote, ClusterResourceKind::Node).await?;
+        let mut nodes: Vec<AttrValue> = resources
+            .into_iter()
+            .filter(|resource| resource.status.as_deref() == Some("online"))
+            .filter_map(|resource| resource.node.map(AttrValue::from))
+            .collect();
+        nodes.sort();
+        Ok(nodes)
+    }
+
+    fn ordered(&self, ctx: &Context<Self>) -> bool {
+        ctx.props().remote.is_none() && self.form_ctx.read().get_field_text(MODE) == "ordered"
+    }
+
+    fn visible_guests(&self) -> Vec<&ClusterResource> {
+        let filter = self.form_ctx.read().get_field_text(FILTER);
+        match &self.guests {
+            Some(Ok(guests)) => guests
+                .iter()
+                .filter(|guest| matches_filter(guest, filter.trim()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn create_tasks(&self, ctx: &Context<Self>) -> Vec<ActionTask> {
+        let guests: Vec<ClusterResource> = match &self.guests {
+            Some(Ok(guests)) => guests
+                .iter()
+                .filter(|guest| {
+                    guest
+                        .vmid
+                        .is_some_and(|vmid| self.selection.contains(&vmid))
+                })
+                .cloned()
+                .collect(),
+            _ => return Vec::new(),
+        };
+
+        if self.ordered(ctx) {
+            let mut nodes: BTreeMap<String, Vec<ClusterResource>> = BTreeMap::new();
+            for guest in guests {
+                let node = guest.node.clone().unwrap_or_default();
+                nodes.entry(node).or_default().push(guest);
+            }
+            nodes
+                .into_iter()
+                .map(|(node, guests)| ActionTask {
+                    node,
+                    guests,
+                    bulk: true,
+                    state: TaskState::Queued,
+                    poll_errors: 0,
+                })
+                .collect()
+        } else {
+            guests
+                .into_iter()
+                .map(|guest| ActionTask {
+                    node: guest.node.clone().unwrap_or_default(),
+                    guests: vec![guest],
+                    bulk: false,
+                    state: TaskState::Queued,
+                    poll_errors: 0,
+                })
+                .collect()
+        }
+    }
+
+    fn submit(&mut self, ctx: &Context<Self>) {
+        self.max_workers = self
+            .form_ctx
+            .read()
+            .get_field_value("maxworkers")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_MAX_WORKERS)
+            .max(1) as usize;
+        self.tasks = Some(self.create_tasks(ctx));
+        self.start_queued(ctx);
+    }
+
+    /// Start queued tasks (in order), until the maximum number of parallel jobs is reached.
+    fn start_queued(&mut self, ctx: &Context<Self>) {
+        let Some(tasks) = &self.tasks else {
+            return;
+        };
+
+        let active = tasks
+            .iter()
+            .filter(|task| matches!(task.state, TaskState::Starting | TaskState::Running(_)))
+            .count();
+
+        let queued: Vec<usize> = tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| task.state == TaskState::Queued)
+            .map(|(index, _)| index)
+            .take(self.max_workers.saturating_sub(active))
+            .collect();
+
+        for index in queued {
+            self.start_task(ctx, index);
+        }
+    }
+
+    fn start_task(&mut self, ctx: &Context<Self>, index: usize) {
+        let props = ctx.props();
+        let action = props.action;
+        let Some(task) = self.tasks.as_mut().and_then(|tasks| tasks.get_mut(index)) else {
+            return;
+        };
+        task.state = TaskState::Starting;
+
+        let form = self.form_ctx.read();
+        let target = form.get_field_text("target");
+        let online = form.get_field_checked("online");
+
+        let (url, data) = if task.bulk {
+            let vms = task
+                .guests
+                .iter()
+                .filter_map(|guest| guest.vmid.map(|vmid| vmid.to_string()))
+                .collect::<Vec<_>>()
+                .join(",");
+            let mut data = json!({ "vms": vms });
+            match action {
+                // start the guests even if 'onboot' is not set
+                BulkGuestAction::Start => data["force"] = true.into(),
+                BulkGuestAction::Migrate => {
+                    data["target"] = target.into();
+                    data["maxworkers"] = self.max_workers.into();
+                }
+                BulkGuestAction::Shutdown | BulkGuestAction::Suspend => {}
+            }
+            let url = format!(
+                "/nodes/{}/{}",
+                percent_encode_component(&task.node),
+                action.node_command(),
+            );
+            (url, Some(data))
+        } else {
+            let guest = &task.guests[0];
+            let (Some(vmid), Some(guest_type)) = (guest.vmid, guest_type(guest)) else {
+                task.state = TaskState::Finished(Err(tr!("Not a guest")));
+                return;
+            };
+            let node = AttrValue::from(task.node.clone());
+            match action {
+                BulkGuestAction::Migrate => {
+                    let url = guest_migrate_url(vmid, &node, &props.remote, guest_type);
+                    let mut data = json!({ "target": target });
+                    if online && guest.status.as_deref() == Some("running") {
+                        match guest_type {
+                            PveGuestType::Qemu => data["online"] = true.into(),
+                            // containers cannot be live migrated
+                            PveGuestType::Lxc => data["restart"] = true.into(),
+                        }
+                    }
+                    (url, Some(data))
+                }
+                _ => {
+                    let url = guest_status_command_url(
+                        vmid,
+                        &node,
+                        &props.remote,
+                        guest_type,
+                        action.guest_command(),
+                    );
+                    (url, None)
+                }
+            }
+        };
+
+        let link = ctx.link().clone();
+        self.async_pool.spawn(async move {
+            let result = crate::http_post(url, data).await;
+            link.send_message(Msg::TaskStarted(index, result));
+        });
+    }
+
+    fn poll_tasks(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let Some(tasks) = &self.tasks else {
+            return;
+        };
+
+        for (index, task) in tasks.iter().enumerate() {
+            let TaskState::Running(upid) = &task.state else {
+                continue;
+            };
+            let url = task_status_url(&props.remote, &task.node, upid);
+            let link = ctx.link().clone();
+            self.async_pool.spawn(async move {
+                let result = crate::http_get(url, None).await;
+                link.send_message(Msg::TaskStatus(index, result));
+            });
+        }
+    }
+
+    fn schedule_poll(&mut self, ctx: &Context<Self>) {
+        let running = self
+            .tasks
+            .iter()
+            .flatten()
+            .any(|task| matches!(task.state, TaskState::Running(_)));
+        if running && self.poll_timeout.is_none() {
+            let link = ctx.link().clone();
+            self.poll_timeout = Some(Timeout::new(1_000, move || link.send_message(Msg::Poll)));
+        }
+    }
+
+    fn task_mut(&mut self, index: usize) -> Option<&mut ActionTask> {
+        self.tasks.as_mut().and_then(|tasks| tasks.get_mut(index))
+    }
+
+    fn set_task_state(&mut self, index: usize, state: TaskState) {
+        if let Some(task) = self.task_mut(index) {
+            task.state = state;
+        }
+    }
+
+    fn guest_list(&self, ctx: &Context<Self>) -> Html {
+        let guests = match &self.guests {
+            None => return html! {<div class="pwt-p-4">{tr!("Loading...")}</div>},
+            Some(Err(err)) => {
+                let msg = pwt::widget::error_message(&tr!("Loading guests failed: {0}", err));
+                return html! {<div class="pwt-p-4">{msg}</div>};
+            }
+            Some(Ok(_)) => self.visible_guests(),
+        };
+
+        if guests.is_empty() {
+            return html! {<div class="pwt-p-4">{tr!("No matching guests")}</div>};
+        }
+
+        let mut list = Column::new()
+            .class(FlexFit)
+            .class("pwt-overflow-auto")
+            .padding_x(4)
+            .gap(1)
+            .style("max-height", "400px");
+        for guest in guests {
+            let Some(vmid) = guest.vmid else {
+                continue;
+            };
+            let status = html! {
+                <span>{format!(
+                    "{} - {}",
+                    guest.node.as_deref().unwrap_or("-"),
+                    guest.status.as_deref().unwrap_or("unknown"),
+                )}</span>
+            };
+            list.add_child(
+                Row::new()
+                    .key(vmid.to_string())
+                    .class(AlignItems::Center)
+                    .gap(2)
+                    .with_child(
+                        Checkbox::new()
+                            .checked(self.selection.contains(&vmid))
+                            .on_input(
+                                ctx.link()
+                                    .callback(move |checked| Msg::Select(vmid, checked)),
+                            ),
+                    )
+                    .with_child(guest_row(guest, status).class(pwt::css::Flex::Fill)),
+            );
+        }
+        list.into()
+    }
+
+    fn edit_view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let ordered = self.ordered(ctx);
+
+        let mut panel = InputPanel::new().padding(4);
+
+        if props.remote.is_none() {
+            let mode_button = |value: &str, label: String, default: bool| {
+                RadioButton::new(value.to_string())
+                    .name(MODE)
+                    .key(format!("mode-{value}"))
+                    .default(default)
+                    .box_label(label)
+                    .submit(false)
+            };
+            panel = panel.with_custom_child(
+                Row::new()
+                    .key("mode")
+                    .gap(4)
+                    .with_child(mode_button("parallel", tr!("Parallel"), true))
+                    .with_child(mode_button(
+                        "ordered",
+                        tr!("Ordered (use startup order)"),
+                        false,
+                    )),
+            );
+        }
+
+        // the bulk endpoints only support a job limit for migrations
+        if !ordered || props.action == BulkGuestAction::Migrate {
+            panel = panel.with_field(
+                tr!("Parallel jobs"),
+                Number::<u64>::new()
+                    .name("maxworkers")
+                    .min(1)
+                    .max(64)
+                    .default(DEFAULT_MAX_WORKERS)
+                    .submit(false),
+            );
+        }
+
+        if props.action == BulkGuestAction::Migrate {
+            panel = panel
+                .with_field(
+                    tr!("Target node"),
+                    Combobox::new()
+                        .name("target")
+                        .items(self.nodes.clone())
+                        .required(true)
+                        .submit(false),
+                )
+                .with_field(
+                    tr!("Online"),
+                    Checkbox::new().name("online").default(true).submit(false),
+                );
+        }
+
+        panel = panel.with_large_field(
+            tr!("Filter"),
+            Field::new()
+                .name(FILTER)
+                .placeholder(tr!("Name, ID or node"))
+                .submit(false),
+        );
+
+        let visible = self.visible_guests();
+        let selected = visible
+            .iter()
+            .filter(|guest| {
+                guest
+                    .vmid
+                    .is_some_and(|vmid| self.selection.contains(&vmid))
+            })
+            .count();
+
+        let selection_bar = Toolbar::new()
+            .with_child(
+                Button::new(tr!("Select All"))
+                    .onclick(ctx.link().callback(|_| Msg::SelectAll(true))),
+            )
+            .with_child(
+                Button::new(tr!("Deselect All"))
+                    .onclick(ctx.link().callback(|_| Msg::SelectAll(false))),
+            );
+
+        let valid = self.form_ctx.read().is_valid();
+        let bbar = Toolbar::new()
+            .with_child(tr!("{0} of {1} guests selected", selected, visible.len()))
+            .with_flex_spacer()
+            .with_child(
+                Button::new(props.action.submit_text())
+                    .disabled(!valid || selected == 0)
+                    .onclick(ctx.link().callback(|_| Msg::Submit)),
+            );
+
+        Form::new()
+            .class(FlexFit)
+            .class("pwt-d-flex pwt-flex-direction-column")
+            .form_context(self.form_ctx.clone())
+            .with_child(panel)
+            .with_child(selection_bar)
+            .with_child(self.guest_list(ctx))
+            .with_child(bbar)
+            .into()
+    }
+
+    fn progress_view(&self, ctx: &Context<Self>, tasks: &[ActionTask]) -> Html {
+        let finished = tasks
+            .iter()
+            .filter(|task| matches!(task.state, TaskState::Finished(_)))
+            .count();
+
+        let mut list = Column::new()
+            .class("pwt-overflow-auto")
+            .padding(4)
+            .gap(1)
+            .style("max-height", "400px");
+        for task in tasks {
+            let (status, text) = match &task.state {
+                TaskState::Queued => (Status::Unknown, tr!("Queued")),
+                TaskState::Starting | TaskState::Running(_) => (Status::Unknown, tr!("running")),
+                TaskState::Finished(Ok(())) => (Status::Success, tr!("OK")),
+                TaskState::Finished(Err(err)) => (Status::Error, err.clone()),
+            };
+            let status: Html = Row::new()
+                .class(AlignItems::Center)
+                .gap(2)
+                .with_child(text)
+                .with_child(Fa::from(status).fixed_width())
+                .into();
+
+            let row = match (task.bulk, task.guests.first()) {
+                (false, Some(guest)) => guest_row(guest, status),
+                _ => Row::new()
+                    .class(AlignItems::Center)
+                    .gap(2)
+                    .with_child(Fa::new("server").fixed_width())
+                    .with_child(tr!("{0} ({1} guests)", task.node, task.guests.len()))
+                    .with_flex_spacer()
+                    .with_child(status),
+            };
+            list.add_child(row);
+        }
+
+        let progress = if tasks.is_empty() {
+            1.0
+        } else {
+            finished as f32 / tasks.len() as f32
+        };
+
+        Column::new()
+            .class(FlexFit)
+            .with_child(Progress::new().value(progress))
+            .with_child(list)
+            .with_child(
+                Toolbar::new()
+                    .with_child(tr!("{0} of {1} tasks finished", finished, tasks.len()))
+                    .with_flex_spacer()
+                    .with_child(Button::new(tr!("Close")).onclick({
+                        let on_close = ctx.props().on_close.clone();
+                        move |_| {
+                            if let Some(on_close) = &on_close {
+                                on_close.emit(());
+                            }
+                        }
+                    })),
+            )
+            .into()
+    }
+}
+
+impl Component for ProxmoxBulkGuestActionDialog {
+    type Message = Msg;
+    type Properties = BulkGuestActionDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let async_pool = AsyncPool::new();
+
+        let link = ctx.link().clone();
+        let remote = props.remote.clone();
+        let node = props.node.clone();
+        let action = props.action;
+        async_pool.spawn(async move {
+            let result = Self::load_guests(remote, node, action).await;
+            link.send_message(Msg::Loaded(result));
+        });
+
+        if action == BulkGuestAction::Migrate {
+            let link = ctx.link().clone();
+            let remote = props.remote.clone();
+            async_pool.spawn(async move {
+                let result = Self::load_nodes(remote).await;
+                link.send_message(Msg::NodesLoaded(result));
+            });
+        }
+
+        Self {
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormUpdate)),
+            guests: None,
+            nodes: Rc::new(Vec::new()),
+            selection: HashSet::new(),
+            tasks: None,
+            max_workers: DEFAULT_MAX_WORKERS as usize,
+            async_pool,
+            poll_timeout: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::FormUpdate => {}
+            Msg::Loaded(result) => {
+                self.guests = Some(result.map_err(|err| err.to_string()));
+                // select all guests by default
+                self.selection = match &self.guests {
+                    Some(Ok(guests)) => guests.iter().filter_map(|guest| guest.vmid).collect(),
+                    _ => HashSet::new(),
+                };
+            }
+            Msg::NodesLoaded(result) => match result {
+                Ok(nodes) => self.nodes = Rc::new(nodes),
+                Err(err) => log::error!("loading nodes failed: {err}"),
+            },
+            Msg::Select(vmid, checked) => {
+                if checked {
+                    self.selection.insert(vmid);
+                } else {
+                    self.selection.remove(&vmid);
+                }
+            }
+            Msg::SelectAll(checked) => {
+                let vmids: Vec<u32> = self
+                    .visible_guests()
+                    .iter()
+                    .filter_map(|guest| guest.vmid)
+                    .collect();
+                for vmid in vmids {
+                    if checked {
+                        self.selection.insert(vmid);
+                    } else {
+                        self.selection.remove(&vmid);
+                    }
+                }
+            }
+            Msg::Submit => self.submit(ctx),
+            Msg::TaskStarted(index, result) => {
+                match result {
+                    Ok(upid) => self.set_task_state(index, TaskState::Running(upid)),
+                    Err(err) => {
+                        self.set_task_state(index, TaskState::Finished(Err(err.to_string())));
+                        self.start_queued(ctx);
+                    }
+                }
+                self.schedule_poll(ctx);
+            }
+            Msg::TaskStatus(index, result) => {
+                match result {
+                    Ok(status) if status["status"].as_str() == Some("stopped") => {
+                        let state = match status["exitstatus"].as_str() {
+                            Some("OK") => Ok(()),
+                            Some(exit_status) => Err(exit_status.to_string()),
+                            None => Err(tr!("unknown")),
+                        };
+                        self.set_task_state(index, TaskState::Finished(state));
+                        self.start_queued(ctx);
+                    }
+                    Ok(_) => {
+                        if let Some(task) = self.task_mut(index) {
+                            task.poll_errors = 0;
+                        }
+                    }
+                    Err(err) => {
+                        log::error!("loading task status failed: {err}");
+                        let give_up = self.task_mut(index).is_some_and(|task| {
+                            task.poll_errors += 1;
+                            task.poll_errors >= MAX_POLL_ERRORS
+                        });
+                        if give_up {
+                            let msg = tr!("Unable to get task status: {0}", err);
+                            self.set_task_state(index, TaskState::Finished(Err(msg)));
+                            self.start_queued(ctx);
+                        }
+                    }
+                }
+                self.schedule_poll(ctx);
+            }
+            Msg::Poll => {
+                self.poll_timeout = None;
+                self.poll_tasks(ctx);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content = match &self.tasks {
+            None => self.edit_view(ctx),
+            Some(tasks) => self.progress_view(ctx, tasks),
+        };
+
+        Dialog::new(props.action.title())
+            .min_width(500)
+            .on_close(props.on_close.clone())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<BulkGuestActionDialog> for VNode {
+    fn from(val: BulkGuestActionDialog) -> Self {
+        let comp = VComp::new::<ProxmoxBulkGuestActionDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
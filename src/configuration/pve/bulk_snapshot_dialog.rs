@@ -0,0 +1,542 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+use gloo_timers::callback::Timeout;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use pve_api_types::{ClusterResource, ClusterResourceType};
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::css::{AlignItems, FlexFit, FontColor};
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Field, Form, FormContext, RadioButton, TextArea};
+use pwt::widget::{Button, Column, Dialog, Fa, InputPanel, Progress, Row, Toolbar};
+use pwt::AsyncPool;
+
+use pwt_macros::builder;
+
+use crate::configuration::guest_snapshot_url;
+use crate::form::pve::PveGuestType;
+use crate::percent_encoding::percent_encode_component;
+use crate::Status;
+
+const MODE: &str = "_mode";
+/// Give up tracking a task after this many failed status requests in a row.
+pub(super) const MAX_POLL_ERRORS: usize = 5;
+
+thread_local! {
+    static SNAPSHOT_NAME_MATCH: Regex = Regex::new(r#"^[a-zA-Z][a-zA-Z0-9_\-]{1,39}$"#).unwrap();
+}
+
+/// Create or delete a snapshot with the same name on several guests.
+///
+/// Before submitting, the dialog lists the guests which will be skipped, because they are
+/// templates, currently locked, or the user lacks the `VM.Snapshot` privilege. After
+/// submitting, the task of every guest is tracked and the overall progress is shown.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct BulkSnapshotDialog {
+    /// The guests to operate on.
+    pub guests: Rc<Vec<ClusterResource>>,
+
+    /// Use Proxmox Datacenter Manager API endpoints
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub remote: Option<AttrValue>,
+
+    /// Endpoint to query the privileges of the current user.
+    ///
+    /// Set to `None` to skip the privilege check.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(Some(AttrValue::Static("/access/permissions")))]
+    pub permissions_url: Option<AttrValue>,
+
+    /// Close callback.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl BulkSnapshotDialog {
+    pub fn new(guests: impl Into<Rc<Vec<ClusterResource>>>) -> Self {
+        yew::props!(Self {
+            guests: guests.into()
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SnapshotAction {
+    Create,
+    Delete,
+}
+
+#[derive(Clone, PartialEq)]
+enum GuestTaskState {
+    Queued,
+    Running(String),
+    Finished(Result<(), String>),
+}
+
+#[derive(Clone, PartialEq)]
+struct GuestTask {
+    guest: ClusterResource,
+    state: GuestTaskState,
+    poll_errors: usize,
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    FormUpdate,
+    Permissions(Value),
+    Submit,
+    TaskStarted(usize, Result<String, Error>),
+    TaskStatus(usize, Result<Value, Error>),
+    Poll,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxBulkSnapshotDialog {
+    form_ctx: FormContext,
+    permissions: Option<Value>,
+    tasks: Option<Vec<GuestTask>>,
+    async_pool: AsyncPool,
+    poll_timeout: Option<Timeout>,
+}
+
+pub(super) fn guest_type(guest: &ClusterResource) -> Option<PveGuestType> {
+    match guest.ty {
+        ClusterResourceType::Qemu => Some(PveGuestType::Qemu),
+        ClusterResourceType::Lxc => Some(PveGuestType::Lxc),
+        _ => None,
+    }
+}
+
+fn has_snapshot_privilege(permissions: &Value, guest: &ClusterResource, vmid: u32) -> bool {
+    // the privileges map to their propagate flag
+    if !permissions[&format!("/vms/{vmid}")]["VM.Snapshot"].is_null() {
+        return true;
+    }
+
+    // privileges on parent paths only apply if they propagate
+    let mut parents = Vec::new();
+    if let Some(pool) = &guest.pool {
+        parents.push(format!("/pool/{pool}"));
+    }
+    parents.push("/vms".into());
+    parents.push("/".into());
+
+    parents
+        .iter()
+        .any(|path| match &permissions[path]["VM.Snapshot"] {
+            Value::Bool(propagate) => *propagate,
+            value => value.as_u64() == Some(1),
+        })
+}
+
+/// Returns why the action cannot be done on the guest, if so.
+fn skip_reason(
+    guest: &ClusterResource,
+    action: SnapshotAction,
+    permissions: Option<&Value>,
+) -> Option<String> {
+    let (Some(vmid), Some(_node), Some(_)) = (guest.vmid, &guest.node, guest_type(guest)) else {
+        return Some(tr!("Not a guest"));
+    };
+    if action == SnapshotAction::Create && guest.template == Some(true) {
+        return Some(tr!("Template"));
+    }
+    if let Some(lock) = &guest.lock {
+        return Some(tr!("Locked ({0})", lock));
+    }
+    if let Some(permissions) = permissions {
+        if !has_snapshot_privilege(permissions, guest, vmid) {
+            return Some(tr!("Missing privilege '{0}'", "VM.Snapshot"));
+        }
+    }
+    None
+}
+
+pub(super) fn guest_title(guest: &ClusterResource) -> String {
+    match (&guest.vmid, &guest.name) {
+        (Some(vmid), Some(name)) => format!("{vmid} ({name})"),
+        (Some(vmid), None) => vmid.to_string(),
+        _ => guest.id.clone(),
+    }
+}
+
+pub(super) fn guest_row(guest: &ClusterResource, status: Html) -> Row {
+    let icon = match guest.ty {
+        ClusterResourceType::Qemu => "desktop",
+        ClusterResourceType::Lxc => "cube",
+        _ => "question",
+    };
+    Row::new()
+        .class(AlignItems::Center)
+        .gap(2)
+        .with_child(Fa::new(icon).fixed_width())
+        .with_child(guest_title(guest))
+        .with_flex_spacer()
+        .with_child(status)
+}
+
+pub(super) fn task_status_url(remote: &Option<AttrValue>, node: &str, upid: &str) -> String {
+    let upid = percent_encode_component(upid);
+    match remote {
+        Some(remote) => format!(
+            "/pve/remotes/{}/tasks/{upid}/status",
+            percent_encode_component(remote)
+        ),
+        None => format!(
+            "/nodes/{}/tasks/{upid}/status",
+            percent_encode_component(node)
+        ),
+    }
+}
+
+impl ProxmoxBulkSnapshotDialog {
+    fn action(&self) -> SnapshotAction {
+        match self.form_ctx.read().get_field_text(MODE).as_str() {
+            "delete" => SnapshotAction::Delete,
+            _ => SnapshotAction::Create,
+        }
+    }
+
+    fn start_tasks(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let action = self.action();
+        let snapname = self.form_ctx.read().get_field_text("snapname");
+        let description = self.form_ctx.read().get_field_text("description");
+        let vmstate = self.form_ctx.read().get_field_checked("vmstate");
+
+        let tasks: Vec<GuestTask> = props
+            .guests
+            .iter()
+            .filter(|guest| skip_reason(guest, action, self.permissions.as_ref()).is_none())
+            .map(|guest| GuestTask {
+                guest: guest.clone(),
+                state: GuestTaskState::Queued,
+                poll_errors: 0,
+            })
+            .collect();
+
+        for (index, task) in tasks.iter().enumerate() {
+            let guest = &task.guest;
+            let (Some(vmid), Some(node), Some(guest_type)) =
+                (guest.vmid, &guest.node, guest_type(guest))
+            else {
+                continue;
+            };
+            let url = guest_snapshot_url(
+                vmid,
+                &AttrValue::from(node.clone()),
+                &props.remote,
+                guest_type,
+            );
+
+            let link = ctx.link().clone();
+            let snapname = snapname.clone();
+            let description = description.clone();
+            self.async_pool.spawn(async move {
+                let result = match action {
+                    SnapshotAction::Create => {
+                        let mut data = json!({ "snapname": snapname });
+                        if !description.is_empty() {
+                            data["description"] = description.into();
+                        }
+                        if vmstate && guest_type == PveGuestType::Qemu {
+                            data["vmstate"] = true.into();
+                        }
+                        crate::http_post(url, Some(data)).await
+                    }
+                    SnapshotAction::Delete => {
+                        let url = format!("{url}/{}", percent_encode_component(&snapname));
+                        crate::http_delete_get(url, None).await
+                    }
+                };
+                link.send_message(Msg::TaskStarted(index, result));
+            });
+        }
+
+        self.tasks = Some(tasks);
+    }
+
+    fn poll_tasks(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let Some(tasks) = &self.tasks else {
+            return;
+        };
+
+        for (index, task) in tasks.iter().enumerate() {
+            let (GuestTaskState::Running(upid), Some(node)) = (&task.state, &task.guest.node)
+            else {
+                continue;
+            };
+            let url = task_status_url(&props.remote, node, upid);
+            let link = ctx.link().clone();
+            self.async_pool.spawn(async move {
+                let result = crate::http_get(url, None).await;
+                link.send_message(Msg::TaskStatus(index, result));
+            });
+        }
+    }
+
+    fn schedule_poll(&mut self, ctx: &Context<Self>) {
+        let running = self
+            .tasks
+            .iter()
+            .flatten()
+            .any(|task| matches!(task.state, GuestTaskState::Running(_)));
+        if running && self.poll_timeout.is_none() {
+            let link = ctx.link().clone();
+            self.poll_timeout = Some(Timeout::new(1_000, move || link.send_message(Msg::Poll)));
+        }
+    }
+
+    fn task_mut(&mut self, index: usize) -> Option<&mut GuestTask> {
+        self.tasks.as_mut().and_then(|tasks| tasks.get_mut(index))
+    }
+
+    fn set_task_state(&mut self, index: usize, state: GuestTaskState) {
+        if let Some(task) = self.task_mut(index) {
+            task.state = state;
+        }
+    }
+
+    fn edit_view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let action = self.action();
+
+        let mode_button = |value: &str, label: String, default: bool| {
+            RadioButton::new(value.to_string())
+                .name(MODE)
+                .key(format!("mode-{value}"))
+                .default(default)
+                .box_label(label)
+                .submit(false)
+        };
+
+        let mut panel = InputPanel::new()
+            .padding(4)
+            .with_custom_child(
+                Row::new()
+                    .key("mode")
+                    .gap(4)
+                    .with_child(mode_button("create", tr!("Create Snapshot"), true))
+                    .with_child(mode_button("delete", tr!("Delete Snapshot"), false)),
+            )
+            .with_field(
+                tr!("Name"),
+                Field::new()
+                    .name("snapname")
+                    .required(true)
+                    .autofocus(true)
+                    .validate(|value: &String| {
+                        if SNAPSHOT_NAME_MATCH.with(|r| r.is_match(value)) {
+                            return Ok(());
+                        }
+                        bail!(tr!("invalid snapshot name"))
+                    }),
+            );
+
+        if action == SnapshotAction::Create {
+            panel = panel
+                .with_field(tr!("Include RAM"), Checkbox::new().name("vmstate"))
+                .with_large_field(
+                    tr!("Description"),
+                    TextArea::new().name("description").attribute("rows", "3"),
+                );
+        }
+
+        let permissions = self.permissions.as_ref();
+        let mut eligible = 0;
+        let mut guest_list = Column::new().padding_x(4).gap(1);
+        for guest in props.guests.iter() {
+            let status = match skip_reason(guest, action, permissions) {
+                Some(reason) => {
+                    html! {<span class={FontColor::Warning}>{tr!("Skipped: {0}", reason)}</span>}
+                }
+                None => {
+                    eligible += 1;
+                    html! {}
+                }
+            };
+            guest_list.add_child(guest_row(guest, status));
+        }
+
+        let submit_text = match action {
+            SnapshotAction::Create => tr!("Create"),
+            SnapshotAction::Delete => tr!("Delete"),
+        };
+
+        let valid = self.form_ctx.read().is_valid();
+        let bbar = Toolbar::new()
+            .with_child(tr!("{0} of {1} guests", eligible, props.guests.len()))
+            .with_flex_spacer()
+            .with_child(
+                Button::new(submit_text)
+                    .disabled(!valid || eligible == 0)
+                    .onclick(ctx.link().callback(|_| Msg::Submit)),
+            );
+
+        Form::new()
+            .class(FlexFit)
+            .class("pwt-d-flex pwt-flex-direction-column")
+            .form_context(self.form_ctx.clone())
+            .with_child(panel)
+            .with_child(guest_list)
+            .with_child(bbar)
+            .into()
+    }
+
+    fn progress_view(&self, ctx: &Context<Self>, tasks: &[GuestTask]) -> Html {
+        let finished = tasks
+            .iter()
+            .filter(|task| matches!(task.state, GuestTaskState::Finished(_)))
+            .count();
+
+        let mut list = Column::new().padding(4).gap(1);
+        for task in tasks {
+            let (status, text) = match &task.state {
+                GuestTaskState::Queued => (Status::Unknown, tr!("Queued")),
+                GuestTaskState::Running(_) => (Status::Unknown, tr!("running")),
+                GuestTaskState::Finished(Ok(())) => (Status::Success, tr!("OK")),
+                GuestTaskState::Finished(Err(err)) => (Status::Error, err.clone()),
+            };
+            let status = Row::new()
+                .class(AlignItems::Center)
+                .gap(2)
+                .with_child(text)
+                .with_child(Fa::from(status).fixed_width());
+            list.add_child(guest_row(&task.guest, status.into()));
+        }
+
+        let progress = if tasks.is_empty() {
+            1.0
+        } else {
+            finished as f32 / tasks.len() as f32
+        };
+
+        Column::new()
+            .class(FlexFit)
+            .with_child(Progress::new().value(progress))
+            .with_child(list)
+            .with_child(
+                Toolbar::new()
+                    .with_child(tr!("{0} of {1} tasks finished", finished, tasks.len()))
+                    .with_flex_spacer()
+                    .with_child(Button::new(tr!("Close")).onclick({
+                        let on_close = ctx.props().on_close.clone();
+                        move |_| {
+                            if let Some(on_close) = &on_close {
+                                on_close.emit(());
+                            }
+                        }
+                    })),
+            )
+            .into()
+    }
+}
+
+impl Component for ProxmoxBulkSnapshotDialog {
+    type Message = Msg;
+    type Properties = BulkSnapshotDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let async_pool = AsyncPool::new();
+
+        if let Some(url) = props.permissions_url.clone() {
+            let link = ctx.link().clone();
+            async_pool.spawn(async move {
+                match crate::http_get(&*url, None).await {
+                    Ok(permissions) => link.send_message(Msg::Permissions(permissions)),
+                    Err(err) => log::error!("loading permissions failed: {err}"),
+                }
+            });
+        }
+
+        Self {
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormUpdate)),
+            permissions: None,
+            tasks: None,
+            async_pool,
+            poll_timeout: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::FormUpdate => {}
+            Msg::Permissions(permissions) => self.permissions = Some(permissions),
+            Msg::Submit => self.start_tasks(ctx),
+            Msg::TaskStarted(index, result) => {
+                let state = match result {
+                    Ok(upid) => GuestTaskState::Running(upid),
+                    Err(err) => GuestTaskState::Finished(Err(err.to_string())),
+                };
+                self.set_task_state(index, state);
+                self.schedule_poll(ctx);
+            }
+            Msg::TaskStatus(index, result) => {
+                match result {
+                    Ok(status) if status["status"].as_str() == Some("stopped") => {
+                        let state = match status["exitstatus"].as_str() {
+                            Some("OK") => Ok(()),
+                            Some(exit_status) => Err(exit_status.to_string()),
+                            None => Err(tr!("unknown")),
+                        };
+                        self.set_task_state(index, GuestTaskState::Finished(state));
+                    }
+                    Ok(_) => {
+                        if let Some(task) = self.task_mut(index) {
+                            task.poll_errors = 0;
+                        }
+                    }
+                    Err(err) => {
+                        log::error!("loading task status failed: {err}");
+                        let give_up = self.task_mut(index).is_some_and(|task| {
+                            task.poll_errors += 1;
+                            task.poll_errors >= MAX_POLL_ERRORS
+                        });
+                        if give_up {
+                            let msg = tr!("Unable to get task status: {0}", err);
+                            self.set_task_state(index, GuestTaskState::Finished(Err(msg)));
+                        }
+                    }
+                }
+                self.schedule_poll(ctx);
+            }
+            Msg::Poll => {
+                self.poll_timeout = None;
+                self.poll_tasks(ctx);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content = match &self.tasks {
+            None => self.edit_view(ctx),
+            Some(tasks) => self.progress_view(ctx, tasks),
+        };
+
+        Dialog::new(tr!("Bulk Snapshot"))
+            .min_width(500)
+            .on_close(props.on_close.clone())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<BulkSnapshotDialog> for VNode {
+    fn from(val: BulkSnapshotDialog) -> Self {
+        let comp = VComp::new::<ProxmoxBulkSnapshotDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
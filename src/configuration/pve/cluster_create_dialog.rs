@@ -0,0 +1,156 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Field, FormContext, Number};
+use pwt::widget::InputPanel;
+
+use pwt_macros::builder;
+
+use crate::{EditWindow, TaskProgress};
+
+/// Dialog to create a new cluster (`POST /cluster/config`) and monitor the creation task.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct ClusterCreateDialog {
+    #[prop_or("/cluster/config".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The cluster configuration API url.
+    pub base_url: AttrValue,
+
+    #[prop_or("/nodes/localhost/tasks".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the task API, used to monitor the creation task.
+    pub task_base_url: AttrValue,
+
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for ClusterCreateDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClusterCreateDialog {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    TaskStarted(String),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxClusterCreateDialog {
+    task_id: Option<String>,
+}
+
+/// Convert a link address field into the link property string.
+pub(crate) fn link_property(data: &mut Value, link: &str) {
+    match data[link].as_str().map(str::trim) {
+        Some("") | None => {
+            if let Some(map) = data.as_object_mut() {
+                map.remove(link);
+            }
+        }
+        Some(address) => data[link] = format!("address={address}").into(),
+    }
+}
+
+fn render_input_form(form_ctx: &FormContext) -> Html {
+    InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_large_field(
+            tr!("Cluster Name"),
+            Field::new().name("clustername").required(true),
+        )
+        .with_large_field(
+            tr!("Cluster Network") + " (link0)",
+            Field::new()
+                .name("link0")
+                .placeholder(tr!("Default: IP resolved by node's hostname")),
+        )
+        .with_advanced_spacer()
+        .with_large_advanced_field(
+            tr!("Cluster Network") + " (link1)",
+            Field::new().name("link1"),
+        )
+        .with_large_advanced_field(
+            tr!("Node ID"),
+            Number::<u32>::new().name("nodeid").min(1).placeholder("1"),
+        )
+        .into()
+}
+
+impl Component for ProxmoxClusterCreateDialog {
+    type Message = Msg;
+    type Properties = ClusterCreateDialog;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { task_id: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::TaskStarted(task_id) => {
+                self.task_id = Some(task_id);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        if let Some(task_id) = &self.task_id {
+            return TaskProgress::new(task_id)
+                .base_url(props.task_base_url.clone())
+                .on_close(props.on_close.clone())
+                .into();
+        }
+
+        let on_submit = {
+            let link = ctx.link().clone();
+            let url = props.base_url.to_string();
+            move |form_ctx: FormContext| {
+                let link = link.clone();
+                let url = url.clone();
+                async move {
+                    let mut data = form_ctx.get_submit_data();
+                    link_property(&mut data, "link0");
+                    link_property(&mut data, "link1");
+                    let task_id: String = crate::http_post(url, Some(data)).await?;
+                    link.send_message(Msg::TaskStarted(task_id));
+                    Ok::<(), Error>(())
+                }
+            }
+        };
+
+        EditWindow::new(tr!("Create Cluster"))
+            .advanced_checkbox(true)
+            .submit_text(tr!("Create"))
+            .renderer(render_input_form)
+            .on_close(props.on_close.clone())
+            .on_submit(on_submit)
+            .into()
+    }
+}
+
+impl From<ClusterCreateDialog> for VNode {
+    fn from(val: ClusterCreateDialog) -> Self {
+        let comp = VComp::new::<ProxmoxClusterCreateDialog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use anyhow::{format_err, Error};
+use serde::Deserialize;
+use serde_json::Value;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Field, FormContext, InputType, TextArea};
+use pwt::widget::{Container, InputPanel, TabBarItem};
+
+use pwt_macros::builder;
+
+use crate::{TaskProgress, Wizard, WizardPageRenderInfo};
+
+use super::cluster_create_dialog::link_property;
+
+/// The join information of a cluster, as shown by the cluster panel of the nodes already
+/// in the cluster (base64 encoded JSON).
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterJoinInfo {
+    /// Address of the peer node.
+    pub ip_address: String,
+    /// Fingerprint of the peer node's API certificate.
+    pub fingerprint: String,
+    /// Addresses of the peer node's corosync links, by link number.
+    #[serde(default)]
+    pub peer_links: BTreeMap<u8, String>,
+    /// The corosync totem configuration.
+    #[serde(default)]
+    pub totem: Value,
+}
+
+impl ClusterJoinInfo {
+    /// Parse the base64 encoded join information.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let text: String = text.split_whitespace().collect();
+        let data = proxmox_base64::decode(text)
+            .map_err(|_| format_err!("{}", tr!("Invalid join information (base64)")))?;
+        serde_json::from_slice(&data)
+            .map_err(|err| format_err!("{} - {err}", tr!("Invalid join information")))
+    }
+}
+
+const JOIN_INFO_FIELD: &str = "_join_info";
+const MAX_LINKS: u8 = 8;
+
+/// Wizard to join this node to an existing cluster (`POST /cluster/config/join`).
+///
+/// The join information of the cluster pre-fills peer address, fingerprint and the cluster
+/// network links. After submitting, the join task is monitored.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct ClusterJoinWizard {
+    #[prop_or("/cluster/config/join".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The cluster join API url.
+    pub join_url: AttrValue,
+
+    #[prop_or("/nodes/localhost/tasks".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the task API, used to monitor the join task.
+    pub task_base_url: AttrValue,
+
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for ClusterJoinWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClusterJoinWizard {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    TaskStarted(String),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxClusterJoinWizard {
+    task_id: Option<String>,
+}
+
+fn join_info_from_form(form_ctx: &FormContext) -> Option<ClusterJoinInfo> {
+    let text = form_ctx.read().get_field_text(JOIN_INFO_FIELD);
+    ClusterJoinInfo::parse(&text).ok()
+}
+
+fn render_join_page(info: &WizardPageRenderInfo) -> Html {
+    let form_ctx = info.form_ctx.clone();
+
+    InputPanel::new()
+        .padding(4)
+        .with_large_field(
+            tr!("Information"),
+            TextArea::new()
+                .name(JOIN_INFO_FIELD)
+                .submit(false)
+                .attribute("rows", "5")
+                .placeholder(tr!("Paste encoded Cluster Information here"))
+                .validate(|text: &String| {
+                    if text.trim().is_empty() {
+                        return Ok(());
+                    }
+                    ClusterJoinInfo::parse(text).map(|_| ())
+                })
+                .on_change({
+                    let form_ctx = form_ctx.clone();
+                    move |text: String| {
+                        if let Ok(join_info) = ClusterJoinInfo::parse(&text) {
+                            let mut form = form_ctx.write();
+                            form.set_field_value("hostname", join_info.ip_address.into());
+                            form.set_field_value("fingerprint", join_info.fingerprint.into());
+                        }
+                    }
+                }),
+        )
+        .with_field(
+            tr!("Peer Address"),
+            Field::new().name("hostname").required(true),
+        )
+        .with_right_field(
+            tr!("Password"),
+            Field::new()
+                .name("password")
+                .input_type(InputType::Password)
+                .required(true),
+        )
+        .with_large_field(
+            tr!("Fingerprint"),
+            Field::new().name("fingerprint").required(true),
+        )
+        .into()
+}
+
+fn render_links_page(info: &WizardPageRenderInfo) -> Html {
+    let join_info = info
+        .lookup_form_context(&Key::from("join"))
+        .and_then(|form_ctx| join_info_from_form(&form_ctx));
+
+    let mut panel = InputPanel::new().padding(4);
+
+    match join_info {
+        Some(join_info) if !join_info.peer_links.is_empty() => {
+            for (link, peer_address) in join_info.peer_links.iter().filter(|(l, _)| **l < MAX_LINKS)
+            {
+                panel.add_large_field(
+                    format!("link{link}"),
+                    Field::new()
+                        .name(format!("link{link}"))
+                        .required(true)
+                        .placeholder(tr!("Peer Address: {0}", peer_address)),
+                );
+            }
+        }
+        _ => {
+            panel.add_large_field(
+                "link0",
+                Field::new()
+                    .name("link0")
+                    .placeholder(tr!("Default: IP resolved by node's hostname")),
+            );
+            panel.add_large_field("link1", Field::new().name("link1"));
+        }
+    }
+
+    panel.add_large_custom_child(
+        Container::new()
+            .key("links_hint")
+            .class("pwt-color-warning")
+            .with_child(tr!(
+                "All existing guests and configurations of this node get lost on join, the \
+                 node must not contain any guests."
+            )),
+    );
+
+    panel.into()
+}
+
+impl Component for ProxmoxClusterJoinWizard {
+    type Message = Msg;
+    type Properties = ClusterJoinWizard;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { task_id: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::TaskStarted(task_id) => {
+                self.task_id = Some(task_id);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        if let Some(task_id) = &self.task_id {
+            return TaskProgress::new(task_id)
+                .base_url(props.task_base_url.clone())
+                .on_close(props.on_close.clone())
+                .into();
+        }
+
+        let on_submit = {
+            let link = ctx.link().clone();
+            let url = props.join_url.to_string();
+            move |mut data: Value| {
+                let link = link.clone();
+                let url = url.clone();
+                async move {
+                    for i in 0..MAX_LINKS {
+                        link_property(&mut data, &format!("link{i}"));
+                    }
+                    let task_id: String = crate::http_post(url, Some(data)).await?;
+                    link.send_message(Msg::TaskStarted(task_id));
+                    Ok::<(), Error>(())
+                }
+            }
+        };
+
+        Wizard::new(tr!("Cluster Join"))
+            .width(700)
+            .on_close(props.on_close.clone())
+            .submit_text(tr!("Join"))
+            .with_page(
+                TabBarItem::new().key("join").label(tr!("Join Information")),
+                render_join_page,
+            )
+            .with_page(
+                TabBarItem::new().key("links").label(tr!("Cluster Network")),
+                render_links_page,
+            )
+            .on_submit(on_submit)
+            .into()
+    }
+}
+
+impl From<ClusterJoinWizard> for VNode {
+    fn from(val: ClusterJoinWizard) -> Self {
+        let comp = VComp::new::<ProxmoxClusterJoinWizard>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_join_info() {
+        let json = r#"{"ipAddress":"192.168.0.1","fingerprint":"AB:CD","peerLinks":{"0":"10.0.0.1","1":"10.1.0.1"},"ring_addr":["10.0.0.1"],"totem":{}}"#;
+        let encoded = proxmox_base64::encode(json);
+
+        let info = ClusterJoinInfo::parse(&encoded).unwrap();
+        assert_eq!(info.ip_address, "192.168.0.1");
+        assert_eq!(info.fingerprint, "AB:CD");
+        assert_eq!(
+            info.peer_links.get(&1).map(String::as_str),
+            Some("10.1.0.1")
+        );
+
+        assert!(ClusterJoinInfo::parse("not base64!").is_err());
+        assert!(ClusterJoinInfo::parse(&proxmox_base64::encode("{}")).is_err());
+    }
+}
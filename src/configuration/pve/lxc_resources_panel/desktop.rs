@@ -28,9 +28,9 @@ use crate::form::pve::{
 };
 use crate::form::typed_load;
 use crate::pending_property_view::{
-    pending_typed_load, render_pending_property_value, PendingPropertyView,
-    PendingPropertyViewScopeExt, PendingPropertyViewState, PvePendingConfiguration,
-    PvePendingPropertyView,
+    pending_typed_load, render_pending_delete_marker, render_pending_property_value,
+    PendingPropertyView, PendingPropertyViewScopeExt, PendingPropertyViewState,
+    PvePendingConfiguration, PvePendingPropertyView,
 };
 use crate::EditableProperty;
 
@@ -92,6 +92,7 @@ impl PveLxcResourcesPanel {
             current: _,
             pending,
             keys: _,
+            ..
         } = match &self.data {
             Some(data) => data,
             _ => &PvePendingConfiguration::new(),
@@ -384,14 +385,16 @@ impl PendingPropertyView for PveLxcResourcesPanel {
             None => true,
         };
 
+        let data = match &self.data {
+            Some(data) => data,
+            _ => &PvePendingConfiguration::new(),
+        };
         let PvePendingConfiguration {
             current,
             pending,
             keys,
-        } = match &self.data {
-            Some(data) => data,
-            _ => &PvePendingConfiguration::new(),
-        };
+            ..
+        } = data;
 
         let create_entry = |name: &str, property: EditableProperty, icon, edit_action| {
             let header = property.title.clone().into();
@@ -401,7 +404,11 @@ impl PendingPropertyView for PveLxcResourcesPanel {
 
             let mut has_changes = false;
 
-            if let Some(new_value) = new_value {
+            let delete_state = data.delete_state(name);
+            if delete_state.is_marked() {
+                has_changes = true;
+                content.add_child(render_pending_delete_marker(delete_state));
+            } else if let Some(new_value) = new_value {
                 has_changes = true;
                 content.add_child(
                     Container::new()
@@ -295,6 +295,7 @@ impl PveLxcResourcesPanel {
             current,
             pending,
             keys,
+            ..
         } = data;
 
         let push_property_tile = |list: &mut Vec<_>, property: EditableProperty, icon, editable| {
@@ -172,6 +172,7 @@ fn is_unprivileged(data: &PvePendingConfiguration) -> bool {
         current: _,
         pending,
         keys: _,
+        ..
     } = data;
 
     match pending["unprivileged"] {
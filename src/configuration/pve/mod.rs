@@ -1,6 +1,20 @@
 mod move_disk_dialog;
 pub use move_disk_dialog::move_disk_dialog;
 
+mod cluster_create_dialog;
+pub use cluster_create_dialog::{ClusterCreateDialog, ProxmoxClusterCreateDialog};
+
+mod cluster_join_wizard;
+pub use cluster_join_wizard::{ClusterJoinInfo, ClusterJoinWizard, ProxmoxClusterJoinWizard};
+
+mod bulk_snapshot_dialog;
+pub use bulk_snapshot_dialog::{BulkSnapshotDialog, ProxmoxBulkSnapshotDialog};
+
+mod bulk_guest_action_dialog;
+pub use bulk_guest_action_dialog::{
+    BulkGuestAction, BulkGuestActionDialog, ProxmoxBulkGuestActionDialog,
+};
+
 mod resize_disk_dialog;
 pub use resize_disk_dialog::resize_disk_dialog;
 
@@ -16,6 +30,9 @@ pub use qemu_options_panel::QemuOptionsPanel;
 mod qemu_hardware_panel;
 pub use qemu_hardware_panel::QemuHardwarePanel;
 
+mod qemu_agent_panel;
+pub use qemu_agent_panel::{PveQemuAgentPanel, QemuAgentPanel};
+
 pub mod guest;
 
 mod lxc_dns_panel;
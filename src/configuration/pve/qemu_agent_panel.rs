@@ -0,0 +1,321 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Map, Value};
+
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::css::{AlignItems, FlexFit, JustifyContent};
+use pwt::prelude::*;
+use pwt::widget::{error_message, Button, Column, Fa, Panel, Toolbar};
+
+use proxmox_human_byte::HumanByte;
+
+use crate::percent_encoding::percent_encode_component;
+use crate::{
+    http_get, KVGrid, KVGridRow, LoadableComponent, LoadableComponentContext,
+    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState,
+};
+
+use pwt_macros::builder;
+
+/// Guest OS information reported by the QEMU guest agent.
+///
+/// Shows the operating system, the IP addresses per network interface and the file system
+/// usage. Each endpoint is queried separately, so partial information is still shown if
+/// the guest agent does not support all commands.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct QemuAgentPanel {
+    vmid: u32,
+    node: AttrValue,
+}
+
+impl QemuAgentPanel {
+    pub fn new(node: impl Into<AttrValue>, vmid: u32) -> Self {
+        yew::props!(Self {
+            node: node.into(),
+            vmid,
+        })
+    }
+}
+
+/// Result of a single guest agent command.
+type AgentResult = Result<Value, String>;
+
+pub enum Msg {
+    Loaded(AgentResult, AgentResult, AgentResult),
+}
+
+/// Returns true if the error means that the guest agent is not available at all.
+fn agent_unavailable(err: &str) -> bool {
+    err.contains("guest agent is not running") || err.contains("No QEMU guest agent configured")
+}
+
+/// Map the interfaces returned by `network-get-interfaces` to their IP addresses.
+///
+/// The loopback interface is skipped.
+fn interface_addresses(data: &Value) -> Vec<(String, Vec<String>)> {
+    let Some(list) = data.as_array() else {
+        return Vec::new();
+    };
+
+    list.iter()
+        .filter_map(|nic| {
+            let name = nic["name"].as_str()?;
+            if name == "lo" || name.starts_with("Loopback") {
+                return None;
+            }
+            let addresses = nic["ip-addresses"]
+                .as_array()
+                .map(|list| {
+                    list.iter()
+                        .filter_map(|ip| {
+                            let address = ip["ip-address"].as_str()?;
+                            Some(match ip["prefix"].as_u64() {
+                                Some(prefix) => format!("{address}/{prefix}"),
+                                None => address.to_string(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some((name.to_string(), addresses))
+        })
+        .collect()
+}
+
+/// Build grid data and rows from a list of `(name, value)` pairs.
+fn grid_content(entries: Vec<(String, Value)>) -> (Rc<Value>, Rc<Vec<KVGridRow>>) {
+    let rows = entries
+        .iter()
+        .map(|(name, _)| KVGridRow::new(name.clone(), name.clone()))
+        .collect();
+    let data: Map<String, Value> = entries.into_iter().collect();
+    (Rc::new(Value::Object(data)), Rc::new(rows))
+}
+
+fn os_info_grid(data: &Value) -> Html {
+    let rows = Rc::new(vec![
+        KVGridRow::new("pretty-name", tr!("Name")),
+        KVGridRow::new("version", tr!("Version")),
+        KVGridRow::new("kernel-release", tr!("Kernel")),
+        KVGridRow::new("kernel-version", tr!("Kernel Version")),
+        KVGridRow::new("machine", tr!("Architecture")),
+    ]);
+    KVGrid::new().data(Rc::new(data.clone())).rows(rows).into()
+}
+
+fn network_grid(data: &Value) -> Html {
+    let entries = interface_addresses(data)
+        .into_iter()
+        .map(|(name, addresses)| (name, Value::from(addresses.join(", "))))
+        .collect();
+    let (data, rows) = grid_content(entries);
+    KVGrid::new().data(data).rows(rows).into()
+}
+
+fn filesystem_grid(data: &Value) -> Html {
+    let entries = data
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|fs| {
+                    let mountpoint = fs["mountpoint"].as_str()?;
+                    let fs_type = fs["type"].as_str().unwrap_or("");
+                    let text = match (fs["used-bytes"].as_u64(), fs["total-bytes"].as_u64()) {
+                        (Some(used), Some(total)) if total > 0 => format!(
+                            "{:.2}% ({} of {}) - {fs_type}",
+                            used as f64 * 100.0 / total as f64,
+                            HumanByte::from(used),
+                            HumanByte::from(total),
+                        ),
+                        _ => fs_type.to_string(),
+                    };
+                    Some((mountpoint.to_string(), Value::from(text)))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let (data, rows) = grid_content(entries);
+    KVGrid::new().data(data).rows(rows).into()
+}
+
+fn section(title: String, content: &AgentResult, render: fn(&Value) -> Html) -> Html {
+    let content = match content {
+        Ok(data) => render(data),
+        Err(err) => error_message(err).padding(2).into(),
+    };
+    Panel::new()
+        .title(title)
+        .border(true)
+        .with_child(content)
+        .into()
+}
+
+#[doc(hidden)]
+pub struct PveQemuAgentPanel {
+    state: LoadableComponentState<()>,
+    os_info: Option<AgentResult>,
+    network: Option<AgentResult>,
+    filesystems: Option<AgentResult>,
+}
+
+pwt::impl_deref_mut_property!(PveQemuAgentPanel, state, LoadableComponentState<()>);
+
+impl PveQemuAgentPanel {
+    fn agent_unavailable_view(&self, err: &str) -> Html {
+        Column::new()
+            .class(FlexFit)
+            .class(AlignItems::Center)
+            .class(JustifyContent::Center)
+            .gap(2)
+            .padding(4)
+            .with_child(Fa::new("info-circle").large_2x())
+            .with_child(tr!("Guest Agent not running"))
+            .with_child(html! {<div class="pwt-opacity-50">{err}</div>})
+            .into()
+    }
+}
+
+impl LoadableComponent for PveQemuAgentPanel {
+    type Properties = QemuAgentPanel;
+    type Message = Msg;
+    type ViewState = ();
+
+    fn create(_ctx: &LoadableComponentContext<Self>) -> Self {
+        Self {
+            state: LoadableComponentState::new(),
+            os_info: None,
+            network: None,
+            filesystems: None,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let props = ctx.props();
+        let base_url = format!(
+            "/nodes/{}/qemu/{}/agent",
+            percent_encode_component(&props.node),
+            props.vmid
+        );
+        let link = ctx.link().clone();
+
+        Box::pin(async move {
+            let get = |command: &'static str| {
+                let url = format!("{base_url}/{command}");
+                async move {
+                    http_get::<Value>(url, None)
+                        .await
+                        .map(|data| data["result"].clone())
+                        .map_err(|err| err.to_string())
+                }
+            };
+
+            let (os_info, network, filesystems) = futures::join!(
+                get("get-osinfo"),
+                get("network-get-interfaces"),
+                get("get-fsinfo"),
+            );
+            link.send_message(Msg::Loaded(os_info, network, filesystems));
+            Ok(())
+        })
+    }
+
+    fn update(&mut self, _ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Loaded(os_info, network, filesystems) => {
+                self.os_info = Some(os_info);
+                self.network = Some(network);
+                self.filesystems = Some(filesystems);
+            }
+        }
+        true
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let toolbar = Toolbar::new()
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_flex_spacer()
+            .with_child({
+                let loading = self.loading();
+                let link = ctx.link().clone();
+                Button::refresh(loading).onclick(move |_| link.send_reload())
+            });
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, _ctx: &LoadableComponentContext<Self>) -> Html {
+        let (Some(os_info), Some(network), Some(filesystems)) =
+            (&self.os_info, &self.network, &self.filesystems)
+        else {
+            return html! {};
+        };
+
+        if let Err(err) = os_info {
+            if agent_unavailable(err) {
+                return self.agent_unavailable_view(err);
+            }
+        }
+
+        Column::new()
+            .class(FlexFit)
+            .class("pwt-overflow-auto")
+            .padding(2)
+            .gap(2)
+            .with_child(section(tr!("Operating System"), os_info, os_info_grid))
+            .with_child(section(tr!("IP Addresses"), network, network_grid))
+            .with_child(section(tr!("File Systems"), filesystems, filesystem_grid))
+            .into()
+    }
+}
+
+impl From<QemuAgentPanel> for VNode {
+    fn from(val: QemuAgentPanel) -> Self {
+        let comp = VComp::new::<LoadableComponentMaster<PveQemuAgentPanel>>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_interface_addresses() {
+        let data = json!([
+            {
+                "name": "lo",
+                "ip-addresses": [{ "ip-address": "127.0.0.1", "prefix": 8 }],
+            },
+            {
+                "name": "eth0",
+                "hardware-address": "aa:bb:cc:dd:ee:ff",
+                "ip-addresses": [
+                    { "ip-address": "192.168.1.10", "ip-address-type": "ipv4", "prefix": 24 },
+                    { "ip-address": "fe80::1", "ip-address-type": "ipv6" },
+                ],
+            },
+            { "name": "eth1" },
+        ]);
+        assert_eq!(
+            interface_addresses(&data),
+            vec![
+                (
+                    "eth0".to_string(),
+                    vec!["192.168.1.10/24".to_string(), "fe80::1".to_string()]
+                ),
+                ("eth1".to_string(), Vec::new()),
+            ]
+        );
+        assert!(interface_addresses(&Value::Null).is_empty());
+    }
+}
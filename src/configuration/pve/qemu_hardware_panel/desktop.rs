@@ -27,16 +27,19 @@ use crate::configuration::pve::guest::{
 };
 use crate::configuration::{guest_config_url, guest_pending_url};
 use crate::form::pve::{
-    qemu_bios_property, qemu_cdrom_property, qemu_disk_property, qemu_display_property,
-    qemu_efidisk_property, qemu_machine_property, qemu_memory_property, qemu_network_property,
-    qemu_scsihw_property, qemu_sockets_cores_property, qemu_tpmstate_property,
-    qemu_vmstate_property, PveGuestType,
+    qemu_audio_property, qemu_bios_property, qemu_cdrom_property, qemu_disk_property,
+    qemu_display_property, qemu_efidisk_property, qemu_hostpci_property, qemu_machine_property,
+    qemu_memory_property, qemu_network_property, qemu_parallel_property, qemu_rng_property,
+    qemu_scsihw_property, qemu_serial_property, qemu_sockets_cores_property,
+    qemu_tpmstate_property, qemu_usb_property, qemu_virtiofs_property, qemu_vmstate_property,
+    PveGuestType, MAX_HOSTPCI_DEVICES, MAX_PARALLEL_PORTS, MAX_SERIAL_PORTS, MAX_USB_DEVICES,
+    MAX_VIRTIOFS_DEVICES,
 };
 use crate::form::typed_load;
 use crate::pending_property_view::{
-    pending_typed_load, render_pending_property_value, PendingPropertyView,
-    PendingPropertyViewScopeExt, PendingPropertyViewState, PvePendingConfiguration,
-    PvePendingPropertyView,
+    pending_typed_load, render_pending_delete_marker, render_pending_property_value,
+    PendingPropertyView, PendingPropertyViewScopeExt, PendingPropertyViewState,
+    PvePendingConfiguration, PvePendingPropertyView,
 };
 use crate::EditableProperty;
 
@@ -96,6 +99,7 @@ impl PveQemuHardwarePanel {
             current: _,
             pending,
             keys: _,
+            ..
         } = match &self.data {
             Some(data) => data,
             _ => &PvePendingConfiguration::new(),
@@ -298,6 +302,7 @@ impl PveQemuHardwarePanel {
             current: _,
             pending,
             keys: _,
+            ..
         } = match &self.data {
             Some(data) => data,
             _ => &PvePendingConfiguration::new(),
@@ -305,6 +310,12 @@ impl PveQemuHardwarePanel {
 
         let has_efidisk = pending.get("efidisk0").is_some();
         let has_tpmstate = pending.get("tpmstate0").is_some();
+        let has_audio = pending.get("audio0").is_some();
+        let has_rng = pending.get("rng0").is_some();
+
+        let all_used = |prefix: &str, max: usize| {
+            (0..max).all(|n| pending.get(format!("{prefix}{n}")).is_some())
+        };
 
         let menu = Menu::new()
             .with_item({
@@ -376,6 +387,77 @@ impl PveQemuHardwarePanel {
                         );
                         move |_| link.send_add_property(property.clone(), None)
                     })
+            })
+            .with_item({
+                MenuItem::new(tr!("PCI Device"))
+                    .icon_class("fa fa-microchip")
+                    .disabled(all_used("hostpci", MAX_HOSTPCI_DEVICES))
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property = qemu_hostpci_property(None, Some(props.node.clone()), false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
+            })
+            .with_item({
+                MenuItem::new(tr!("USB Device"))
+                    .icon_class("fa fa-usb")
+                    .disabled(all_used("usb", MAX_USB_DEVICES))
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property = qemu_usb_property(None, Some(props.node.clone()), false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
+            })
+            .with_item({
+                MenuItem::new(tr!("Serial Port"))
+                    .icon_class("fa fa-chevron-right")
+                    .disabled(all_used("serial", MAX_SERIAL_PORTS))
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property = qemu_serial_property(None, false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
+            })
+            .with_item({
+                MenuItem::new(tr!("Parallel Port"))
+                    .icon_class("fa fa-print")
+                    .disabled(all_used("parallel", MAX_PARALLEL_PORTS))
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property = qemu_parallel_property(None, false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
+            })
+            .with_item({
+                MenuItem::new(tr!("Audio Device"))
+                    .icon_class("fa fa-volume-up")
+                    .disabled(has_audio)
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property = qemu_audio_property(false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
+            })
+            .with_item({
+                MenuItem::new(tr!("VirtIO RNG"))
+                    .icon_class("fa fa-cogs")
+                    .disabled(has_rng)
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property = qemu_rng_property(false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
+            })
+            .with_item({
+                MenuItem::new(tr!("Virtiofs"))
+                    .icon_class("fa fa-folder")
+                    .disabled(all_used("virtiofs", MAX_VIRTIOFS_DEVICES))
+                    .on_select({
+                        let link = ctx.link().clone();
+                        let property =
+                            qemu_virtiofs_property(None, Some(props.node.clone()), false);
+                        move |_| link.send_add_property(property.clone(), None)
+                    })
             });
 
         MenuButton::new(tr!("Add"))
@@ -428,14 +510,16 @@ impl PendingPropertyView for PveQemuHardwarePanel {
             .unwrap_or_default();
         let user_is_root = props.remote.is_none() && username == "root@pam";
 
+        let data = match &self.data {
+            Some(data) => data,
+            _ => &PvePendingConfiguration::new(),
+        };
         let PvePendingConfiguration {
             current,
             pending,
             keys,
-        } = match &self.data {
-            Some(data) => data,
-            _ => &PvePendingConfiguration::new(),
-        };
+            ..
+        } = data;
 
         let create_entry = |name: &str, property: EditableProperty, icon, edit_action| {
             let header = property.title.clone().into();
@@ -445,7 +529,11 @@ impl PendingPropertyView for PveQemuHardwarePanel {
 
             let mut has_changes = false;
 
-            if let Some(new_value) = new_value {
+            let delete_state = data.delete_state(name);
+            if delete_state.is_marked() {
+                has_changes = true;
+                content.add_child(render_pending_delete_marker(delete_state));
+            } else if let Some(new_value) = new_value {
                 has_changes = true;
                 content.add_child(
                     Container::new()
@@ -559,7 +647,7 @@ impl PendingPropertyView for PveQemuHardwarePanel {
         );
         push_property(
             &mut list,
-            qemu_machine_property(false),
+            qemu_machine_property(props.node.clone(), props.remote.clone(), false),
             Fa::new("cogs"),
             EditAction::Edit,
         );
@@ -648,6 +736,91 @@ impl PendingPropertyView for PveQemuHardwarePanel {
             push_network_property(&mut list, &name);
         }
 
+        for n in 0..MAX_HOSTPCI_DEVICES {
+            let name = format!("hostpci{n}");
+            if !keys.contains(&name) {
+                continue;
+            }
+            let property =
+                qemu_hostpci_property(Some(name.clone()), Some(props.node.clone()), false);
+            list.push(create_entry(
+                &name,
+                property,
+                Fa::new("microchip"),
+                EditAction::Edit,
+            ));
+        }
+
+        for n in 0..MAX_USB_DEVICES {
+            let name = format!("usb{n}");
+            if !keys.contains(&name) {
+                continue;
+            }
+            let property = qemu_usb_property(Some(name.clone()), Some(props.node.clone()), false);
+            list.push(create_entry(
+                &name,
+                property,
+                Fa::new("usb"),
+                EditAction::Edit,
+            ));
+        }
+
+        for n in 0..MAX_SERIAL_PORTS {
+            let name = format!("serial{n}");
+            if !keys.contains(&name) {
+                continue;
+            }
+            let property = qemu_serial_property(Some(name.clone()), false);
+            list.push(create_entry(
+                &name,
+                property,
+                Fa::new("chevron-right"),
+                EditAction::Edit,
+            ));
+        }
+
+        for n in 0..MAX_PARALLEL_PORTS {
+            let name = format!("parallel{n}");
+            if !keys.contains(&name) {
+                continue;
+            }
+            let property = qemu_parallel_property(Some(name.clone()), false);
+            list.push(create_entry(
+                &name,
+                property,
+                Fa::new("print"),
+                EditAction::Edit,
+            ));
+        }
+
+        for n in 0..MAX_VIRTIOFS_DEVICES {
+            let name = format!("virtiofs{n}");
+            if !keys.contains(&name) {
+                continue;
+            }
+            let property =
+                qemu_virtiofs_property(Some(name.clone()), Some(props.node.clone()), false);
+            list.push(create_entry(
+                &name,
+                property,
+                Fa::new("folder"),
+                EditAction::Edit,
+            ));
+        }
+
+        push_property(
+            &mut list,
+            qemu_audio_property(false),
+            Fa::new("volume-up"),
+            EditAction::Edit,
+        );
+        push_property(
+            &mut list,
+            qemu_rng_property(false),
+            Fa::new("cogs"),
+            EditAction::Edit,
+        );
+
         for n in 0..QemuConfigUnusedArray::MAX {
             let name = format!("unused{n}");
             if !keys.contains(&name) {
@@ -473,6 +473,7 @@ impl PveQemuHardwarePanel {
             current,
             pending,
             keys,
+            ..
         } = data;
 
         let push_property_tile = |list: &mut Vec<_>, property: EditableProperty, icon, editable| {
@@ -643,6 +644,7 @@ impl PveQemuHardwarePanel {
             current: _,
             pending,
             keys: _,
+            ..
         } = data;
 
         let has_efidisk = pending.get("efidisk0").is_some();
@@ -766,9 +768,17 @@ impl PendingPropertyView for PveQemuHardwarePanel {
                 mobile,
             ),
             kernel_scheduler_property: qemu_kernel_scheduler_property(user_is_root, mobile),
-            cpu_flags_property: qemu_cpu_flags_property(mobile),
+            cpu_flags_property: qemu_cpu_flags_property(
+                props.node.clone(),
+                props.remote.clone(),
+                mobile,
+            ),
             display_property: qemu_display_property(mobile),
-            machine_property: qemu_machine_property(mobile),
+            machine_property: qemu_machine_property(
+                props.node.clone(),
+                props.remote.clone(),
+                mobile,
+            ),
             scsihw_property: qemu_scsihw_property(mobile),
             vmstate_property: qemu_vmstate_property(),
         }
@@ -5,10 +5,10 @@ use serde_json::Value;
 
 use pwt::prelude::*;
 use pwt::widget::form::FormContext;
-use pwt::widget::InputPanel;
+use pwt::widget::{Column, InputPanel};
 
 use crate::utils::render_epoch;
-use crate::{ObjectGrid, ObjectGridRow, TimezoneSelector};
+use crate::{ObjectGrid, ObjectGridRow, TimeSyncStatusCard, TimezoneSelector};
 
 async fn store_timezone(form: FormContext) -> Result<(), Error> {
     let value = form.get_submit_data();
@@ -53,10 +53,15 @@ pub fn time_panel() -> Html {
             .required(true),
     ]);
 
-    ObjectGrid::new()
-        .editable(true)
-        .loader("/nodes/localhost/time")
-        .on_submit(store_timezone)
-        .rows(rows)
+    Column::new()
+        .gap(2)
+        .with_child(
+            ObjectGrid::new()
+                .editable(true)
+                .loader("/nodes/localhost/time")
+                .on_submit(store_timezone)
+                .rows(rows),
+        )
+        .with_child(TimeSyncStatusCard::new())
         .into()
 }
@@ -0,0 +1,161 @@
+use gloo_timers::callback::Timeout;
+use yew::html::IntoPropValue;
+
+use pwt::css::{ColorScheme, FlexFit};
+use pwt::prelude::*;
+use pwt::touch::{SnackBar, SnackBarContextExt};
+use pwt::widget::form::{DisplayField, Field, InputType};
+use pwt::widget::{Button, Row, Tooltip};
+use pwt::AsyncPool;
+
+use pwt_macros::{builder, widget};
+
+use crate::utils::write_text_to_clipboard;
+
+/// How long the button shows the copy result (milliseconds).
+const FEEDBACK_DURATION_MS: u32 = 2000;
+
+/// Read-only text with a button to copy it to the clipboard.
+///
+/// Meant for values users need to transfer somewhere else, like fingerprints, join
+/// information, token secrets or UPIDs. The button icon shows whether copying worked. If a
+/// [SnackBarController](pwt::touch::SnackBarController) context is available, the result is
+/// shown as snackbar, too.
+#[widget(comp=ProxmoxCopyField, @element)]
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct CopyField {
+    /// The value to display and copy.
+    pub value: AttrValue,
+
+    /// Hide the value like a password (it is still copied in clear text).
+    #[builder]
+    #[prop_or_default]
+    pub password: bool,
+
+    /// Tooltip for the copy button.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub tip: Option<AttrValue>,
+}
+
+impl CopyField {
+    /// Creates a new instance.
+    pub fn new(value: impl Into<AttrValue>) -> Self {
+        yew::props!(Self {
+            value: value.into()
+        })
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Copy,
+    CopyResult(Result<(), String>),
+    ResetFeedback,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxCopyField {
+    // result of the last copy operation, shown until the feedback timeout is over
+    copied: Option<bool>,
+    feedback_timeout: Option<Timeout>,
+    async_pool: AsyncPool,
+}
+
+impl Component for ProxmoxCopyField {
+    type Message = Msg;
+    type Properties = CopyField;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            copied: None,
+            feedback_timeout: None,
+            async_pool: AsyncPool::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Copy => {
+                let text = ctx.props().value.clone();
+                let link = ctx.link().clone();
+                self.async_pool.spawn(async move {
+                    let result = write_text_to_clipboard(&text)
+                        .await
+                        .map_err(|err| err.to_string());
+                    link.send_message(Msg::CopyResult(result));
+                });
+                false
+            }
+            Msg::CopyResult(result) => {
+                if let Some(controller) = ctx.link().snackbar_controller() {
+                    let message = match &result {
+                        Ok(()) => tr!("Copied to clipboard."),
+                        Err(err) => tr!("Could not copy to clipboard: {0}", err),
+                    };
+                    controller.show_snackbar(SnackBar::new().message(message));
+                }
+                if let Err(err) = &result {
+                    log::error!("could not copy to clipboard: {err}");
+                }
+                self.copied = Some(result.is_ok());
+                let link = ctx.link().clone();
+                self.feedback_timeout = Some(Timeout::new(FEEDBACK_DURATION_MS, move || {
+                    link.send_message(Msg::ResetFeedback)
+                }));
+                true
+            }
+            Msg::ResetFeedback => {
+                self.copied = None;
+                self.feedback_timeout = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let value: Html = if props.password {
+            Field::new()
+                .input_type(InputType::Password)
+                .class(FlexFit)
+                .value(props.value.to_string())
+                .read_only(true)
+                .into()
+        } else {
+            DisplayField::new()
+                .class(FlexFit)
+                .value(props.value.clone())
+                .border(true)
+                .into()
+        };
+
+        let icon = match self.copied {
+            None => "fa fa-clipboard",
+            Some(true) => "fa fa-check",
+            Some(false) => "fa fa-exclamation-triangle",
+        };
+
+        let tip = props
+            .tip
+            .clone()
+            .unwrap_or_else(|| tr!("Copy to clipboard").into());
+
+        Row::new()
+            .with_std_props(&props.std_props)
+            .listeners(&props.listeners)
+            .gap(2)
+            .with_child(value)
+            .with_child(
+                Tooltip::new(
+                    Button::new_icon(icon)
+                        .class(ColorScheme::Primary)
+                        .on_activate(ctx.link().callback(|_| Msg::Copy)),
+                )
+                .tip(tip),
+            )
+            .into()
+    }
+}
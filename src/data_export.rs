@@ -0,0 +1,242 @@
+//! Export displayed data as CSV or JSON file.
+
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde::Serialize;
+use serde_json::Value;
+
+use pwt::prelude::*;
+use pwt::state::Store;
+use pwt::widget::data_table::DataTableHeader;
+use pwt::widget::menu::{Menu, MenuButton, MenuItem};
+
+use crate::utils::download_text_file;
+
+/// Export file format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DataExportFormat {
+    Csv,
+    Json,
+}
+
+impl DataExportFormat {
+    /// The file name extension (without dot).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DataExportFormat::Csv => "csv",
+            DataExportFormat::Json => "json",
+        }
+    }
+}
+
+/// An exported column.
+///
+/// The `name` selects the property of the serialized record, the `header` is used
+/// as CSV column title.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataExportColumn {
+    pub name: AttrValue,
+    pub header: AttrValue,
+}
+
+impl DataExportColumn {
+    pub fn new(name: impl Into<AttrValue>, header: impl Into<AttrValue>) -> Self {
+        Self {
+            name: name.into(),
+            header: header.into(),
+        }
+    }
+}
+
+/// Export columns for the visible columns of a data table, in display order.
+///
+/// The column key selects the property of the serialized record, so columns without
+/// key are skipped. Pass the headers as currently shown (for example
+/// [PersistentGridState::columns](crate::grid_state::PersistentGridState::columns)), to
+/// respect the order and visibility changes of the user.
+pub fn data_table_export_columns<T: 'static>(
+    headers: &[DataTableHeader<T>],
+) -> Vec<DataExportColumn> {
+    headers
+        .iter()
+        .filter_map(|header| match header {
+            DataTableHeader::Column(column) if !column.hidden => {
+                let key = column.key.as_ref()?;
+                Some(DataExportColumn::new(key.to_string(), column.name.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => csv_escape(s),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(_) | Value::Object(_) => csv_escape(&value.to_string()),
+    }
+}
+
+/// Format rows as CSV, with a header line and columns in the given order.
+pub fn format_csv(columns: &[DataExportColumn], rows: &[Value]) -> String {
+    let mut text = String::new();
+
+    let header: Vec<String> = columns.iter().map(|c| csv_escape(&c.header)).collect();
+    text.push_str(&header.join(","));
+    text.push_str("\r\n");
+
+    for row in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|c| csv_cell(&row[c.name.as_str()]))
+            .collect();
+        text.push_str(&cells.join(","));
+        text.push_str("\r\n");
+    }
+
+    text
+}
+
+/// Format rows as JSON array, containing only the given columns.
+///
+/// Object keys are written in column order.
+pub fn format_json(columns: &[DataExportColumn], rows: &[Value]) -> String {
+    let rows: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let props: Vec<String> = columns
+                .iter()
+                .filter(|c| !row[c.name.as_str()].is_null())
+                .map(|c| {
+                    let name = c.name.as_str();
+                    format!("{}:{}", Value::from(name), row[name])
+                })
+                .collect();
+            format!("{{{}}}", props.join(","))
+        })
+        .collect();
+
+    format!("[{}]", rows.join(","))
+}
+
+/// Format rows and offer the result as file download.
+///
+/// The extension is appended to `filename`.
+pub fn export_rows(
+    filename: &str,
+    format: DataExportFormat,
+    columns: &[DataExportColumn],
+    rows: &[Value],
+) -> Result<(), Error> {
+    let text = match format {
+        DataExportFormat::Csv => format_csv(columns, rows),
+        DataExportFormat::Json => format_json(columns, rows),
+    };
+    download_text_file(&format!("{filename}.{}", format.extension()), &text)
+}
+
+/// Export the records of a store, as currently displayed (filtered and sorted).
+pub fn export_store<T: Serialize + 'static>(
+    store: &Store<T>,
+    filename: &str,
+    format: DataExportFormat,
+    columns: &[DataExportColumn],
+) -> Result<(), Error> {
+    let rows = store
+        .read()
+        .filtered_data()
+        .map(|(_, record)| serde_json::to_value(record))
+        .collect::<Result<Vec<Value>, _>>()?;
+    export_rows(filename, format, columns, &rows)
+}
+
+/// Create an "Export" menu button, offering CSV and JSON downloads.
+///
+/// The callback gets called with the selected format.
+pub fn data_export_button(on_export: impl Into<Callback<DataExportFormat>>) -> MenuButton {
+    let on_export = on_export.into();
+    let item = |text: String, format: DataExportFormat| {
+        let on_export = on_export.clone();
+        MenuItem::new(text).on_select(move |_| on_export.emit(format))
+    };
+
+    MenuButton::new(tr!("Export"))
+        .icon_class("fa fa-download")
+        .show_arrow(true)
+        .menu(
+            Menu::new()
+                .with_item(item(tr!("CSV"), DataExportFormat::Csv))
+                .with_item(item(tr!("JSON"), DataExportFormat::Json)),
+        )
+}
+
+/// Create an export button for the visible records of a grid store.
+///
+/// Exports the visible columns of the data table headers, see [data_table_export_columns].
+/// The records are only serialized when an export is requested.
+///
+/// ```ignore
+/// Toolbar::new().with_child(store_export_button(
+///     self.store.clone(),
+///     "tasks",
+///     grid_state.columns(),
+/// ))
+/// ```
+pub fn store_export_button<T: Serialize + 'static>(
+    store: Store<T>,
+    filename: impl Into<AttrValue>,
+    headers: Rc<Vec<DataTableHeader<T>>>,
+) -> MenuButton {
+    let filename = filename.into();
+    data_export_button(move |format| {
+        let columns = data_table_export_columns(&headers);
+        if let Err(err) = export_store(&store, &filename, format, &columns) {
+            log::error!("unable to export data: {err}");
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn columns() -> Vec<DataExportColumn> {
+        vec![
+            DataExportColumn::new("name", "Name"),
+            DataExportColumn::new("size", "Size, bytes"),
+        ]
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let rows = vec![
+            json!({ "size": 10, "name": "a \"quoted\" name", "other": 1 }),
+            json!({ "name": "b" }),
+        ];
+        assert_eq!(
+            format_csv(&columns(), &rows),
+            "Name,\"Size, bytes\"\r\n\"a \"\"quoted\"\" name\",10\r\nb,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_format_json() {
+        let rows = vec![json!({ "size": 10, "name": "a", "other": 1 }), json!({})];
+        assert_eq!(
+            format_json(&columns(), &rows),
+            r#"[{"name":"a","size":10},{}]"#
+        );
+    }
+}
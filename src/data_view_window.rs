@@ -1,6 +1,8 @@
 use std::rc::Rc;
 
+use anyhow::Error;
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 
 use yew::html::IntoEventCallback;
 use yew::prelude::*;
@@ -11,10 +13,12 @@ use pwt::props::{
     AsCssStylesMut, CssStyles, IntoLoadCallback, LoadCallback, RenderFn, WidgetStyleBuilder,
 };
 use pwt::state::Loader;
-use pwt::widget::Dialog;
+use pwt::widget::{Dialog, Toolbar};
 
 use pwt_macros::builder;
 
+use crate::data_export::{data_export_button, export_rows, DataExportColumn};
+
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
 pub struct DataViewWindow<T: PartialEq> {
@@ -50,6 +54,18 @@ pub struct DataViewWindow<T: PartialEq> {
     #[builder]
     pub auto_center: bool,
 
+    /// Columns for the data export.
+    ///
+    /// If set, the window shows an export button. The loaded data is serialized, and
+    /// arrays are exported row by row (objects as single row).
+    #[prop_or_default]
+    pub export_columns: Option<Rc<Vec<DataExportColumn>>>,
+
+    /// File name for exported data (without extension).
+    #[prop_or(AttrValue::Static("export"))]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub export_filename: AttrValue,
+
     /// CSS style for the dialog window.
     #[prop_or_default]
     pub styles: CssStyles,
@@ -85,6 +101,12 @@ impl<T: 'static + PartialEq> DataViewWindow<T> {
         self.renderer = Some(RenderFn::new(renderer));
         self
     }
+
+    /// Builder style method to set the export columns.
+    pub fn export_columns(mut self, columns: Vec<DataExportColumn>) -> Self {
+        self.export_columns = Some(Rc::new(columns));
+        self
+    }
 }
 
 #[doc(hidden)]
@@ -127,11 +149,47 @@ impl<T: 'static + Serialize + DeserializeOwned + PartialEq> Component for Proxmo
             .resizable(props.resizable)
             .auto_center(props.auto_center)
             .styles(props.styles.clone())
+            .with_optional_child(self.export_toolbar(props))
             .with_child(panel)
             .into()
     }
 }
 
+impl<T: 'static + Serialize + DeserializeOwned + PartialEq> ProxmoxDataViewWindow<T> {
+    fn export_toolbar(&self, props: &DataViewWindow<T>) -> Option<Html> {
+        let columns = props.export_columns.clone()?;
+        let data = match &self.loader.read().data {
+            Some(Ok(data)) => Rc::clone(data),
+            _ => return None,
+        };
+        let filename = props.export_filename.clone();
+
+        // only serialize the data when exporting
+        let button = data_export_button(move |format| {
+            let result = serde_json::to_value(&*data)
+                .map_err(Error::from)
+                .and_then(|data| {
+                    let rows = match data {
+                        Value::Array(rows) => rows,
+                        row => vec![row],
+                    };
+                    export_rows(&filename, format, &columns, &rows)
+                });
+            if let Err(err) = result {
+                log::error!("unable to export data: {err}");
+            }
+        });
+
+        Some(
+            Toolbar::new()
+                .class("pwt-border-bottom")
+                .with_flex_spacer()
+                .with_child(button)
+                .into(),
+        )
+    }
+}
+
 impl<T: 'static + Serialize + DeserializeOwned + PartialEq> From<DataViewWindow<T>> for VNode {
     fn from(props: DataViewWindow<T>) -> VNode {
         let key = props.key.clone();
@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::css::FlexFit;
+use pwt::prelude::*;
+use pwt::widget::{Container, Fa, Panel, Row};
+use pwt_macros::builder;
+
+use proxmox_human_byte::HumanByte;
+use pve_api_types::{ClusterResource, ClusterResourceType};
+
+use crate::{
+    http_get, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState, MeterLabel, StatusRow,
+};
+
+/// Number of running and stopped guests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GuestCounts {
+    pub running: usize,
+    pub stopped: usize,
+}
+
+impl GuestCounts {
+    /// Total number of guests.
+    pub fn total(&self) -> usize {
+        self.running + self.stopped
+    }
+}
+
+/// Aggregated state of a cluster, computed from the `/cluster/resources` list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClusterStats {
+    pub nodes_online: usize,
+    pub nodes_offline: usize,
+    /// Virtual machines (templates are not counted).
+    pub vms: GuestCounts,
+    /// Containers (templates are not counted).
+    pub containers: GuestCounts,
+    /// Used storage space (bytes) of all available storages.
+    pub storage_used: u64,
+    /// Total storage space (bytes) of all available storages.
+    pub storage_total: u64,
+}
+
+impl ClusterStats {
+    /// Aggregate the resource list.
+    ///
+    /// Shared storages show up once per node, so they are only counted once (by name).
+    pub fn from_resources(resources: &[ClusterResource]) -> Self {
+        let mut stats = Self::default();
+        let mut seen_storages = HashSet::new();
+
+        for resource in resources {
+            let status = resource.status.as_deref().unwrap_or("unknown");
+            match resource.ty {
+                ClusterResourceType::Node => {
+                    if status == "online" {
+                        stats.nodes_online += 1;
+                    } else {
+                        stats.nodes_offline += 1;
+                    }
+                }
+                ClusterResourceType::Qemu | ClusterResourceType::Lxc => {
+                    if resource.template == Some(true) {
+                        continue;
+                    }
+                    let counts = if resource.ty == ClusterResourceType::Qemu {
+                        &mut stats.vms
+                    } else {
+                        &mut stats.containers
+                    };
+                    if status == "running" {
+                        counts.running += 1;
+                    } else {
+                        counts.stopped += 1;
+                    }
+                }
+                ClusterResourceType::Storage => {
+                    if status != "available" {
+                        continue;
+                    }
+                    if resource.shared == Some(true)
+                        && !seen_storages.insert(resource.storage.clone())
+                    {
+                        continue;
+                    }
+                    stats.storage_used += resource.disk.unwrap_or(0);
+                    stats.storage_total += resource.maxdisk.unwrap_or(0);
+                }
+                _ => {}
+            }
+        }
+
+        stats
+    }
+
+    /// Total number of nodes.
+    pub fn nodes_total(&self) -> usize {
+        self.nodes_online + self.nodes_offline
+    }
+
+    /// Used fraction of the storage space, `None` if there is no storage.
+    pub fn storage_usage(&self) -> Option<f64> {
+        (self.storage_total > 0).then(|| self.storage_used as f64 / self.storage_total as f64)
+    }
+}
+
+/// Datacenter summary, showing node and guest states and the storage usage.
+///
+/// The `/cluster/resources` list is reloaded periodically.
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct DatacenterSummaryPanel {
+    /// URL path to load the resource list from.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(AttrValue::Static("/cluster/resources"))]
+    pub base_url: AttrValue,
+
+    /// Reload interval in milliseconds.
+    #[builder]
+    #[prop_or(10_000)]
+    pub interval: u32,
+}
+
+impl Default for DatacenterSummaryPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatacenterSummaryPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+pub enum Msg {
+    Loaded(ClusterStats),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxDatacenterSummaryPanel {
+    state: LoadableComponentState<()>,
+    stats: Option<ClusterStats>,
+}
+
+pwt::impl_deref_mut_property!(
+    ProxmoxDatacenterSummaryPanel,
+    state,
+    LoadableComponentState<()>
+);
+
+fn guest_status(counts: &GuestCounts) -> String {
+    tr!("{0} running, {1} stopped", counts.running, counts.stopped)
+}
+
+impl LoadableComponent for ProxmoxDatacenterSummaryPanel {
+    type Message = Msg;
+    type ViewState = ();
+    type Properties = DatacenterSummaryPanel;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        ctx.link().repeated_load(ctx.props().interval);
+        Self {
+            state: LoadableComponentState::new(),
+            stats: None,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let url = ctx.props().base_url.clone();
+        let link = ctx.link().clone();
+
+        Box::pin(async move {
+            let resources: Vec<ClusterResource> = http_get(url.as_str(), None).await?;
+            link.send_message(Msg::Loaded(ClusterStats::from_resources(&resources)));
+            Ok(())
+        })
+    }
+
+    fn update(&mut self, _ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Loaded(stats) => {
+                if self.stats.as_ref() == Some(&stats) {
+                    return false;
+                }
+                self.stats = Some(stats);
+                true
+            }
+        }
+    }
+
+    fn main_view(&self, _ctx: &LoadableComponentContext<Self>) -> Html {
+        let stats = self.stats.clone().unwrap_or_default();
+
+        let storage = match stats.storage_usage() {
+            Some(fraction) => MeterLabel::with_zero_optimum(tr!("Storage"))
+                .value(fraction as f32)
+                .status(format!(
+                    "{:.2}% ({} of {})",
+                    fraction * 100.0,
+                    HumanByte::from(stats.storage_used),
+                    HumanByte::from(stats.storage_total),
+                )),
+            None => MeterLabel::with_zero_optimum(tr!("Storage")).status(tr!("N/A")),
+        };
+
+        let content = Container::new()
+            .class("pwt-d-grid pwt-gap-2 pwt-align-items-center")
+            .style("grid-template-columns", "1fr 20px 1fr")
+            .style("height", "fit-content")
+            .padding(4)
+            .with_child(
+                StatusRow::new(tr!("Nodes online"))
+                    .icon_class("fa fa-fw fa-server")
+                    .status(format!("{} / {}", stats.nodes_online, stats.nodes_total())),
+            )
+            .with_child(
+                StatusRow::new(tr!("Nodes offline"))
+                    .style("grid-column", "3")
+                    .icon_class(classes!(
+                        "fa",
+                        "fa-fw",
+                        "fa-server",
+                        (stats.nodes_offline > 0).then_some("pwt-color-error")
+                    ))
+                    .status(stats.nodes_offline.to_string()),
+            )
+            .with_child(
+                StatusRow::new(tr!("Virtual Machines"))
+                    .icon_class("fa fa-fw fa-desktop")
+                    .status(guest_status(&stats.vms)),
+            )
+            .with_child(
+                StatusRow::new(tr!("LXC Containers"))
+                    .style("grid-column", "3")
+                    .icon_class("fa fa-fw fa-cube")
+                    .status(guest_status(&stats.containers)),
+            )
+            .with_child(Container::new().padding(2).style("grid-column", "1/-1"))
+            .with_child(
+                storage
+                    .animated(true)
+                    .style("grid-column", "1/-1")
+                    .icon_class("fa fa-fw fa-database"),
+            );
+
+        Panel::new()
+            .border(false)
+            .class(FlexFit)
+            .title(
+                Row::new()
+                    .class(pwt::css::AlignItems::Center)
+                    .gap(2)
+                    .with_child(Fa::new("book"))
+                    .with_child(tr!("Datacenter Summary"))
+                    .into_html(),
+            )
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<DatacenterSummaryPanel> for VNode {
+    fn from(value: DatacenterSummaryPanel) -> Self {
+        VComp::new::<LoadableComponentMaster<ProxmoxDatacenterSummaryPanel>>(Rc::new(value), None)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_cluster_stats() {
+        let resources: Vec<ClusterResource> = serde_json::from_value(json!([
+            { "id": "node/a", "type": "node", "node": "a", "status": "online" },
+            { "id": "node/b", "type": "node", "node": "b", "status": "offline" },
+            { "id": "qemu/100", "type": "qemu", "vmid": 100, "status": "running" },
+            { "id": "qemu/101", "type": "qemu", "vmid": 101, "status": "stopped" },
+            { "id": "qemu/102", "type": "qemu", "vmid": 102, "status": "stopped", "template": true },
+            { "id": "lxc/200", "type": "lxc", "vmid": 200, "status": "running" },
+            { "id": "storage/a/local", "type": "storage", "node": "a", "storage": "local",
+              "status": "available", "disk": 10, "maxdisk": 100, "shared": 0 },
+            { "id": "storage/b/local", "type": "storage", "node": "b", "storage": "local",
+              "status": "available", "disk": 10, "maxdisk": 100, "shared": 0 },
+            { "id": "storage/a/ceph", "type": "storage", "node": "a", "storage": "ceph",
+              "status": "available", "disk": 50, "maxdisk": 1000, "shared": 1 },
+            { "id": "storage/b/ceph", "type": "storage", "node": "b", "storage": "ceph",
+              "status": "available", "disk": 50, "maxdisk": 1000, "shared": 1 },
+        ]))
+        .unwrap();
+
+        let stats = ClusterStats::from_resources(&resources);
+        assert_eq!((stats.nodes_online, stats.nodes_offline), (1, 1));
+        assert_eq!(
+            stats.vms,
+            GuestCounts {
+                running: 1,
+                stopped: 1
+            }
+        );
+        assert_eq!(stats.containers.total(), 1);
+        assert_eq!((stats.storage_used, stats.storage_total), (70, 1200));
+    }
+}
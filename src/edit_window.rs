@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use anyhow::Error;
+use gloo_events::{EventListener, EventListenerOptions};
 use pwt::state::PersistentState;
 use serde_json::Value;
 
@@ -12,16 +13,17 @@ use proxmox_client::ApiResponseData;
 
 use pwt::css::{AlignItems, ColorScheme};
 use pwt::props::{
-    AsCssStylesMut, CssStyles, IntoSubmitCallback, RenderFn, SubmitCallback, WidgetStyleBuilder,
+    AsCssStylesMut, CssStyles, IntoLoadCallback, IntoSubmitCallback, LoadCallback, RenderFn,
+    SubmitCallback, WidgetStyleBuilder,
 };
 use pwt::touch::AdaptiveDialog;
 use pwt::widget::form::{Checkbox, Form, FormContext, Hidden, ResetButton, SubmitButton};
-use pwt::widget::{AlertDialog, Column, Dialog, Fa, Mask, Row};
+use pwt::widget::{AlertDialog, Column, ConfirmDialog, Dialog, Fa, Mask, Row};
 use pwt::{prelude::*, AsyncPool};
 
 use pwt_macros::builder;
 
-use crate::{ApiLoadCallback, IntoApiLoadCallback};
+use crate::{ApiError, ApiLoadCallback, IntoApiLoadCallback, SchemaForm};
 
 /// How an [EditWindow] anchors itself on screen.
 #[derive(Copy, Clone, PartialEq, Eq, Default)]
@@ -59,6 +61,15 @@ pub struct EditWindow {
     #[prop_or_default]
     pub loader: Option<ApiLoadCallback<Value>>,
 
+    /// Client side form defaults.
+    ///
+    /// Resolved before the loader (if any), while the form is masked. Useful for create
+    /// dialogs which need computed defaults (e.g. the next free VMID) without a loader URL.
+    /// Does not switch the window into edit mode.
+    #[builder_cb(IntoLoadCallback, into_load_callback, Value)]
+    #[prop_or_default]
+    pub defaults: Option<LoadCallback<Value>>,
+
     /// Submit button text.
     ///
     /// Default is Add, or Update if there is a loader.
@@ -140,6 +151,19 @@ pub struct EditWindow {
     #[prop_or_default]
     #[builder]
     pub inline_error: bool,
+
+    /// Ask for confirmation before discarding unsaved changes.
+    ///
+    /// Closing the window (close button or escape) with modified data opens a "Discard
+    /// changes?" dialog, and the browser asks before leaving or reloading the page.
+    #[prop_or_default]
+    #[builder]
+    pub confirm_discard: bool,
+
+    /// Called when the form changes between clean and modified (dirty) state.
+    #[builder_cb(IntoEventCallback, into_event_callback, bool)]
+    #[prop_or_default]
+    pub on_dirty_change: Option<Callback<bool>>,
 }
 
 impl AsCssStylesMut for EditWindow {
@@ -164,6 +188,16 @@ impl EditWindow {
         self
     }
 
+    /// Builder style method to use an input panel generated from a schema.
+    ///
+    /// This replaces the form renderer.
+    pub fn schema_form(mut self, form: SchemaForm) -> Self {
+        self.renderer = Some(RenderFn::new(move |form_ctx: &FormContext| {
+            form.render(form_ctx)
+        }));
+        self
+    }
+
     pub fn on_submit(mut self, callback: impl IntoSubmitCallback<FormContext>) -> Self {
         self.on_submit = callback.into_submit_callback();
         self
@@ -201,11 +235,40 @@ impl AdaptiveEditWindow {
     }
 }
 
+/// Keeps the browser from leaving the page without asking, as long as the listener is alive.
+pub(crate) fn unload_guard() -> EventListener {
+    EventListener::new_with_options(
+        &gloo_utils::window(),
+        "beforeunload",
+        EventListenerOptions::enable_prevent_default(),
+        |event| {
+            event.prevent_default();
+            // older browsers need a return value to show the dialog
+            let _ = js_sys::Reflect::set(event, &"returnValue".into(), &"".into());
+        },
+    )
+}
+
+/// The dialog asking whether unsaved changes should be discarded.
+pub(crate) fn discard_changes_dialog(
+    on_confirm: Callback<()>,
+    on_close: Callback<()>,
+) -> ConfirmDialog {
+    ConfirmDialog::default()
+        .confirm_message(tr!("Discard changes?"))
+        .on_confirm(on_confirm)
+        .on_close(on_close)
+}
+
 pub enum Msg {
     FormDataChange,
+    Close,
+    DiscardChanges,
+    CancelDiscard,
     Submit,
     SubmitResult(Result<(), Error>),
     Load,
+    DefaultsResult(Result<Value, Error>),
     LoadResult(Result<ApiResponseData<Value>, Error>),
     ClearError,
     ShowAdvanced(bool),
@@ -216,9 +279,40 @@ pub struct PwtEditWindow {
     loading: bool,
     form_ctx: FormContext,
     submit_error: Option<String>,
+    // form data at the time of the submit error
+    submit_error_data: Option<Value>,
     load_error: Option<String>,
     show_advanced: PersistentState<bool>,
     async_pool: AsyncPool,
+    dirty: bool,
+    show_discard_dialog: bool,
+    unload_guard: Option<EventListener>,
+}
+
+impl PwtEditWindow {
+    fn update_dirty(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let dirty = self.form_ctx.read().is_dirty();
+        if dirty == self.dirty {
+            return;
+        }
+        self.dirty = dirty;
+        self.unload_guard = (dirty && props.confirm_discard).then(unload_guard);
+        if let Some(on_dirty_change) = &props.on_dirty_change {
+            on_dirty_change.emit(dirty);
+        }
+    }
+
+    fn close(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        self.unload_guard = None;
+        if let Some(on_close) = &props.on_close {
+            on_close.emit(());
+        }
+        if let Some(on_done) = &props.on_done {
+            on_done.emit(());
+        }
+    }
 }
 
 impl Component for PwtEditWindow {
@@ -237,9 +331,13 @@ impl Component for PwtEditWindow {
             form_ctx,
             loading: false,
             submit_error: None,
+            submit_error_data: None,
             load_error: None,
             show_advanced,
             async_pool: AsyncPool::new(),
+            dirty: false,
+            show_discard_dialog: false,
+            unload_guard: None,
         }
     }
 
@@ -257,16 +355,37 @@ impl Component for PwtEditWindow {
                 true
             }
             Msg::Load => {
-                if let Some(loader) = props.loader.clone() {
+                let defaults = props.defaults.clone();
+                let loader = props.loader.clone();
+                if defaults.is_some() || loader.is_some() {
                     self.loading = true;
                     let link = ctx.link().clone();
                     self.async_pool.spawn(async move {
-                        let res = loader.apply().await;
-                        link.send_message(Msg::LoadResult(res));
+                        if let Some(defaults) = defaults {
+                            let res = defaults.apply().await;
+                            let failed = res.is_err();
+                            link.send_message(Msg::DefaultsResult(res));
+                            if failed {
+                                return;
+                            }
+                        }
+                        if let Some(loader) = loader {
+                            let res = loader.apply().await;
+                            link.send_message(Msg::LoadResult(res));
+                        }
                     });
                 }
                 true
             }
+            Msg::DefaultsResult(result) => {
+                // keep the mask if the loader still runs
+                self.loading = result.is_ok() && props.loader.is_some();
+                match result {
+                    Err(err) => self.load_error = Some(err.to_string()),
+                    Ok(value) => self.form_ctx.load_form(value),
+                }
+                true
+            }
             Msg::LoadResult(result) => {
                 self.loading = false;
                 match result {
@@ -284,15 +403,38 @@ impl Component for PwtEditWindow {
                 true
             }
             Msg::FormDataChange => {
-                if self.submit_error.is_some() {
+                // marking fields invalid also triggers a change, keep the error until the
+                // user modifies the data
+                if self.submit_error.is_some()
+                    && self.submit_error_data.as_ref() != Some(&self.form_ctx.get_submit_data())
+                {
                     self.submit_error = None;
+                    self.submit_error_data = None;
                 }
+                self.update_dirty(ctx);
                 if let Some(on_change) = &props.on_change {
                     on_change.emit(self.form_ctx.clone());
                 }
                 // Note: we redraw on any data change
                 true
             }
+            Msg::Close => {
+                if props.confirm_discard && self.dirty {
+                    self.show_discard_dialog = true;
+                    return true;
+                }
+                self.close(ctx);
+                false
+            }
+            Msg::DiscardChanges => {
+                self.show_discard_dialog = false;
+                self.close(ctx);
+                true
+            }
+            Msg::CancelDiscard => {
+                self.show_discard_dialog = false;
+                true
+            }
             Msg::Submit => {
                 if let Some(on_submit) = props.on_submit.clone() {
                     let link = ctx.link().clone();
@@ -310,12 +452,17 @@ impl Component for PwtEditWindow {
                 match result {
                     Ok(_) => {
                         self.submit_error = None;
+                        self.unload_guard = None;
                         if let Some(on_done) = &props.on_done {
                             on_done.emit(());
                         }
                     }
                     Err(err) => {
+                        if let Some(api_err) = ApiError::from_error(&err) {
+                            api_err.mark_form_fields(&self.form_ctx);
+                        }
                         self.submit_error = Some(err.to_string());
+                        self.submit_error_data = Some(self.form_ctx.get_submit_data());
                     }
                 }
                 true
@@ -418,23 +565,15 @@ impl Component for PwtEditWindow {
             .filter(|_| !props.inline_error)
             .map(|msg| AlertDialog::new(msg).on_close(ctx.link().callback(|_| Msg::ClearError)));
 
-        let on_close = {
-            let on_close = props.on_close.clone();
-            let on_done = props.on_done.clone();
+        let on_close = (props.on_close.is_some() || props.on_done.is_some())
+            .then(|| ctx.link().callback(|()| Msg::Close));
 
-            if on_close.is_some() || on_done.is_some() {
-                Some(move |()| {
-                    if let Some(on_close) = &on_close {
-                        on_close.emit(());
-                    }
-                    if let Some(on_done) = &on_done {
-                        on_done.emit(());
-                    }
-                })
-            } else {
-                None
-            }
-        };
+        let discard_dialog = self.show_discard_dialog.then(|| {
+            discard_changes_dialog(
+                ctx.link().callback(|_| Msg::DiscardChanges),
+                ctx.link().callback(|_| Msg::CancelDiscard),
+            )
+        });
 
         let load_err = self
             .load_error
@@ -459,6 +598,7 @@ impl Component for PwtEditWindow {
                 .with_child(form)
                 .with_optional_child(alert)
                 .with_optional_child(load_err)
+                .with_optional_child(discard_dialog)
                 .into()
         } else {
             Dialog::new(props.title.clone())
@@ -470,6 +610,7 @@ impl Component for PwtEditWindow {
                 .with_child(form)
                 .with_optional_child(alert)
                 .with_optional_child(load_err)
+                .with_optional_child(discard_dialog)
                 .into()
         }
     }
@@ -23,6 +23,8 @@ const MOUNT_POINT_ID: &str = "_mount_point_id_";
 const NOREPLICATE_FIELD_NAME: &str = "_noreplicate_";
 const DISK_SIZE_FIELD_NAME: &str = "_disk_size_";
 const IMAGE_STORAGE: &str = "_storage_";
+const MOUNT_TYPE: &str = "_mount_type_";
+const HOST_PATH: &str = "_host_path_";
 
 const VOLUME_PN: &str = "_volume";
 const MOUNT_PATH_PN: &str = "_mp";
@@ -36,6 +38,55 @@ const BACKUP_PN: &str = "_backup";
 const ACL_PN: &str = "_acl";
 const QUOTA_PN: &str = "_quota";
 
+/// What gets mounted into the container.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MountType {
+    /// A volume managed by a storage (`storage:volume`)
+    Volume,
+    /// A directory on the host (bind mount)
+    Bind,
+    /// A block device on the host (`/dev/...`)
+    Device,
+}
+
+impl MountType {
+    fn from_volume(volume: &str) -> Self {
+        if volume.starts_with("/dev/") {
+            MountType::Device
+        } else if volume_storage(volume).is_none() {
+            MountType::Bind
+        } else {
+            MountType::Volume
+        }
+    }
+
+    fn from_field(value: &str) -> Self {
+        match value {
+            "bind" => MountType::Bind,
+            "device" => MountType::Device,
+            _ => MountType::Volume,
+        }
+    }
+}
+
+fn validate_host_path(path: &str, mount_type: MountType) -> Result<(), anyhow::Error> {
+    if !path.starts_with('/') {
+        bail!(tr!("Path must be absolute."));
+    }
+    if path.split('/').any(|component| component == "..") {
+        bail!(tr!("Path must not contain '..'."));
+    }
+    match mount_type {
+        MountType::Device if !path.starts_with("/dev/") => {
+            bail!(tr!("Device path must start with '/dev/'."));
+        }
+        MountType::Bind if path == "/" || path.starts_with("/dev/") => {
+            bail!(tr!("Invalid bind mount source."));
+        }
+        _ => Ok(()),
+    }
+}
+
 #[derive(Properties, Clone, PartialEq)]
 struct MountPointPanel {
     name: Option<String>,
@@ -146,20 +197,18 @@ impl Component for MountPointComp {
             _ => String::new(),
         };
 
-        let is_bindmount = if props.unused_disk.is_some() {
-            false
+        let mount_type = if props.unused_disk.is_some() || props.rootfs {
+            MountType::Volume
+        } else if self.is_create {
+            MountType::from_field(&form_ctx.read().get_field_text(MOUNT_TYPE))
         } else {
             match state.record.get(VOLUME_PN) {
-                Some(Value::String(volume)) => {
-                    if volume.starts_with("/dev/") {
-                        false
-                    } else {
-                        volume_storage(volume).is_none()
-                    }
-                }
-                _ => false,
+                Some(Value::String(volume)) => MountType::from_volume(volume),
+                _ => MountType::Volume,
             }
         };
+        let is_bindmount = mount_type == MountType::Bind;
+        let create_volume = self.is_create && mount_type == MountType::Volume;
 
         let enable_quota = !(storage_type == "zfs"
             || storage_type == "zfspool"
@@ -196,12 +245,40 @@ impl Component for MountPointComp {
             .max((LxcConfigMpArray::MAX - 1) as u16)
             .validate(self.validate_id.clone());
 
+        let mount_type_label = tr!("Type");
+        let mount_type_field = Combobox::from_key_value_pairs([
+            ("volume", tr!("Storage Volume")),
+            ("bind", tr!("Bind Mount")),
+            ("device", tr!("Device Mount")),
+        ])
+        .name(MOUNT_TYPE)
+        .submit(false)
+        .required(true)
+        .default("volume");
+
+        let host_path_label = match mount_type {
+            MountType::Device => tr!("Device"),
+            _ => tr!("Source Path"),
+        };
+        let host_path_field = Field::new()
+            .name(HOST_PATH)
+            .key(format!("host_path_{mount_type:?}"))
+            .submit(false)
+            .required(!create_volume)
+            .disabled(create_volume)
+            .placeholder(match mount_type {
+                MountType::Device => "/dev/sdX",
+                _ => "/mnt/host/path",
+            })
+            .validate(move |path: &String| validate_host_path(path, mount_type));
+
         let storage_label = tr!("Storage");
         let storage_field = PveStorageSelector::new(props.node.clone())
             .remote(props.remote.clone())
             .name(IMAGE_STORAGE)
             .submit(false)
-            .required(true)
+            .disabled(!create_volume)
+            .required(create_volume)
             .content_types(Some(vec![StorageContent::Rootdir]))
             .on_change(ctx.link().callback(Msg::StorageInfo))
             .mobile(mobile);
@@ -210,7 +287,8 @@ impl Component for MountPointComp {
         let disk_size_field = Number::<f64>::new()
             .name(DISK_SIZE_FIELD_NAME)
             .submit(false)
-            .required(true)
+            .disabled(!create_volume)
+            .required(create_volume)
             .min(0.001)
             .max(128.0 * 1024.0)
             .default(32.0);
@@ -236,6 +314,13 @@ impl Component for MountPointComp {
         let readonly_label = tr!("Read-only");
         let readonly_field = Checkbox::new().switch(mobile).name(READONLY_PN);
 
+        let shared_label = tr!("Shared");
+        let shared_field = Checkbox::new()
+            .switch(mobile)
+            .name(SHARED_PN)
+            // only for mounts not managed by a storage
+            .disabled(mount_type == MountType::Volume);
+
         let quota_label = tr!("Enable quota");
         let quota_field = Checkbox::new()
             .switch(mobile)
@@ -266,8 +351,13 @@ impl Component for MountPointComp {
                 panel.add_field(mount_point_id_label, mount_point_id_field);
             } else if self.is_create {
                 panel.add_field(mount_point_id_label, mount_point_id_field);
-                panel.add_field(storage_label, storage_field);
-                panel.add_field(disk_size_label, disk_size_field);
+                panel.add_field(mount_type_label, mount_type_field);
+                if create_volume {
+                    panel.add_field(storage_label, storage_field);
+                    panel.add_field(disk_size_label, disk_size_field);
+                } else {
+                    panel.add_field(host_path_label, host_path_field);
+                }
             } else {
                 panel.add_custom_child(file_info_child);
             }
@@ -300,14 +390,22 @@ impl Component for MountPointComp {
                 skip_replication_field,
             );
             panel.add_single_line_field(true, false, readonly_label, readonly_field);
+            if !props.rootfs {
+                panel.add_single_line_field(true, false, shared_label, shared_field);
+            }
         } else {
             if props.unused_disk.is_some() {
                 panel.add_field(mount_point_id_label, mount_point_id_field);
                 panel.add_custom_child(file_info_child);
             } else if self.is_create {
                 panel.add_field(mount_point_id_label, mount_point_id_field);
-                panel.add_field(storage_label, storage_field);
-                panel.add_field(disk_size_label, disk_size_field);
+                panel.add_field(mount_type_label, mount_type_field);
+                if create_volume {
+                    panel.add_field(storage_label, storage_field);
+                    panel.add_field(disk_size_label, disk_size_field);
+                } else {
+                    panel.add_field(host_path_label, host_path_field);
+                }
             } else {
                 panel.add_custom_child(file_info_child);
             }
@@ -360,6 +458,15 @@ impl Component for MountPointComp {
                 skip_replication_label,
                 skip_replication_field,
             );
+            if !props.rootfs {
+                panel.add_field_with_options(
+                    pwt::widget::FieldPosition::Left,
+                    true,
+                    false,
+                    shared_label,
+                    shared_field,
+                );
+            }
             panel.add_field_with_options(
                 pwt::widget::FieldPosition::Large,
                 true,
@@ -446,6 +553,12 @@ fn mount_point_property(
                         }
                         _ => bail!("got invalid value for unused volume"),
                     }
+                } else if is_create
+                    && MountType::from_field(&form_ctx.read().get_field_text(MOUNT_TYPE))
+                        != MountType::Volume
+                {
+                    let host_path = form_ctx.read().get_field_text(HOST_PATH);
+                    data[VOLUME_PN] = host_path.into();
                 } else if is_create && data[VOLUME_PN].is_null() {
                     let image_storage = form_ctx.read().get_field_text(IMAGE_STORAGE);
                     let image_size =
@@ -585,3 +698,28 @@ fn volume_storage(volume: &str) -> Option<String> {
         None => None,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mount_type() {
+        assert_eq!(
+            MountType::from_volume("local-lvm:vm-100-disk-1"),
+            MountType::Volume
+        );
+        assert_eq!(MountType::from_volume("/mnt/data"), MountType::Bind);
+        assert_eq!(MountType::from_volume("/dev/sdb1"), MountType::Device);
+    }
+
+    #[test]
+    fn test_validate_host_path() {
+        assert!(validate_host_path("/mnt/data", MountType::Bind).is_ok());
+        assert!(validate_host_path("mnt/data", MountType::Bind).is_err());
+        assert!(validate_host_path("/mnt/../etc", MountType::Bind).is_err());
+        assert!(validate_host_path("/", MountType::Bind).is_err());
+        assert!(validate_host_path("/dev/sdb1", MountType::Device).is_ok());
+        assert!(validate_host_path("/mnt/data", MountType::Device).is_err());
+    }
+}
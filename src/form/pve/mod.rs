@@ -36,8 +36,19 @@ pub use qemu_machine_version_selector::QemuMachineVersionSelector;
 mod pve_network_selector;
 pub use pve_network_selector::PveNetworkSelector;
 
+mod pve_dir_mapping_selector;
+pub use pve_dir_mapping_selector::{dir_mapping_path, PveDirMappingSelector};
+
+mod pve_pci_device_selector;
+pub use pve_pci_device_selector::{PvePciDeviceSelector, PvePciMdevSelector};
+
+mod pve_usb_device_selector;
+pub use pve_usb_device_selector::{
+    usb_device_id, usb_port_id, PveUsbDeviceSelector, UsbDeviceSelectMode,
+};
+
 mod pve_storage_content_selector;
-pub use pve_storage_content_selector::PveStorageContentSelector;
+pub use pve_storage_content_selector::{PveStorageContentSelector, StorageContentSort};
 
 mod pve_vlan_field;
 pub use pve_vlan_field::PveVlanField;
@@ -64,15 +75,21 @@ pub use lxc_property::{
 mod qemu_property;
 pub use qemu_property::{
     extract_used_devices, qemu_acpi_property, qemu_agent_property, qemu_amd_sev_property,
-    qemu_bios_property, qemu_boot_property, qemu_cdrom_property, qemu_cpu_flags_property,
-    qemu_disk_property, qemu_display_property, qemu_efidisk_property, qemu_freeze_property,
-    qemu_hotplug_property, qemu_intel_tdx_property, qemu_kernel_scheduler_property,
-    qemu_kvm_property, qemu_localtime_property, qemu_machine_property, qemu_memory_property,
-    qemu_name_property, qemu_network_mtu_property, qemu_network_property, qemu_onboot_property,
-    qemu_ostype_property, qemu_protection_property, qemu_scsihw_property, qemu_smbios_property,
-    qemu_sockets_cores_property, qemu_spice_enhancement_property, qemu_startdate_property,
-    qemu_startup_property, qemu_tablet_property, qemu_tpmstate_property, qemu_vmstate_property,
-    qemu_vmstatestorage_property,
+    qemu_audio_property, qemu_bios_property, qemu_boot_property, qemu_cdrom_property,
+    qemu_cpu_flags_property, qemu_disk_property, qemu_display_property, qemu_efidisk_property,
+    qemu_freeze_property, qemu_hostpci_property, qemu_hotplug_property, qemu_intel_tdx_property,
+    qemu_kernel_scheduler_property, qemu_kvm_property, qemu_localtime_property,
+    qemu_machine_property, qemu_memory_property, qemu_name_property, qemu_network_mtu_property,
+    qemu_network_property, qemu_onboot_property, qemu_ostype_property, qemu_parallel_property,
+    qemu_protection_property, qemu_rng_property, qemu_scsihw_property, qemu_serial_property,
+    qemu_smbios_property, qemu_sockets_cores_property, qemu_spice_enhancement_property,
+    qemu_startdate_property, qemu_startup_property, qemu_tablet_property, qemu_tpmstate_property,
+    qemu_usb_property, qemu_virtiofs_property, qemu_vmstate_property, qemu_vmstatestorage_property,
+};
+
+pub(crate) use qemu_property::{
+    MAX_HOSTPCI_DEVICES, MAX_PARALLEL_PORTS, MAX_SERIAL_PORTS, MAX_USB_DEVICES,
+    MAX_VIRTIOFS_DEVICES,
 };
 
 pub mod firewall_property;
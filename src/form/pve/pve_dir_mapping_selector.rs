@@ -0,0 +1,200 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::Key;
+
+use pwt::prelude::*;
+
+use pwt::props::{FieldBuilder, LoadCallback, WidgetBuilder, WidgetStyleBuilder};
+use pwt::state::Store;
+use pwt::widget::{
+    data_table::{DataTable, DataTableColumn, DataTableHeader},
+    form::{Selector, SelectorRenderArgs},
+    GridPicker,
+};
+use pwt_macros::{builder, widget};
+
+use crate::http_get;
+use crate::pve_api_types::DirMapping;
+
+/// The host path of a directory mapping on `node`.
+///
+/// Mapping entries are property strings like `node=pve1,path=/mnt/share`.
+pub fn dir_mapping_path(mapping: &DirMapping, node: &str) -> Option<String> {
+    mapping.map.iter().find_map(|entry| {
+        let mut entry_node = None;
+        let mut path = None;
+        for part in entry.split(',') {
+            match part.split_once('=') {
+                Some(("node", value)) => entry_node = Some(value),
+                Some(("path", value)) => path = Some(value),
+                _ => {}
+            }
+        }
+        if entry_node == Some(node) {
+            path.map(String::from)
+        } else {
+            None
+        }
+    })
+}
+
+/// Selector for cluster wide directory mappings, used for virtiofs.
+///
+/// Only mappings with a path on `node` are listed.
+#[widget(comp=PveDirMappingSelectorComp, @input)]
+#[derive(Clone, Properties, PartialEq)]
+#[builder]
+pub struct PveDirMappingSelector {
+    /// The default value
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub default: Option<AttrValue>,
+
+    /// The node where the guest runs
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+}
+
+impl Default for PveDirMappingSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PveDirMappingSelector {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+/// List entry, with the path resolved for the selected node.
+#[derive(Clone, PartialEq)]
+struct DirMappingEntry {
+    id: String,
+    path: String,
+    description: String,
+}
+
+pub struct PveDirMappingSelectorComp {
+    store: Store<DirMappingEntry>,
+    load_callback: LoadCallback<Vec<DirMappingEntry>>,
+}
+
+impl PveDirMappingSelectorComp {
+    async fn get_mapping_list(node: AttrValue) -> Result<Vec<DirMappingEntry>, Error> {
+        let mappings: Vec<DirMapping> = http_get("/cluster/mapping/dir", None).await?;
+        let mut list: Vec<DirMappingEntry> = mappings
+            .into_iter()
+            .filter_map(|mapping| {
+                let path = dir_mapping_path(&mapping, &node)?;
+                Some(DirMappingEntry {
+                    id: mapping.id,
+                    path,
+                    description: mapping.description.unwrap_or_default(),
+                })
+            })
+            .collect();
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(list)
+    }
+
+    fn create_load_callback(ctx: &yew::Context<Self>) -> LoadCallback<Vec<DirMappingEntry>> {
+        let node = ctx
+            .props()
+            .node
+            .clone()
+            .unwrap_or(AttrValue::from("localhost"));
+        (move || Self::get_mapping_list(node.clone())).into()
+    }
+}
+
+impl Component for PveDirMappingSelectorComp {
+    type Message = ();
+    type Properties = PveDirMappingSelector;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        Self {
+            store: Store::with_extract_key(|entry: &DirMappingEntry| Key::from(entry.id.as_str())),
+            load_callback: Self::create_load_callback(ctx),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        if old_props.node != ctx.props().node {
+            self.load_callback = Self::create_load_callback(ctx);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+
+        Selector::new(
+            self.store.clone(),
+            move |args: &SelectorRenderArgs<Store<DirMappingEntry>>| {
+                GridPicker::new(
+                    DataTable::new(columns(), args.store.clone())
+                        .min_width(500)
+                        .header_focusable(false)
+                        .class(pwt::css::FlexFit),
+                )
+                .selection(args.selection.clone())
+                .on_select(args.controller.on_select_callback())
+                .into()
+            },
+        )
+        .loader(self.load_callback.clone())
+        .with_std_props(&props.std_props)
+        .with_input_props(&props.input_props)
+        .default(props.default.clone())
+        .into()
+    }
+}
+
+fn columns() -> Rc<Vec<DataTableHeader<DirMappingEntry>>> {
+    Rc::new(vec![
+        DataTableColumn::new(tr!("Directory ID"))
+            .width("150px")
+            .get_property(|entry: &DirMappingEntry| &entry.id)
+            .sort_order(true)
+            .into(),
+        DataTableColumn::new(tr!("Path"))
+            .width("200px")
+            .get_property(|entry: &DirMappingEntry| &entry.path)
+            .into(),
+        DataTableColumn::new(tr!("Comment"))
+            .flex(1)
+            .get_property(|entry: &DirMappingEntry| &entry.description)
+            .into(),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dir_mapping_path() {
+        let mapping = DirMapping {
+            id: "share".into(),
+            description: None,
+            map: vec![
+                "node=pve1,path=/mnt/share".into(),
+                "path=/srv/share,node=pve2".into(),
+            ],
+        };
+        assert_eq!(
+            dir_mapping_path(&mapping, "pve1").as_deref(),
+            Some("/mnt/share")
+        );
+        assert_eq!(
+            dir_mapping_path(&mapping, "pve2").as_deref(),
+            Some("/srv/share")
+        );
+        assert_eq!(dir_mapping_path(&mapping, "pve3"), None);
+    }
+}
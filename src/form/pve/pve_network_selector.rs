@@ -1,7 +1,8 @@
+use std::cmp::Ordering;
 use std::rc::Rc;
 
 use anyhow::Error;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::Key;
@@ -22,6 +23,9 @@ use crate::{http_get, percent_encoding::percent_encode_component};
 
 use pve_api_types::{ListNetworksType, NetworkInterface};
 
+/// Selector for the bridges of a node, used for guest network devices.
+///
+/// Optionally includes the SDN vnets, listed after the local bridges.
 #[widget(comp=PveNetworkSelectorComp, @input)]
 #[derive(Clone, Properties, PartialEq)]
 #[builder]
@@ -50,6 +54,19 @@ pub struct PveNetworkSelector {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or(Some(ListNetworksType::AnyBridge))]
     pub interface_type: Option<ListNetworksType>,
+
+    /// Show the SDN zone of vnets.
+    ///
+    /// Only vnets deployed on the node are listed, as they are part of the node's network
+    /// list. This adds the zone information from the cluster wide vnet configuration.
+    #[builder]
+    #[prop_or(false)]
+    pub include_sdn: bool,
+
+    /// Only list VLAN aware bridges and vnets.
+    #[builder]
+    #[prop_or(false)]
+    pub vlan_aware_only: bool,
 }
 
 impl Default for PveNetworkSelector {
@@ -64,9 +81,56 @@ impl PveNetworkSelector {
     }
 }
 
+/// A network a guest can be attached to.
+#[derive(Clone, PartialEq)]
+struct NetworkEntry {
+    name: String,
+    /// The SDN zone, `None` for local bridges.
+    zone: Option<String>,
+    active: Option<bool>,
+    vlan_aware: bool,
+    comment: String,
+}
+
+impl From<NetworkInterface> for NetworkEntry {
+    fn from(iface: NetworkInterface) -> Self {
+        Self {
+            name: iface.iface,
+            zone: None,
+            active: iface.active,
+            vlan_aware: iface.bridge_vlan_aware.unwrap_or(false),
+            comment: iface.comments.unwrap_or_default(),
+        }
+    }
+}
+
+/// Parse an entry of the SDN vnet list (`GET /cluster/sdn/vnets`).
+fn parse_vnet(data: &Value) -> Option<NetworkEntry> {
+    let vlan_aware = match &data["vlanaware"] {
+        Value::Bool(value) => *value,
+        Value::Number(value) => value.as_u64() == Some(1),
+        _ => false,
+    };
+    Some(NetworkEntry {
+        name: data["vnet"].as_str()?.to_string(),
+        zone: data["zone"].as_str().map(String::from),
+        active: None,
+        vlan_aware,
+        comment: data["alias"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// Sort local bridges first, followed by the SDN vnets.
+fn compare_entries(a: &NetworkEntry, b: &NetworkEntry) -> Ordering {
+    a.zone
+        .is_some()
+        .cmp(&b.zone.is_some())
+        .then_with(|| a.name.cmp(&b.name))
+}
+
 pub struct PveNetworkSelectorComp {
-    store: Store<NetworkInterface>,
-    load_callback: LoadCallback<Vec<NetworkInterface>>,
+    store: Store<NetworkEntry>,
+    load_callback: LoadCallback<Vec<NetworkEntry>>,
 }
 
 impl PveNetworkSelectorComp {
@@ -74,32 +138,80 @@ impl PveNetworkSelectorComp {
         node: AttrValue,
         remote: Option<AttrValue>,
         ty: Option<ListNetworksType>,
-    ) -> Result<Vec<NetworkInterface>, Error> {
-        let url = if let Some(remote) = &remote {
-            // fixme: is this url correct?
-            format!(
-                "/pve/remotes/{}/nodes/{}/network",
-                percent_encode_component(&node),
-                percent_encode_component(remote),
+        include_sdn: bool,
+        vlan_aware_only: bool,
+    ) -> Result<Vec<NetworkEntry>, Error> {
+        let (url, sdn_url) = if let Some(remote) = &remote {
+            // fixme: are these urls correct?
+            (
+                format!(
+                    "/pve/remotes/{}/nodes/{}/network",
+                    percent_encode_component(remote),
+                    percent_encode_component(&node),
+                ),
+                format!(
+                    "/pve/remotes/{}/cluster/sdn/vnets",
+                    percent_encode_component(remote)
+                ),
             )
         } else {
-            format!("/nodes/{}/network", percent_encode_component(&node))
+            (
+                format!("/nodes/{}/network", percent_encode_component(&node)),
+                String::from("/cluster/sdn/vnets"),
+            )
         };
 
         let param = ty.map(|ty| json!({"type": ty}));
 
-        let mut interfaces: Vec<NetworkInterface> = http_get(url, param).await?;
-        interfaces.sort_by(|a, b| a.iface.cmp(&b.iface));
-        Ok(interfaces)
+        let interfaces: Vec<NetworkInterface> = http_get(url, param).await?;
+        let mut list: Vec<NetworkEntry> = interfaces.into_iter().map(NetworkEntry::from).collect();
+
+        if include_sdn {
+            // SDN may not be configured at all, so errors are not fatal
+            match http_get::<Vec<Value>>(sdn_url, None).await {
+                Ok(vnets) => {
+                    // vnets of zones not deployed on the node are not part of the node's
+                    // network list, and cannot be used there
+                    for vnet in vnets.iter().filter_map(parse_vnet) {
+                        if let Some(entry) = list.iter_mut().find(|entry| entry.name == vnet.name) {
+                            entry.zone = vnet.zone;
+                            entry.vlan_aware |= vnet.vlan_aware;
+                            if entry.comment.is_empty() {
+                                entry.comment = vnet.comment;
+                            }
+                        }
+                    }
+                }
+                Err(err) => log::info!("unable to load SDN vnets: {err}"),
+            }
+        }
+
+        if vlan_aware_only {
+            list.retain(|entry| entry.vlan_aware);
+        }
+
+        list.sort_by(compare_entries);
+        Ok(list)
     }
 
-    fn create_load_callback(ctx: &yew::Context<Self>) -> LoadCallback<Vec<NetworkInterface>> {
+    fn create_load_callback(ctx: &yew::Context<Self>) -> LoadCallback<Vec<NetworkEntry>> {
         let props = ctx.props();
         let node = props.node.clone().unwrap_or(AttrValue::from("localhost"));
         let remote = props.remote.clone();
         let ty = props.interface_type;
+        let include_sdn = props.include_sdn;
+        let vlan_aware_only = props.vlan_aware_only;
 
-        (move || Self::get_network_list(node.clone(), remote.clone(), ty)).into()
+        (move || {
+            Self::get_network_list(
+                node.clone(),
+                remote.clone(),
+                ty,
+                include_sdn,
+                vlan_aware_only,
+            )
+        })
+        .into()
     }
 }
 
@@ -109,16 +221,19 @@ impl Component for PveNetworkSelectorComp {
 
     fn create(ctx: &yew::Context<Self>) -> Self {
         Self {
-            store: Store::with_extract_key(|iface: &NetworkInterface| {
-                Key::from(iface.iface.as_str())
-            }),
+            store: Store::with_extract_key(|entry: &NetworkEntry| Key::from(entry.name.as_str())),
             load_callback: Self::create_load_callback(ctx),
         }
     }
 
     fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
         let props = ctx.props();
-        if old_props.node != props.node || old_props.interface_type != props.interface_type {
+        if old_props.node != props.node
+            || old_props.remote != props.remote
+            || old_props.interface_type != props.interface_type
+            || old_props.include_sdn != props.include_sdn
+            || old_props.vlan_aware_only != props.vlan_aware_only
+        {
             self.load_callback = Self::create_load_callback(ctx);
         }
         true
@@ -136,16 +251,17 @@ impl Component for PveNetworkSelectorComp {
                         .read()
                         .iter()
                         .find(|e| key == store.extract_key(e))
-                        .map(|e| e.iface.clone().into());
+                        .map(|e| e.name.clone().into());
                     on_change.emit(result);
                 }
             }
         };
+        let include_sdn = props.include_sdn;
         Selector::new(
             self.store.clone(),
-            move |args: &SelectorRenderArgs<Store<NetworkInterface>>| {
+            move |args: &SelectorRenderArgs<Store<NetworkEntry>>| {
                 GridPicker::new(
-                    DataTable::new(columns(), args.store.clone())
+                    DataTable::new(columns(include_sdn), args.store.clone())
                         .min_width(300)
                         .header_focusable(false)
                         .class(pwt::css::FlexFit),
@@ -165,19 +281,79 @@ impl Component for PveNetworkSelectorComp {
     }
 }
 
-fn columns() -> Rc<Vec<DataTableHeader<NetworkInterface>>> {
-    Rc::new(vec![
-        DataTableColumn::new(tr!("Bridge"))
-            .get_property(|entry: &NetworkInterface| &entry.iface)
-            .sort_order(true)
-            .into(),
+fn columns(include_sdn: bool) -> Rc<Vec<DataTableHeader<NetworkEntry>>> {
+    let mut columns = vec![DataTableColumn::new(tr!("Bridge"))
+        .get_property(|entry: &NetworkEntry| &entry.name)
+        // keep the local bridges and the vnets grouped
+        .sorter(compare_entries)
+        .sort_order(true)
+        .into()];
+
+    if include_sdn {
+        columns.push(
+            DataTableColumn::new(tr!("SDN Zone"))
+                .render(|entry: &NetworkEntry| html! {entry.zone.as_deref().unwrap_or("-")})
+                .into(),
+        );
+    }
+
+    columns.extend([
         DataTableColumn::new(tr!("Active"))
-            .render(|entry: &NetworkInterface| render_boolean(entry.active.unwrap_or(false)).into())
+            .render(|entry: &NetworkEntry| match (&entry.zone, entry.active) {
+                (Some(_), None) => html! {"-"},
+                (_, active) => render_boolean(active.unwrap_or(false)).into(),
+            })
+            .into(),
+        DataTableColumn::new(tr!("VLAN aware"))
+            .render(|entry: &NetworkEntry| render_boolean(entry.vlan_aware).into())
             .into(),
         DataTableColumn::new(tr!("Comment"))
-            .render(
-                |entry: &NetworkInterface| html! {entry.comments.as_deref().unwrap_or_default()},
-            )
+            .render(|entry: &NetworkEntry| html! {&entry.comment})
             .into(),
-    ])
+    ]);
+
+    Rc::new(columns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vnet() {
+        let vnet = parse_vnet(&json!({
+            "vnet": "vnet1",
+            "zone": "zone1",
+            "alias": "Lab network",
+            "vlanaware": 1,
+            "type": "vnet",
+        }))
+        .unwrap();
+        assert_eq!(vnet.name, "vnet1");
+        assert_eq!(vnet.zone.as_deref(), Some("zone1"));
+        assert!(vnet.vlan_aware);
+        assert_eq!(vnet.comment, "Lab network");
+
+        assert!(parse_vnet(&json!({ "zone": "zone1" })).is_none());
+    }
+
+    #[test]
+    fn test_compare_entries() {
+        let entry = |name: &str, zone: Option<&str>| NetworkEntry {
+            name: name.to_string(),
+            zone: zone.map(String::from),
+            active: None,
+            vlan_aware: false,
+            comment: String::new(),
+        };
+        let mut list = vec![
+            entry("vnet0", Some("zone1")),
+            entry("vmbr1", None),
+            entry("abc", Some("zone1")),
+            entry("vmbr0", None),
+        ];
+        list.sort_by(compare_entries);
+        let names: Vec<&str> = list.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["vmbr0", "vmbr1", "abc", "vnet0"]);
+    }
 }
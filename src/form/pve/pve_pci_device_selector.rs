@@ -0,0 +1,278 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::Key;
+
+use pwt::prelude::*;
+
+use pwt::props::{FieldBuilder, LoadCallback, WidgetBuilder, WidgetStyleBuilder};
+use pwt::state::Store;
+use pwt::widget::{
+    data_table::{DataTable, DataTableColumn, DataTableHeader},
+    form::{Selector, SelectorRenderArgs},
+    GridPicker,
+};
+use pwt_macros::{builder, widget};
+
+use crate::pve_api_types::{PciDevice, PciMdevType};
+use crate::utils::render_boolean;
+use crate::{http_get, percent_encoding::percent_encode_component};
+
+/// Selector for host PCI devices, used for PCI passthrough.
+#[widget(comp=PvePciDeviceSelectorComp, @input)]
+#[derive(Clone, Properties, PartialEq)]
+#[builder]
+pub struct PvePciDeviceSelector {
+    /// The default value
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub default: Option<AttrValue>,
+
+    /// Change callback, called with the selected device.
+    #[builder_cb(IntoEventCallback, into_event_callback, Option<PciDevice>)]
+    #[prop_or_default]
+    pub on_change: Option<Callback<Option<PciDevice>>>,
+
+    /// The node to list the devices from
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+}
+
+impl Default for PvePciDeviceSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PvePciDeviceSelector {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+pub struct PvePciDeviceSelectorComp {
+    store: Store<PciDevice>,
+    load_callback: LoadCallback<Vec<PciDevice>>,
+}
+
+impl PvePciDeviceSelectorComp {
+    async fn get_device_list(node: AttrValue) -> Result<Vec<PciDevice>, Error> {
+        let url = format!("/nodes/{}/hardware/pci", percent_encode_component(&node));
+        // list all devices, not only the ones commonly used for passthrough
+        let param = serde_json::json!({ "pci-class-blacklist": "" });
+        let mut devices: Vec<PciDevice> = http_get(url, Some(param)).await?;
+        devices.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(devices)
+    }
+
+    fn create_load_callback(ctx: &yew::Context<Self>) -> LoadCallback<Vec<PciDevice>> {
+        let node = ctx
+            .props()
+            .node
+            .clone()
+            .unwrap_or(AttrValue::from("localhost"));
+        (move || Self::get_device_list(node.clone())).into()
+    }
+}
+
+impl Component for PvePciDeviceSelectorComp {
+    type Message = ();
+    type Properties = PvePciDeviceSelector;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        Self {
+            store: Store::with_extract_key(|device: &PciDevice| Key::from(device.id.as_str())),
+            load_callback: Self::create_load_callback(ctx),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        if old_props.node != ctx.props().node {
+            self.load_callback = Self::create_load_callback(ctx);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+
+        let on_change = {
+            let on_change = props.on_change.clone();
+            let store = self.store.clone();
+            move |key: Key| {
+                if let Some(on_change) = &on_change {
+                    let result = store.read().lookup_record(&key).cloned();
+                    on_change.emit(result);
+                }
+            }
+        };
+        Selector::new(
+            self.store.clone(),
+            move |args: &SelectorRenderArgs<Store<PciDevice>>| {
+                GridPicker::new(
+                    DataTable::new(columns(), args.store.clone())
+                        .min_width(600)
+                        .header_focusable(false)
+                        .class(pwt::css::FlexFit),
+                )
+                .selection(args.selection.clone())
+                .on_select(args.controller.on_select_callback())
+                .into()
+            },
+        )
+        .loader(self.load_callback.clone())
+        .with_std_props(&props.std_props)
+        .with_input_props(&props.input_props)
+        .on_change(on_change)
+        .default(props.default.clone())
+        .into()
+    }
+}
+
+fn render_iommu_group(group: i64) -> String {
+    if group < 0 {
+        tr!("none")
+    } else {
+        group.to_string()
+    }
+}
+
+fn columns() -> Rc<Vec<DataTableHeader<PciDevice>>> {
+    Rc::new(vec![
+        DataTableColumn::new(tr!("ID"))
+            .width("120px")
+            .get_property(|entry: &PciDevice| &entry.id)
+            .sort_order(true)
+            .into(),
+        DataTableColumn::new(tr!("IOMMU Group"))
+            .width("100px")
+            .justify("right")
+            .render(|entry: &PciDevice| render_iommu_group(entry.iommugroup).into())
+            .sorter(|a: &PciDevice, b: &PciDevice| a.iommugroup.cmp(&b.iommugroup))
+            .into(),
+        DataTableColumn::new(tr!("Vendor"))
+            .width("200px")
+            .render(|entry: &PciDevice| html! {entry.vendor_name.as_deref().unwrap_or_default()})
+            .into(),
+        DataTableColumn::new(tr!("Device"))
+            .flex(1)
+            .render(|entry: &PciDevice| html! {entry.device_name.as_deref().unwrap_or_default()})
+            .into(),
+        DataTableColumn::new(tr!("Mediated Devices"))
+            .width("130px")
+            .render(|entry: &PciDevice| render_boolean(entry.mdev.unwrap_or(false)).into())
+            .into(),
+    ])
+}
+
+/// Selector for the mediated device types of a host PCI device.
+#[widget(comp=PvePciMdevSelectorComp, @input)]
+#[derive(Clone, Properties, PartialEq)]
+#[builder]
+pub struct PvePciMdevSelector {
+    /// The default value
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub default: Option<AttrValue>,
+
+    /// The node of the PCI device
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+
+    /// The PCI ID of the device.
+    pub pci_id: AttrValue,
+}
+
+impl PvePciMdevSelector {
+    pub fn new(pci_id: impl Into<AttrValue>) -> Self {
+        yew::props!(Self {
+            pci_id: pci_id.into()
+        })
+    }
+}
+
+pub struct PvePciMdevSelectorComp {
+    store: Store<PciMdevType>,
+    load_callback: LoadCallback<Vec<PciMdevType>>,
+}
+
+impl PvePciMdevSelectorComp {
+    fn create_load_callback(ctx: &yew::Context<Self>) -> LoadCallback<Vec<PciMdevType>> {
+        let props = ctx.props();
+        let url = format!(
+            "/nodes/{}/hardware/pci/{}/mdev",
+            percent_encode_component(props.node.as_deref().unwrap_or("localhost")),
+            percent_encode_component(&props.pci_id),
+        );
+        (move || http_get(url.clone(), None)).into()
+    }
+}
+
+impl Component for PvePciMdevSelectorComp {
+    type Message = ();
+    type Properties = PvePciMdevSelector;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        Self {
+            store: Store::with_extract_key(|mdev: &PciMdevType| Key::from(mdev.ty.as_str())),
+            load_callback: Self::create_load_callback(ctx),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if old_props.node != props.node || old_props.pci_id != props.pci_id {
+            self.load_callback = Self::create_load_callback(ctx);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+
+        Selector::new(
+            self.store.clone(),
+            move |args: &SelectorRenderArgs<Store<PciMdevType>>| {
+                GridPicker::new(
+                    DataTable::new(mdev_columns(), args.store.clone())
+                        .min_width(500)
+                        .header_focusable(false)
+                        .class(pwt::css::FlexFit),
+                )
+                .selection(args.selection.clone())
+                .on_select(args.controller.on_select_callback())
+                .into()
+            },
+        )
+        .loader(self.load_callback.clone())
+        .with_std_props(&props.std_props)
+        .with_input_props(&props.input_props)
+        .default(props.default.clone())
+        .into()
+    }
+}
+
+fn mdev_columns() -> Rc<Vec<DataTableHeader<PciMdevType>>> {
+    Rc::new(vec![
+        DataTableColumn::new(tr!("Type"))
+            .width("150px")
+            .get_property(|entry: &PciMdevType| &entry.ty)
+            .sort_order(true)
+            .into(),
+        DataTableColumn::new(tr!("Available"))
+            .width("80px")
+            .justify("right")
+            .render(|entry: &PciMdevType| entry.available.to_string().into())
+            .into(),
+        DataTableColumn::new(tr!("Description"))
+            .flex(1)
+            .render(|entry: &PciMdevType| {
+                html! {entry.description.as_deref().or(entry.name.as_deref()).unwrap_or_default()}
+            })
+            .into(),
+    ])
+}
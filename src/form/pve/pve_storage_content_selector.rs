@@ -16,7 +16,7 @@ use pwt::props::{FieldBuilder, LoadCallback, WidgetBuilder, WidgetStyleBuilder};
 use pwt::state::Store;
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
 use pwt::widget::form::{Selector, SelectorRenderArgs};
-use pwt::widget::{Column, Container, Row};
+use pwt::widget::{Container, Row, Tooltip};
 
 use pwt_macros::{builder, widget};
 
@@ -24,6 +24,19 @@ use crate::http_get;
 use crate::layout::list_tile::title_subtitle_column;
 use crate::percent_encoding::percent_encode_component;
 use crate::pve_api_types::StorageEntry;
+use crate::utils::render_epoch_short;
+
+/// Sort order of the content list.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StorageContentSort {
+    /// Sort by volume name.
+    #[default]
+    Name,
+    /// Newest first, for example to find the latest backup.
+    Newest,
+    /// Largest first.
+    Size,
+}
 
 #[widget(comp=PveStorageContentSelectorComp, @input)]
 #[derive(Clone, Properties, PartialEq)]
@@ -68,6 +81,11 @@ pub struct PveStorageContentSelector {
     #[prop_or(false)]
     pub display_volid: bool,
 
+    /// Initial sort order of the list.
+    #[builder]
+    #[prop_or_default]
+    pub sort: StorageContentSort,
+
     /// Layout for mobile devices.
     #[builder]
     #[prop_or(false)]
@@ -162,19 +180,26 @@ impl Component for PveStorageContentSelectorComp {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
         let mobile = props.mobile;
+        let sort = props.sort;
         Selector::new(
             self.store.clone(),
             move |args: &SelectorRenderArgs<Store<StorageEntry>>| {
                 GridPicker::new(
                     DataTable::new(
-                        if mobile { columns_mobile() } else { columns() },
+                        if mobile {
+                            columns_mobile(sort)
+                        } else {
+                            columns(sort)
+                        },
                         args.store.clone(),
                     )
                     .show_header(!mobile)
-                    .min_width(300)
+                    .min_width(if mobile { 300 } else { 500 })
                     .header_focusable(false)
                     .class(pwt::css::FlexFit),
                 )
+                // type to filter, lists can be very long (e.g. ISO images, backups)
+                .show_filter(true)
                 .selection(args.selection.clone())
                 .on_select(args.controller.on_select_callback())
                 .into()
@@ -210,52 +235,104 @@ fn volume_name(entry: &StorageEntry) -> &str {
         Some((_, name)) => name,
     }
 }
-fn columns_mobile() -> Rc<Vec<DataTableHeader<StorageEntry>>> {
-    Rc::new(vec![DataTableColumn::new(tr!("Name"))
+
+/// Returns the used size if the volume is thin provisioned.
+fn thin_provisioned_usage(entry: &StorageEntry) -> Option<i64> {
+    entry.used.filter(|used| *used < entry.size)
+}
+
+fn render_size(entry: &StorageEntry) -> Html {
+    let size = HumanByte::new_decimal(entry.size as f64);
+    match thin_provisioned_usage(entry) {
+        Some(used) => Tooltip::new(format!("{size} *"))
+            .tip(tr!(
+                "Thin provisioned, currently uses {0}.",
+                HumanByte::new_decimal(used as f64)
+            ))
+            .into(),
+        None => size.to_string().into(),
+    }
+}
+
+fn render_ctime(entry: &StorageEntry) -> String {
+    entry.ctime.map(render_epoch_short).unwrap_or_default()
+}
+
+fn columns_mobile(sort: StorageContentSort) -> Rc<Vec<DataTableHeader<StorageEntry>>> {
+    let mut column = DataTableColumn::new(tr!("Name"))
         .get_property(|entry: &StorageEntry| volume_name(entry))
-        .sort_order(true)
-        .render(|entry: &StorageEntry| {
-            Column::new()
-                .with_child(Container::new().with_child(volume_name(entry)))
-                .with_child(
-                    Row::new()
-                        .gap(1)
-                        .with_child(Container::new().with_child(&entry.format))
-                        .with_flex_spacer()
-                        .with_child(
-                            Container::new().with_child(HumanByte::new_decimal(entry.size as f64)),
-                        ),
-                )
-                .into()
-        })
         .render(|entry: &StorageEntry| {
+            let subtitle = match entry.ctime {
+                Some(_) => format!("{}, {}", entry.format, render_ctime(entry)),
+                None => entry.format.clone(),
+            };
             Row::new()
-                .with_child(title_subtitle_column(
-                    volume_name(entry),
-                    entry.format.clone(),
-                ))
+                .with_child(title_subtitle_column(volume_name(entry), subtitle))
                 .with_flex_spacer()
-                .with_child(Container::new().with_child(HumanByte::new_decimal(entry.size as f64)))
+                .with_child(Container::new().with_child(render_size(entry)))
                 .into()
-        })
-        .into()])
+        });
+
+    column = match sort {
+        StorageContentSort::Name => column.sort_order(true),
+        StorageContentSort::Newest => column
+            .sorter(|a: &StorageEntry, b: &StorageEntry| a.ctime.cmp(&b.ctime))
+            .sort_order(false),
+        StorageContentSort::Size => column
+            .sorter(|a: &StorageEntry, b: &StorageEntry| a.size.cmp(&b.size))
+            .sort_order(false),
+    };
+
+    Rc::new(vec![column.into()])
 }
 
-fn columns() -> Rc<Vec<DataTableHeader<StorageEntry>>> {
+fn columns(sort: StorageContentSort) -> Rc<Vec<DataTableHeader<StorageEntry>>> {
     Rc::new(vec![
         DataTableColumn::new(tr!("Name"))
-            .get_property(|entry: &StorageEntry| match entry.volid.split_once('/') {
-                None => entry.volid.as_str(),
-                Some((_, name)) => name,
-            })
-            .sort_order(true)
+            .flex(1)
+            .get_property(|entry: &StorageEntry| volume_name(entry))
+            .sort_order((sort == StorageContentSort::Name).then_some(true))
+            .into(),
+        DataTableColumn::new(tr!("Date"))
+            .width("150px")
+            .sorter(|a: &StorageEntry, b: &StorageEntry| a.ctime.cmp(&b.ctime))
+            .sort_order((sort == StorageContentSort::Newest).then_some(false))
+            .render(|entry: &StorageEntry| render_ctime(entry).into())
             .into(),
         DataTableColumn::new(tr!("Format"))
+            .width("80px")
             .get_property(|entry: &StorageEntry| &entry.format)
             .into(),
         DataTableColumn::new(tr!("Size"))
+            .width("100px")
+            .justify("right")
             .sorter(|a: &StorageEntry, b: &StorageEntry| a.size.cmp(&b.size))
-            .render(|entry: &StorageEntry| HumanByte::new_decimal(entry.size as f64).into())
+            .sort_order((sort == StorageContentSort::Size).then_some(false))
+            .render(render_size)
             .into(),
     ])
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_thin_provisioned_usage() {
+        let mut entry = StorageEntry {
+            format: "raw".into(),
+            content: "images".into(),
+            size: 32 * 1024 * 1024 * 1024,
+            volid: "local-lvm:vm-100-disk-0".into(),
+            ctime: None,
+            used: None,
+        };
+        assert_eq!(thin_provisioned_usage(&entry), None);
+
+        entry.used = Some(entry.size);
+        assert_eq!(thin_provisioned_usage(&entry), None);
+
+        entry.used = Some(1024);
+        assert_eq!(thin_provisioned_usage(&entry), Some(1024));
+    }
+}
@@ -1,11 +1,11 @@
 use std::rc::Rc;
 
-use anyhow::{format_err, Error};
+use anyhow::{bail, format_err, Error};
 use serde_json::json;
 
 use proxmox_human_byte::HumanByte;
 
-use pve_api_types::{StorageContent, StorageInfo};
+use pve_api_types::{StorageContent, StorageInfo, StorageInfoFormatsDefault};
 
 use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::Key;
@@ -55,6 +55,21 @@ pub struct PveStorageSelector {
     #[prop_or(true)]
     pub include_select_existing: bool,
 
+    /// Only show shared storages.
+    #[builder]
+    #[prop_or(false)]
+    pub shared_only: bool,
+
+    /// Only show storages which support snapshots.
+    #[builder]
+    #[prop_or(false)]
+    pub snapshot_capable: bool,
+
+    /// Only show storages supporting this image format.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub format: Option<StorageInfoFormatsDefault>,
+
     /// If set, automatically selects the first value from the store (if no default is selected)
     #[builder]
     #[prop_or(false)]
@@ -74,6 +89,65 @@ impl PveStorageSelector {
     }
 }
 
+/// Capability filters, applied to the storage list.
+#[derive(Clone, Copy, PartialEq)]
+struct StorageFilter {
+    include_select_existing: bool,
+    shared_only: bool,
+    snapshot_capable: bool,
+    format: Option<StorageInfoFormatsDefault>,
+}
+
+impl StorageFilter {
+    fn new(props: &PveStorageSelector) -> Self {
+        Self {
+            include_select_existing: props.include_select_existing,
+            shared_only: props.shared_only,
+            snapshot_capable: props.snapshot_capable,
+            format: props.format,
+        }
+    }
+
+    fn matches(&self, info: &StorageInfo) -> bool {
+        if !self.include_select_existing && info.select_existing == Some(true) {
+            return false;
+        }
+        if self.shared_only && info.shared != Some(true) {
+            return false;
+        }
+        if self.snapshot_capable && !supports_snapshots(info) {
+            return false;
+        }
+        if let Some(format) = self.format {
+            match &info.formats {
+                Some(formats) => formats.supported.contains(&format),
+                // storages without format info only support raw images
+                None => format == StorageInfoFormatsDefault::Raw,
+            }
+        } else {
+            true
+        }
+    }
+}
+
+/// Storage types with native snapshot support.
+const SNAPSHOT_STORAGE_TYPES: &[&str] = &["btrfs", "lvmthin", "rbd", "zfs", "zfspool"];
+
+/// File based storages support snapshots with the qcow2 format.
+fn supports_snapshots(info: &StorageInfo) -> bool {
+    SNAPSHOT_STORAGE_TYPES.contains(&info.ty.as_str())
+        || info.formats.as_ref().is_some_and(|formats| {
+            formats
+                .supported
+                .contains(&StorageInfoFormatsDefault::Qcow2)
+        })
+}
+
+/// Disabled or inactive storages are listed, but cannot be selected.
+fn storage_usable(info: &StorageInfo) -> bool {
+    info.enabled != Some(false) && info.active != Some(false)
+}
+
 pub struct PveStorageSelectorComp {
     store: Store<StorageInfo>,
     load_callback: LoadCallback<Vec<StorageInfo>>,
@@ -85,14 +159,14 @@ impl PveStorageSelectorComp {
         node: AttrValue,
         remote: Option<AttrValue>,
         content: Option<Vec<StorageContent>>,
-        include_select_existing: bool,
+        filter: StorageFilter,
     ) -> Result<Vec<StorageInfo>, Error> {
         let url = if let Some(remote) = &remote {
             // fixme: is this url correct?
             format!(
                 "/pve/remotes/{}/nodes/{}/storage",
-                percent_encode_component(&node),
                 percent_encode_component(remote),
+                percent_encode_component(&node),
             )
         } else {
             format!("/nodes/{}/storage", percent_encode_component(&node))
@@ -109,9 +183,7 @@ impl PveStorageSelectorComp {
 
         let mut storages: Vec<StorageInfo> = crate::http_get(url, Some(param)).await?;
 
-        if !include_select_existing {
-            storages.retain(|info| info.select_existing != Some(true));
-        }
+        storages.retain(|info| filter.matches(info));
 
         storages.sort_by(|a, b| a.storage.cmp(&b.storage));
         Ok(storages)
@@ -122,14 +194,14 @@ impl PveStorageSelectorComp {
         let node = props.node.clone();
         let remote = props.remote.clone();
         let content_types = props.content_types.clone();
-        let include_select_existing = props.include_select_existing;
+        let filter = StorageFilter::new(props);
 
         (move || {
             Self::get_storage_list(
                 node.clone().unwrap_or("localhost".into()),
                 remote.clone(),
                 content_types.clone(),
-                include_select_existing,
+                filter,
             )
         })
         .into()
@@ -142,12 +214,15 @@ impl Component for PveStorageSelectorComp {
 
     fn create(ctx: &yew::Context<Self>) -> Self {
         let validate_fn = ValidateFn::new(|(value, store): &(String, Store<StorageInfo>)| {
-            store
-                .read()
+            let store = store.read();
+            let info = store
                 .iter()
                 .find(|item| item.storage == *value)
-                .ok_or_else(|| format_err!("no such item"))
-                .map(|_| ())
+                .ok_or_else(|| format_err!("no such item"))?;
+            if !storage_usable(info) {
+                bail!(tr!("Storage '{0}' is disabled or not active.", value));
+            }
+            Ok(())
         });
         Self {
             store: Store::with_extract_key(|storage: &StorageInfo| {
@@ -161,7 +236,11 @@ impl Component for PveStorageSelectorComp {
     fn changed(&mut self, ctx: &yew::Context<Self>, old: &Self::Properties) -> bool {
         let props = ctx.props();
 
-        if old.node != props.node || old.content_types != props.content_types {
+        if old.node != props.node
+            || old.remote != props.remote
+            || old.content_types != props.content_types
+            || StorageFilter::new(old) != StorageFilter::new(props)
+        {
             self.load_callback = Self::create_load_callback(ctx);
         }
 
@@ -217,6 +296,29 @@ impl Component for PveStorageSelectorComp {
     }
 }
 
+fn usage_fraction(info: &StorageInfo) -> Option<f32> {
+    match (info.total, info.used) {
+        (Some(total), Some(used)) if total > 0 => Some((used as f32) / (total as f32)),
+        _ => None,
+    }
+}
+
+/// Render disabled or inactive storages grayed out.
+fn render_name(entry: &StorageInfo) -> Html {
+    let name = format!("{} ({})", &entry.storage, &entry.ty);
+    if storage_usable(entry) {
+        return name.into();
+    }
+    let reason = if entry.enabled == Some(false) {
+        tr!("disabled")
+    } else {
+        tr!("not active")
+    };
+    html! {
+        <span class="pwt-opacity-50">{format!("{name} - {reason}")}</span>
+    }
+}
+
 fn columns_mobile() -> Rc<Vec<DataTableHeader<StorageInfo>>> {
     Rc::new(vec![DataTableColumn::new(tr!("Name"))
         .get_property(|entry: &StorageInfo| &entry.storage)
@@ -236,7 +338,7 @@ fn columns_mobile() -> Rc<Vec<DataTableHeader<StorageInfo>>> {
             .with_flex_spacer()
             .with_child(html! {
                 <div class="pwt-font-size-title-small">{right_text.to_string()}</div>
-            }), (used as f32) / (total as f32))
+            }), usage_fraction(entry).unwrap_or(0.0))
                 } else {
                     (
                         Row::new()
@@ -247,9 +349,7 @@ fn columns_mobile() -> Rc<Vec<DataTableHeader<StorageInfo>>> {
                 };
 
             Column::new()
-                .with_child(
-                    Container::new().with_child(format!("{} ({})", &entry.storage, &entry.ty)),
-                )
+                .with_child(Container::new().with_child(render_name(entry)))
                 .with_child(
                     Column::new()
                         .gap(1)
@@ -266,23 +366,46 @@ fn columns() -> Rc<Vec<DataTableHeader<StorageInfo>>> {
         DataTableColumn::new(tr!("Name"))
             .get_property(|entry: &StorageInfo| &entry.storage)
             .sort_order(true)
+            .render(|entry: &StorageInfo| {
+                if storage_usable(entry) {
+                    html! {&entry.storage}
+                } else {
+                    html! {<span class="pwt-opacity-50">{&entry.storage}</span>}
+                }
+            })
             .into(),
         DataTableColumn::new(tr!("Type"))
             .get_property(|entry: &StorageInfo| &entry.ty)
             .into(),
         DataTableColumn::new(tr!("Avail"))
-            .get_property_owned(|entry: &StorageInfo| entry.used.unwrap_or_default())
+            .get_property_owned(|entry: &StorageInfo| entry.avail.unwrap_or_default())
             .render(|entry: &StorageInfo| match entry.avail {
                 Some(avail) => html! {format!("{:.2}", HumanByte::new_decimal(avail as f64))},
                 None => html! {"-"},
             })
             .into(),
         DataTableColumn::new(tr!("Capacity"))
-            .get_property_owned(|entry: &StorageInfo| entry.avail.unwrap_or_default())
+            .get_property_owned(|entry: &StorageInfo| entry.total.unwrap_or_default())
             .render(|entry: &StorageInfo| match entry.total {
                 Some(total) => html! { format!("{:.2}", HumanByte::new_decimal(total as f64))},
                 None => html! {"-"},
             })
             .into(),
+        DataTableColumn::new(tr!("Usage"))
+            .width("120px")
+            .sorter(|a: &StorageInfo, b: &StorageInfo| {
+                let a = usage_fraction(a).unwrap_or(0.0);
+                let b = usage_fraction(b).unwrap_or(0.0);
+                a.total_cmp(&b)
+            })
+            .render(|entry: &StorageInfo| match usage_fraction(entry) {
+                Some(fraction) => Column::new()
+                    .gap(1)
+                    .with_child(format!("{:.1}%", fraction * 100.0))
+                    .with_child(Progress::new().value(fraction))
+                    .into(),
+                None => html! {"-"},
+            })
+            .into(),
     ])
 }
@@ -0,0 +1,237 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::Key;
+
+use pwt::prelude::*;
+
+use pwt::props::{FieldBuilder, LoadCallback, WidgetBuilder, WidgetStyleBuilder};
+use pwt::state::Store;
+use pwt::widget::{
+    data_table::{DataTable, DataTableColumn, DataTableHeader},
+    form::{Selector, SelectorRenderArgs},
+    GridPicker,
+};
+use pwt_macros::{builder, widget};
+
+use crate::pve_api_types::UsbDevice;
+use crate::{http_get, percent_encoding::percent_encode_component};
+
+/// The `vendor:product` ID, as used by the `host` option of USB passthrough.
+pub fn usb_device_id(device: &UsbDevice) -> String {
+    format!(
+        "{}:{}",
+        device.vendid.trim_start_matches("0x"),
+        device.prodid.trim_start_matches("0x")
+    )
+}
+
+/// The `bus-port` path, as used by the `host` option of USB passthrough.
+///
+/// Returns `None` for root hubs, which have no port.
+pub fn usb_port_id(device: &UsbDevice) -> Option<String> {
+    device
+        .usbpath
+        .as_ref()
+        .filter(|path| !path.is_empty() && *path != "0")
+        .map(|path| format!("{}-{path}", device.busnum))
+}
+
+/// Selects host USB devices either by vendor/product ID or by the port they are plugged in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UsbDeviceSelectMode {
+    Device,
+    Port,
+}
+
+/// Selector for host USB devices, used for USB passthrough.
+#[widget(comp=PveUsbDeviceSelectorComp, @input)]
+#[derive(Clone, Properties, PartialEq)]
+#[builder]
+pub struct PveUsbDeviceSelector {
+    /// The default value
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub default: Option<AttrValue>,
+
+    /// The node to list the devices from
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+
+    /// Use the device ID or the port as value.
+    #[builder]
+    #[prop_or(UsbDeviceSelectMode::Device)]
+    pub mode: UsbDeviceSelectMode,
+}
+
+impl Default for PveUsbDeviceSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PveUsbDeviceSelector {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+/// List entry, keyed by device or port ID.
+#[derive(Clone, PartialEq)]
+struct UsbDeviceEntry {
+    key: String,
+    device: UsbDevice,
+}
+
+pub struct PveUsbDeviceSelectorComp {
+    store: Store<UsbDeviceEntry>,
+    load_callback: LoadCallback<Vec<UsbDeviceEntry>>,
+}
+
+impl PveUsbDeviceSelectorComp {
+    async fn get_device_list(
+        node: AttrValue,
+        mode: UsbDeviceSelectMode,
+    ) -> Result<Vec<UsbDeviceEntry>, Error> {
+        let url = format!("/nodes/{}/hardware/usb", percent_encode_component(&node));
+        let devices: Vec<UsbDevice> = http_get(url, None).await?;
+        let mut list: Vec<UsbDeviceEntry> = devices
+            .into_iter()
+            // hubs cannot be passed through
+            .filter(|device| device.class != Some(9))
+            .filter_map(|device| {
+                let key = match mode {
+                    UsbDeviceSelectMode::Device => usb_device_id(&device),
+                    UsbDeviceSelectMode::Port => usb_port_id(&device)?,
+                };
+                Some(UsbDeviceEntry { key, device })
+            })
+            .collect();
+        list.sort_by(|a, b| a.key.cmp(&b.key));
+        list.dedup_by(|a, b| a.key == b.key);
+        Ok(list)
+    }
+
+    fn create_load_callback(ctx: &yew::Context<Self>) -> LoadCallback<Vec<UsbDeviceEntry>> {
+        let props = ctx.props();
+        let node = props.node.clone().unwrap_or(AttrValue::from("localhost"));
+        let mode = props.mode;
+        (move || Self::get_device_list(node.clone(), mode)).into()
+    }
+}
+
+impl Component for PveUsbDeviceSelectorComp {
+    type Message = ();
+    type Properties = PveUsbDeviceSelector;
+
+    fn create(ctx: &yew::Context<Self>) -> Self {
+        Self {
+            store: Store::with_extract_key(|entry: &UsbDeviceEntry| Key::from(entry.key.as_str())),
+            load_callback: Self::create_load_callback(ctx),
+        }
+    }
+
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if old_props.node != props.node || old_props.mode != props.mode {
+            self.load_callback = Self::create_load_callback(ctx);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
+        let props = ctx.props();
+        let mode = props.mode;
+
+        Selector::new(
+            self.store.clone(),
+            move |args: &SelectorRenderArgs<Store<UsbDeviceEntry>>| {
+                GridPicker::new(
+                    DataTable::new(columns(mode), args.store.clone())
+                        .min_width(500)
+                        .header_focusable(false)
+                        .class(pwt::css::FlexFit),
+                )
+                .selection(args.selection.clone())
+                .on_select(args.controller.on_select_callback())
+                .into()
+            },
+        )
+        .loader(self.load_callback.clone())
+        .with_std_props(&props.std_props)
+        .with_input_props(&props.input_props)
+        .default(props.default.clone())
+        .into()
+    }
+}
+
+fn render_speed(speed: Option<&str>) -> String {
+    match speed {
+        Some("1.5") => String::from("USB 1.x (Low Speed)"),
+        Some("12") => String::from("USB 1.x"),
+        Some("480") => String::from("USB 2.0"),
+        Some("5000") => String::from("USB 3.0"),
+        Some("10000") => String::from("USB 3.1"),
+        Some("20000") => String::from("USB 3.2"),
+        Some(speed) => format!("{speed} Mbps"),
+        None => String::new(),
+    }
+}
+
+fn columns(mode: UsbDeviceSelectMode) -> Rc<Vec<DataTableHeader<UsbDeviceEntry>>> {
+    let key_title = match mode {
+        UsbDeviceSelectMode::Device => tr!("Device"),
+        UsbDeviceSelectMode::Port => tr!("Port"),
+    };
+    Rc::new(vec![
+        DataTableColumn::new(key_title)
+            .width("100px")
+            .get_property(|entry: &UsbDeviceEntry| &entry.key)
+            .sort_order(true)
+            .into(),
+        DataTableColumn::new(tr!("Manufacturer"))
+            .width("150px")
+            .render(|entry: &UsbDeviceEntry| {
+                html! {entry.device.manufacturer.as_deref().unwrap_or_default()}
+            })
+            .into(),
+        DataTableColumn::new(tr!("Product"))
+            .flex(1)
+            .render(|entry: &UsbDeviceEntry| {
+                html! {entry.device.product.as_deref().unwrap_or_default()}
+            })
+            .into(),
+        DataTableColumn::new(tr!("Speed"))
+            .width("120px")
+            .render(|entry: &UsbDeviceEntry| render_speed(entry.device.speed.as_deref()).into())
+            .into(),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_usb_device_ids() {
+        let mut device = UsbDevice {
+            busnum: 1,
+            devnum: 3,
+            vendid: "0x046d".into(),
+            prodid: "0xc52b".into(),
+            usbpath: Some("2.1".into()),
+            manufacturer: None,
+            product: None,
+            speed: None,
+            class: None,
+        };
+        assert_eq!(usb_device_id(&device), "046d:c52b");
+        assert_eq!(usb_port_id(&device).as_deref(), Some("1-2.1"));
+
+        device.usbpath = Some("0".into());
+        assert_eq!(usb_port_id(&device), None);
+    }
+}
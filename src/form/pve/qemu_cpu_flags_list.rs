@@ -11,14 +11,33 @@ use pwt::widget::form::{
 };
 use pwt::widget::{Column, List, ListTile, Row};
 
+use yew::html::IntoPropValue;
+
 use pwt_macros::{builder, widget};
 
+use crate::http_get;
+use crate::percent_encoding::percent_encode_component;
+
 pub type QemuCpuFlagsComp = ManagedFieldMaster<QemuCpuFlagsField>;
 
+/// Editor for the CPU flags of a VM.
+///
+/// The list of flags is loaded from the node capabilities. If the node does not
+/// provide this information, a built-in list of commonly used flags is shown.
 #[widget(comp=QemuCpuFlagsComp, @input)]
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
-pub struct QemuCpuFlags {}
+pub struct QemuCpuFlags {
+    /// The node to query
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+
+    /// Use Proxmox Datacenter Manager API endpoints
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub remote: Option<AttrValue>,
+}
 
 impl Default for QemuCpuFlags {
     fn default() -> Self {
@@ -34,6 +53,15 @@ impl QemuCpuFlags {
 
 pub enum Msg {
     Set(String, Option<bool>),
+    FlagsLoaded(Result<Vec<CpuFlagInfo>, String>),
+}
+
+/// CPU flag info (GET /api2/json/nodes/{node}/capabilities/qemu/cpu-flags)
+#[derive(Clone, Debug, Deserialize)]
+pub struct CpuFlagInfo {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -46,7 +74,7 @@ struct FlagEntry {
 #[doc(hidden)]
 pub struct QemuCpuFlagsField {
     state: ManagedFieldState,
-    flag_list: IndexMap<&'static str, FlagEntry>,
+    flag_list: IndexMap<String, FlagEntry>,
 }
 
 fn parse_flags(flags: &str) -> HashMap<String, bool> {
@@ -69,11 +97,68 @@ fn parse_flags(flags: &str) -> HashMap<String, bool> {
 
 pwt::impl_deref_mut_property!(QemuCpuFlagsField, state, ManagedFieldState);
 
+/// Commonly used flags, used if the node does not provide the list of supported flags.
+fn builtin_flags() -> Vec<(String, String)> {
+    [
+        ("md-clear", tr!("Required to let the guest OS know if MDS is mitigated correctly")),
+        ("pcid", tr!("Meltdown fix cost reduction on Westmere, Sandy-, and IvyBridge Intel CPUs")),
+        ("spec-ctrl", tr!("Allows improved Spectre mitigation with Intel CPUs")),
+        ("ssbd", tr!("Protection for \"Speculative Store Bypass\" for Intel models")),
+        ("ibpb", tr!("Allows improved Spectre mitigation with AMD CPUs")),
+        ("virt-ssbd", tr!("Basis for \"Speculative Store Bypass\" protection for AMD models")),
+        ("amd-ssbd", tr!("Improves Spectre mitigation performance with AMD CPUs, best used with \"virt-ssbd\"")),
+        ("amd-no-ssb", tr!("Notifies guest OS that host is not vulnerable for Spectre on AMD CPUs")),
+        ("pdpe1gb", tr!("Allow guest OS to use 1GB size pages, if host HW supports it")),
+        ("hv-tlbflush", tr!("Improve performance in overcommitted Windows guests. May lead to guest bluescreens on old CPUs.")),
+        ("hv-evmcs", tr!("Improve performance for nested virtualization. Only supported on Intel CPUs.")),
+        ("aes", tr!("Activate AES instruction set for HW acceleration.")),
+    ]
+    .into_iter()
+    .map(|(flag, descr)| (flag.to_string(), descr))
+    .collect()
+}
+
 impl QemuCpuFlagsField {
+    fn load_flags(&self, ctx: &ManagedFieldContext<Self>) {
+        let props = ctx.props();
+        let node = props.node.as_deref().unwrap_or("localhost");
+        let url = match &props.remote {
+            Some(remote) => format!(
+                "/pve/remotes/{}/nodes/{}/capabilities/qemu/cpu-flags",
+                percent_encode_component(remote),
+                percent_encode_component(node),
+            ),
+            None => format!(
+                "/nodes/{}/capabilities/qemu/cpu-flags",
+                percent_encode_component(node)
+            ),
+        };
+        let callback = ctx.link().callback(Msg::FlagsLoaded);
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = http_get(url, None).await.map_err(|err| err.to_string());
+            callback.emit(result);
+        });
+    }
+
+    fn set_flag_list(&mut self, flags: Vec<(String, String)>) {
+        self.flag_list = flags
+            .into_iter()
+            .map(|(name, descr)| {
+                let entry = FlagEntry {
+                    name: name.clone(),
+                    descr,
+                    enabled: None,
+                };
+                (name, entry)
+            })
+            .collect();
+        self.update_flag_list(self.state.value.clone());
+    }
+
     pub fn update_flag_list(&mut self, value: Value) {
         let current_flags = parse_flags(value.as_str().unwrap_or(""));
         for (name, item) in self.flag_list.iter_mut() {
-            if let Some(value) = current_flags.get(*name) {
+            if let Some(value) = current_flags.get(name) {
                 item.enabled = Some(*value);
             } else {
                 item.enabled = None;
@@ -93,39 +178,22 @@ impl ManagedField for QemuCpuFlagsField {
         Ok(value.clone())
     }
 
-    fn create(_ctx: &ManagedFieldContext<Self>) -> Self {
-        // TODO: let qemu-server host this and autogenerate or get from API call??
-        let all_flags = [
-            ("md-clear", tr!("Required to let the guest OS know if MDS is mitigated correctly")),
-            ("pcid", tr!("Meltdown fix cost reduction on Westmere, Sandy-, and IvyBridge Intel CPUs")),
-            ("spec-ctrl", tr!("Allows improved Spectre mitigation with Intel CPUs")),
-            ("ssbd", tr!("Protection for \"Speculative Store Bypass\" for Intel models")),
-            ("ibpb", tr!("Allows improved Spectre mitigation with AMD CPUs")),
-            ("virt-ssbd", tr!("Basis for \"Speculative Store Bypass\" protection for AMD models")),
-            ("amd-ssbd", tr!("Improves Spectre mitigation performance with AMD CPUs, best used with \"virt-ssbd\"")),
-            ("amd-no-ssb", tr!("Notifies guest OS that host is not vulnerable for Spectre on AMD CPUs")),
-            ("pdpe1gb", tr!("Allow guest OS to use 1GB size pages, if host HW supports it")),
-            ("hv-tlbflush", tr!("Improve performance in overcommitted Windows guests. May lead to guest bluescreens on old CPUs.")),
-            ("hv-evmcs", tr!("Improve performance for nested virtualization. Only supported on Intel CPUs.")),
-            ("aes", tr!("Activate AES instruction set for HW acceleration.")),
-        ];
-
-        let mut flag_list = IndexMap::new();
-        flag_list.extend(all_flags.into_iter().map(|(flag, descr)| {
-            (
-                flag,
-                FlagEntry {
-                    name: flag.to_string(),
-                    descr,
-                    enabled: None,
-                },
-            )
-        }));
-
-        Self {
+    fn create(ctx: &ManagedFieldContext<Self>) -> Self {
+        let mut this = Self {
             state: ManagedFieldState::new(Value::Null, Value::Null),
-            flag_list,
+            flag_list: IndexMap::new(),
+        };
+        this.set_flag_list(builtin_flags());
+        this.load_flags(ctx);
+        this
+    }
+
+    fn changed(&mut self, ctx: &ManagedFieldContext<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.node != old_props.node || props.remote != old_props.remote {
+            self.load_flags(ctx);
         }
+        true
     }
 
     fn value_changed(&mut self, _ctx: &ManagedFieldContext<Self>) {
@@ -135,7 +203,23 @@ impl ManagedField for QemuCpuFlagsField {
     fn update(&mut self, ctx: &ManagedFieldContext<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Set(flag, enabled) => {
-                self.flag_list[flag.as_str()].enabled = enabled;
+                if let Some(entry) = self.flag_list.get_mut(&flag) {
+                    entry.enabled = enabled;
+                }
+            }
+            Msg::FlagsLoaded(Ok(flags)) if !flags.is_empty() => {
+                let flags = flags
+                    .into_iter()
+                    .map(|info| (info.name, info.description.unwrap_or_default()))
+                    .collect();
+                self.set_flag_list(flags);
+                return true;
+            }
+            Msg::FlagsLoaded(result) => {
+                if let Err(err) = result {
+                    log::info!("unable to load cpu flags, using built-in list: {err}");
+                }
+                return false;
             }
         }
 
@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
-use anyhow::{format_err, Error};
+use anyhow::{bail, format_err, Error};
+use serde_json::Value;
 
 use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::Key;
@@ -63,53 +64,156 @@ impl QemuCpuModelSelector {
     }
 }
 
+/// List entry, with host compatibility information.
+#[derive(Clone)]
+struct CpuModelEntry {
+    model: QemuCpuModel,
+    /// The model requires a CPU from a different vendor than the host CPU.
+    unsupported: bool,
+}
+
 pub struct QemuCpuModelSelectorComp {
-    store: Store<QemuCpuModel>,
-    load_callback: LoadCallback<Vec<QemuCpuModel>>,
-    validate_fn: pwt::widget::form::ValidateFn<(String, Store<QemuCpuModel>)>,
+    store: Store<CpuModelEntry>,
+    load_callback: LoadCallback<Vec<CpuModelEntry>>,
+    validate_fn: pwt::widget::form::ValidateFn<(String, Store<CpuModelEntry>)>,
 }
 
-async fn get_cpu_model_list(props: QemuCpuModelSelector) -> Result<Vec<QemuCpuModel>, Error> {
+fn node_url(props: &QemuCpuModelSelector, path: &str) -> String {
     let node = props.node.as_deref().unwrap_or("localhost");
-    let url = if let Some(remote) = &props.remote {
+    if let Some(remote) = &props.remote {
         format!(
-            "/pve/remotes/{}/nodes/{}/capabilities/qemu/cpu",
-            percent_encode_component(node),
+            "/pve/remotes/{}/nodes/{}/{path}",
             percent_encode_component(remote),
+            percent_encode_component(node),
         )
     } else {
-        format!(
-            "/nodes/{}/capabilities/qemu/cpu",
-            percent_encode_component(node)
-        )
-    };
+        format!("/nodes/{}/{path}", percent_encode_component(node))
+    }
+}
+
+/// The vendor of the host CPU, derived from the model name reported by the node.
+fn host_cpu_vendor(cpu_model: &str) -> Option<&'static str> {
+    if cpu_model.contains("Intel") {
+        Some("Intel")
+    } else if cpu_model.contains("AMD") {
+        Some("AMD")
+    } else {
+        None
+    }
+}
+
+/// Map the vendor ID reported for CPU models (`GenuineIntel`, `AuthenticAMD` or
+/// `default`) to the vendor name.
+fn vendor_name(vendor: &str) -> &str {
+    match vendor {
+        "GenuineIntel" => "Intel",
+        "AuthenticAMD" => "AMD",
+        other => other,
+    }
+}
+
+/// Vendor specific models only work on hosts with a CPU from that vendor.
+///
+/// Generic models (like `kvm64` or `x86-64-v2-AES`) are always supported.
+fn model_supported(vendor: &str, host_vendor: Option<&str>) -> bool {
+    let vendor = vendor_name(vendor);
+    match host_vendor {
+        Some(host_vendor) if vendor == "Intel" || vendor == "AMD" => vendor == host_vendor,
+        _ => true,
+    }
+}
+
+async fn get_cpu_model_list(props: QemuCpuModelSelector) -> Result<Vec<CpuModelEntry>, Error> {
+    let (model_list, status) = futures::join!(
+        http_get::<Vec<QemuCpuModel>>(node_url(&props, "capabilities/qemu/cpu"), None),
+        http_get::<Value>(node_url(&props, "status"), None),
+    );
+
+    // do not disable anything if the host CPU is unknown
+    let host_vendor = status.ok().and_then(|status| {
+        status["cpuinfo"]["model"]
+            .as_str()
+            .and_then(host_cpu_vendor)
+    });
 
-    let mut model_list: Vec<QemuCpuModel> = http_get(url, None).await?;
-    model_list.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut model_list: Vec<CpuModelEntry> = model_list?
+        .into_iter()
+        .map(|model| CpuModelEntry {
+            unsupported: !model_supported(&model.vendor, host_vendor),
+            model,
+        })
+        .collect();
+
+    // group by vendor, custom models first
+    model_list.sort_by(|a, b| {
+        b.model
+            .custom
+            .cmp(&a.model.custom)
+            .then_with(|| a.model.vendor.cmp(&b.model.vendor))
+            .then_with(|| a.model.name.cmp(&b.model.name))
+    });
     Ok(model_list)
 }
 
+fn render_vendor(model: &QemuCpuModel) -> String {
+    let vendor = match vendor_name(&model.vendor) {
+        "default" => tr!("Generic"),
+        vendor => vendor.to_string(),
+    };
+    if model.custom {
+        format!("{} ({})", vendor, tr!("Custom"))
+    } else {
+        vendor
+    }
+}
+
+fn render_name(entry: &CpuModelEntry) -> Html {
+    if entry.unsupported {
+        html! {
+            <span class="pwt-opacity-50">
+                {format!("{} ({})", entry.model.name, tr!("not supported by host"))}
+            </span>
+        }
+    } else {
+        html! {&entry.model.name}
+    }
+}
+
 impl Component for QemuCpuModelSelectorComp {
     type Message = ();
     type Properties = QemuCpuModelSelector;
 
     fn create(ctx: &yew::Context<Self>) -> Self {
         let props = ctx.props().clone();
-        let validate_fn = ValidateFn::new(|(value, store): &(String, Store<QemuCpuModel>)| {
-            store
-                .read()
+        let validate_fn = ValidateFn::new(|(value, store): &(String, Store<CpuModelEntry>)| {
+            let store = store.read();
+            let entry = store
                 .iter()
-                .find(|item| item.name == *value)
-                .ok_or_else(|| format_err!("no such item"))
-                .map(|_| ())
+                .find(|item| item.model.name == *value)
+                .ok_or_else(|| format_err!("no such item"))?;
+            if entry.unsupported {
+                bail!(tr!("CPU model not supported by the host CPU"));
+            }
+            Ok(())
         });
         Self {
-            store: Store::with_extract_key(|info: &QemuCpuModel| Key::from(info.name.as_str())),
+            store: Store::with_extract_key(|entry: &CpuModelEntry| {
+                Key::from(entry.model.name.as_str())
+            }),
             load_callback: LoadCallback::new(move || get_cpu_model_list(props.clone())),
             validate_fn,
         }
     }
 
+    fn changed(&mut self, ctx: &yew::Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.node != old_props.node || props.remote != old_props.remote {
+            let props = props.clone();
+            self.load_callback = LoadCallback::new(move || get_cpu_model_list(props.clone()));
+        }
+        true
+    }
+
     fn view(&self, ctx: &yew::Context<Self>) -> yew::Html {
         let props = ctx.props();
 
@@ -122,7 +226,7 @@ impl Component for QemuCpuModelSelectorComp {
                         .read()
                         .iter()
                         .find(|e| key == store.extract_key(e))
-                        .map(|e| e.name.clone().into());
+                        .map(|e| e.model.name.clone().into());
                     on_change.emit(result);
                 }
             }
@@ -132,7 +236,7 @@ impl Component for QemuCpuModelSelectorComp {
 
         Selector::new(
             self.store.clone(),
-            move |args: &SelectorRenderArgs<Store<QemuCpuModel>>| {
+            move |args: &SelectorRenderArgs<Store<CpuModelEntry>>| {
                 GridPicker::new(
                     DataTable::new(
                         if mobile { columns_mobile() } else { columns() },
@@ -161,24 +265,43 @@ impl Component for QemuCpuModelSelectorComp {
     }
 }
 
-fn columns_mobile() -> Rc<Vec<DataTableHeader<QemuCpuModel>>> {
+fn columns_mobile() -> Rc<Vec<DataTableHeader<CpuModelEntry>>> {
     Rc::new(vec![DataTableColumn::new(tr!("Name"))
-        .get_property(|entry: &QemuCpuModel| &entry.name)
-        //.sort_order(true)
-        .render(|entry: &QemuCpuModel| {
-            title_subtitle_column(entry.name.clone(), entry.vendor.clone()).into()
+        .get_property(|entry: &CpuModelEntry| &entry.model.name)
+        .render(|entry: &CpuModelEntry| {
+            title_subtitle_column(render_name(entry), render_vendor(&entry.model)).into()
         })
         .into()])
 }
 
-fn columns() -> Rc<Vec<DataTableHeader<QemuCpuModel>>> {
+fn columns() -> Rc<Vec<DataTableHeader<CpuModelEntry>>> {
     Rc::new(vec![
-        DataTableColumn::new(tr!("Name"))
-            .get_property(|entry: &QemuCpuModel| &entry.name)
-            //.sort_order(true)
-            .into(),
         DataTableColumn::new(tr!("Vendor"))
-            .get_property(|entry: &QemuCpuModel| &entry.vendor)
+            .render(|entry: &CpuModelEntry| render_vendor(&entry.model).into())
+            .into(),
+        DataTableColumn::new(tr!("Name"))
+            .get_property(|entry: &CpuModelEntry| &entry.model.name)
+            .render(render_name)
             .into(),
     ])
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_model_supported() {
+        let intel = host_cpu_vendor("Intel(R) Xeon(R) CPU E5-2620 v4 @ 2.10GHz");
+        let amd = host_cpu_vendor("AMD EPYC 7302P 16-Core Processor");
+        assert_eq!(intel, Some("Intel"));
+        assert_eq!(amd, Some("AMD"));
+
+        assert!(model_supported("GenuineIntel", intel));
+        assert!(!model_supported("GenuineIntel", amd));
+        assert!(!model_supported("AuthenticAMD", intel));
+        assert!(model_supported("AuthenticAMD", amd));
+        assert!(model_supported("default", amd));
+        assert!(model_supported("AuthenticAMD", None));
+    }
+}
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use anyhow::{format_err, Error};
-use pwt::widget::GridPicker;
+use pwt::widget::{Fa, GridPicker, Row, Tooltip};
 
 use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::Key;
@@ -16,6 +16,7 @@ use pwt::widget::form::{Selector, SelectorRenderArgs, ValidateFn};
 use pwt_macros::{builder, widget};
 
 use crate::http_get;
+use crate::percent_encoding::percent_encode_component;
 use crate::pve_api_types::{QemuMachineInfo, QemuMachineType};
 
 #[widget(comp=QemuMachineVersionSelectorComp, @input)]
@@ -35,6 +36,16 @@ pub struct QemuMachineVersionSelector {
     /// List versions for this machine type.
     pub machine_type: QemuMachineType,
 
+    /// The node to query
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub node: Option<AttrValue>,
+
+    /// Use Proxmox Datacenter Manager API endpoints
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub remote: Option<AttrValue>,
+
     /// If set, automatically selects the first value from the store (if no default is selected)
     #[builder]
     #[prop_or(false)]
@@ -47,37 +58,101 @@ impl QemuMachineVersionSelector {
     }
 }
 
+/// QEMU keeps versioned machine types for about six years, and there is one major
+/// release per year.
+const SUPPORTED_MAJOR_VERSIONS: u32 = 6;
+
+/// List entry with deprecation info.
+#[derive(Clone, PartialEq)]
+struct MachineVersionEntry {
+    info: QemuMachineInfo,
+    /// The "latest" pseudo-option, which is not pinned to a specific version.
+    latest: bool,
+    /// The version is scheduled for removal in a future QEMU release.
+    deprecated: bool,
+}
+
 pub struct QemuMachineVersionSelectorComp {
-    store: Store<QemuMachineInfo>,
-    load_callback: LoadCallback<Vec<QemuMachineInfo>>,
-    validate_fn: pwt::widget::form::ValidateFn<(String, Store<QemuMachineInfo>)>,
+    store: Store<MachineVersionEntry>,
+    load_callback: LoadCallback<Vec<MachineVersionEntry>>,
+    validate_fn: pwt::widget::form::ValidateFn<(String, Store<MachineVersionEntry>)>,
 }
 
-// We cannot use store.set_filter(), because GridPicker overwrites that
-async fn get_machine_list(machine_type: QemuMachineType) -> Result<Vec<QemuMachineInfo>, Error> {
-    let url = "/nodes/localhost/capabilities/qemu/machines";
+/// The QEMU major version of a machine version like `9.2+pve1`.
+fn version_major(version: &str) -> Option<u32> {
+    version.split(['.', '+']).next()?.parse().ok()
+}
 
-    let mut model_list = Vec::new();
-    model_list.push(QemuMachineInfo {
-        ty: QemuMachineType::I440fx,
-        id: "pc".into(),
-        version: tr!("Latest"),
-        changes: None,
-    });
-    model_list.push(QemuMachineInfo {
-        ty: QemuMachineType::Q35,
-        id: "q35".into(),
-        version: tr!("Latest"),
-        changes: None,
-    });
-
-    model_list.extend(http_get::<Vec<QemuMachineInfo>>(url, None).await?);
-
-    let model_list: Vec<QemuMachineInfo> = model_list
+fn is_deprecated(version: &str, latest_major: u32) -> bool {
+    match version_major(version) {
+        Some(major) => major + SUPPORTED_MAJOR_VERSIONS <= latest_major,
+        None => false,
+    }
+}
+
+fn create_load_callback(
+    props: &QemuMachineVersionSelector,
+) -> LoadCallback<Vec<MachineVersionEntry>> {
+    let machine_type = props.machine_type;
+    let node = props.node.as_deref().unwrap_or("localhost");
+    let url = match &props.remote {
+        Some(remote) => format!(
+            "/pve/remotes/{}/nodes/{}/capabilities/qemu/machines",
+            percent_encode_component(remote),
+            percent_encode_component(node),
+        ),
+        None => format!(
+            "/nodes/{}/capabilities/qemu/machines",
+            percent_encode_component(node)
+        ),
+    };
+    LoadCallback::new(move || get_machine_list(url.clone(), machine_type))
+}
+
+// We cannot use store.set_filter(), because GridPicker overwrites that
+async fn get_machine_list(
+    url: String,
+    machine_type: QemuMachineType,
+) -> Result<Vec<MachineVersionEntry>, Error> {
+    let machine_list: Vec<QemuMachineInfo> = http_get::<Vec<QemuMachineInfo>>(url, None)
+        .await?
         .into_iter()
         .filter(|item| item.ty == machine_type)
         .collect();
 
+    let latest_major = machine_list
+        .iter()
+        .filter_map(|item| version_major(&item.version))
+        .max()
+        .unwrap_or(0);
+
+    let latest_id = match machine_type {
+        QemuMachineType::Q35 => Some("q35"),
+        QemuMachineType::I440fx => Some("pc"),
+        // there is no unversioned alias for virt
+        QemuMachineType::Virt => None,
+    };
+
+    let mut model_list = Vec::new();
+    if let Some(id) = latest_id {
+        model_list.push(MachineVersionEntry {
+            info: QemuMachineInfo {
+                ty: machine_type,
+                id: id.into(),
+                version: tr!("Latest"),
+                changes: None,
+            },
+            latest: true,
+            deprecated: false,
+        });
+    }
+
+    model_list.extend(machine_list.into_iter().map(|info| MachineVersionEntry {
+        deprecated: is_deprecated(&info.version, latest_major),
+        latest: false,
+        info,
+    }));
+
     Ok(model_list)
 }
 
@@ -87,37 +162,39 @@ impl Component for QemuMachineVersionSelectorComp {
 
     fn create(ctx: &yew::Context<Self>) -> Self {
         let props = ctx.props();
-        let validate_fn = ValidateFn::new({
-            let machine_type = props.machine_type;
-            move |(value, store): &(String, Store<QemuMachineInfo>)| {
+        let validate_fn =
+            ValidateFn::new(|(value, store): &(String, Store<MachineVersionEntry>)| {
                 store
                     .read()
                     .iter()
-                    .find(|item| item.ty == machine_type && &item.id == value)
-                    .ok_or_else(|| format_err!("no such item"))
+                    .find(|item| &item.info.id == value)
+                    .ok_or_else(|| {
+                        format_err!(tr!(
+                            "Machine version '{0}' is not supported by this node.",
+                            value
+                        ))
+                    })
                     .map(|_| ())
-            }
-        });
+            });
 
-        let store = Store::with_extract_key(|info: &QemuMachineInfo| Key::from(info.id.as_str()));
+        let store = Store::with_extract_key(|entry: &MachineVersionEntry| {
+            Key::from(entry.info.id.as_str())
+        });
 
         Self {
             store,
-            load_callback: LoadCallback::new({
-                let machine_type = props.machine_type;
-                move || get_machine_list(machine_type)
-            }),
+            load_callback: create_load_callback(props),
             validate_fn,
         }
     }
 
     fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
         let props = ctx.props();
-        if props.machine_type != old_props.machine_type {
-            self.load_callback = LoadCallback::new({
-                let machine_type = props.machine_type;
-                move || get_machine_list(machine_type)
-            });
+        if props.machine_type != old_props.machine_type
+            || props.node != old_props.node
+            || props.remote != old_props.remote
+        {
+            self.load_callback = create_load_callback(props);
         }
         true
     }
@@ -134,15 +211,32 @@ impl Component for QemuMachineVersionSelectorComp {
                         .read()
                         .iter()
                         .find(|e| key == store.extract_key(e))
-                        .map(|e| e.id.clone().into());
+                        .map(|e| e.info.id.clone().into());
                     on_change.emit(result);
                 }
             }
         };
 
+        let render_value = {
+            let store = self.store.clone();
+            move |id: &AttrValue| {
+                let text = extract_version_text(id.as_str());
+                let deprecated = store
+                    .read()
+                    .lookup_record(&Key::from(id.as_str()))
+                    .map(|entry| entry.deprecated)
+                    .unwrap_or(false);
+                if deprecated {
+                    format!("{text} ({})", tr!("deprecated")).into()
+                } else {
+                    text.into()
+                }
+            }
+        };
+
         Selector::new(
             self.store.clone(),
-            move |args: &SelectorRenderArgs<Store<QemuMachineInfo>>| {
+            move |args: &SelectorRenderArgs<Store<MachineVersionEntry>>| {
                 GridPicker::new(
                     DataTable::new(columns(), args.store.clone())
                         .min_width(200)
@@ -164,7 +258,7 @@ impl Component for QemuMachineVersionSelectorComp {
         .on_change(on_change)
         .default(props.default.clone())
         .placeholder(tr!("Latest"))
-        .render_value(|id: &AttrValue| extract_version_text(id.as_str()).into())
+        .render_value(render_value)
         .into()
     }
 }
@@ -190,9 +284,52 @@ fn extract_version_text(id: &str) -> String {
     id.to_string()
 }
 
-fn columns() -> Rc<Vec<DataTableHeader<QemuMachineInfo>>> {
+fn render_version(entry: &MachineVersionEntry) -> Html {
+    if entry.latest {
+        return Tooltip::new(entry.info.version.clone())
+            .tip(tr!(
+                "Use the newest version supported by the node at VM start. Pin a version if the guest OS depends on a stable hardware layout (e.g. Windows)."
+            ))
+            .into();
+    }
+
+    let mut row = Row::new()
+        .gap(2)
+        .class(pwt::css::AlignItems::Baseline)
+        .with_child(entry.info.version.clone());
+
+    if let Some(changes) = &entry.info.changes {
+        row.add_child(html! {<span class="pwt-opacity-50">{changes}</span>});
+    }
+    if entry.deprecated {
+        row.add_child(
+            Tooltip::new(Fa::new("exclamation-triangle").class("pwt-color-warning")).tip(tr!(
+                "This machine version is deprecated and will be removed in a future QEMU release."
+            )),
+        );
+    }
+    row.into()
+}
+
+fn columns() -> Rc<Vec<DataTableHeader<MachineVersionEntry>>> {
     Rc::new(vec![DataTableColumn::new(tr!("Version"))
-        .get_property(|entry: &QemuMachineInfo| &entry.version)
-        .render(|entry: &QemuMachineInfo| entry.version.clone().into())
+        .get_property(|entry: &MachineVersionEntry| &entry.info.version)
+        .render(render_version)
         .into()])
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_versions() {
+        assert_eq!(version_major("9.2+pve1"), Some(9));
+        assert_eq!(version_major("10.0"), Some(10));
+        assert_eq!(version_major("latest"), None);
+
+        assert!(is_deprecated("4.2", 10));
+        assert!(!is_deprecated("5.0", 10));
+        assert!(!is_deprecated("latest", 10));
+    }
+}
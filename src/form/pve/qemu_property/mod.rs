@@ -20,7 +20,9 @@ use crate::form::pve::{
 use crate::form::{delete_empty_values, property_string_load_hook, property_string_submit_hook};
 
 use crate::pve_api_types::QemuConfigStartup;
-use crate::{EditableProperty, PropertyEditorState, RenderPropertyInputPanelFn};
+use crate::{
+    EditableProperty, PropertyEditorState, RenderPropertyInputPanelFn, SecondsDurationField,
+};
 
 mod qemu_disk_property;
 pub use qemu_disk_property::{extract_used_devices, qemu_cdrom_property, qemu_disk_property};
@@ -64,6 +66,24 @@ pub use qemu_agent_property::qemu_agent_property;
 mod qemu_bios_property;
 pub use qemu_bios_property::qemu_bios_property;
 
+mod qemu_passthrough_property;
+pub use qemu_passthrough_property::{
+    qemu_hostpci_property, qemu_parallel_property, qemu_serial_property, qemu_usb_property,
+};
+pub(crate) use qemu_passthrough_property::{
+    MAX_HOSTPCI_DEVICES, MAX_PARALLEL_PORTS, MAX_SERIAL_PORTS, MAX_USB_DEVICES,
+};
+
+mod qemu_audio_property;
+pub use qemu_audio_property::qemu_audio_property;
+
+mod qemu_rng_property;
+pub use qemu_rng_property::qemu_rng_property;
+
+mod qemu_virtiofs_property;
+pub use qemu_virtiofs_property::qemu_virtiofs_property;
+pub(crate) use qemu_virtiofs_property::MAX_VIRTIOFS_DEVICES;
+
 mod qemu_processor_property;
 pub use qemu_processor_property::{
     qemu_cpu_flags_property, qemu_kernel_scheduler_property, qemu_sockets_cores_property,
@@ -209,10 +229,14 @@ pub fn qemu_startup_property(mobile: bool) -> EditableProperty {
             let order_field = Number::<u32>::new().name("_order").placeholder(tr!("any"));
             let order_label = tr!("Order");
             let up_label = tr!("Startup delay");
-            let up_field = Number::<u32>::new().name("_up").placeholder(tr!("default"));
+            let up_field = SecondsDurationField::new()
+                .name("_up")
+                .max(u32::MAX as u64)
+                .placeholder(tr!("default"));
             let down_label = tr!("Shutdown timeout");
-            let down_field = Number::<u32>::new()
+            let down_field = SecondsDurationField::new()
                 .name("_down")
+                .max(u32::MAX as u64)
                 .placeholder(tr!("default"));
 
             InputPanel::new()
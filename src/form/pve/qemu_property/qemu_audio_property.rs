@@ -0,0 +1,75 @@
+use proxmox_schema::property_string::PropertyString;
+use serde_json::Value;
+
+use pwt::prelude::*;
+use pwt::widget::form::Combobox;
+use pwt::widget::InputPanel;
+
+use crate::form::{property_string_load_hook, property_string_submit_hook};
+use crate::pve_api_types::QemuConfigAudio;
+use crate::{EditableProperty, PropertyEditorState, RenderPropertyInputPanelFn};
+
+fn format_audio_driver(driver: &str) -> String {
+    match driver {
+        "spice" => String::from("SPICE"),
+        "none" => tr!("none"),
+        _ => driver.to_string(),
+    }
+}
+
+fn renderer(_name: &str, value: &Value, _record: &Value) -> Html {
+    match serde_json::from_value::<PropertyString<QemuConfigAudio>>(value.clone()) {
+        Ok(audio) => {
+            let device = audio.device.as_deref().unwrap_or("");
+            match audio.driver.as_deref() {
+                Some(driver) => format!("{device}, {}", format_audio_driver(driver)).into(),
+                None => device.into(),
+            }
+        }
+        Err(_) => match value {
+            Value::String(s) => s.into(),
+            _ => value.into(),
+        },
+    }
+}
+
+fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
+    RenderPropertyInputPanelFn::new(move |_state: PropertyEditorState| {
+        InputPanel::new()
+            .mobile(mobile)
+            .class(pwt::css::FlexFit)
+            .padding_x(2)
+            .with_field(
+                tr!("Audio Device"),
+                Combobox::from_key_value_pairs([
+                    ("ich9-intel-hda", "ich9-intel-hda"),
+                    ("intel-hda", "intel-hda"),
+                    ("AC97", "AC97"),
+                ])
+                .name("_device")
+                .required(true)
+                .default("ich9-intel-hda"),
+            )
+            .with_field(
+                tr!("Backend Driver"),
+                Combobox::from_key_value_pairs([
+                    ("spice", format_audio_driver("spice")),
+                    ("none", format_audio_driver("none")),
+                ])
+                .name("_driver")
+                .required(true)
+                .default("spice"),
+            )
+            .into()
+    })
+}
+
+pub fn qemu_audio_property(mobile: bool) -> EditableProperty {
+    EditableProperty::new("audio0", tr!("Audio Device"))
+        .renderer(renderer)
+        .render_input_panel(input_panel(mobile))
+        .load_hook(property_string_load_hook::<QemuConfigAudio>("audio0"))
+        .submit_hook(property_string_submit_hook::<QemuConfigAudio>(
+            "audio0", true,
+        ))
+}
@@ -56,7 +56,11 @@ fn get_version_prop_name(machine_type: String) -> String {
     format!("_{machine_type}-version")
 }
 
-fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
+fn input_panel(
+    node: AttrValue,
+    remote: Option<AttrValue>,
+    mobile: bool,
+) -> RenderPropertyInputPanelFn {
     RenderPropertyInputPanelFn::new(move |state: PropertyEditorState| {
         let form_ctx = state.form_ctx;
         let hint = |msg: String| Container::new().class("pwt-color-warning").with_child(msg);
@@ -97,6 +101,8 @@ fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
             let name = get_version_prop_name(ty.to_string());
 
             let field = QemuMachineVersionSelector::new(ty)
+                .node(node.clone())
+                .remote(remote.clone())
                 .name(name)
                 .disabled(disabled)
                 .required(is_windows.unwrap_or_default())
@@ -135,6 +141,9 @@ fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
         add_version_selector(&mut panel, QemuMachineType::Q35);
         add_version_selector(&mut panel, QemuMachineType::Virt);
 
+        let viommu = form_ctx.read().get_field_text("_viommu");
+        let viommu_unsupported = !viommu_supported(&viommu, machine_type);
+
         let mut items = Vec::new();
         if machine_type == QemuMachineType::Q35 {
             items.push(("intel", tr!("Intel (AMD Compatible)")));
@@ -160,6 +169,17 @@ fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
                 }),
         );
 
+        // shown without advanced options, because the vIOMMU field is an advanced option
+        panel.add_custom_child_with_options(
+            FieldPosition::Left,
+            false,
+            !viommu_unsupported,
+            hint(tr!(
+                "The Intel vIOMMU requires the Q35 machine type and will be removed."
+            ))
+            .key("viommu_hint"),
+        );
+
         panel.add_custom_child_with_options(
             FieldPosition::Left,
             false,
@@ -174,7 +194,16 @@ fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
     })
 }
 
-pub fn qemu_machine_property(mobile: bool) -> EditableProperty {
+/// The Intel vIOMMU is only available with the Q35 machine type.
+fn viommu_supported(viommu: &str, machine_type: QemuMachineType) -> bool {
+    viommu != "intel" || machine_type == QemuMachineType::Q35
+}
+
+pub fn qemu_machine_property(
+    node: AttrValue,
+    remote: Option<AttrValue>,
+    mobile: bool,
+) -> EditableProperty {
     EditableProperty::new("machine", tr!("Machine"))
         .required(true)
         .advanced_checkbox(true)
@@ -190,7 +219,7 @@ pub fn qemu_machine_property(mobile: bool) -> EditableProperty {
                 (None, _) => placeholder().into(),
             }
         })
-        .render_input_panel(input_panel(mobile))
+        .render_input_panel(input_panel(node, remote, mobile))
         .load_hook(move |mut record: Value| {
             flatten_property_string::<QemuConfigMachine>(&mut record, "machine")?;
 
@@ -220,6 +249,12 @@ pub fn qemu_machine_property(mobile: bool) -> EditableProperty {
                 }
                 data["_type"] = version.into();
 
+                let machine_type = machine_type.parse().unwrap_or(QemuMachineType::I440fx);
+                let viommu = form_ctx.read().get_field_text("_viommu");
+                if !viommu_supported(&viommu, machine_type) {
+                    data["_viommu"] = Value::Null;
+                }
+
                 property_string_add_missing_data::<QemuConfigMachine>(
                     &mut data,
                     &state.record,
@@ -232,3 +267,16 @@ pub fn qemu_machine_property(mobile: bool) -> EditableProperty {
             }
         })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_viommu_supported() {
+        assert!(viommu_supported("", QemuMachineType::I440fx));
+        assert!(viommu_supported("virtio", QemuMachineType::I440fx));
+        assert!(viommu_supported("intel", QemuMachineType::Q35));
+        assert!(!viommu_supported("intel", QemuMachineType::I440fx));
+    }
+}
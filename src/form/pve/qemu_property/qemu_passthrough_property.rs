@@ -0,0 +1,411 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+use regex::Regex;
+use serde_json::Value;
+
+use pwt::prelude::*;
+use pwt::state::Store;
+use pwt::widget::form::{Checkbox, Combobox, Field, ValidateFn};
+use pwt::widget::{FieldPosition, InputPanel};
+
+use crate::form::pve::{
+    PvePciDeviceSelector, PvePciMdevSelector, PveUsbDeviceSelector, UsbDeviceSelectMode,
+};
+use crate::form::{
+    delete_empty_values, flatten_property_string, property_string_add_missing_data,
+    property_string_from_parts,
+};
+use crate::pve_api_types::{QemuConfigHostpci, QemuConfigUsb};
+use crate::{EditableProperty, PropertyEditorState, RenderPropertyInputPanelFn};
+
+// maximum number of devices, see qemu-server
+pub(crate) const MAX_HOSTPCI_DEVICES: usize = 16;
+pub(crate) const MAX_USB_DEVICES: usize = 14;
+pub(crate) const MAX_SERIAL_PORTS: usize = 4;
+pub(crate) const MAX_PARALLEL_PORTS: usize = 3;
+
+/// Find the first unused `{prefix}N` key in `record`.
+pub(super) fn find_free_slot(record: &Value, prefix: &str, max: usize) -> Result<String, Error> {
+    for i in 0..max {
+        let name = format!("{prefix}{i}");
+        if record.get(&name).is_none() {
+            return Ok(name);
+        }
+    }
+    bail!(tr!("All {0} devices in use.", prefix));
+}
+
+pub(super) fn title_with_name(title: String, name: Option<&str>) -> String {
+    match name {
+        Some(name) => title + " (" + name + ")",
+        None => title,
+    }
+}
+
+fn hostpci_input_panel(node: Option<AttrValue>, mobile: bool) -> RenderPropertyInputPanelFn {
+    RenderPropertyInputPanelFn::new(move |state: PropertyEditorState| {
+        let form_ctx = state.form_ctx;
+        let advanced = form_ctx.get_show_advanced();
+        let host = form_ctx.read().get_field_text("_host");
+
+        InputPanel::new()
+            .mobile(mobile)
+            .show_advanced(advanced)
+            .label_width("max-content")
+            .class(pwt::css::FlexFit)
+            .padding_x(2)
+            .with_large_field(
+                tr!("Raw Device"),
+                PvePciDeviceSelector::new()
+                    .node(node.clone())
+                    .name("_host")
+                    .required(true),
+            )
+            .with_field(
+                tr!("All Functions"),
+                Checkbox::new().name("_all_functions").submit(false),
+            )
+            .with_right_field(tr!("Primary GPU"), Checkbox::new().name("_x-vga"))
+            .with_large_field(
+                tr!("MDev Type"),
+                PvePciMdevSelector::new(host.clone())
+                    .node(node.clone())
+                    .name("_mdev")
+                    .disabled(host.is_empty())
+                    .placeholder(tr!("None")),
+            )
+            .with_advanced_spacer()
+            .with_field_and_options(
+                FieldPosition::Left,
+                true,
+                false,
+                tr!("ROM-Bar"),
+                Checkbox::new().name("_rombar").default(true),
+            )
+            .with_field_and_options(
+                FieldPosition::Right,
+                true,
+                false,
+                tr!("PCI-Express"),
+                Checkbox::new().name("_pcie"),
+            )
+            .with_field_and_options(
+                FieldPosition::Left,
+                true,
+                false,
+                tr!("ROM-File"),
+                Field::new().name("_romfile"),
+            )
+            .into()
+    })
+}
+
+/// Strip the function number from a PCI ID (`0000:01:00.0` => `0000:01:00`).
+fn pci_id_without_function(id: &str) -> &str {
+    match id.rsplit_once('.') {
+        Some((base, function)) if function.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => id,
+    }
+}
+
+pub fn qemu_hostpci_property(
+    name: Option<String>,
+    node: Option<AttrValue>,
+    mobile: bool,
+) -> EditableProperty {
+    let title = title_with_name(tr!("PCI Device"), name.as_deref());
+    EditableProperty::new(name.clone(), title)
+        .advanced_checkbox(!mobile)
+        .render_input_panel(hostpci_input_panel(node, mobile))
+        .load_hook({
+            let name = name.clone();
+            move |mut record: Value| {
+                let Some(name) = name.as_deref() else {
+                    let _ = find_free_slot(&record, "hostpci", MAX_HOSTPCI_DEVICES)?;
+                    return Ok(record);
+                };
+                flatten_property_string::<QemuConfigHostpci>(&mut record, name)?;
+                // without function number, all functions are passed through
+                if let Some(host) = record["_host"].as_str() {
+                    if pci_id_without_function(host) == host {
+                        record["_host"] = format!("{host}.0").into();
+                        record["_all_functions"] = true.into();
+                    }
+                }
+                Ok(record)
+            }
+        })
+        .submit_hook({
+            let name = name.clone();
+            move |state: PropertyEditorState| {
+                let mut data = state.get_submit_data();
+                let name = match name.clone() {
+                    Some(name) => name,
+                    None => find_free_slot(&state.record, "hostpci", MAX_HOSTPCI_DEVICES)?,
+                };
+
+                if state.form_ctx.read().get_field_checked("_all_functions") {
+                    if let Some(host) = data["_host"].as_str() {
+                        data["_host"] = pci_id_without_function(host).into();
+                    }
+                }
+                // do not set default values
+                if let Value::Bool(true) = data["_rombar"] {
+                    data["_rombar"] = Value::Null;
+                }
+                for key in ["_pcie", "_x-vga"] {
+                    if let Value::Bool(false) = data[key] {
+                        data[key] = Value::Null;
+                    }
+                }
+
+                property_string_add_missing_data::<QemuConfigHostpci>(
+                    &mut data,
+                    &state.record,
+                    &state.form_ctx,
+                )?;
+                property_string_from_parts::<QemuConfigHostpci>(&mut data, &name, true)?;
+                Ok(delete_empty_values(&data, &[&name], false))
+            }
+        })
+}
+
+fn usb_input_panel(node: Option<AttrValue>, mobile: bool) -> RenderPropertyInputPanelFn {
+    RenderPropertyInputPanelFn::new(move |state: PropertyEditorState| {
+        let mode = state.form_ctx.read().get_field_text("_mode");
+        let select_mode = match mode.as_str() {
+            "port" => UsbDeviceSelectMode::Port,
+            _ => UsbDeviceSelectMode::Device,
+        };
+        let spice = usb_host_mode(&mode) == "spice";
+
+        InputPanel::new()
+            .mobile(mobile)
+            .label_width("max-content")
+            .class(pwt::css::FlexFit)
+            .padding_x(2)
+            .with_field(
+                tr!("Mode"),
+                Combobox::from_key_value_pairs([
+                    ("spice", tr!("Spice Port")),
+                    ("device", tr!("Use USB Vendor/Device ID")),
+                    ("port", tr!("Use USB Port")),
+                ])
+                .name("_mode")
+                .submit(false)
+                .required(true)
+                .default("spice"),
+            )
+            .with_large_field(
+                tr!("Choose Device"),
+                PveUsbDeviceSelector::new()
+                    // reload the device list when the mode changes
+                    .key(mode.clone())
+                    .node(node.clone())
+                    .mode(select_mode)
+                    .name("_host")
+                    .disabled(spice)
+                    .required(!spice),
+            )
+            .with_field(tr!("Use USB3"), Checkbox::new().name("_usb3"))
+            .into()
+    })
+}
+
+/// The selection mode for the `host` option of a USB device.
+fn usb_host_mode(host: &str) -> &'static str {
+    if host.is_empty() || host == "spice" {
+        "spice"
+    } else if host.contains('-') {
+        "port"
+    } else {
+        "device"
+    }
+}
+
+pub fn qemu_usb_property(
+    name: Option<String>,
+    node: Option<AttrValue>,
+    mobile: bool,
+) -> EditableProperty {
+    let title = title_with_name(tr!("USB Device"), name.as_deref());
+    EditableProperty::new(name.clone(), title)
+        .render_input_panel(usb_input_panel(node, mobile))
+        .load_hook({
+            let name = name.clone();
+            move |mut record: Value| {
+                let Some(name) = name.as_deref() else {
+                    let _ = find_free_slot(&record, "usb", MAX_USB_DEVICES)?;
+                    return Ok(record);
+                };
+                flatten_property_string::<QemuConfigUsb>(&mut record, name)?;
+                let mode = usb_host_mode(record["_host"].as_str().unwrap_or(""));
+                record["_mode"] = mode.into();
+                Ok(record)
+            }
+        })
+        .submit_hook({
+            let name = name.clone();
+            move |state: PropertyEditorState| {
+                let mut data = state.get_submit_data();
+                let name = match name.clone() {
+                    Some(name) => name,
+                    None => find_free_slot(&state.record, "usb", MAX_USB_DEVICES)?,
+                };
+
+                if state.form_ctx.read().get_field_text("_mode") == "spice" {
+                    data["_host"] = "spice".into();
+                }
+                if let Value::Bool(false) = data["_usb3"] {
+                    data["_usb3"] = Value::Null;
+                }
+
+                property_string_from_parts::<QemuConfigUsb>(&mut data, &name, true)?;
+                Ok(delete_empty_values(&data, &[&name], false))
+            }
+        })
+}
+
+/// Load hook for properties which are edited with a single `_value` field.
+fn load_single_value(
+    prefix: &'static str,
+    max: usize,
+    name: Option<String>,
+) -> Callback<Value, Result<Value, Error>> {
+    Callback::from(move |mut record: Value| {
+        match name.as_deref() {
+            Some(name) => record["_value"] = record[name].clone(),
+            None => {
+                let _ = find_free_slot(&record, prefix, max)?;
+            }
+        }
+        Ok(record)
+    })
+}
+
+/// Submit hook for properties which are edited with a single `_value` field.
+fn submit_single_value(
+    prefix: &'static str,
+    max: usize,
+    name: Option<String>,
+) -> Callback<PropertyEditorState, Result<Value, Error>> {
+    Callback::from(move |state: PropertyEditorState| {
+        let mut data = state.get_submit_data();
+        let name = match name.clone() {
+            Some(name) => name,
+            None => find_free_slot(&state.record, prefix, max)?,
+        };
+        if let Value::Object(map) = &mut data {
+            let value = map.remove("_value").unwrap_or(Value::Null);
+            map.insert(name.clone(), value);
+        }
+        Ok(delete_empty_values(&data, &[&name], false))
+    })
+}
+
+fn validate_serial_port(value: &str) -> Result<(), Error> {
+    thread_local! {
+        static SERIAL_MATCH: Regex = Regex::new(r"^(/dev/.+|socket)$").unwrap();
+    }
+    if !SERIAL_MATCH.with(|r| r.is_match(value)) {
+        bail!(tr!("Expected 'socket' or a host device like '/dev/ttyS0'."));
+    }
+    Ok(())
+}
+
+fn validate_parallel_port(value: &str) -> Result<(), Error> {
+    thread_local! {
+        static PARALLEL_MATCH: Regex = Regex::new(r"^/dev/(parport\d+|usb/lp\d+)$").unwrap();
+    }
+    if !PARALLEL_MATCH.with(|r| r.is_match(value)) {
+        bail!(tr!(
+            "Expected a host device like '/dev/parport0' or '/dev/usb/lp0'."
+        ));
+    }
+    Ok(())
+}
+
+pub fn qemu_serial_property(name: Option<String>, mobile: bool) -> EditableProperty {
+    let title = title_with_name(tr!("Serial Port"), name.as_deref());
+    EditableProperty::new(name.clone(), title.clone())
+        .render_input_panel(move |_| {
+            InputPanel::new()
+                .mobile(mobile)
+                .class(pwt::css::FlexFit)
+                .padding_x(2)
+                .with_field(
+                    tr!("Serial Port"),
+                    Combobox::new()
+                        .name("_value")
+                        .editable(true)
+                        .required(true)
+                        .default("socket")
+                        .items(Rc::new(vec![AttrValue::from("socket")]))
+                        .validate(ValidateFn::new(
+                            |(value, _store): &(String, Store<AttrValue>)| {
+                                validate_serial_port(value)
+                            },
+                        )),
+                )
+                .into()
+        })
+        .load_hook(load_single_value("serial", MAX_SERIAL_PORTS, name.clone()))
+        .submit_hook(submit_single_value("serial", MAX_SERIAL_PORTS, name))
+}
+
+pub fn qemu_parallel_property(name: Option<String>, mobile: bool) -> EditableProperty {
+    let title = title_with_name(tr!("Parallel Port"), name.as_deref());
+    EditableProperty::new(name.clone(), title)
+        .render_input_panel(move |_| {
+            InputPanel::new()
+                .mobile(mobile)
+                .class(pwt::css::FlexFit)
+                .padding_x(2)
+                .with_field(
+                    tr!("Host Device"),
+                    Field::new()
+                        .name("_value")
+                        .required(true)
+                        .placeholder("/dev/parport0")
+                        .validate(|value: &String| validate_parallel_port(value)),
+                )
+                .into()
+        })
+        .load_hook(load_single_value(
+            "parallel",
+            MAX_PARALLEL_PORTS,
+            name.clone(),
+        ))
+        .submit_hook(submit_single_value("parallel", MAX_PARALLEL_PORTS, name))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_find_free_slot() {
+        let record = json!({ "usb0": "spice", "usb1": "host=1-2" });
+        assert_eq!(find_free_slot(&record, "usb", 3).unwrap(), "usb2");
+        assert!(find_free_slot(&record, "usb", 2).is_err());
+    }
+
+    #[test]
+    fn test_passthrough_values() {
+        assert_eq!(pci_id_without_function("0000:01:00.1"), "0000:01:00");
+        assert_eq!(pci_id_without_function("0000:01:00"), "0000:01:00");
+
+        assert_eq!(usb_host_mode("spice"), "spice");
+        assert_eq!(usb_host_mode("1-2.3"), "port");
+        assert_eq!(usb_host_mode("046d:c52b"), "device");
+
+        assert!(validate_serial_port("socket").is_ok());
+        assert!(validate_serial_port("/dev/ttyS0").is_ok());
+        assert!(validate_serial_port("ttyS0").is_err());
+        assert!(validate_parallel_port("/dev/usb/lp0").is_ok());
+        assert!(validate_parallel_port("/dev/ttyS0").is_err());
+    }
+}
@@ -210,15 +210,29 @@ pub fn qemu_sockets_cores_property(
     })
 }
 
-fn cpu_flags_input_panel(_mobile: bool) -> RenderPropertyInputPanelFn {
-    RenderPropertyInputPanelFn::new(move |_| QemuCpuFlags::new().name("_flags").into())
+fn cpu_flags_input_panel(
+    node: AttrValue,
+    remote: Option<AttrValue>,
+    _mobile: bool,
+) -> RenderPropertyInputPanelFn {
+    RenderPropertyInputPanelFn::new(move |_| {
+        QemuCpuFlags::new()
+            .node(node.clone())
+            .remote(remote.clone())
+            .name("_flags")
+            .into()
+    })
 }
 
-pub fn qemu_cpu_flags_property(mobile: bool) -> EditableProperty {
+pub fn qemu_cpu_flags_property(
+    node: AttrValue,
+    remote: Option<AttrValue>,
+    mobile: bool,
+) -> EditableProperty {
     EditableProperty::new("cpu", tr!("CPU flags"))
         .required(true)
         .renderer(renderer)
-        .render_input_panel(cpu_flags_input_panel(mobile))
+        .render_input_panel(cpu_flags_input_panel(node, remote, mobile))
         .load_hook(move |mut record: Value| {
             flatten_property_string::<PveVmCpuConf>(&mut record, "cpu")?;
             Ok(record)
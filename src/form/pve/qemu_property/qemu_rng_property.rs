@@ -0,0 +1,84 @@
+use std::rc::Rc;
+
+use pwt::prelude::*;
+use pwt::widget::form::{Combobox, Number};
+use pwt::widget::{Container, FieldPosition, InputPanel};
+
+use crate::form::{property_string_load_hook, property_string_submit_hook};
+use crate::pve_api_types::QemuConfigRng;
+use crate::{EditableProperty, PropertyEditorState, RenderPropertyInputPanelFn};
+
+fn input_panel(mobile: bool) -> RenderPropertyInputPanelFn {
+    RenderPropertyInputPanelFn::new(move |state: PropertyEditorState| {
+        let form_ctx = state.form_ctx;
+        let advanced = form_ctx.get_show_advanced();
+        let source = form_ctx.read().get_field_text("_source");
+        let max_bytes = form_ctx.read().get_field_text("_max_bytes");
+
+        let hint = |msg: String| Container::new().class("pwt-color-warning").with_child(msg);
+
+        let random_hint = hint(tr!(
+            "Using /dev/random as entropy source is discouraged, as it can lead to host entropy starvation. /dev/urandom is preferred, and does not lead to a decrease in security in practice."
+        ))
+        .key("random_hint");
+
+        let limit_hint = hint(tr!(
+            "Disabling the limiter can potentially allow a guest to overload the host. Proceed with caution."
+        ))
+        .key("limit_hint");
+
+        InputPanel::new()
+            .mobile(mobile)
+            .show_advanced(advanced)
+            .class(pwt::css::FlexFit)
+            .padding_x(2)
+            .with_field(
+                tr!("Entropy source"),
+                Combobox::new()
+                    .name("_source")
+                    .required(true)
+                    .default("/dev/urandom")
+                    .items(Rc::new(vec![
+                        AttrValue::from("/dev/urandom"),
+                        AttrValue::from("/dev/random"),
+                        AttrValue::from("/dev/hwrng"),
+                    ])),
+            )
+            .with_custom_child_and_options(
+                FieldPosition::Left,
+                false,
+                source != "/dev/random",
+                random_hint,
+            )
+            .with_field_and_options(
+                FieldPosition::Left,
+                true,
+                false,
+                tr!("Limit (Bytes/Period)"),
+                Number::<u64>::new()
+                    .name("_max_bytes")
+                    .placeholder("1024")
+                    .min(0),
+            )
+            .with_field_and_options(
+                FieldPosition::Left,
+                true,
+                false,
+                tr!("Period") + " (ms)",
+                Number::<u64>::new()
+                    .name("_period")
+                    .placeholder("1000")
+                    .min(1),
+            )
+            .with_custom_child_and_options(FieldPosition::Left, true, max_bytes != "0", limit_hint)
+            .into()
+    })
+}
+
+pub fn qemu_rng_property(mobile: bool) -> EditableProperty {
+    EditableProperty::new("rng0", tr!("VirtIO RNG"))
+        .advanced_checkbox(!mobile)
+        .render_input_panel(input_panel(mobile))
+        .load_hook(property_string_load_hook::<QemuConfigRng>("rng0"))
+        .submit_hook(property_string_submit_hook::<QemuConfigRng>("rng0", true))
+}
@@ -0,0 +1,118 @@
+use serde_json::Value;
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Combobox};
+use pwt::widget::{FieldPosition, InputPanel};
+
+use crate::form::pve::PveDirMappingSelector;
+use crate::form::{delete_empty_values, flatten_property_string, property_string_from_parts};
+use crate::pve_api_types::QemuConfigVirtiofs;
+use crate::{EditableProperty, PropertyEditorState, RenderPropertyInputPanelFn};
+
+use super::qemu_passthrough_property::{find_free_slot, title_with_name};
+
+// maximum number of virtiofs devices, see qemu-server
+pub(crate) const MAX_VIRTIOFS_DEVICES: usize = 10;
+
+fn input_panel(node: Option<AttrValue>, mobile: bool) -> RenderPropertyInputPanelFn {
+    RenderPropertyInputPanelFn::new(move |state: PropertyEditorState| {
+        let form_ctx = state.form_ctx;
+        let advanced = form_ctx.get_show_advanced();
+        // ACL support requires extended attributes
+        let expose_acl = form_ctx.read().get_field_checked("_expose-acl");
+
+        InputPanel::new()
+            .mobile(mobile)
+            .show_advanced(advanced)
+            .label_width("max-content")
+            .class(pwt::css::FlexFit)
+            .padding_x(2)
+            .with_large_field(
+                tr!("Directory ID"),
+                PveDirMappingSelector::new()
+                    .node(node.clone())
+                    .name("_dirid")
+                    .required(true),
+            )
+            .with_field(
+                tr!("Cache"),
+                Combobox::from_key_value_pairs([
+                    ("auto", "auto"),
+                    ("always", "always"),
+                    ("metadata", "metadata"),
+                    ("never", "never"),
+                ])
+                .name("_cache")
+                .placeholder(tr!("Default") + " (auto)"),
+            )
+            .with_advanced_spacer()
+            .with_field_and_options(
+                FieldPosition::Left,
+                true,
+                false,
+                tr!("xattr Support"),
+                Checkbox::new()
+                    .name("_expose-xattr")
+                    .disabled(expose_acl)
+                    .submit(!expose_acl),
+            )
+            .with_field_and_options(
+                FieldPosition::Right,
+                true,
+                false,
+                tr!("POSIX ACLs"),
+                Checkbox::new().name("_expose-acl"),
+            )
+            .with_field_and_options(
+                FieldPosition::Left,
+                true,
+                false,
+                tr!("Allow Direct IO"),
+                Checkbox::new().name("_direct-io"),
+            )
+            .into()
+    })
+}
+
+pub fn qemu_virtiofs_property(
+    name: Option<String>,
+    node: Option<AttrValue>,
+    mobile: bool,
+) -> EditableProperty {
+    let title = title_with_name(tr!("Virtiofs"), name.as_deref());
+    EditableProperty::new(name.clone(), title)
+        .advanced_checkbox(!mobile)
+        .render_input_panel(input_panel(node, mobile))
+        .load_hook({
+            let name = name.clone();
+            move |mut record: Value| {
+                match name.as_deref() {
+                    Some(name) => flatten_property_string::<QemuConfigVirtiofs>(&mut record, name)?,
+                    None => {
+                        let _ = find_free_slot(&record, "virtiofs", MAX_VIRTIOFS_DEVICES)?;
+                    }
+                }
+                Ok(record)
+            }
+        })
+        .submit_hook({
+            let name = name.clone();
+            move |state: PropertyEditorState| {
+                let mut data = state.get_submit_data();
+                let name = match name.clone() {
+                    Some(name) => name,
+                    None => find_free_slot(&state.record, "virtiofs", MAX_VIRTIOFS_DEVICES)?,
+                };
+
+                // do not set default values
+                for key in ["_expose-xattr", "_expose-acl", "_direct-io"] {
+                    if let Value::Bool(false) = data[key] {
+                        data[key] = Value::Null;
+                    }
+                }
+
+                property_string_from_parts::<QemuConfigVirtiofs>(&mut data, &name, true)?;
+                Ok(delete_empty_values(&data, &[&name], false))
+            }
+        })
+}
@@ -1,6 +1,8 @@
 use std::rc::Rc;
 
+use proxmox_human_byte::HumanByte;
 use pwt::widget::Column;
+use yew::html::IntoPropValue;
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
@@ -26,6 +28,43 @@ pub struct Gauge {
     #[prop_or(0.8)]
     #[builder]
     pub warning_threshold: f32,
+
+    /// Mark the warning and critical thresholds on the gauge.
+    #[prop_or_default]
+    #[builder]
+    pub show_thresholds: bool,
+
+    /// Custom colors (fraction, CSS color), used from the given fraction upwards.
+    ///
+    /// Replace the warning and critical colors if set.
+    #[prop_or_default]
+    pub color_stops: Vec<(f32, AttrValue)>,
+
+    /// Width of the arc (the radius is 100).
+    #[prop_or(10.0)]
+    #[builder]
+    pub arc_width: f32,
+
+    /// Start angle of the arc in degrees.
+    ///
+    /// Angles are measured clockwise, starting at the left (9 o'clock). The
+    /// default is a half circle from 0 to 180 degrees.
+    #[prop_or(0.0)]
+    #[builder]
+    pub start_angle: f32,
+
+    /// End angle of the arc in degrees, see [Self::start_angle].
+    ///
+    /// The arc spans less than a full circle, so the difference to the start angle
+    /// is limited to 359 degrees.
+    #[prop_or(180.0)]
+    #[builder]
+    pub end_angle: f32,
+
+    /// Secondary value line, shown below the percentage (e.g. "12.1 GiB of 32 GiB").
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub secondary_text: Option<AttrValue>,
 }
 
 impl Default for Gauge {
@@ -40,6 +79,47 @@ impl Gauge {
         yew::props!(Self {})
     }
 
+    /// Builder style method to set the warning threshold in percent.
+    pub fn warning_threshold_percent(mut self, percent: f32) -> Self {
+        self.warning_threshold = percent / 100.0;
+        self
+    }
+
+    /// Builder style method to set the critical threshold in percent.
+    pub fn critical_threshold_percent(mut self, percent: f32) -> Self {
+        self.critical_threshold = percent / 100.0;
+        self
+    }
+
+    /// Builder style method to show a usage, sets the value and the secondary text.
+    pub fn usage(mut self, used: u64, total: u64) -> Self {
+        self.value = if total > 0 {
+            ((used as f64) / (total as f64)) as f32
+        } else {
+            0.0
+        };
+        self.secondary_text = Some(
+            tr!(
+                "{0} of {1}",
+                HumanByte::from(used).to_string(),
+                HumanByte::from(total).to_string()
+            )
+            .into(),
+        );
+        self
+    }
+
+    /// Builder style method to add a color stop.
+    pub fn color_stop(mut self, fraction: f32, color: impl Into<AttrValue>) -> Self {
+        self.add_color_stop(fraction, color);
+        self
+    }
+
+    /// Method to add a color stop.
+    pub fn add_color_stop(&mut self, fraction: f32, color: impl Into<AttrValue>) {
+        self.color_stops.push((fraction, color.into()));
+    }
+
     /// Builder style method to set the status text.
     pub fn status(mut self, status: impl IntoOptionalInlineHtml) -> Self {
         self.set_status(status);
@@ -70,11 +150,28 @@ impl Component for ProxmoxGauge {
         let fraction = fraction.clamp(0f32, 1f32);
 
         let r = 100f32;
-        let stroke_width = 10.0;
+        let stroke_width = props.arc_width.clamp(1.0, r);
         let space = stroke_width / 2.0;
 
-        let x = (r + space) - (std::f32::consts::PI * fraction).cos() * r;
-        let y = (r + space) - (std::f32::consts::PI * fraction).sin() * r;
+        let sweep = (props.end_angle - props.start_angle).clamp(1.0, 359.0);
+        let start_angle = props.start_angle.to_radians();
+        let sweep_angle = sweep.to_radians();
+
+        let (width, height, center_x, center_y) = arc_bounds(start_angle, sweep_angle, r + space);
+
+        // position on the arc with distance `d` from the center
+        let arc_point = |fraction: f32, d: f32| {
+            let angle = start_angle + sweep_angle * fraction;
+            (center_x - angle.cos() * d, center_y - angle.sin() * d)
+        };
+
+        // SVG path of the arc from the start up to `fraction`
+        let arc_path = |fraction: f32| {
+            let (x1, y1) = arc_point(0.0, r);
+            let (x2, y2) = arc_point(fraction, r);
+            let large_arc = if sweep * fraction > 180.0 { 1 } else { 0 };
+            format!("M {x1},{y1} A {r},{r} 0,{large_arc},1 {x2},{y2}")
+        };
 
         let color_class = if fraction > props.critical_threshold {
             "pwt-stroke-error"
@@ -84,44 +181,79 @@ impl Component for ProxmoxGauge {
             "pwt-stroke-primary"
         };
 
+        let stop_color = props
+            .color_stops
+            .iter()
+            .filter(|(stop, _)| fraction >= *stop)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, color)| color);
+
+        let value_path = Path::new()
+            .fill("none")
+            .stroke_width(stroke_width)
+            .d(arc_path(fraction));
+        let value_path = match stop_color {
+            Some(color) => value_path.attribute("style", format!("stroke:{color}")),
+            None => value_path.class(color_class),
+        };
+
         let percentage = (fraction * 1000.0).round() / 10.0;
         let percentage = format!("{}%", percentage);
 
-        let canvas = Canvas::new()
-            .width(2.0 * (r + space))
-            .height(r + space)
+        // text is centered if the arc surrounds the center, else placed above it
+        let text_y = if sweep > 180.0 {
+            center_y + 10.0
+        } else {
+            center_y - 15.0
+        };
+        let text_y = match props.secondary_text {
+            Some(_) => text_y - 20.0,
+            None => text_y,
+        };
+
+        let mut canvas = Canvas::new()
+            .width(width)
+            .height(height)
             .with_child(
                 Path::new()
                     .fill("none")
                     .class("pwt-stroke-surface")
                     .stroke_width(stroke_width)
-                    .d(format!(
-                        "M {},{} A {r},{r} 0,0,1 {},{}",
-                        space,
-                        space + r,
-                        2.0 * r + space,
-                        r + space,
-                    )),
-            )
-            .with_child(
-                Path::new()
-                    .fill("none")
-                    .class(color_class)
-                    .stroke_width(stroke_width)
-                    .d(format!(
-                        "M {},{} A {r},{r} 0,0,1 {},{}",
-                        space,
-                        space + r,
-                        x,
-                        y,
-                    )),
+                    .d(arc_path(1.0)),
             )
+            .with_child(value_path)
             .with_child(
                 Text::new(percentage)
                     .class("pwt-font-display-medium")
                     .attribute("text-anchor", "middle")
-                    .position(r + space, r - 15.0),
+                    .position(center_x, text_y),
+            );
+
+        if let Some(secondary_text) = &props.secondary_text {
+            canvas.add_child(
+                Text::new(secondary_text.to_string())
+                    .class("pwt-font-body-medium")
+                    .attribute("text-anchor", "middle")
+                    .position(center_x, text_y + 22.0),
             );
+        }
+
+        if props.show_thresholds {
+            for (threshold, class) in [
+                (props.warning_threshold, "pwt-stroke-warning"),
+                (props.critical_threshold, "pwt-stroke-error"),
+            ] {
+                let threshold = threshold.clamp(0f32, 1f32);
+                let (x1, y1) = arc_point(threshold, r - stroke_width);
+                let (x2, y2) = arc_point(threshold, r + space);
+                canvas.add_child(
+                    Path::new()
+                        .class(class)
+                        .stroke_width(2.0)
+                        .d(format!("M {x1},{y1} L {x2},{y2}")),
+                );
+            }
+        }
 
         let mut column = Column::new()
             .class("pwt-align-items-center")
@@ -138,9 +270,63 @@ impl Component for ProxmoxGauge {
     }
 }
 
+// Bounding box (width, height) and center position of an arc with radius `r`,
+// including the center itself.
+fn arc_bounds(start_angle: f32, sweep_angle: f32, r: f32) -> (f32, f32, f32, f32) {
+    let point = |angle: f32| (-angle.cos() * r, -angle.sin() * r);
+
+    let mut points = vec![
+        (0.0, 0.0),
+        point(start_angle),
+        point(start_angle + sweep_angle),
+    ];
+    // extreme points, if the arc passes them
+    let quarter = std::f32::consts::FRAC_PI_2;
+    let first = (start_angle / quarter).ceil() as i32;
+    let last = ((start_angle + sweep_angle) / quarter).floor() as i32;
+    for i in first..=last {
+        points.push(point(i as f32 * quarter));
+    }
+
+    let min_x = points.iter().map(|p| p.0).fold(f32::MAX, f32::min);
+    let max_x = points.iter().map(|p| p.0).fold(f32::MIN, f32::max);
+    let min_y = points.iter().map(|p| p.1).fold(f32::MAX, f32::min);
+    let max_y = points.iter().map(|p| p.1).fold(f32::MIN, f32::max);
+
+    (max_x - min_x, max_y - min_y, -min_x, -min_y)
+}
+
 impl From<Gauge> for VNode {
     fn from(val: Gauge) -> Self {
         let comp = VComp::new::<ProxmoxGauge>(Rc::new(val), None);
         VNode::from(comp)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round(v: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        (v.0.round(), v.1.round(), v.2.round(), v.3.round())
+    }
+
+    #[test]
+    fn test_arc_bounds() {
+        let pi = std::f32::consts::PI;
+        // default half circle
+        assert_eq!(
+            round(arc_bounds(0.0, pi, 105.0)),
+            (210.0, 105.0, 105.0, 105.0)
+        );
+        // quarter circle from left to top
+        assert_eq!(
+            round(arc_bounds(0.0, pi / 2.0, 100.0)),
+            (100.0, 100.0, 100.0, 100.0)
+        );
+        // three quarters, from bottom left to bottom right
+        let (width, height, x, y) = round(arc_bounds(-pi / 4.0, pi * 1.5, 100.0));
+        assert_eq!((width, x, y), (200.0, 100.0, 100.0));
+        assert_eq!(height, 171.0);
+    }
+}
@@ -0,0 +1,325 @@
+//! Persistent column state for data tables.
+//!
+//! Components with a `state_id` property store the user's column adjustments
+//! (visibility, width, order and sort) in the browser's local storage, and restore
+//! them the next time the grid is shown.
+//!
+//! Columns are identified by their key (see `DataTableColumn::key`), so the saved state
+//! does not depend on the (translated) column name. Columns without a key are not saved.
+
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use yew::virtual_dom::Key;
+
+use pwt::prelude::*;
+use pwt::state::{DataStore, PersistentState};
+use pwt::widget::data_table::{DataTable, DataTableHeader};
+use pwt::widget::menu::{Menu, MenuButton, MenuItem};
+
+/// Saved state of a single column, identified by its key.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GridColumnState {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// Saved state of a data table.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GridState {
+    /// Adjusted columns, in display order.
+    #[serde(default)]
+    pub columns: Vec<GridColumnState>,
+    /// Sort column and direction (`true` means ascending).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<(String, bool)>,
+}
+
+impl GridState {
+    fn lookup(&self, id: &str) -> Option<&GridColumnState> {
+        self.columns.iter().find(|column| column.id == id)
+    }
+
+    fn lookup_mut(&mut self, id: &str) -> &mut GridColumnState {
+        let pos = match self.columns.iter().position(|column| column.id == id) {
+            Some(pos) => pos,
+            None => {
+                self.columns.push(GridColumnState {
+                    id: id.to_string(),
+                    ..Default::default()
+                });
+                self.columns.len() - 1
+            }
+        };
+        &mut self.columns[pos]
+    }
+
+    /// Returns the saved visibility of a column.
+    pub fn hidden(&self, id: &str) -> Option<bool> {
+        self.lookup(id).map(|column| column.hidden)
+    }
+
+    pub fn set_hidden(&mut self, id: &str, hidden: bool) {
+        self.lookup_mut(id).hidden = hidden;
+    }
+
+    pub fn set_width(&mut self, id: &str, width: impl Into<String>) {
+        self.lookup_mut(id).width = Some(width.into());
+    }
+
+    /// Set the column order. Columns not listed keep their default position.
+    pub fn set_order(&mut self, ids: &[&str]) {
+        for id in ids {
+            self.lookup_mut(id);
+        }
+        self.columns.sort_by_key(|column| {
+            ids.iter()
+                .position(|id| *id == column.id)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    /// Set the sort column, `None` removes the saved sort order.
+    pub fn set_sort(&mut self, sort: Option<(&str, bool)>) {
+        self.sort = sort.map(|(id, ascending)| (id.to_string(), ascending));
+    }
+
+    /// Compute the display order of columns (indices into `ids`).
+    ///
+    /// Saved columns are placed in saved order, using the slots of saved columns.
+    /// Unknown columns and columns without key keep their position.
+    pub fn column_order(&self, ids: &[Option<&str>]) -> Vec<usize> {
+        let saved_pos = |id: &Option<&str>| {
+            id.and_then(|id| self.columns.iter().position(|column| column.id == id))
+        };
+
+        let mut saved: Vec<usize> = (0..ids.len())
+            .filter(|i| saved_pos(&ids[*i]).is_some())
+            .collect();
+        saved.sort_by_key(|i| saved_pos(&ids[*i]));
+
+        let mut saved = saved.into_iter();
+        (0..ids.len())
+            .map(|i| match saved_pos(&ids[i]) {
+                Some(_) => saved.next().unwrap(),
+                None => i,
+            })
+            .collect()
+    }
+
+    /// Apply the saved state to the top level columns of a data table.
+    pub fn apply<T: 'static>(&self, headers: &[DataTableHeader<T>]) -> Vec<DataTableHeader<T>> {
+        let ids: Vec<Option<&str>> = headers.iter().map(header_id).collect();
+
+        self.column_order(&ids)
+            .into_iter()
+            .map(|i| match (&headers[i], ids[i]) {
+                (DataTableHeader::Column(column), Some(id)) => {
+                    let mut column = column.clone();
+                    if let Some(state) = self.lookup(id) {
+                        if let Some(width) = &state.width {
+                            column = column.width(width.clone());
+                        }
+                        column = column.hidden(state.hidden);
+                    }
+                    if let Some((sort_id, ascending)) = &self.sort {
+                        let sort_order = (sort_id == id).then_some(*ascending);
+                        column = column.sort_order(sort_order);
+                    }
+                    column.into()
+                }
+                (header, _) => header.clone(),
+            })
+            .collect()
+    }
+}
+
+fn header_id<T: 'static>(header: &DataTableHeader<T>) -> Option<&str> {
+    match header {
+        DataTableHeader::Column(column) => column.key.as_ref().map(|key| &**key),
+        _ => None,
+    }
+}
+
+/// Load the saved state of the grid with the given id.
+pub fn load_grid_state(state_id: &str) -> PersistentState<GridState> {
+    let state_id = format!("proxmox-grid-state-{state_id}");
+    PersistentState::new(state_id.as_str())
+}
+
+/// Saved grid state, together with the columns it is applied to.
+///
+/// The columns with the saved state applied are cached, and only recomputed when the
+/// state or the columns change.
+pub struct PersistentGridState<T: 'static> {
+    state: PersistentState<GridState>,
+    columns: Rc<Vec<DataTableHeader<T>>>,
+    applied: Rc<Vec<DataTableHeader<T>>>,
+}
+
+impl<T: 'static> PersistentGridState<T> {
+    /// Load the saved state of the grid with the given id, and apply it to `columns`.
+    pub fn new(state_id: &str, columns: Rc<Vec<DataTableHeader<T>>>) -> Self {
+        let state = load_grid_state(state_id);
+        let applied = Rc::new(state.apply(&columns));
+        Self {
+            state,
+            columns,
+            applied,
+        }
+    }
+
+    /// The saved state.
+    pub fn state(&self) -> &GridState {
+        &self.state
+    }
+
+    /// The columns with the saved state applied.
+    pub fn columns(&self) -> Rc<Vec<DataTableHeader<T>>> {
+        Rc::clone(&self.applied)
+    }
+
+    /// Replace the columns the state is applied to.
+    pub fn set_columns(&mut self, columns: Rc<Vec<DataTableHeader<T>>>) {
+        if !Rc::ptr_eq(&self.columns, &columns) {
+            self.columns = columns;
+            self.applied = Rc::new(self.state.apply(&self.columns));
+        }
+    }
+
+    /// Store a new state.
+    pub fn update(&mut self, state: GridState) {
+        if *self.state != state {
+            self.state.update(state);
+            self.applied = Rc::new(self.state.apply(&self.columns));
+        }
+    }
+
+    /// Create a "Columns" menu for the grid, see [grid_columns_menu].
+    pub fn columns_menu(&self, on_change: impl Into<Callback<GridState>>) -> MenuButton {
+        grid_columns_menu(&self.columns, &self.state, on_change)
+    }
+
+    /// Report width, order and sort changes made in the table header to `on_change`.
+    pub fn track<S: DataStore>(
+        &self,
+        table: DataTable<S>,
+        on_change: impl Into<Callback<GridState>>,
+    ) -> DataTable<S> {
+        let on_change = on_change.into();
+        let state: GridState = (*self.state).clone();
+
+        table
+            .on_column_width_change({
+                let state = state.clone();
+                let on_change = on_change.clone();
+                move |(key, width): (Key, f64)| {
+                    let mut state = state.clone();
+                    state.set_width(&key, format!("{width}px"));
+                    on_change.emit(state);
+                }
+            })
+            .on_column_order_change({
+                let state = state.clone();
+                let on_change = on_change.clone();
+                move |keys: Vec<Key>| {
+                    let mut state = state.clone();
+                    let ids: Vec<&str> = keys.iter().map(|key| &**key).collect();
+                    state.set_order(&ids);
+                    on_change.emit(state);
+                }
+            })
+            .on_sort_change(move |sorters: Vec<(Key, bool)>| {
+                let mut state = state.clone();
+                state.set_sort(sorters.first().map(|(key, ascending)| (&**key, *ascending)));
+                on_change.emit(state);
+            })
+    }
+}
+
+/// Create a "Columns" menu to toggle column visibility and to reset the saved state.
+///
+/// `on_change` is called with the new state, which should be stored by the caller.
+pub fn grid_columns_menu<T: 'static>(
+    headers: &[DataTableHeader<T>],
+    state: &GridState,
+    on_change: impl Into<Callback<GridState>>,
+) -> MenuButton {
+    let on_change = on_change.into();
+
+    let mut menu = Menu::new();
+    for header in headers {
+        let DataTableHeader::Column(column) = header else {
+            continue;
+        };
+        let Some(id) = header_id(header) else {
+            continue;
+        };
+
+        let hidden = state.hidden(id).unwrap_or(column.hidden);
+        let icon_class = if hidden {
+            "fa fa-fw fa-square-o"
+        } else {
+            "fa fa-fw fa-check-square-o"
+        };
+
+        let mut new_state = state.clone();
+        new_state.set_hidden(id, !hidden);
+        let on_change = on_change.clone();
+        menu = menu.with_item(
+            MenuItem::new(column.name.clone())
+                .icon_class(icon_class)
+                .on_select(move |_| on_change.emit(new_state.clone())),
+        );
+    }
+
+    menu = menu.with_item(
+        MenuItem::new(tr!("Reset"))
+            .icon_class("fa fa-fw fa-undo")
+            .on_select(move |_| on_change.emit(GridState::default())),
+    );
+
+    MenuButton::new(tr!("Columns"))
+        .icon_class("fa fa-columns")
+        .show_arrow(true)
+        .menu(menu)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_column_order() {
+        let mut state = GridState::default();
+        state.set_order(&["c", "a"]);
+
+        // unnamed and unknown columns keep their slot
+        let ids = [None, Some("a"), Some("b"), Some("c")];
+        assert_eq!(state.column_order(&ids), vec![0, 3, 2, 1]);
+
+        assert_eq!(GridState::default().column_order(&ids), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_set_hidden() {
+        let mut state = GridState::default();
+        assert_eq!(state.hidden("a"), None);
+        state.set_hidden("a", true);
+        state.set_width("a", "100px");
+        assert_eq!(state.hidden("a"), Some(true));
+        assert_eq!(state.columns.len(), 1);
+    }
+
+    #[test]
+    fn test_set_sort() {
+        let mut state = GridState::default();
+        state.set_sort(Some(("a", false)));
+        assert_eq!(state.sort, Some(("a".to_string(), false)));
+        state.set_sort(None);
+        assert_eq!(state.sort, None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::rc::Rc;
+
+use anyhow::{format_err, Error};
+use serde::{Deserialize, Serialize};
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::Key;
+
+use pwt::prelude::*;
+use pwt::props::{FieldBuilder, RenderFn, WidgetBuilder};
+use pwt::state::Store;
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::form::{Selector, SelectorRenderArgs, ValidateFn};
+use pwt::widget::GridPicker;
+
+use pwt_macros::{builder, widget};
+
+/// Group list entry (`GET /access/groups`, PVE only).
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct GroupListEntry {
+    pub groupid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+thread_local! {
+    static COLUMNS: Rc<Vec<DataTableHeader<GroupListEntry>>> = Rc::new(vec![
+        DataTableColumn::new(tr!("Group"))
+            .width("150px")
+            .show_menu(false)
+            .render(|item: &GroupListEntry| html!{item.groupid.clone()})
+            .sorter(|a: &GroupListEntry, b: &GroupListEntry| a.groupid.cmp(&b.groupid))
+            .sort_order(true)
+            .into(),
+        DataTableColumn::new(tr!("Comment"))
+            .width("300px")
+            .show_menu(false)
+            .render(|item: &GroupListEntry| {
+                html!{item.comment.clone().unwrap_or_default()}
+            })
+            .into(),
+    ]);
+}
+
+/// Selector for user groups.
+#[widget(comp=ProxmoxGroupSelector, @input)]
+#[derive(Clone, Properties, PartialEq)]
+#[builder]
+pub struct GroupSelector {
+    /// The default value.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub default: Option<AttrValue>,
+
+    /// The path for getting the group list.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or("/access/groups".into())]
+    pub path: AttrValue,
+}
+
+impl Default for GroupSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroupSelector {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxGroupSelector {
+    store: Store<GroupListEntry>,
+    validate: ValidateFn<(String, Store<GroupListEntry>)>,
+    picker: RenderFn<SelectorRenderArgs<Store<GroupListEntry>>>,
+}
+
+impl Component for ProxmoxGroupSelector {
+    type Message = ();
+    type Properties = GroupSelector;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let store =
+            Store::with_extract_key(|item: &GroupListEntry| Key::from(item.groupid.clone()))
+                .on_change(ctx.link().callback(|_| ())); // trigger redraw
+
+        let validate = ValidateFn::new(|(group, store): &(String, Store<GroupListEntry>)| {
+            store
+                .read()
+                .data()
+                .iter()
+                .find(|item| &item.groupid == group)
+                .map(drop)
+                .ok_or_else(|| format_err!("no such group"))
+        });
+
+        let picker = RenderFn::new(|args: &SelectorRenderArgs<Store<GroupListEntry>>| {
+            let table =
+                DataTable::new(COLUMNS.with(Rc::clone), args.store.clone()).class("pwt-fit");
+
+            GridPicker::new(table)
+                .selection(args.selection.clone())
+                .on_select(args.controller.on_select_callback())
+                .into()
+        });
+
+        Self {
+            store,
+            validate,
+            picker,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        Selector::new(self.store.clone(), self.picker.clone())
+            .with_std_props(&props.std_props)
+            .with_input_props(&props.input_props)
+            .default(&props.default)
+            .loader((
+                |url: AttrValue| async move {
+                    let groups: Vec<GroupListEntry> = crate::http_get(&*url, None).await?;
+                    Ok::<_, Error>(groups)
+                },
+                props.path.clone(),
+            ))
+            .validate(self.validate.clone())
+            .into()
+    }
+}
@@ -0,0 +1,281 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+use proxmox_client::ApiResponseData;
+use serde::Deserialize;
+use serde_json::Value;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Field, FormContext, Number};
+use pwt::widget::{error_message, Container, InputPanel};
+
+use pwt_macros::builder;
+
+use crate::form::delete_empty_values;
+use crate::percent_encoding::percent_encode_component;
+use crate::EditWindow;
+
+use super::{format_node_priorities, parse_node_priorities};
+
+fn node_field(node: &str) -> String {
+    format!("_node_{node}")
+}
+
+fn priority_field(node: &str) -> String {
+    format!("_priority_{node}")
+}
+
+/// Edit window for HA groups, including a per-node priority grid.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct HaGroupEdit {
+    /// Edit existing group.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub group: Option<AttrValue>,
+
+    #[prop_or("/cluster/ha".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the HA API.
+    pub base_url: AttrValue,
+
+    #[prop_or("/nodes".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The url listing the cluster nodes.
+    pub nodes_url: AttrValue,
+
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for HaGroupEdit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HaGroupEdit {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[derive(Deserialize)]
+struct NodeListEntry {
+    node: String,
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Nodes(Result<Vec<NodeListEntry>, Error>),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxHaGroupEdit {
+    nodes: Option<Result<Rc<Vec<String>>, String>>,
+}
+
+/// Convert the loaded group config into form values.
+fn config_to_form(mut data: Value) -> Value {
+    let nodes = parse_node_priorities(data["nodes"].as_str().unwrap_or_default());
+    for (node, priority) in nodes {
+        data[node_field(&node)] = true.into();
+        if let Some(priority) = priority {
+            data[priority_field(&node)] = priority.into();
+        }
+    }
+    data
+}
+
+/// Convert the form values into the group config.
+fn form_to_config(form_ctx: &FormContext, nodes: &[String]) -> Result<Value, Error> {
+    let mut data = form_ctx.get_submit_data();
+    let form = form_ctx.read();
+
+    let nodes: Vec<(String, Option<u32>)> = nodes
+        .iter()
+        .filter(|node| form.get_field_checked(node_field(node)))
+        .map(|node| {
+            let priority = form.get_field_text(priority_field(node)).parse().ok();
+            (node.clone(), priority)
+        })
+        .collect();
+
+    if nodes.is_empty() {
+        bail!(tr!("At least one node is required."));
+    }
+    data["nodes"] = format_node_priorities(&nodes).into();
+
+    Ok(data)
+}
+
+async fn submit_item(
+    form_ctx: FormContext,
+    base_url: String,
+    group: Option<AttrValue>,
+    nodes: Rc<Vec<String>>,
+) -> Result<(), Error> {
+    let mut data = form_to_config(&form_ctx, &nodes)?;
+    match group {
+        Some(group) => {
+            let url = format!("{base_url}/{}", percent_encode_component(&group));
+            let data = delete_empty_values(&data, &["comment", "restricted", "nofailback"], false);
+            crate::http_put(url, Some(data)).await
+        }
+        None => {
+            if let Some(map) = data.as_object_mut() {
+                map.retain(|_, value| !(value.is_null() || value.as_str() == Some("")));
+            }
+            crate::http_post(base_url, Some(data)).await
+        }
+    }
+}
+
+fn render_node_grid(
+    form_ctx: &FormContext,
+    nodes: &Option<Result<Rc<Vec<String>>, String>>,
+) -> Html {
+    let mut grid = Container::new()
+        .key("node_priorities")
+        .class("pwt-d-grid pwt-gap-2 pwt-align-items-center")
+        .style("grid-template-columns", "1fr 150px");
+
+    match nodes {
+        None => grid.add_child(tr!("Loading...")),
+        Some(Err(err)) => grid.add_child(error_message(err)),
+        Some(Ok(nodes)) => {
+            grid.add_child(html! {<span class="pwt-font-title-medium">{tr!("Node")}</span>});
+            grid.add_child(html! {<span class="pwt-font-title-medium">{tr!("Priority")}</span>});
+            for node in nodes.iter() {
+                let selected = form_ctx.read().get_field_checked(node_field(node));
+                grid.add_child(
+                    Checkbox::new()
+                        .name(node_field(node))
+                        .submit(false)
+                        .box_label(node.clone()),
+                );
+                grid.add_child(
+                    Number::<u32>::new()
+                        .name(priority_field(node))
+                        .submit(false)
+                        .min(0)
+                        .max(1000)
+                        .disabled(!selected)
+                        .placeholder(tr!("Default")),
+                );
+            }
+        }
+    }
+
+    grid.into()
+}
+
+fn render_input_form(
+    form_ctx: &FormContext,
+    is_edit: bool,
+    nodes: &Option<Result<Rc<Vec<String>>, String>>,
+) -> Html {
+    InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_field(
+            tr!("ID"),
+            Field::new()
+                .name("group")
+                .required(true)
+                .disabled(is_edit)
+                .submit(!is_edit),
+        )
+        .with_right_field(tr!("restricted"), Checkbox::new().name("restricted"))
+        .with_field(tr!("Comment"), Field::new().name("comment"))
+        .with_right_field(tr!("nofailback"), Checkbox::new().name("nofailback"))
+        .with_large_custom_child(render_node_grid(form_ctx, nodes))
+        .into()
+}
+
+impl Component for ProxmoxHaGroupEdit {
+    type Message = Msg;
+    type Properties = HaGroupEdit;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let url = ctx.props().nodes_url.to_string();
+        ctx.link().send_future(async move {
+            let nodes = crate::http_get(url, None).await;
+            Msg::Nodes(nodes)
+        });
+        Self { nodes: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Nodes(result) => {
+                self.nodes = Some(
+                    result
+                        .map(|list| {
+                            let mut nodes: Vec<String> =
+                                list.into_iter().map(|entry| entry.node).collect();
+                            nodes.sort();
+                            Rc::new(nodes)
+                        })
+                        .map_err(|err| err.to_string()),
+                );
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let is_edit = props.group.is_some();
+        let action = if is_edit { tr!("Edit") } else { tr!("Create") };
+
+        let base_url = format!("{}/groups", props.base_url);
+
+        let loader = props.group.as_ref().map(|group| {
+            (
+                |url: AttrValue| async move {
+                    let resp: ApiResponseData<Value> = crate::http_get_full(&*url, None).await?;
+                    Ok(ApiResponseData {
+                        data: config_to_form(resp.data),
+                        attribs: resp.attribs,
+                    })
+                },
+                format!("{base_url}/{}", percent_encode_component(group)),
+            )
+        });
+
+        let nodes = match &self.nodes {
+            Some(Ok(nodes)) => Rc::clone(nodes),
+            _ => Rc::new(Vec::new()),
+        };
+
+        let on_submit = {
+            let group = props.group.clone();
+            move |form_ctx| submit_item(form_ctx, base_url.clone(), group.clone(), nodes.clone())
+        };
+
+        let node_state = self.nodes.clone();
+
+        EditWindow::new(action + ": " + &tr!("HA Group"))
+            .loader(loader)
+            .renderer(move |form_ctx: &FormContext| {
+                render_input_form(form_ctx, is_edit, &node_state)
+            })
+            .on_done(props.on_close.clone())
+            .on_submit(on_submit)
+            .into()
+    }
+}
+
+impl From<HaGroupEdit> for VNode {
+    fn from(val: HaGroupEdit) -> Self {
+        let comp = VComp::new::<ProxmoxHaGroupEdit>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,242 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::{Button, Toolbar};
+
+use pwt_macros::builder;
+
+use crate::percent_encoding::percent_encode_component;
+use crate::utils::render_boolean;
+use crate::{
+    ConfirmButton, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState,
+};
+
+use super::{HaGroup, HaGroupEdit};
+
+/// HA group list with create, edit and remove actions.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct HaGroupsPanel {
+    #[prop_or("/cluster/ha".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the HA API.
+    pub base_url: AttrValue,
+
+    #[prop_or("/nodes".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The url listing the cluster nodes.
+    pub nodes_url: AttrValue,
+
+    /// Read-only view - hide toolbar and all buttons/menus to edit content.
+    #[prop_or_default]
+    #[builder]
+    pub readonly: bool,
+}
+
+impl Default for HaGroupsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HaGroupsPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxHaGroupsPanel {
+    state: LoadableComponentState<ViewState>,
+    selection: Selection,
+    store: Store<HaGroup>,
+    columns: Rc<Vec<DataTableHeader<HaGroup>>>,
+}
+
+pwt::impl_deref_mut_property!(
+    ProxmoxHaGroupsPanel,
+    state,
+    LoadableComponentState<ViewState>
+);
+
+#[derive(PartialEq)]
+pub enum ViewState {
+    Add,
+    Edit(Key),
+}
+
+impl LoadableComponent for ProxmoxHaGroupsPanel {
+    type Properties = HaGroupsPanel;
+    type Message = ();
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let selection = Selection::new().on_select({
+            let link = ctx.link().clone();
+            move |_| link.send_redraw()
+        });
+        let store = Store::with_extract_key(|record: &HaGroup| Key::from(record.group.clone()));
+
+        let columns = Rc::new(vec![
+            DataTableColumn::new(tr!("Group"))
+                .width("150px")
+                .render(|record: &HaGroup| html! { &record.group })
+                .sorter(|a: &HaGroup, b: &HaGroup| a.group.cmp(&b.group))
+                .sort_order(true)
+                .into(),
+            DataTableColumn::new(tr!("restricted"))
+                .width("100px")
+                .render(
+                    |record: &HaGroup| html! { render_boolean(record.restricted.unwrap_or(false)) },
+                )
+                .into(),
+            DataTableColumn::new(tr!("nofailback"))
+                .width("100px")
+                .render(
+                    |record: &HaGroup| html! { render_boolean(record.nofailback.unwrap_or(false)) },
+                )
+                .into(),
+            DataTableColumn::new(tr!("Nodes"))
+                .flex(1)
+                .render(|record: &HaGroup| html! { &record.nodes })
+                .into(),
+            DataTableColumn::new(tr!("Comment"))
+                .flex(1)
+                .render(|record: &HaGroup| html! { record.comment.as_deref().unwrap_or_default() })
+                .into(),
+        ]);
+
+        Self {
+            state: LoadableComponentState::new(),
+            selection,
+            store,
+            columns,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let store = self.store.clone();
+        let url = format!("{}/groups", ctx.props().base_url);
+        Box::pin(async move {
+            let data = crate::http_get(url, None).await?;
+            store.write().set_data(data);
+            Ok(())
+        })
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let props = ctx.props();
+        if props.readonly {
+            return None;
+        }
+        let selected_key = self.selection.selected_key();
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_child(
+                Button::new(tr!("Create"))
+                    .onclick(ctx.link().change_view_callback(|_| Some(ViewState::Add))),
+            )
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Edit"))
+                    .disabled(selected_key.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        let selected_key = selected_key.clone();
+                        move |_| {
+                            if let Some(selected_key) = &selected_key {
+                                link.change_view(Some(ViewState::Edit(selected_key.clone())));
+                            }
+                        }
+                    }),
+            )
+            .with_child(
+                ConfirmButton::remove_entry(selected_key.as_deref().unwrap_or("").to_string())
+                    .dangerous(true)
+                    .disabled(selected_key.is_none())
+                    .on_activate({
+                        let link = ctx.link().clone();
+                        let selected_key = selected_key.clone();
+                        let base_url = props.base_url.clone();
+                        move |_| {
+                            let Some(selected_key) = &selected_key else {
+                                return;
+                            };
+                            let url = format!(
+                                "{base_url}/groups/{}",
+                                percent_encode_component(selected_key)
+                            );
+                            let link = link.clone();
+                            link.clone().spawn(async move {
+                                if let Err(err) = crate::http_delete(url, None).await {
+                                    link.show_error(tr!("Unable to remove group"), err, true);
+                                }
+                                link.send_reload();
+                            });
+                        }
+                    }),
+            );
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let mut table = DataTable::new(self.columns.clone(), self.store.clone())
+            .class("pwt-flex-fit")
+            .selection(self.selection.clone());
+
+        if !ctx.props().readonly {
+            table = table.on_row_dblclick({
+                let selection = self.selection.clone();
+                let link = ctx.link().clone();
+                move |_: &mut _| {
+                    if let Some(selected_key) = selection.selected_key() {
+                        link.change_view(Some(ViewState::Edit(selected_key.clone())));
+                    }
+                }
+            });
+        }
+
+        table.into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        let props = ctx.props();
+        let dialog = HaGroupEdit::new()
+            .base_url(props.base_url.clone())
+            .nodes_url(props.nodes_url.clone())
+            .on_close(ctx.link().change_view_callback(|_| None));
+
+        match view_state {
+            ViewState::Add => Some(dialog.into()),
+            ViewState::Edit(group) => Some(dialog.group(group.to_string()).into()),
+        }
+    }
+}
+
+impl From<HaGroupsPanel> for VNode {
+    fn from(val: HaGroupsPanel) -> Self {
+        let comp = VComp::new::<LoadableComponentMaster<ProxmoxHaGroupsPanel>>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,119 @@
+//! High availability resource and group management.
+
+use serde::{Deserialize, Serialize};
+
+mod resource_edit;
+pub use resource_edit::{HaResourceEdit, ProxmoxHaResourceEdit};
+
+mod resources_panel;
+pub use resources_panel::{HaResourcesPanel, ProxmoxHaResourcesPanel};
+
+mod group_edit;
+pub use group_edit::{HaGroupEdit, ProxmoxHaGroupEdit};
+
+mod groups_panel;
+pub use groups_panel::{HaGroupsPanel, ProxmoxHaGroupsPanel};
+
+/// HA resource list entry (`GET /cluster/ha/resources`).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HaResource {
+    /// The resource ID, for example `vm:100`.
+    pub sid: String,
+    /// The resource type (`vm` or `ct`).
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The requested state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// The HA group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Maximal number of restart tries on the same node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_restart: Option<u64>,
+    /// Maximal number of relocate tries to other nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_relocate: Option<u64>,
+    /// Comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// HA group list entry (`GET /cluster/ha/groups`).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HaGroup {
+    /// The group name.
+    pub group: String,
+    /// List of nodes with optional priority (`node1:2,node2`).
+    pub nodes: String,
+    /// Resources may only run on the group nodes.
+    #[serde(deserialize_with = "proxmox_serde::perl::deserialize_bool")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restricted: Option<bool>,
+    /// Do not migrate resources back to a node with higher priority.
+    #[serde(deserialize_with = "proxmox_serde::perl::deserialize_bool")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nofailback: Option<bool>,
+    /// Comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Parse a HA group node list (`node1:2,node2`) into node names and priorities.
+pub fn parse_node_priorities(nodes: &str) -> Vec<(String, Option<u32>)> {
+    nodes
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once(':') {
+            Some((node, priority)) => (node.to_string(), priority.parse().ok()),
+            None => (entry.to_string(), None),
+        })
+        .collect()
+}
+
+/// Format node names and priorities as HA group node list.
+pub fn format_node_priorities(nodes: &[(String, Option<u32>)]) -> String {
+    nodes
+        .iter()
+        .map(|(node, priority)| match priority {
+            Some(priority) => format!("{node}:{priority}"),
+            None => node.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_node_priorities() {
+        let nodes = parse_node_priorities("node1:2,node2, node3:x");
+        assert_eq!(
+            nodes,
+            [
+                ("node1".to_string(), Some(2)),
+                ("node2".to_string(), None),
+                ("node3".to_string(), None),
+            ]
+        );
+        assert_eq!(format_node_priorities(&nodes), "node1:2,node2,node3");
+        assert!(parse_node_priorities("").is_empty());
+    }
+
+    #[test]
+    fn test_ha_group_perl_bool() {
+        let group: HaGroup = serde_json::from_value(serde_json::json!({
+            "group": "g1",
+            "nodes": "node1",
+            "restricted": 1,
+            "nofailback": 0,
+        }))
+        .unwrap();
+        assert_eq!(group.restricted, Some(true));
+        assert_eq!(group.nofailback, Some(false));
+    }
+}
@@ -0,0 +1,203 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Combobox, Field, FormContext, Number};
+use pwt::widget::InputPanel;
+
+use pwt_macros::builder;
+
+use crate::form::delete_empty_values;
+use crate::form::pve::PveGuestSelector;
+use crate::percent_encoding::percent_encode_component;
+use crate::EditWindow;
+
+/// Edit window for HA resources.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct HaResourceEdit {
+    /// Edit existing resource (the resource ID, for example `vm:100`).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub sid: Option<AttrValue>,
+
+    #[prop_or("/cluster/ha".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the HA API.
+    pub base_url: AttrValue,
+
+    /// The available HA groups.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub groups: Rc<Vec<AttrValue>>,
+
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for HaResourceEdit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HaResourceEdit {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxHaResourceEdit {}
+
+async fn submit_item(
+    form_ctx: FormContext,
+    base_url: String,
+    sid: Option<AttrValue>,
+) -> Result<(), Error> {
+    let mut data = form_ctx.get_submit_data();
+    match sid {
+        Some(sid) => {
+            let url = format!("{base_url}/{}", percent_encode_component(&sid));
+            let data = delete_empty_values(
+                &data,
+                &["group", "comment", "max_restart", "max_relocate"],
+                false,
+            );
+            crate::http_put(url, Some(data)).await
+        }
+        None => {
+            if let Some(map) = data.as_object_mut() {
+                map.retain(|_, value| !(value.is_null() || value.as_str() == Some("")));
+            }
+            crate::http_post(base_url, Some(data)).await
+        }
+    }
+}
+
+fn render_input_form(
+    form_ctx: &FormContext,
+    sid: Option<&AttrValue>,
+    groups: Rc<Vec<AttrValue>>,
+) -> Html {
+    let state_items = Rc::new(vec![
+        AttrValue::Static("started"),
+        AttrValue::Static("stopped"),
+        AttrValue::Static("disabled"),
+        AttrValue::Static("ignored"),
+    ]);
+
+    let mut panel = InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4);
+
+    if let Some(sid) = sid {
+        panel.add_field(
+            tr!("Resource"),
+            Field::new()
+                .name("sid")
+                .default(sid.clone())
+                .disabled(true)
+                .submit(false),
+        );
+    } else {
+        panel.add_field(
+            tr!("VM/CT"),
+            PveGuestSelector::new()
+                .name("sid")
+                .required(true)
+                .templates(Some(false)),
+        );
+    }
+
+    panel
+        .with_right_field(
+            tr!("Request State"),
+            Combobox::new()
+                .name("state")
+                .items(state_items)
+                .default("started")
+                .required(true),
+        )
+        .with_field(
+            tr!("Group"),
+            Combobox::new()
+                .name("group")
+                .items(groups)
+                .placeholder(tr!("none")),
+        )
+        .with_right_field(
+            tr!("Max. Restart"),
+            Number::<u64>::new()
+                .name("max_restart")
+                .min(0)
+                .max(10)
+                .placeholder("1"),
+        )
+        .with_right_field(
+            tr!("Max. Relocate"),
+            Number::<u64>::new()
+                .name("max_relocate")
+                .min(0)
+                .max(10)
+                .placeholder("1"),
+        )
+        .with_large_field(tr!("Comment"), Field::new().name("comment"))
+        .into()
+}
+
+impl Component for ProxmoxHaResourceEdit {
+    type Message = ();
+    type Properties = HaResourceEdit;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {}
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let action = if props.sid.is_some() {
+            tr!("Edit")
+        } else {
+            tr!("Add")
+        };
+
+        let base_url = format!("{}/resources", props.base_url);
+
+        let loader = props
+            .sid
+            .as_ref()
+            .map(|sid| format!("{base_url}/{}", percent_encode_component(sid)));
+
+        let on_submit = {
+            let sid = props.sid.clone();
+            move |form_ctx| submit_item(form_ctx, base_url.clone(), sid.clone())
+        };
+
+        let sid = props.sid.clone();
+        let groups = props.groups.clone();
+
+        EditWindow::new(action + ": " + &tr!("Resource"))
+            .loader(loader)
+            .renderer(move |form_ctx: &FormContext| {
+                render_input_form(form_ctx, sid.as_ref(), groups.clone())
+            })
+            .on_done(props.on_close.clone())
+            .on_submit(on_submit)
+            .into()
+    }
+}
+
+impl From<HaResourceEdit> for VNode {
+    fn from(val: HaResourceEdit) -> Self {
+        let comp = VComp::new::<ProxmoxHaResourceEdit>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
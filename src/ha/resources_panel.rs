@@ -0,0 +1,268 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::{Button, Fa, Toolbar};
+
+use pwt_macros::builder;
+
+use crate::percent_encoding::percent_encode_component;
+use crate::{
+    ConfirmButton, HaState, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState,
+};
+
+use super::{HaGroup, HaResource, HaResourceEdit};
+
+/// HA resource list with add, edit and remove actions.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct HaResourcesPanel {
+    #[prop_or("/cluster/ha".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the HA API.
+    pub base_url: AttrValue,
+
+    /// Read-only view - hide toolbar and all buttons/menus to edit content.
+    #[prop_or_default]
+    #[builder]
+    pub readonly: bool,
+}
+
+impl Default for HaResourcesPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HaResourcesPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxHaResourcesPanel {
+    state: LoadableComponentState<ViewState>,
+    selection: Selection,
+    store: Store<HaResource>,
+    groups: Store<HaGroup>,
+    columns: Rc<Vec<DataTableHeader<HaResource>>>,
+}
+
+pwt::impl_deref_mut_property!(
+    ProxmoxHaResourcesPanel,
+    state,
+    LoadableComponentState<ViewState>
+);
+
+#[derive(PartialEq)]
+pub enum ViewState {
+    Add,
+    Edit(Key),
+}
+
+fn render_optional_number(value: Option<u64>, default: u64) -> Html {
+    html! { value.unwrap_or(default) }
+}
+
+impl ProxmoxHaResourcesPanel {
+    fn group_names(&self) -> Rc<Vec<AttrValue>> {
+        Rc::new(
+            self.groups
+                .read()
+                .iter()
+                .map(|group| AttrValue::from(group.group.clone()))
+                .collect(),
+        )
+    }
+}
+
+impl LoadableComponent for ProxmoxHaResourcesPanel {
+    type Properties = HaResourcesPanel;
+    type Message = ();
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let selection = Selection::new().on_select({
+            let link = ctx.link().clone();
+            move |_| link.send_redraw()
+        });
+        let store = Store::with_extract_key(|record: &HaResource| Key::from(record.sid.clone()));
+        let groups = Store::with_extract_key(|record: &HaGroup| Key::from(record.group.clone()));
+
+        let columns = Rc::new(vec![
+            DataTableColumn::new("ID")
+                .width("100px")
+                .render(|record: &HaResource| html! { &record.sid })
+                .sorter(|a: &HaResource, b: &HaResource| a.sid.cmp(&b.sid))
+                .sort_order(true)
+                .into(),
+            DataTableColumn::new(tr!("State"))
+                .width("100px")
+                .render(|record: &HaResource| {
+                    let state = record.state.as_deref().unwrap_or("started");
+                    html! {<>{Fa::from(HaState::from_state(state))}{" "}{state}</>}
+                })
+                .into(),
+            DataTableColumn::new(tr!("Group"))
+                .width("120px")
+                .render(|record: &HaResource| html! { record.group.as_deref().unwrap_or_default() })
+                .into(),
+            DataTableColumn::new(tr!("Max. Restart"))
+                .width("100px")
+                .justify("right")
+                .render(|record: &HaResource| render_optional_number(record.max_restart, 1))
+                .into(),
+            DataTableColumn::new(tr!("Max. Relocate"))
+                .width("100px")
+                .justify("right")
+                .render(|record: &HaResource| render_optional_number(record.max_relocate, 1))
+                .into(),
+            DataTableColumn::new(tr!("Comment"))
+                .flex(1)
+                .render(|record: &HaResource| {
+                    html! { record.comment.as_deref().unwrap_or_default() }
+                })
+                .into(),
+        ]);
+
+        Self {
+            state: LoadableComponentState::new(),
+            selection,
+            store,
+            groups,
+            columns,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let store = self.store.clone();
+        let groups = self.groups.clone();
+        let base_url = ctx.props().base_url.clone();
+        Box::pin(async move {
+            let data = crate::http_get(format!("{base_url}/resources"), None).await?;
+            store.write().set_data(data);
+            // groups are optional (newer versions use rules instead)
+            match crate::http_get(format!("{base_url}/groups"), None).await {
+                Ok(data) => groups.write().set_data(data),
+                Err(err) => log::error!("unable to load HA groups - {err}"),
+            }
+            Ok(())
+        })
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let props = ctx.props();
+        if props.readonly {
+            return None;
+        }
+        let selected_key = self.selection.selected_key();
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_child(
+                Button::new(tr!("Add"))
+                    .onclick(ctx.link().change_view_callback(|_| Some(ViewState::Add))),
+            )
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Edit"))
+                    .disabled(selected_key.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        let selected_key = selected_key.clone();
+                        move |_| {
+                            if let Some(selected_key) = &selected_key {
+                                link.change_view(Some(ViewState::Edit(selected_key.clone())));
+                            }
+                        }
+                    }),
+            )
+            .with_child(
+                ConfirmButton::remove_entry(selected_key.as_deref().unwrap_or("").to_string())
+                    .dangerous(true)
+                    .disabled(selected_key.is_none())
+                    .on_activate({
+                        let link = ctx.link().clone();
+                        let selected_key = selected_key.clone();
+                        let base_url = props.base_url.clone();
+                        move |_| {
+                            let Some(selected_key) = &selected_key else {
+                                return;
+                            };
+                            let url = format!(
+                                "{base_url}/resources/{}",
+                                percent_encode_component(selected_key)
+                            );
+                            let link = link.clone();
+                            link.clone().spawn(async move {
+                                if let Err(err) = crate::http_delete(url, None).await {
+                                    link.show_error(tr!("Unable to remove resource"), err, true);
+                                }
+                                link.send_reload();
+                            });
+                        }
+                    }),
+            );
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let mut table = DataTable::new(self.columns.clone(), self.store.clone())
+            .class("pwt-flex-fit")
+            .selection(self.selection.clone());
+
+        if !ctx.props().readonly {
+            table = table.on_row_dblclick({
+                let selection = self.selection.clone();
+                let link = ctx.link().clone();
+                move |_: &mut _| {
+                    if let Some(selected_key) = selection.selected_key() {
+                        link.change_view(Some(ViewState::Edit(selected_key.clone())));
+                    }
+                }
+            });
+        }
+
+        table.into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        let dialog = HaResourceEdit::new()
+            .base_url(ctx.props().base_url.clone())
+            .groups(self.group_names())
+            .on_close(ctx.link().change_view_callback(|_| None));
+
+        match view_state {
+            ViewState::Add => Some(dialog.into()),
+            ViewState::Edit(sid) => Some(dialog.sid(sid.to_string()).into()),
+        }
+    }
+}
+
+impl From<HaResourcesPanel> for VNode {
+    fn from(val: HaResourcesPanel) -> Self {
+        let comp =
+            VComp::new::<LoadableComponentMaster<ProxmoxHaResourcesPanel>>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
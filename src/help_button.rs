@@ -1,26 +1,59 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
+use yew::html::IntoPropValue;
 use yew::prelude::*;
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
-use pwt::widget::Button;
+use pwt::touch::{SideDialog, SideDialogLocation};
+use pwt::widget::{Button, Column, Row};
+use pwt::AsyncPool;
 
+use pwt_macros::builder;
+
+use crate::{ExistingProduct, Markdown, ProjectInfo};
+
+thread_local! {
+    // docs path => bundled docs reachable
+    static OFFLINE_DOCS: RefCell<HashMap<&'static str, bool>> = RefCell::new(HashMap::new());
+}
+
+/// Help button, linking to the documentation of a product.
+///
+/// Links point to the documentation bundled with the product. If that is not installed,
+/// the public documentation is used instead. With `inline_help`, a click opens a side
+/// panel with the given Markdown text first.
 #[derive(Clone, PartialEq, Properties)]
-pub struct HelpButton {
+#[builder]
+pub struct ProductHelpButton {
     #[prop_or_default]
     section: Option<String>,
     #[prop_or_default]
     class: Classes,
+
+    /// The product, determines the documentation location.
+    #[builder]
+    #[prop_or(ExistingProduct::PBS)]
+    product: ExistingProduct,
+
+    /// Short help text (Markdown), shown in a side panel.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    inline_help: Option<AttrValue>,
 }
 
-impl Default for HelpButton {
+/// Alias kept for existing users.
+pub type HelpButton = ProductHelpButton;
+
+impl Default for ProductHelpButton {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl HelpButton {
+impl ProductHelpButton {
     pub fn new() -> Self {
         yew::props!(Self {})
     }
@@ -42,35 +75,186 @@ impl HelpButton {
     }
 }
 
-#[function_component(PbsHelpButton)]
-pub fn pbs_help_button(props: &HelpButton) -> Html {
-    let button = if props.section.is_some() {
-        Button::new("?").class("circle").aria_label("help")
-    } else {
-        Button::new("Documentation")
-            .icon_class("fa fa-book")
-            .aria_label("documentation")
+/// Compute the documentation URL for a section.
+///
+/// Uses the public documentation if the bundled one is not available.
+pub fn help_link(product: &dyn ProjectInfo, section: Option<&str>, offline_docs: bool) -> String {
+    let base = match product.docs_public_url() {
+        url if !offline_docs && !url.is_empty() => url,
+        _ => product.docs_path(),
     };
+    match section {
+        Some(section) => format!("{base}{}", product.docs_section_link(section)),
+        None => format!("{base}index.html"),
+    }
+}
+
+async fn check_offline_docs(docs_path: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    let init = web_sys::RequestInit::new();
+    init.set_method("HEAD");
+    let url = format!("{docs_path}index.html");
+    match wasm_bindgen_futures::JsFuture::from(window.fetch_with_str_and_init(&url, &init)).await {
+        Ok(response) => web_sys::Response::from(response).ok(),
+        Err(_) => false,
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    OfflineDocs(bool),
+    ShowPanel(bool),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxProductHelpButton {
+    // assume the bundled docs are available until the check finished
+    offline_docs: bool,
+    show_panel: bool,
+    _async_pool: AsyncPool,
+}
+
+/// The former PBS specific component, now works for all products.
+#[deprecated(note = "use ProxmoxProductHelpButton")]
+pub type PbsHelpButton = ProxmoxProductHelpButton;
+
+impl ProxmoxProductHelpButton {
+    fn open_docs(url: &str) {
+        let _ = gloo_utils::window().open_with_url_and_target(url, "top");
+    }
 
-    button
-        .class(props.class.clone())
-        .onclick({
-            let url = get_help_link(props.section.as_deref());
+    fn help_panel(&self, ctx: &Context<Self>, text: &AttrValue, url: String) -> Html {
+        let link = ctx.link();
+        SideDialog::new()
+            .location(SideDialogLocation::Right)
+            .on_close(link.callback(|_| Msg::ShowPanel(false)))
+            .with_child(
+                Column::new()
+                    .class(pwt::css::FlexFit)
+                    .style("max-width", "400px")
+                    .with_child(
+                        Markdown::new()
+                            .class("pwt-flex-fit pwt-overflow-auto pwt-p-2")
+                            .text(text.clone()),
+                    )
+                    .with_child(
+                        Row::new()
+                            .padding(2)
+                            .class("pwt-border-top")
+                            .with_flex_spacer()
+                            .with_child(
+                                Button::new(tr!("Open Documentation"))
+                                    .icon_class("fa fa-book")
+                                    .onclick(move |_| Self::open_docs(&url)),
+                            ),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl Component for ProxmoxProductHelpButton {
+    type Message = Msg;
+    type Properties = ProductHelpButton;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let docs_path = ctx.props().product.docs_path();
+        let cached = OFFLINE_DOCS.with(|cache| cache.borrow().get(docs_path).copied());
+
+        let async_pool = AsyncPool::new();
+        if cached.is_none() {
+            let link = ctx.link().clone();
+            async_pool.spawn(async move {
+                let available = check_offline_docs(docs_path).await;
+                OFFLINE_DOCS.with(|cache| cache.borrow_mut().insert(docs_path, available));
+                link.send_message(Msg::OfflineDocs(available));
+            });
+        }
+
+        Self {
+            offline_docs: cached.unwrap_or(true),
+            show_panel: false,
+            _async_pool: async_pool,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::OfflineDocs(available) => {
+                self.offline_docs = available;
+                true
+            }
+            Msg::ShowPanel(show) => {
+                self.show_panel = show;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let button = if props.section.is_some() {
+            Button::new("?").class("circle").aria_label("help")
+        } else {
+            Button::new("Documentation")
+                .icon_class("fa fa-book")
+                .aria_label("documentation")
+        };
+
+        let url = help_link(&props.product, props.section.as_deref(), self.offline_docs);
+
+        let panel = match &props.inline_help {
+            Some(text) if self.show_panel => Some(self.help_panel(ctx, text, url.clone())),
+            _ => None,
+        };
+
+        let button = button.class(props.class.clone()).onclick({
+            let link = ctx.link().clone();
+            let inline_help = props.inline_help.is_some();
             move |_| {
-                let _ = gloo_utils::window().open_with_url_and_target(&url, "top");
+                if inline_help {
+                    link.send_message(Msg::ShowPanel(true));
+                } else {
+                    Self::open_docs(&url);
+                }
             }
-        })
-        .into()
+        });
+
+        html! {<>{button}{panel}</>}
+    }
 }
 
-impl From<HelpButton> for VNode {
-    fn from(val: HelpButton) -> Self {
-        let comp = VComp::new::<PbsHelpButton>(Rc::new(val), None);
+impl From<ProductHelpButton> for VNode {
+    fn from(val: ProductHelpButton) -> Self {
+        let comp = VComp::new::<ProxmoxProductHelpButton>(Rc::new(val), None);
         VNode::from(comp)
     }
 }
 
-fn get_help_link(_section: Option<&str>) -> String {
-    // TODO:
-    "/docs/index.html".to_string()
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_help_link() {
+        let pve = ExistingProduct::PVE;
+        assert_eq!(
+            help_link(&pve, Some("chapter_ha_manager"), true),
+            "/pve-docs/pve-admin-guide.html#chapter_ha_manager"
+        );
+        assert_eq!(
+            help_link(&pve, None, false),
+            "https://pve.proxmox.com/pve-docs/index.html"
+        );
+
+        let pbs = ExistingProduct::PBS;
+        assert_eq!(
+            help_link(&pbs, Some("user_mgmt"), true),
+            "/docs/user-management.html#user-mgmt"
+        );
+        assert_eq!(help_link(&pbs, Some("unknown"), true), "/docs/index.html");
+    }
 }
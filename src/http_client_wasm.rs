@@ -36,7 +36,7 @@ pub fn authentication_from_cookie(project: &dyn ProjectInfo) -> Option<Authentic
     None
 }
 
-fn extract_auth_from_cookie(project: &dyn ProjectInfo) -> Option<Ticket> {
+pub(crate) fn extract_auth_from_cookie(project: &dyn ProjectInfo) -> Option<Ticket> {
     let cookie = crate::get_cookie();
     let name = project.auth_cookie_name();
     let prefixes = project
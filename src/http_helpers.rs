@@ -1,16 +1,20 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::thread_local;
 
 use anyhow::{bail, format_err, Error};
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use gloo_events::EventListener;
 use js_sys::Uint8Array;
 use pwt::{convert_js_error, AsyncAbortGuard, WebSysAbortGuard};
 use slab::Slab;
-use web_sys::{File, Headers, Request, RequestInit, Response};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BroadcastChannel, File, Headers, MessageEvent, Request, RequestInit, Response};
 
 use proxmox_client::ApiResponseData;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
 use proxmox_client::HttpApiClient;
@@ -18,7 +22,7 @@ use proxmox_login::{ticket::Validity, Authentication, TicketResult};
 use yew::Callback;
 
 use crate::acl_context::LocalAclTree;
-use crate::{json_object_to_query, ExistingProduct, HttpClientWasm, ProjectInfo};
+use crate::{json_object_to_query, ApiError, ExistingProduct, HttpClientWasm, ProjectInfo};
 
 static LAST_NOTIFY_EPOCH: AtomicU32 = AtomicU32::new(0);
 static CLIENT_NOTIFY_EPOCH: AtomicU32 = AtomicU32::new(0);
@@ -26,6 +30,7 @@ static CLIENT_NOTIFY_EPOCH: AtomicU32 = AtomicU32::new(0);
 thread_local! {
     pub static CLIENT: RefCell<Rc<HttpClientWasm>> = {
         start_ticket_refresh_loop();
+        start_auth_tab_sync();
         CLIENT_NOTIFY_EPOCH.fetch_add(1, Ordering::SeqCst);
         RefCell::new(Rc::new(
             HttpClientWasm::new(&ExistingProduct::PBS, notify_auth_listeners)
@@ -84,6 +89,152 @@ pub fn register_auth_observer(callback: impl Into<Callback<bool>>) -> AuthObserv
     })
 }
 
+thread_local! {
+    static LOGIN_OBSERVER: RefCell<Slab<Callback<Authentication>>> = const { RefCell::new(Slab::new()) };
+}
+
+pub struct LoginObserver {
+    key: usize,
+}
+
+impl Drop for LoginObserver {
+    fn drop(&mut self) {
+        LOGIN_OBSERVER.with(|slab| {
+            let mut slab = slab.borrow_mut();
+            slab.remove(self.key);
+        });
+    }
+}
+
+/// Register a callback which is called when a user logged in from another browser tab.
+///
+/// Logouts in other tabs are reported to the observers registered with
+/// [register_auth_observer].
+pub fn register_login_observer(callback: impl Into<Callback<Authentication>>) -> LoginObserver {
+    let callback = callback.into();
+    LOGIN_OBSERVER.with(|slab| {
+        let mut slab = slab.borrow_mut();
+        let key = slab.insert(callback);
+        LoginObserver { key }
+    })
+}
+
+fn notify_login_listeners(auth: &Authentication) {
+    let list: Vec<Callback<Authentication>> =
+        LOGIN_OBSERVER.with(|slab| slab.borrow().iter().map(|(_key, cb)| cb.clone()).collect());
+    for callback in list {
+        callback.emit(auth.clone());
+    }
+}
+
+// Messages sent to the other tabs of the same origin. No credentials are sent, after a
+// login each tab renews the ticket (from the shared cookie) to get its own CSRF token, as the
+// CSRF token is stored per tab (session storage).
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum AuthSyncMessage {
+    Login { userid: String },
+    Logout,
+}
+
+const AUTH_SYNC_CHANNEL: &str = "proxmox-auth-sync";
+
+thread_local! {
+    static AUTH_SYNC: RefCell<Option<(BroadcastChannel, EventListener)>> = const { RefCell::new(None) };
+}
+
+fn start_auth_tab_sync() {
+    let channel = match BroadcastChannel::new(AUTH_SYNC_CHANNEL) {
+        Ok(channel) => channel,
+        Err(err) => {
+            // old browsers, tabs then only notice changes on the next failing request
+            log::error!("unable to open auth sync channel - {:?}", err);
+            return;
+        }
+    };
+
+    let listener = EventListener::new(&channel, "message", |event| {
+        let Some(data) = event
+            .dyn_ref::<MessageEvent>()
+            .and_then(|event| event.data().as_string())
+        else {
+            return;
+        };
+        match serde_json::from_str(&data) {
+            Ok(msg) => handle_auth_sync_message(msg),
+            Err(err) => log::error!("got invalid auth sync message - {err}"),
+        }
+    });
+
+    AUTH_SYNC.with_borrow_mut(|v| *v = Some((channel, listener)));
+}
+
+fn broadcast_auth_sync_message(msg: AuthSyncMessage) {
+    let Ok(data) = serde_json::to_string(&msg) else {
+        return;
+    };
+    AUTH_SYNC.with_borrow(|v| {
+        if let Some((channel, _)) = v {
+            if let Err(err) = channel.post_message(&JsValue::from_str(&data)) {
+                log::error!("unable to send auth sync message - {:?}", err);
+            }
+        }
+    });
+}
+
+fn broadcast_login(auth: &Authentication) {
+    broadcast_auth_sync_message(AuthSyncMessage::Login {
+        userid: auth.userid.clone(),
+    });
+}
+
+fn handle_auth_sync_message(msg: AuthSyncMessage) {
+    let client = CLIENT.with(|c| Rc::clone(&*c.borrow()));
+
+    match msg {
+        AuthSyncMessage::Logout => {
+            log::info!("auth sync: logout in other tab");
+            // the other tab already did the logout API call and cleared the cookie
+            if client.get_auth().is_some() {
+                client.clear_auth();
+            }
+        }
+        AuthSyncMessage::Login { userid } => {
+            // the ticket refresh loop keeps our own ticket valid
+            if client
+                .get_auth()
+                .is_some_and(|current| current.userid == userid)
+            {
+                return;
+            }
+
+            log::info!("auth sync: login in other tab");
+            let product = client.product();
+            // a readable (signed) ticket cookie is renewed by logging in with it, like the
+            // ticket refresh loop does, HttpOnly cookies are sent by the browser on refresh
+            let ticket = crate::http_client_wasm::extract_auth_from_cookie(product)
+                .filter(|ticket| !ticket.is_info_only() && ticket.userid() == userid);
+            let client = HttpClientWasm::new(product, notify_auth_listeners);
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = match ticket {
+                    Some(ticket) => client.login(&userid, &ticket.to_string()).await,
+                    None => client.refresh(&userid).await,
+                };
+                match result {
+                    Ok(TicketResult::Full(auth)) | Ok(TicketResult::HttpOnly(auth)) => {
+                        client.set_auth(auth.clone());
+                        update_global_client(client);
+                        LocalAclTree::load().await;
+                        notify_login_listeners(&auth);
+                    }
+                    Ok(_) => { /* do nothing */ }
+                    Err(err) => log::error!("auth sync: unable to renew ticket - {err}"),
+                }
+            });
+        }
+    }
+}
+
 thread_local! {
     static TICKET_REFRESH_LOOP_GUARD: RefCell<Option<AsyncAbortGuard>> = const { RefCell::new(None) };
 }
@@ -161,11 +312,13 @@ thread_local! {
 }
 
 pub fn http_clear_auth() {
+    http_cache_clear();
     let abort_guard = AsyncAbortGuard::spawn(async move {
         let client = CLIENT.with(|c| Rc::clone(&*c.borrow()));
         let _ = client.logout().await;
         client.clear_auth();
         crate::clear_auth_cookie(client.product().auth_cookie_name());
+        broadcast_auth_sync_message(AuthSyncMessage::Logout);
     });
 
     LOGOUT_GUARD.with_borrow_mut(|v| *v = Some(abort_guard));
@@ -193,12 +346,14 @@ pub async fn http_login(
         TicketResult::Full(auth) => {
             client.set_auth(auth.clone());
             update_global_client(client);
+            broadcast_login(&auth);
             LocalAclTree::load().await;
             Ok(TicketResult::Full(auth))
         }
         TicketResult::HttpOnly(auth) => {
             client.set_auth(auth.clone());
             update_global_client(client);
+            broadcast_login(&auth);
             LocalAclTree::load().await;
             Ok(TicketResult::HttpOnly(auth))
         }
@@ -215,6 +370,7 @@ pub async fn http_login_tfa(
     let auth = client.login_tfa(challenge, request).await?;
     client.set_auth(auth.clone());
     update_global_client(client);
+    broadcast_login(&auth);
     LocalAclTree::load().await;
     Ok(auth)
 }
@@ -230,6 +386,31 @@ fn path_and_param_to_api_url<P: Serialize>(path: &str, data: Option<P>) -> Resul
     Ok(path_and_query)
 }
 
+/// Parse the response body, returning the [ApiError] of failed responses.
+///
+/// The body is only parsed once, both for the error check and the returned data.
+fn parse_api_response<T: DeserializeOwned>(
+    resp: &proxmox_client::HttpApiResponse,
+) -> Result<ApiResponseData<T>, Error> {
+    let value = serde_json::from_slice::<Value>(&resp.body).ok();
+    if let Some(err) = ApiError::from_parsed_response(resp.status, &resp.body, value.as_ref()) {
+        return Err(err.into());
+    }
+
+    let Some(Value::Object(mut map)) = value else {
+        bail!("unexpected API response, expected a JSON object");
+    };
+    let data = map.remove("data").unwrap_or(Value::Null);
+    for key in ["success", "status", "message", "errors"] {
+        map.remove(key);
+    }
+
+    Ok(ApiResponseData {
+        data: serde_json::from_value(data)?,
+        attribs: map.into_iter().collect(),
+    })
+}
+
 pub async fn http_get_full<T: DeserializeOwned>(
     path: impl Into<String>,
     data: Option<Value>,
@@ -239,8 +420,7 @@ pub async fn http_get_full<T: DeserializeOwned>(
     let path_and_query = path_and_param_to_api_url(&path.into(), data)?;
 
     let resp: proxmox_client::HttpApiResponse = client.get(&path_and_query).await?;
-    let resp: ApiResponseData<T> = resp.expect_json()?;
-    Ok(resp)
+    parse_api_response(&resp)
 }
 
 pub async fn http_get<T: DeserializeOwned>(
@@ -251,6 +431,116 @@ pub async fn http_get<T: DeserializeOwned>(
     Ok(resp.data)
 }
 
+type CachedResponse = Result<Rc<ApiResponseData<Value>>, Rc<Error>>;
+
+enum CacheEntry {
+    Pending(Shared<LocalBoxFuture<'static, CachedResponse>>),
+    Ready {
+        expires: f64,
+        response: Rc<ApiResponseData<Value>>,
+    },
+}
+
+thread_local! {
+    static GET_CACHE: RefCell<HashMap<String, CacheEntry>> = RefCell::new(HashMap::new());
+}
+
+/// GET with request deduplication and response caching.
+///
+/// Identical requests which are in flight at the same time are only sent once. Successful
+/// responses are kept for `ttl_ms` milliseconds, so many widgets polling the same endpoint
+/// (for example `/cluster/resources`) do not multiply the load on the server. Errors are
+/// never cached.
+///
+/// The cache is cleared on logout, and by all other (modifying) request helpers once their
+/// request completed. Use [http_cache_invalidate] to drop entries after changes made by other
+/// means.
+pub async fn http_get_cached_full<T: DeserializeOwned>(
+    path: impl Into<String>,
+    data: Option<Value>,
+    ttl_ms: u32,
+) -> Result<ApiResponseData<T>, Error> {
+    let url = path_and_param_to_api_url(&path.into(), data)?;
+    let now = js_sys::Date::now();
+
+    let future = GET_CACHE.with_borrow_mut(|cache| {
+        match cache.get(&url) {
+            Some(CacheEntry::Ready { expires, response }) if *expires > now => {
+                return Ok(Rc::clone(response));
+            }
+            Some(CacheEntry::Pending(future)) => return Err(future.clone()),
+            _ => {}
+        }
+        let client = CLIENT.with(|c| Rc::clone(&c.borrow()));
+        let path_and_query = url.clone();
+        let future = async move {
+            let resp: proxmox_client::HttpApiResponse = client
+                .get(&path_and_query)
+                .await
+                .map_err(|err| Rc::new(Error::from(err)))?;
+            let resp: ApiResponseData<Value> = parse_api_response(&resp).map_err(Rc::new)?;
+            Ok(Rc::new(resp))
+        }
+        .boxed_local()
+        .shared();
+        cache.insert(url.clone(), CacheEntry::Pending(future.clone()));
+        Err(future)
+    });
+
+    let response = match future {
+        Ok(response) => response,
+        Err(future) => {
+            let result = future.await;
+            GET_CACHE.with_borrow_mut(|cache| {
+                // the cache may have been cleared or refilled meanwhile
+                if !matches!(cache.get(&url), Some(CacheEntry::Pending(_))) {
+                    return;
+                }
+                match &result {
+                    Ok(response) if ttl_ms > 0 => {
+                        let expires = js_sys::Date::now() + ttl_ms as f64;
+                        let response = Rc::clone(response);
+                        cache.insert(url.clone(), CacheEntry::Ready { expires, response });
+                    }
+                    _ => {
+                        cache.remove(&url);
+                    }
+                }
+            });
+            result.map_err(|err| match ApiError::from_error(&err) {
+                Some(api_err) => Error::from(api_err.clone()),
+                None => format_err!("{err}"),
+            })?
+        }
+    };
+
+    Ok(ApiResponseData {
+        data: serde_json::from_value(response.data.clone())?,
+        attribs: response.attribs.clone(),
+    })
+}
+
+/// GET with request deduplication and response caching, see [http_get_cached_full].
+pub async fn http_get_cached<T: DeserializeOwned>(
+    path: impl Into<String>,
+    data: Option<Value>,
+    ttl_ms: u32,
+) -> Result<T, Error> {
+    let resp = http_get_cached_full(path, data, ttl_ms).await?;
+    Ok(resp.data)
+}
+
+/// Remove cached responses of [http_get_cached] for all paths starting with `path`.
+pub fn http_cache_invalidate(path: &str) {
+    let prefix = format!("/api2/extjs{path}");
+    GET_CACHE.with_borrow_mut(|cache| cache.retain(|url, _| !url.starts_with(&prefix)));
+}
+
+/// Remove all cached responses of [http_get_cached].
+pub fn http_cache_clear() {
+    GET_CACHE.with_borrow_mut(|cache| cache.clear());
+}
+
 /// Delete and return data
 pub async fn http_delete_get<T: DeserializeOwned>(
     path: impl Into<String>,
@@ -260,8 +550,11 @@ pub async fn http_delete_get<T: DeserializeOwned>(
 
     let path_and_query = path_and_param_to_api_url(&path.into(), data)?;
 
-    let resp: proxmox_client::HttpApiResponse = client.delete(&path_and_query).await?;
-    let resp: ApiResponseData<T> = resp.expect_json()?;
+    let resp = client.delete(&path_and_query).await;
+    // clear after the change is done, so concurrent GETs cannot refill it with stale data
+    http_cache_clear();
+    let resp: proxmox_client::HttpApiResponse = resp?;
+    let resp: ApiResponseData<T> = parse_api_response(&resp)?;
     Ok(resp.data)
 }
 
@@ -271,10 +564,12 @@ pub async fn http_delete(path: impl Into<String>, data: Option<Value>) -> Result
 
     let path_and_query = path_and_param_to_api_url(&path.into(), None::<()>)?;
 
-    let resp: proxmox_client::HttpApiResponse = client
+    let resp = client
         .request(http::Method::DELETE, &path_and_query, data)
-        .await?;
-    resp.nodata()?; // we do not expect and data here
+        .await;
+    http_cache_clear();
+    let resp: proxmox_client::HttpApiResponse = resp?;
+    let _: ApiResponseData<Value> = parse_api_response(&resp)?; // we do not expect any data here
     Ok(())
 }
 
@@ -298,12 +593,14 @@ pub async fn http_post_full<T: DeserializeOwned>(
 
     let path_and_query = path_and_param_to_api_url(&path.into(), None::<()>)?;
 
-    let resp: proxmox_client::HttpApiResponse = if let Some(data) = &data {
-        client.post(&path_and_query, &data).await?
+    let resp = if let Some(data) = &data {
+        client.post(&path_and_query, &data).await
     } else {
-        client.post_without_body(&path_and_query).await?
+        client.post_without_body(&path_and_query).await
     };
-    Ok(resp.expect_json()?)
+    http_cache_clear();
+    let resp: proxmox_client::HttpApiResponse = resp?;
+    parse_api_response(&resp)
 }
 
 pub async fn http_put<T: DeserializeOwned>(
@@ -314,12 +611,14 @@ pub async fn http_put<T: DeserializeOwned>(
 
     let path_and_query = path_and_param_to_api_url(&path.into(), None::<()>)?;
 
-    let resp: proxmox_client::HttpApiResponse = if let Some(data) = &data {
-        client.put(&path_and_query, &data).await?
+    let resp = if let Some(data) = &data {
+        client.put(&path_and_query, &data).await
     } else {
-        client.put_without_body(&path_and_query).await?
+        client.put_without_body(&path_and_query).await
     };
-    let resp: ApiResponseData<T> = resp.expect_json()?;
+    http_cache_clear();
+    let resp: proxmox_client::HttpApiResponse = resp?;
+    let resp: ApiResponseData<T> = parse_api_response(&resp)?;
     Ok(resp.data)
 }
 
@@ -4,12 +4,13 @@ use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::Poll;
 
-use anyhow::{format_err, Context as _, Error};
+use anyhow::{bail, format_err, Context as _, Error};
 use futures::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead};
 use serde::Deserialize;
 use serde_json::Value;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
+use yew::Callback;
 
 use pwt::convert_js_error;
 
@@ -363,3 +364,109 @@ where
         Ok(Self::from(Records::try_from(reader)?))
     }
 }
+
+/// Progress of a [download_to_file] call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DownloadProgress {
+    /// Bytes received so far.
+    pub loaded: u64,
+    /// The size of the download, if the server sent a `content-length` header.
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// The received fraction (0.0 to 1.0), if the total size is known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total
+            .filter(|total| *total > 0)
+            .map(|total| (self.loaded as f64 / total as f64).min(1.0))
+    }
+}
+
+/// Download `url` and offer the result as file `filename` to the user.
+///
+/// The response body is read chunk by chunk and the chunks are passed to a `Blob` as they
+/// are, so large downloads (task logs, certificates, client bundles) are never copied into a
+/// single buffer. `on_progress` is called after each received chunk.
+///
+/// Returns the number of received bytes. Dropping the future aborts the download.
+pub async fn download_to_file(
+    url: &str,
+    filename: &str,
+    on_progress: Option<Callback<DownloadProgress>>,
+) -> Result<u64, Error> {
+    let window = web_sys::window().ok_or_else(|| format_err!("unable to get window object"))?;
+    let abort = pwt::WebSysAbortGuard::new()?;
+
+    let init = web_sys::RequestInit::new();
+    init.set_method("GET");
+    init.set_signal(Some(&abort.signal()));
+
+    let response: web_sys::Response =
+        wasm_bindgen_futures::JsFuture::from(window.fetch_with_str_and_init(url, &init))
+            .await
+            .map_err(convert_js_error)?
+            .into();
+
+    if response.status() == 401 {
+        log::info!("got UNAUTHORIZED while downloading - clearing the auth cookie");
+        crate::http_clear_auth();
+        bail!("could not download '{url}' - UNAUTHORIZED");
+    }
+    if !response.ok() {
+        bail!(
+            "could not download '{url}', response status {}",
+            response.status()
+        );
+    }
+
+    let headers = response.headers();
+    let total = headers
+        .get("content-length")
+        .ok()
+        .flatten()
+        .and_then(|len| len.parse().ok());
+    let content_type = headers.get("content-type").ok().flatten();
+
+    let parts = js_sys::Array::new();
+    let mut loaded = 0;
+
+    if let Some(body) = response.body() {
+        let reader = web_sys::ReadableStreamDefaultReader::new(&body).map_err(convert_js_error)?;
+        loop {
+            // We get a `{ done: bool, value: Uint8Array }` from the reader.
+            let result = wasm_bindgen_futures::JsFuture::from(reader.read())
+                .await
+                .map_err(convert_js_error)?;
+
+            if let Ok(chunk) = js_sys::Reflect::get(&result, &"value".into())
+                .and_then(|value| value.dyn_into::<js_sys::Uint8Array>())
+            {
+                loaded += chunk.length() as u64;
+                parts.push(&chunk);
+                if let Some(on_progress) = &on_progress {
+                    on_progress.emit(DownloadProgress { loaded, total });
+                }
+            }
+
+            if js_sys::Reflect::get(&result, &"done".into())
+                .ok()
+                .and_then(|value| value.as_bool())
+                .unwrap_or(true)
+            {
+                break;
+            }
+        }
+    }
+
+    let options = web_sys::BlobPropertyBag::new();
+    if let Some(content_type) = &content_type {
+        options.set_type(content_type);
+    }
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(convert_js_error)?;
+
+    crate::utils::download_blob(filename, &blob)?;
+
+    Ok(loaded)
+}
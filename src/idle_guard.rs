@@ -0,0 +1,283 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gloo_events::EventListener;
+use gloo_timers::callback::Interval;
+
+use yew::html::IntoEventCallback;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::local_storage;
+use pwt::widget::{Button, Column, Dialog, Row};
+
+use pwt_macros::builder;
+
+/// Events considered as user activity.
+const ACTIVITY_EVENTS: [&str; 5] = [
+    "pointerdown",
+    "pointermove",
+    "keydown",
+    "wheel",
+    "touchstart",
+];
+
+/// Local storage key used to share the last activity time between browser tabs.
+const LAST_ACTIVITY_KEY: &str = "ProxmoxIdleGuardLastActivity";
+
+fn store_last_activity(time: f64) {
+    if let Some(store) = local_storage() {
+        let _ = store.set_item(LAST_ACTIVITY_KEY, &time.to_string());
+    }
+}
+
+fn load_last_activity() -> Option<f64> {
+    local_storage()?
+        .get_item(LAST_ACTIVITY_KEY)
+        .ok()??
+        .parse()
+        .ok()
+}
+
+/// Logs out the user after a period of inactivity.
+///
+/// Pointer and keyboard events count as activity. The time of the last activity is shared
+/// between browser tabs through the local storage, so activity in any tab keeps the session
+/// alive. After `idle_timeout` seconds without activity, a dialog counts down `warning_time`
+/// seconds. If the user does not choose to stay logged in, the authentication (ticket cookie
+/// and CSRF token) is cleared with [http_clear_auth](crate::http_clear_auth) and `on_logout`
+/// is called.
+///
+/// Nothing happens while there is no authentication, so it is safe to wrap the whole
+/// application, including the login panel.
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct IdleGuard {
+    #[prop_or_default]
+    pub children: Html,
+
+    /// Seconds without user activity until the logout warning is shown.
+    #[builder]
+    #[prop_or(15 * 60)]
+    pub idle_timeout: u32,
+
+    /// Seconds the logout warning is shown before the user gets logged out.
+    #[builder]
+    #[prop_or(60)]
+    pub warning_time: u32,
+
+    /// Called after the user was logged out because of inactivity.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_logout: Option<Callback<()>>,
+}
+
+impl IdleGuard {
+    /// Create a new instance.
+    pub fn new(children: impl Into<Html>) -> Self {
+        yew::props!(Self {
+            children: children.into(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IdleState {
+    Active,
+    /// Seconds left until logout.
+    Warning(u32),
+    Expired,
+}
+
+/// Compute the state after `idle` milliseconds without user activity.
+fn idle_state(idle: f64, idle_timeout: u32, warning_time: u32) -> IdleState {
+    let idle = (idle / 1000.0).max(0.0) as u64;
+    let idle_timeout = idle_timeout as u64;
+    let logout_time = idle_timeout + warning_time as u64;
+
+    if idle >= logout_time {
+        IdleState::Expired
+    } else if idle >= idle_timeout {
+        IdleState::Warning((logout_time - idle) as u32)
+    } else {
+        IdleState::Active
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Tick,
+    StayLoggedIn,
+    Logout,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxIdleGuard {
+    state: IdleState,
+    last_activity: Rc<Cell<f64>>,
+    // activity is ignored while the warning is shown, the user has to confirm explicitly
+    warning: Rc<Cell<bool>>,
+    _listeners: Vec<EventListener>,
+    _interval: Interval,
+}
+
+impl ProxmoxIdleGuard {
+    fn reset(&mut self) {
+        let now = js_sys::Date::now();
+        self.last_activity.set(now);
+        store_last_activity(now);
+        self.warning.set(false);
+        self.state = IdleState::Active;
+    }
+
+    fn logout(&mut self, ctx: &Context<Self>) {
+        self.reset();
+        crate::http_clear_auth();
+        if let Some(on_logout) = &ctx.props().on_logout {
+            on_logout.emit(());
+        }
+    }
+
+    fn warning_dialog(&self, ctx: &Context<Self>, seconds: u32) -> Html {
+        let link = ctx.link();
+
+        Dialog::new(tr!("Session Timeout"))
+            .min_width(300)
+            .on_close(link.callback(|_| Msg::StayLoggedIn))
+            .with_child(Column::new().padding(2).with_child(tr!(
+                "You will be logged out in {0} seconds due to inactivity.",
+                seconds
+            )))
+            .with_child(
+                Row::new()
+                    .padding(2)
+                    .gap(2)
+                    .with_flex_spacer()
+                    .with_child(Button::new(tr!("Logout")).onclick(link.callback(|_| Msg::Logout)))
+                    .with_child(
+                        Button::new(tr!("Stay logged in"))
+                            .class("pwt-scheme-primary")
+                            .onclick(link.callback(|_| Msg::StayLoggedIn)),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl Component for ProxmoxIdleGuard {
+    type Message = Msg;
+    type Properties = IdleGuard;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let now = js_sys::Date::now();
+        store_last_activity(now);
+        let last_activity = Rc::new(Cell::new(now));
+        let warning = Rc::new(Cell::new(false));
+        let last_stored = Rc::new(Cell::new(now));
+
+        let document = gloo_utils::document();
+        let listeners = ACTIVITY_EVENTS
+            .into_iter()
+            .map(|event| {
+                let last_activity = Rc::clone(&last_activity);
+                let warning = Rc::clone(&warning);
+                let last_stored = Rc::clone(&last_stored);
+                EventListener::new(&document, event, move |_| {
+                    if !warning.get() {
+                        let now = js_sys::Date::now();
+                        // limit local storage writes, pointer events fire very often
+                        if now - last_stored.get() >= 1000.0 {
+                            store_last_activity(now);
+                            last_stored.set(now);
+                        }
+                        last_activity.set(now);
+                    }
+                })
+            })
+            .collect();
+
+        let link = ctx.link().clone();
+        let interval = Interval::new(1000, move || link.send_message(Msg::Tick));
+
+        Self {
+            state: IdleState::Active,
+            last_activity,
+            warning,
+            _listeners: listeners,
+            _interval: interval,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Tick => {
+                if crate::http_get_auth().is_none() {
+                    let changed = self.state != IdleState::Active;
+                    self.reset();
+                    return changed;
+                }
+
+                // pick up activity (or "stay logged in") from other tabs
+                if let Some(shared) = load_last_activity() {
+                    if shared > self.last_activity.get() {
+                        self.last_activity.set(shared);
+                    }
+                }
+
+                let idle = js_sys::Date::now() - self.last_activity.get();
+                let state = idle_state(idle, props.idle_timeout, props.warning_time);
+                if state == IdleState::Expired {
+                    self.logout(ctx);
+                    return true;
+                }
+                self.warning.set(state != IdleState::Active);
+                if state == self.state {
+                    return false;
+                }
+                self.state = state;
+                true
+            }
+            Msg::StayLoggedIn => {
+                self.reset();
+                true
+            }
+            Msg::Logout => {
+                self.logout(ctx);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let dialog = match self.state {
+            IdleState::Warning(seconds) => Some(self.warning_dialog(ctx, seconds)),
+            _ => None,
+        };
+
+        html! {<>{ctx.props().children.clone()}{dialog}</>}
+    }
+}
+
+impl From<IdleGuard> for VNode {
+    fn from(val: IdleGuard) -> Self {
+        let comp = VComp::new::<ProxmoxIdleGuard>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_idle_state() {
+        assert_eq!(idle_state(0.0, 600, 60), IdleState::Active);
+        assert_eq!(idle_state(599_999.0, 600, 60), IdleState::Active);
+        assert_eq!(idle_state(600_000.0, 600, 60), IdleState::Warning(60));
+        assert_eq!(idle_state(630_500.0, 600, 60), IdleState::Warning(30));
+        assert_eq!(idle_state(660_000.0, 600, 60), IdleState::Expired);
+        // clock changes must not log out immediately
+        assert_eq!(idle_state(-5_000.0, 600, 60), IdleState::Active);
+    }
+}
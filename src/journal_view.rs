@@ -10,7 +10,7 @@ use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::dom::IntoHtmlElement;
 use pwt::props::{AsClassesMut, AsCssStylesMut, CssStyles, FieldBuilder, WidgetBuilder};
-use pwt::widget::form::{Checkbox, Combobox};
+use pwt::widget::form::{Checkbox, Combobox, DateField, Field, InputType, PlainDate};
 use pwt::widget::{Button, Column, Container, FieldLabel, Row, Toolbar, VisibilityContext};
 use pwt::{prelude::*, AsyncPool};
 use pwt_macros::builder;
@@ -63,6 +63,14 @@ pub struct JournalView {
     #[builder(IntoPropValue, into_prop_value)]
     pub unit: Option<AttrValue>,
 
+    /// Show the navigation row with the since/until pickers and the jump controls.
+    ///
+    /// Without a time range the view tails the journal live; selecting a range loads the entries
+    /// of that range once and stops tailing.
+    #[prop_or_default]
+    #[builder]
+    pub show_navigation: bool,
+
     /// Callback when the loading state changes.
     /// The values determine if it's currently loading and if it's in "tail view" mode
     #[prop_or_default]
@@ -166,12 +174,43 @@ pub enum Msg {
     ApplyFilters,
     /// The "Reset" button cleared every filter back to the unfiltered default.
     ResetFilters,
+    /// The since date of the time range changed.
+    SinceDate(Option<PlainDate>),
+    /// The since time of the time range changed.
+    SinceTime(String),
+    /// The until date of the time range changed.
+    UntilDate(Option<PlainDate>),
+    /// The until time of the time range changed.
+    UntilTime(String),
+    /// Clear the time range and go back to live tailing.
+    ClearRange,
+    /// Scroll to the beginning of the buffer, loading older entries in live mode.
+    JumpToStart,
+    /// Scroll to the end of the buffer and resume live tailing.
+    JumpToEnd,
 }
 
 enum JournalRequest {
     Initial(usize),
     Bottom(String),
     Top(usize, String),
+    Range(TimeRange),
+}
+
+/// A time range selected with the navigation pickers, in seconds since the epoch.
+///
+/// A range is loaded with a single `since`/`until` request, like the ExtJS timespan mode; cursor
+/// paging and live tailing only apply to the unbounded view.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct TimeRange {
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+impl TimeRange {
+    fn is_active(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
 }
 
 /// Server-side filter selection shared by every request of a view.
@@ -242,6 +281,15 @@ pub struct ProxmoxJournalView {
     units: Vec<String>,
     /// debounce timer for freeform-filter changes, so typing does not reload on every keystroke
     filter_timeout: Option<Timeout>,
+
+    /// the selected time range; an inactive range means live tailing
+    range: TimeRange,
+    /// whether the entries of the active time range were loaded, ranges are not paged
+    range_loaded: bool,
+    since_date: Option<PlainDate>,
+    since_time: String,
+    until_date: Option<PlainDate>,
+    until_time: String,
 }
 
 /// Convert a realtime timestamp in microseconds to a short syslog-like local time string,
@@ -300,6 +348,16 @@ async fn load_content(
             }),
             Position::Initial,
         ),
+        JournalRequest::Range(range) => {
+            let mut param = json!({});
+            if let Some(since) = range.since {
+                param["since"] = since.into();
+            }
+            if let Some(until) = range.until {
+                param["until"] = until.into();
+            }
+            (param, Position::Initial)
+        }
     };
 
     if structured {
@@ -380,6 +438,12 @@ impl ProxmoxJournalView {
             return;
         }
         let (request, timeout) = match (&self.position, &self.cursors) {
+            _ if self.range.is_active() => {
+                if self.range_loaded {
+                    return;
+                }
+                (JournalRequest::Range(self.range), 0)
+            }
             (_, None) => {
                 self.position = Position::Bottom;
                 (JournalRequest::Initial(ENTRIES_LOAD_NUM), 0)
@@ -475,9 +539,29 @@ impl ProxmoxJournalView {
         self.content = Content::Structured(Vec::new());
         self.position = Position::Bottom;
         self.last_error = None;
+        self.range_loaded = false;
         self.load(ctx);
     }
 
+    /// Recompute the time range from the pickers, reloading if it changed.
+    fn update_range(&mut self, ctx: &Context<Self>) -> bool {
+        let range = TimeRange {
+            since: self
+                .since_date
+                .as_ref()
+                .and_then(|date| crate::syslog::date_time_to_epoch(date, &self.since_time)),
+            until: self
+                .until_date
+                .as_ref()
+                .and_then(|date| crate::syslog::date_time_to_epoch(date, &self.until_time)),
+        };
+        if self.range != range {
+            self.range = range;
+            self.reset_and_reload(ctx);
+        }
+        true
+    }
+
     /// Debounce a freeform-filter change: the unit and identifier comboboxes emit on every
     /// keystroke, so wait for a typing pause before reloading rather than querying per character.
     fn schedule_filter_reload(&mut self, ctx: &Context<Self>) {
@@ -531,6 +615,12 @@ impl Component for ProxmoxJournalView {
             identifiers: Vec::new(),
             units: Vec::new(),
             filter_timeout: None,
+            range: TimeRange::default(),
+            range_loaded: false,
+            since_date: None,
+            since_time: "00:00".to_string(),
+            until_date: None,
+            until_time: "23:59".to_string(),
         };
 
         this.load(ctx);
@@ -541,6 +631,7 @@ impl Component for ProxmoxJournalView {
         match msg {
             Msg::EmptyLoad(completions) => {
                 self.timeout.take();
+                self.range_loaded = self.range.is_active();
                 if let Some(callback) = ctx.props().on_loading_change.clone() {
                     callback.emit((false, self.position == Position::Bottom));
                 }
@@ -553,6 +644,7 @@ impl Component for ProxmoxJournalView {
             }
             Msg::PageLoad(page, response_type, completions) => {
                 self.timeout.take();
+                self.range_loaded = self.range.is_active();
                 if let Some(callback) = ctx.props().on_loading_change.clone() {
                     callback.emit((false, self.position == Position::Bottom));
                 }
@@ -669,6 +761,43 @@ impl Component for ProxmoxJournalView {
                 self.reset_and_reload(ctx);
                 true
             }
+            Msg::SinceDate(date) => {
+                self.since_date = date;
+                self.update_range(ctx)
+            }
+            Msg::SinceTime(time) => {
+                self.since_time = time;
+                self.update_range(ctx)
+            }
+            Msg::UntilDate(date) => {
+                self.until_date = date;
+                self.update_range(ctx)
+            }
+            Msg::UntilTime(time) => {
+                self.until_time = time;
+                self.update_range(ctx)
+            }
+            Msg::ClearRange => {
+                self.since_date = None;
+                self.until_date = None;
+                self.update_range(ctx)
+            }
+            Msg::JumpToStart => {
+                // `rendered` keeps the distance to the bottom for the top position, so pretend
+                // the buffer grew by its full height to land on the first entry
+                if let Some(el) = self.log_ref.cast::<web_sys::Element>() {
+                    self.old_scroll_height = el.scroll_height();
+                }
+                self.position = Position::Top;
+                self.timeout.take();
+                self.load(ctx);
+                true
+            }
+            Msg::JumpToEnd => {
+                self.position = Position::Bottom;
+                self.load(ctx);
+                true
+            }
         }
     }
 
@@ -718,6 +847,11 @@ impl Component for ProxmoxJournalView {
             .class("pwt-flex-fit")
             .class(props.class.clone())
             .styles(props.style.clone())
+            .with_optional_child(
+                props
+                    .show_navigation
+                    .then(|| self.render_navigation_row(ctx)),
+            )
             .with_optional_child(
                 (props.structured && props.show_filters).then(|| self.render_filter_row(ctx)),
             )
@@ -773,6 +907,80 @@ impl ProxmoxJournalView {
         }
     }
 
+    /// The navigation row: since/until pickers for the time range and the jump controls.
+    fn render_navigation_row(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let date_field = |date: &Option<PlainDate>, callback: Callback<Option<PlainDate>>| {
+            DateField::new()
+                .value(
+                    date.as_ref()
+                        .map(|date| date.to_string())
+                        .unwrap_or_default(),
+                )
+                .on_change(callback)
+        };
+        let time_field = |time: &str, enabled: bool, callback: Callback<String>| {
+            Field::new()
+                .input_type(InputType::Time)
+                .required(true) // avoid clear button in firefox
+                .disabled(!enabled)
+                .class("pwt-input-hide-clear-button")
+                .value(time.to_string())
+                .on_change(callback)
+        };
+
+        let labeled = |label: String, date: DateField, time: Field| {
+            Row::new()
+                .gap(2)
+                .class(pwt::css::AlignItems::Center)
+                .with_child(FieldLabel::new(label))
+                .with_child(date)
+                .with_child(time)
+        };
+
+        Toolbar::new()
+            .class("pwt-border-bottom")
+            .class("pwt-gap-4")
+            .with_child(labeled(
+                tr!("Since"),
+                date_field(&self.since_date, link.callback(Msg::SinceDate)),
+                time_field(
+                    &self.since_time,
+                    self.since_date.is_some(),
+                    link.callback(Msg::SinceTime),
+                ),
+            ))
+            .with_child(labeled(
+                tr!("Until"),
+                date_field(&self.until_date, link.callback(Msg::UntilDate)),
+                time_field(
+                    &self.until_time,
+                    self.until_date.is_some(),
+                    link.callback(Msg::UntilTime),
+                ),
+            ))
+            .with_child(
+                Button::new(tr!("Clear"))
+                    .disabled(!self.range.is_active())
+                    .onclick(link.callback(|_| Msg::ClearRange)),
+            )
+            .with_flex_spacer()
+            .with_child(
+                Button::new_icon("fa fa-angle-double-up")
+                    .aria_label(tr!("Jump to beginning"))
+                    .attribute("title", tr!("Jump to beginning"))
+                    .onclick(link.callback(|_| Msg::JumpToStart)),
+            )
+            .with_child(
+                Button::new_icon("fa fa-angle-double-down")
+                    .aria_label(tr!("Jump to end"))
+                    .attribute("title", tr!("Jump to end"))
+                    .onclick(link.callback(|_| Msg::JumpToEnd)),
+            )
+            .into()
+    }
+
     /// The filter row: minimum priority, unit, identifier, and a kernel-only toggle.
     ///
     /// Mirrors the ExtJS filter row: kernel-only is exclusive, so it disables the unit and
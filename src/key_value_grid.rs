@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
 use derivative::Derivative;
@@ -9,7 +10,7 @@ use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::props::{CallbackMut, ExtractPrimaryKey, IntoEventCallbackMut};
-use pwt::state::{Selection, Store};
+use pwt::state::{Selection, SlabTree, SlabTreeNodeMut, TreeStore};
 use pwt::widget::data_table::{
     CellConfiguration, DataTable, DataTableColumn, DataTableHeader, DataTableKeyboardEvent,
     DataTableMouseEvent,
@@ -17,6 +18,8 @@ use pwt::widget::data_table::{
 
 use pwt_macros::builder;
 
+use crate::utils::{render_boolean_badge, value_to_boolean};
+
 /// For use with KVGrid
 #[derive(Derivative)]
 #[derivative(Clone, PartialEq)]
@@ -48,6 +51,8 @@ pub struct KVGridRow {
     #[builder(IntoPropValue, into_prop_value)]
     pub placeholder: Option<String>,
     pub renderer: Option<RenderKVGridRecordFn>,
+    /// Renderers for nested values (tree mode), by depth (starting at 1).
+    pub depth_renderers: BTreeMap<usize, RenderKVGridRecordFn>,
 }
 
 impl KVGridRow {
@@ -58,6 +63,7 @@ impl KVGridRow {
             required: false,
             placeholder: None,
             renderer: None,
+            depth_renderers: BTreeMap::new(),
         }
     }
 
@@ -69,6 +75,39 @@ impl KVGridRow {
     pub fn set_renderer(&mut self, renderer: impl 'static + Fn(&str, &Value, &Value) -> Html) {
         self.renderer = Some(RenderKVGridRecordFn::new(renderer));
     }
+
+    /// Set the renderer for nested values at `depth` (tree mode, see [KVGrid::tree]).
+    ///
+    /// Direct children of the row have depth 1. The renderer gets the name (or array
+    /// index) of the nested value, the value and the grid data.
+    pub fn depth_renderer(
+        mut self,
+        depth: usize,
+        renderer: impl 'static + Fn(&str, &Value, &Value) -> Html,
+    ) -> Self {
+        if depth == 0 {
+            self.set_renderer(renderer);
+        } else {
+            self.depth_renderers
+                .insert(depth, RenderKVGridRecordFn::new(renderer));
+        }
+        self
+    }
+
+    /// Render the value as boolean badge.
+    ///
+    /// Useful for properties using `0`/`1` instead of JSON booleans. Unset values are shown
+    /// as "Unknown", or as the given default.
+    pub fn boolean(mut self, default: Option<bool>) -> Self {
+        self.set_renderer(move |_name, value, _record| {
+            let value = match value {
+                Value::Null => default,
+                value => value_to_boolean(value),
+            };
+            render_boolean_badge(value)
+        });
+        self
+    }
 }
 
 #[derive(Properties, PartialEq, Clone)]
@@ -97,6 +136,11 @@ pub struct KVGrid {
     #[builder]
     pub striped: bool,
 
+    /// Show nested objects and arrays as expandable child rows.
+    #[prop_or_default]
+    #[builder]
+    pub tree: bool,
+
     rows: Rc<Vec<KVGridRow>>,
     data: Rc<Value>,
     /// Select callback.
@@ -181,46 +225,120 @@ impl KVGrid {
 
 #[derive(Clone, PartialEq)]
 struct KVGridRecord {
+    key: Key,
     row: Rc<KVGridRow>,
+    // name or array index of nested values
+    name: Option<String>,
+    depth: usize,
     value: Value,
     store: Rc<Value>,
 }
 
 impl ExtractPrimaryKey for KVGridRecord {
     fn extract_key(&self) -> Key {
-        Key::from(self.row.name.as_str())
+        self.key.clone()
     }
 }
 
 #[doc(hidden)]
 pub struct PwtKVGrid {
     rows: Rc<IndexMap<String, Rc<KVGridRow>>>,
-    store: Store<KVGridRecord>,
+    store: TreeStore<KVGridRecord>,
+    columns: Rc<Vec<DataTableHeader<KVGridRecord>>>,
     selection: Selection,
 }
 
-thread_local! {
-    static COLUMNS: Rc<Vec<DataTableHeader<KVGridRecord>>> = Rc::new(vec![
+fn columns(store: &TreeStore<KVGridRecord>, tree: bool) -> Rc<Vec<DataTableHeader<KVGridRecord>>> {
+    Rc::new(vec![
         DataTableColumn::new("Key")
             .show_menu(false)
-            .render(|record: &KVGridRecord| html!{record.row.header.clone()})
+            .tree_column(tree.then(|| store.clone()))
+            .render(|record: &KVGridRecord| match &record.name {
+                Some(name) => html! {name.clone()},
+                None => html! {record.row.header.clone()},
+            })
             .into(),
         DataTableColumn::new("Value")
             .width("100%")
             .show_menu(false)
-            .render(|record: &KVGridRecord|  {
-                match &record.row.renderer {
-                    Some(renderer) => renderer.apply(&record.row.name, &record.value, &record.store),
-                    None => render_value(&record.value),
-                }
-            })
+            .render(move |record: &KVGridRecord| render_record(record, tree))
             .into(),
-    ]);
+    ])
+}
+
+fn render_record(record: &KVGridRecord, tree: bool) -> Html {
+    let (name, renderer) = match &record.name {
+        None => (record.row.name.as_str(), record.row.renderer.as_ref()),
+        Some(name) => (name.as_str(), record.row.depth_renderers.get(&record.depth)),
+    };
+
+    if let Some(renderer) = renderer {
+        return renderer.apply(name, &record.value, &record.store);
+    }
+
+    match &record.value {
+        Value::Object(map) if tree && !map.is_empty() => {
+            html! {tr!("One item" | "{n} items" % map.len())}
+        }
+        Value::Array(list) if tree && !list.is_empty() => {
+            html! {tr!("One item" | "{n} items" % list.len())}
+        }
+        value => render_value(value),
+    }
 }
 
 impl PwtKVGrid {
+    fn is_expanded(&self, key: &Key) -> bool {
+        self.store
+            .read()
+            .lookup_node(key)
+            .map(|node| node.expanded())
+            .unwrap_or(false)
+    }
+
+    // add nested values as child nodes, keeping the expanded state of existing nodes
+    fn add_children(&self, mut node: SlabTreeNodeMut<'_, KVGridRecord>, props: &KVGrid) {
+        let parent = node.record().clone();
+        let children: Vec<(String, Value)> = match &parent.value {
+            Value::Object(map) => map
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            Value::Array(list) => list
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (i.to_string(), value.clone()))
+                .collect(),
+            _ => return,
+        };
+
+        for (name, value) in children {
+            let key = Key::from(format!("{}/{name}", parent.key));
+            let expanded = self.is_expanded(&key);
+            let mut child = node.append(KVGridRecord {
+                key,
+                row: Rc::clone(&parent.row),
+                name: Some(name),
+                depth: parent.depth + 1,
+                value,
+                store: Rc::clone(&props.data),
+            });
+            child.set_expanded(expanded);
+            self.add_children(child, props);
+        }
+    }
+
     fn data_update(&mut self, props: &KVGrid) {
-        let mut visible_rows: Vec<KVGridRecord> = Vec::new();
+        let mut tree = SlabTree::new();
+        let mut root = tree.set_root(KVGridRecord {
+            key: Key::from("__root__"),
+            row: Rc::new(KVGridRow::new("", "")),
+            name: None,
+            depth: 0,
+            value: Value::Null,
+            store: Rc::clone(&props.data),
+        });
+        root.set_expanded(true);
 
         for row in self.rows.values() {
             let name = row.name.as_str();
@@ -238,14 +356,23 @@ impl PwtKVGrid {
                     Some(value) => value.clone(),
                 };
 
-                visible_rows.push(KVGridRecord {
+                let key = Key::from(name);
+                let expanded = self.is_expanded(&key);
+                let mut node = root.append(KVGridRecord {
+                    key,
                     row: Rc::clone(row),
+                    name: None,
+                    depth: 0,
                     value,
                     store: Rc::clone(&props.data),
                 });
+                if props.tree {
+                    node.set_expanded(expanded);
+                    self.add_children(node, props);
+                }
             }
         }
-        self.store.set_data(visible_rows);
+        self.store.set_data(tree);
     }
 }
 
@@ -273,9 +400,11 @@ impl Component for PwtKVGrid {
             }
         });
 
+        let store = TreeStore::new().view_root(false);
         let mut me = Self {
             rows: convert_rows(&props.rows),
-            store: Store::new(),
+            columns: columns(&store, props.tree),
+            store,
             selection,
         };
         me.data_update(props);
@@ -285,7 +414,14 @@ impl Component for PwtKVGrid {
     fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
         let props = ctx.props();
 
-        if props.data != old_props.data || !Rc::ptr_eq(&props.rows, &old_props.rows) {
+        if props.tree != old_props.tree {
+            self.columns = columns(&self.store, props.tree);
+        }
+
+        if props.data != old_props.data
+            || props.tree != old_props.tree
+            || !Rc::ptr_eq(&props.rows, &old_props.rows)
+        {
             if !Rc::ptr_eq(&props.rows, &old_props.rows) {
                 self.rows = convert_rows(&props.rows);
             }
@@ -297,7 +433,7 @@ impl Component for PwtKVGrid {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
-        DataTable::new(COLUMNS.with(Rc::clone), self.store.clone())
+        DataTable::new(Rc::clone(&self.columns), self.store.clone())
             .class(props.class.clone())
             .cell_configuration(props.cell_configuration.clone())
             .borderless(props.borderless)
@@ -323,7 +459,7 @@ impl From<KVGrid> for VNode {
 fn render_value(value: &Value) -> Html {
     match value {
         Value::Null => html! { {"NULL"} },
-        Value::Bool(v) => html! { {v.to_string()} },
+        Value::Bool(v) => render_boolean_badge(Some(*v)),
         Value::Number(v) => html! { {v.to_string()} },
         Value::String(v) => html! { {v} },
         v => html! { {v.to_string()} },
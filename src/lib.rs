@@ -1,8 +1,14 @@
 pub mod acme;
 
+mod account_dialog;
+pub use account_dialog::{AccountDialog, ProxmoxAccountDialog};
+
 mod acl_context;
 pub use acl_context::{AclContext, AclContextProvider};
 
+mod api_error;
+pub use api_error::ApiError;
+
 mod api_load_callback;
 pub use api_load_callback::{ApiLoadCallback, IntoApiLoadCallback};
 
@@ -23,13 +29,13 @@ mod auth_edit_openid;
 pub use auth_edit_openid::{AuthEditOpenID, ProxmoxAuthEditOpenID};
 
 mod auth_edit_ldap;
-pub use auth_edit_ldap::{AuthEditLDAP, ProxmoxAuthEditLDAP};
+pub use auth_edit_ldap::{AuthEditAD, AuthEditLDAP, ProxmoxAuthEditLDAP};
 
 mod authid_selector;
 pub use authid_selector::AuthidSelector;
 
 mod acl;
-pub use acl::{AclEdit, AclPathSelector, AclView};
+pub use acl::{AclEdit, AclOverview, AclPathSelector, AclView};
 
 mod bandwidth_selector;
 pub use bandwidth_selector::{BandwidthSelector, ProxmoxBandwidthSelector};
@@ -48,14 +54,31 @@ mod confirm_button;
 pub use confirm_button::default_confirm_remove_message;
 pub use confirm_button::{ConfirmButton, ProxmoxConfirmButton};
 
+mod copy_field;
+pub use copy_field::{CopyField, ProxmoxCopyField};
+
+pub mod data_export;
+
 mod data_view_window;
 pub use data_view_window::{DataViewWindow, ProxmoxDataViewWindow};
 
+mod datacenter_summary_panel;
+pub use datacenter_summary_panel::{
+    ClusterStats, DatacenterSummaryPanel, GuestCounts, ProxmoxDatacenterSummaryPanel,
+};
+
 pub mod form;
 
 pub mod gauge;
 pub use gauge::{Gauge, ProxmoxGauge};
 
+pub mod grid_state;
+
+mod group_selector;
+pub use group_selector::{GroupListEntry, GroupSelector};
+
+pub mod ha;
+
 mod http_client_wasm;
 pub use http_client_wasm::*;
 
@@ -65,7 +88,12 @@ mod http_helpers;
 pub use http_helpers::*;
 
 mod help_button;
-pub use help_button::{HelpButton, PbsHelpButton};
+#[allow(deprecated)]
+pub use help_button::PbsHelpButton;
+pub use help_button::{help_link, HelpButton, ProductHelpButton, ProxmoxProductHelpButton};
+
+mod idle_guard;
+pub use idle_guard::{IdleGuard, ProxmoxIdleGuard};
 
 mod calendar_event_selector;
 pub use calendar_event_selector::CalendarEventSelector;
@@ -88,8 +116,9 @@ pub mod layout;
 
 mod loadable_component;
 pub use loadable_component::{
-    LoadableComponent, LoadableComponentContext, LoadableComponentMaster, LoadableComponentScope,
-    LoadableComponentScopeExt, LoadableComponentState,
+    skeleton_placeholder, LoadRetry, LoadableComponent, LoadableComponentContext,
+    LoadableComponentMaster, LoadableComponentScope, LoadableComponentScopeExt,
+    LoadableComponentState,
 };
 
 mod node_info;
@@ -101,11 +130,16 @@ pub use node_status_panel::NodeStatusPanel;
 mod notes_view;
 pub use notes_view::{NotesView, NotesWithDigest, ProxmoxNotesView};
 
+pub mod notification;
+
 mod object_grid;
 pub use object_grid::{
     ObjectGrid, ObjectGridController, ObjectGridRow, PwtObjectGrid, RenderObjectGridItemFn,
 };
 
+mod panel_registry;
+pub use panel_registry::{PanelEntry, PanelPrivilege, PanelRegistry};
+
 mod permission_panel;
 pub use permission_panel::{PermissionPanel, ProxmoxPermissionPanel};
 
@@ -115,8 +149,22 @@ pub mod pending_property_view;
 
 pub mod pve_api_types;
 
+mod qr_code;
+pub use qr_code::{ProxmoxQrCode, QrCode};
+
 mod realm_selector;
-pub use realm_selector::RealmSelector;
+pub use realm_selector::{RealmFilterFn, RealmSelector};
+
+mod refresh_scheduler;
+pub use refresh_scheduler::{RefreshScheduler, RefreshTimeout};
+
+mod repository_status;
+pub use repository_status::{
+    ProxmoxRepositoryHealthRows, RepositoryHealthRows, RepositoryStatus, RepositoryStatusLine,
+};
+
+mod role_panel;
+pub use role_panel::{PrivilegeSelector, PveRoleInfo, RolePanel};
 
 mod role_selector;
 pub use role_selector::RoleSelector;
@@ -124,13 +172,25 @@ pub use role_selector::RoleSelector;
 #[cfg(feature = "rrd")]
 mod rrd;
 #[cfg(feature = "rrd")]
-pub use rrd::{rrd_value_renderer, RRDGraph, Series};
+pub use rrd::{rrd_value_renderer, GraphMode, GraphViewRange, RRDGraph, Series};
+
+#[cfg(feature = "rrd")]
+mod rrd_data_source;
+#[cfg(feature = "rrd")]
+pub use rrd_data_source::{RrdData, RrdDataSource};
 
 #[cfg(feature = "rrd")]
 mod rrd_grid;
 #[cfg(feature = "rrd")]
 pub use rrd_grid::RRDGrid;
 
+#[cfg(feature = "rrd")]
+mod storage_status_panel;
+#[cfg(feature = "rrd")]
+pub use storage_status_panel::{
+    storage_content_text, ProxmoxStorageStatusPanel, StorageStatus, StorageStatusPanel,
+};
+
 #[cfg(feature = "rrd")]
 mod rrd_timeframe_selector;
 #[cfg(feature = "rrd")]
@@ -164,16 +224,46 @@ mod journal_view;
 pub use journal_view::JournalView;
 
 mod meter_label;
-pub use meter_label::{MeterLabel, ProxmoxMeterLabel};
+pub use meter_label::{threshold_status, MeterLabel, ProxmoxMeterLabel};
+
+mod mini_task_log;
+pub use mini_task_log::{MiniTaskLog, ProxmoxMiniTaskLog};
 
 mod sanitize_html;
 pub use sanitize_html::sanitize_html;
 
+mod schema_form;
+pub use schema_form::{schema_form_label, SchemaForm, SchemaFormField, SchemaFormFieldType};
+
+mod search_palette;
+pub use search_palette::{
+    search_matches, ProxmoxSearchPalette, SearchPalette, SearchProvider, SearchResult,
+};
+
 mod schema_validation;
 pub use schema_validation::*;
 
+mod session_keeper;
+pub use session_keeper::{ProxmoxSessionKeeper, SessionContext, SessionKeeper, SessionState};
+
+mod seconds_duration_field;
+pub use seconds_duration_field::{
+    format_seconds_duration, parse_seconds_duration, ProxmoxSecondsDurationField,
+    SecondsDurationField,
+};
+
+mod services_panel;
+pub use services_panel::{ProxmoxServicesPanel, ServiceEntry, ServicesPanel};
+
+mod shortcuts;
+pub use shortcuts::{
+    KeyCombo, ProxmoxShortcutsProvider, Shortcut, ShortcutGuard, Shortcuts, ShortcutsProvider,
+};
+
 mod status;
-pub use status::{GuestState, NodeState, Status, StorageState};
+pub use status::{
+    render_guest_status_icons, GuestLock, GuestState, HaState, NodeState, Status, StorageState,
+};
 
 mod status_row;
 pub use status_row::{ProxmoxStatusRow, StatusRow};
@@ -200,12 +290,21 @@ pub mod tfa;
 mod time_zone_selector;
 pub use time_zone_selector::{ProxmoxTimezoneSelector, TimezoneSelector};
 
+mod time_sync_status_card;
+pub use time_sync_status_card::{
+    format_time_offset, time_offset_status, NodeTimeStatus, ProxmoxTimeSyncStatusCard,
+    TimeSyncStatusCard,
+};
+
 mod theme_dialog;
 pub use theme_dialog::{ProxmoxThemeDialog, ThemeDialog};
 
 mod task_viewer;
 pub use task_viewer::*;
 
+mod task_log_parser;
+pub use task_log_parser::{parse_task_log, parse_task_progress, TaskLogSection};
+
 mod task_progress;
 pub use task_progress::TaskProgress;
 
@@ -216,7 +315,7 @@ mod task_type_selector;
 pub use task_type_selector::{ProxmoxTaskTypeSelector, TaskTypeSelector};
 
 mod tasks;
-pub use tasks::{ProxmoxTasks, Tasks};
+pub use tasks::{task_guest_id, ProxmoxTasks, Tasks};
 
 pub mod percent_encoding;
 
@@ -226,6 +325,12 @@ pub use proxmox_product::{ExistingProduct, ProjectInfo};
 mod wizard;
 pub use wizard::{PwtWizard, Wizard, WizardPageRenderInfo};
 
+mod ui_feedback;
+pub use ui_feedback::{Toast, UiFeedback, UiFeedbackProvider};
+
+mod update_check;
+pub use update_check::{check_for_updates, compare_versions, running_version, UpdateCheck};
+
 mod user_panel;
 pub use user_panel::UserPanel;
 
@@ -1,23 +1,27 @@
+use std::cell::Cell;
 use std::future::Future;
 use std::ops::DerefMut;
 use std::pin::Pin;
+use std::rc::Rc;
 
 use anyhow::Error;
 use gloo_timers::callback::Timeout;
 
 use serde_json::Value;
+use yew::context::ContextHandle;
 use yew::html::Scope;
 
+use pwt::css::ColorScheme;
 use pwt::dom::DomVisibilityObserver;
 use pwt::prelude::*;
-use pwt::widget::{AlertDialog, Column};
+use pwt::widget::{AlertDialog, Column, Container};
 use pwt::AsyncPool;
 
 #[cfg(doc)]
 #[cfg(doc)]
 use pwt::widget::Dialog;
 
-use crate::{TaskProgress, TaskViewer};
+use crate::{KeyCombo, Shortcut, ShortcutGuard, Shortcuts, TaskProgress, TaskViewer, UiFeedback};
 
 pub type LoadableComponentContext<L> = Context<LoadableComponentMaster<L>>;
 pub type LoadableComponentScope<L> = Scope<LoadableComponentMaster<L>>;
@@ -30,6 +34,10 @@ pub type LoadableComponentScope<L> = Scope<LoadableComponentMaster<L>>;
 /// - display the loaded data [LoadableComponent::main_view]
 /// - display an optional toolbar [LoadableComponent::toolbar]
 /// - display any errors from failed load.
+/// - report loads and errors to the [UiFeedback] context, if available
+/// - reload with `R` while the panel has the focus, if there is a [Shortcuts] context
+/// - optionally retry failed loads with exponential backoff (see [LoadRetry])
+/// - display an optional placeholder until the first load finished [LoadableComponent::loading_view]
 /// - display additional dialogs depening on [LoadableComponent::ViewState]
 ///
 /// The [LoadableComponentScopeExt] defines available control function on the scope.
@@ -180,6 +188,14 @@ pub trait LoadableComponent:
     /// toolbar on the top.
     fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html;
 
+    /// Optional placeholder, rendered instead of the main view until the first load succeeded.
+    ///
+    /// [skeleton_placeholder] provides a generic placeholder for lists.
+    #[allow(unused_variables)]
+    fn loading_view(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        None
+    }
+
     /// ViewState dependent dialogs
     ///
     /// The result is rendered below the main view. Usually some kind of [Dialog] window.
@@ -220,6 +236,7 @@ pub enum Msg<M, V: PartialEq> {
     ChangeView(/*reload*/ bool, ViewState<V>),
     ChildMessage(M),
     Visible(bool),
+    FeedbackUpdate(UiFeedback),
     Spawn(Pin<Box<dyn Future<Output = ()>>>),
 }
 
@@ -347,6 +364,59 @@ impl<M, V: PartialEq, T: 'static + LoadableComponent<Message = M, ViewState = V>
     }
 }
 
+/// Skeleton placeholder with `rows` gray bars, for use in [LoadableComponent::loading_view].
+pub fn skeleton_placeholder(rows: usize) -> Html {
+    Column::new()
+        .class("pwt-flex-fill")
+        .padding(2)
+        .gap(2)
+        .children(
+            (0..rows)
+                .map(|i| {
+                    // vary the width a bit, so that it looks like text
+                    let width = [100, 80, 90, 70][i % 4];
+                    Container::new()
+                        .class(ColorScheme::Neutral)
+                        .class("pwt-opacity-50")
+                        .style("height", "1.5em")
+                        .style("width", format!("{width}%"))
+                        .into()
+                })
+                .collect::<Vec<Html>>(),
+        )
+        .into()
+}
+
+/// Retry policy for failed loads.
+///
+/// The delay doubles with each attempt, starting at `initial_delay` and limited to
+/// `max_delay` (both in milliseconds).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoadRetry {
+    pub max_retries: u32,
+    pub initial_delay: u32,
+    pub max_delay: u32,
+}
+
+impl Default for LoadRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: 1000,
+            max_delay: 30_000,
+        }
+    }
+}
+
+impl LoadRetry {
+    /// The delay (in milliseconds) before retry number `attempt` (starting at 0).
+    pub fn delay(&self, attempt: u32) -> u32 {
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+}
+
 /// Base state for [LoadableComponent] implementations.
 ///
 /// The struct provides the following features:
@@ -379,6 +449,13 @@ pub struct LoadableComponentState<V: PartialEq> {
     task_base_url: Option<AttrValue>,
     view_state: ViewState<V>,
     reload_timeout: Option<Timeout>,
+    retry: Option<LoadRetry>,
+    retry_count: u32,
+    retry_timeout: Option<Timeout>,
+    loaded: bool,
+    feedback: Option<UiFeedback>,
+    shortcut_target: Rc<Cell<ShortcutTarget>>,
+    shortcuts: Vec<ShortcutGuard>,
     visible: bool,
     visibility_observer: Option<DomVisibilityObserver>,
     node_ref: NodeRef,
@@ -400,6 +477,13 @@ impl<V: PartialEq> LoadableComponentState<V> {
             task_base_url: None,
             view_state: ViewState::Main,
             reload_timeout: None,
+            retry: None,
+            retry_count: 0,
+            retry_timeout: None,
+            loaded: false,
+            feedback: None,
+            shortcut_target: Rc::new(Cell::new(ShortcutTarget::Inactive)),
+            shortcuts: Vec::new(),
             visible: true,
             visibility_observer: None,
             node_ref: NodeRef::default(),
@@ -415,6 +499,16 @@ impl<V: PartialEq> LoadableComponentState<V> {
         self.last_load_error.as_deref()
     }
 
+    /// Returns true if at least one load succeeded.
+    pub fn loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// Retry failed loads, the error dialog is only shown after the last attempt failed.
+    pub fn set_load_retry(&mut self, retry: impl Into<Option<LoadRetry>>) {
+        self.retry = retry.into();
+    }
+
     pub fn set_task_base_url(&mut self, base_url: AttrValue) {
         self.task_base_url = Some(base_url);
     }
@@ -428,9 +522,68 @@ impl<V: PartialEq> LoadableComponentState<V> {
     }
 }
 
+// What keyboard shortcuts apply to, updated on each render.
+#[derive(Copy, Clone, PartialEq)]
+enum ShortcutTarget {
+    Inactive,
+    Main,
+}
+
 #[doc(hidden)]
 pub struct LoadableComponentMaster<L: LoadableComponent> {
     state: L,
+    _feedback_handle: Option<ContextHandle<UiFeedback>>,
+}
+
+impl<L: LoadableComponent + 'static> LoadableComponentMaster<L> {
+    /// Show the error as toast, if configured. Returns false if there is no [UiFeedback].
+    fn show_error_toast(&self, title: &str, msg: &str) -> bool {
+        match &self.state.feedback {
+            Some(feedback) if feedback.errors_as_toast() => {
+                feedback.show_error(title, msg);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn register_shortcuts(
+        ctx: &Context<Self>,
+        shortcuts: &Shortcuts,
+        target: &Rc<Cell<ShortcutTarget>>,
+        node_ref: &NodeRef,
+    ) -> Vec<ShortcutGuard> {
+        // only reload the panel containing the focus, not every panel on the page.
+        // Dialogs close themselves on Escape (Dialog::on_close).
+        let reload = Shortcut::new(KeyCombo::new("r"), tr!("Reload"), {
+            let link = ctx.link().clone();
+            let target = Rc::clone(target);
+            let node_ref = node_ref.clone();
+            move |_| {
+                if target.get() != ShortcutTarget::Main {
+                    return false;
+                }
+                let Some(el) = node_ref.cast::<web_sys::Element>() else {
+                    return false;
+                };
+                let focused = gloo_utils::document().active_element();
+                if !el.contains(focused.as_deref()) {
+                    return false;
+                }
+                link.send_message(Msg::Load);
+                true
+            }
+        });
+
+        vec![shortcuts.register(reload.scope(tr!("Panel")))]
+    }
+
+    fn show_load_error(&mut self, err: &Error) {
+        let title = tr!("Load failed");
+        if !self.show_error_toast(&title, &err.to_string()) {
+            self.state.view_state = ViewState::Error(title, err.to_string(), false);
+        }
+    }
 }
 
 impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
@@ -444,8 +597,36 @@ impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
 
         let mut state = L::create(ctx);
         state.visible = true;
+        let _feedback_handle = match ctx
+            .link()
+            .context::<UiFeedback>(ctx.link().callback(Msg::FeedbackUpdate))
+        {
+            Some((feedback, handle)) => {
+                state.feedback = Some(feedback);
+                Some(handle)
+            }
+            None => None,
+        };
+
+        if let Some((shortcuts, _handle)) = ctx.link().context::<Shortcuts>(Callback::noop()) {
+            let target = Rc::clone(&state.shortcut_target);
+            let node_ref = state.node_ref.clone();
+            state.shortcuts = Self::register_shortcuts(ctx, &shortcuts, &target, &node_ref);
+        }
 
-        Self { state }
+        Self {
+            state,
+            _feedback_handle,
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        // do not leave the global busy indicator running
+        if let Some(feedback) = &self.state.feedback {
+            for _ in 0..self.state.loading {
+                feedback.end_busy();
+            }
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -455,9 +636,18 @@ impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
                 false
             }
             Msg::DataChange => true,
+            Msg::FeedbackUpdate(feedback) => {
+                self.state.feedback = Some(feedback);
+                false
+            }
             Msg::Load => {
+                // an explicit load replaces any pending retry
+                self.state.retry_timeout = None;
                 let load_future = self.state.load(ctx);
                 self.state.loading += 1;
+                if let Some(feedback) = &self.state.feedback {
+                    feedback.begin_busy();
+                }
                 let link = ctx.link().clone();
                 self.state.async_pool.spawn(async move {
                     let data = load_future.await;
@@ -475,22 +665,44 @@ impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
             }
             Msg::LoadResult(data) => {
                 self.state.loading -= 1;
+                if let Some(feedback) = &self.state.feedback {
+                    feedback.end_busy();
+                }
+                let mut retry_delay = None;
                 match data {
                     Ok(()) => {
                         self.state.last_load_error = None;
+                        self.state.retry_count = 0;
+                        self.state.loaded = true;
                     }
                     Err(err) => {
                         let this_is_the_first_error = self.state.last_load_error.is_none();
                         self.state.last_load_error = Some(err.to_string());
-                        if this_is_the_first_error {
-                            self.state.view_state =
-                                ViewState::Error(tr!("Load failed"), err.to_string(), false);
+                        match self.state.retry {
+                            Some(retry) if self.state.retry_count < retry.max_retries => {
+                                retry_delay = Some(retry.delay(self.state.retry_count));
+                                self.state.retry_count += 1;
+                            }
+                            Some(_) => {
+                                // give up, but try again on the next load
+                                self.state.retry_count = 0;
+                                self.show_load_error(&err);
+                            }
+                            None if this_is_the_first_error => {
+                                self.show_load_error(&err);
+                            }
+                            None => {}
                         }
                     }
                 }
 
                 self.state.reload_timeout = None;
-                if self.state.loading == 0 {
+                if let (0, Some(delay)) = (self.state.loading, retry_delay) {
+                    let link = ctx.link().clone();
+                    self.state.retry_timeout = Some(Timeout::new(delay, move || {
+                        link.send_message(Msg::Load);
+                    }));
+                } else if self.state.loading == 0 {
                     /* no outstanding loads */
                     if self.state.repeat_timespan > 0 {
                         let link = ctx.link().clone();
@@ -509,6 +721,15 @@ impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
                     return false;
                 }
 
+                if let ViewState::Error(title, msg, reload_on_close) = &view_state {
+                    if self.show_error_toast(title, msg) {
+                        if *reload_on_close {
+                            ctx.link().send_message(Msg::Load);
+                        }
+                        return false;
+                    }
+                }
+
                 if reload_data {
                     ctx.link().send_message(Msg::Load);
                 }
@@ -541,7 +762,20 @@ impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let main_view = self.state.main_view(ctx);
+        let placeholder = match self.state.loaded {
+            false if self.state.loading > 0 || self.state.retry_timeout.is_some() => {
+                self.state.loading_view(ctx)
+            }
+            _ => None,
+        };
+        let main_view = placeholder.unwrap_or_else(|| self.state.main_view(ctx));
+
+        self.state
+            .shortcut_target
+            .set(match &self.state.view_state {
+                ViewState::Main if self.state.visible => ShortcutTarget::Main,
+                _ => ShortcutTarget::Inactive,
+            });
 
         let dialog: Option<Html> =
             match &self.state.view_state {
@@ -614,3 +848,18 @@ impl<L: LoadableComponent + 'static> Component for LoadableComponentMaster<L> {
         self.state.rendered(ctx, first_render);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::LoadRetry;
+
+    #[test]
+    fn test_load_retry_delay() {
+        let retry = LoadRetry::default();
+        assert_eq!(retry.delay(0), 1000);
+        assert_eq!(retry.delay(1), 2000);
+        assert_eq!(retry.delay(2), 4000);
+        assert_eq!(retry.delay(5), 30_000);
+        assert_eq!(retry.delay(40), 30_000);
+    }
+}
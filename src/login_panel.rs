@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::OnceLock;
 
+use serde_json::Value;
+
 use pwt::css::ColorScheme;
 use pwt::props::PwtSpace;
 use pwt::state::PersistentState;
@@ -10,8 +12,11 @@ use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::prelude::*;
 use yew::virtual_dom::{VComp, VNode};
 
-use pwt::widget::form::{Checkbox, Field, Form, FormContext, InputType, ResetButton, SubmitButton};
-use pwt::widget::{Column, FieldLabel, InputPanel, LanguageSelector, Mask, Row};
+use pwt::state::Store;
+use pwt::widget::form::{
+    Checkbox, Combobox, Field, Form, FormContext, InputType, ResetButton, SubmitButton, ValidateFn,
+};
+use pwt::widget::{Column, Container, Fa, FieldLabel, InputPanel, LanguageSelector, Mask, Row};
 use pwt::{prelude::*, AsyncPool};
 
 use proxmox_login::api::CreateTicketResponse;
@@ -19,12 +24,15 @@ use proxmox_login::{Authentication, SecondFactorChallenge, Ticket, TicketResult}
 
 use crate::common_api_types::BasicRealmInfo;
 use crate::utils;
-use crate::{tfa::TfaDialog, RealmSelector};
+use crate::{tfa::TfaDialog, Markdown, RealmSelector};
 
 use pwt_macros::builder;
 
 static OPENID_LOGIN: OnceLock<()> = OnceLock::new();
 
+/// Number of user names remembered for autocompletion.
+const MAX_SAVED_USERNAMES: usize = 5;
+
 /// Proxmox login panel
 ///
 /// Should support all proxmox product and TFA.
@@ -65,6 +73,27 @@ pub struct LoginPanel {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or("/access/domains".into())]
     pub domain_path: AttrValue,
+
+    /// Consent banner text (Markdown).
+    ///
+    /// If set, users need to agree to it before they can log in.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub consent_text: Option<AttrValue>,
+
+    /// URL to load the consent banner text from.
+    ///
+    /// Needs to be accessible without authentication. The response is either the text
+    /// itself or an object with a `consent-text` property. Only used if `consent_text` is
+    /// not set.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub consent_url: Option<AttrValue>,
+
+    /// Static message shown above the login form, e.g. a maintenance notice.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub message: Option<Html>,
 }
 
 impl Default for LoginPanel {
@@ -94,6 +123,8 @@ pub enum Msg {
     UpdateRealm(BasicRealmInfo),
     OpenIDLogin,
     OpenIDAuthorization(HashMap<String, String>),
+    ConsentText(Option<String>),
+    AcceptConsent(bool),
 }
 
 pub struct ProxmoxLoginPanel {
@@ -103,11 +134,130 @@ pub struct ProxmoxLoginPanel {
     challenge: Option<Rc<SecondFactorChallenge>>,
     save_username: PersistentState<bool>,
     last_username: PersistentState<String>,
+    saved_usernames: PersistentState<Vec<String>>,
     async_pool: AsyncPool,
     selected_realm: Option<BasicRealmInfo>,
+    loaded_consent_text: Option<String>,
+    consent_accepted: bool,
+}
+
+/// Add `userid` to the front of the list of saved user names.
+fn remember_username(list: &mut Vec<String>, userid: &str) {
+    list.retain(|item| item != userid);
+    list.insert(0, userid.to_string());
+    list.truncate(MAX_SAVED_USERNAMES);
+}
+
+fn validate_username(value: &str, realm_selectable: bool) -> Result<(), anyhow::Error> {
+    if realm_selectable {
+        return Ok(());
+    } else if let Some((user, realm)) = value.rsplit_once('@') {
+        if !user.is_empty() && !realm.is_empty() {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("{}", tr!("invalid username"));
+}
+
+/// Extract the consent text from the API response.
+fn extract_consent_text(data: &Value) -> Option<String> {
+    let text = match data {
+        Value::String(text) => text.as_str(),
+        Value::Object(_) => data["consent-text"].as_str()?,
+        _ => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
 }
 
 impl ProxmoxLoginPanel {
+    fn load_consent_text(&self, ctx: &Context<Self>, url: AttrValue) {
+        let link = ctx.link().clone();
+        self.async_pool.spawn(async move {
+            match crate::http_get::<Value>(url.as_str(), None).await {
+                Ok(data) => link.send_message(Msg::ConsentText(extract_consent_text(&data))),
+                Err(err) => log::error!("could not load consent text: {err}"),
+            }
+        });
+    }
+
+    fn consent_text(&self, props: &LoginPanel) -> Option<String> {
+        props
+            .consent_text
+            .as_ref()
+            .map(|text| text.to_string())
+            .or_else(|| self.loaded_consent_text.clone())
+    }
+
+    /// Saved user names for autocompletion.
+    ///
+    /// Without realm selector the full user ID is used, else only the user name part.
+    fn username_items(&self, props: &LoginPanel) -> Vec<AttrValue> {
+        let mut items: Vec<AttrValue> = Vec::new();
+        for userid in self.saved_usernames.iter() {
+            let item = match (props.realm_selectable, userid.rsplit_once('@')) {
+                (true, Some((user, _realm))) => user,
+                _ => userid.as_str(),
+            };
+            if !items.iter().any(|i| i == item) {
+                items.push(item.to_string().into());
+            }
+        }
+        items
+    }
+
+    /// Returns true if there is a consent banner the user did not agree to yet.
+    fn consent_missing(&self, props: &LoginPanel) -> bool {
+        !self.consent_accepted && self.consent_text(props).is_some()
+    }
+
+    fn render_message(&self, props: &LoginPanel) -> Option<Html> {
+        props.message.clone().map(|message| {
+            Row::new()
+                .class(pwt::css::AlignItems::Center)
+                .gap(2)
+                .padding(2)
+                .class("pwt-border-bottom")
+                .with_child(Fa::new("info-circle").large())
+                .with_child(message)
+                .into()
+        })
+    }
+
+    fn render_consent(&self, ctx: &Context<Self>) -> Option<Html> {
+        let text = self.consent_text(ctx.props())?;
+
+        let label_id = pwt::widget::get_unique_element_id();
+
+        let consent = Column::new()
+            .gap(2)
+            .padding(2)
+            .with_child(
+                Container::new()
+                    .class("pwt-embedded-html pwt-border")
+                    .padding(2)
+                    .style("max-height", "200px")
+                    .class(pwt::css::Overflow::Auto)
+                    .with_child(Markdown::new().text(text)),
+            )
+            .with_child(
+                Row::new()
+                    .class(pwt::css::AlignItems::Center)
+                    .with_child(
+                        Checkbox::new()
+                            .margin_end(1)
+                            .label_id(label_id.clone())
+                            .checked(self.consent_accepted)
+                            .on_change(ctx.link().callback(Msg::AcceptConsent)),
+                    )
+                    .with_child(html! {
+                        <label id={label_id} style="user-select:none;">{tr!("I agree")}</label>
+                    }),
+            );
+
+        Some(consent.into())
+    }
+
     fn send_login(&self, ctx: &Context<Self>, username: String, password: String, realm: String) {
         let link = ctx.link().clone();
         self.async_pool.spawn(async move {
@@ -252,6 +402,42 @@ impl ProxmoxLoginPanel {
         (default_username, default_realm)
     }
 
+    fn mobile_username_field(
+        &self,
+        props: &LoginPanel,
+        label_id: String,
+        default_username: String,
+    ) -> Html {
+        let realm_selectable = props.realm_selectable;
+        let items = self.username_items(props);
+
+        if items.is_empty() {
+            return Field::new()
+                .name("username")
+                .label_id(label_id)
+                .default(default_username)
+                .required(true)
+                .validate(move |value: &String| validate_username(value, realm_selectable))
+                .autofocus(true)
+                .into();
+        }
+
+        Combobox::new()
+            .name("username")
+            .label_id(label_id)
+            .default(AttrValue::from(default_username))
+            .required(true)
+            .editable(true)
+            .items(Rc::new(items))
+            .validate(ValidateFn::new(
+                move |(value, _store): &(String, Store<AttrValue>)| {
+                    validate_username(value, realm_selectable)
+                },
+            ))
+            .autofocus(true)
+            .into()
+    }
+
     fn mobile_view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
         let link = ctx.link().clone();
@@ -275,6 +461,7 @@ impl ProxmoxLoginPanel {
         let mut form_panel = Column::new()
             .class(pwt::css::FlexFit)
             .padding(2)
+            .with_optional_child(self.render_message(props))
             .with_flex_spacer();
 
         if self
@@ -289,27 +476,7 @@ impl ProxmoxLoginPanel {
                         .id(username_label_id.clone())
                         .padding_bottom(PwtSpace::Em(0.25)),
                 )
-                .with_child(
-                    Field::new()
-                        .name("username")
-                        .label_id(username_label_id)
-                        .default(default_username)
-                        .required(true)
-                        .validate({
-                            let realm_selectable = props.realm_selectable;
-                            move |value: &String| {
-                                if realm_selectable {
-                                    return Ok(());
-                                } else if let Some((user, realm)) = value.rsplit_once('@') {
-                                    if !user.is_empty() && !realm.is_empty() {
-                                        return Ok(());
-                                    }
-                                }
-                                anyhow::bail!("{}", tr!("invalid username"));
-                            }
-                        })
-                        .autofocus(true),
-                )
+                .with_child(self.mobile_username_field(props, username_label_id, default_username))
                 .with_child(
                     FieldLabel::new(tr!("Password"))
                         .id(password_label_id.clone())
@@ -325,7 +492,10 @@ impl ProxmoxLoginPanel {
                 );
         }
 
-        let submit_button = SubmitButton::new().class(ColorScheme::Primary).margin_y(4);
+        let submit_button = SubmitButton::new()
+            .class(ColorScheme::Primary)
+            .margin_y(4)
+            .disabled(self.consent_missing(props));
 
         let submit_button = if self
             .selected_realm
@@ -361,6 +531,7 @@ impl ProxmoxLoginPanel {
                     })
                     .default(default_realm),
             ))
+            .with_optional_child(self.render_consent(ctx))
             .with_child(submit_button)
             .with_optional_child(self.login_error.as_ref().map(|msg| {
                 let icon_class = classes!("fa-lg", "fa", "fa-align-center", "fa-exclamation-triangle");
@@ -459,7 +630,9 @@ impl ProxmoxLoginPanel {
             .with_child(html! {<label id={save_username_label_id} style="user-select:none;">{tr!("Save User name")}</label>})
             .with_child(save_username_field);
 
-        let submit_button = SubmitButton::new().class(ColorScheme::Primary);
+        let submit_button = SubmitButton::new()
+            .class(ColorScheme::Primary)
+            .disabled(self.consent_missing(props));
 
         let submit_button = if self
             .selected_realm
@@ -490,7 +663,9 @@ impl ProxmoxLoginPanel {
 
         let form_panel = Column::new()
             .class(pwt::css::FlexFit)
+            .with_optional_child(self.render_message(props))
             .with_child(input_panel)
+            .with_optional_child(self.render_consent(ctx))
             .with_optional_child(tfa_dialog)
             .with_optional_child(self.login_error.as_ref().map(|msg| {
                 pwt::widget::error_message(&tr!("Login failed. Please try again ({0})", msg))
@@ -521,21 +696,33 @@ impl Component for ProxmoxLoginPanel {
 
         let save_username = PersistentState::<bool>::new("ProxmoxLoginPanelSaveUsername");
         let last_username = PersistentState::<String>::new("ProxmoxLoginPanelUsername");
+        let saved_usernames =
+            PersistentState::<Vec<String>>::new("ProxmoxLoginPanelSavedUsernames");
 
         if let Some(auth) = utils::openid_redirection_authorization() {
             ctx.link().send_message(Msg::OpenIDAuthorization(auth));
         }
 
-        Self {
+        let this = Self {
             form_ctx,
             loading: false,
             login_error: None,
             challenge: None,
             save_username,
             last_username,
+            saved_usernames,
             async_pool: AsyncPool::new(),
             selected_realm: None,
+            loaded_consent_text: None,
+            consent_accepted: false,
+        };
+
+        let props = ctx.props();
+        if let (None, Some(url)) = (&props.consent_text, &props.consent_url) {
+            this.load_consent_text(ctx, url.clone());
         }
+
+        this
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -633,6 +820,9 @@ impl Component for ProxmoxLoginPanel {
                 true
             }
             Msg::Submit => {
+                if self.consent_missing(props) {
+                    return false;
+                }
                 self.loading = true;
 
                 let password = self.form_ctx.read().get_field_text("password");
@@ -659,6 +849,9 @@ impl Component for ProxmoxLoginPanel {
                 self.loading = false;
                 if props.mobile || *self.save_username {
                     self.last_username.update(info.userid.clone());
+                    let mut saved_usernames = (*self.saved_usernames).clone();
+                    remember_username(&mut saved_usernames, &info.userid);
+                    self.saved_usernames.update(saved_usernames);
                 }
                 if let Some(on_login) = &props.on_login {
                     on_login.emit(info);
@@ -683,6 +876,9 @@ impl Component for ProxmoxLoginPanel {
                 true
             }
             Msg::OpenIDLogin => {
+                if self.consent_missing(props) {
+                    return false;
+                }
                 self.loading = true;
                 self.openid_redirect(ctx);
                 false
@@ -692,6 +888,14 @@ impl Component for ProxmoxLoginPanel {
                 self.openid_login(ctx, auth);
                 false
             }
+            Msg::ConsentText(text) => {
+                self.loaded_consent_text = text;
+                true
+            }
+            Msg::AcceptConsent(accepted) => {
+                self.consent_accepted = accepted;
+                true
+            }
         }
     }
 
@@ -710,3 +914,36 @@ impl From<LoginPanel> for VNode {
         VNode::from(comp)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_remember_username() {
+        let mut list = Vec::new();
+        for userid in [
+            "a@pam", "b@pam", "a@pam", "c@pve", "d@pam", "e@pam", "f@pam",
+        ] {
+            remember_username(&mut list, userid);
+        }
+        assert_eq!(list, ["f@pam", "e@pam", "d@pam", "c@pve", "a@pam"]);
+    }
+
+    #[test]
+    fn test_extract_consent_text() {
+        assert_eq!(
+            extract_consent_text(&json!(" Authorized use only. \n")),
+            Some("Authorized use only.".to_string())
+        );
+        assert_eq!(
+            extract_consent_text(&json!({ "consent-text": "Authorized use only." })),
+            Some("Authorized use only.".to_string())
+        );
+        assert_eq!(extract_consent_text(&json!("")), None);
+        assert_eq!(extract_consent_text(&json!({})), None);
+        assert_eq!(extract_consent_text(&Value::Null), None);
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
 use yew::html::IntoPropValue;
 
 use pwt::{prelude::*, widget::Container};
@@ -112,6 +112,170 @@ impl Slugger {
     }
 }
 
+/// Lexical rules of a language supported by [highlight_code].
+struct Syntax {
+    line_comments: &'static [&'static str],
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+#[rustfmt::skip]
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while",
+];
+
+#[rustfmt::skip]
+const JS_KEYWORDS: &[&str] = &[
+    "async", "await", "break", "case", "catch", "class", "const", "continue", "default",
+    "delete", "do", "else", "export", "extends", "false", "finally", "for", "function", "if",
+    "import", "in", "instanceof", "let", "new", "null", "return", "switch", "this", "throw",
+    "true", "try", "typeof", "undefined", "var", "void", "while", "yield",
+];
+
+#[rustfmt::skip]
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+    "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+    "while", "with", "yield",
+];
+
+#[rustfmt::skip]
+const SHELL_KEYWORDS: &[&str] = &[
+    "case", "do", "done", "elif", "else", "esac", "exit", "export", "fi", "for", "function",
+    "if", "in", "local", "return", "then", "until", "while",
+];
+
+#[rustfmt::skip]
+const PERL_KEYWORDS: &[&str] = &[
+    "die", "else", "elsif", "eval", "for", "foreach", "if", "last", "local", "my", "next",
+    "our", "package", "return", "sub", "unless", "until", "use", "while",
+];
+
+const JSON_KEYWORDS: &[&str] = &["false", "null", "true"];
+
+fn syntax_for_language(lang: &str) -> Option<Syntax> {
+    let syntax = match lang.to_lowercase().as_str() {
+        "rust" | "rs" => Syntax {
+            line_comments: &["//"],
+            quotes: &['"'],
+            keywords: RUST_KEYWORDS,
+        },
+        "javascript" | "js" | "typescript" | "ts" => Syntax {
+            line_comments: &["//"],
+            quotes: &['"', '\'', '`'],
+            keywords: JS_KEYWORDS,
+        },
+        "python" | "py" => Syntax {
+            line_comments: &["#"],
+            quotes: &['"', '\''],
+            keywords: PYTHON_KEYWORDS,
+        },
+        "sh" | "bash" | "shell" | "console" => Syntax {
+            line_comments: &["#"],
+            quotes: &['"', '\''],
+            keywords: SHELL_KEYWORDS,
+        },
+        "perl" | "pl" => Syntax {
+            line_comments: &["#"],
+            quotes: &['"', '\''],
+            keywords: PERL_KEYWORDS,
+        },
+        "json" => Syntax {
+            line_comments: &[],
+            quotes: &['"'],
+            keywords: JSON_KEYWORDS,
+        },
+        "yaml" | "yml" | "ini" | "conf" | "toml" => Syntax {
+            line_comments: &["#", ";"],
+            quotes: &['"', '\''],
+            keywords: JSON_KEYWORDS,
+        },
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+fn push_token(out: &mut String, class: &str, text: &str) {
+    out.push_str(&format!("<span class=\"pmx-hl-{class}\">"));
+    push_escaped(out, text);
+    out.push_str("</span>");
+}
+
+/// Highlight a code block by wrapping comments, strings, numbers and keywords in `<span>`
+/// elements with `pmx-hl-*` classes. Returns escaped HTML.
+///
+/// This is a simple line based tokenizer, good enough for the short snippets found in notes.
+fn highlight_code(code: &str, syntax: &Syntax) -> String {
+    let mut out = String::new();
+    let mut rest = code;
+
+    while let Some(c) = rest.chars().next() {
+        if syntax
+            .line_comments
+            .iter()
+            .any(|start| rest.starts_with(start))
+        {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            push_token(&mut out, "comment", &rest[..end]);
+            rest = &rest[end..];
+        } else if syntax.quotes.contains(&c) {
+            let mut end = rest.len();
+            let mut escaped = false;
+            for (i, ch) in rest.char_indices().skip(1) {
+                if ch == '\n' {
+                    end = i;
+                    break;
+                }
+                if ch == c && !escaped {
+                    end = i + ch.len_utf8();
+                    break;
+                }
+                escaped = ch == '\\' && !escaped;
+            }
+            push_token(&mut out, "string", &rest[..end]);
+            rest = &rest[end..];
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '_'))
+                .unwrap_or(rest.len());
+            push_token(&mut out, "number", &rest[..end]);
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if syntax.keywords.contains(&word) {
+                push_token(&mut out, "keyword", word);
+            } else {
+                push_escaped(&mut out, word);
+            }
+            rest = &rest[end..];
+        } else {
+            let len = c.len_utf8();
+            push_escaped(&mut out, &rest[..len]);
+            rest = &rest[len..];
+        }
+    }
+    out
+}
+
 /// Render markdown to (still untrusted) HTML, with auto-derived heading ids. Mirrors marked's
 /// `headerIds: true` on the JS side so fragment links like `[link](#section)` resolve to the
 /// matching heading; the sanitizer then namespaces both the heading id and the link href with the
@@ -124,9 +288,37 @@ fn markdown_to_unsanitized_html(text: &str) -> String {
     let mut heading_start: Option<usize> = None;
     let mut heading_text = String::new();
     let mut slugger = Slugger::new();
+    // fenced code block with a supported language: (language, syntax, collected code)
+    let mut code_block: Option<(String, Syntax, String)> = None;
 
     for event in parser {
+        if let Some((lang, syntax, code)) = code_block.as_mut() {
+            match &event {
+                Event::Text(text) => code.push_str(text),
+                Event::End(TagEnd::CodeBlock) => {
+                    let mut html = format!("<pre><code class=\"language-{lang}\">");
+                    html.push_str(&highlight_code(code, syntax));
+                    html.push_str("</code></pre>\n");
+                    events.push(Event::Html(CowStr::from(html)));
+                    code_block = None;
+                }
+                _ => {}
+            }
+            continue;
+        }
+
         match &event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+                // only use the first word of the info string, as pulldown-cmark does
+                let lang = lang.split_whitespace().next().unwrap_or("");
+                let valid_name = lang
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                if let Some(syntax) = syntax_for_language(lang).filter(|_| valid_name) {
+                    code_block = Some((lang.to_string(), syntax, String::new()));
+                    continue;
+                }
+            }
             Event::Start(Tag::Heading { id, .. }) => {
                 if let Some(explicit) = id {
                     // An explicit `{#anchor}` does not need an auto-id, but reserve it in the
@@ -295,4 +487,63 @@ fn main() {}
         assert!(html.contains("<ul>"), "{html}");
         assert!(html.contains("<pre><code>fn main() {}"), "{html}");
     }
+
+    #[test]
+    fn highlight_code_tokens() {
+        let syntax = syntax_for_language("rust").unwrap();
+        assert_eq!(
+            highlight_code("let x = \"a<b\"; // 42", &syntax),
+            "<span class=\"pmx-hl-keyword\">let</span> x = \
+             <span class=\"pmx-hl-string\">&quot;a&lt;b&quot;</span>; \
+             <span class=\"pmx-hl-comment\">// 42</span>"
+        );
+
+        let syntax = syntax_for_language("sh").unwrap();
+        assert_eq!(
+            highlight_code("echo 'it\\'s' 10", &syntax),
+            "echo <span class=\"pmx-hl-string\">'it\\'s'</span> \
+             <span class=\"pmx-hl-number\">10</span>"
+        );
+
+        assert!(syntax_for_language("brainfuck").is_none());
+    }
+
+    /// Tables, task lists and fenced code blocks, as used by notes written in the ExtJS UI.
+    #[test]
+    fn markdown_to_unsanitized_html_renders_gfm_elements() {
+        let md = "\
+| Name | Value |
+|------|-------|
+| a    | 1     |
+
+- [x] done
+- [ ] todo
+
+```bash
+# comment
+```
+
+```unknown
+<b>
+```
+";
+        let html = markdown_to_unsanitized_html(md);
+        assert!(html.contains("<table>"), "{html}");
+        assert!(html.contains("<th>Name</th>"), "{html}");
+        assert!(html.contains("<td>1</td>"), "{html}");
+        assert!(
+            html.contains(r#"<input disabled="" type="checkbox" checked=""/>"#),
+            "{html}"
+        );
+        assert!(
+            html.contains(
+                r#"<pre><code class="language-bash"><span class="pmx-hl-comment"># comment</span>"#
+            ),
+            "{html}"
+        );
+        assert!(
+            html.contains(r#"<pre><code class="language-unknown">&lt;b&gt;"#),
+            "{html}"
+        );
+    }
 }
@@ -1,12 +1,17 @@
-use pwt::css::AlignItems;
-use yew::html::IntoPropValue;
+use gloo_timers::callback::Interval;
+use pwt::css::{AlignItems, FontColor};
+use yew::html::{IntoEventCallback, IntoPropValue};
 
 use pwt::prelude::*;
-use pwt::widget::{Container, Meter};
+use pwt::widget::{Container, Dialog, Meter};
 
 use pwt_macros::{builder, widget};
 
-use crate::StatusRow;
+use crate::utils::activatable;
+use crate::{Status, StatusRow};
+
+const ANIMATION_DURATION_MS: f64 = 400.0;
+const ANIMATION_STEP_MS: u32 = 20;
 
 #[widget(comp=ProxmoxMeterLabel, @element)]
 #[derive(Properties, Clone, PartialEq)]
@@ -67,14 +72,35 @@ pub struct MeterLabel {
     pub status: Option<Html>,
 
     /// If the underlying Meter should be animated
+    ///
+    /// Value changes are also animated smoothly (bar and percentage).
     #[prop_or_default]
     #[builder]
     pub animated: bool,
 
+    /// Warning threshold, values above are shown in warning color.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub warning: Option<f32>,
+
+    /// Critical threshold, values above are shown in error color.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub critical: Option<f32>,
+
+    /// Details, shown in a dialog when the label is activated.
+    #[prop_or_default]
+    pub details: Option<Html>,
+
     /// If set to `true`, places the icon on the right side instead of the left
     #[prop_or_default]
     #[builder]
     pub icon_right: bool,
+
+    /// Activation callback, called when the label is clicked or activated by keyboard.
+    #[prop_or_default]
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    pub on_activate: Option<Callback<()>>,
 }
 
 impl MeterLabel {
@@ -105,51 +131,220 @@ impl MeterLabel {
         self.status = Some(status.into());
         self
     }
+
+    /// Builder style method to set the warning and critical thresholds.
+    pub fn thresholds(mut self, warning: f32, critical: f32) -> Self {
+        self.warning = Some(warning);
+        self.critical = Some(critical);
+        self
+    }
+
+    /// Builder style method to set the details dialog content.
+    pub fn details(mut self, details: impl Into<Html>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+/// Returns the status for a value, given the (optional) thresholds.
+pub fn threshold_status(value: f32, warning: Option<f32>, critical: Option<f32>) -> Status {
+    match (warning, critical) {
+        (_, Some(critical)) if value >= critical => Status::Error,
+        (Some(warning), _) if value >= warning => Status::Warning,
+        _ => Status::Success,
+    }
+}
+
+// ease out cubic, `t` is the elapsed part of the animation (0 to 1)
+fn ease_out(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
 }
 
 #[doc(hidden)]
-pub struct ProxmoxMeterLabel {}
+pub enum Msg {
+    Tick,
+    ShowDetails(bool),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxMeterLabel {
+    // the value currently shown, differs from the property while animating
+    displayed: Option<f32>,
+    animation_start: Option<(f32, f64)>,
+    animation: Option<Interval>,
+    show_details: bool,
+}
 
 impl Component for ProxmoxMeterLabel {
-    type Message = ();
+    type Message = Msg;
     type Properties = MeterLabel;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self {}
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            displayed: ctx.props().value,
+            animation_start: None,
+            animation: None,
+            show_details: false,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.value != old_props.value {
+            match (self.displayed, props.value) {
+                (Some(displayed), Some(_)) if props.animated => {
+                    self.animation_start = Some((displayed, js_sys::Date::now()));
+                    let link = ctx.link().clone();
+                    self.animation = Some(Interval::new(ANIMATION_STEP_MS, move || {
+                        link.send_message(Msg::Tick)
+                    }));
+                }
+                _ => {
+                    self.displayed = props.value;
+                    self.animation_start = None;
+                    self.animation = None;
+                }
+            }
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Tick => {
+                let (Some((start, start_time)), Some(target)) =
+                    (self.animation_start, ctx.props().value)
+                else {
+                    self.animation = None;
+                    return false;
+                };
+                let t = (js_sys::Date::now() - start_time) / ANIMATION_DURATION_MS;
+                self.displayed = Some(start + (target - start) * ease_out(t) as f32);
+                if t >= 1.0 {
+                    self.animation_start = None;
+                    self.animation = None;
+                }
+                true
+            }
+            Msg::ShowDetails(show) => {
+                self.show_details = show;
+                true
+            }
+        }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
 
+        let threshold_class: Classes = match self
+            .displayed
+            .map(|value| threshold_status(value, props.warning, props.critical))
+        {
+            Some(Status::Error) => FontColor::Error.into(),
+            Some(Status::Warning) => FontColor::Warning.into(),
+            _ => Classes::new(),
+        };
+
         let status = match &props.status {
             Some(text) => text.clone(),
-            None => match props.value {
-                Some(value) => html! {format!("{:.2} %", value * 100.0)},
+            None => match self.displayed {
+                Some(value) => html! {
+                    <span class={threshold_class}>
+                        {format!("{:.2} %", value * 100.0)}
+                    </span>
+                },
                 None => html! {},
             },
         };
 
+        // thresholds map to the meter ranges, so that the bar color matches
+        let has_thresholds = props.warning.is_some() || props.critical.is_some();
+        let (low, high, optimum) = if has_thresholds {
+            (
+                props.warning.or(props.critical),
+                props.critical.or(props.warning),
+                Some(props.min),
+            )
+        } else {
+            (props.low, props.high, props.optimum)
+        };
+
         let text_row = StatusRow::new(props.title.clone())
             .class(AlignItems::Baseline)
             .status(status)
             .icon_class(props.icon_class.clone())
             .icon_right(props.icon_right);
 
-        Container::new()
+        let container = Container::new()
             .with_std_props(&props.std_props)
             .listeners(&props.listeners)
             .with_child(text_row)
-            .with_optional_child(props.value.map(|value| {
+            .with_optional_child(self.displayed.map(|value| {
                 Meter::new()
                     .margin_top(1)
                     .value(value)
                     .min(props.min)
                     .max(props.max)
-                    .low(props.low)
-                    .high(props.high)
-                    .optimum(props.optimum)
+                    .low(low)
+                    .high(high)
+                    .optimum(optimum)
                     .animated(props.animated)
-            }))
-            .into()
+            }));
+
+        let on_activate = match (&props.details, &props.on_activate) {
+            (None, on_activate) => on_activate.clone(),
+            (Some(_), on_activate) => {
+                let link = ctx.link().clone();
+                let on_activate = on_activate.clone();
+                Some(Callback::from(move |()| {
+                    link.send_message(Msg::ShowDetails(true));
+                    if let Some(on_activate) = &on_activate {
+                        on_activate.emit(());
+                    }
+                }))
+            }
+        };
+
+        let details = match &props.details {
+            Some(details) if self.show_details => Some(
+                Dialog::new(props.title.clone())
+                    .min_width(400)
+                    .on_close(ctx.link().callback(|_| Msg::ShowDetails(false)))
+                    .with_child(details.clone()),
+            ),
+            _ => None,
+        };
+
+        html! {
+            <>
+                {activatable(container, on_activate.as_ref())}
+                {details}
+            </>
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_threshold_status() {
+        assert_eq!(threshold_status(0.5, Some(0.8), Some(0.9)), Status::Success);
+        assert_eq!(
+            threshold_status(0.85, Some(0.8), Some(0.9)),
+            Status::Warning
+        );
+        assert_eq!(threshold_status(0.95, Some(0.8), Some(0.9)), Status::Error);
+        assert_eq!(threshold_status(0.95, None, None), Status::Success);
+    }
+
+    #[test]
+    fn test_ease_out() {
+        assert_eq!(ease_out(0.0), 0.0);
+        assert_eq!(ease_out(1.0), 1.0);
+        assert_eq!(ease_out(2.0), 1.0);
+        assert!(ease_out(0.5) > 0.5);
     }
 }
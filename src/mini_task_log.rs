@@ -0,0 +1,155 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde::Deserialize;
+use serde_json::json;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::{error_message, Container, Progress};
+use pwt::AsyncAbortGuard;
+
+use pwt_macros::builder;
+
+use crate::percent_encoding::percent_encode_component;
+
+/// Compact view of the last lines of a task log.
+///
+/// Used to expand task list rows inline, which is much faster than opening the
+/// [TaskViewer](crate::TaskViewer) for checking why a task failed. The log is loaded when the
+/// component gets created.
+#[derive(Properties, PartialEq, Clone)]
+#[builder]
+pub struct MiniTaskLog {
+    /// The task UPID.
+    pub task_id: AttrValue,
+
+    #[prop_or("/nodes/localhost/tasks".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the task API.
+    pub base_url: AttrValue,
+
+    /// Number of log lines to show.
+    #[prop_or(20)]
+    #[builder]
+    pub lines: u64,
+}
+
+impl MiniTaskLog {
+    pub fn new(task_id: impl Into<AttrValue>) -> Self {
+        yew::props!(Self {
+            task_id: task_id.into(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct LogEntry {
+    t: String,
+}
+
+async fn load_log_tail(url: String, lines: u64) -> Result<Vec<String>, Error> {
+    // first query the number of lines, the API has no way to request the tail directly
+    let resp = crate::http_get_full::<Vec<LogEntry>>(&url, Some(json!({ "start": 0, "limit": 1 })))
+        .await?;
+    let total = resp
+        .attribs
+        .get("total")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(resp.data.len() as u64);
+
+    let start = total.saturating_sub(lines);
+    let data: Vec<LogEntry> =
+        crate::http_get(&url, Some(json!({ "start": start, "limit": lines }))).await?;
+
+    Ok(data.into_iter().map(|entry| entry.t).collect())
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    LoadResult(Result<Vec<String>, Error>),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxMiniTaskLog {
+    data: Option<Result<Vec<String>, String>>,
+    _load_guard: Option<AsyncAbortGuard>,
+}
+
+impl ProxmoxMiniTaskLog {
+    fn load(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let url = format!(
+            "{}/{}/log",
+            props.base_url,
+            percent_encode_component(&props.task_id),
+        );
+        let lines = props.lines;
+        let link = ctx.link().clone();
+        self.data = None;
+        self._load_guard = Some(AsyncAbortGuard::spawn(async move {
+            let result = load_log_tail(url, lines).await;
+            link.send_message(Msg::LoadResult(result));
+        }));
+    }
+}
+
+impl Component for ProxmoxMiniTaskLog {
+    type Message = Msg;
+    type Properties = MiniTaskLog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut me = Self {
+            data: None,
+            _load_guard: None,
+        };
+        me.load(ctx);
+        me
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadResult(result) => {
+                self.data = Some(result.map_err(|err| err.to_string()));
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props() != old_props {
+            self.load(ctx);
+        }
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let content = match &self.data {
+            None => Progress::new().class("pwt-delay-visibility").into(),
+            Some(Err(err)) => error_message(err).into(),
+            Some(Ok(lines)) if lines.is_empty() => html! {tr!("No log lines")},
+            Some(Ok(lines)) => html! {
+                <pre class="pwt-font-monospace" style="margin:0;white-space:pre-wrap;">
+                    {lines.join("\n")}
+                </pre>
+            },
+        };
+
+        Container::new()
+            .class("pwt-border-top")
+            .padding_y(1)
+            .style("max-height", "20em")
+            .style("overflow", "auto")
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<MiniTaskLog> for VNode {
+    fn from(val: MiniTaskLog) -> Self {
+        let comp = VComp::new::<ProxmoxMiniTaskLog>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -1,8 +1,10 @@
 use proxmox_human_byte::HumanByte;
 use proxmox_node_status::BootMode;
 use pwt::{prelude::*, widget::Container};
+use serde_json::Value;
 
-use crate::{MeterLabel, StatusRow};
+use crate::utils::value_to_boolean;
+use crate::{MeterLabel, Status, StatusRow};
 
 /// Type that holds either a PVE NodeStatus or a PBS NodeStatus
 pub enum NodeStatus<'a> {
@@ -23,6 +25,44 @@ impl<'a> From<&'a pbs_api_types::NodeStatus> for NodeStatus<'a> {
     }
 }
 
+impl NodeStatus<'_> {
+    // the status as returned by the API, including fields unknown to the typed structs
+    fn to_value(&self) -> Value {
+        let value = match self {
+            NodeStatus::Pve(node_status) => serde_json::to_value(node_status),
+            NodeStatus::Pbs(node_status) => serde_json::to_value(node_status),
+            NodeStatus::Common(node_status) => serde_json::to_value(node_status),
+        };
+        value.unwrap_or_default()
+    }
+}
+
+// boot mode from the `boot-info` property, returns (efi, secureboot)
+fn boot_mode_info(raw: &Value) -> Option<(bool, bool)> {
+    let boot_info = &raw["boot-info"];
+    let efi = match boot_info["mode"].as_str()? {
+        "efi" => true,
+        "legacy-bios" => false,
+        _ => return None,
+    };
+    let secureboot = value_to_boolean(&boot_info["secureboot"]).unwrap_or(false);
+    Some((efi, secureboot))
+}
+
+// hardware virtualization support (`cpuinfo.hvm`), only reported by PVE
+fn cpu_virtualization_row(raw: &Value) -> Option<StatusRow> {
+    let hvm = value_to_boolean(&raw["cpuinfo"]["hvm"])?;
+    let row = StatusRow::new(tr!("CPU Virtualization"));
+    let icon_class = |status: Status| classes!("fa", "fa-fw", status);
+    Some(if hvm {
+        row.icon_class(icon_class(Status::Success))
+            .status(tr!("Supported"))
+    } else {
+        row.icon_class(icon_class(Status::Warning))
+            .status(tr!("Not supported, or disabled in the firmware"))
+    })
+}
+
 /// Renders the NodeInfo panel content
 // TODO: add repository status
 // NOTE: if we need internal state or the tree get's too big, we should convert this
@@ -148,10 +188,17 @@ pub fn node_info(data: Option<NodeStatus>) -> Container {
 
     let build_date = k_version.split(['(', ')']).nth(1).unwrap_or("unknown");
 
-    let boot_mode = if let Some(NodeStatus::Common(node_status)) = data {
-        Some(&node_status.boot_info)
-    } else {
-        None
+    let raw = data
+        .as_ref()
+        .map(|data| data.to_value())
+        .unwrap_or_default();
+
+    let boot_mode = match data {
+        Some(NodeStatus::Common(node_status)) => Some((
+            matches!(node_status.boot_info.mode, BootMode::Efi),
+            node_status.boot_info.secureboot,
+        )),
+        _ => boot_mode_info(&raw),
     };
 
     Container::new()
@@ -255,14 +302,33 @@ pub fn node_info(data: Option<NodeStatus>) -> Container {
                 .style("grid-column", "1/-1")
                 .status(format!("{k_sysname} {k_release} ({build_date})")),
         )
-        .with_optional_child(boot_mode.map(|m| {
-            let mode = match m.mode {
-                BootMode::LegacyBios => tr!("Legacy BIOS"),
-                BootMode::Efi if m.secureboot => tr!("UEFI (Secure Boot Enabled)"),
-                BootMode::Efi => tr!("UEFI"),
+        .with_optional_child(boot_mode.map(|(efi, secureboot)| {
+            let mode = match (efi, secureboot) {
+                (false, _) => tr!("Legacy BIOS"),
+                (true, true) => tr!("UEFI (Secure Boot Enabled)"),
+                (true, false) => tr!("UEFI"),
             };
             StatusRow::new(tr!("Boot Mode"))
                 .style("grid-column", "1/-1")
                 .status(mode)
         }))
+        .with_optional_child(
+            cpu_virtualization_row(&raw).map(|row| row.style("grid-column", "1/-1")),
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_boot_mode_info() {
+        let raw = json!({ "boot-info": { "mode": "efi", "secureboot": 1 } });
+        assert_eq!(boot_mode_info(&raw), Some((true, true)));
+        let raw = json!({ "boot-info": { "mode": "legacy-bios" } });
+        assert_eq!(boot_mode_info(&raw), Some((false, false)));
+        assert_eq!(boot_mode_info(&json!({})), None);
+    }
 }
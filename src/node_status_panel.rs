@@ -4,20 +4,19 @@ use std::rc::Rc;
 use anyhow::Error;
 use html::IntoPropValue;
 use pwt::css::{AlignItems, ColorScheme, FlexFit};
-use pwt::widget::form::DisplayField;
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
-use pwt::widget::{error_message, Fa, Panel, Row, Tooltip};
+use pwt::widget::{error_message, Fa, Panel, Row};
 use pwt::widget::{Button, Dialog};
 use pwt_macros::builder;
 
 use proxmox_node_status::{NodePowerCommand, NodeStatus};
 
-use crate::utils::copy_text_to_clipboard;
 use crate::{
-    http_get, http_post, node_info, ConfirmButton, LoadableComponent, LoadableComponentContext,
-    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState,
+    http_get, http_post, node_info, ConfirmButton, CopyField, LoadableComponent,
+    LoadableComponentContext, LoadableComponentMaster, LoadableComponentScopeExt,
+    LoadableComponentState,
 };
 
 #[derive(Properties, Clone, PartialEq)]
@@ -108,31 +107,16 @@ impl ProxmoxNodeStatusPanel {
     ) -> Dialog {
         let link = ctx.link().clone();
         let link_button = ctx.link().clone();
-        let fingerprint = fingerprint.to_owned();
 
         Dialog::new(tr!("Fingerprint"))
             .resizable(true)
             .min_width(500)
             .on_close(move |_| link.change_view(None))
             .with_child(
-                Row::new()
-                    .gap(2)
+                CopyField::new(fingerprint.to_owned())
                     .margin_start(2)
                     .margin_end(2)
-                    .with_child(
-                        DisplayField::new()
-                            .class(pwt::css::FlexFit)
-                            .value(fingerprint.clone())
-                            .border(true),
-                    )
-                    .with_child(
-                        Tooltip::new(
-                            Button::new_icon("fa fa-clipboard")
-                                .class(ColorScheme::Primary)
-                                .on_activate(move |_| copy_text_to_clipboard(&fingerprint)),
-                        )
-                        .tip(tr!("Copy token secret to clipboard.")),
-                    ),
+                    .tip(tr!("Copy fingerprint to clipboard.")),
             )
             .with_child(
                 Row::new()
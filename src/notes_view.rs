@@ -2,22 +2,24 @@ use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use yew::html::Scope;
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::props::{IntoSubmitCallback, SubmitCallback};
-use pwt::widget::form::{FormContext, TextArea};
-use pwt::widget::{Button, Column, Container, Toolbar};
+use pwt::widget::form::FormContext;
+use pwt::widget::{Button, Column, Container, Fa, Row, Toolbar};
 
 use proxmox_client::ApiResponseData;
 
 use crate::{
     ApiLoadCallback, EditWindow, LoadableComponent, LoadableComponentContext,
     LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState, Markdown,
+    MarkdownEditor, MarkdownViewMode, Status,
 };
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
@@ -52,6 +54,18 @@ async fn update_notes_property(
     crate::http_put(&*url, Some(param)).await
 }
 
+/// Combine the notes stored on the server with the local changes, using conflict markers so
+/// the user can resolve the differences manually.
+fn merge_notes(current: &str, mine: &str) -> String {
+    format!(
+        "<<<<<<< {}\n{}\n=======\n{}\n>>>>>>> {}\n",
+        tr!("current"),
+        current.trim_end(),
+        mine.trim_end(),
+        tr!("yours"),
+    )
+}
+
 use pwt_macros::builder;
 
 #[derive(PartialEq, Properties)]
@@ -64,6 +78,11 @@ pub struct NotesView {
     #[builder_cb(IntoSubmitCallback, into_submit_callback, NotesWithDigest)]
     #[prop_or_default]
     pub on_submit: Option<SubmitCallback<NotesWithDigest>>,
+
+    /// Maximum number of characters accepted by the API.
+    #[builder]
+    #[prop_or(64 * 1024)]
+    pub max_length: usize,
 }
 
 impl NotesView {
@@ -77,17 +96,28 @@ impl NotesView {
     /// Automatically create a loader and on_submit callback.
     pub fn edit_property(url: impl Into<AttrValue>, prop_name: impl Into<AttrValue>) -> Self {
         let url = url.into();
+        Self::edit_property_urls(url.clone(), url, prop_name)
+    }
+
+    /// Create a new instance, assume that notes are stored as object property.
+    ///
+    /// Like [Self::edit_property], but uses separate URLs to load and store the notes, e.g.
+    /// for APIs where the configuration is read from a different path than it is updated.
+    pub fn edit_property_urls(
+        load_url: impl Into<AttrValue>,
+        store_url: impl Into<AttrValue>,
+        prop_name: impl Into<AttrValue>,
+    ) -> Self {
+        let load_url = load_url.into();
+        let store_url = store_url.into();
         let prop_name = prop_name.into();
 
         let loader = ApiLoadCallback::new({
-            let url = url.clone();
             let prop_name = prop_name.clone();
-            move || load_notes_property(url.clone(), prop_name.clone())
+            move || load_notes_property(load_url.clone(), prop_name.clone())
         });
         let on_submit = SubmitCallback::new({
-            let url = url.clone();
-            let prop_name = prop_name.clone();
-            move |data| update_notes_property(url.clone(), prop_name.clone(), data)
+            move |data| update_notes_property(store_url.clone(), prop_name.clone(), data)
         });
         yew::props!(Self {
             loader,
@@ -103,13 +133,103 @@ pub enum ViewState {
 
 pub enum Msg {
     Load(NotesWithDigest),
+    /// The notes were modified on the server while editing.
+    Conflict(NotesWithDigest),
+    /// The user resolved the conflict, submit against the current server version.
+    ResolveConflict,
 }
 
+type NotesViewScope = Scope<LoadableComponentMaster<ProxmoxNotesView>>;
+
 #[doc(hidden)]
 pub struct ProxmoxNotesView {
     state: LoadableComponentState<ViewState>,
     data: NotesWithDigest,
     edit_window_loader: ApiLoadCallback<Value>,
+    conflict: Option<NotesWithDigest>,
+    // digest used instead of the one loaded by the edit window, after resolving a conflict
+    resolved_digest: Option<Value>,
+}
+
+impl ProxmoxNotesView {
+    fn render_conflict(
+        link: &NotesViewScope,
+        form_ctx: &FormContext,
+        current: &NotesWithDigest,
+    ) -> Html {
+        let set_notes = |text: Option<String>| {
+            let form_ctx = form_ctx.clone();
+            let link = link.clone();
+            move |_| {
+                if let Some(text) = &text {
+                    form_ctx
+                        .write()
+                        .set_field_value("notes", text.clone().into());
+                }
+                link.send_message(Msg::ResolveConflict);
+            }
+        };
+
+        let mine = form_ctx.read().get_field_text("notes");
+        let merged = merge_notes(&current.notes, &mine);
+
+        Row::new()
+            .padding(2)
+            .gap(2)
+            .class(pwt::css::AlignItems::Center)
+            .class("pwt-border-top")
+            .with_child(Fa::from(Status::Warning))
+            .with_child(tr!("The notes were modified in the meantime."))
+            .with_flex_spacer()
+            .with_child(Button::new(tr!("Overwrite")).onclick(set_notes(None)))
+            .with_child(Button::new(tr!("Merge")).onclick(set_notes(Some(merged))))
+            .with_child(
+                Button::new(tr!("Discard my changes"))
+                    .onclick(set_notes(Some(current.notes.clone()))),
+            )
+            .into()
+    }
+
+    fn render_editor(
+        link: &NotesViewScope,
+        form_ctx: &FormContext,
+        max_length: usize,
+        conflict: Option<&NotesWithDigest>,
+    ) -> Html {
+        let length = form_ctx.read().get_field_text("notes").chars().count();
+
+        let counter = Row::new()
+            .padding_x(2)
+            .padding_bottom(1)
+            .with_flex_spacer()
+            .with_child(
+                Container::new()
+                    .class((length > max_length).then_some("pwt-color-error"))
+                    .with_child(format!("{length} / {max_length}")),
+            );
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(
+                MarkdownEditor::new()
+                    .padding(2)
+                    .name("notes")
+                    .submit_empty(true)
+                    .initial_mode(MarkdownViewMode::Split)
+                    .class(pwt::css::FlexFit)
+                    .validate(move |value: &String| {
+                        if value.chars().count() > max_length {
+                            bail!(tr!("Maximum length is {0} characters.", max_length));
+                        }
+                        Ok(())
+                    }),
+            )
+            .with_child(counter)
+            .with_optional_child(
+                conflict.map(|current| Self::render_conflict(link, form_ctx, current)),
+            )
+            .into()
+    }
 }
 
 pwt::impl_deref_mut_property!(ProxmoxNotesView, state, LoadableComponentState<ViewState>);
@@ -139,6 +259,8 @@ impl LoadableComponent for ProxmoxNotesView {
                 digest: None,
             },
             edit_window_loader,
+            conflict: None,
+            resolved_digest: None,
         }
     }
 
@@ -161,6 +283,18 @@ impl LoadableComponent for ProxmoxNotesView {
         match msg {
             Msg::Load(data) => {
                 self.data = data;
+                self.conflict = None;
+                self.resolved_digest = None;
+                true
+            }
+            Msg::Conflict(current) => {
+                self.conflict = Some(current);
+                true
+            }
+            Msg::ResolveConflict => {
+                if let Some(current) = self.conflict.take() {
+                    self.resolved_digest = current.digest;
+                }
                 true
             }
         }
@@ -205,29 +339,49 @@ impl LoadableComponent for ProxmoxNotesView {
                     .loader(self.edit_window_loader.clone())
                     .on_submit({
                         let on_submit = props.on_submit.clone();
+                        let loader = props.loader.clone();
+                        let resolved_digest = self.resolved_digest.clone();
+                        let link = ctx.link().clone();
                         move |form_ctx: FormContext| {
                             let on_submit = on_submit.clone();
+                            let loader = loader.clone();
+                            let resolved_digest = resolved_digest.clone();
+                            let link = link.clone();
                             async move {
-                                if let Some(on_submit) = &on_submit {
-                                    let data = form_ctx.read().get_submit_data();
-                                    let data: NotesWithDigest = serde_json::from_value(data)?;
-                                    on_submit.apply(data).await?;
+                                let Some(on_submit) = &on_submit else {
+                                    return Ok(());
+                                };
+                                let data = form_ctx.read().get_submit_data();
+                                let mut data: NotesWithDigest = serde_json::from_value(data)?;
+                                if resolved_digest.is_some() {
+                                    data.digest = resolved_digest;
                                 }
-                                Ok(())
+
+                                // check for concurrent modifications, so that we can offer to
+                                // merge instead of just failing
+                                if let Some(digest) = &data.digest {
+                                    let resp = loader.apply().await?;
+                                    let current = NotesWithDigest {
+                                        notes: resp.data,
+                                        digest: resp.attribs.get("digest").cloned(),
+                                    };
+                                    if current.digest.as_ref().is_some_and(|d| d != digest) {
+                                        link.send_message(Msg::Conflict(current));
+                                        bail!(tr!("The notes were modified in the meantime."));
+                                    }
+                                }
+
+                                on_submit.apply(data).await
                             }
                         }
                     })
-                    .renderer(|_form_ctx| {
-                        Column::new()
-                            .class(pwt::css::FlexFit)
-                            .with_child(
-                                TextArea::new()
-                                    .padding(2)
-                                    .name("notes")
-                                    .submit_empty(true)
-                                    .class(pwt::css::FlexFit),
-                            )
-                            .into()
+                    .renderer({
+                        let link = ctx.link().clone();
+                        let max_length = props.max_length;
+                        let conflict = self.conflict.clone();
+                        move |form_ctx: &FormContext| {
+                            Self::render_editor(&link, form_ctx, max_length, conflict.as_ref())
+                        }
                     });
 
                 Some(dialog.into())
@@ -242,3 +396,16 @@ impl From<NotesView> for VNode {
         VNode::from(comp)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_merge_notes() {
+        assert_eq!(
+            merge_notes("theirs\n", "mine"),
+            "<<<<<<< current\ntheirs\n=======\nmine\n>>>>>>> yours\n"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use proxmox_client::ApiResponseData;
+use serde_json::Value;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, InputType, Number, TextArea};
+use pwt::widget::InputPanel;
+
+use pwt_macros::builder;
+
+use crate::form::delete_empty_values;
+use crate::percent_encoding::percent_encode_component;
+use crate::EditWindow;
+
+use super::{split_list, NotificationEndpointType};
+
+/// Edit window for notification endpoints (sendmail, smtp, gotify and webhook).
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct NotificationEndpointEdit {
+    /// The endpoint type.
+    pub endpoint_type: NotificationEndpointType,
+
+    /// Edit existing endpoint.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub name: Option<AttrValue>,
+
+    #[prop_or("/cluster/notifications".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the notification API.
+    pub base_url: AttrValue,
+
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl NotificationEndpointEdit {
+    pub fn new(endpoint_type: NotificationEndpointType) -> Self {
+        yew::props!(Self { endpoint_type })
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxNotificationEndpointEdit {}
+
+fn endpoint_url(base_url: &str, endpoint_type: NotificationEndpointType) -> String {
+    format!("{base_url}/endpoints/{endpoint_type}")
+}
+
+const LIST_FIELDS: &[&str] = &["mailto", "mailto-user"];
+
+// the API uses "disable", the form shows "Enable"
+pub(crate) const ENABLE_FIELD: &str = "_enable";
+
+/// Convert the loaded endpoint config into form values.
+fn config_to_form(endpoint_type: NotificationEndpointType, mut data: Value) -> Value {
+    data[ENABLE_FIELD] = (!data["disable"].as_bool().unwrap_or(false)).into();
+
+    for name in LIST_FIELDS {
+        if let Some(list) = data[name].as_array() {
+            let list: Vec<&str> = list.iter().filter_map(|v| v.as_str()).collect();
+            data[name] = list.join(", ").into();
+        }
+    }
+
+    if endpoint_type == NotificationEndpointType::Webhook {
+        if let Some(body) = data["body"].as_str() {
+            data["body"] = decode_base64(body).into();
+        }
+        if let Some(headers) = data["header"].as_array() {
+            let headers: Vec<String> = headers
+                .iter()
+                .filter_map(|header| header.as_str())
+                .filter_map(parse_webhook_header)
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect();
+            data["header"] = headers.join("\n").into();
+        }
+    }
+    data
+}
+
+fn decode_base64(text: &str) -> String {
+    proxmox_base64::decode(text)
+        .ok()
+        .and_then(|data| String::from_utf8(data).ok())
+        .unwrap_or_default()
+}
+
+/// Parse a webhook header property string (`name=<name>,value=<base64 value>`).
+fn parse_webhook_header(header: &str) -> Option<(String, String)> {
+    let mut name = None;
+    let mut value = String::new();
+    for part in header.split(',') {
+        match part.split_once('=') {
+            Some(("name", n)) => name = Some(n.to_string()),
+            Some(("value", v)) => value = decode_base64(v),
+            _ => {}
+        }
+    }
+    name.map(|name| (name, value))
+}
+
+/// Convert the form values into the endpoint config.
+fn form_to_config(
+    endpoint_type: NotificationEndpointType,
+    form_ctx: &FormContext,
+    is_edit: bool,
+) -> Value {
+    let mut data = form_ctx.get_submit_data();
+
+    let enable = form_ctx.read().get_field_checked(ENABLE_FIELD);
+    data["disable"] = if enable { Value::Null } else { true.into() };
+
+    for name in LIST_FIELDS {
+        if let Some(text) = data[name].as_str() {
+            let list = split_list(text);
+            data[name] = if list.is_empty() {
+                Value::Null
+            } else {
+                list.into()
+            };
+        }
+    }
+
+    // keep stored secrets if the field was left empty
+    for name in ["password", "token"] {
+        if is_edit && data[name].as_str() == Some("") {
+            data.as_object_mut().unwrap().remove(name);
+        }
+    }
+
+    if endpoint_type == NotificationEndpointType::Webhook {
+        if let Some(body) = data["body"].as_str() {
+            data["body"] = if body.is_empty() {
+                Value::Null
+            } else {
+                proxmox_base64::encode(body).into()
+            };
+        }
+        if let Some(headers) = data["header"].as_str() {
+            let headers: Vec<String> = headers
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| {
+                    format!(
+                        "name={},value={}",
+                        name.trim(),
+                        proxmox_base64::encode(value.trim())
+                    )
+                })
+                .collect();
+            data["header"] = if headers.is_empty() {
+                Value::Null
+            } else {
+                headers.into()
+            };
+        }
+    }
+
+    data
+}
+
+fn optional_fields(endpoint_type: NotificationEndpointType) -> &'static [&'static str] {
+    match endpoint_type {
+        NotificationEndpointType::Sendmail => &[
+            "comment",
+            "disable",
+            "mailto",
+            "mailto-user",
+            "from-address",
+            "author",
+        ],
+        NotificationEndpointType::Smtp => &[
+            "comment",
+            "disable",
+            "mailto",
+            "mailto-user",
+            "author",
+            "port",
+            "mode",
+            "username",
+            "password",
+        ],
+        NotificationEndpointType::Gotify => &["comment", "disable"],
+        NotificationEndpointType::Webhook => &["comment", "disable", "header", "body"],
+    }
+}
+
+async fn create_item(
+    form_ctx: FormContext,
+    url: String,
+    endpoint_type: NotificationEndpointType,
+) -> Result<(), Error> {
+    let mut data = form_to_config(endpoint_type, &form_ctx, false);
+    if let Some(map) = data.as_object_mut() {
+        map.retain(|_, value| !(value.is_null() || value.as_str() == Some("")));
+    }
+    crate::http_post(url, Some(data)).await
+}
+
+async fn update_item(
+    form_ctx: FormContext,
+    url: String,
+    endpoint_type: NotificationEndpointType,
+) -> Result<(), Error> {
+    let data = form_to_config(endpoint_type, &form_ctx, true);
+    let data = delete_empty_values(&data, optional_fields(endpoint_type), false);
+    crate::http_put(url, Some(data)).await
+}
+
+fn mail_recipient_fields(panel: InputPanel) -> InputPanel {
+    panel
+        .with_large_field(
+            tr!("Recipient(s)"),
+            Field::new()
+                .name("mailto")
+                .placeholder(tr!("Comma separated list of E-Mail addresses")),
+        )
+        .with_large_field(
+            tr!("User(s)"),
+            Field::new()
+                .name("mailto-user")
+                .placeholder(tr!("Comma separated list of users")),
+        )
+}
+
+fn render_input_form(form_ctx: &FormContext, props: &NotificationEndpointEdit) -> Html {
+    let is_edit = props.name.is_some();
+
+    let mut panel = InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_field(
+            tr!("Endpoint Name"),
+            Field::new()
+                .name("name")
+                .required(true)
+                .disabled(is_edit)
+                .submit(!is_edit),
+        )
+        .with_right_field(
+            tr!("Enable"),
+            Checkbox::new()
+                .name(ENABLE_FIELD)
+                .submit(false)
+                .default(true),
+        );
+
+    let secret_placeholder = if is_edit {
+        tr!("Unchanged")
+    } else {
+        String::new()
+    };
+
+    panel = match props.endpoint_type {
+        NotificationEndpointType::Sendmail => {
+            let panel = mail_recipient_fields(panel);
+            panel
+                .with_advanced_spacer()
+                .with_field(
+                    tr!("From Address"),
+                    Field::new().name("from-address").placeholder(tr!(
+                        "Defaults to datacenter configuration, or root@$hostname"
+                    )),
+                )
+                .with_right_field(
+                    tr!("Author"),
+                    Field::new().name("author").placeholder("Proxmox VE"),
+                )
+        }
+        NotificationEndpointType::Smtp => {
+            let mode_items = Rc::new(vec![
+                AttrValue::Static("insecure"),
+                AttrValue::Static("starttls"),
+                AttrValue::Static("tls"),
+            ]);
+            let panel = panel
+                .with_field(tr!("Server"), Field::new().name("server").required(true))
+                .with_right_field(
+                    tr!("Encryption"),
+                    Combobox::new()
+                        .name("mode")
+                        .items(mode_items)
+                        .placeholder("tls"),
+                )
+                .with_field(
+                    tr!("Port"),
+                    Number::<u16>::new()
+                        .name("port")
+                        .min(1)
+                        .placeholder(tr!("Default")),
+                )
+                .with_right_field(tr!("Username"), Field::new().name("username"))
+                .with_field(
+                    tr!("From Address"),
+                    Field::new().name("from-address").required(true),
+                )
+                .with_right_field(
+                    tr!("Password"),
+                    Field::new()
+                        .name("password")
+                        .input_type(InputType::Password)
+                        .placeholder(secret_placeholder),
+                );
+            mail_recipient_fields(panel).with_large_advanced_field(
+                tr!("Author"),
+                Field::new().name("author").placeholder("Proxmox VE"),
+            )
+        }
+        NotificationEndpointType::Gotify => panel
+            .with_large_field(
+                tr!("Server URL"),
+                Field::new().name("server").required(true),
+            )
+            .with_large_field(
+                tr!("API Token"),
+                Field::new()
+                    .name("token")
+                    .input_type(InputType::Password)
+                    .required(!is_edit)
+                    .placeholder(secret_placeholder),
+            ),
+        NotificationEndpointType::Webhook => {
+            let method_items = Rc::new(vec![
+                AttrValue::Static("post"),
+                AttrValue::Static("put"),
+                AttrValue::Static("get"),
+            ]);
+            panel
+                .with_field(
+                    tr!("Method"),
+                    Combobox::new()
+                        .name("method")
+                        .required(true)
+                        .default("post")
+                        .items(method_items),
+                )
+                .with_large_field(tr!("URL"), Field::new().name("url").required(true))
+                .with_large_field(
+                    tr!("Headers"),
+                    TextArea::new()
+                        .name("header")
+                        .attribute("rows", "3")
+                        .placeholder(tr!("One 'Name: Value' pair per line")),
+                )
+                .with_large_field(
+                    tr!("Body"),
+                    TextArea::new().name("body").attribute("rows", "5"),
+                )
+        }
+    };
+
+    panel
+        .with_large_field(tr!("Comment"), Field::new().name("comment"))
+        .into()
+}
+
+impl Component for ProxmoxNotificationEndpointEdit {
+    type Message = ();
+    type Properties = NotificationEndpointEdit;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {}
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let is_edit = props.name.is_some();
+        let endpoint_type = props.endpoint_type;
+
+        let action = if is_edit { tr!("Edit") } else { tr!("Add") };
+
+        let base_url = endpoint_url(&props.base_url, endpoint_type);
+        let item_url = props
+            .name
+            .as_ref()
+            .map(|name| format!("{base_url}/{}", percent_encode_component(name)));
+
+        let on_submit = {
+            let item_url = item_url.clone();
+            move |form_ctx| {
+                let base_url = base_url.clone();
+                let item_url = item_url.clone();
+                async move {
+                    match item_url {
+                        Some(url) => update_item(form_ctx, url, endpoint_type).await,
+                        None => create_item(form_ctx, base_url, endpoint_type).await,
+                    }
+                }
+            }
+        };
+
+        let loader = item_url.map(|url| {
+            (
+                move |url: AttrValue| async move {
+                    let resp: ApiResponseData<Value> = crate::http_get_full(&*url, None).await?;
+                    Ok(ApiResponseData {
+                        data: config_to_form(endpoint_type, resp.data),
+                        attribs: resp.attribs,
+                    })
+                },
+                AttrValue::from(url),
+            )
+        });
+
+        EditWindow::new(action + ": " + &endpoint_type.title())
+            .advanced_checkbox(true)
+            .loader(loader)
+            .renderer({
+                let props = props.clone();
+                move |form_ctx: &FormContext| render_input_form(form_ctx, &props)
+            })
+            .on_done(props.on_close.clone())
+            .on_submit(on_submit)
+            .into()
+    }
+}
+
+impl From<NotificationEndpointEdit> for VNode {
+    fn from(val: NotificationEndpointEdit) -> Self {
+        let comp = VComp::new::<ProxmoxNotificationEndpointEdit>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,358 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use proxmox_client::ApiResponseData;
+use serde_json::Value;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, TextArea};
+use pwt::widget::{error_message, Container, InputPanel, Row};
+
+use pwt_macros::builder;
+
+use crate::form::delete_empty_values;
+use crate::percent_encoding::percent_encode_component;
+use crate::EditWindow;
+
+use super::endpoint_edit::ENABLE_FIELD;
+use super::{split_list, NotificationTarget};
+
+const SEVERITIES: &[&str] = &["info", "notice", "warning", "error", "unknown"];
+
+fn target_field(name: &str) -> String {
+    format!("_target_{name}")
+}
+
+fn severity_field(severity: &str) -> String {
+    format!("_severity_{severity}")
+}
+
+/// Edit window for notification matchers.
+///
+/// Matchers route notifications to targets, depending on severity, metadata fields and the
+/// time the notification was sent.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct NotificationMatcherEdit {
+    /// Edit existing matcher.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub name: Option<AttrValue>,
+
+    #[prop_or("/cluster/notifications".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the notification API.
+    pub base_url: AttrValue,
+
+    /// Close/Abort callback
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for NotificationMatcherEdit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationMatcherEdit {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Targets(Result<Vec<NotificationTarget>, Error>),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxNotificationMatcherEdit {
+    targets: Option<Result<Rc<Vec<NotificationTarget>>, String>>,
+}
+
+/// Convert the loaded matcher config into form values.
+fn config_to_form(mut data: Value) -> Value {
+    data[ENABLE_FIELD] = (!data["disable"].as_bool().unwrap_or(false)).into();
+
+    let list = |value: &Value| -> Vec<String> {
+        value
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    for target in list(&data["target"]) {
+        data[target_field(&target)] = true.into();
+    }
+    for entry in list(&data["match-severity"]) {
+        for severity in split_list(&entry) {
+            data[severity_field(&severity)] = true.into();
+        }
+    }
+    data["match-field"] = list(&data["match-field"]).join("\n").into();
+    data["match-calendar"] = list(&data["match-calendar"]).join(", ").into();
+
+    data
+}
+
+/// Convert the form values into the matcher config.
+fn form_to_config(form_ctx: &FormContext, targets: &[NotificationTarget]) -> Value {
+    let mut data = form_ctx.get_submit_data();
+    let form = form_ctx.read();
+
+    data["disable"] = if form.get_field_checked(ENABLE_FIELD) {
+        Value::Null
+    } else {
+        true.into()
+    };
+
+    let target: Vec<&str> = targets
+        .iter()
+        .filter(|target| form.get_field_checked(target_field(&target.name)))
+        .map(|target| target.name.as_str())
+        .collect();
+    data["target"] = if target.is_empty() {
+        Value::Null
+    } else {
+        target.into()
+    };
+
+    let severities: Vec<&str> = SEVERITIES
+        .iter()
+        .copied()
+        .filter(|severity| form.get_field_checked(severity_field(severity)))
+        .collect();
+    data["match-severity"] = if severities.is_empty() {
+        Value::Null
+    } else {
+        vec![severities.join(",")].into()
+    };
+
+    let match_field: Vec<&str> = data["match-field"]
+        .as_str()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    data["match-field"] = if match_field.is_empty() {
+        Value::Null
+    } else {
+        match_field.into()
+    };
+
+    let match_calendar: Vec<String> = data["match-calendar"]
+        .as_str()
+        .unwrap_or_default()
+        .split(',')
+        .map(|event| event.trim().to_string())
+        .filter(|event| !event.is_empty())
+        .collect();
+    data["match-calendar"] = if match_calendar.is_empty() {
+        Value::Null
+    } else {
+        match_calendar.into()
+    };
+
+    data
+}
+
+const OPTIONAL_FIELDS: &[&str] = &[
+    "comment",
+    "disable",
+    "invert-match",
+    "mode",
+    "target",
+    "match-severity",
+    "match-field",
+    "match-calendar",
+];
+
+async fn submit_item(
+    form_ctx: FormContext,
+    base_url: String,
+    name: Option<AttrValue>,
+    targets: Rc<Vec<NotificationTarget>>,
+) -> Result<(), Error> {
+    let mut data = form_to_config(&form_ctx, &targets);
+    match name {
+        Some(name) => {
+            let url = format!("{base_url}/{}", percent_encode_component(&name));
+            let data = delete_empty_values(&data, OPTIONAL_FIELDS, false);
+            crate::http_put(url, Some(data)).await
+        }
+        None => {
+            if let Some(map) = data.as_object_mut() {
+                map.retain(|_, value| !(value.is_null() || value.as_str() == Some("")));
+            }
+            crate::http_post(base_url, Some(data)).await
+        }
+    }
+}
+
+fn render_input_form(
+    form_ctx: &FormContext,
+    is_edit: bool,
+    targets: &Option<Result<Rc<Vec<NotificationTarget>>, String>>,
+) -> Html {
+    let mode_items = Rc::new(vec![AttrValue::Static("all"), AttrValue::Static("any")]);
+
+    let mut target_list = Container::new().key("targets").class("pwt-d-grid").style(
+        "grid-template-columns",
+        "repeat(auto-fill, minmax(200px, 1fr))",
+    );
+    match targets {
+        None => target_list.add_child(tr!("Loading...")),
+        Some(Err(err)) => target_list.add_child(error_message(err)),
+        Some(Ok(targets)) => {
+            for target in targets.iter() {
+                target_list.add_child(
+                    Checkbox::new()
+                        .name(target_field(&target.name))
+                        .submit(false)
+                        .box_label(format!("{} ({})", target.name, target.ty.title())),
+                );
+            }
+        }
+    }
+
+    let mut severity_list = Row::new().key("severities").gap(4);
+    for severity in SEVERITIES {
+        severity_list.add_child(
+            Checkbox::new()
+                .name(severity_field(severity))
+                .submit(false)
+                .box_label(severity.to_string()),
+        );
+    }
+
+    InputPanel::new()
+        .show_advanced(form_ctx.get_show_advanced())
+        .padding(4)
+        .with_field(
+            tr!("Matcher Name"),
+            Field::new()
+                .name("name")
+                .required(true)
+                .disabled(is_edit)
+                .submit(!is_edit),
+        )
+        .with_right_field(
+            tr!("Enable"),
+            Checkbox::new()
+                .name(ENABLE_FIELD)
+                .submit(false)
+                .default(true),
+        )
+        .with_large_field(tr!("Comment"), Field::new().name("comment"))
+        .with_custom_child(html! {<span key="targets_title">{tr!("Targets to notify")}</span>})
+        .with_large_custom_child(target_list)
+        .with_custom_child(html! {<span key="severity_title">{tr!("Match Severity")}</span>})
+        .with_large_custom_child(severity_list)
+        .with_field(
+            tr!("Mode"),
+            Combobox::new()
+                .name("mode")
+                .items(mode_items)
+                .placeholder("all"),
+        )
+        .with_right_field(tr!("Invert match"), Checkbox::new().name("invert-match"))
+        .with_large_field(
+            tr!("Match Fields"),
+            TextArea::new()
+                .name("match-field")
+                .attribute("rows", "3")
+                .placeholder(tr!("One rule per line, e.g. 'exact:type=vzdump'")),
+        )
+        .with_large_advanced_field(
+            tr!("Match Calendar"),
+            Field::new()
+                .name("match-calendar")
+                .placeholder(tr!("Comma separated list of calendar events")),
+        )
+        .into()
+}
+
+impl Component for ProxmoxNotificationMatcherEdit {
+    type Message = Msg;
+    type Properties = NotificationMatcherEdit;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let url = format!("{}/targets", ctx.props().base_url);
+        ctx.link().send_future(async move {
+            let targets = crate::http_get(url, None).await;
+            Msg::Targets(targets)
+        });
+        Self { targets: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Targets(result) => {
+                self.targets = Some(result.map(Rc::new).map_err(|err| err.to_string()));
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let is_edit = props.name.is_some();
+        let action = if is_edit { tr!("Edit") } else { tr!("Add") };
+
+        let base_url = format!("{}/matchers", props.base_url);
+
+        let loader = props.name.as_ref().map(|name| {
+            (
+                |url: AttrValue| async move {
+                    let resp: ApiResponseData<Value> = crate::http_get_full(&*url, None).await?;
+                    Ok(ApiResponseData {
+                        data: config_to_form(resp.data),
+                        attribs: resp.attribs,
+                    })
+                },
+                format!("{base_url}/{}", percent_encode_component(name)),
+            )
+        });
+
+        let targets = match &self.targets {
+            Some(Ok(targets)) => Rc::clone(targets),
+            _ => Rc::new(Vec::new()),
+        };
+
+        let on_submit = {
+            let name = props.name.clone();
+            move |form_ctx| submit_item(form_ctx, base_url.clone(), name.clone(), targets.clone())
+        };
+
+        let target_state = self.targets.clone();
+
+        EditWindow::new(action + ": " + &tr!("Notification Matcher"))
+            .advanced_checkbox(true)
+            .loader(loader)
+            .renderer(move |form_ctx: &FormContext| {
+                render_input_form(form_ctx, is_edit, &target_state)
+            })
+            .on_done(props.on_close.clone())
+            .on_submit(on_submit)
+            .into()
+    }
+}
+
+impl From<NotificationMatcherEdit> for VNode {
+    fn from(val: NotificationMatcherEdit) -> Self {
+        let comp = VComp::new::<ProxmoxNotificationMatcherEdit>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,250 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::{Button, Toolbar};
+
+use pwt_macros::builder;
+
+use crate::percent_encoding::percent_encode_component;
+use crate::utils::render_boolean_icon;
+use crate::{
+    ConfirmButton, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState,
+};
+
+use super::{NotificationConfigOrigin, NotificationMatcher, NotificationMatcherEdit};
+
+/// Notification matcher list with add, edit and remove actions.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct NotificationMatchersPanel {
+    #[prop_or("/cluster/notifications".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the notification API.
+    pub base_url: AttrValue,
+
+    /// Read-only view - hide toolbar and all buttons/menus to edit content.
+    #[prop_or_default]
+    #[builder]
+    pub readonly: bool,
+}
+
+impl Default for NotificationMatchersPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationMatchersPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxNotificationMatchersPanel {
+    state: LoadableComponentState<ViewState>,
+    selection: Selection,
+    store: Store<NotificationMatcher>,
+    columns: Rc<Vec<DataTableHeader<NotificationMatcher>>>,
+}
+
+pwt::impl_deref_mut_property!(
+    ProxmoxNotificationMatchersPanel,
+    state,
+    LoadableComponentState<ViewState>
+);
+
+#[derive(PartialEq)]
+pub enum ViewState {
+    Add,
+    Edit(Key),
+}
+
+impl LoadableComponent for ProxmoxNotificationMatchersPanel {
+    type Properties = NotificationMatchersPanel;
+    type Message = ();
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let selection = Selection::new().on_select({
+            let link = ctx.link().clone();
+            move |_| link.send_redraw()
+        });
+        let store =
+            Store::with_extract_key(|record: &NotificationMatcher| Key::from(record.name.clone()));
+
+        let columns = Rc::new(vec![
+            DataTableColumn::new(tr!("Enable"))
+                .width("80px")
+                .justify("center")
+                .render(|record: &NotificationMatcher| {
+                    render_boolean_icon(Some(!record.disable.unwrap_or(false)))
+                })
+                .into(),
+            DataTableColumn::new(tr!("Matcher Name"))
+                .flex(1)
+                .render(|record: &NotificationMatcher| html! { &record.name })
+                .sorter(|a: &NotificationMatcher, b: &NotificationMatcher| a.name.cmp(&b.name))
+                .sort_order(true)
+                .into(),
+            DataTableColumn::new(tr!("Targets"))
+                .flex(1)
+                .render(|record: &NotificationMatcher| html! { record.target.join(", ") })
+                .into(),
+            DataTableColumn::new(tr!("Comment"))
+                .flex(2)
+                .render(|record: &NotificationMatcher| {
+                    html! { record.comment.as_deref().unwrap_or_default() }
+                })
+                .into(),
+        ]);
+
+        Self {
+            state: LoadableComponentState::new(),
+            selection,
+            store,
+            columns,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let store = self.store.clone();
+        let url = format!("{}/matchers", ctx.props().base_url);
+        Box::pin(async move {
+            let data = crate::http_get(url, None).await?;
+            store.write().set_data(data);
+            Ok(())
+        })
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let props = ctx.props();
+        if props.readonly {
+            return None;
+        }
+        let selected_key = self.selection.selected_key();
+        let is_builtin = selected_key.as_ref().is_some_and(|key| {
+            self.store
+                .read()
+                .lookup_record(key)
+                .is_some_and(|record| record.origin == Some(NotificationConfigOrigin::Builtin))
+        });
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_child(
+                Button::new(tr!("Add"))
+                    .onclick(ctx.link().change_view_callback(|_| Some(ViewState::Add))),
+            )
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Edit"))
+                    .disabled(selected_key.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        let selected_key = selected_key.clone();
+                        move |_| {
+                            if let Some(selected_key) = &selected_key {
+                                link.change_view(Some(ViewState::Edit(selected_key.clone())));
+                            }
+                        }
+                    }),
+            )
+            .with_child(
+                ConfirmButton::remove_entry(selected_key.as_deref().unwrap_or("").to_string())
+                    .dangerous(true)
+                    .disabled(selected_key.is_none() || is_builtin)
+                    .on_activate({
+                        let link = ctx.link().clone();
+                        let selected_key = selected_key.clone();
+                        let base_url = props.base_url.clone();
+                        move |_| {
+                            let Some(selected_key) = &selected_key else {
+                                return;
+                            };
+                            let url = format!(
+                                "{base_url}/matchers/{}",
+                                percent_encode_component(selected_key)
+                            );
+                            let link = link.clone();
+                            link.clone().spawn(async move {
+                                if let Err(err) = crate::http_delete(url, None).await {
+                                    link.show_error(tr!("Unable to remove matcher"), err, true);
+                                }
+                                link.send_reload();
+                            });
+                        }
+                    }),
+            );
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let mut table = DataTable::new(self.columns.clone(), self.store.clone())
+            .class("pwt-flex-fit")
+            .selection(self.selection.clone());
+
+        if !ctx.props().readonly {
+            table = table.on_row_dblclick({
+                let selection = self.selection.clone();
+                let link = ctx.link().clone();
+                move |_: &mut _| {
+                    if let Some(selected_key) = selection.selected_key() {
+                        link.change_view(Some(ViewState::Edit(selected_key.clone())));
+                    }
+                }
+            });
+        }
+
+        table.into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        let base_url = ctx.props().base_url.clone();
+        match view_state {
+            ViewState::Add => Some(
+                NotificationMatcherEdit::new()
+                    .base_url(base_url)
+                    .on_close(ctx.link().change_view_callback(|_| None))
+                    .into(),
+            ),
+            ViewState::Edit(name) => Some(
+                NotificationMatcherEdit::new()
+                    .base_url(base_url)
+                    .name(name.to_string())
+                    .on_close(ctx.link().change_view_callback(|_| None))
+                    .into(),
+            ),
+        }
+    }
+}
+
+impl From<NotificationMatchersPanel> for VNode {
+    fn from(val: NotificationMatchersPanel) -> Self {
+        let comp = VComp::new::<LoadableComponentMaster<ProxmoxNotificationMatchersPanel>>(
+            Rc::new(val),
+            None,
+        );
+        VNode::from(comp)
+    }
+}
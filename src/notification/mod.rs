@@ -0,0 +1,148 @@
+//! Notification endpoint (target) and matcher configuration.
+
+use serde::{Deserialize, Serialize};
+
+use pwt::tr;
+
+mod endpoint_edit;
+pub use endpoint_edit::{NotificationEndpointEdit, ProxmoxNotificationEndpointEdit};
+
+mod matcher_edit;
+pub use matcher_edit::{NotificationMatcherEdit, ProxmoxNotificationMatcherEdit};
+
+mod targets_panel;
+pub use targets_panel::{NotificationTargetsPanel, ProxmoxNotificationTargetsPanel};
+
+mod matchers_panel;
+pub use matchers_panel::{NotificationMatchersPanel, ProxmoxNotificationMatchersPanel};
+
+/// Notification endpoint types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationEndpointType {
+    Sendmail,
+    Smtp,
+    Gotify,
+    Webhook,
+}
+
+impl NotificationEndpointType {
+    /// All endpoint types, in the order they are offered.
+    pub const ALL: [NotificationEndpointType; 4] = [
+        NotificationEndpointType::Sendmail,
+        NotificationEndpointType::Smtp,
+        NotificationEndpointType::Gotify,
+        NotificationEndpointType::Webhook,
+    ];
+
+    /// The type name used in the API paths.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEndpointType::Sendmail => "sendmail",
+            NotificationEndpointType::Smtp => "smtp",
+            NotificationEndpointType::Gotify => "gotify",
+            NotificationEndpointType::Webhook => "webhook",
+        }
+    }
+
+    /// Human readable type name.
+    pub fn title(&self) -> String {
+        match self {
+            NotificationEndpointType::Sendmail => tr!("Sendmail"),
+            NotificationEndpointType::Smtp => tr!("SMTP"),
+            NotificationEndpointType::Gotify => tr!("Gotify"),
+            NotificationEndpointType::Webhook => tr!("Webhook"),
+        }
+    }
+
+    pub(crate) fn icon_class(&self) -> &'static str {
+        match self {
+            NotificationEndpointType::Sendmail | NotificationEndpointType::Smtp => {
+                "fa fa-fw fa-envelope-o"
+            }
+            NotificationEndpointType::Gotify => "fa fa-fw fa-bell-o",
+            NotificationEndpointType::Webhook => "fa fa-fw fa-globe",
+        }
+    }
+}
+
+impl std::fmt::Display for NotificationEndpointType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a notification target or matcher is defined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationConfigOrigin {
+    /// User created entry.
+    UserCreated,
+    /// Built-in entry, modified by the user.
+    ModifiedBuiltin,
+    /// Built-in entry.
+    Builtin,
+}
+
+/// Notification target list entry (`GET /cluster/notifications/targets`).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NotificationTarget {
+    /// Name of the target.
+    pub name: String,
+    /// Type of the target.
+    #[serde(rename = "type")]
+    pub ty: NotificationEndpointType,
+    /// Comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Whether the target is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable: Option<bool>,
+    /// Where the target is defined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<NotificationConfigOrigin>,
+}
+
+/// Notification matcher list entry (`GET /cluster/notifications/matchers`).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NotificationMatcher {
+    /// Name of the matcher.
+    pub name: String,
+    /// Comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Whether the matcher is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable: Option<bool>,
+    /// Whether all (`all`) or any (`any`) match rule has to match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Invert the result of the match rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invert_match: Option<bool>,
+    /// Targets to notify.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target: Vec<String>,
+    /// Severities to match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub match_severity: Vec<String>,
+    /// Metadata fields to match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub match_field: Vec<String>,
+    /// Calendar events to match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub match_calendar: Vec<String>,
+    /// Where the matcher is defined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<NotificationConfigOrigin>,
+}
+
+/// Split a comma/whitespace separated list, as entered in list fields.
+pub(crate) fn split_list(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,335 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::menu::{Menu, MenuButton, MenuItem};
+use pwt::widget::{Button, MessageBox, Toolbar};
+
+use pwt_macros::builder;
+
+use crate::percent_encoding::percent_encode_component;
+use crate::utils::render_boolean_icon;
+use crate::{
+    ConfirmButton, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState,
+};
+
+use super::{
+    NotificationConfigOrigin, NotificationEndpointEdit, NotificationEndpointType,
+    NotificationTarget,
+};
+
+/// Notification target (endpoint) list with add, edit, remove and test actions.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct NotificationTargetsPanel {
+    #[prop_or("/cluster/notifications".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the notification API.
+    pub base_url: AttrValue,
+
+    /// Read-only view - hide toolbar and all buttons/menus to edit content.
+    #[prop_or_default]
+    #[builder]
+    pub readonly: bool,
+}
+
+impl Default for NotificationTargetsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationTargetsPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxNotificationTargetsPanel {
+    state: LoadableComponentState<ViewState>,
+    selection: Selection,
+    store: Store<NotificationTarget>,
+    columns: Rc<Vec<DataTableHeader<NotificationTarget>>>,
+}
+
+pwt::impl_deref_mut_property!(
+    ProxmoxNotificationTargetsPanel,
+    state,
+    LoadableComponentState<ViewState>
+);
+
+#[derive(PartialEq)]
+pub enum ViewState {
+    Add(NotificationEndpointType),
+    Edit(NotificationEndpointType, AttrValue),
+    TestSent(String),
+}
+
+impl ProxmoxNotificationTargetsPanel {
+    fn selected_target(&self) -> Option<NotificationTarget> {
+        let key = self.selection.selected_key()?;
+        self.store.read().lookup_record(&key).cloned()
+    }
+}
+
+impl LoadableComponent for ProxmoxNotificationTargetsPanel {
+    type Properties = NotificationTargetsPanel;
+    type Message = ();
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let selection = Selection::new().on_select({
+            let link = ctx.link().clone();
+            move |_| link.send_redraw()
+        });
+        let store =
+            Store::with_extract_key(|record: &NotificationTarget| Key::from(record.name.clone()));
+
+        let columns = Rc::new(vec![
+            DataTableColumn::new(tr!("Enable"))
+                .width("80px")
+                .justify("center")
+                .render(|record: &NotificationTarget| {
+                    render_boolean_icon(Some(!record.disable.unwrap_or(false)))
+                })
+                .into(),
+            DataTableColumn::new(tr!("Target Name"))
+                .flex(1)
+                .render(|record: &NotificationTarget| html! { &record.name })
+                .sorter(|a: &NotificationTarget, b: &NotificationTarget| a.name.cmp(&b.name))
+                .sort_order(true)
+                .into(),
+            DataTableColumn::new(tr!("Type"))
+                .width("120px")
+                .render(|record: &NotificationTarget| html! { record.ty.title() })
+                .into(),
+            DataTableColumn::new(tr!("Comment"))
+                .flex(2)
+                .render(|record: &NotificationTarget| {
+                    html! { record.comment.as_deref().unwrap_or_default() }
+                })
+                .into(),
+            DataTableColumn::new(tr!("Origin"))
+                .width("120px")
+                .render(|record: &NotificationTarget| {
+                    html! { render_origin(record.origin) }
+                })
+                .into(),
+        ]);
+
+        Self {
+            state: LoadableComponentState::new(),
+            selection,
+            store,
+            columns,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let store = self.store.clone();
+        let url = format!("{}/targets", ctx.props().base_url);
+        Box::pin(async move {
+            let data = crate::http_get(url, None).await?;
+            store.write().set_data(data);
+            Ok(())
+        })
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let props = ctx.props();
+        if props.readonly {
+            return None;
+        }
+        let selected = self.selected_target();
+
+        let mut add_menu = Menu::new();
+        for endpoint_type in NotificationEndpointType::ALL {
+            add_menu.add_item(
+                MenuItem::new(endpoint_type.title())
+                    .icon_class(endpoint_type.icon_class())
+                    .on_select(
+                        ctx.link()
+                            .change_view_callback(move |_| Some(ViewState::Add(endpoint_type))),
+                    ),
+            );
+        }
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_child(MenuButton::new(tr!("Add")).show_arrow(true).menu(add_menu))
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Edit"))
+                    .disabled(selected.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        let selected = selected.clone();
+                        move |_| {
+                            if let Some(target) = &selected {
+                                link.change_view(Some(ViewState::Edit(
+                                    target.ty,
+                                    target.name.clone().into(),
+                                )));
+                            }
+                        }
+                    }),
+            )
+            .with_child(
+                ConfirmButton::remove_entry(
+                    selected
+                        .as_ref()
+                        .map(|target| target.name.clone())
+                        .unwrap_or_default(),
+                )
+                .dangerous(true)
+                // built-in targets can only be reset by removing them, but not when unmodified
+                .disabled(selected.as_ref().map_or(true, |target| {
+                    target.origin == Some(NotificationConfigOrigin::Builtin)
+                }))
+                .on_activate({
+                    let link = ctx.link().clone();
+                    let selected = selected.clone();
+                    let base_url = props.base_url.clone();
+                    move |_| {
+                        let Some(target) = &selected else {
+                            return;
+                        };
+                        let url = format!(
+                            "{base_url}/endpoints/{}/{}",
+                            target.ty,
+                            percent_encode_component(&target.name),
+                        );
+                        let link = link.clone();
+                        link.clone().spawn(async move {
+                            if let Err(err) = crate::http_delete(url, None).await {
+                                link.show_error(tr!("Unable to remove target"), err, true);
+                            }
+                            link.send_reload();
+                        });
+                    }
+                }),
+            )
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Test"))
+                    .disabled(selected.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        let selected = selected.clone();
+                        let base_url = props.base_url.clone();
+                        move |_| {
+                            let Some(target) = &selected else {
+                                return;
+                            };
+                            let name = target.name.clone();
+                            let url = format!(
+                                "{base_url}/targets/{}/test",
+                                percent_encode_component(&name),
+                            );
+                            let link = link.clone();
+                            link.clone().spawn(async move {
+                                match crate::http_post::<()>(url, None).await {
+                                    Ok(()) => link.change_view(Some(ViewState::TestSent(name))),
+                                    Err(err) => {
+                                        link.show_error(tr!("Test notification failed"), err, false)
+                                    }
+                                }
+                            });
+                        }
+                    }),
+            );
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let mut table = DataTable::new(self.columns.clone(), self.store.clone())
+            .class("pwt-flex-fit")
+            .selection(self.selection.clone());
+
+        if !ctx.props().readonly {
+            table = table.on_row_dblclick({
+                let selection = self.selection.clone();
+                let store = self.store.clone();
+                let link = ctx.link().clone();
+                move |_: &mut _| {
+                    let Some(key) = selection.selected_key() else {
+                        return;
+                    };
+                    if let Some(target) = store.read().lookup_record(&key) {
+                        link.change_view(Some(ViewState::Edit(
+                            target.ty,
+                            target.name.clone().into(),
+                        )));
+                    }
+                }
+            });
+        }
+
+        table.into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        let base_url = ctx.props().base_url.clone();
+        match view_state {
+            ViewState::Add(endpoint_type) => Some(
+                NotificationEndpointEdit::new(*endpoint_type)
+                    .base_url(base_url)
+                    .on_close(ctx.link().change_view_callback(|_| None))
+                    .into(),
+            ),
+            ViewState::Edit(endpoint_type, name) => Some(
+                NotificationEndpointEdit::new(*endpoint_type)
+                    .base_url(base_url)
+                    .name(name.clone())
+                    .on_close(ctx.link().change_view_callback(|_| None))
+                    .into(),
+            ),
+            ViewState::TestSent(name) => Some(
+                MessageBox::new(
+                    tr!("Notification Test"),
+                    tr!("Test notification sent to target '{0}'.", name),
+                )
+                .on_close(ctx.link().change_view_callback(|_| None))
+                .into(),
+            ),
+        }
+    }
+}
+
+fn render_origin(origin: Option<NotificationConfigOrigin>) -> String {
+    match origin {
+        Some(NotificationConfigOrigin::Builtin) => tr!("Built-In"),
+        Some(NotificationConfigOrigin::ModifiedBuiltin) => tr!("Modified Built-In"),
+        Some(NotificationConfigOrigin::UserCreated) | None => tr!("Custom"),
+    }
+}
+
+impl From<NotificationTargetsPanel> for VNode {
+    fn from(val: NotificationTargetsPanel) -> Self {
+        let comp = VComp::new::<LoadableComponentMaster<ProxmoxNotificationTargetsPanel>>(
+            Rc::new(val),
+            None,
+        );
+        VNode::from(comp)
+    }
+}
@@ -137,6 +137,12 @@ impl ObjectGridRow {
         self.row.set_renderer(renderer);
     }
 
+    /// Render the value as boolean badge, see [KVGridRow::boolean].
+    pub fn boolean(mut self, default: Option<bool>) -> Self {
+        self.row = self.row.boolean(default);
+        self
+    }
+
     pub fn editor(
         mut self,
         editor: impl 'static + Fn(&FormContext, &str, &Value, &Value) -> Html,
@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::Key;
+
+use pwt::prelude::*;
+use pwt::props::RenderFn;
+use pwt::widget::nav::{Menu, MenuItem};
+use pwt::widget::{SelectionView, SelectionViewRenderInfo};
+
+use crate::AclContext;
+
+/// Privileges required to see a panel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PanelPrivilege {
+    /// The ACL path, split into its components.
+    pub path: Vec<String>,
+    /// The privilege bits, as used by [AclContext::check_privs].
+    pub privs: u64,
+    /// Also grant access if any of the privileges is set below the path.
+    pub any_below: bool,
+}
+
+/// A panel registered in a [PanelRegistry].
+#[derive(Clone, PartialEq)]
+pub struct PanelEntry {
+    id: Key,
+    title: AttrValue,
+    icon_class: Option<AttrValue>,
+    parent: Option<Key>,
+    privilege: Option<PanelPrivilege>,
+    feature: Option<AttrValue>,
+    renderer: RenderFn<SelectionViewRenderInfo>,
+}
+
+impl PanelEntry {
+    /// Create a new panel entry.
+    ///
+    /// The `id` is used as menu and route key, the `renderer` creates the panel content.
+    pub fn new(
+        id: impl Into<Key>,
+        title: impl Into<AttrValue>,
+        renderer: impl 'static + Fn(&SelectionViewRenderInfo) -> Html,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            icon_class: None,
+            parent: None,
+            privilege: None,
+            feature: None,
+            renderer: RenderFn::new(renderer),
+        }
+    }
+
+    /// Builder style method to set the menu icon class.
+    pub fn icon_class(mut self, icon_class: impl IntoPropValue<Option<AttrValue>>) -> Self {
+        self.icon_class = icon_class.into_prop_value();
+        self
+    }
+
+    /// Builder style method to nest the panel below another panel in the navigation tree.
+    pub fn parent(mut self, parent: impl Into<Key>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    /// Builder style method to require privileges on an ACL path.
+    pub fn privileges(mut self, path: &[&str], privs: u64) -> Self {
+        self.privilege = Some(PanelPrivilege {
+            path: path.iter().map(|c| c.to_string()).collect(),
+            privs,
+            any_below: false,
+        });
+        self
+    }
+
+    /// Builder style method to require any of the privileges on or below an ACL path.
+    pub fn privileges_below(mut self, path: &[&str], privs: u64) -> Self {
+        self.privilege = Some(PanelPrivilege {
+            path: path.iter().map(|c| c.to_string()).collect(),
+            privs,
+            any_below: true,
+        });
+        self
+    }
+
+    /// Builder style method to tie the panel to a feature, see [PanelRegistry::set_feature].
+    pub fn feature(mut self, feature: impl IntoPropValue<Option<AttrValue>>) -> Self {
+        self.feature = feature.into_prop_value();
+        self
+    }
+
+    /// The panel id.
+    pub fn id(&self) -> &Key {
+        &self.id
+    }
+
+    /// The panel title.
+    pub fn title(&self) -> &AttrValue {
+        &self.title
+    }
+
+    /// Render the panel content.
+    pub fn render(&self, info: &SelectionViewRenderInfo) -> Html {
+        self.renderer.apply(info)
+    }
+
+    fn check_privilege(&self, acl: Option<&AclContext>) -> bool {
+        let (Some(privilege), Some(acl)) = (&self.privilege, acl) else {
+            // without ACL information the server still enforces the permissions
+            return true;
+        };
+        let path: Vec<&str> = privilege.path.iter().map(String::as_str).collect();
+        if privilege.any_below {
+            acl.any_privs_below(&path, privilege.privs)
+        } else {
+            acl.check_privs(&path, privilege.privs)
+        }
+    }
+}
+
+/// Declarative list of the panels of a product shell.
+///
+/// Components register themselves with an id, title, icon, required privileges and a render
+/// function. The product composes its navigation menu ([PanelRegistry::menu]) and its route
+/// table ([PanelRegistry::selection_view]) from the registry, so panels can be hidden at runtime,
+/// either because the user lacks the privileges or because a feature is disabled (for example
+/// the Ceph panels if Ceph is not installed).
+///
+/// Panels are listed in registration order, parents have to be registered before their
+/// children.
+#[derive(Clone, Default, PartialEq)]
+pub struct PanelRegistry {
+    panels: Vec<PanelEntry>,
+    disabled_features: HashSet<AttrValue>,
+}
+
+impl PanelRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder style method to register a panel.
+    pub fn with_panel(mut self, panel: PanelEntry) -> Self {
+        self.register(panel);
+        self
+    }
+
+    /// Register a panel, replacing a previously registered panel with the same id.
+    pub fn register(&mut self, panel: PanelEntry) {
+        match self.panels.iter_mut().find(|p| p.id == panel.id) {
+            Some(existing) => *existing = panel,
+            None => self.panels.push(panel),
+        }
+    }
+
+    /// Remove a panel (and its children) from the registry.
+    pub fn unregister(&mut self, id: &Key) {
+        let mut removed = vec![id.clone()];
+        while let Some(id) = removed.pop() {
+            self.panels.retain(|panel| {
+                if panel.id == id {
+                    return false;
+                }
+                if panel.parent.as_ref() == Some(&id) {
+                    removed.push(panel.id.clone());
+                    return false;
+                }
+                true
+            });
+        }
+    }
+
+    /// Lookup a panel by id.
+    pub fn get(&self, id: &Key) -> Option<&PanelEntry> {
+        self.panels.iter().find(|panel| &panel.id == id)
+    }
+
+    /// Enable or disable a feature. All features are enabled by default.
+    pub fn set_feature(&mut self, feature: impl Into<AttrValue>, enabled: bool) {
+        let feature = feature.into();
+        if enabled {
+            self.disabled_features.remove(&feature);
+        } else {
+            self.disabled_features.insert(feature);
+        }
+    }
+
+    /// Builder style method to enable or disable a feature.
+    pub fn feature(mut self, feature: impl Into<AttrValue>, enabled: bool) -> Self {
+        self.set_feature(feature, enabled);
+        self
+    }
+
+    /// Returns true if the panel is registered, its feature is enabled, the user has the
+    /// required privileges and all of its parents are visible.
+    pub fn is_visible(&self, id: &Key, acl: Option<&AclContext>) -> bool {
+        let mut current = Some(id);
+        // guard against parent cycles
+        let mut seen = HashSet::new();
+        while let Some(id) = current {
+            if !seen.insert(id.clone()) {
+                return false;
+            }
+            let Some(panel) = self.get(id) else {
+                return false;
+            };
+            if let Some(feature) = &panel.feature {
+                if self.disabled_features.contains(feature) {
+                    return false;
+                }
+            }
+            if !panel.check_privilege(acl) {
+                return false;
+            }
+            current = panel.parent.as_ref();
+        }
+        true
+    }
+
+    /// The visible panels, in registration order.
+    pub fn visible_panels<'a>(
+        &'a self,
+        acl: Option<&'a AclContext>,
+    ) -> impl Iterator<Item = &'a PanelEntry> + 'a {
+        self.panels
+            .iter()
+            .filter(move |panel| self.is_visible(&panel.id, acl))
+    }
+
+    /// Build the navigation menu from the visible panels.
+    pub fn menu(&self, acl: Option<&AclContext>) -> Menu {
+        self.submenu(None, acl)
+    }
+
+    fn submenu(&self, parent: Option<&Key>, acl: Option<&AclContext>) -> Menu {
+        let mut menu = Menu::new();
+        for panel in self.visible_panels(acl) {
+            if panel.parent.as_ref() != parent {
+                continue;
+            }
+            let mut item = MenuItem::new(panel.title.clone())
+                .key(panel.id.clone())
+                .icon_class(panel.icon_class.clone());
+
+            let has_children = self
+                .panels
+                .iter()
+                .any(|p| p.parent.as_ref() == Some(&panel.id));
+            if has_children {
+                item = item.submenu(self.submenu(Some(&panel.id), acl));
+            }
+            menu.add_item(item);
+        }
+        menu
+    }
+
+    /// Add the render functions of all visible panels to a [SelectionView].
+    pub fn selection_view(
+        &self,
+        mut view: SelectionView,
+        acl: Option<&AclContext>,
+    ) -> SelectionView {
+        for panel in self.visible_panels(acl) {
+            let renderer = panel.renderer.clone();
+            view.add_builder(panel.id.clone(), move |info: &SelectionViewRenderInfo| {
+                renderer.apply(info)
+            });
+        }
+        view
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn registry() -> PanelRegistry {
+        PanelRegistry::new()
+            .with_panel(PanelEntry::new("dashboard", "Dashboard", |_| html! {}))
+            .with_panel(PanelEntry::new("ceph", "Ceph", |_| html! {}).feature("ceph"))
+            .with_panel(PanelEntry::new("ceph-osd", "OSD", |_| html! {}).parent("ceph"))
+    }
+
+    fn visible_ids(registry: &PanelRegistry) -> Vec<String> {
+        registry
+            .visible_panels(None)
+            .map(|panel| panel.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_feature_toggle() {
+        let mut registry = registry();
+        assert_eq!(visible_ids(&registry), ["dashboard", "ceph", "ceph-osd"]);
+
+        registry.set_feature("ceph", false);
+        assert_eq!(visible_ids(&registry), ["dashboard"]);
+
+        registry.set_feature("ceph", true);
+        assert_eq!(visible_ids(&registry), ["dashboard", "ceph", "ceph-osd"]);
+    }
+
+    #[test]
+    fn test_register_and_unregister() {
+        let mut registry = registry();
+
+        registry.register(PanelEntry::new("dashboard", "Overview", |_| html! {}));
+        assert_eq!(visible_ids(&registry), ["dashboard", "ceph", "ceph-osd"]);
+        assert_eq!(
+            registry
+                .get(&Key::from("dashboard"))
+                .unwrap()
+                .title()
+                .as_str(),
+            "Overview"
+        );
+
+        registry.unregister(&Key::from("ceph"));
+        assert_eq!(visible_ids(&registry), ["dashboard"]);
+    }
+}
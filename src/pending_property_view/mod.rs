@@ -3,13 +3,19 @@ pub use pending_property_grid::PendingPropertyGrid;
 
 mod pending_property_list;
 pub use pending_property_list::PendingPropertyList;
+
+mod pending_diff_dialog;
+pub use pending_diff_dialog::{
+    pending_config_diff, PendingDiffDialog, PendingDiffEntry, PendingDiffKind,
+    ProxmoxPendingDiffDialog,
+};
 use yew::html::Scope;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::DerefMut;
+use std::rc::Rc;
 
 use anyhow::Error;
-use gloo_timers::callback::Timeout;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -18,11 +24,15 @@ use proxmox_client::ApiResponseData;
 
 use pwt::props::SubmitCallback;
 use pwt::touch::{SnackBar, SnackBarContextExt};
-use pwt::widget::AlertDialog;
+use pwt::widget::{ActionIcon, AlertDialog, Container, Tooltip};
 use pwt::AsyncAbortGuard;
 use pwt::{prelude::*, AsyncPool};
 
-use crate::{http_get_full, ApiLoadCallback, EditableProperty, PropertyEditDialog};
+use crate::pve_api_types::PendingDeleteState;
+use crate::{
+    http_get_full, ApiLoadCallback, EditableProperty, PropertyEditDialog, RefreshScheduler,
+    RefreshTimeout,
+};
 use pve_api_types::PendingConfigValue;
 
 /// Pending configuration data
@@ -35,6 +45,8 @@ pub struct PvePendingConfiguration {
     pub current: Value,
     pub pending: Value,
     pub keys: HashSet<String>,
+    /// Keys marked for deletion, with their delete state.
+    pub delete: HashMap<String, PendingDeleteState>,
 }
 
 impl Default for PvePendingConfiguration {
@@ -49,17 +61,39 @@ impl PvePendingConfiguration {
             current: Value::Null,
             pending: Value::Null,
             keys: HashSet::new(),
+            delete: HashMap::new(),
         }
     }
 
     pub fn from_config_array(data: Vec<PendingConfigValue>) -> Self {
+        let delete = pve_pending_config_delete_states(&data);
         let (current, pending, keys) = pve_pending_config_array_to_objects(data);
         Self {
             current,
             pending,
             keys,
+            delete,
         }
     }
+
+    /// Returns the (sorted) keys with pending changes, including keys marked for deletion.
+    pub fn pending_keys(&self) -> Vec<String> {
+        pending_config_diff(self)
+            .into_iter()
+            .filter(|entry| entry.kind != PendingDiffKind::Unchanged)
+            .map(|entry| entry.key)
+            .collect()
+    }
+
+    /// Returns true if there are pending changes.
+    pub fn has_pending_changes(&self) -> bool {
+        !self.pending_keys().is_empty()
+    }
+
+    /// Returns the pending delete state for configuration key `name`.
+    pub fn delete_state(&self, name: &str) -> PendingDeleteState {
+        self.delete.get(name).copied().unwrap_or_default()
+    }
 }
 
 /// Load data using PVE pending api
@@ -86,6 +120,7 @@ pub fn pending_typed_load<T: DeserializeOwned + Serialize>(
 pub fn pve_pending_config_array_to_objects_typed<T: DeserializeOwned + Serialize>(
     data: Vec<PendingConfigValue>,
 ) -> Result<PvePendingConfiguration, Error> {
+    let delete = pve_pending_config_delete_states(&data);
     let (current, pending, keys) = pve_pending_config_array_to_objects(data);
 
     let current: T = serde_json::from_value(current)?;
@@ -98,9 +133,18 @@ pub fn pve_pending_config_array_to_objects_typed<T: DeserializeOwned + Serialize
         current,
         pending,
         keys,
+        delete,
     })
 }
 
+/// The configuration keys to revert for `property`, `None` if the property has no name.
+fn property_revert_keys(property: &EditableProperty) -> Option<Vec<String>> {
+    match property.revert_keys.as_deref() {
+        Some(keys) => Some(keys.iter().map(|a| a.to_string()).collect()),
+        None => property.get_name().map(|name| vec![name.to_string()]),
+    }
+}
+
 pub enum PendingPropertyViewMsg<M> {
     Load,
     LoadResult(Result<PvePendingConfiguration, String>),
@@ -108,6 +152,8 @@ pub enum PendingPropertyViewMsg<M> {
     EditProperty(EditableProperty, Option<SubmitCallback<Value>>),
     AddProperty(EditableProperty, Option<SubmitCallback<Value>>),
     RevertProperty(EditableProperty),
+    /// Revert the pending changes of the given (rendered) properties.
+    RevertAll(Rc<Vec<EditableProperty>>),
     CommandResult(Result<(), Error>, String),
     Delete(String, Option<SubmitCallback<Value>>),
     Redraw,
@@ -120,6 +166,7 @@ pub trait PendingPropertyViewScopeExt<M> {
     fn send_redraw(&self);
     fn send_show_dialog(&self, dialog: Option<Html>);
     fn send_revert_property(&self, property: EditableProperty);
+    fn send_revert_all(&self, properties: Rc<Vec<EditableProperty>>);
     fn send_delete(&self, property_name: &str, on_submit: Option<SubmitCallback<Value>>);
     fn send_add_property(
         &self,
@@ -174,6 +221,10 @@ impl<M, T: 'static + PendingPropertyView<Message = M>> PendingPropertyViewScopeE
         self.send_message(PendingPropertyViewMsg::RevertProperty(property));
     }
 
+    fn send_revert_all(&self, properties: Rc<Vec<EditableProperty>>) {
+        self.send_message(PendingPropertyViewMsg::RevertAll(properties));
+    }
+
     fn send_delete(&self, property_name: &str, on_submit: Option<SubmitCallback<Value>>) {
         self.send_message(PendingPropertyViewMsg::Delete(
             property_name.to_string(),
@@ -202,11 +253,15 @@ impl<M, T: 'static + PendingPropertyView<Message = M>> PendingPropertyViewScopeE
 pub struct PendingPropertyViewState {
     pub data: Option<PvePendingConfiguration>,
     pub error: Option<String>,
-    pub reload_timeout: Option<Timeout>,
+    pub reload_timeout: Option<RefreshTimeout>,
+    /// Number of consecutive load errors, used to back off reloading.
+    pub load_errors: u32,
     pub load_guard: Option<AsyncAbortGuard>,
     pub revert_guard: Option<AsyncAbortGuard>,
     pub async_pool: AsyncPool,
     pub dialog: Option<Html>,
+    /// Whether the loaded configuration has pending changes (`None` before the first load).
+    pub has_pending_changes: Option<bool>,
 }
 
 pub trait PendingPropertyView: DerefMut<Target = PendingPropertyViewState> {
@@ -222,6 +277,19 @@ pub trait PendingPropertyView: DerefMut<Target = PendingPropertyViewState> {
 
     fn on_submit(props: &Self::Properties) -> Option<SubmitCallback<Value>>;
 
+    /// Called after loading when the configuration changes between having and not having
+    /// pending changes.
+    #[allow(unused_variables)]
+    fn on_pending_change(props: &Self::Properties) -> Option<Callback<bool>> {
+        None
+    }
+
+    /// The refresh scheduler, falls back to the one provided by the context.
+    #[allow(unused_variables)]
+    fn refresh_scheduler(props: &Self::Properties) -> Option<RefreshScheduler> {
+        None
+    }
+
     fn create(ctx: &Context<PvePendingPropertyView<Self>>) -> Self
     where
         Self: 'static + Sized;
@@ -264,9 +332,11 @@ impl PendingPropertyViewState {
             Ok(data) => {
                 self.error = None;
                 self.data = Some(data);
+                self.load_errors = 0;
             }
             Err(err) => {
                 self.error = Some(err);
+                self.load_errors = self.load_errors.saturating_add(1);
             }
         }
     }
@@ -313,18 +383,9 @@ impl<T: 'static + PendingPropertyView> Component for PvePendingPropertyView<T> {
             }
             PendingPropertyViewMsg::RevertProperty(property) => {
                 let link = ctx.link().clone();
-                let keys: Vec<String> = match property.revert_keys.as_deref() {
-                    Some(keys) => keys.iter().map(|a| a.to_string()).collect(),
-                    None::<_> => {
-                        if let Some(name) = property.get_name() {
-                            vec![name.to_string()]
-                        } else {
-                            log::error!(
-                                "pending property list: cannot revert property without name",
-                            );
-                            return false;
-                        }
-                    }
+                let Some(keys) = property_revert_keys(&property) else {
+                    log::error!("pending property list: cannot revert property without name");
+                    return false;
                 };
                 if let Some(on_submit) = T::on_submit(props) {
                     let param = json!({ "revert": keys });
@@ -334,6 +395,36 @@ impl<T: 'static + PendingPropertyView> Component for PvePendingPropertyView<T> {
                     }));
                 }
             }
+            PendingPropertyViewMsg::RevertAll(properties) => {
+                let link = ctx.link().clone();
+                // only revert the keys of the properties shown by this view
+                let revert_keys: HashSet<String> = properties
+                    .iter()
+                    .filter_map(property_revert_keys)
+                    .flatten()
+                    .collect();
+                let keys: Vec<String> = match &self.state.data {
+                    Some(data) => data
+                        .pending_keys()
+                        .into_iter()
+                        .filter(|key| revert_keys.contains(key))
+                        .collect(),
+                    None => return false,
+                };
+                if keys.is_empty() {
+                    return false;
+                }
+                if let Some(on_submit) = T::on_submit(props) {
+                    let param = json!({ "revert": keys });
+                    self.state.revert_guard = Some(AsyncAbortGuard::spawn(async move {
+                        let result = on_submit.apply(param).await;
+                        link.send_message(PendingPropertyViewMsg::CommandResult(
+                            result,
+                            tr!("Revert pending changes failed"),
+                        ));
+                    }));
+                }
+            }
             PendingPropertyViewMsg::CommandResult(result, message) => {
                 if let Err(err) = result {
                     if T::MOBILE {
@@ -399,11 +490,23 @@ impl<T: 'static + PendingPropertyView> Component for PvePendingPropertyView<T> {
             }
             PendingPropertyViewMsg::LoadResult(result) => {
                 self.state.set_load_result(result);
+                if let Some(data) = &self.state.data {
+                    let has_pending_changes = data.has_pending_changes();
+                    if self.state.has_pending_changes != Some(has_pending_changes) {
+                        self.state.has_pending_changes = Some(has_pending_changes);
+                        if let Some(on_pending_change) = T::on_pending_change(props) {
+                            on_pending_change.emit(has_pending_changes);
+                        }
+                    }
+                }
                 self.state.update_data(ctx);
-                let link = ctx.link().clone();
-                self.state.reload_timeout = Some(Timeout::new(3000, move || {
-                    link.send_message(PendingPropertyViewMsg::Load);
-                }));
+                let scheduler = T::refresh_scheduler(props)
+                    .or_else(|| RefreshScheduler::from_context(ctx.link()))
+                    .unwrap_or_default();
+                self.state.reload_timeout = Some(scheduler.schedule(
+                    self.state.load_errors,
+                    ctx.link().callback(|_| PendingPropertyViewMsg::Load),
+                ));
             }
             PendingPropertyViewMsg::ShowDialog(dialog) => {
                 if dialog.is_none() && self.state.reload_timeout.is_some() {
@@ -439,6 +542,45 @@ pub fn render_pending_property_value(
     }
 }
 
+/// Render the marker shown instead of the pending value for keys marked for deletion.
+pub fn render_pending_delete_marker(state: PendingDeleteState) -> Html {
+    let text = match state {
+        PendingDeleteState::Force => tr!("Marked for deletion (forced)"),
+        _ => tr!("Marked for deletion"),
+    };
+    Container::new()
+        .class("pwt-color-error")
+        .with_child(text)
+        .into()
+}
+
+/// Render the current value of a property marked for deletion (struck through).
+pub fn render_pending_deleted_value(value: Html) -> Html {
+    Container::new()
+        .style("text-decoration", "line-through")
+        .with_child(value)
+        .into()
+}
+
+/// Render a button to undo the pending deletion of a property.
+pub fn render_undo_delete_button(on_undo: Callback<Event>) -> Html {
+    Tooltip::new(ActionIcon::new("fa fa-undo").on_activate(on_undo))
+        .tip(tr!("Undo deletion"))
+        .into()
+}
+
+/// Extract the delete state of all keys marked for deletion.
+pub fn pve_pending_config_delete_states(
+    data: &[PendingConfigValue],
+) -> HashMap<String, PendingDeleteState> {
+    data.iter()
+        .filter_map(|item| {
+            let state = PendingDeleteState::from(item);
+            state.is_marked().then(|| (item.key.clone(), state))
+        })
+        .collect()
+}
+
 /// Parse PVE pending configuration array
 ///
 /// Returns 2 Objects, containing current and pending configuration,
@@ -456,7 +598,7 @@ pub fn pve_pending_config_array_to_objects(
         if let Some(value) = item.value.clone() {
             current.insert(item.key.clone(), value);
         }
-        if matches!(item.delete, Some(1) | Some(2)) {
+        if PendingDeleteState::from(item).is_marked() {
             continue;
         }
         if let Some(value) = item.pending.clone() {
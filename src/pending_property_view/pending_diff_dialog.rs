@@ -0,0 +1,462 @@
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{json, Value};
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::css::{FlexFit, FontColor};
+use pwt::prelude::*;
+use pwt::props::{ExtractPrimaryKey, IntoSubmitCallback, SubmitCallback};
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::form::Checkbox;
+use pwt::widget::{error_message, AlertDialog, Button, Column, Container, Dialog, Mask, Toolbar};
+use pwt::AsyncAbortGuard;
+
+use pwt_macros::builder;
+
+use crate::property_view::render_property_value;
+use crate::{ApiLoadCallback, EditableProperty, IntoApiLoadCallback};
+
+use super::PvePendingConfiguration;
+
+/// How a configuration key differs between current and pending configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PendingDiffKind {
+    Unchanged,
+    /// The key only exists in the pending configuration.
+    Added,
+    /// The key gets deleted.
+    Removed,
+    /// The value changes.
+    Changed,
+}
+
+/// A single configuration key of a [pending_config_diff].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingDiffEntry {
+    pub key: String,
+    pub current: Option<Value>,
+    pub pending: Option<Value>,
+    pub kind: PendingDiffKind,
+}
+
+impl ExtractPrimaryKey for PendingDiffEntry {
+    fn extract_key(&self) -> Key {
+        Key::from(self.key.as_str())
+    }
+}
+
+/// Compare current and pending configuration, sorted by key.
+///
+/// The `digest` is not part of the result.
+pub fn pending_config_diff(data: &PvePendingConfiguration) -> Vec<PendingDiffEntry> {
+    let mut keys: BTreeSet<&str> = data.keys.iter().map(String::as_str).collect();
+    for config in [&data.current, &data.pending] {
+        if let Some(map) = config.as_object() {
+            keys.extend(map.keys().map(String::as_str));
+        }
+    }
+    keys.remove("digest");
+
+    keys.into_iter()
+        .map(|key| {
+            let current = data.current.get(key).filter(|v| !v.is_null()).cloned();
+            let pending = data.pending.get(key).filter(|v| !v.is_null()).cloned();
+            let kind = if data.delete_state(key).is_marked() {
+                PendingDiffKind::Removed
+            } else {
+                match (&current, &pending) {
+                    (None, Some(_)) => PendingDiffKind::Added,
+                    (Some(_), None) => PendingDiffKind::Removed,
+                    (current, pending) if current != pending => PendingDiffKind::Changed,
+                    _ => PendingDiffKind::Unchanged,
+                }
+            };
+            PendingDiffEntry {
+                key: key.to_string(),
+                current,
+                pending,
+                kind,
+            }
+        })
+        .collect()
+}
+
+/// Dialog comparing the current and the pending configuration of a guest.
+///
+/// Added, removed and changed keys are highlighted, and the selected pending changes can
+/// be reverted at once (by submitting `{ "revert": [keys] }`).
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct PendingDiffDialog {
+    /// Yew component key
+    #[prop_or_default]
+    pub key: Option<Key>,
+
+    /// Dialog title
+    #[prop_or_default]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub title: AttrValue,
+
+    /// Load the configuration with pending changes.
+    #[builder_cb(IntoApiLoadCallback, into_api_load_callback, PvePendingConfiguration)]
+    #[prop_or_default]
+    pub pending_loader: Option<ApiLoadCallback<PvePendingConfiguration>>,
+
+    /// Property definitions, used for titles and value renderers.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub properties: Option<Rc<Vec<EditableProperty>>>,
+
+    /// Submit callback, used to revert pending changes.
+    #[builder_cb(IntoSubmitCallback, into_submit_callback, Value)]
+    #[prop_or_default]
+    pub on_submit: Option<SubmitCallback<Value>>,
+
+    /// Close callback.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_close: Option<Callback<()>>,
+}
+
+impl Default for PendingDiffDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PendingDiffDialog {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        yew::props!(Self {
+            title: tr!("Pending Changes"),
+        })
+    }
+
+    pwt::impl_yew_std_props_builder!();
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Load,
+    LoadResult(Result<PvePendingConfiguration, Error>),
+    OnlyChanges(bool),
+    SelectionChange,
+    Revert,
+    RevertResult(Result<(), Error>),
+    ClearError,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxPendingDiffDialog {
+    data: Option<Result<Rc<PvePendingConfiguration>, String>>,
+    store: Store<PendingDiffEntry>,
+    selection: Selection,
+    only_changes: bool,
+    reverting: bool,
+    revert_error: Option<String>,
+    load_guard: Option<AsyncAbortGuard>,
+    revert_guard: Option<AsyncAbortGuard>,
+}
+
+impl ProxmoxPendingDiffDialog {
+    fn update_store(&mut self) {
+        let entries = match &self.data {
+            Some(Ok(data)) => pending_config_diff(data),
+            _ => Vec::new(),
+        };
+        let only_changes = self.only_changes;
+        self.store.set_data(
+            entries
+                .into_iter()
+                .filter(|entry| !only_changes || entry.kind != PendingDiffKind::Unchanged)
+                .collect(),
+        );
+    }
+
+    // keys which can be reverted
+    fn selected_changes(&self) -> Vec<String> {
+        let store = self.store.read();
+        self.selection
+            .selected_keys()
+            .iter()
+            .filter_map(|key| store.lookup_record(key))
+            .filter(|entry| entry.kind != PendingDiffKind::Unchanged)
+            .map(|entry| entry.key.clone())
+            .collect()
+    }
+
+    fn columns(&self, ctx: &Context<Self>) -> Rc<Vec<DataTableHeader<PendingDiffEntry>>> {
+        let props = ctx.props();
+
+        let properties: Rc<HashMap<String, EditableProperty>> = Rc::new(
+            props
+                .properties
+                .iter()
+                .flat_map(|list| list.iter())
+                .filter_map(|property| {
+                    let name = property.get_name()?;
+                    Some((name.to_string(), property.clone()))
+                })
+                .collect(),
+        );
+
+        let data = match &self.data {
+            Some(Ok(data)) => Some(Rc::clone(data)),
+            _ => None,
+        };
+
+        let render_value = {
+            let properties = Rc::clone(&properties);
+            move |entry: &PendingDiffEntry, pending: bool| -> Html {
+                let value = if pending {
+                    &entry.pending
+                } else {
+                    &entry.current
+                };
+                let Some(value) = value else {
+                    return html! {"-"};
+                };
+                match (properties.get(&entry.key), &data) {
+                    (Some(property), Some(data)) => {
+                        let record = if pending {
+                            &data.pending
+                        } else {
+                            &data.current
+                        };
+                        render_property_value(record, property)
+                    }
+                    _ => match value {
+                        Value::String(s) => html! {s},
+                        v => html! {v.to_string()},
+                    },
+                }
+            }
+        };
+
+        Rc::new(vec![
+            DataTableColumn::selection_indicator().into(),
+            DataTableColumn::new(tr!("Property"))
+                .width("150px")
+                .render(
+                    move |entry: &PendingDiffEntry| match properties.get(&entry.key) {
+                        Some(property) if !property.title.is_empty() => {
+                            html! {property.title.clone()}
+                        }
+                        _ => html! {entry.key.clone()},
+                    },
+                )
+                .into(),
+            DataTableColumn::new(tr!("Current"))
+                .flex(1)
+                .render({
+                    let render_value = render_value.clone();
+                    move |entry: &PendingDiffEntry| {
+                        let value = render_value(entry, false);
+                        match entry.kind {
+                            PendingDiffKind::Removed => Container::new()
+                                .class(FontColor::Error)
+                                .style("text-decoration", "line-through")
+                                .with_child(value)
+                                .into(),
+                            _ => value,
+                        }
+                    }
+                })
+                .into(),
+            DataTableColumn::new(tr!("Pending"))
+                .flex(1)
+                .render(move |entry: &PendingDiffEntry| {
+                    let color = match entry.kind {
+                        PendingDiffKind::Unchanged => return html! {},
+                        PendingDiffKind::Removed => {
+                            return Container::new()
+                                .class(FontColor::Error)
+                                .with_child(tr!("Marked for deletion"))
+                                .into()
+                        }
+                        PendingDiffKind::Added => FontColor::Success,
+                        PendingDiffKind::Changed => FontColor::Warning,
+                    };
+                    Container::new()
+                        .class(color)
+                        .with_child(render_value(entry, true))
+                        .into()
+                })
+                .into(),
+        ])
+    }
+}
+
+impl Component for ProxmoxPendingDiffDialog {
+    type Message = Msg;
+    type Properties = PendingDiffDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        let selection = Selection::new()
+            .multiselect(true)
+            .on_select(ctx.link().callback(|_| Msg::SelectionChange));
+        Self {
+            data: None,
+            store: Store::new(),
+            selection,
+            only_changes: true,
+            reverting: false,
+            revert_error: None,
+            load_guard: None,
+            revert_guard: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                if let Some(loader) = props.pending_loader.clone() {
+                    let link = ctx.link().clone();
+                    self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                        let result = loader.apply().await.map(|resp| resp.data);
+                        link.send_message(Msg::LoadResult(result));
+                    }));
+                }
+                false
+            }
+            Msg::LoadResult(result) => {
+                self.data = Some(result.map(Rc::new).map_err(|err| err.to_string()));
+                self.update_store();
+                true
+            }
+            Msg::OnlyChanges(only_changes) => {
+                self.only_changes = only_changes;
+                self.update_store();
+                true
+            }
+            Msg::SelectionChange => true,
+            Msg::Revert => {
+                let keys = self.selected_changes();
+                if keys.is_empty() {
+                    return false;
+                }
+                if let Some(on_submit) = props.on_submit.clone() {
+                    let link = ctx.link().clone();
+                    let param = json!({ "revert": keys });
+                    self.reverting = true;
+                    self.revert_guard = Some(AsyncAbortGuard::spawn(async move {
+                        let result = on_submit.apply(param).await;
+                        link.send_message(Msg::RevertResult(result));
+                    }));
+                }
+                true
+            }
+            Msg::RevertResult(result) => {
+                self.reverting = false;
+                match result {
+                    Ok(()) => {
+                        self.selection.clear();
+                        ctx.link().send_message(Msg::Load);
+                    }
+                    Err(err) => self.revert_error = Some(err.to_string()),
+                }
+                true
+            }
+            Msg::ClearError => {
+                self.revert_error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let can_revert = props.on_submit.is_some() && !self.selected_changes().is_empty();
+
+        let toolbar = Toolbar::new()
+            .class("pwt-border-bottom")
+            .with_child(
+                Checkbox::new()
+                    .box_label(tr!("Only show changes"))
+                    .checked(self.only_changes)
+                    .on_change(ctx.link().callback(Msg::OnlyChanges)),
+            )
+            .with_flex_spacer()
+            .with_child(
+                Button::new(tr!("Revert Selected"))
+                    .icon_class("fa fa-undo")
+                    .disabled(!can_revert || self.reverting)
+                    .onclick(ctx.link().callback(|_| Msg::Revert)),
+            );
+
+        let content: Html = match &self.data {
+            Some(Err(err)) => error_message(err).padding(2).into(),
+            _ => DataTable::new(self.columns(ctx), self.store.clone())
+                .class(FlexFit)
+                .selection(self.selection.clone())
+                .into(),
+        };
+
+        let panel = Column::new()
+            .class(FlexFit)
+            .with_child(toolbar)
+            .with_child(content);
+
+        Dialog::new(props.title.clone())
+            .resizable(true)
+            .style("width", "800px")
+            .style("height", "500px")
+            .on_close(props.on_close.clone())
+            .with_child(
+                Mask::new(panel)
+                    .class(FlexFit)
+                    .visible(self.data.is_none() || self.reverting),
+            )
+            .with_optional_child(self.revert_error.as_ref().map(|err| {
+                AlertDialog::new(err).on_close(ctx.link().callback(|_| Msg::ClearError))
+            }))
+            .into()
+    }
+}
+
+impl From<PendingDiffDialog> for VNode {
+    fn from(val: PendingDiffDialog) -> Self {
+        let key = val.key.clone();
+        let comp = VComp::new::<ProxmoxPendingDiffDialog>(Rc::new(val), key);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pending_config_diff() {
+        let data = PvePendingConfiguration {
+            current: json!({ "cores": 2, "memory": 2048, "name": "vm", "digest": "abc" }),
+            pending: json!({ "cores": 4, "name": "vm", "onboot": 1 }),
+            keys: ["cores", "memory", "name", "onboot"]
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            delete: HashMap::new(),
+        };
+        let diff: Vec<(String, PendingDiffKind)> = pending_config_diff(&data)
+            .into_iter()
+            .map(|entry| (entry.key, entry.kind))
+            .collect();
+        assert_eq!(
+            diff,
+            [
+                ("cores".to_string(), PendingDiffKind::Changed),
+                ("memory".to_string(), PendingDiffKind::Removed),
+                ("name".to_string(), PendingDiffKind::Unchanged),
+                ("onboot".to_string(), PendingDiffKind::Added),
+            ]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use serde_json::Value;
 
-use yew::html::IntoEventCallback;
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
@@ -11,9 +11,9 @@ use pwt::state::{Selection, Store};
 use pwt::widget::data_table::{
     DataTable, DataTableHeader, DataTableKeyboardEvent, DataTableMouseEvent,
 };
-use pwt::widget::{Button, Column, Container, Toolbar};
+use pwt::widget::{Button, Column, Container, Row, Toolbar};
 
-use crate::{ApiLoadCallback, IntoApiLoadCallback};
+use crate::{ApiLoadCallback, ConfirmButton, IntoApiLoadCallback, RefreshScheduler};
 
 use pwt_macros::builder;
 
@@ -55,6 +55,24 @@ pub struct PendingPropertyGrid {
     #[prop_or_default]
     pub on_submit: Option<SubmitCallback<Value>>,
 
+    /// Reload interval and back off settings (default is the scheduler provided by the
+    /// context).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub refresh_scheduler: Option<RefreshScheduler>,
+
+    /// Show a button to revert the pending changes of all properties at once (after
+    /// confirmation).
+    #[builder]
+    #[prop_or_default]
+    pub revert_all: bool,
+
+    /// Called when the configuration changes between having and not having pending changes,
+    /// for example to show an "unapplied changes" banner.
+    #[builder_cb(IntoEventCallback, into_event_callback, bool)]
+    #[prop_or_default]
+    pub on_pending_change: Option<Callback<bool>>,
+
     /// Select callback.
     #[prop_or_default]
     #[builder_cb(IntoEventCallback, into_event_callback, Option<Key>)]
@@ -125,7 +143,17 @@ impl PvePendingPropertyGrid {
                             }
                         }
                     }),
-            );
+            )
+            .with_optional_child(ctx.props().revert_all.then(|| {
+                ConfirmButton::new(tr!("Revert All"))
+                    .disabled(self.has_pending_changes != Some(true))
+                    .confirm_message(tr!("Are you sure you want to revert all pending changes?"))
+                    .on_activate({
+                        let link = link.clone();
+                        let properties = Rc::clone(&ctx.props().properties);
+                        move |_| link.send_revert_all(Rc::clone(&properties))
+                    })
+            }));
 
         toolbar.into()
     }
@@ -151,6 +179,14 @@ impl PendingPropertyView for PvePendingPropertyGrid {
         props.on_submit.clone()
     }
 
+    fn refresh_scheduler(props: &Self::Properties) -> Option<RefreshScheduler> {
+        props.refresh_scheduler.clone()
+    }
+
+    fn on_pending_change(props: &Self::Properties) -> Option<Callback<bool>> {
+        props.on_pending_change.clone()
+    }
+
     fn create(ctx: &Context<PvePendingPropertyView<Self>>) -> Self {
         let props = ctx.props();
         let selection = Selection::new().on_select({
@@ -175,15 +211,18 @@ impl PendingPropertyView for PvePendingPropertyGrid {
 
     fn update_data(&mut self, ctx: &Context<super::PvePendingPropertyView<Self>>) {
         let props = ctx.props();
+        let readonly = props.on_submit.is_none();
 
+        let data = match &self.data {
+            Some(data) => data,
+            _ => &PvePendingConfiguration::new(),
+        };
         let PvePendingConfiguration {
             current,
             pending,
             keys,
-        } = match &self.data {
-            Some(data) => data,
-            _ => &PvePendingConfiguration::new(),
-        };
+            ..
+        } = data;
 
         let mut rows: Vec<PropertyGridRecord> = Vec::new();
 
@@ -201,13 +240,24 @@ impl PendingPropertyView for PvePendingPropertyGrid {
                 let (value, new_value) =
                     super::render_pending_property_value(current, pending, item);
 
+                let delete_state = data.delete_state(&name);
+
+                let value = if delete_state.is_marked() {
+                    super::render_pending_deleted_value(value)
+                } else {
+                    value
+                };
+
                 let mut content = Column::new()
                     //.gap(0.5)
                     .with_child(Container::new().with_child(value.clone()));
 
                 let mut has_changes = false;
 
-                if let Some(new_value) = new_value {
+                if delete_state.is_marked() {
+                    has_changes = true;
+                    content.add_child(super::render_pending_delete_marker(delete_state));
+                } else if let Some(new_value) = new_value {
                     has_changes = true;
                     content.add_child(
                         Container::new()
@@ -216,11 +266,28 @@ impl PendingPropertyView for PvePendingPropertyGrid {
                     );
                 }
 
+                let content: Html = if delete_state.is_marked() && !readonly {
+                    let on_undo = Callback::from({
+                        let link = ctx.link().clone();
+                        let property = item.clone();
+                        move |_: Event| link.send_revert_property(property.clone())
+                    });
+                    Row::new()
+                        .class(pwt::css::AlignItems::Center)
+                        .gap(2)
+                        .with_child(content)
+                        .with_flex_spacer()
+                        .with_child(super::render_undo_delete_button(on_undo))
+                        .into()
+                } else {
+                    content.into()
+                };
+
                 rows.push(PropertyGridRecord {
                     key: Key::from(name.clone()),
                     property: item.clone(),
                     header,
-                    content: content.into(),
+                    content,
                     has_changes,
                 });
             }
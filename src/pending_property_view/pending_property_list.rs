@@ -2,17 +2,19 @@ use std::rc::Rc;
 
 use serde_json::Value;
 
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::props::{IntoOptionalInlineHtml, IntoSubmitCallback, SubmitCallback};
 use pwt::widget::{ActionIcon, Fa, List, ListTile, Row};
 
-use crate::{ApiLoadCallback, IntoApiLoadCallback};
+use crate::{ApiLoadCallback, ConfirmButton, IntoApiLoadCallback, RefreshScheduler};
 
 use pwt_macros::builder;
 
 use crate::layout::list_tile::title_subtitle_column;
+use crate::pve_api_types::PendingDeleteState;
 use crate::EditableProperty;
 
 use super::{
@@ -49,6 +51,24 @@ pub struct PendingPropertyList {
     #[builder_cb(IntoSubmitCallback, into_submit_callback, Value)]
     #[prop_or_default]
     pub on_submit: Option<SubmitCallback<Value>>,
+
+    /// Reload interval and back off settings (default is the scheduler provided by the
+    /// context).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub refresh_scheduler: Option<RefreshScheduler>,
+
+    /// Show a button to revert the pending changes of all properties at once (after
+    /// confirmation).
+    #[builder]
+    #[prop_or_default]
+    pub revert_all: bool,
+
+    /// Called when the configuration changes between having and not having pending changes,
+    /// for example to show an "unapplied changes" banner.
+    #[builder_cb(IntoEventCallback, into_event_callback, bool)]
+    #[prop_or_default]
+    pub on_pending_change: Option<Callback<bool>>,
 }
 
 impl PendingPropertyList {
@@ -69,7 +89,15 @@ impl PendingPropertyList {
         trailing: impl IntoOptionalInlineHtml,
         on_revert: Option<Callback<Event>>,
     ) -> ListTile {
-        Self::render_list_tile_internal(current, pending, property, None, trailing, on_revert)
+        Self::render_list_tile_internal(
+            current,
+            pending,
+            PendingDeleteState::None,
+            property,
+            None,
+            trailing,
+            on_revert,
+        )
     }
 
     /// Render a ListTile with a two children, icon + rest.
@@ -83,13 +111,22 @@ impl PendingPropertyList {
         trailing: impl IntoOptionalInlineHtml,
         on_revert: Option<Callback<Event>>,
     ) -> ListTile {
-        Self::render_list_tile_internal(current, pending, property, Some(icon), trailing, on_revert)
+        Self::render_list_tile_internal(
+            current,
+            pending,
+            PendingDeleteState::None,
+            property,
+            Some(icon),
+            trailing,
+            on_revert,
+        )
     }
 
     // Note: We do not use 3 columns so that we do not waste space on the right side.
     fn render_list_tile_internal(
         current: &Value,
         pending: &Value,
+        delete_state: PendingDeleteState,
         property: &EditableProperty,
         icon: Option<Fa>,
         trailing: impl IntoOptionalInlineHtml,
@@ -98,10 +135,23 @@ impl PendingPropertyList {
         let (value, new_value) =
             crate::pending_property_view::render_pending_property_value(current, pending, property);
 
+        let (value, new_value) = if delete_state.is_marked() {
+            (
+                super::render_pending_deleted_value(value),
+                Some(super::render_pending_delete_marker(delete_state)),
+            )
+        } else {
+            (value, new_value)
+        };
+
         let revert: Option<Html> = on_revert.map(|on_revert| {
-            ActionIcon::new("fa fa-undo")
-                .on_activate(on_revert.clone())
-                .into()
+            if delete_state.is_marked() {
+                super::render_undo_delete_button(on_revert)
+            } else {
+                ActionIcon::new("fa fa-undo")
+                    .on_activate(on_revert.clone())
+                    .into()
+            }
         });
 
         if let Some(new_value) = new_value {
@@ -153,8 +203,7 @@ impl PvePendingPropertyList {
     fn property_tile(
         &self,
         ctx: &Context<PvePendingPropertyView<Self>>,
-        current: &Value,
-        pending: &Value,
+        data: &PvePendingConfiguration,
         property: &EditableProperty,
     ) -> ListTile {
         let props = ctx.props();
@@ -167,8 +216,20 @@ impl PvePendingPropertyList {
                 move |_: Event| link.send_revert_property(property.clone())
             })
         });
-        let list_tile =
-            PendingPropertyList::render_list_tile(current, pending, property, (), on_revert);
+        let delete_state = property
+            .get_name()
+            .map(|name| data.delete_state(name))
+            .unwrap_or_default();
+
+        let list_tile = PendingPropertyList::render_list_tile_internal(
+            &data.current,
+            &data.pending,
+            delete_state,
+            property,
+            None,
+            (),
+            on_revert,
+        );
 
         if !readonly && property.render_input_panel.is_some() {
             list_tile.interactive(true).on_activate({
@@ -202,6 +263,14 @@ impl PendingPropertyView for PvePendingPropertyList {
         props.on_submit.clone()
     }
 
+    fn refresh_scheduler(props: &Self::Properties) -> Option<RefreshScheduler> {
+        props.refresh_scheduler.clone()
+    }
+
+    fn on_pending_change(props: &Self::Properties) -> Option<Callback<bool>> {
+        props.on_pending_change.clone()
+    }
+
     fn create(_ctx: &Context<PvePendingPropertyView<Self>>) -> Self {
         Self {
             view_state: PendingPropertyViewState::default(),
@@ -225,11 +294,7 @@ impl PendingPropertyView for PvePendingPropertyList {
 
         let mut tiles: Vec<ListTile> = Vec::new();
 
-        let PvePendingConfiguration {
-            current,
-            pending,
-            keys,
-        } = match &self.data {
+        let data = match &self.data {
             Some(data) => data,
             _ => &PvePendingConfiguration::new(),
         };
@@ -242,8 +307,8 @@ impl PendingPropertyView for PvePendingPropertyList {
                     continue;
                 }
             };
-            if item.required || keys.contains(&name) {
-                let mut tile = self.property_tile(ctx, current, pending, item);
+            if item.required || data.keys.contains(&name) {
+                let mut tile = self.property_tile(ctx, data, item);
                 tile.set_key(name);
                 tiles.push(tile);
             }
@@ -257,11 +322,31 @@ impl PendingPropertyView for PvePendingPropertyList {
 
         let loading = self.loading();
 
+        let readonly = props.on_submit.is_none();
+        let toolbar = (props.revert_all && !readonly && self.has_pending_changes == Some(true))
+            .then(|| {
+                Row::new()
+                    .padding(2)
+                    .with_flex_spacer()
+                    .with_child(
+                        ConfirmButton::new(tr!("Revert All"))
+                            .confirm_message(tr!(
+                                "Are you sure you want to revert all pending changes?"
+                            ))
+                            .on_activate({
+                                let link = ctx.link().clone();
+                                let properties = Rc::clone(&props.properties);
+                                move |_| link.send_revert_all(Rc::clone(&properties))
+                            }),
+                    )
+                    .into()
+            });
+
         let class = props.class.clone();
         let dialog = self.dialog.clone();
         let error = self.error.clone();
 
-        crate::property_view::render_loadable_panel(class, panel, None, dialog, loading, error)
+        crate::property_view::render_loadable_panel(class, panel, toolbar, dialog, loading, error)
     }
 }
 
@@ -4,21 +4,28 @@ use std::pin::Pin;
 use std::rc::Rc;
 
 use pwt::props::ExtractPrimaryKey;
-use pwt::widget::Fa;
+use pwt::widget::form::{Form, FormContext};
+use pwt::widget::{Column, Fa, InputPanel};
 use serde_json::{json, Value};
 
 use yew::html::IntoPropValue;
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
-use pwt::state::{SlabTree, SlabTreeNodeMut, TreeStore};
+use pwt::state::{SlabTree, SlabTreeNodeMut, Store, TreeStore};
 use pwt::widget::data_table::{
     DataTable, DataTableCellRenderArgs, DataTableColumn, DataTableHeader,
 };
 
 use pwt_macros::builder;
 
-use crate::{http_get, LoadableComponent, LoadableComponentMaster, LoadableComponentState};
+use proxmox_access_control::types::AclListItem;
+
+use crate::percent_encoding::percent_encode_component;
+use crate::{
+    http_get, AclPathSelector, AuthidSelector, LoadableComponent, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState,
+};
 
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
@@ -32,6 +39,27 @@ pub struct PermissionPanel {
     /// The base url for
     #[prop_or_default]
     pub auth_id: Option<AttrValue>,
+
+    /// Show selectors for an auth id and a path, to check the effective
+    /// permissions of arbitrary users and tokens.
+    ///
+    /// The ACL entries contributing to the selected path are listed below
+    /// the permissions.
+    #[builder]
+    #[prop_or_default]
+    pub calculator: bool,
+
+    /// Endpoint returning the ACL entries (calculator mode).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(AttrValue::Static("/access/acl"))]
+    pub acl_api_endpoint: AttrValue,
+
+    /// Endpoint returning user information including their `groups` (calculator mode).
+    ///
+    /// If set, ACL entries for the groups of the selected user are listed too.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub users_api_endpoint: Option<AttrValue>,
 }
 
 impl Default for PermissionPanel {
@@ -45,10 +73,16 @@ impl PermissionPanel {
         yew::props!(Self {})
     }
 }
+pub enum Msg {
+    FormChange,
+}
+
 pub struct ProxmoxPermissionPanel {
     state: LoadableComponentState<()>,
     store: TreeStore<PermissionInfo>,
     columns: Rc<Vec<DataTableHeader<PermissionInfo>>>,
+    form_ctx: FormContext,
+    acl_store: Store<AclListItem>,
 }
 
 pwt::impl_deref_mut_property!(ProxmoxPermissionPanel, state, LoadableComponentState<()>);
@@ -111,18 +145,86 @@ fn insert_node(
     }
 }
 
+/// Returns true if an ACL entry on `acl_path` applies to `path`.
+fn acl_applies(acl_path: &str, propagate: bool, path: &str) -> bool {
+    let acl_path = acl_path.trim_end_matches('/');
+    let path = path.trim_end_matches('/');
+    if acl_path == path {
+        return true;
+    }
+    propagate && (acl_path.is_empty() || path.starts_with(&format!("{acl_path}/")))
+}
+
+/// Load the ACL entries which contribute to the permissions of `auth_id` on `path`.
+///
+/// Token permissions are limited by the permissions of the owning user, so
+/// the user's entries are included too.
+async fn load_contributing_acls(
+    acl_url: AttrValue,
+    users_url: Option<AttrValue>,
+    auth_id: String,
+    path: String,
+) -> Result<Vec<AclListItem>, anyhow::Error> {
+    let userid = match auth_id.split_once('!') {
+        Some((userid, _token)) => userid.to_string(),
+        None => auth_id.clone(),
+    };
+
+    let groups: Vec<String> = match users_url {
+        Some(url) => {
+            let url = format!("{url}/{}", percent_encode_component(&userid));
+            let info: Value = http_get(&url, None).await?;
+            match &info["groups"] {
+                Value::Array(list) => list
+                    .iter()
+                    .filter_map(|g| g.as_str().map(String::from))
+                    .collect(),
+                // PVE returns a comma separated list for some versions
+                Value::String(list) => list.split(',').map(String::from).collect(),
+                _ => Vec::new(),
+            }
+        }
+        None => Vec::new(),
+    };
+
+    let list: Vec<AclListItem> = http_get(&*acl_url, None).await?;
+    Ok(list
+        .into_iter()
+        .filter(|item| {
+            (item.ugid == auth_id || item.ugid == userid || groups.contains(&item.ugid))
+                && acl_applies(&item.path, item.propagate, &path)
+        })
+        .collect())
+}
+
 impl LoadableComponent for ProxmoxPermissionPanel {
     type Properties = PermissionPanel;
-    type Message = ();
+    type Message = Msg;
     type ViewState = ();
 
-    fn create(_ctx: &Context<LoadableComponentMaster<Self>>) -> Self {
+    fn create(ctx: &Context<LoadableComponentMaster<Self>>) -> Self {
         let store = TreeStore::new();
         let columns = Rc::new(columns(&store));
+        let acl_store = Store::with_extract_key(|item: &AclListItem| {
+            Key::from(format!("{} for {} - {}", item.path, item.ugid, item.roleid))
+        });
         Self {
             state: LoadableComponentState::new(),
             store,
             columns,
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormChange)),
+            acl_store,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<LoadableComponentMaster<Self>>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::FormChange => {
+                if self.form_ctx.read().is_valid() {
+                    ctx.link().send_reload();
+                }
+                false
+            }
         }
     }
 
@@ -132,13 +234,50 @@ impl LoadableComponent for ProxmoxPermissionPanel {
     ) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>>>> {
         let props = ctx.props();
         let base_url = props.base_url.clone();
-        let args: Option<Value> = props
-            .auth_id
-            .as_ref()
-            .map(|auth_id| json!({ "auth-id": **auth_id}));
         let store = self.store.clone();
+        let acl_store = self.acl_store.clone();
+
+        let (auth_id, path) = if props.calculator {
+            let form = self.form_ctx.read();
+            let auth_id = form.get_field_text("auth-id");
+            let path = form.get_field_text("path");
+            (
+                (!auth_id.is_empty()).then_some(auth_id),
+                (!path.is_empty()).then_some(path),
+            )
+        } else {
+            (props.auth_id.as_ref().map(|a| a.to_string()), None)
+        };
+
+        let mut args = json!({});
+        if let Some(auth_id) = &auth_id {
+            args["auth-id"] = auth_id.as_str().into();
+        }
+        if let Some(path) = &path {
+            args["path"] = path.as_str().into();
+        }
+
+        let contributing = match (props.calculator, auth_id, path) {
+            (true, Some(auth_id), Some(path)) => Some((
+                props.acl_api_endpoint.clone(),
+                props.users_api_endpoint.clone(),
+                auth_id,
+                path,
+            )),
+            _ => None,
+        };
+
+        // without an auth id, the calculator has nothing to show
+        let skip = props.calculator && args.get("auth-id").is_none();
 
         Box::pin(async move {
+            if skip {
+                store.set_data(SlabTree::new());
+                acl_store.clear();
+                return Ok(());
+            }
+
+            let args = (!args.as_object().unwrap().is_empty()).then_some(args);
             let data: HashMap<String, HashMap<String, bool>> = http_get(&*base_url, args).await?;
             let mut tree: SlabTree<PermissionInfo> = SlabTree::new();
             tree.set_root(PermissionInfo::Path(String::from("/"), String::from("/")));
@@ -154,17 +293,87 @@ impl LoadableComponent for ProxmoxPermissionPanel {
             tree.sort(true);
 
             store.set_data(tree);
+
+            match contributing {
+                Some((acl_url, users_url, auth_id, path)) => {
+                    let list = load_contributing_acls(acl_url, users_url, auth_id, path).await?;
+                    acl_store.set_data(list);
+                }
+                None => acl_store.clear(),
+            }
             Ok(())
         })
     }
 
-    fn main_view(&self, _ctx: &Context<LoadableComponentMaster<Self>>) -> Html {
-        DataTable::new(Rc::clone(&self.columns), self.store.clone())
+    fn main_view(&self, ctx: &Context<LoadableComponentMaster<Self>>) -> Html {
+        let table =
+            DataTable::new(Rc::clone(&self.columns), self.store.clone()).class("pwt-flex-fit");
+
+        if !ctx.props().calculator {
+            return table.into();
+        }
+
+        let form = Form::new().form_context(self.form_ctx.clone()).with_child(
+            InputPanel::new()
+                .padding(2)
+                .with_field(
+                    tr!("User/API Token"),
+                    AuthidSelector::new().name("auth-id").required(true),
+                )
+                .with_right_field(
+                    tr!("Path"),
+                    AclPathSelector::new()
+                        .acl_api_endpoint(ctx.props().acl_api_endpoint.clone())
+                        .name("path"),
+                ),
+        );
+
+        Column::new()
             .class("pwt-flex-fit")
+            .with_child(form)
+            .with_child(table)
+            .with_child(
+                Column::new()
+                    .class("pwt-border-top")
+                    .style("max-height", "250px")
+                    .with_child(
+                        html! {<div class="pwt-p-2 pwt-font-title-small">{tr!("Contributing ACL Entries")}</div>},
+                    )
+                    .with_child(
+                        DataTable::new(acl_columns(), self.acl_store.clone())
+                            .class("pwt-flex-fit"),
+                    ),
+            )
             .into()
     }
 }
 
+fn acl_columns() -> Rc<Vec<DataTableHeader<AclListItem>>> {
+    Rc::new(vec![
+        DataTableColumn::new(tr!("Path"))
+            .flex(1)
+            .render(|item: &AclListItem| item.path.as_str().into())
+            .into(),
+        DataTableColumn::new(tr!("User/Group/API Token"))
+            .flex(1)
+            .render(|item: &AclListItem| item.ugid.as_str().into())
+            .into(),
+        DataTableColumn::new(tr!("Role"))
+            .flex(1)
+            .render(|item: &AclListItem| item.roleid.as_str().into())
+            .into(),
+        DataTableColumn::new(tr!("Propagate"))
+            .render(|item: &AclListItem| {
+                if item.propagate {
+                    tr!("Yes").into()
+                } else {
+                    tr!("No").into()
+                }
+            })
+            .into(),
+    ])
+}
+
 fn columns(store: &TreeStore<PermissionInfo>) -> Vec<DataTableHeader<PermissionInfo>> {
     vec![
         DataTableColumn::new(tr!("Path") + "/" + &tr!("Permission"))
@@ -208,3 +417,19 @@ impl From<PermissionPanel> for VNode {
         VNode::from(comp)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::acl_applies;
+
+    #[test]
+    fn test_acl_applies() {
+        assert!(acl_applies("/", true, "/datastore/store1"));
+        assert!(!acl_applies("/", false, "/datastore/store1"));
+        assert!(acl_applies("/", false, "/"));
+        assert!(acl_applies("/datastore", true, "/datastore/store1"));
+        assert!(acl_applies("/datastore/store1", false, "/datastore/store1"));
+        assert!(!acl_applies("/datastore/store", true, "/datastore/store1"));
+        assert!(!acl_applies("/datastore/store1", true, "/datastore"));
+    }
+}
@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use anyhow::Error;
+use gloo_events::EventListener;
 use pwt::state::PersistentState;
 use pwt::touch::SideDialog;
 use serde_json::Value;
@@ -19,8 +20,9 @@ use pwt::AsyncPool;
 
 use pwt_macros::builder;
 
+use crate::edit_window::{discard_changes_dialog, unload_guard};
 use crate::{
-    ApiLoadCallback, IntoApiLoadCallback, PropertyEditorState, RenderPropertyInputPanelFn,
+    ApiError, ApiLoadCallback, IntoApiLoadCallback, PropertyEditorState, RenderPropertyInputPanelFn,
 };
 
 /// Edit dialog for for property editors.
@@ -135,6 +137,17 @@ pub struct PropertyEditDialog {
     #[builder]
     #[prop_or(false)]
     pub mobile: bool,
+
+    /// Ask for confirmation before discarding unsaved changes, see
+    /// [EditWindow::confirm_discard](crate::EditWindow::confirm_discard).
+    #[prop_or_default]
+    #[builder]
+    pub confirm_discard: bool,
+
+    /// Called when the form changes between clean and modified (dirty) state.
+    #[builder_cb(IntoEventCallback, into_event_callback, bool)]
+    #[prop_or_default]
+    pub on_dirty_change: Option<Callback<bool>>,
 }
 
 impl PropertyEditDialog {
@@ -171,6 +184,9 @@ pub enum Msg {
     Submit,
     SubmitResult(Result<(), Error>),
     ClearSubmitError,
+    Close,
+    DiscardChanges,
+    CancelDiscard,
     Load,
     LoadResult(Result<ApiResponseData<Value>, Error>),
     ShowAdvanced(bool),
@@ -181,10 +197,41 @@ pub struct PwtPropertyEditDialog {
     loading: bool,
     form_ctx: FormContext,
     submit_error: Option<String>,
+    // form data at the time of the submit error
+    submit_error_data: Option<Value>,
     load_data: Rc<Value>,
     load_error: Option<String>,
     async_pool: AsyncPool,
     show_advanced: PersistentState<bool>,
+    dirty: bool,
+    show_discard_dialog: bool,
+    unload_guard: Option<EventListener>,
+}
+
+impl PwtPropertyEditDialog {
+    fn update_dirty(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let dirty = self.form_ctx.read().is_dirty();
+        if dirty == self.dirty {
+            return;
+        }
+        self.dirty = dirty;
+        self.unload_guard = (dirty && props.confirm_discard).then(unload_guard);
+        if let Some(on_dirty_change) = &props.on_dirty_change {
+            on_dirty_change.emit(dirty);
+        }
+    }
+
+    fn close(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        self.unload_guard = None;
+        if let Some(on_close) = &props.on_close {
+            on_close.emit(());
+        }
+        if let Some(on_done) = &props.on_done {
+            on_done.emit(());
+        }
+    }
 }
 
 impl Component for PwtPropertyEditDialog {
@@ -203,10 +250,14 @@ impl Component for PwtPropertyEditDialog {
             form_ctx,
             loading: false,
             submit_error: None,
+            submit_error_data: None,
             load_error: None,
             load_data: Rc::new(Value::Null),
             async_pool: AsyncPool::new(),
             show_advanced,
+            dirty: false,
+            show_discard_dialog: false,
+            unload_guard: None,
         }
     }
 
@@ -222,6 +273,23 @@ impl Component for PwtPropertyEditDialog {
                 self.submit_error = None;
                 true
             }
+            Msg::Close => {
+                if props.confirm_discard && self.dirty {
+                    self.show_discard_dialog = true;
+                    return true;
+                }
+                self.close(ctx);
+                false
+            }
+            Msg::DiscardChanges => {
+                self.show_discard_dialog = false;
+                self.close(ctx);
+                true
+            }
+            Msg::CancelDiscard => {
+                self.show_discard_dialog = false;
+                true
+            }
             Msg::Load => {
                 if let Some(loader) = props.loader.clone() {
                     self.loading = true;
@@ -264,9 +332,15 @@ impl Component for PwtPropertyEditDialog {
                 true
             }
             Msg::FormDataChange => {
-                if self.submit_error.is_some() {
+                // marking fields invalid also triggers a change, keep the error until the
+                // user modifies the data
+                if self.submit_error.is_some()
+                    && self.submit_error_data.as_ref() != Some(&self.form_ctx.get_submit_data())
+                {
                     self.submit_error = None;
+                    self.submit_error_data = None;
                 }
+                self.update_dirty(ctx);
                 if let Some(on_change) = &props.on_change {
                     let state = PropertyEditorState {
                         form_ctx: self.form_ctx.clone(),
@@ -307,12 +381,17 @@ impl Component for PwtPropertyEditDialog {
                 match result {
                     Ok(_) => {
                         self.submit_error = None;
+                        self.unload_guard = None;
                         if let Some(on_done) = &props.on_done {
                             on_done.emit(());
                         }
                     }
                     Err(err) => {
+                        if let Some(api_err) = ApiError::from_error(&err) {
+                            api_err.mark_form_fields(&self.form_ctx);
+                        }
                         self.submit_error = Some(err.to_string());
+                        self.submit_error_data = Some(self.form_ctx.get_submit_data());
                     }
                 }
                 true
@@ -430,23 +509,15 @@ impl Component for PwtPropertyEditDialog {
             )
             .with_child(form.style("visibility", loading.then_some("hidden")));
 
-        let on_close = {
-            let on_close = props.on_close.clone();
-            let on_done = props.on_done.clone();
+        let on_close = (props.on_close.is_some() || props.on_done.is_some())
+            .then(|| link.callback(|()| Msg::Close));
 
-            if on_close.is_some() || on_done.is_some() {
-                Some(move |()| {
-                    if let Some(on_close) = &on_close {
-                        on_close.emit(());
-                    }
-                    if let Some(on_done) = &on_done {
-                        on_done.emit(());
-                    }
-                })
-            } else {
-                None
-            }
-        };
+        let discard_dialog = self.show_discard_dialog.then(|| {
+            discard_changes_dialog(
+                link.callback(|_| Msg::DiscardChanges),
+                link.callback(|_| Msg::CancelDiscard),
+            )
+        });
 
         let submit_alert = self.submit_error.as_ref().map({
             let link = ctx.link();
@@ -461,6 +532,7 @@ impl Component for PwtPropertyEditDialog {
                         .style("max-height", "90dvh")
                         .with_child(form)
                         .with_optional_child(submit_alert)
+                        .with_optional_child(discard_dialog)
                         .location(pwt::touch::SideDialogLocation::Bottom)
                         .on_close(on_close)
                         .into()
@@ -469,6 +541,7 @@ impl Component for PwtPropertyEditDialog {
                         .style("min-width", "300px")
                         .with_child(form)
                         .with_optional_child(submit_alert)
+                        .with_optional_child(discard_dialog)
                         .on_close(on_close)
                         .into()
                 }
@@ -6,7 +6,6 @@ pub use property_grid::{property_grid_columns, PropertyGrid};
 mod property_list;
 pub use property_list::PropertyList;
 
-use gloo_timers::callback::Timeout;
 use serde_json::Value;
 
 use yew::html::Scope;
@@ -18,7 +17,9 @@ use pwt::widget::{Column, Container};
 use pwt::AsyncAbortGuard;
 
 use crate::utils::render_boolean;
-use crate::{ApiLoadCallback, EditableProperty, PropertyEditDialog};
+use crate::{
+    ApiLoadCallback, EditableProperty, PropertyEditDialog, RefreshScheduler, RefreshTimeout,
+};
 
 #[derive(Clone, PartialEq)]
 pub struct PropertyGridRecord {
@@ -85,6 +86,12 @@ pub trait PropertyView: DerefMut<Target = PropertyViewState> {
 
     fn on_submit(props: &Self::Properties) -> Option<SubmitCallback<Value>>;
 
+    /// The refresh scheduler, falls back to the one provided by the context.
+    #[allow(unused_variables)]
+    fn refresh_scheduler(props: &Self::Properties) -> Option<RefreshScheduler> {
+        None
+    }
+
     fn create(ctx: &Context<PvePropertyView<Self>>) -> Self
     where
         Self: 'static + Sized;
@@ -125,7 +132,9 @@ pub trait PropertyView: DerefMut<Target = PropertyViewState> {
 pub struct PropertyViewState {
     pub data: Option<Value>,
     pub error: Option<String>,
-    pub reload_timeout: Option<Timeout>,
+    pub reload_timeout: Option<RefreshTimeout>,
+    /// Number of consecutive load errors, used to back off reloading.
+    pub load_errors: u32,
     pub load_guard: Option<AsyncAbortGuard>,
     pub dialog: Option<Html>,
 }
@@ -136,9 +145,11 @@ impl PropertyViewState {
             Ok(data) => {
                 self.error = None;
                 self.data = Some(data);
+                self.load_errors = 0;
             }
             Err(err) => {
                 self.error = Some(err);
+                self.load_errors = self.load_errors.saturating_add(1);
             }
         }
     }
@@ -202,10 +213,13 @@ impl<T: 'static + PropertyView> Component for PvePropertyView<T> {
                 self.state.set_load_result(result);
 
                 self.state.update_data(ctx);
-                let link = ctx.link().clone();
-                self.state.reload_timeout = Some(Timeout::new(3000, move || {
-                    link.send_message(PropertyViewMsg::Load);
-                }));
+                let scheduler = T::refresh_scheduler(props)
+                    .or_else(|| RefreshScheduler::from_context(ctx.link()))
+                    .unwrap_or_default();
+                self.state.reload_timeout = Some(scheduler.schedule(
+                    self.state.load_errors,
+                    ctx.link().callback(|_| PropertyViewMsg::Load),
+                ));
             }
             PropertyViewMsg::ShowDialog(dialog) => {
                 if dialog.is_none() && self.state.reload_timeout.is_some() {
@@ -4,7 +4,7 @@ use pwt::state::{Selection, Store};
 use pwt::widget::{Button, Toolbar};
 use serde_json::Value;
 
-use yew::html::IntoEventCallback;
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
@@ -13,7 +13,7 @@ use pwt::widget::data_table::{
     DataTable, DataTableColumn, DataTableHeader, DataTableKeyboardEvent, DataTableMouseEvent,
 };
 
-use crate::{ApiLoadCallback, IntoApiLoadCallback};
+use crate::{ApiLoadCallback, IntoApiLoadCallback, RefreshScheduler};
 
 use pwt_macros::builder;
 
@@ -48,6 +48,12 @@ pub struct PropertyGrid {
     #[prop_or_default]
     pub on_submit: Option<SubmitCallback<Value>>,
 
+    /// Reload interval and back off settings (default is the scheduler provided by the
+    /// context).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub refresh_scheduler: Option<RefreshScheduler>,
+
     /// Select callback.
     #[prop_or_default]
     #[builder_cb(IntoEventCallback, into_event_callback, Option<Key>)]
@@ -118,6 +124,10 @@ impl PropertyView for PvePropertyGrid {
         props.on_submit.clone()
     }
 
+    fn refresh_scheduler(props: &Self::Properties) -> Option<RefreshScheduler> {
+        props.refresh_scheduler.clone()
+    }
+
     fn create(ctx: &Context<PvePropertyView<Self>>) -> Self {
         let props = ctx.props();
         let selection = Selection::new().on_select({
@@ -2,13 +2,14 @@ use std::rc::Rc;
 
 use serde_json::Value;
 
+use yew::html::IntoPropValue;
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::props::{IntoSubmitCallback, SubmitCallback};
 use pwt::widget::{List, ListTile};
 
-use crate::{ApiLoadCallback, IntoApiLoadCallback};
+use crate::{ApiLoadCallback, IntoApiLoadCallback, RefreshScheduler};
 
 use pwt_macros::builder;
 
@@ -41,6 +42,12 @@ pub struct PropertyList {
     #[builder_cb(IntoSubmitCallback, into_submit_callback, Value)]
     #[prop_or_default]
     pub on_submit: Option<SubmitCallback<Value>>,
+
+    /// Reload interval and back off settings (default is the scheduler provided by the
+    /// context).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub refresh_scheduler: Option<RefreshScheduler>,
 }
 
 impl PropertyList {
@@ -96,6 +103,10 @@ impl PropertyView for PvePropertyList {
         props.on_submit.clone()
     }
 
+    fn refresh_scheduler(props: &Self::Properties) -> Option<RefreshScheduler> {
+        props.refresh_scheduler.clone()
+    }
+
     fn create(_ctx: &Context<PvePropertyView<Self>>) -> Self
     where
         Self: 'static + Sized,
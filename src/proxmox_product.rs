@@ -20,22 +20,104 @@ pub trait ProjectInfo {
     fn subscription_url(&self) -> &'static str {
         ""
     }
+
+    /// Path of the documentation bundled with the product (with trailing slash).
+    fn docs_path(&self) -> &'static str {
+        "/docs/"
+    }
+
+    /// Public documentation URL (with trailing slash), used if the bundled documentation
+    /// is not installed. Empty if there is none.
+    fn docs_public_url(&self) -> &'static str {
+        ""
+    }
+
+    /// Link to a documentation section, relative to the documentation root.
+    fn docs_section_link(&self, section: &str) -> String {
+        format!("index.html#{section}")
+    }
+
+    /// Name of the Debian package which determines the product version.
+    fn package_name(&self) -> &'static str {
+        ""
+    }
+
+    /// Returns true for the mobile clients.
+    fn is_mobile(&self) -> bool {
+        false
+    }
+}
+
+// The Sphinx based guides are split into several pages, so the help keys (section anchors)
+// need to be mapped to the page containing them.
+const PBS_DOCS_PAGES: &[(&str, &str)] = &[
+    ("backup-remote", "managing-remotes.html"),
+    ("chapter-zfs", "sysadmin.html"),
+    ("client-creating-backups", "backup-client.html"),
+    ("client-encryption", "backup-client.html"),
+    ("client-repository", "backup-client.html"),
+    ("datastore-intro", "storage.html"),
+    ("disk-management", "storage.html"),
+    ("get-help", "introduction.html"),
+    ("maintenance-gc", "maintenance.html"),
+    ("maintenance-pruning", "maintenance.html"),
+    ("maintenance-verification", "maintenance.html"),
+    ("notifications", "notifications.html"),
+    ("storage-datastore-create", "storage.html"),
+    ("syncjobs", "managing-remotes.html"),
+    (
+        "sysadmin-certificate-management",
+        "certificate-management.html",
+    ),
+    ("sysadmin-host-administration", "sysadmin.html"),
+    ("sysadmin-network-configuration", "network-management.html"),
+    ("sysadmin-package-repositories", "package-repositories.html"),
+    ("sysadmin-traffic-control", "network-management.html"),
+    ("tape-backup", "tape-backup.html"),
+    ("user-acl", "user-management.html"),
+    ("user-apitokens", "user-management.html"),
+    ("user-mgmt", "user-management.html"),
+    ("user-realms-ldap", "user-management.html"),
+    ("user-tfa-setup-recovery-keys", "user-management.html"),
+    ("user-tfa-setup-totp", "user-management.html"),
+    ("user-tfa-setup-webauthn", "user-management.html"),
+];
+
+const PDM_DOCS_PAGES: &[(&str, &str)] = &[
+    ("access-control", "access-control.html"),
+    ("get-help", "introduction.html"),
+    ("installation", "installation.html"),
+    ("remotes", "remotes.html"),
+    ("sysadmin-package-repositories", "package-repositories.html"),
+];
+
+// Sphinx turns underscores in labels into dashes, so accept both spellings. Unknown keys
+// link to the start page, as the anchor would not exist there.
+fn sphinx_section_link(pages: &[(&str, &str)], section: &str) -> String {
+    let anchor = section.replace('_', "-");
+    match pages.iter().find(|(key, _)| *key == anchor) {
+        Some((_, page)) => format!("{page}#{anchor}"),
+        None => String::from("index.html"),
+    }
 }
 
 /// Enumerate the different Proxmox products.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum ExistingProduct {
     PVE,
     PMG,
     PBS,
     POM,
     PDM,
+    /// The Proxmox VE mobile client, talks to a PVE node.
+    PVEMobile,
 }
 
 impl ProjectInfo for ExistingProduct {
     fn auth_cookie_name(&self) -> &'static str {
         match self {
-            ExistingProduct::PVE => "PVEAuthCookie",
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => "PVEAuthCookie",
             ExistingProduct::PMG => "PMGAuthCookie",
             ExistingProduct::PBS => "PBSAuthCookie",
             ExistingProduct::POM => "POMAuthCookie",
@@ -45,7 +127,7 @@ impl ProjectInfo for ExistingProduct {
 
     fn auth_cookie_prefixes(&self) -> &'static [&'static str] {
         match self {
-            ExistingProduct::PVE => &["PVE"],
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => &["PVE"],
             ExistingProduct::PMG => &["PMG", "PMGQUAR"],
             ExistingProduct::PBS => &["PBS"],
             ExistingProduct::POM => &["POM"],
@@ -60,12 +142,13 @@ impl ProjectInfo for ExistingProduct {
             ExistingProduct::PBS => tr!("Proxmox Backup Server"),
             ExistingProduct::POM => tr!("Proxmox Offline Mirror"),
             ExistingProduct::PDM => tr!("Proxmox Datacenter Manager"),
+            ExistingProduct::PVEMobile => tr!("Proxmox Virtual Environment Mobile"),
         }
     }
 
     fn short_name(&self) -> &'static str {
         match self {
-            ExistingProduct::PVE => "PVE",
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => "PVE",
             ExistingProduct::PMG => "PMG",
             ExistingProduct::PBS => "PBS",
             ExistingProduct::POM => "POM",
@@ -76,4 +159,51 @@ impl ProjectInfo for ExistingProduct {
     fn subscription_url(&self) -> &'static str {
         "/nodes/localhost/subscription"
     }
+
+    fn docs_path(&self) -> &'static str {
+        match self {
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => "/pve-docs/",
+            ExistingProduct::PMG => "/pmg-docs/",
+            ExistingProduct::PBS | ExistingProduct::POM | ExistingProduct::PDM => "/docs/",
+        }
+    }
+
+    fn docs_public_url(&self) -> &'static str {
+        match self {
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => {
+                "https://pve.proxmox.com/pve-docs/"
+            }
+            ExistingProduct::PMG => "https://pmg.proxmox.com/pmg-docs/",
+            ExistingProduct::PBS => "https://pbs.proxmox.com/docs/",
+            ExistingProduct::POM => "https://pom.proxmox.com/",
+            ExistingProduct::PDM => "https://pdm.proxmox.com/docs/",
+        }
+    }
+
+    fn docs_section_link(&self, section: &str) -> String {
+        match self {
+            // the asciidoc based guides contain all anchors in a single page
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => {
+                format!("pve-admin-guide.html#{section}")
+            }
+            ExistingProduct::PMG => format!("pmg-admin-guide.html#{section}"),
+            ExistingProduct::PBS => sphinx_section_link(PBS_DOCS_PAGES, section),
+            ExistingProduct::PDM => sphinx_section_link(PDM_DOCS_PAGES, section),
+            ExistingProduct::POM => sphinx_section_link(&[], section),
+        }
+    }
+
+    fn package_name(&self) -> &'static str {
+        match self {
+            ExistingProduct::PVE | ExistingProduct::PVEMobile => "pve-manager",
+            ExistingProduct::PMG => "pmg-api",
+            ExistingProduct::PBS => "proxmox-backup-server",
+            ExistingProduct::POM => "proxmox-offline-mirror",
+            ExistingProduct::PDM => "proxmox-datacenter-manager",
+        }
+    }
+
+    fn is_mobile(&self) -> bool {
+        matches!(self, ExistingProduct::PVEMobile)
+    }
 }
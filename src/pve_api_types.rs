@@ -5,11 +5,36 @@ use proxmox_schema::{api, ApiStringFormat};
 // fixme: define all those types in pve-api-types
 
 #[derive(Deserialize, Serialize, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct StorageEntry {
     pub format: String,
     pub content: String,
     pub size: i64,
     pub volid: String,
+    /// Creation time (seconds since epoch), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ctime: Option<i64>,
+    /// Used space, smaller than `size` for thin provisioned volumes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<i64>,
+}
+
+impl StorageEntry {
+    pub fn new(
+        volid: impl Into<String>,
+        content: impl Into<String>,
+        format: impl Into<String>,
+        size: i64,
+    ) -> Self {
+        Self {
+            format: format.into(),
+            content: content.into(),
+            size,
+            volid: volid.into(),
+            ctime: None,
+            used: None,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Clone)]
@@ -57,6 +82,243 @@ pub struct QemuConfigStartup {
     pub down: Option<u32>,
 }
 
+#[api(
+    default_key: "host",
+    properties: {
+        host: {
+            type: String,
+            optional: true,
+        },
+        mdev: {
+            type: String,
+            optional: true,
+        },
+        pcie: {
+            type: bool,
+            optional: true,
+        },
+        rombar: {
+            type: bool,
+            optional: true,
+        },
+        romfile: {
+            type: String,
+            optional: true,
+        },
+        "x-vga": {
+            type: bool,
+            optional: true,
+        },
+    }
+)]
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+/// Qemu host PCI device passthrough (`hostpciN`)
+pub struct QemuConfigHostpci {
+    /// Host PCI device ID, without function number to pass through all functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The type of mediated device to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mdev: Option<String>,
+    /// Use PCI-express bus (only q35 machine type).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pcie: Option<bool>,
+    /// Specify whether or not the device's ROM will be visible in the guest's memory map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rombar: Option<bool>,
+    /// Custom PCI device ROM filename (must be located in /usr/share/kvm/).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub romfile: Option<String>,
+    /// Enable vfio-vga device support (primary GPU).
+    #[serde(rename = "x-vga", skip_serializing_if = "Option::is_none")]
+    pub x_vga: Option<bool>,
+}
+
+#[api(
+    default_key: "host",
+    properties: {
+        host: {
+            type: String,
+            optional: true,
+        },
+        usb3: {
+            type: bool,
+            optional: true,
+        },
+    }
+)]
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+/// Qemu USB device passthrough (`usbN`)
+pub struct QemuConfigUsb {
+    /// The host USB device (`vendor:product`, `bus-port` or `spice`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Use USB 3 host controller.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usb3: Option<bool>,
+}
+
+/// PCI device (GET /api2/json/nodes/{node}/hardware/pci)
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct PciDevice {
+    /// The PCI ID (`domain:bus:device.function`).
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    /// The IOMMU group, `-1` if there is none.
+    pub iommugroup: i64,
+    /// The device supports mediated devices.
+    #[serde(deserialize_with = "proxmox_serde::perl::deserialize_bool")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mdev: Option<bool>,
+}
+
+/// Mediated device type (GET /api2/json/nodes/{node}/hardware/pci/{id}/mdev)
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct PciMdevType {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub available: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// USB device (GET /api2/json/nodes/{node}/hardware/usb)
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct UsbDevice {
+    pub busnum: u64,
+    pub devnum: u64,
+    /// Vendor ID, like `0x1234`.
+    pub vendid: String,
+    /// Product ID, like `0x5678`.
+    pub prodid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usbpath: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<u64>,
+}
+
+#[api(
+    default_key: "device",
+    properties: {
+        device: {
+            type: String,
+            optional: true,
+        },
+        driver: {
+            type: String,
+            optional: true,
+        },
+    }
+)]
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+/// Qemu audio device (`audio0`)
+pub struct QemuConfigAudio {
+    /// The emulated audio device (`ich9-intel-hda`, `intel-hda` or `AC97`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The audio backend driver (`spice` or `none`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+}
+
+#[api(
+    default_key: "source",
+    properties: {
+        source: {
+            type: String,
+            optional: true,
+        },
+        max_bytes: {
+            type: u64,
+            optional: true,
+        },
+        period: {
+            type: u64,
+            optional: true,
+        },
+    }
+)]
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+/// Qemu VirtIO random number generator (`rng0`)
+pub struct QemuConfigRng {
+    /// The entropy source on the host (`/dev/urandom`, `/dev/random` or `/dev/hwrng`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Maximum bytes of entropy allowed to get injected into the guest every `period`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Every `period` milliseconds the entropy-injection quota is reset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<u64>,
+}
+
+#[api(
+    default_key: "dirid",
+    properties: {
+        dirid: {
+            type: String,
+            optional: true,
+        },
+        cache: {
+            type: String,
+            optional: true,
+        },
+        "direct-io": {
+            type: bool,
+            optional: true,
+        },
+        "expose-acl": {
+            type: bool,
+            optional: true,
+        },
+        "expose-xattr": {
+            type: bool,
+            optional: true,
+        },
+    }
+)]
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+/// Qemu virtiofs directory share (`virtiofsN`)
+pub struct QemuConfigVirtiofs {
+    /// Mapping identifier of the directory mapping to be shared with the guest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirid: Option<String>,
+    /// The caching policy the file system should use (`auto`, `always`, `metadata` or `never`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<String>,
+    /// Honor the O_DIRECT flag passed down by guest applications.
+    #[serde(rename = "direct-io", skip_serializing_if = "Option::is_none")]
+    pub direct_io: Option<bool>,
+    /// Enable support for POSIX ACLs (implies `expose-xattr`).
+    #[serde(rename = "expose-acl", skip_serializing_if = "Option::is_none")]
+    pub expose_acl: Option<bool>,
+    /// Enable support for extended attributes.
+    #[serde(rename = "expose-xattr", skip_serializing_if = "Option::is_none")]
+    pub expose_xattr: Option<bool>,
+}
+
+/// Directory mapping (GET /api2/json/cluster/mapping/dir)
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct DirMapping {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Per node mappings, as property strings like `node=pve1,path=/mnt/share`.
+    #[serde(default)]
+    pub map: Vec<String>,
+}
+
 #[api]
 /// Qemu Machine Type (q35 or pc)
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -98,3 +360,35 @@ pub struct QemuMachineInfo {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub changes: Option<String>,
 }
+
+/// Pending delete state of a guest configuration key.
+///
+/// The PVE pending API (`GET .../pending`) returns this as the numeric
+/// `delete` attribute (unset, `1` or `2`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PendingDeleteState {
+    /// The key is not marked for deletion.
+    #[default]
+    None,
+    /// The key is marked for deletion once the pending changes get applied.
+    Delete,
+    /// The key is marked for forced deletion.
+    Force,
+}
+
+impl PendingDeleteState {
+    /// Returns true if the key is marked for deletion (forced or not).
+    pub fn is_marked(&self) -> bool {
+        !matches!(self, PendingDeleteState::None)
+    }
+}
+
+impl From<&pve_api_types::PendingConfigValue> for PendingDeleteState {
+    fn from(item: &pve_api_types::PendingConfigValue) -> Self {
+        match item.delete {
+            Some(1) => PendingDeleteState::Delete,
+            Some(2) => PendingDeleteState::Force,
+            _ => PendingDeleteState::None,
+        }
+    }
+}
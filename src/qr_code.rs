@@ -0,0 +1,97 @@
+use qrcode::render::svg;
+use yew::html::IntoPropValue;
+
+use pwt::prelude::*;
+use pwt::widget::Container;
+
+use pwt_macros::{builder, widget};
+
+/// QR code, rendered as inline SVG.
+///
+/// The code is generated in Rust, so no additional JavaScript library is needed. Dark and
+/// light colors are fixed, because QR code scanners expect dark modules on a light
+/// background (also with dark themes).
+#[widget(comp=ProxmoxQrCode, @element)]
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct QrCode {
+    /// The encoded text.
+    pub text: AttrValue,
+
+    /// Minimum width and height in pixels.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub size: Option<u32>,
+}
+
+impl QrCode {
+    /// Creates a new instance.
+    pub fn new(text: impl Into<AttrValue>) -> Self {
+        yew::props!(Self { text: text.into() })
+    }
+}
+
+/// Render `text` as SVG document.
+fn render_svg(text: &str, size: Option<u32>) -> Result<String, qrcode::types::QrError> {
+    let code = qrcode::QrCode::new(text)?;
+    let mut renderer = code.render::<svg::Color>();
+    renderer
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"));
+    if let Some(size) = size {
+        renderer.min_dimensions(size, size);
+    }
+    Ok(renderer.build())
+}
+
+fn render_html(props: &QrCode) -> Html {
+    match render_svg(&props.text, props.size) {
+        Ok(svg) => Html::from_html_unchecked(AttrValue::from(svg)),
+        Err(err) => pwt::widget::error_message(&tr!("Unable to create QR code: {0}", err)).into(),
+    }
+}
+
+#[doc(hidden)]
+pub struct ProxmoxQrCode {
+    html: Html,
+}
+
+impl Component for ProxmoxQrCode {
+    type Message = ();
+    type Properties = QrCode;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            html: render_html(ctx.props()),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.text != old_props.text || props.size != old_props.size {
+            self.html = render_html(props);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        Container::new()
+            .with_std_props(&props.std_props)
+            .listeners(&props.listeners)
+            .with_child(self.html.clone())
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_svg() {
+        let svg = render_svg("otpauth://totp/test?secret=ABCDEFGH", Some(200)).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("#000000"));
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{format_err, Error};
+use derivative::Derivative;
 use html::IntoEventCallback;
 use std::rc::Rc;
 
@@ -6,7 +7,7 @@ use yew::html::IntoPropValue;
 use yew::prelude::*;
 
 use pwt::props::RenderFn;
-use pwt::state::Store;
+use pwt::state::{PersistentState, Store};
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
 use pwt::widget::form::{Selector, SelectorRenderArgs, ValidateFn};
 use pwt::widget::GridPicker;
@@ -35,6 +36,22 @@ thread_local! {
 use pwt::props::{FieldBuilder, WidgetBuilder};
 use pwt_macros::{builder, widget};
 
+/// State key used to remember the last used realm.
+const LAST_REALM_STATE: &str = "ProxmoxRealmSelectorLastRealm";
+
+/// Filter predicate for realms.
+#[derive(Derivative)]
+#[derivative(Clone, PartialEq)]
+pub struct RealmFilterFn(
+    #[derivative(PartialEq(compare_with = "Rc::ptr_eq"))] Rc<dyn Fn(&BasicRealmInfo) -> bool>,
+);
+
+impl RealmFilterFn {
+    pub fn new(filter: impl 'static + Fn(&BasicRealmInfo) -> bool) -> Self {
+        Self(Rc::new(filter))
+    }
+}
+
 #[widget(comp=ProxmoxRealmSelector, @input)]
 #[derive(Clone, Properties, PartialEq)]
 #[builder]
@@ -53,6 +70,18 @@ pub struct RealmSelector {
     #[builder_cb(IntoEventCallback, into_event_callback, BasicRealmInfo)]
     #[prop_or_default]
     pub on_change: Option<Callback<BasicRealmInfo>>,
+
+    /// Remember the last used realm and select it by default.
+    ///
+    /// An explicitly set `default` still takes precedence.
+    #[builder]
+    #[prop_or_default]
+    pub remember: bool,
+
+    /// Only show realms matching this predicate, e.g. to hide realms without login
+    /// capability.
+    #[prop_or_default]
+    pub filter: Option<RealmFilterFn>,
 }
 
 impl Default for RealmSelector {
@@ -65,6 +94,31 @@ impl RealmSelector {
     pub fn new() -> Self {
         yew::props!(Self {})
     }
+
+    /// Builder style method to set the realm filter.
+    pub fn filter(mut self, filter: impl 'static + Fn(&BasicRealmInfo) -> bool) -> Self {
+        self.set_filter(filter);
+        self
+    }
+
+    /// Method to set the realm filter.
+    pub fn set_filter(&mut self, filter: impl 'static + Fn(&BasicRealmInfo) -> bool) {
+        self.filter = Some(RealmFilterFn::new(filter));
+    }
+}
+
+/// Filter the realm list and sort the server default realm first.
+///
+/// The sort is stable, so the server order is kept otherwise.
+fn prepare_realm_list(
+    mut data: Vec<BasicRealmInfo>,
+    filter: Option<&RealmFilterFn>,
+) -> Vec<BasicRealmInfo> {
+    if let Some(RealmFilterFn(filter)) = filter {
+        data.retain(|realm| filter(realm));
+    }
+    data.sort_by_key(|realm| !realm.default.unwrap_or_default());
+    data
 }
 
 struct ProxmoxRealmSelector {
@@ -134,15 +188,26 @@ impl Component for ProxmoxRealmSelector {
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::LoadComplete(data) => {
-                let realm = ctx
-                    .props()
+                let props = ctx.props();
+                let data = prepare_realm_list(data, props.filter.as_ref());
+
+                let last_realm = props
+                    .remember
+                    .then(|| (*PersistentState::<String>::new(LAST_REALM_STATE)).clone());
+
+                let realm = props
                     .default
                     .as_ref()
                     .and_then(|d| data.iter().find(|r| &r.realm == d))
+                    .or_else(|| {
+                        last_realm
+                            .as_ref()
+                            .and_then(|last| data.iter().find(|r| &r.realm == last))
+                    })
                     .or_else(|| data.iter().find(|r| r.default.unwrap_or_default()))
                     .or_else(|| data.iter().find(|r| r.ty == "pam"));
 
-                if let Some(cb) = ctx.props().on_change.as_ref() {
+                if let Some(cb) = props.on_change.as_ref() {
                     if let Some(realm) = realm {
                         cb.emit(realm.clone());
                     }
@@ -167,10 +232,18 @@ impl Component for ProxmoxRealmSelector {
             .or_else(|| self.loaded_default_realm.clone())
             .unwrap_or(AttrValue::from("pam"));
 
-        let on_change = props.on_change.clone().map(|c| {
+        let on_change = (props.on_change.is_some() || props.remember).then(|| {
+            let on_change = props.on_change.clone();
+            let remember = props.remember;
             Callback::from(move |k| {
                 if let Some(realm) = store.read().lookup_record(&k) {
-                    c.emit(realm.clone());
+                    if remember {
+                        PersistentState::<String>::new(LAST_REALM_STATE)
+                            .update(realm.realm.clone());
+                    }
+                    if let Some(on_change) = &on_change {
+                        on_change.emit(realm.clone());
+                    }
                 }
             })
         });
@@ -188,3 +261,42 @@ impl Component for ProxmoxRealmSelector {
             .into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn realm(name: &str, ty: &str, default: Option<bool>) -> BasicRealmInfo {
+        BasicRealmInfo {
+            realm: name.into(),
+            ty: ty.into(),
+            default,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_prepare_realm_list() {
+        let data = vec![
+            realm("pam", "pam", None),
+            realm("pve", "pve", Some(false)),
+            realm("ldap", "ldap", Some(true)),
+            realm("openid", "openid", None),
+        ];
+
+        let names = |list: Vec<BasicRealmInfo>| -> Vec<String> {
+            list.into_iter().map(|r| r.realm).collect()
+        };
+
+        assert_eq!(
+            names(prepare_realm_list(data.clone(), None)),
+            ["ldap", "pam", "pve", "openid"]
+        );
+
+        let filter = RealmFilterFn::new(|r| r.ty != "openid");
+        assert_eq!(
+            names(prepare_realm_list(data, Some(&filter))),
+            ["ldap", "pam", "pve"]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use gloo_events::EventListener;
+use gloo_timers::callback::Timeout;
+
+use yew::html::Scope;
+use yew::prelude::*;
+
+/// Settings for periodically reloading components.
+///
+/// Components which poll the API use the scheduler passed as property, or the scheduler
+/// provided by a `ContextProvider<RefreshScheduler>`, or [RefreshScheduler::default] (reload
+/// every 3 seconds).
+///
+/// ```ignore
+/// html! {
+///     <ContextProvider<RefreshScheduler> context={RefreshScheduler::new(10_000)}>
+///         {children}
+///     </ContextProvider<RefreshScheduler>>
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefreshScheduler {
+    interval: u32,
+    max_interval: u32,
+    pause_when_hidden: bool,
+}
+
+impl Default for RefreshScheduler {
+    fn default() -> Self {
+        Self::new(3000)
+    }
+}
+
+impl RefreshScheduler {
+    /// Create a new scheduler reloading every `interval` milliseconds.
+    pub fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            max_interval: 60_000,
+            pause_when_hidden: true,
+        }
+    }
+
+    /// Builder style method to set the maximum interval (in milliseconds) used when backing
+    /// off after errors.
+    pub fn max_interval(mut self, max_interval: u32) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Builder style method to pause reloading while the browser tab is hidden (default).
+    pub fn pause_when_hidden(mut self, pause_when_hidden: bool) -> Self {
+        self.pause_when_hidden = pause_when_hidden;
+        self
+    }
+
+    /// The scheduler provided by the context, if any.
+    pub fn from_context<C: Component>(link: &Scope<C>) -> Option<Self> {
+        link.context::<Self>(Callback::noop())
+            .map(|(scheduler, _handle)| scheduler)
+    }
+
+    /// The delay until the next reload after `errors` consecutive load errors.
+    ///
+    /// Doubles the interval for each error, up to the maximum interval.
+    pub fn delay(&self, errors: u32) -> u32 {
+        let max_interval = self.max_interval.max(self.interval);
+        self.interval
+            .saturating_mul(1 << errors.min(16))
+            .min(max_interval)
+    }
+
+    /// Schedule the next reload.
+    ///
+    /// The reload is canceled when the returned [RefreshTimeout] gets dropped.
+    pub fn schedule(&self, errors: u32, callback: impl Into<Callback<()>>) -> RefreshTimeout {
+        let callback = callback.into();
+        let listener = Rc::new(RefCell::new(None));
+        let pause_when_hidden = self.pause_when_hidden;
+
+        let timeout = Timeout::new(self.delay(errors), {
+            let listener = Rc::clone(&listener);
+            move || {
+                let document = gloo_utils::document();
+                if !(pause_when_hidden && document.hidden()) {
+                    callback.emit(());
+                    return;
+                }
+                // wait until the tab is visible again
+                let fired = Cell::new(false);
+                *listener.borrow_mut() = Some(EventListener::new(
+                    &document,
+                    "visibilitychange",
+                    move |_| {
+                        if !fired.get() && !gloo_utils::document().hidden() {
+                            fired.set(true);
+                            callback.emit(());
+                        }
+                    },
+                ));
+            }
+        });
+
+        RefreshTimeout {
+            _timeout: timeout,
+            _listener: listener,
+        }
+    }
+}
+
+/// A scheduled reload, see [RefreshScheduler::schedule].
+pub struct RefreshTimeout {
+    _timeout: Timeout,
+    _listener: Rc<RefCell<Option<EventListener>>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let scheduler = RefreshScheduler::new(3000).max_interval(20_000);
+        assert_eq!(scheduler.delay(0), 3000);
+        assert_eq!(scheduler.delay(1), 6000);
+        assert_eq!(scheduler.delay(2), 12000);
+        assert_eq!(scheduler.delay(3), 20000);
+        assert_eq!(scheduler.delay(100), 20000);
+
+        // the maximum never undercuts the interval
+        assert_eq!(RefreshScheduler::new(90_000).delay(1), 90_000);
+    }
+}
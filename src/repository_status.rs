@@ -0,0 +1,449 @@
+//! Health status of the APT repository configuration.
+//!
+//! The same checks are used by the [AptRepositories](crate::AptRepositories) panel,
+//! and by [RepositoryHealthRows], which can be embedded into dashboards.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::props::ExtractPrimaryKey;
+use pwt::widget::{Column, Container, Fa, Row};
+use pwt::AsyncPool;
+
+use pwt_macros::builder;
+
+use proxmox_apt_api_types::{
+    APTRepositoriesResult, APTRepositoryPackageType, APTStandardRepoSummary, APTStandardRepository,
+};
+
+use crate::subscription_alert::subscription_is_active;
+use crate::{ExistingProduct, ProjectInfo, Status};
+
+/// A single status message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepositoryStatusLine {
+    /// [Status::Success], [Status::Warning] or [Status::Error].
+    pub status: Status,
+    pub message: String,
+}
+
+impl RepositoryStatusLine {
+    fn ok(msg: impl Into<String>) -> Self {
+        Self {
+            status: Status::Success,
+            message: msg.into(),
+        }
+    }
+    fn warning(msg: impl Into<String>) -> Self {
+        Self {
+            status: Status::Warning,
+            message: msg.into(),
+        }
+    }
+    fn error(msg: impl Into<String>) -> Self {
+        Self {
+            status: Status::Error,
+            message: msg.into(),
+        }
+    }
+
+    /// Render the message with a status icon.
+    pub fn render(&self) -> Html {
+        let (icon, color_class) = match self.status {
+            Status::Warning => ("exclamation", "pwt-color-warning"),
+            Status::Error => ("times", "pwt-color-error"),
+            _ => ("check", ""),
+        };
+        let icon = Fa::new(icon).fixed_width().padding_end(2);
+        html! {<span class={color_class}>{icon}{&self.message}</span>}
+    }
+}
+
+impl ExtractPrimaryKey for RepositoryStatusLine {
+    fn extract_key(&self) -> Key {
+        Key::from(self.message.clone())
+    }
+}
+
+/// Status of the repository configuration, as list of messages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RepositoryStatus {
+    pub lines: Vec<RepositoryStatusLine>,
+}
+
+impl RepositoryStatus {
+    /// Compute the status of a repository configuration.
+    ///
+    /// Without `product`, only parsing errors are reported.
+    // Note: this should implement the same logic we have in APTRepositories.js
+    pub fn compute(
+        product: Option<ExistingProduct>,
+        config: &APTRepositoriesResult,
+        standard_repos: &HashMap<String, APTStandardRepository>,
+        active_subscription: bool,
+    ) -> Self {
+        let mut list = Vec::new();
+
+        for error in &config.errors {
+            list.push(RepositoryStatusLine::error(format!(
+                "{} - {}",
+                error.path, error.error
+            )));
+        }
+
+        let product = match product {
+            Some(product) => product,
+            None => return Self { lines: list },
+        };
+
+        let summary = APTStandardRepoSummary::from_repos(standard_repos.values());
+        for handle in &summary.unrecognized {
+            log::warn!("encountered unknown APT repository handle variant '{handle}'");
+        }
+        let has_enterprise = summary.has_enterprise;
+        let has_no_subscription = summary.has_no_subscription;
+        let has_test = summary.has_test;
+        let has_ceph_enterprise = summary.has_ceph_enterprise;
+        let has_ceph_no_subscription = summary.has_ceph_no_subscription;
+        let has_ceph_test = summary.has_ceph_test;
+
+        if !(has_enterprise | has_no_subscription | has_test) {
+            list.push(RepositoryStatusLine::error(tr!(
+                "No {0} repository is enabled, you do not get any updates!",
+                product.project_text()
+            )));
+        } else if config.errors.is_empty() {
+            // just avoid that we show "get updates"
+            if has_test || has_no_subscription {
+                list.push(RepositoryStatusLine::ok(tr!(
+                    "You get updates for {0}",
+                    product.project_text()
+                )));
+            } else if has_enterprise && active_subscription {
+                list.push(RepositoryStatusLine::ok(tr!(
+                    "You get supported updates for {0}",
+                    product.project_text()
+                )));
+            }
+        }
+
+        let mut enabled_repos: HashSet<(&str, usize)> = HashSet::new();
+        for file in &config.files {
+            for (index, repo) in file.repositories.iter().enumerate() {
+                if repo.enabled {
+                    if let Some(path) = &file.path {
+                        enabled_repos.insert((path, index));
+                    }
+                }
+            }
+        }
+
+        let mut mixed_suites = false;
+        let mut check_mixed_suites = false;
+
+        let mut ignore_pre_upgrade_warning: HashSet<(&str, usize)> = HashSet::new();
+        let mut controlled_origin: HashSet<(&str, usize)> = HashSet::new();
+        for info in &config.infos {
+            if info.kind == "ignore-pre-upgrade-warning" {
+                ignore_pre_upgrade_warning.insert((&info.path, info.index));
+                check_mixed_suites = true;
+            }
+            if info.kind == "origin" && (info.message == "Debian" || info.message == "Proxmox") {
+                controlled_origin.insert((&info.path, info.index));
+            }
+        }
+
+        let mut suites_warning = false;
+        for info in &config.infos {
+            if info.kind == "warning"
+                && info.property.as_deref() == Some("Suites")
+                && enabled_repos.contains(&(&info.path, info.index))
+            {
+                suites_warning = true;
+                break;
+            }
+        }
+
+        if suites_warning {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "Some suites are misconfigured"
+            )));
+        }
+
+        for file in &config.files {
+            if let Some(path) = &file.path {
+                for (index, repo) in file.repositories.iter().enumerate() {
+                    if check_mixed_suites
+                        && repo.enabled
+                        && repo.types.contains(&APTRepositoryPackageType::Deb)
+                        && controlled_origin.contains(&(path, index))
+                    {
+                        mixed_suites = true;
+                    }
+                }
+            }
+        }
+
+        if mixed_suites {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "Detected mixed suites before upgrade"
+            )));
+        }
+
+        // production ready check
+        if has_enterprise && !active_subscription {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "The {0}enterprise repository is enabled, but there is no active subscription!",
+                product.project_text() + " ",
+            )));
+        }
+
+        if has_no_subscription {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "The {0}no-subscription{1} repository is not recommended for production use!",
+                product.project_text() + " ",
+                "",
+            )));
+        }
+
+        if has_test {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "The {0}test repository may pull in unstable updates and is not recommended for production use!",
+                product.project_text() + " ",
+            )));
+        }
+
+        // check Ceph repositories
+        if has_ceph_enterprise && !active_subscription {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "The {0}enterprise repository is enabled, but there is no active subscription!",
+                "Ceph ",
+            )));
+        }
+
+        if has_ceph_no_subscription {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "The {0}no-subscription{1} repository is not recommended for production use!",
+                "Ceph ",
+                "/main", // TODO drop alternate 'main' name when no longer relevant
+            )));
+        }
+
+        if has_ceph_test {
+            list.push(RepositoryStatusLine::warning(tr!(
+                "The {0}test repository may pull in unstable updates and is not recommended for production use!",
+                "Ceph ",
+            )));
+        }
+
+        if !config.errors.is_empty() {
+            list.push(RepositoryStatusLine::error(tr!(
+                "Fatal parsing error for at least one repository"
+            )));
+        }
+
+        if !list.iter().any(|l| l.status != Status::Success) {
+            list.push(RepositoryStatusLine::ok(tr!(
+                "All OK, you have production-ready repositories configured!"
+            )));
+        }
+
+        Self { lines: list }
+    }
+
+    /// Compute the status, using the standard repositories of the configuration.
+    pub fn from_config(
+        product: Option<ExistingProduct>,
+        config: &APTRepositoriesResult,
+        active_subscription: bool,
+    ) -> Self {
+        let standard_repos: HashMap<String, APTStandardRepository> = config
+            .standard_repos
+            .iter()
+            .filter_map(|item| Some((serde_plain::to_string(&item.handle).ok()?, item.clone())))
+            .collect();
+        Self::compute(product, config, &standard_repos, active_subscription)
+    }
+
+    /// Load the repository configuration and subscription status, and compute the status.
+    pub async fn load(
+        product: Option<ExistingProduct>,
+        base_url: &str,
+        subscription_url: &str,
+    ) -> Result<Self, Error> {
+        let config: APTRepositoriesResult =
+            crate::http_get(format!("{base_url}/repositories"), None).await?;
+        let subscription: Result<Value, Error> = crate::http_get(subscription_url, None).await;
+        let active_subscription = subscription_is_active(Some(&subscription));
+        Ok(Self::from_config(product, &config, active_subscription))
+    }
+
+    /// The most severe status of all messages.
+    pub fn overall(&self) -> Status {
+        let level = |status: Status| match status {
+            Status::Error => 2,
+            Status::Warning => 1,
+            _ => 0,
+        };
+        self.lines
+            .iter()
+            .map(|line| line.status)
+            .max_by_key(|status| level(*status))
+            .unwrap_or(Status::Unknown)
+    }
+}
+
+/// Repository health messages, for dashboards like the node summary.
+///
+/// Loads the repository configuration and subscription status itself, and shows
+/// the same messages as the [AptRepositories](crate::AptRepositories) panel.
+#[derive(Properties, PartialEq, Clone)]
+#[builder]
+pub struct RepositoryHealthRows {
+    /// The base url for the APT endpoint (see [AptRepositories](crate::AptRepositories)).
+    #[prop_or("/nodes/localhost/apt".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub base_url: AttrValue,
+
+    /// The subscription url for getting the nodes subscription status.
+    #[prop_or("/nodes/localhost/subscription".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub subscription_url: AttrValue,
+
+    /// The Product
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub product: Option<ExistingProduct>,
+
+    /// Only show warnings and errors (and a single line if everything is fine).
+    #[builder]
+    #[prop_or_default]
+    pub problems_only: bool,
+}
+
+impl Default for RepositoryHealthRows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RepositoryHealthRows {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Load,
+    Loaded(Result<RepositoryStatus, Error>),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxRepositoryHealthRows {
+    status: Option<Result<RepositoryStatus, String>>,
+    async_pool: AsyncPool,
+}
+
+impl Component for ProxmoxRepositoryHealthRows {
+    type Message = Msg;
+    type Properties = RepositoryHealthRows;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            status: None,
+            async_pool: AsyncPool::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Load => {
+                let props = ctx.props();
+                let product = props.product;
+                let base_url = props.base_url.clone();
+                let subscription_url = props.subscription_url.clone();
+                let link = ctx.link().clone();
+                self.async_pool.spawn(async move {
+                    let status =
+                        RepositoryStatus::load(product, &base_url, &subscription_url).await;
+                    link.send_message(Msg::Loaded(status));
+                });
+                false
+            }
+            Msg::Loaded(status) => {
+                self.status = Some(status.map_err(|err| err.to_string()));
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.base_url != old_props.base_url
+            || props.subscription_url != old_props.subscription_url
+            || props.product != old_props.product
+        {
+            ctx.link().send_message(Msg::Load);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let status = match &self.status {
+            None => return Container::new().with_child(tr!("Loading...")).into(),
+            Some(Err(err)) => {
+                return RepositoryStatusLine::error(tr!("Unable to load repositories: {0}", err))
+                    .render()
+            }
+            Some(Ok(status)) => status,
+        };
+
+        let mut lines: Vec<&RepositoryStatusLine> = status.lines.iter().collect();
+        if ctx.props().problems_only {
+            lines.retain(|line| line.status != Status::Success);
+        }
+
+        let mut column = Column::new().gap(1);
+        if lines.is_empty() {
+            column.add_child(
+                RepositoryStatusLine::ok(tr!("Repositories are configured correctly")).render(),
+            );
+        }
+        for line in lines {
+            column.add_child(Row::new().with_child(line.render()));
+        }
+        column.into()
+    }
+}
+
+impl From<RepositoryHealthRows> for VNode {
+    fn from(val: RepositoryHealthRows) -> Self {
+        let comp = VComp::new::<ProxmoxRepositoryHealthRows>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overall_status() {
+        let mut status = RepositoryStatus::default();
+        assert_eq!(status.overall(), Status::Unknown);
+        status.lines.push(RepositoryStatusLine::ok("ok"));
+        assert_eq!(status.overall(), Status::Success);
+        status.lines.push(RepositoryStatusLine::error("error"));
+        status.lines.push(RepositoryStatusLine::warning("warning"));
+        assert_eq!(status.overall(), Status::Error);
+    }
+}
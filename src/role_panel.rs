@@ -0,0 +1,566 @@
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+use proxmox_client::ApiResponseData;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader, DataTableMouseEvent};
+use pwt::widget::form::{
+    Checkbox, Field, FormContext, ManagedField, ManagedFieldContext, ManagedFieldMaster,
+    ManagedFieldScopeExt, ManagedFieldState,
+};
+use pwt::widget::{Button, Container, InputPanel, Toolbar};
+
+use pwt_macros::{builder, widget};
+
+use crate::percent_encoding::percent_encode_component;
+use crate::role_selector::render_privileges;
+use crate::utils::render_boolean;
+use crate::{
+    ConfirmButton, EditWindow, ExistingProduct, LoadableComponent, LoadableComponentContext,
+    LoadableComponentMaster, LoadableComponentScopeExt, LoadableComponentState,
+};
+
+/// Role list entry (`GET /access/roles`).
+///
+/// PVE returns the privileges as comma separated string and marks built-in roles
+/// as `special`, PBS and PDM return a privilege list.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PveRoleInfo {
+    pub roleid: String,
+    #[serde(default, deserialize_with = "deserialize_privilege_list")]
+    pub privs: Vec<String>,
+    /// Built-in role, cannot be modified.
+    #[serde(deserialize_with = "proxmox_serde::perl::deserialize_bool")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub special: Option<bool>,
+}
+
+impl PveRoleInfo {
+    /// Returns true for built-in roles.
+    pub fn is_special(&self) -> bool {
+        self.special.unwrap_or(false)
+    }
+}
+
+fn deserialize_privilege_list<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PrivilegeList {
+        List(Vec<String>),
+        Text(String),
+    }
+
+    Ok(match Option::<PrivilegeList>::deserialize(deserializer)? {
+        Some(PrivilegeList::List(list)) => list,
+        Some(PrivilegeList::Text(text)) => text
+            .split([',', ' '])
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect(),
+        None => Vec::new(),
+    })
+}
+
+/// Collect all known privileges from a role list.
+///
+/// There is no separate privilege listing, but the built-in `Administrator`
+/// role contains all privileges.
+fn collect_privileges(roles: &[PveRoleInfo]) -> Vec<String> {
+    roles
+        .iter()
+        .flat_map(|role| role.privs.iter().cloned())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+#[widget(comp=ManagedFieldMaster<PrivilegeSelectorMaster>, @input)]
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct PrivilegeSelector {
+    /// The privileges to choose from.
+    #[prop_or_default]
+    pub privileges: Rc<Vec<String>>,
+}
+
+impl PrivilegeSelector {
+    pub fn new(privileges: Rc<Vec<String>>) -> Self {
+        yew::props!(Self { privileges })
+    }
+}
+
+pub enum Msg {
+    SetValue(String, bool),
+}
+
+#[doc(hidden)]
+pub struct PrivilegeSelectorMaster {
+    state: ManagedFieldState,
+    selection: BTreeSet<String>,
+}
+
+pwt::impl_deref_mut_property!(PrivilegeSelectorMaster, state, ManagedFieldState);
+
+impl PrivilegeSelectorMaster {
+    fn update_selection(&mut self) {
+        self.selection = match &self.state.value {
+            Value::String(list) => list
+                .split([',', ' '])
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            Value::Array(list) => list
+                .iter()
+                .filter_map(|item| item.as_str().map(String::from))
+                .collect(),
+            _ => BTreeSet::new(),
+        };
+    }
+}
+
+impl ManagedField for PrivilegeSelectorMaster {
+    type Message = Msg;
+    type Properties = PrivilegeSelector;
+    type ValidateClosure = bool;
+
+    fn validation_args(props: &Self::Properties) -> Self::ValidateClosure {
+        props.input_props.required
+    }
+
+    fn validator(required: &Self::ValidateClosure, value: &Value) -> Result<Value, Error> {
+        let list: Vec<&str> = match value {
+            Value::Array(list) => list.iter().filter_map(|item| item.as_str()).collect(),
+            Value::String(list) => list.split([',', ' ']).filter(|s| !s.is_empty()).collect(),
+            _ => Vec::new(),
+        };
+        if *required && list.is_empty() {
+            return Err(Error::msg(tr!("Field may not be empty.")));
+        }
+        Ok(Value::from(list.join(",")))
+    }
+
+    fn create(_ctx: &ManagedFieldContext<Self>) -> Self {
+        let mut me = Self {
+            state: ManagedFieldState::new(Value::Null, Value::Null),
+            selection: BTreeSet::new(),
+        };
+        me.update_selection();
+        me
+    }
+
+    fn value_changed(&mut self, _ctx: &ManagedFieldContext<Self>) {
+        self.update_selection();
+    }
+
+    fn update(&mut self, ctx: &ManagedFieldContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetValue(name, checked) => {
+                if checked {
+                    self.selection.insert(name);
+                } else {
+                    self.selection.remove(&name);
+                }
+            }
+        }
+        ctx.link()
+            .update_value(serde_json::to_value(&self.selection).unwrap());
+        true
+    }
+
+    fn view(&self, ctx: &ManagedFieldContext<Self>) -> Html {
+        let props = ctx.props();
+        let disabled = props.input_props.disabled;
+
+        let children: Vec<Html> = props
+            .privileges
+            .iter()
+            .map(|privilege| {
+                let name = privilege.clone();
+                Checkbox::new()
+                    .disabled(disabled)
+                    .checked(self.selection.contains(privilege))
+                    .box_label(privilege.clone())
+                    .on_input(
+                        ctx.link()
+                            .callback(move |checked| Msg::SetValue(name.clone(), checked)),
+                    )
+                    .into()
+            })
+            .collect();
+
+        Container::new()
+            .class("pwt-d-grid pwt-gap-1")
+            .style(
+                "grid-template-columns",
+                "repeat(auto-fill, minmax(220px, 1fr))",
+            )
+            .style("max-height", "400px")
+            .class("pwt-overflow-auto")
+            .children(children)
+            .into()
+    }
+}
+
+#[derive(PartialEq, Properties)]
+#[builder]
+pub struct RolePanel {
+    /// The API endpoint to list, create, update and delete roles.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or(AttrValue::Static("/access/roles"))]
+    role_api_endpoint: AttrValue,
+
+    /// The Product. Only PVE supports custom roles, the roles of other products
+    /// are shown read-only.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    product: Option<ExistingProduct>,
+
+    /// Read-only view - hide toolbar and all buttons/menus to edit content.
+    #[builder]
+    #[prop_or_default]
+    readonly: bool,
+}
+
+impl Default for RolePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RolePanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+
+    fn editable(&self) -> bool {
+        !self.readonly && self.product == Some(ExistingProduct::PVE)
+    }
+}
+
+#[derive(PartialEq)]
+pub enum ViewState {
+    Add,
+    Edit,
+}
+
+pub enum PanelMsg {
+    Remove,
+}
+
+pub struct ProxmoxRolePanel {
+    state: LoadableComponentState<ViewState>,
+    store: Store<PveRoleInfo>,
+    selection: Selection,
+}
+
+pwt::impl_deref_mut_property!(ProxmoxRolePanel, state, LoadableComponentState<ViewState>);
+
+impl LoadableComponent for ProxmoxRolePanel {
+    type Message = PanelMsg;
+    type Properties = RolePanel;
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let store =
+            Store::with_extract_key(|record: &PveRoleInfo| Key::from(record.roleid.as_str()));
+
+        let selection = Selection::new().on_select({
+            let link = ctx.link().clone();
+            move |_| link.send_redraw()
+        });
+
+        Self {
+            state: LoadableComponentState::new(),
+            store,
+            selection,
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let store = self.store.clone();
+        let url = ctx.props().role_api_endpoint.clone();
+        Box::pin(async move {
+            let data: Vec<PveRoleInfo> = crate::http_get(&*url, None).await?;
+            store.write().set_data(data);
+            Ok(())
+        })
+    }
+
+    fn update(&mut self, ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PanelMsg::Remove => {
+                if !ctx.props().editable() {
+                    return false;
+                }
+                if let Some(key) = self.selected_custom_role() {
+                    let link = ctx.link().clone();
+                    let url = format!(
+                        "{}/{}",
+                        ctx.props().role_api_endpoint,
+                        percent_encode_component(&key)
+                    );
+                    link.clone().spawn(async move {
+                        if let Err(err) = crate::http_delete(&url, None).await {
+                            link.show_error(tr!("Unable to remove role"), err, true);
+                        }
+                        link.send_reload();
+                    })
+                }
+                false
+            }
+        }
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let link = ctx.link();
+        let refresh = {
+            let loading = self.loading();
+            let link = ctx.link().clone();
+            Button::refresh(loading).onclick(move |_| link.send_reload())
+        };
+
+        if !ctx.props().editable() {
+            let toolbar = Toolbar::new()
+                .class("pwt-w-100")
+                .class("pwt-border-bottom")
+                .with_flex_spacer()
+                .with_child(refresh);
+            return Some(toolbar.into());
+        }
+
+        // built-in roles cannot be modified
+        let selected = self.selected_custom_role();
+        let no_selection = selected.is_none();
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_child(
+                Button::new(tr!("Create"))
+                    .onclick(link.change_view_callback(|_| Some(ViewState::Add))),
+            )
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Edit"))
+                    .disabled(no_selection)
+                    .onclick(link.change_view_callback(|_| Some(ViewState::Edit))),
+            )
+            .with_child(
+                ConfirmButton::new(tr!("Remove"))
+                    .dangerous(true)
+                    .disabled(no_selection)
+                    .confirm_message(match &selected {
+                        Some(key) => {
+                            tr!(
+                                "Are you sure you want to remove role '{0}'?",
+                                key.to_string()
+                            )
+                        }
+                        None => String::new(),
+                    })
+                    .on_activate(link.callback(|_| PanelMsg::Remove)),
+            )
+            .with_flex_spacer()
+            .with_child(refresh);
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let mut table = DataTable::new(columns(), self.store.clone())
+            .class("pwt-flex-fill pwt-overflow-auto")
+            .selection(self.selection.clone())
+            .striped(true);
+
+        if ctx.props().editable() {
+            let link = ctx.link().clone();
+            let store = self.store.clone();
+            table = table.on_row_dblclick(move |event: &mut DataTableMouseEvent| {
+                let special = store
+                    .read()
+                    .lookup_record(&event.record_key)
+                    .is_some_and(|role| role.is_special());
+                if !special {
+                    link.change_view(Some(ViewState::Edit));
+                }
+            });
+        }
+
+        table.into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        if !ctx.props().editable() {
+            return None;
+        }
+        match view_state {
+            ViewState::Add => Some(self.create_edit_dialog(ctx, None)),
+            ViewState::Edit => self
+                .selected_custom_role()
+                .map(|key| self.create_edit_dialog(ctx, Some(key))),
+        }
+    }
+}
+
+impl ProxmoxRolePanel {
+    /// The selected role, if it is not a built-in role.
+    fn selected_custom_role(&self) -> Option<Key> {
+        let key = self.selection.selected_key()?;
+        let special = self
+            .store
+            .read()
+            .lookup_record(&key)
+            .map(|role| role.is_special())
+            .unwrap_or(true);
+        (!special).then_some(key)
+    }
+
+    fn create_edit_dialog(&self, ctx: &LoadableComponentContext<Self>, key: Option<Key>) -> Html {
+        let privileges = Rc::new(collect_privileges(self.store.read().data()));
+        let base_url = ctx.props().role_api_endpoint.clone();
+        let is_edit = key.is_some();
+
+        let title = if is_edit { tr!("Edit") } else { tr!("Create") } + ": " + &tr!("Role");
+
+        let mut window = EditWindow::new(title)
+            .renderer(move |_form_ctx: &FormContext| {
+                InputPanel::new()
+                    .padding(4)
+                    .with_large_field(
+                        tr!("Name"),
+                        Field::new()
+                            .name("roleid")
+                            .required(true)
+                            .disabled(is_edit)
+                            .submit(!is_edit),
+                    )
+                    .with_large_field(
+                        tr!("Privileges"),
+                        PrivilegeSelector::new(privileges.clone())
+                            .name("privs")
+                            .required(true),
+                    )
+                    .into()
+            })
+            .on_done(ctx.link().change_view_callback(|_| None));
+
+        window = match key {
+            Some(key) => {
+                let record = self.store.read().lookup_record(&key).cloned();
+                let url = format!("{base_url}/{}", percent_encode_component(&key));
+                window
+                    .loader(move || {
+                        let record = record.clone();
+                        async move {
+                            let data = match record {
+                                Some(role) => json!({ "roleid": role.roleid, "privs": role.privs }),
+                                None => Value::Null,
+                            };
+                            Ok(ApiResponseData {
+                                data,
+                                attribs: Default::default(),
+                            })
+                        }
+                    })
+                    .on_submit(move |form_ctx: FormContext| {
+                        let url = url.clone();
+                        async move {
+                            let data = form_ctx.get_submit_data();
+                            crate::http_put(&url, Some(data)).await
+                        }
+                    })
+            }
+            None => window.on_submit(move |form_ctx: FormContext| {
+                let url = base_url.clone();
+                async move {
+                    let data = form_ctx.get_submit_data();
+                    crate::http_post(&*url, Some(data)).await
+                }
+            }),
+        };
+
+        window.into()
+    }
+}
+
+impl From<RolePanel> for VNode {
+    fn from(val: RolePanel) -> Self {
+        let comp = VComp::new::<LoadableComponentMaster<ProxmoxRolePanel>>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+fn columns() -> Rc<Vec<DataTableHeader<PveRoleInfo>>> {
+    Rc::new(vec![
+        DataTableColumn::new(tr!("Name"))
+            .width("200px")
+            .render(|record: &PveRoleInfo| html! {record.roleid.clone()})
+            .sorter(|a: &PveRoleInfo, b: &PveRoleInfo| a.roleid.cmp(&b.roleid))
+            .sort_order(true)
+            .into(),
+        DataTableColumn::new(tr!("Built-In"))
+            .width("100px")
+            .render(|record: &PveRoleInfo| render_boolean(record.is_special()).into())
+            .into(),
+        DataTableColumn::new(tr!("Privileges"))
+            .flex(1)
+            .render(|record: &PveRoleInfo| render_privileges(&record.privs))
+            .into(),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collect_privileges() {
+        let role = |roleid: &str, privs: &[&str]| -> PveRoleInfo {
+            serde_json::from_value(json!({ "roleid": roleid, "privs": privs })).unwrap()
+        };
+        let roles = vec![
+            role("Audit", &["Sys.Audit", "Datastore.Audit"]),
+            role("Backup", &["Datastore.Backup", "Datastore.Audit"]),
+        ];
+        assert_eq!(
+            collect_privileges(&roles),
+            vec!["Datastore.Audit", "Datastore.Backup", "Sys.Audit"]
+        );
+    }
+
+    #[test]
+    fn test_pve_role_info() {
+        let role: PveRoleInfo = serde_json::from_value(json!({
+            "roleid": "PVEAuditor",
+            "privs": "Sys.Audit,VM.Audit",
+            "special": 1,
+        }))
+        .unwrap();
+        assert_eq!(role.privs, vec!["Sys.Audit", "VM.Audit"]);
+        assert!(role.is_special());
+
+        let role: PveRoleInfo =
+            serde_json::from_value(json!({ "roleid": "Custom", "privs": [] })).unwrap();
+        assert!(!role.is_special());
+    }
+}
@@ -9,7 +9,7 @@ use pwt::props::RenderFn;
 use pwt::state::Store;
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
 use pwt::widget::form::{Selector, SelectorRenderArgs, ValidateFn};
-use pwt::widget::GridPicker;
+use pwt::widget::{GridPicker, Tooltip};
 
 use proxmox_access_control::types::RoleInfo;
 
@@ -29,14 +29,34 @@ thread_local! {
         DataTableColumn::new(tr!("Privileges"))
             .width("400px")
             .show_menu(false)
-            .render(|record: &RoleInfo| {
-                let text = record.privs.join(" ");
-                html!{<span class="pwt-white-space-normal">{text}</span>}
-            })
+            .render(|record: &RoleInfo| render_privileges(&record.privs))
             .into(),
     ]);
 }
 
+/// Number of privileges listed before the list gets abbreviated.
+const PRIVILEGE_SUMMARY_COUNT: usize = 4;
+
+/// Render an abbreviated privilege list, the full list is shown as tooltip.
+pub(crate) fn render_privileges(privs: &[String]) -> Html {
+    let count = privs.len();
+    if count <= PRIVILEGE_SUMMARY_COUNT {
+        let text = privs.join(" ");
+        return html! {<span class="pwt-white-space-normal">{text}</span>};
+    }
+
+    let summary = format!(
+        "{} {}",
+        privs[..PRIVILEGE_SUMMARY_COUNT].join(" "),
+        tr!("(+{0} more)", count - PRIVILEGE_SUMMARY_COUNT),
+    );
+    let tip = html! {<pre class="pwt-font-monospace">{privs.join("\n")}</pre>};
+
+    Tooltip::new(html! {<span class="pwt-white-space-normal">{summary}</span>})
+        .rich_tip(tip)
+        .into()
+}
+
 use pwt::props::{FieldBuilder, WidgetBuilder};
 use pwt_macros::{builder, widget};
 
@@ -0,0 +1,183 @@
+use anyhow::{format_err, Error};
+use wasm_bindgen::JsCast;
+
+use pwt::convert_js_error;
+
+// style properties needed to render the SVG outside of the page
+const INLINE_STYLE_PROPERTIES: &[&str] = &[
+    "fill",
+    "fill-opacity",
+    "stroke",
+    "stroke-opacity",
+    "stroke-width",
+    "stroke-dasharray",
+    "opacity",
+    "font-family",
+    "font-size",
+    "font-weight",
+    "text-anchor",
+    "dominant-baseline",
+];
+
+fn inline_computed_style(
+    window: &web_sys::Window,
+    source: &web_sys::Element,
+    target: &web_sys::Element,
+) -> Result<(), Error> {
+    let style = match window
+        .get_computed_style(source)
+        .map_err(convert_js_error)?
+    {
+        Some(style) => style,
+        None => return Ok(()),
+    };
+
+    let mut inline_style = String::new();
+    for property in INLINE_STYLE_PROPERTIES {
+        if let Ok(value) = style.get_property_value(property) {
+            if !value.is_empty() {
+                inline_style.push_str(&format!("{property}:{value};"));
+            }
+        }
+    }
+    let _ = target.set_attribute("style", &inline_style);
+    Ok(())
+}
+
+/// Serialize a SVG element, including the styles applied by CSS classes.
+pub(crate) fn svg_to_string(svg: &web_sys::Element) -> Result<String, Error> {
+    let window = gloo_utils::window();
+
+    let copy = svg
+        .clone_node_with_deep(true)
+        .map_err(convert_js_error)?
+        .dyn_into::<web_sys::Element>()
+        .map_err(|_| format_err!("unable to copy SVG element"))?;
+
+    inline_computed_style(&window, svg, &copy)?;
+
+    let source_list = svg.query_selector_all("*").map_err(convert_js_error)?;
+    let target_list = copy.query_selector_all("*").map_err(convert_js_error)?;
+    for i in 0..source_list.length() {
+        let source = source_list.item(i).and_then(|n| n.dyn_into().ok());
+        let target = target_list.item(i).and_then(|n| n.dyn_into().ok());
+        if let (Some(source), Some(target)) = (source, target) {
+            inline_computed_style(&window, &source, &target)?;
+        }
+    }
+
+    let _ = copy.set_attribute("xmlns", "http://www.w3.org/2000/svg");
+
+    Ok(copy.outer_html())
+}
+
+/// Render a serialized SVG image as PNG.
+///
+/// The image is scaled with the device pixel ratio and drawn on the `background` color.
+pub(crate) async fn svg_to_png(
+    svg: &str,
+    width: f64,
+    height: f64,
+    background: Option<&str>,
+) -> Result<web_sys::Blob, Error> {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("image/svg+xml");
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(svg));
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(convert_js_error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(convert_js_error)?;
+
+    let image = web_sys::HtmlImageElement::new().map_err(convert_js_error)?;
+    let loaded = js_sys::Promise::new(&mut |resolve, reject| {
+        image.set_onload(Some(&resolve));
+        image.set_onerror(Some(&reject));
+    });
+    image.set_src(&url);
+    let result = wasm_bindgen_futures::JsFuture::from(loaded).await;
+    let _ = web_sys::Url::revoke_object_url(&url);
+    result.map_err(|_| format_err!("unable to load SVG image"))?;
+
+    let scale = gloo_utils::window().device_pixel_ratio().max(1.0);
+
+    let canvas = gloo_utils::document()
+        .create_element("canvas")
+        .map_err(convert_js_error)?
+        .dyn_into::<web_sys::HtmlCanvasElement>()
+        .map_err(|_| format_err!("unable to create canvas"))?;
+    canvas.set_width((width * scale) as u32);
+    canvas.set_height((height * scale) as u32);
+
+    let context = canvas
+        .get_context("2d")
+        .map_err(convert_js_error)?
+        .and_then(|context| context.dyn_into::<web_sys::CanvasRenderingContext2d>().ok())
+        .ok_or_else(|| format_err!("unable to get canvas context"))?;
+
+    let _ = context.scale(scale, scale);
+    if let Some(background) = background {
+        context.set_fill_style_str(background);
+        context.fill_rect(0.0, 0.0, width, height);
+    }
+    context
+        .draw_image_with_html_image_element(&image, 0.0, 0.0)
+        .map_err(convert_js_error)?;
+
+    let png = js_sys::Promise::new(&mut |resolve, _reject| {
+        let _ = canvas.to_blob_with_type(&resolve, "image/png");
+    });
+    wasm_bindgen_futures::JsFuture::from(png)
+        .await
+        .map_err(convert_js_error)?
+        .dyn_into::<web_sys::Blob>()
+        .map_err(|_| format_err!("unable to create PNG image"))
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Format the series data as CSV, with one row per point in time.
+///
+/// Missing values are left empty.
+pub(crate) fn series_to_csv(time_data: &[i64], series: &[(&str, &[f64])]) -> String {
+    let mut csv = String::from("time");
+    for (label, _) in series {
+        csv.push(',');
+        csv.push_str(&csv_escape(label));
+    }
+    csv.push('\n');
+
+    for (i, t) in time_data.iter().enumerate() {
+        csv.push_str(&t.to_string());
+        for (_, data) in series {
+            csv.push(',');
+            if let Some(v) = data.get(i).filter(|v| v.is_finite()) {
+                csv.push_str(&v.to_string());
+            }
+        }
+        csv.push('\n');
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_series_to_csv() {
+        let csv = series_to_csv(
+            &[60, 120],
+            &[
+                ("netin", &[1.5, f64::NAN][..]),
+                ("disk \"a\", b", &[2.0][..]),
+            ],
+        );
+        assert_eq!(csv, "time,netin,\"disk \"\"a\"\", b\"\n60,1.5,2\n120,,\n");
+    }
+}
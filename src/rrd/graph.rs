@@ -1,7 +1,9 @@
 use std::rc::Rc;
 
+use anyhow::{format_err, Error};
 use derivative::Derivative;
 
+use yew::context::ContextHandle;
 use yew::html::IntoPropValue;
 use yew::prelude::*;
 use yew::virtual_dom::{VComp, VNode};
@@ -10,8 +12,9 @@ use pwt::dom::align::{align_to, AlignOptions};
 use pwt::dom::DomSizeObserver;
 use pwt::prelude::*;
 use pwt::props::{IntoOptionalRenderFn, RenderFn};
-use pwt::state::optional_rc_ptr_eq;
+use pwt::widget::menu::{Menu, MenuButton, MenuItem};
 use pwt::widget::{Button, Container, Panel};
+use pwt::AsyncPool;
 
 use pwt_macros::builder;
 
@@ -31,15 +34,10 @@ pub struct RRDGraph {
     #[prop_or_default]
     pub time_data: Rc<Vec<i64>>,
 
+    /// The data series. The legend (to toggle series) is shown if there is more than one.
     #[prop_or_default]
-    #[derivative(PartialEq(compare_with = "optional_rc_ptr_eq"))]
-    #[prop_or_default]
-    pub serie0: Option<Rc<Series>>,
-
-    #[prop_or_default]
-    #[derivative(PartialEq(compare_with = "optional_rc_ptr_eq"))]
-    #[prop_or_default]
-    pub serie1: Option<Rc<Series>>,
+    #[derivative(PartialEq(compare_with = "series_list_ptr_eq"))]
+    pub series: Vec<Rc<Series>>,
 
     /// Always include zero in displayed data range.
     #[prop_or(true)]
@@ -51,25 +49,79 @@ pub struct RRDGraph {
     #[builder]
     pub binary: bool,
 
+    /// How the series get rendered (line, stacked or rate).
+    #[prop_or_default]
+    #[builder]
+    pub mode: GraphMode,
+
+    /// Draw a warning line at this value, with a colored band up to the critical threshold.
+    #[prop_or_default]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub warning_threshold: Option<f64>,
+
+    /// Draw a critical line at this value, with a colored band above.
+    #[prop_or_default]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub critical_threshold: Option<f64>,
+
+    /// Show a menu to export the displayed range as image (SVG, PNG) or CSV.
+    #[prop_or_default]
+    #[builder]
+    pub export: bool,
+
     #[prop_or_default]
     pub render_value: Option<RenderFn<f64, String>>,
 }
 
+fn series_list_ptr_eq(a: &[Rc<Series>], b: &[Rc<Series>]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| Rc::ptr_eq(a, b))
+}
+
 impl RRDGraph {
     pub fn new(time_data: Rc<Vec<i64>>) -> Self {
         yew::props!(RRDGraph { time_data })
     }
 
+    /// Builder style method to set the data series.
+    pub fn series(mut self, series: Vec<Rc<Series>>) -> Self {
+        self.series = series;
+        self
+    }
+
+    #[deprecated(note = "use series() or with_series() instead")]
     pub fn serie0(mut self, serie: Option<Rc<Series>>) -> Self {
-        self.serie0 = serie;
+        self.set_series_at(0, serie);
         self
     }
 
+    #[deprecated(note = "use series() or with_series() instead")]
     pub fn serie1(mut self, serie: Option<Rc<Series>>) -> Self {
-        self.serie1 = serie;
+        self.set_series_at(1, serie);
+        self
+    }
+
+    // replace the series at `idx`, append if there are fewer series
+    //
+    // `None` is ignored, removing would shift the position (and color) of later series
+    fn set_series_at(&mut self, idx: usize, serie: Option<Rc<Series>>) {
+        match serie {
+            Some(serie) if idx < self.series.len() => self.series[idx] = serie,
+            Some(serie) => self.series.push(serie),
+            None => {}
+        }
+    }
+
+    /// Builder style method to add a data series.
+    pub fn with_series(mut self, series: impl Into<Rc<Series>>) -> Self {
+        self.add_series(series);
         self
     }
 
+    /// Method to add a data series.
+    pub fn add_series(&mut self, series: impl Into<Rc<Series>>) {
+        self.series.push(series.into());
+    }
+
     pub fn title(mut self, title: impl IntoPropValue<Option<AttrValue>>) -> Self {
         self.set_title(title);
         self
@@ -95,6 +147,18 @@ impl RRDGraph {
         self.class.push(class);
     }
 
+    /// Builder style method to set the warning threshold in percent of `total`.
+    pub fn warning_threshold_percent(mut self, percent: f64, total: f64) -> Self {
+        self.warning_threshold = Some(total * percent / 100.0);
+        self
+    }
+
+    /// Builder style method to set the critical threshold in percent of `total`.
+    pub fn critical_threshold_percent(mut self, percent: f64, total: f64) -> Self {
+        self.critical_threshold = Some(total * percent / 100.0);
+        self
+    }
+
     pub fn render_value(mut self, renderer: impl IntoOptionalRenderFn<f64, String>) -> Self {
         self.render_value = renderer.into_optional_render_fn();
         self
@@ -111,7 +175,11 @@ pub enum Msg {
     PointerEnter,
     PointerLeave,
     ClearViewRange,
-    ToggleSeries(u32), // index
+    ToggleSeries(usize), // index
+    ExportSvg,
+    ExportPng,
+    ExportCsv,
+    ViewRangeContextChanged(GraphViewRange),
 }
 
 pub struct PwtRRDGraph {
@@ -126,18 +194,25 @@ pub struct PwtRRDGraph {
     tooltip_align_ref: NodeRef,
     tooltip_ref: NodeRef,
     y_label_ref: NodeRef,
-    serie0_visible: bool,
-    serie1_visible: bool,
+    series_visible: Vec<bool>,
+    series_values: Vec<Vec<f64>>, // values shown in the tooltip (empty for hidden series)
+    draw_data: Vec<Vec<f64>>,     // values used for drawing (stacked upper bound)
+    stack_base: Vec<Vec<f64>>,    // lower bound of stacked series
     grid: RrdGrid,
     series_paths: Vec<Option<(String, String)>>, //outline path, fill path
+    async_pool: AsyncPool,
+    view_range_context: Option<GraphViewRange>,
+    _view_range_context_handle: Option<ContextHandle<GraphViewRange>>,
 }
 
 use pwt::widget::canvas::{Canvas, Circle, Group, Path, Rect};
 
+use super::export::{series_to_csv, svg_to_png, svg_to_string};
+use super::graph_mode::{derive_rate, stack_series};
 use super::graph_space::{CoordinateRange, GraphSpace};
 use super::grid::RrdGrid;
-use super::series::{compute_fill_path, compute_outline_path};
-use super::Series;
+use super::series::{compute_fill_path, compute_outline_path, compute_stacked_fill_path};
+use super::{GraphMode, GraphViewRange, Series};
 
 fn format_date_time(t: i64) -> String {
     let (time, date) = format_time(t);
@@ -183,65 +258,247 @@ fn render_value(props: &RRDGraph, v: f64) -> String {
     }
 }
 
+// returns the part of `data` inside the view range
+fn view_slice<T>(data: &[T], view_range: Option<(usize, usize)>) -> &[T] {
+    match view_range {
+        Some((start, end)) => {
+            let data_start = start.min(data.len().saturating_sub(1));
+            let data_end = end.min(data.len());
+            &data[data_start..data_end]
+        }
+        None => data,
+    }
+}
+
+// file name for exports, derived from the title
+fn export_filename(props: &RRDGraph, extension: &str) -> String {
+    let name = match &props.title {
+        Some(title) => title
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect(),
+        None => String::from("graph"),
+    };
+    format!("{name}.{extension}")
+}
+
 impl PwtRRDGraph {
-    fn update_grid_content(&mut self, ctx: &Context<Self>) {
-        let props = ctx.props();
-        let (time_data, data1, data2) = self.get_view_data(ctx);
-        self.graph_space
-            .update(time_data, &[data1, data2], props.include_zero, props.binary);
-        self.grid = RrdGrid::new(&self.graph_space);
+    // set the view range and report it to linked graphs
+    fn set_view_range(&mut self, ctx: &Context<Self>, view_range: Option<(usize, usize)>) {
+        self.view_range = view_range;
+        if let Some(context) = &self.view_range_context {
+            let time_data = &ctx.props().time_data;
+            let range =
+                view_range.and_then(|range| GraphViewRange::index_to_time_range(time_data, range));
+            if range != context.range() {
+                context.set_range(range);
+            }
+        }
+    }
 
-        let mut paths = Vec::new();
-        if self.serie0_visible {
-            let outline_path = compute_outline_path(time_data, data1, &self.graph_space);
-            let fill_path = compute_fill_path(time_data, data1, &self.graph_space);
-            paths.push(Some((outline_path, fill_path)));
-        } else {
-            paths.push(None);
+    // use the view range of the linked graphs
+    fn apply_view_range_context(&mut self, ctx: &Context<Self>) {
+        if let Some(context) = &self.view_range_context {
+            let time_data = &ctx.props().time_data;
+            self.view_range = context.range().and_then(|range| {
+                let (start, end) = GraphViewRange::time_to_index_range(time_data, range);
+                (end > start + 1).then_some((start, end))
+            });
         }
-        if self.serie1_visible {
-            let outline_path = compute_outline_path(time_data, data2, &self.graph_space);
-            let fill_path = compute_fill_path(time_data, data2, &self.graph_space);
-            paths.push(Some((outline_path, fill_path)));
-        } else {
-            paths.push(None);
+    }
+
+    fn threshold_elements(&self, props: &RRDGraph) -> Vec<Html> {
+        let (min_x, max_x) = self.graph_space.get_x_range(CoordinateRange::InsideBorder);
+        let graph_data = &self.graph_space.graph_data;
+
+        let mut children = Vec::new();
+        let mut band_top = graph_data.data_max;
+        for (name, threshold, color) in [
+            ("critical", props.critical_threshold, "error"),
+            ("warning", props.warning_threshold, "warning"),
+        ] {
+            let value = match threshold {
+                Some(value) if value < graph_data.data_max => value.max(graph_data.data_min),
+                _ => continue,
+            };
+            let y = self.graph_space.compute_y(value);
+            let top = self.graph_space.compute_y(band_top.max(value));
+            band_top = value;
+
+            children.push(
+                Rect::new()
+                    .key(format!("{name}-threshold-band"))
+                    .class(format!("pwt-fill-{color}"))
+                    .attribute("fill-opacity", "0.1")
+                    .position(min_x as f32, top as f32)
+                    .width((max_x - min_x) as f32)
+                    .height((y - top) as f32)
+                    .into(),
+            );
+            children.push(
+                Path::new()
+                    .key(format!("{name}-threshold-line"))
+                    .class(format!("pwt-stroke-{color}"))
+                    .attribute("stroke-dasharray", "4 4")
+                    .d(format!("M {min_x:.1} {y:.1} L {max_x:.1} {y:.1}"))
+                    .into(),
+            );
         }
+        children
+    }
 
-        self.series_paths = paths;
+    fn svg_content(&self) -> Result<String, Error> {
+        let svg = self
+            .canvas_ref
+            .cast::<web_sys::Element>()
+            .ok_or_else(|| format_err!("graph not rendered"))?;
+        svg_to_string(&svg)
     }
 
-    fn get_view_data<'a>(&self, ctx: &'a Context<Self>) -> (&'a [i64], &'a [f64], &'a [f64]) {
+    // the CSV contains the values shown in the tooltip for the displayed range
+    fn csv_content(&self, ctx: &Context<Self>) -> String {
         let props = ctx.props();
+        let time_data = view_slice(&props.time_data, self.view_range);
+        let series: Vec<(&str, &[f64])> = props
+            .series
+            .iter()
+            .zip(&self.series_values)
+            .enumerate()
+            .filter(|(idx, _)| self.is_series_visible(*idx))
+            .map(|(_, (serie, data))| (serie.label.as_str(), view_slice(data, self.view_range)))
+            .collect();
+        series_to_csv(time_data, &series)
+    }
 
-        let time_data = &props.time_data;
-        let serie0_data = match (self.serie0_visible, &props.serie0) {
-            (true, Some(serie)) => &serie.data[..],
-            _ => &[],
-        };
-        let serie1_data = match (self.serie1_visible, &props.serie1) {
-            (true, Some(serie)) => &serie.data[..],
-            _ => &[],
+    fn export(&self, ctx: &Context<Self>, msg: Msg) {
+        let props = ctx.props();
+        let result = match msg {
+            Msg::ExportCsv => crate::utils::download_text_file(
+                &export_filename(props, "csv"),
+                &self.csv_content(ctx),
+            ),
+            Msg::ExportSvg => self.svg_content().and_then(|svg| {
+                crate::utils::download_text_file(&export_filename(props, "svg"), &svg)
+            }),
+            Msg::ExportPng => self.svg_content().map(|svg| {
+                let width = self.graph_space.get_width() as f64;
+                let height = self.graph_space.get_height() as f64;
+                let background = self
+                    .node_ref
+                    .cast::<web_sys::Element>()
+                    .and_then(|el| gloo_utils::window().get_computed_style(&el).ok()?)
+                    .and_then(|style| style.get_property_value("background-color").ok());
+                let filename = export_filename(props, "png");
+                self.async_pool.spawn(async move {
+                    let result = svg_to_png(&svg, width, height, background.as_deref())
+                        .await
+                        .and_then(|png| crate::utils::download_blob(&filename, &png));
+                    if let Err(err) = result {
+                        log::error!("unable to export graph: {err}");
+                    }
+                });
+            }),
+            _ => Ok(()),
         };
+        if let Err(err) = result {
+            log::error!("unable to export graph: {err}");
+        }
+    }
 
-        if let Some((start, end)) = self.view_range {
-            let serie0_start = start.min(serie0_data.len().saturating_sub(1));
-            let serie0_end = end.min(serie0_data.len());
-            let serie1_start = start.min(serie1_data.len().saturating_sub(1));
-            let serie1_end = end.min(serie1_data.len());
-            (
-                &time_data[start..end],
-                &serie0_data[serie0_start..serie0_end],
-                &serie1_data[serie1_start..serie1_end],
-            )
+    // apply the graph mode to the series data
+    fn update_series_data(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+
+        self.series_values = props
+            .series
+            .iter()
+            .enumerate()
+            .map(|(idx, serie)| {
+                if !self.is_series_visible(idx) {
+                    return Vec::new();
+                }
+                match props.mode {
+                    GraphMode::Rate => derive_rate(&props.time_data, &serie.data),
+                    GraphMode::Line | GraphMode::Stacked => serie.data.clone(),
+                }
+            })
+            .collect();
+
+        if props.mode == GraphMode::Stacked {
+            let (lower, upper) = stack_series(&self.series_values);
+            self.stack_base = lower;
+            self.draw_data = upper;
         } else {
-            (time_data, serie0_data, serie1_data)
+            self.stack_base = Vec::new();
+            self.draw_data = self.series_values.clone();
+        }
+    }
+
+    fn update_grid_content(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let view_range = self.view_range;
+        let time_data = view_slice(&props.time_data, view_range);
+        let series_data: Vec<&[f64]> = self
+            .draw_data
+            .iter()
+            .map(|data| view_slice(data, view_range))
+            .collect();
+
+        // stacked areas always start at zero
+        let include_zero = props.include_zero || props.mode == GraphMode::Stacked;
+        self.graph_space
+            .update(time_data, &series_data, include_zero, props.binary);
+        self.grid = RrdGrid::new(&self.graph_space);
+
+        self.series_paths = series_data
+            .iter()
+            .enumerate()
+            .map(|(idx, data)| {
+                self.is_series_visible(idx).then(|| {
+                    let outline_path = compute_outline_path(time_data, data, &self.graph_space);
+                    let fill_path = match self.stack_base.get(idx) {
+                        Some(lower) => compute_stacked_fill_path(
+                            time_data,
+                            view_slice(lower, view_range),
+                            data,
+                            &self.graph_space,
+                        ),
+                        None => compute_fill_path(time_data, data, &self.graph_space),
+                    };
+                    (outline_path, fill_path)
+                })
+            })
+            .collect();
+    }
+
+    fn is_series_visible(&self, idx: usize) -> bool {
+        self.series_visible.get(idx).copied().unwrap_or(true)
+    }
+
+    // make sure we have a visibility flag for each series
+    fn sync_series_visible(&mut self, ctx: &Context<Self>) {
+        let count = ctx.props().series.len();
+        self.series_visible.resize(count, true);
+        if !self.series_visible.iter().any(|v| *v) {
+            self.series_visible.fill(true);
         }
     }
 
+    // returns the time data and the drawn data of all series (empty for hidden series)
+    fn get_view_data<'a>(&'a self, ctx: &'a Context<Self>) -> (&'a [i64], Vec<&'a [f64]>) {
+        let time_data = view_slice(&ctx.props().time_data, self.view_range);
+        let series_data = self
+            .draw_data
+            .iter()
+            .map(|data| view_slice(data, self.view_range))
+            .collect();
+        (time_data, series_data)
+    }
+
     fn create_graph(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
 
-        let (data0, data1, data2) = self.get_view_data(ctx);
+        let (data0, series_data) = self.get_view_data(ctx);
 
         let mut children: Vec<Html> = Vec::new();
 
@@ -260,25 +517,33 @@ impl PwtRRDGraph {
                 .into_html_with_ref(self.y_label_ref.clone()),
         );
 
+        // draw threshold bands and lines below the series
+        children.extend(self.threshold_elements(props));
+
         // draw series
-        for (idx, series) in self.series_paths.iter().enumerate() {
+        for (idx, (paths, serie)) in self.series_paths.iter().zip(&props.series).enumerate() {
             let idx = idx + 1;
-            let (outline_path, fill_path) = match series {
+            let (outline_path, fill_path) = match paths {
                 Some(res) => res,
                 None => continue,
             };
-            children.extend(vec![
-                Path::new()
-                    .key(format!("series{idx}-path"))
-                    .class(format!("pwt-rrd-outline-path{idx}"))
-                    .d(outline_path.to_string())
-                    .into(),
-                Path::new()
-                    .key(format!("series{idx}-fill"))
-                    .class(format!("pwt-rrd-fill-path{idx}"))
-                    .d(fill_path.to_string())
-                    .into(),
-            ]);
+            let mut outline = Path::new()
+                .key(format!("series{idx}-path"))
+                .class(format!("pwt-rrd-outline-path{idx}"))
+                .class(serie.class.clone())
+                .d(outline_path.to_string());
+            let mut fill = Path::new()
+                .key(format!("series{idx}-fill"))
+                .class(format!("pwt-rrd-fill-path{idx}"))
+                .class(serie.class.clone())
+                .d(fill_path.to_string());
+            if let Some(color) = &serie.color {
+                // use inline styles, presentation attributes lose against the CSS classes
+                outline = outline.attribute("style", format!("stroke:{color}"));
+                fill = fill.attribute("style", format!("fill:{color}"));
+            }
+            children.push(outline.into());
+            children.push(fill.into());
         }
 
         // draw selection rectangle
@@ -303,7 +568,7 @@ impl PwtRRDGraph {
 
         // draw cross and data circles
         if let Some((x, y)) = self.cross_pos {
-            let (path, circles) = self.get_cross_positions(data0, &[data1, data2], x, y);
+            let (path, circles) = self.get_cross_positions(data0, &series_data, x, y);
             for (idx, (px, py)) in circles.into_iter().enumerate() {
                 children.push(
                     Circle::new()
@@ -451,6 +716,11 @@ impl Component for PwtRRDGraph {
         let graph_space = GraphSpace::default();
         let grid = RrdGrid::new(&graph_space);
 
+        let (view_range_context, _view_range_context_handle) = ctx
+            .link()
+            .context(ctx.link().callback(Msg::ViewRangeContextChanged))
+            .unzip();
+
         let mut this = Self {
             node_ref: NodeRef::default(),
             size_observer: None,
@@ -463,12 +733,20 @@ impl Component for PwtRRDGraph {
             tooltip_align_ref: NodeRef::default(),
             tooltip_ref: NodeRef::default(),
             y_label_ref: NodeRef::default(),
-            serie0_visible: true,
-            serie1_visible: true,
+            series_visible: Vec::new(),
+            series_values: Vec::new(),
+            draw_data: Vec::new(),
+            stack_base: Vec::new(),
             grid,
             series_paths: Vec::new(),
+            async_pool: AsyncPool::new(),
+            view_range_context,
+            _view_range_context_handle,
         };
 
+        this.apply_view_range_context(ctx);
+        this.sync_series_visible(ctx);
+        this.update_series_data(ctx);
         this.update_grid_content(ctx);
         this
     }
@@ -477,6 +755,10 @@ impl Component for PwtRRDGraph {
         //let props = ctx.props();
         match msg {
             Msg::Reload => true,
+            msg @ (Msg::ExportSvg | Msg::ExportPng | Msg::ExportCsv) => {
+                self.export(ctx, msg);
+                false
+            }
             Msg::ViewportResize(width, _height) => {
                 if width > 0.0 {
                     self.graph_space.set_width(width as usize);
@@ -485,22 +767,26 @@ impl Component for PwtRRDGraph {
                 true
             }
             Msg::ToggleSeries(idx) => {
-                if idx == 0 {
-                    self.serie0_visible = !self.serie0_visible;
-                    if !(self.serie0_visible || self.serie1_visible) {
-                        self.serie1_visible = true;
-                    }
-                } else if idx == 1 {
-                    self.serie1_visible = !self.serie1_visible;
-                    if !(self.serie0_visible || self.serie1_visible) {
-                        self.serie0_visible = true;
-                    }
+                if let Some(visible) = self.series_visible.get_mut(idx) {
+                    *visible = !*visible;
+                }
+                // never hide all series, show the others instead
+                if !self.series_visible.iter().any(|v| *v) {
+                    self.series_visible.fill(true);
+                    self.series_visible[idx] = false;
                 }
+                self.update_series_data(ctx);
                 self.update_grid_content(ctx);
                 true
             }
             Msg::ClearViewRange => {
-                self.view_range = None;
+                self.set_view_range(ctx, None);
+                self.update_grid_content(ctx);
+                true
+            }
+            Msg::ViewRangeContextChanged(context) => {
+                self.view_range_context = Some(context);
+                self.apply_view_range_context(ctx);
                 self.update_grid_content(ctx);
                 true
             }
@@ -522,7 +808,7 @@ impl Component for PwtRRDGraph {
                 if let Some(el) = self.canvas_ref.cast::<web_sys::Element>() {
                     let _ = el.set_pointer_capture(pointer_id);
                 }
-                let (data0, _, _) = self.get_view_data(ctx);
+                let (data0, _) = self.get_view_data(ctx);
                 let start_index = self.offset_to_time_index(x, data0);
                 self.selection = Some((start_index, start_index));
                 true
@@ -531,7 +817,7 @@ impl Component for PwtRRDGraph {
                 self.cross_pos = Some((x, y));
                 self.selection = match self.selection {
                     Some((start, _)) => {
-                        let (data0, _, _) = self.get_view_data(ctx);
+                        let (data0, _) = self.get_view_data(ctx);
                         let end_index = self.offset_to_time_index(x, data0);
                         //log::info!("Move SELECTION {start} {end_index}");
                         Some((start, end_index))
@@ -548,7 +834,7 @@ impl Component for PwtRRDGraph {
                 }
                 self.selection = match self.selection {
                     Some((start, _)) => {
-                        let (data0, _, _) = self.get_view_data(ctx);
+                        let (data0, _) = self.get_view_data(ctx);
                         let end_index = self.offset_to_time_index(x, data0);
                         let (start, end_index) = if start > end_index {
                             (end_index, start)
@@ -558,15 +844,13 @@ impl Component for PwtRRDGraph {
 
                         if (end_index - start) > 10 {
                             //log::info!("End SELECTION {start} {end_index}");
-                            match self.view_range {
+                            let view_range = match self.view_range {
                                 Some((view_start, _view_end)) => {
-                                    self.view_range =
-                                        Some((view_start + start, view_start + end_index));
+                                    (view_start + start, view_start + end_index)
                                 }
-                                None => {
-                                    self.view_range = Some((start, end_index));
-                                }
-                            }
+                                None => (start, end_index),
+                            };
+                            self.set_view_range(ctx, Some(view_range));
                         }
                         None
                     }
@@ -583,44 +867,51 @@ impl Component for PwtRRDGraph {
         let props = ctx.props();
 
         let mut data_time = None;
-        let mut serie0_value = None;
-        let mut serie1_value = None;
+        let mut series_values = vec![None; props.series.len()];
 
         if let Some((x, _)) = self.cross_pos {
-            let (data0, data1, data2) = self.get_view_data(ctx);
+            let (data0, _) = self.get_view_data(ctx);
             let idx = self.offset_to_time_index(x, data0);
             if let Some(t) = data0.get(idx) {
                 data_time = Some(format_date_time(*t));
-                if let Some(v) = data1.get(idx) {
-                    if v.is_finite() {
-                        serie0_value = Some(render_value(props, *v));
-                    }
-                }
-                if let Some(v) = data2.get(idx) {
-                    if v.is_finite() {
-                        serie1_value = Some(render_value(props, *v));
+                let series_data = self
+                    .series_values
+                    .iter()
+                    .map(|data| view_slice(data, self.view_range));
+                for (value, data) in series_values.iter_mut().zip(series_data) {
+                    if let Some(v) = data.get(idx) {
+                        if v.is_finite() {
+                            *value = Some(render_value(props, *v));
+                        }
                     }
                 }
             }
         }
 
         let tip = Container::new()
-        .attribute("role", "tooltip")
-        .attribute("aria-live", "polite")
-        .attribute("data-show", (self.cross_pos.is_some() && data_time.is_some()).then_some(""))
-        .class("pwt-tooltip")
-        .class("pwt-tooltip-rich")
-        .with_optional_child(match (self.serie0_visible, &props.serie0) {
-            (true, Some(serie)) => Some(html!{<div>{format!("{}: {}", serie.label.clone(), serie0_value.as_deref().unwrap_or("-"))}</div>}),
-            _ => None,
-        })
-        .with_optional_child(match (self.serie1_visible, &props.serie1) {
-            (true, Some(serie)) => Some(html!{<div>{format!("{}: {}", serie.label.clone(), serie1_value.as_deref().unwrap_or("-"))}</div>}),
-            _ => None,
-        })
-        .with_child(html!{<hr/>})
-        .with_child(html!{<div>{data_time.as_deref().unwrap_or("-")}</div>})
-        .into_html_with_ref(self.tooltip_ref.clone());
+            .attribute("role", "tooltip")
+            .attribute("aria-live", "polite")
+            .attribute(
+                "data-show",
+                (self.cross_pos.is_some() && data_time.is_some()).then_some(""),
+            )
+            .class("pwt-tooltip")
+            .class("pwt-tooltip-rich")
+            .children(
+                props
+                    .series
+                    .iter()
+                    .zip(&series_values)
+                    .enumerate()
+                    .filter_map(|(idx, (serie, value))| {
+                        self.is_series_visible(idx).then(|| html!{
+                <div>{format!("{}: {}", serie.label.clone(), value.as_deref().unwrap_or("-"))}</div>
+            })
+                    }),
+            )
+            .with_child(html! {<hr/>})
+            .with_child(html! {<div>{data_time.as_deref().unwrap_or("-")}</div>})
+            .into_html_with_ref(self.tooltip_ref.clone());
 
         let mut panel = Panel::new()
             .title(props.title.clone())
@@ -636,35 +927,52 @@ impl Component for PwtRRDGraph {
                     .into_html_with_ref(self.node_ref.clone()),
             );
 
-        if let Some(serie0) = &props.serie0 {
-            if let Some(serie1) = &props.serie1 {
-                let icon_class0 = classes!(
-                    "pwt-rrd-legend-marker0",
+        if props.series.len() > 1 {
+            for (idx, serie) in props.series.iter().enumerate() {
+                let icon_class = classes!(
+                    format!("pwt-rrd-legend-marker{idx}"),
+                    serie.class.clone(),
                     "fa",
                     "fa-circle",
-                    (!self.serie0_visible).then_some("disabled")
-                );
-                panel.add_tool(
-                    Button::new(serie0.label.clone())
-                        .class("pwt-button-elevated")
-                        .icon_class(icon_class0)
-                        .onclick(ctx.link().callback(|_| Msg::ToggleSeries(0))),
-                );
-                let icon_class1 = classes!(
-                    "pwt-rrd-legend-marker1",
-                    "fa",
-                    "fa-circle",
-                    (!self.serie1_visible).then_some("disabled")
-                );
-                panel.add_tool(
-                    Button::new(serie1.label.clone())
-                        .class("pwt-button-elevated")
-                        .icon_class(icon_class1)
-                        .onclick(ctx.link().callback(|_| Msg::ToggleSeries(1))),
+                    (!self.is_series_visible(idx)).then_some("disabled")
                 );
+                let mut button = Button::new(serie.label.clone())
+                    .class("pwt-button-elevated")
+                    .icon_class(icon_class)
+                    .onclick(ctx.link().callback(move |_| Msg::ToggleSeries(idx)));
+                if let Some(color) = &serie.color {
+                    // the marker icon inherits the text color
+                    button = button.style("color", color.clone());
+                }
+                panel.add_tool(button);
             }
         }
 
+        if props.export {
+            let menu = Menu::new()
+                .with_item(
+                    MenuItem::new(tr!("Export as PNG"))
+                        .icon_class("fa fa-fw fa-file-image-o")
+                        .on_select(ctx.link().callback(|_| Msg::ExportPng)),
+                )
+                .with_item(
+                    MenuItem::new(tr!("Export as SVG"))
+                        .icon_class("fa fa-fw fa-file-code-o")
+                        .on_select(ctx.link().callback(|_| Msg::ExportSvg)),
+                )
+                .with_item(
+                    MenuItem::new(tr!("Export as CSV"))
+                        .icon_class("fa fa-fw fa-file-text-o")
+                        .on_select(ctx.link().callback(|_| Msg::ExportCsv)),
+                );
+            panel.add_tool(
+                MenuButton::new(tr!("Export"))
+                    .class("pwt-button-elevated")
+                    .icon_class("fa fa-download")
+                    .menu(menu),
+            );
+        }
+
         panel.into()
     }
 
@@ -672,7 +980,9 @@ impl Component for PwtRRDGraph {
         let props = ctx.props();
 
         // clamp view range to the new time data range
-        if let Some((start, end)) = self.view_range {
+        if self.view_range_context.is_some() {
+            self.apply_view_range_context(ctx);
+        } else if let Some((start, end)) = self.view_range {
             if props.time_data.len() < 10 {
                 self.view_range = None;
             } else {
@@ -683,10 +993,12 @@ impl Component for PwtRRDGraph {
         }
 
         // we need to recalculate the grid content when the series or time data changes
-        if props.serie0 != old_props.serie0
-            || props.serie1 != old_props.serie1
+        if !series_list_ptr_eq(&props.series, &old_props.series)
             || props.time_data != old_props.time_data
+            || props.mode != old_props.mode
         {
+            self.sync_series_visible(ctx);
+            self.update_series_data(ctx);
             self.update_grid_content(ctx);
         }
 
@@ -0,0 +1,96 @@
+/// How the series of an [`crate::RRDGraph`] are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphMode {
+    /// Draw each series as line with a filled area down to zero.
+    #[default]
+    Line,
+    /// Stack the series on top of each other (e.g. used, cached and free memory).
+    ///
+    /// The value axis always includes zero.
+    Stacked,
+    /// Treat the series as counters and draw the rate of change (per second).
+    ///
+    /// Counter resets (decreasing values) show up as gaps.
+    Rate,
+}
+
+/// Derive the per second rate from counter values.
+///
+/// The first value and values following a counter reset are NaN.
+pub(crate) fn derive_rate(time_data: &[i64], values: &[f64]) -> Vec<f64> {
+    let mut result = Vec::with_capacity(values.len());
+    for (i, value) in values.iter().enumerate() {
+        if i == 0 {
+            result.push(f64::NAN);
+            continue;
+        }
+        let rate = match (time_data.get(i - 1), time_data.get(i)) {
+            (Some(prev_t), Some(t)) if t > prev_t => {
+                let diff = value - values[i - 1];
+                if diff >= 0.0 {
+                    diff / (t - prev_t) as f64
+                } else {
+                    f64::NAN
+                }
+            }
+            _ => f64::NAN,
+        };
+        result.push(rate);
+    }
+    result
+}
+
+/// Stack the series values on top of each other.
+///
+/// Returns the lower and upper bound for each series. Missing values (NaN) stay missing in
+/// the upper bound, but count as zero for the following series. Empty (hidden) series are
+/// not stacked.
+pub(crate) fn stack_series(series: &[Vec<f64>]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let len = series.iter().map(|data| data.len()).max().unwrap_or(0);
+    let mut base = vec![0.0; len];
+
+    let mut lower_list = Vec::with_capacity(series.len());
+    let mut upper_list = Vec::with_capacity(series.len());
+
+    for data in series {
+        let lower = base[..data.len()].to_vec();
+        let mut upper = Vec::with_capacity(data.len());
+        for (i, value) in data.iter().enumerate() {
+            if value.is_finite() {
+                base[i] += value;
+                upper.push(base[i]);
+            } else {
+                upper.push(f64::NAN);
+            }
+        }
+        lower_list.push(lower);
+        upper_list.push(upper);
+    }
+
+    (lower_list, upper_list)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_derive_rate() {
+        let rate = derive_rate(&[0, 10, 20, 30], &[100.0, 200.0, 50.0, 150.0]);
+        assert!(rate[0].is_nan());
+        assert_eq!(rate[1], 10.0);
+        assert!(rate[2].is_nan()); // counter reset
+        assert_eq!(rate[3], 10.0);
+    }
+
+    #[test]
+    fn test_stack_series() {
+        let series = vec![vec![1.0, 2.0, f64::NAN], vec![], vec![3.0, 4.0, 5.0]];
+        let (lower, upper) = stack_series(&series);
+        assert_eq!(lower[0], [0.0, 0.0, 0.0]);
+        assert!(upper[0][2].is_nan());
+        assert!(lower[1].is_empty() && upper[1].is_empty());
+        assert_eq!(lower[2], [1.0, 2.0, 0.0]);
+        assert_eq!(upper[2], [4.0, 6.0, 5.0]);
+    }
+}
@@ -0,0 +1,91 @@
+use yew::Callback;
+
+/// Time range (epoch, end exclusive) displayed by linked [crate::RRDGraph]s.
+///
+/// Provided as context by [crate::RRDGrid] when `link_view_range` is set. Graphs using
+/// this context report range selections (zoom) here and display the shared range.
+#[derive(Clone, PartialEq)]
+pub struct GraphViewRange {
+    range: Option<(i64, i64)>,
+    on_change: Callback<Option<(i64, i64)>>,
+}
+
+impl GraphViewRange {
+    pub fn new(
+        range: Option<(i64, i64)>,
+        on_change: impl Into<Callback<Option<(i64, i64)>>>,
+    ) -> Self {
+        Self {
+            range,
+            on_change: on_change.into(),
+        }
+    }
+
+    /// The selected time range, `None` if the whole time frame is displayed.
+    pub fn range(&self) -> Option<(i64, i64)> {
+        self.range
+    }
+
+    /// Change the time range of all linked graphs.
+    pub fn set_range(&self, range: Option<(i64, i64)>) {
+        self.on_change.emit(range);
+    }
+
+    /// Display the whole time frame in all linked graphs.
+    pub fn reset(&self) {
+        self.set_range(None);
+    }
+
+    /// Convert a range of indices into `time_data` to a time range.
+    pub(crate) fn index_to_time_range(
+        time_data: &[i64],
+        (start, end): (usize, usize),
+    ) -> Option<(i64, i64)> {
+        let start_time = *time_data.get(start)?;
+        let end_time = match time_data.get(end) {
+            Some(t) => *t,
+            None => time_data.last()? + 1,
+        };
+        Some((start_time, end_time))
+    }
+
+    /// Convert a time range to a range of indices into `time_data`.
+    pub(crate) fn time_to_index_range(
+        time_data: &[i64],
+        (start_time, end_time): (i64, i64),
+    ) -> (usize, usize) {
+        let start = time_data.partition_point(|t| *t < start_time);
+        let end = time_data.partition_point(|t| *t < end_time);
+        (start, end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_range_conversion() {
+        let time_data = [60, 120, 180, 240, 300];
+
+        let range = GraphViewRange::index_to_time_range(&time_data, (1, 3));
+        assert_eq!(range, Some((120, 240)));
+        assert_eq!(
+            GraphViewRange::time_to_index_range(&time_data, (120, 240)),
+            (1, 3)
+        );
+
+        let range = GraphViewRange::index_to_time_range(&time_data, (2, 5));
+        assert_eq!(range, Some((180, 301)));
+        assert_eq!(
+            GraphViewRange::time_to_index_range(&time_data, (180, 301)),
+            (2, 5)
+        );
+
+        // other graphs may have slightly different time data
+        assert_eq!(
+            GraphViewRange::time_to_index_range(&[50, 110, 170, 230], (120, 240)),
+            (2, 4)
+        );
+    }
+}
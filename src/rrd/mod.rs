@@ -1,6 +1,14 @@
 mod graph;
 pub use graph::*;
 
+pub(crate) mod export;
+
+pub(crate) mod graph_mode;
+pub use graph_mode::GraphMode;
+
+pub(crate) mod graph_view_range;
+pub use graph_view_range::GraphViewRange;
+
 pub(crate) mod graph_space;
 
 pub(crate) mod grid;
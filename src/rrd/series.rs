@@ -1,4 +1,5 @@
-use yew::AttrValue;
+use yew::html::IntoPropValue;
+use yew::{AttrValue, Classes};
 
 use super::graph_space::GraphSpace;
 
@@ -7,6 +8,10 @@ use super::graph_space::GraphSpace;
 pub struct Series {
     pub label: AttrValue,
     pub data: Vec<f64>,
+    /// Color used to draw the series, overrides the color from the default classes.
+    pub color: Option<AttrValue>,
+    /// Additional classes for the outline, fill and legend marker elements.
+    pub class: Classes,
 }
 
 impl Series {
@@ -14,8 +19,22 @@ impl Series {
         Self {
             label: label.into(),
             data,
+            color: None,
+            class: Classes::new(),
         }
     }
+
+    /// Builder style method to set the series color (any CSS color).
+    pub fn color(mut self, color: impl IntoPropValue<Option<AttrValue>>) -> Self {
+        self.color = color.into_prop_value();
+        self
+    }
+
+    /// Builder style method to add a html class
+    pub fn class(mut self, class: impl Into<Classes>) -> Self {
+        self.class.push(class);
+        self
+    }
 }
 
 /// Calculate the outline path of a series of [`f64`] data for [`i64`] points in time.
@@ -94,3 +113,44 @@ pub fn compute_fill_path(time_data: &[i64], values: &[f64], graph_space: &GraphS
 
     path
 }
+
+/// Calculate the fill path between the `lower` and `upper` values of a stacked series.
+///
+/// The area will not be filled for points where the upper value is missing
+pub fn compute_stacked_fill_path(
+    time_data: &[i64],
+    lower: &[f64],
+    upper: &[f64],
+    graph_space: &GraphSpace,
+) -> String {
+    // go back along the lower bound to close the area
+    fn close_area(path: &mut String, lower_points: &mut Vec<(f64, f64)>) {
+        if lower_points.is_empty() {
+            return;
+        }
+        for (x, y) in lower_points.drain(..).rev() {
+            path.push_str(&format!(" L {:.1} {:.1}", x, y));
+        }
+        path.push_str(" Z");
+    }
+
+    let mut path = String::new();
+    let mut lower_points = Vec::new();
+    for (i, t) in time_data.iter().enumerate() {
+        let value = *upper.get(i).unwrap_or(&f64::NAN);
+        if value.is_nan() {
+            close_area(&mut path, &mut lower_points);
+            continue;
+        }
+        let x = graph_space.compute_x(*t);
+        let y = graph_space.compute_y(value);
+        let command = if lower_points.is_empty() { "M" } else { "L" };
+        path.push_str(&format!(" {command} {:.1} {:.1}", x, y));
+
+        let lower_value = *lower.get(i).unwrap_or(&0.0);
+        lower_points.push((x, graph_space.compute_y(lower_value)));
+    }
+    close_area(&mut path, &mut lower_points);
+
+    path
+}
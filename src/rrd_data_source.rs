@@ -0,0 +1,142 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use yew::AttrValue;
+
+use crate::{RRDTimeframe, Series};
+
+/// Loads data from a `rrddata` API endpoint and converts it for [crate::RRDGraph].
+///
+/// The API returns a list of records with a `time` property and one property per data
+/// source. Records without a value for a data source show up as gaps (NaN) in the graph.
+///
+/// ```ignore
+/// let source = RrdDataSource::new(format!("/nodes/{node}/rrddata"))
+///     .field("netin", tr!("Incoming"))
+///     .field("netout", tr!("Outgoing"));
+///
+/// let data = source.load(RRDTimeframe::load()).await?;
+/// let graph = RRDGraph::new(data.time_data.clone()).series(data.series_list());
+/// ```
+///
+/// Use [RRDTimeframe::observe] to reload the data when the user selects another timeframe.
+#[derive(Clone, PartialEq)]
+pub struct RrdDataSource {
+    url: AttrValue,
+    fields: Vec<(AttrValue, AttrValue)>, // name, label
+}
+
+impl RrdDataSource {
+    /// Create a new instance for the API endpoint at `url`.
+    pub fn new(url: impl Into<AttrValue>) -> Self {
+        Self {
+            url: url.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Builder style method to add a data source field, displayed with `label`.
+    pub fn field(mut self, name: impl Into<AttrValue>, label: impl Into<AttrValue>) -> Self {
+        self.add_field(name, label);
+        self
+    }
+
+    /// Method to add a data source field, displayed with `label`.
+    pub fn add_field(&mut self, name: impl Into<AttrValue>, label: impl Into<AttrValue>) {
+        self.fields.push((name.into(), label.into()));
+    }
+
+    /// Fetch the data for the given timeframe and consolidation function.
+    pub async fn load(&self, timeframe: RRDTimeframe) -> Result<RrdData, Error> {
+        let records: Vec<Value> =
+            crate::http_get(self.url.to_string(), Some(timeframe.api_params())).await?;
+        Ok(self.convert(&records))
+    }
+
+    /// Convert the records returned by the API.
+    pub fn convert(&self, records: &[Value]) -> RrdData {
+        let mut time_data = Vec::with_capacity(records.len());
+        let mut values: Vec<Vec<f64>> = vec![Vec::with_capacity(records.len()); self.fields.len()];
+
+        for record in records {
+            let time = match record["time"].as_i64() {
+                Some(time) => time,
+                None => continue,
+            };
+            time_data.push(time);
+            for ((name, _), data) in self.fields.iter().zip(values.iter_mut()) {
+                data.push(record[name.as_str()].as_f64().unwrap_or(f64::NAN));
+            }
+        }
+
+        let series = self
+            .fields
+            .iter()
+            .zip(values)
+            .map(|((name, label), data)| (name.clone(), Rc::new(Series::new(label.clone(), data))))
+            .collect();
+
+        RrdData {
+            time_data: Rc::new(time_data),
+            series,
+        }
+    }
+}
+
+/// Data loaded by a [RrdDataSource].
+#[derive(Clone, Default, PartialEq)]
+pub struct RrdData {
+    /// The time of each record (epoch).
+    pub time_data: Rc<Vec<i64>>,
+    /// The series for each field (field name, series).
+    pub series: Vec<(AttrValue, Rc<Series>)>,
+}
+
+impl RrdData {
+    /// The series of a single field.
+    pub fn get(&self, name: &str) -> Option<Rc<Series>> {
+        self.series
+            .iter()
+            .find(|(field, _)| field.as_str() == name)
+            .map(|(_, series)| Rc::clone(series))
+    }
+
+    /// The series of all fields, in the order they were added to the [RrdDataSource].
+    pub fn series_list(&self) -> Vec<Rc<Series>> {
+        self.series
+            .iter()
+            .map(|(_, series)| Rc::clone(series))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let source = RrdDataSource::new("/nodes/localhost/rrddata")
+            .field("netin", "In")
+            .field("netout", "Out");
+
+        let records = [
+            json!({ "time": 60, "netin": 1.0, "netout": 2.0 }),
+            json!({ "time": 120, "netin": 3.0 }),
+            json!({ "netin": 5.0 }), // no time
+        ];
+
+        let data = source.convert(&records);
+        assert_eq!(*data.time_data, [60, 120]);
+
+        let netout = data.get("netout").unwrap();
+        assert_eq!(netout.label, "Out");
+        assert_eq!(netout.data[0], 2.0);
+        assert!(netout.data[1].is_nan());
+        assert_eq!(data.series_list().len(), 2);
+    }
+}
@@ -1,13 +1,24 @@
 use pwt::css::{Display, FlexDirection};
 use pwt::dom::DomSizeObserver;
 use pwt::prelude::*;
-use pwt::widget::Container;
+use pwt::widget::{Button, Container, Toolbar};
 
-use pwt_macros::widget;
+use pwt_macros::{builder, widget};
+
+use crate::GraphViewRange;
 
 #[widget(comp=ProxmoxRRDGrid, @container)]
 #[derive(Default, PartialEq, Clone, Properties)]
-pub struct RRDGrid {}
+#[builder]
+pub struct RRDGrid {
+    /// Link the displayed time range of all contained graphs.
+    ///
+    /// Selecting a time range on one graph zooms all others. A toolbar with a button to
+    /// reset the zoom is shown above the graphs.
+    #[prop_or_default]
+    #[builder]
+    pub link_view_range: bool,
+}
 
 impl RRDGrid {
     /// Create a new instance.
@@ -17,6 +28,7 @@ impl RRDGrid {
 }
 pub enum Msg {
     ViewportResize(f64, f64),
+    SetViewRange(Option<(i64, i64)>),
 }
 
 #[doc(hidden)]
@@ -25,6 +37,7 @@ pub struct ProxmoxRRDGrid {
     cols: usize,
     col_width: usize,
     node_ref: NodeRef,
+    view_range: Option<(i64, i64)>,
 }
 
 impl Component for ProxmoxRRDGrid {
@@ -37,6 +50,7 @@ impl Component for ProxmoxRRDGrid {
             cols: 1,
             col_width: 800,
             node_ref: NodeRef::default(),
+            view_range: None,
         }
     }
 
@@ -56,27 +70,53 @@ impl Component for ProxmoxRRDGrid {
                 self.col_width = col_width - padding;
                 true
             }
+            Msg::SetViewRange(view_range) => {
+                if self.view_range == view_range {
+                    return false;
+                }
+                self.view_range = view_range;
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
 
+        let grid: Html = Container::new()
+            .class(Display::Grid)
+            .class("pwt-gap-4 pwt-w-100")
+            .padding(4)
+            .attribute(
+                "style",
+                format!("grid-template-columns:repeat({}, 1fr);", self.cols),
+            )
+            .children(props.children.clone())
+            .into();
+
+        let (toolbar, grid) = if props.link_view_range {
+            let context =
+                GraphViewRange::new(self.view_range, ctx.link().callback(Msg::SetViewRange));
+            let toolbar = Toolbar::new().class("pwt-border-bottom").with_child(
+                Button::new(tr!("Reset Zoom"))
+                    .icon_class("fa fa-search-minus")
+                    .disabled(self.view_range.is_none())
+                    .onclick(ctx.link().callback(|_| Msg::SetViewRange(None))),
+            );
+            let grid = html! {
+                <ContextProvider<GraphViewRange> {context}>{grid}</ContextProvider<GraphViewRange>>
+            };
+            (Some(toolbar), grid)
+        } else {
+            (None, grid)
+        };
+
         Container::from_widget_props(props.std_props.clone(), None)
             .class(Display::Flex)
             .class(FlexDirection::Column)
             .class("pwt-overflow-auto")
-            .with_child(
-                Container::new()
-                    .class(Display::Grid)
-                    .class("pwt-gap-4 pwt-w-100")
-                    .padding(4)
-                    .attribute(
-                        "style",
-                        format!("grid-template-columns:repeat({}, 1fr);", self.cols),
-                    )
-                    .children(props.children.clone()),
-            )
+            .with_optional_child(toolbar)
+            .with_child(grid)
             .with_child(html! {<div class="pwt-flex-fill"/>})
             .into_html_with_ref(self.node_ref.clone())
     }
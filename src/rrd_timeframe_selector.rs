@@ -1,4 +1,5 @@
 use anyhow::{format_err, Error};
+use gloo_events::EventListener;
 use serde_json::{json, Value};
 use std::rc::Rc;
 
@@ -134,6 +135,19 @@ impl RRDTimeframe {
         }
     }
 
+    /// Listen for timeframe changes, for example from a [RRDTimeframeSelector].
+    ///
+    /// The callback gets the newly stored timeframe. Changes stop being reported when the
+    /// returned listener gets dropped.
+    pub fn observe(callback: impl Into<Callback<RRDTimeframe>>) -> EventListener {
+        let callback = callback.into();
+        EventListener::new(
+            &gloo_utils::document(),
+            "proxmox-rrd-timeframe-changed",
+            move |_| callback.emit(RRDTimeframe::load()),
+        )
+    }
+
     pub fn api_params(&self) -> Value {
         json!({ "cf": self.mode, "timeframe": self.timeframe })
     }
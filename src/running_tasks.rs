@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::rc::Rc;
 
 use yew::html::{IntoEventCallback, IntoPropValue};
@@ -7,9 +8,12 @@ use pwt::prelude::*;
 use pwt::props::{IntoOptionalRenderFn, RenderFn};
 use pwt::state::{Loader, LoaderState, SharedStateObserver, Store};
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
-use pwt::widget::{ActionIcon, Button, Container, Panel, Toolbar, Tooltip};
+use pwt::widget::menu::MenuButton;
+use pwt::widget::{ActionIcon, Button, Column, Container, Panel, Row, Toolbar, Tooltip};
 
+use crate::tasks::task_action_menu;
 use crate::utils::{format_duration_human, format_upid, render_epoch_short};
+use crate::{task_guest_id, MiniTaskLog};
 use pbs_api_types::TaskListItem;
 
 use pwt_macros::builder;
@@ -40,6 +44,21 @@ pub struct RunningTasks {
     #[prop_or_default]
     /// Custom Buttons instead of the default 'Show all' one.
     pub buttons: Option<Vec<Button>>,
+
+    #[prop_or("/nodes/localhost/tasks".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    /// The base url of the task API, used to load the inline task log.
+    pub base_url: AttrValue,
+
+    /// Only show the tasks of this guest.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub vmid: Option<u32>,
+
+    /// Called with the UPID whenever a task gets opened.
+    #[builder_cb(IntoEventCallback, into_event_callback, String)]
+    #[prop_or_default]
+    pub on_open_task: Option<Callback<String>>,
 }
 
 impl RunningTasks {
@@ -50,11 +69,16 @@ impl RunningTasks {
 
 pub enum Msg {
     DataChange,
+    ToggleLog(String),
+    OpenTask(String),
+    FilterGuest(Option<u32>),
 }
 
 #[doc(hidden)]
 pub struct ProxmoxRunningTasks {
     store: Store<TaskListItem>,
+    expanded: HashSet<String>,
+    guest_filter: Option<u32>,
     _listener: SharedStateObserver<LoaderState<Vec<TaskListItem>>>,
 }
 
@@ -63,6 +87,9 @@ impl ProxmoxRunningTasks {
         let props = ctx.props();
         if let Some(Ok(data)) = &props.loader.read().data {
             let mut list = data.to_vec();
+            if let Some(vmid) = self.guest_filter {
+                list.retain(|item| task_guest_id(item) == Some(vmid));
+            }
             list.sort_by(|a, b| a.starttime.cmp(&b.starttime));
             list.truncate(10);
 
@@ -80,15 +107,46 @@ impl ProxmoxRunningTasks {
         let props = ctx.props();
 
         Rc::new(vec![
+            DataTableColumn::new("")
+                .width("30px")
+                .render({
+                    let link = ctx.link().clone();
+                    let expanded = self.expanded.clone();
+                    move |item: &TaskListItem| {
+                        let (icon_class, tip) = if expanded.contains(&item.upid) {
+                            ("fa fa-chevron-down", tr!("Hide Log"))
+                        } else {
+                            ("fa fa-chevron-right", tr!("Show Log"))
+                        };
+                        let upid = item.upid.clone();
+                        let icon = ActionIcon::new(icon_class)
+                            .on_activate(link.callback(move |_| Msg::ToggleLog(upid.clone())));
+                        Tooltip::new(icon).tip(tip).into()
+                    }
+                })
+                .into(),
             DataTableColumn::new(tr!("Task"))
                 .flex(1)
                 .render({
                     let render = props.render.clone();
+                    let expanded = self.expanded.clone();
+                    let base_url = props.base_url.clone();
                     move |item: &TaskListItem| {
-                        if let Some(render) = &render {
+                        let task = if let Some(render) = &render {
                             render.apply(item)
                         } else {
                             html! {format_upid(&item.upid)}
+                        };
+                        if expanded.contains(&item.upid) {
+                            Column::new()
+                                .gap(1)
+                                .with_child(task)
+                                .with_child(
+                                    MiniTaskLog::new(item.upid.clone()).base_url(base_url.clone()),
+                                )
+                                .into()
+                        } else {
+                            task
                         }
                     }
                 })
@@ -107,19 +165,26 @@ impl ProxmoxRunningTasks {
                 })
                 .into(),
             DataTableColumn::new(tr!("Action"))
-                .width("40px")
+                .width("70px")
                 .render({
-                    let on_show_task = props.on_show_task.clone();
+                    let link = ctx.link().clone();
                     move |item: &TaskListItem| {
                         let upid = item.upid.clone();
-                        let endtime = item.endtime;
-                        let on_show_task = on_show_task.clone();
-                        let icon = ActionIcon::new("fa fa-chevron-right").on_activate(move |_| {
-                            if let Some(on_show_task) = &on_show_task {
-                                on_show_task.emit((upid.clone(), endtime));
-                            }
-                        });
-                        Tooltip::new(icon).tip(tr!("Open Task")).into()
+                        let icon = ActionIcon::new("fa fa-chevron-right")
+                            .on_activate(link.callback(move |_| Msg::OpenTask(upid.clone())));
+                        let menu = task_action_menu(
+                            item,
+                            link.callback(Msg::OpenTask),
+                            Some(link.callback(|vmid| Msg::FilterGuest(Some(vmid)))),
+                        );
+                        Row::new()
+                            .with_child(Tooltip::new(icon).tip(tr!("Open Task")))
+                            .with_child(
+                                MenuButton::new("")
+                                    .icon_class("fa fa-ellipsis-v")
+                                    .menu(menu),
+                            )
+                            .into()
                     }
                 })
                 .into(),
@@ -139,7 +204,12 @@ impl Component for ProxmoxRunningTasks {
             .loader
             .add_listener(ctx.link().callback(|_| Msg::DataChange));
 
-        let mut me = Self { store, _listener };
+        let mut me = Self {
+            store,
+            expanded: HashSet::new(),
+            guest_filter: props.vmid,
+            _listener,
+        };
 
         me.update_data(ctx);
         me
@@ -151,9 +221,43 @@ impl Component for ProxmoxRunningTasks {
                 self.update_data(ctx);
                 true
             }
+            Msg::ToggleLog(upid) => {
+                if !self.expanded.remove(&upid) {
+                    self.expanded.insert(upid);
+                }
+                true
+            }
+            Msg::OpenTask(upid) => {
+                let props = ctx.props();
+                if let Some(on_open_task) = &props.on_open_task {
+                    on_open_task.emit(upid.clone());
+                }
+                if let Some(on_show_task) = &props.on_show_task {
+                    let endtime = self
+                        .store
+                        .read()
+                        .lookup_record(&Key::from(upid.clone()))
+                        .and_then(|item| item.endtime);
+                    on_show_task.emit((upid, endtime));
+                }
+                false
+            }
+            Msg::FilterGuest(vmid) => {
+                self.guest_filter = vmid;
+                self.update_data(ctx);
+                true
+            }
         }
     }
 
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().vmid != old_props.vmid {
+            self.guest_filter = ctx.props().vmid;
+            self.update_data(ctx);
+        }
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
 
@@ -193,11 +297,27 @@ impl Component for ProxmoxRunningTasks {
             }
         });
 
-        Panel::new()
+        let title = match self.guest_filter {
+            Some(vmid) => tr!("Running Tasks of Guest {0}", vmid),
+            None => tr!("Running Tasks"),
+        };
+
+        // only allow clearing filters set from the menu
+        let clear_filter = (self.guest_filter != props.vmid).then(|| {
+            Button::new(tr!("Clear Filter"))
+                .onclick(ctx.link().callback(|_| Msg::FilterGuest(None)))
+        });
+
+        let mut panel = Panel::new()
             .min_width(600)
             .class("pwt-flex-fit")
             .border(true)
-            .title(tr!("Running Tasks"))
+            .title(title);
+        if let Some(clear_filter) = clear_filter {
+            panel = panel.with_tool(clear_filter);
+        }
+
+        panel
             .with_child(content)
             .with_optional_child(toolbar)
             .into()
@@ -240,6 +240,12 @@ fn sanitize_html_element(node: &web_sys::Node, base_url: &str, prefix: &str) ->
                 }
             }
 
+            // inputs (task list checkboxes) are display only, never let them be edited.
+            if tag_name == "input" {
+                elem.set_attribute("disabled", "")
+                    .map_err(convert_js_error)?;
+            }
+
             // snapshot children -- recursion may replace nodes.
             let children = node.child_nodes();
             let mut child_vec: Vec<web_sys::Node> = Vec::with_capacity(children.length() as usize);
@@ -0,0 +1,367 @@
+//! Generate simple input panels from API schemas.
+//!
+//! This is meant for simple edit dialogs, where all fields map directly to
+//! properties of an API object. The schema can be either a static
+//! [ObjectSchema], or the JSON schema description returned by the API
+//! (the `parameters` or `returns` object of an `api/info` entry).
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+use derivative::Derivative;
+use serde_json::Value;
+
+use proxmox_schema::{ApiStringFormat, ObjectSchema, ObjectSchemaType, Schema};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, Number};
+use pwt::widget::InputPanel;
+
+use crate::SchemaValidation;
+
+/// Field type, derived from the property schema.
+#[derive(Clone, Derivative)]
+#[derivative(Debug, PartialEq)]
+pub enum SchemaFormFieldType {
+    Boolean,
+    Integer {
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    Number {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Enum(Vec<AttrValue>),
+    /// A string field. Static schemas are used for validation.
+    String(
+        #[derivative(Debug = "ignore", PartialEq(compare_with = "schema_ptr_eq"))]
+        Option<&'static Schema>,
+    ),
+}
+
+// All our schemas are static, so comparing the pointers is good enough.
+fn schema_ptr_eq(a: &Option<&'static Schema>, b: &Option<&'static Schema>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => std::ptr::eq(*a, *b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// A single generated field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaFormField {
+    pub name: String,
+    pub description: String,
+    pub optional: bool,
+    pub default: Option<Value>,
+    pub field_type: SchemaFormFieldType,
+}
+
+impl SchemaFormField {
+    fn from_schema(name: &str, optional: bool, schema: &'static Schema) -> Option<Self> {
+        let (description, default, field_type) = match schema {
+            Schema::Boolean(s) => (
+                s.description,
+                s.default.map(Value::from),
+                SchemaFormFieldType::Boolean,
+            ),
+            Schema::Integer(s) => (
+                s.description,
+                s.default.map(Value::from),
+                SchemaFormFieldType::Integer {
+                    min: s.minimum.map(|v| v as i64),
+                    max: s.maximum.map(|v| v as i64),
+                },
+            ),
+            Schema::Number(s) => (
+                s.description,
+                s.default.map(Value::from),
+                SchemaFormFieldType::Number {
+                    min: s.minimum,
+                    max: s.maximum,
+                },
+            ),
+            Schema::String(s) => {
+                let field_type = match s.format {
+                    Some(ApiStringFormat::Enum(list)) => SchemaFormFieldType::Enum(
+                        list.iter().map(|e| AttrValue::from(e.value)).collect(),
+                    ),
+                    _ => SchemaFormFieldType::String(Some(schema)),
+                };
+                (s.description, s.default.map(Value::from), field_type)
+            }
+            // no simple input for arrays, objects and nulls
+            _ => return None,
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            optional,
+            default,
+            field_type,
+        })
+    }
+
+    /// Parse a property of a JSON schema as returned by the API.
+    fn from_json(name: &str, schema: &Value) -> Option<Self> {
+        let optional = match &schema["optional"] {
+            Value::Bool(optional) => *optional,
+            Value::Number(n) => n.as_u64() == Some(1),
+            _ => false,
+        };
+
+        let field_type = match schema["type"].as_str() {
+            Some("boolean") => SchemaFormFieldType::Boolean,
+            Some("integer") => SchemaFormFieldType::Integer {
+                min: schema["minimum"].as_i64(),
+                max: schema["maximum"].as_i64(),
+            },
+            Some("number") => SchemaFormFieldType::Number {
+                min: schema["minimum"].as_f64(),
+                max: schema["maximum"].as_f64(),
+            },
+            Some("string") => match schema["enum"].as_array() {
+                Some(list) => SchemaFormFieldType::Enum(
+                    list.iter()
+                        .filter_map(|v| v.as_str())
+                        .map(|v| AttrValue::from(v.to_string()))
+                        .collect(),
+                ),
+                None => SchemaFormFieldType::String(None),
+            },
+            _ => return None,
+        };
+
+        let default = match &schema["default"] {
+            Value::Null => None,
+            // the API returns booleans as 0/1
+            Value::Number(n) if field_type == SchemaFormFieldType::Boolean => {
+                Some(Value::Bool(n.as_u64() == Some(1)))
+            }
+            value => Some(value.clone()),
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            description: schema["description"].as_str().unwrap_or("").to_string(),
+            optional,
+            default,
+            field_type,
+        })
+    }
+
+    fn placeholder(&self) -> Option<String> {
+        match &self.default {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Create a label from a property name, i.e. `max-workers` => `Max workers`.
+pub fn schema_form_label(name: &str) -> String {
+    let text = name.replace(['-', '_'], " ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Input panel generator for simple API objects.
+///
+/// ```ignore
+/// EditWindow::new(tr!("Options"))
+///     .loader("/config/options")
+///     .schema_form(SchemaForm::new(&OPTIONS_SCHEMA).skip("digest"))
+///     .on_submit(...)
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct SchemaForm {
+    fields: Rc<Vec<SchemaFormField>>,
+    skip: Vec<String>,
+    labels: HashMap<String, AttrValue>,
+    mobile: bool,
+}
+
+impl SchemaForm {
+    /// Create a form for the properties of a static object schema.
+    pub fn new(schema: &'static ObjectSchema) -> Self {
+        let fields = schema
+            .properties()
+            .filter_map(|(name, optional, schema)| {
+                SchemaFormField::from_schema(name, *optional, *schema)
+            })
+            .collect();
+        Self::with_fields(fields)
+    }
+
+    /// Create a form from a JSON object schema, as returned by the API.
+    pub fn from_json_schema(schema: &Value) -> Result<Self, Error> {
+        let Some(properties) = schema["properties"].as_object() else {
+            bail!("schema has no properties");
+        };
+        let fields = properties
+            .iter()
+            .filter_map(|(name, schema)| SchemaFormField::from_json(name, schema))
+            .collect();
+        Ok(Self::with_fields(fields))
+    }
+
+    fn with_fields(fields: Vec<SchemaFormField>) -> Self {
+        Self {
+            fields: Rc::new(fields),
+            skip: Vec::new(),
+            labels: HashMap::new(),
+            mobile: false,
+        }
+    }
+
+    /// The generated field descriptions.
+    pub fn fields(&self) -> &[SchemaFormField] {
+        &self.fields
+    }
+
+    /// Builder style method to exclude a property from the form.
+    pub fn skip(mut self, name: impl Into<String>) -> Self {
+        self.skip.push(name.into());
+        self
+    }
+
+    /// Builder style method to set the label of a property.
+    ///
+    /// By default, the label is derived from the property name.
+    pub fn label(mut self, name: impl Into<String>, label: impl Into<AttrValue>) -> Self {
+        self.labels.insert(name.into(), label.into());
+        self
+    }
+
+    /// Builder style method to set the mobile layout.
+    pub fn mobile(mut self, mobile: bool) -> Self {
+        self.mobile = mobile;
+        self
+    }
+
+    /// Render the input panel.
+    pub fn render(&self, _form_ctx: &FormContext) -> Html {
+        let mut panel = InputPanel::new()
+            .mobile(self.mobile)
+            .class(pwt::css::FlexFit)
+            .padding_x(2);
+
+        for field in self.fields.iter() {
+            if self.skip.contains(&field.name) {
+                continue;
+            }
+            let label = match self.labels.get(&field.name) {
+                Some(label) => label.to_string(),
+                None => schema_form_label(&field.name),
+            };
+            let name = field.name.clone();
+            let required = !field.optional;
+            let placeholder = field.placeholder();
+
+            panel = match &field.field_type {
+                SchemaFormFieldType::Boolean => panel.with_field(
+                    label,
+                    Checkbox::new()
+                        .name(name)
+                        .default(field.default.as_ref().and_then(Value::as_bool) == Some(true)),
+                ),
+                SchemaFormFieldType::Integer { min, max } => panel.with_field(
+                    label,
+                    Number::<i64>::new()
+                        .name(name)
+                        .required(required)
+                        .min(*min)
+                        .max(*max)
+                        .placeholder(placeholder),
+                ),
+                SchemaFormFieldType::Number { min, max } => panel.with_field(
+                    label,
+                    Number::<f64>::new()
+                        .name(name)
+                        .required(required)
+                        .min(*min)
+                        .max(*max)
+                        .placeholder(placeholder),
+                ),
+                SchemaFormFieldType::Enum(items) => panel.with_field(
+                    label,
+                    Combobox::new()
+                        .name(name)
+                        .required(required)
+                        .items(Rc::new(items.clone()))
+                        .placeholder(placeholder),
+                ),
+                SchemaFormFieldType::String(Some(schema)) => panel.with_field(
+                    label,
+                    Field::new().name(name).required(required).schema(*schema),
+                ),
+                SchemaFormFieldType::String(None) => panel.with_field(
+                    label,
+                    Field::new()
+                        .name(name)
+                        .required(required)
+                        .placeholder(placeholder),
+                ),
+            };
+        }
+
+        panel.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_schema_form_label() {
+        assert_eq!(schema_form_label("max-workers"), "Max workers");
+        assert_eq!(schema_form_label("comment"), "Comment");
+        assert_eq!(schema_form_label(""), "");
+    }
+
+    #[test]
+    fn test_from_json_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "enable": { "type": "boolean", "optional": 1, "default": 1 },
+                "workers": { "type": "integer", "minimum": 1, "maximum": 8 },
+                "mode": { "type": "string", "enum": ["a", "b"], "optional": 1 },
+                "list": { "type": "array", "items": { "type": "string" } },
+            },
+        });
+        let form = SchemaForm::from_json_schema(&schema).unwrap();
+        let fields = form.fields();
+        assert_eq!(fields.len(), 3);
+
+        let field = |name: &str| fields.iter().find(|f| f.name == name).unwrap();
+
+        assert_eq!(field("enable").default, Some(Value::Bool(true)));
+        assert!(field("enable").optional);
+        assert_eq!(
+            field("workers").field_type,
+            SchemaFormFieldType::Integer {
+                min: Some(1),
+                max: Some(8)
+            }
+        );
+        assert!(!field("workers").optional);
+        assert_eq!(
+            field("mode").field_type,
+            SchemaFormFieldType::Enum(vec!["a".into(), "b".into()])
+        );
+
+        assert!(SchemaForm::from_json_schema(&json!({})).is_err());
+    }
+}
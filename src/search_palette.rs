@@ -0,0 +1,522 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_events::{EventListener, EventListenerOptions};
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::Field;
+use pwt::widget::{Column, Container, Dialog, Fa, Row};
+use pwt::AsyncPool;
+
+use pwt_macros::builder;
+
+use pbs_api_types::TaskListItem;
+use pve_api_types::{ClusterResource, ClusterResourceType};
+
+use crate::utils::format_upid;
+
+const SEARCH_DELAY_MS: u32 = 200;
+
+type SearchFuture = Pin<Box<dyn Future<Output = Result<Vec<SearchResult>, Error>>>>;
+
+/// A single search result.
+#[derive(Clone, PartialEq)]
+pub struct SearchResult {
+    pub title: AttrValue,
+    pub subtitle: Option<AttrValue>,
+    pub icon_class: Option<AttrValue>,
+    /// Called when the result gets selected, usually to navigate to the object.
+    pub on_select: Callback<()>,
+}
+
+impl SearchResult {
+    pub fn new(title: impl Into<AttrValue>, on_select: impl Into<Callback<()>>) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            icon_class: None,
+            on_select: on_select.into(),
+        }
+    }
+
+    /// Builder style method to set the subtitle.
+    pub fn subtitle(mut self, subtitle: impl Into<AttrValue>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Builder style method to set the icon class.
+    pub fn icon_class(mut self, icon_class: impl Into<AttrValue>) -> Self {
+        self.icon_class = Some(icon_class.into());
+        self
+    }
+}
+
+/// A named source of search results.
+///
+/// The search function gets called with the query text and returns the matching
+/// results (asynchronously, so that providers can query the API).
+#[derive(Clone)]
+pub struct SearchProvider {
+    pub name: AttrValue,
+    search: Rc<dyn Fn(String) -> SearchFuture>,
+}
+
+impl PartialEq for SearchProvider {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Rc::ptr_eq(&self.search, &other.search)
+    }
+}
+
+impl SearchProvider {
+    pub fn new<F, R>(name: impl Into<AttrValue>, search: F) -> Self
+    where
+        F: 'static + Fn(String) -> R,
+        R: 'static + Future<Output = Result<Vec<SearchResult>, Error>>,
+    {
+        Self {
+            name: name.into(),
+            search: Rc::new(move |query| Box::pin(search(query))),
+        }
+    }
+
+    /// Provider for a static list, for example the configuration sections of the UI.
+    ///
+    /// Entries match if the title or subtitle contain all words of the query.
+    pub fn with_entries(name: impl Into<AttrValue>, entries: Vec<SearchResult>) -> Self {
+        let entries = Rc::new(entries);
+        Self::new(name, move |query: String| {
+            let entries = Rc::clone(&entries);
+            async move {
+                Ok(entries
+                    .iter()
+                    .filter(|entry| {
+                        let subtitle = entry.subtitle.as_deref().unwrap_or("");
+                        search_matches(&query, &[entry.title.as_str(), subtitle])
+                    })
+                    .cloned()
+                    .collect())
+            }
+        })
+    }
+
+    /// Provider for cluster resources (`/cluster/resources`).
+    ///
+    /// `on_select` is called with the resource `id` (for example `qemu/100`).
+    pub fn cluster_resources(url: impl Into<AttrValue>, on_select: Callback<String>) -> Self {
+        let url = url.into();
+        Self::new(tr!("Resources"), move |query: String| {
+            let url = url.clone();
+            let on_select = on_select.clone();
+            async move {
+                let resources: Vec<ClusterResource> = crate::http_get(url.as_str(), None).await?;
+                Ok(resources
+                    .iter()
+                    .filter_map(|resource| {
+                        let id = resource.id.as_str();
+                        let name = resource.name.as_deref().unwrap_or("");
+                        let node = resource.node.as_deref().unwrap_or("");
+                        let mut texts = vec![id, name, node];
+                        if let Some(tags) = &resource.tags {
+                            texts.extend(tags.split(';').filter(|tag| !tag.is_empty()));
+                        }
+                        if !search_matches(&query, &texts) {
+                            return None;
+                        }
+                        let title = match name {
+                            "" => id.to_string(),
+                            name => format!("{id} ({name})"),
+                        };
+                        let id = id.to_string();
+                        let on_select = on_select.clone();
+                        Some(
+                            SearchResult::new(title, move |_| on_select.emit(id.clone()))
+                                .subtitle(node.to_string())
+                                .icon_class(resource_icon_class(resource.ty)),
+                        )
+                    })
+                    .collect())
+            }
+        })
+    }
+
+    /// Provider for recent tasks (`<base_url>` is a task list endpoint).
+    ///
+    /// `on_select` is called with the UPID.
+    pub fn recent_tasks(base_url: impl Into<AttrValue>, on_select: Callback<String>) -> Self {
+        let base_url = base_url.into();
+        Self::new(tr!("Recent Tasks"), move |query: String| {
+            let base_url = base_url.clone();
+            let on_select = on_select.clone();
+            async move {
+                let param = serde_json::json!({ "limit": 100 });
+                let tasks: Vec<TaskListItem> =
+                    crate::http_get(base_url.as_str(), Some(param)).await?;
+                Ok(tasks
+                    .into_iter()
+                    .filter_map(|task| {
+                        let description = format_upid(&task.upid);
+                        if !search_matches(
+                            &query,
+                            &[description.as_str(), task.user.as_str(), task.node.as_str()],
+                        ) {
+                            return None;
+                        }
+                        let upid = task.upid.clone();
+                        let on_select = on_select.clone();
+                        Some(
+                            SearchResult::new(description, move |_| on_select.emit(upid.clone()))
+                                .subtitle(format!("{}, {}", task.node, task.user))
+                                .icon_class("fa fa-list-alt"),
+                        )
+                    })
+                    .collect())
+            }
+        })
+    }
+}
+
+fn resource_icon_class(resource_type: ClusterResourceType) -> &'static str {
+    match resource_type {
+        ClusterResourceType::Qemu => "fa fa-desktop",
+        ClusterResourceType::Lxc => "fa fa-cube",
+        ClusterResourceType::Node => "fa fa-building",
+        ClusterResourceType::Storage => "fa fa-database",
+        ClusterResourceType::Pool => "fa fa-tags",
+        ClusterResourceType::Sdn => "fa fa-th",
+        _ => "fa fa-question",
+    }
+}
+
+/// Returns true if all (whitespace separated) words of the query are contained
+/// in one of the texts (case insensitive).
+pub fn search_matches(query: &str, texts: &[&str]) -> bool {
+    let texts: Vec<String> = texts.iter().map(|text| text.to_lowercase()).collect();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| texts.iter().any(|text| text.contains(word)))
+}
+
+/// Search overlay, opened with `Ctrl+K` (`Cmd+K` on macOS).
+///
+/// Wraps the application (like [IdleGuard](crate::IdleGuard)) and queries all providers
+/// while the user types. Results are grouped by provider, selecting a result closes the
+/// palette and calls its `on_select` callback.
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct SearchPalette {
+    #[prop_or_default]
+    pub children: Html,
+
+    /// The search providers, queried in order.
+    #[prop_or_default]
+    pub providers: Vec<SearchProvider>,
+
+    /// Maximum number of results shown per provider.
+    #[builder]
+    #[prop_or(10)]
+    pub max_results: usize,
+}
+
+impl SearchPalette {
+    /// Create a new instance.
+    pub fn new(children: impl Into<Html>) -> Self {
+        yew::props!(Self {
+            children: children.into(),
+        })
+    }
+
+    /// Builder style method to add a search provider.
+    pub fn with_provider(mut self, provider: SearchProvider) -> Self {
+        self.providers.push(provider);
+        self
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Open,
+    Close,
+    Query(String),
+    Search,
+    Result(usize, usize, Result<Vec<SearchResult>, Error>),
+    Move(isize),
+    Select(Option<usize>),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxSearchPalette {
+    open: bool,
+    query: String,
+    // increased with each search, so that results of old queries get ignored
+    generation: usize,
+    results: Vec<Option<Result<Vec<SearchResult>, String>>>,
+    cursor: usize,
+    async_pool: AsyncPool,
+    search_timeout: Option<Timeout>,
+    _listener: EventListener,
+}
+
+impl ProxmoxSearchPalette {
+    fn visible_results<'a>(&'a self, ctx: &Context<Self>) -> Vec<&'a SearchResult> {
+        let max_results = ctx.props().max_results;
+        self.results
+            .iter()
+            .filter_map(|result| match result {
+                Some(Ok(list)) => Some(list.iter().take(max_results)),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn view_palette(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+
+        let mut list = Column::new()
+            .class("pwt-overflow-auto")
+            .style("max-height", "60vh")
+            .attribute("role", "listbox");
+
+        let mut index = 0;
+        for (provider, result) in props.providers.iter().zip(&self.results) {
+            let entries = match result {
+                None => {
+                    list.add_child(
+                        Container::new()
+                            .padding(2)
+                            .class("pwt-font-label-medium")
+                            .with_child(Fa::new("spinner").class("fa-pulse"))
+                            .with_child(format!(" {}", provider.name)),
+                    );
+                    continue;
+                }
+                Some(Err(err)) => {
+                    log::error!("search provider '{}' failed: {err}", provider.name);
+                    continue;
+                }
+                Some(Ok(entries)) if entries.is_empty() => continue,
+                Some(Ok(entries)) => entries,
+            };
+
+            list.add_child(
+                Container::new()
+                    .padding_x(2)
+                    .padding_top(2)
+                    .class("pwt-font-label-medium")
+                    .with_child(provider.name.clone()),
+            );
+
+            for entry in entries.iter().take(props.max_results) {
+                let selected = index == self.cursor;
+                let current = index;
+                list.add_child(
+                    Row::new()
+                        .padding(2)
+                        .gap(2)
+                        .class(selected.then_some("pwt-scheme-secondary-container"))
+                        .class(pwt::css::AlignItems::Center)
+                        .style("cursor", "pointer")
+                        .attribute("role", "option")
+                        .attribute("aria-selected", selected.then_some("true"))
+                        .with_optional_child(
+                            entry
+                                .icon_class
+                                .as_ref()
+                                .map(|class| html! {<i class={class.to_string()}/>}),
+                        )
+                        .with_child(
+                            Column::new()
+                                .with_child(entry.title.clone())
+                                .with_optional_child(entry.subtitle.as_ref().map(|subtitle| {
+                                    Container::new()
+                                        .class("pwt-font-label-small")
+                                        .with_child(subtitle.clone())
+                                })),
+                        )
+                        .onclick(link.callback(move |_| Msg::Select(Some(current)))),
+                );
+                index += 1;
+            }
+        }
+
+        if !self.query.trim().is_empty() && index == 0 && self.results.iter().all(Option::is_some) {
+            list.add_child(
+                Container::new()
+                    .padding(2)
+                    .with_child(tr!("No matching results")),
+            );
+        }
+
+        let input = Container::new()
+            .padding(2)
+            .class("pwt-border-bottom")
+            .with_child(
+                Field::new()
+                    .class("pwt-w-100")
+                    .autofocus(true)
+                    .placeholder(tr!("Search"))
+                    .on_change(link.callback(Msg::Query)),
+            )
+            .onkeydown({
+                let link = link.clone();
+                move |event: KeyboardEvent| {
+                    match event.key().as_str() {
+                        "ArrowDown" => link.send_message(Msg::Move(1)),
+                        "ArrowUp" => link.send_message(Msg::Move(-1)),
+                        "Enter" => link.send_message(Msg::Select(None)),
+                        _ => return,
+                    }
+                    event.stop_propagation();
+                    event.prevent_default();
+                }
+            });
+
+        Dialog::new(tr!("Search"))
+            .min_width(500)
+            .on_close(link.callback(|_| Msg::Close))
+            .with_child(input)
+            .with_child(list)
+            .into()
+    }
+}
+
+impl Component for ProxmoxSearchPalette {
+    type Message = Msg;
+    type Properties = SearchPalette;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let listener = EventListener::new_with_options(
+            &gloo_utils::document(),
+            "keydown",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                if (event.ctrl_key() || event.meta_key()) && event.key() == "k" {
+                    event.prevent_default();
+                    link.send_message(Msg::Open);
+                }
+            },
+        );
+
+        Self {
+            open: false,
+            query: String::new(),
+            generation: 0,
+            results: Vec::new(),
+            cursor: 0,
+            async_pool: AsyncPool::new(),
+            search_timeout: None,
+            _listener: listener,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Open => {
+                if self.open {
+                    return false;
+                }
+                self.open = true;
+                self.query.clear();
+                self.results.clear();
+                self.cursor = 0;
+                true
+            }
+            Msg::Close => {
+                self.open = false;
+                self.generation += 1;
+                self.search_timeout = None;
+                true
+            }
+            Msg::Query(query) => {
+                self.query = query;
+                self.cursor = 0;
+                let link = ctx.link().clone();
+                self.search_timeout = Some(Timeout::new(SEARCH_DELAY_MS, move || {
+                    link.send_message(Msg::Search)
+                }));
+                true
+            }
+            Msg::Search => {
+                self.generation += 1;
+                if self.query.trim().is_empty() {
+                    self.results.clear();
+                    return true;
+                }
+                self.results = vec![None; props.providers.len()];
+                for (index, provider) in props.providers.iter().enumerate() {
+                    let future = (provider.search)(self.query.clone());
+                    let link = ctx.link().clone();
+                    let generation = self.generation;
+                    self.async_pool.spawn(async move {
+                        let result = future.await;
+                        link.send_message(Msg::Result(generation, index, result));
+                    });
+                }
+                true
+            }
+            Msg::Result(generation, index, result) => {
+                if generation != self.generation || index >= self.results.len() {
+                    return false;
+                }
+                self.results[index] = Some(result.map_err(|err| err.to_string()));
+                true
+            }
+            Msg::Move(offset) => {
+                let count = self.visible_results(ctx).len();
+                if count == 0 {
+                    return false;
+                }
+                self.cursor = (self.cursor as isize + offset).rem_euclid(count as isize) as usize;
+                true
+            }
+            Msg::Select(index) => {
+                let index = index.unwrap_or(self.cursor);
+                let Some(result) = self.visible_results(ctx).get(index).cloned().cloned() else {
+                    return false;
+                };
+                self.open = false;
+                self.generation += 1;
+                result.on_select.emit(());
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let palette = self.open.then(|| self.view_palette(ctx));
+        html! {<>{ctx.props().children.clone()}{palette}</>}
+    }
+}
+
+impl From<SearchPalette> for VNode {
+    fn from(val: SearchPalette) -> Self {
+        let comp = VComp::new::<ProxmoxSearchPalette>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_search_matches() {
+        assert!(search_matches("", &["anything"]));
+        assert!(search_matches("Web 10", &["qemu/100", "webserver"]));
+        assert!(!search_matches("web db", &["qemu/100", "webserver"]));
+    }
+}
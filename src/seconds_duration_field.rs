@@ -0,0 +1,293 @@
+use anyhow::{bail, Error};
+use serde_json::Value;
+
+use yew::html::IntoPropValue;
+
+use pwt::css::AlignItems;
+use pwt::prelude::*;
+use pwt::widget::form::{
+    Field, ManagedField, ManagedFieldContext, ManagedFieldMaster, ManagedFieldScopeExt,
+    ManagedFieldState,
+};
+use pwt::widget::Container;
+
+use pwt::props::{FieldBuilder, WidgetBuilder};
+
+use pwt_macros::{builder, widget};
+
+/// Parse a duration in seconds.
+///
+/// Accepts a plain number of seconds (`90`) or a sequence of numbers with a unit suffix
+/// (`1m30s`, `2h`, `1d 12h`). Known units are `d`, `h`, `m` and `s`.
+pub fn parse_seconds_duration(text: &str) -> Result<u64, Error> {
+    let text = text.trim();
+    if text.is_empty() {
+        bail!("empty duration");
+    }
+    if let Ok(seconds) = text.parse::<u64>() {
+        return Ok(seconds);
+    }
+
+    let mut total: u64 = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+        let factor = match c {
+            'd' => 86400,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => bail!("unknown unit '{c}'"),
+        };
+        if number.is_empty() {
+            bail!("missing number before unit '{c}'");
+        }
+        let value: u64 = number.parse()?;
+        number.clear();
+        total = value
+            .checked_mul(factor)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(|| Error::msg("duration too large"))?;
+    }
+    if !number.is_empty() {
+        bail!("missing unit after '{number}'");
+    }
+    Ok(total)
+}
+
+/// Format seconds as duration text, for example `1m 30s`.
+///
+/// Unlike [format_duration_human](crate::utils::format_duration_human) this is exact, so the
+/// result can be parsed again with [parse_seconds_duration].
+pub fn format_seconds_duration(seconds: u64) -> String {
+    if seconds == 0 {
+        return String::from("0s");
+    }
+
+    let mut parts = Vec::new();
+    let mut remaining = seconds;
+    for (factor, unit) in [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")] {
+        let value = remaining / factor;
+        remaining %= factor;
+        if value > 0 {
+            parts.push(format!("{value}{unit}"));
+        }
+    }
+    parts.join(" ")
+}
+
+pub type ProxmoxSecondsDurationField = ManagedFieldMaster<ProxmoxSecondsDurationFieldComp>;
+
+/// Input field for durations in seconds.
+///
+/// Accepts input like `90`, `1m30s` or `2h` (see [parse_seconds_duration]), shows a
+/// humanized preview and submits the number of seconds.
+#[widget(comp=ManagedFieldMaster<ProxmoxSecondsDurationFieldComp>, @input)]
+#[derive(Clone, Properties, PartialEq)]
+#[builder]
+pub struct SecondsDurationField {
+    /// The default value (in seconds).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub default: Option<u64>,
+
+    /// Minimum value (in seconds).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub min: Option<u64>,
+
+    /// Maximum value (in seconds).
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub max: Option<u64>,
+
+    /// Placeholder text.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub placeholder: Option<AttrValue>,
+}
+
+impl Default for SecondsDurationField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecondsDurationField {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+pub enum Msg {
+    Input(String),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxSecondsDurationFieldComp {
+    state: ManagedFieldState,
+    text: String,
+}
+
+pwt::impl_deref_mut_property!(ProxmoxSecondsDurationFieldComp, state, ManagedFieldState);
+
+#[derive(PartialEq)]
+pub struct ValidateClosure {
+    required: bool,
+    min: Option<u64>,
+    max: Option<u64>,
+}
+
+fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+impl ManagedField for ProxmoxSecondsDurationFieldComp {
+    type Message = Msg;
+    type Properties = SecondsDurationField;
+    type ValidateClosure = ValidateClosure;
+
+    fn validation_args(props: &Self::Properties) -> Self::ValidateClosure {
+        ValidateClosure {
+            required: props.input_props.required,
+            min: props.min,
+            max: props.max,
+        }
+    }
+
+    fn validator(props: &Self::ValidateClosure, value: &Value) -> Result<Value, Error> {
+        let text = match value {
+            Value::Null => String::new(),
+            Value::Number(_) | Value::String(_) => value_to_text(value),
+            _ => return Err(Error::msg(tr!("Got wrong data type!"))),
+        };
+
+        if text.trim().is_empty() {
+            if props.required {
+                return Err(Error::msg(tr!("Field may not be empty.")));
+            }
+            return Ok(Value::String(String::new()));
+        }
+
+        let seconds = parse_seconds_duration(&text)
+            .map_err(|err| Error::msg(tr!("unable to parse duration: {}", err)))?;
+
+        if let Some(min) = props.min {
+            if seconds < min {
+                bail!(tr!("Minimum duration is {}", format_seconds_duration(min)));
+            }
+        }
+        if let Some(max) = props.max {
+            if seconds > max {
+                bail!(tr!("Maximum duration is {}", format_seconds_duration(max)));
+            }
+        }
+
+        Ok(seconds.into())
+    }
+
+    fn create(ctx: &ManagedFieldContext<Self>) -> Self {
+        let props = ctx.props();
+
+        let default: Value = match props.default {
+            Some(default) => default.into(),
+            None => String::new().into(),
+        };
+
+        let mut me = Self {
+            state: ManagedFieldState::new(default.clone(), default),
+            text: String::new(),
+        };
+        me.value_changed(ctx);
+        me
+    }
+
+    fn value_changed(&mut self, _ctx: &ManagedFieldContext<Self>) {
+        self.text = value_to_text(&self.state.value);
+    }
+
+    fn update(&mut self, ctx: &ManagedFieldContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Input(text) => {
+                ctx.link().update_value(text);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &ManagedFieldContext<Self>) -> Html {
+        let props = ctx.props();
+
+        let mut input_props = props.input_props.clone();
+        input_props.name = None;
+
+        let input = Field::new()
+            .with_input_props(&input_props)
+            .submit(false)
+            .class("pwt-flex-fill")
+            .placeholder(props.placeholder.clone())
+            .value(self.text.clone())
+            .on_change(ctx.link().callback(Msg::Input));
+
+        // only show the preview if it adds information
+        let preview = match parse_seconds_duration(&self.text) {
+            Ok(seconds) if self.text.trim() != format_seconds_duration(seconds) => {
+                Some(format_seconds_duration(seconds))
+            }
+            _ => None,
+        };
+
+        Container::new()
+            .with_std_props(&props.std_props)
+            .class("pwt-d-flex pwt-gap-2")
+            .class(AlignItems::Center)
+            .with_child(input)
+            .with_optional_child(preview.map(|preview| {
+                Container::new()
+                    .class("pwt-opacity-50")
+                    .style("white-space", "nowrap")
+                    .with_child(preview)
+            }))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_seconds_duration() {
+        assert_eq!(parse_seconds_duration("90").unwrap(), 90);
+        assert_eq!(parse_seconds_duration("1m30s").unwrap(), 90);
+        assert_eq!(parse_seconds_duration("2h").unwrap(), 7200);
+        assert_eq!(parse_seconds_duration(" 1d 1h 1m 1s ").unwrap(), 90061);
+
+        assert!(parse_seconds_duration("").is_err());
+        assert!(parse_seconds_duration("10x").is_err());
+        assert!(parse_seconds_duration("m").is_err());
+        assert!(parse_seconds_duration("1m30").is_err());
+    }
+
+    #[test]
+    fn test_format_seconds_duration() {
+        assert_eq!(format_seconds_duration(0), "0s");
+        assert_eq!(format_seconds_duration(90), "1m 30s");
+        assert_eq!(format_seconds_duration(7200), "2h");
+        for seconds in [1, 59, 61, 3599, 90061] {
+            let text = format_seconds_duration(seconds);
+            assert_eq!(parse_seconds_duration(&text).unwrap(), seconds);
+        }
+    }
+}
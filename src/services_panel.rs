@@ -0,0 +1,311 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::state::{Selection, Store};
+use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
+use pwt::widget::{Button, Dialog, Toolbar};
+
+use crate::percent_encoding::percent_encode_component;
+use crate::{
+    LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState, Syslog,
+};
+
+use pwt_macros::builder;
+
+/// A system service entry (`GET /nodes/{node}/services`).
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServiceEntry {
+    /// The service id, used in the API paths.
+    pub service: String,
+    /// The systemd unit name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Service description.
+    #[serde(default)]
+    pub desc: Option<String>,
+    /// The service state (for example `running` or `dead`).
+    #[serde(default)]
+    pub state: Option<String>,
+    /// The systemd active state.
+    #[serde(default)]
+    pub active_state: Option<String>,
+    /// The systemd unit file state (for example `enabled` or `disabled`).
+    #[serde(default)]
+    pub unit_state: Option<String>,
+}
+
+/// List of the node's system daemons.
+///
+/// Services can be started, stopped, restarted and reloaded, the resulting task is shown in
+/// a [TaskViewer](crate::TaskViewer). The syslog button shows the journal filtered by the
+/// selected service.
+#[derive(Properties, PartialEq, Clone)]
+#[builder]
+pub struct ServicesPanel {
+    /// The base url of the services API.
+    #[prop_or("/nodes/localhost/services".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub base_url: AttrValue,
+
+    /// The base url for tasks.
+    #[prop_or("/nodes/localhost/tasks".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub task_base_url: AttrValue,
+
+    /// The base url for the syslog API.
+    #[prop_or("/nodes/localhost/syslog".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub syslog_base_url: AttrValue,
+
+    /// The base url for the journal API.
+    #[prop_or("/nodes/localhost/journal".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub journal_base_url: AttrValue,
+}
+
+impl Default for ServicesPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServicesPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[derive(PartialEq)]
+pub enum ViewState {
+    Syslog(Key),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxServicesPanel {
+    state: LoadableComponentState<ViewState>,
+    selection: Selection,
+    store: Store<ServiceEntry>,
+    columns: Rc<Vec<DataTableHeader<ServiceEntry>>>,
+}
+
+pwt::impl_deref_mut_property!(
+    ProxmoxServicesPanel,
+    state,
+    LoadableComponentState<ViewState>
+);
+
+fn optional_text(text: &Option<String>) -> Html {
+    html! { text.as_deref().unwrap_or("-") }
+}
+
+impl ProxmoxServicesPanel {
+    fn columns() -> Rc<Vec<DataTableHeader<ServiceEntry>>> {
+        Rc::new(vec![
+            DataTableColumn::new(tr!("Name"))
+                .width("150px")
+                .render(|record: &ServiceEntry| html! { &record.service })
+                .sorter(|a: &ServiceEntry, b: &ServiceEntry| a.service.cmp(&b.service))
+                .sort_order(true)
+                .into(),
+            DataTableColumn::new(tr!("State"))
+                .width("100px")
+                .render(|record: &ServiceEntry| optional_text(&record.state))
+                .sorter(|a: &ServiceEntry, b: &ServiceEntry| a.state.cmp(&b.state))
+                .into(),
+            DataTableColumn::new(tr!("Active"))
+                .width("100px")
+                .hidden(true)
+                .render(|record: &ServiceEntry| optional_text(&record.active_state))
+                .into(),
+            DataTableColumn::new(tr!("Unit"))
+                .width("100px")
+                .render(|record: &ServiceEntry| optional_text(&record.unit_state))
+                .sorter(|a: &ServiceEntry, b: &ServiceEntry| a.unit_state.cmp(&b.unit_state))
+                .into(),
+            DataTableColumn::new(tr!("Description"))
+                .flex(1)
+                .render(|record: &ServiceEntry| optional_text(&record.desc))
+                .into(),
+        ])
+    }
+
+    fn action_button(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        label: String,
+        icon_class: &'static str,
+        action: &'static str,
+        disabled: bool,
+    ) -> Button {
+        let selected_key = self.selection.selected_key();
+        let base_url = ctx.props().base_url.clone();
+        let link = ctx.link().clone();
+        Button::new(label)
+            .icon_class(icon_class)
+            .disabled(disabled || selected_key.is_none())
+            .onclick(move |_| {
+                if let Some(service) = &selected_key {
+                    let command =
+                        format!("{base_url}/{}/{action}", percent_encode_component(service));
+                    link.start_task(command, None, false);
+                }
+            })
+    }
+}
+
+impl LoadableComponent for ProxmoxServicesPanel {
+    type Properties = ServicesPanel;
+    type Message = ();
+    type ViewState = ViewState;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        let selection = Selection::new().on_select({
+            let link = ctx.link().clone();
+            move |_| link.send_redraw()
+        });
+        let store =
+            Store::with_extract_key(|record: &ServiceEntry| Key::from(record.service.clone()));
+
+        let mut state = LoadableComponentState::new();
+        state.set_task_base_url(ctx.props().task_base_url.clone());
+
+        Self {
+            state,
+            selection,
+            store,
+            columns: Self::columns(),
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>>>> {
+        let url = ctx.props().base_url.to_string();
+        let store = self.store.clone();
+        Box::pin(async move {
+            let data = crate::http_get(url, None).await?;
+            store.write().set_data(data);
+            Ok(())
+        })
+    }
+
+    fn toolbar(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        let selected_key = self.selection.selected_key();
+        let running = selected_key
+            .as_ref()
+            .and_then(|key| {
+                self.store
+                    .read()
+                    .lookup_record(key)
+                    .map(|record| record.state.as_deref() == Some("running"))
+            })
+            .unwrap_or(false);
+
+        let toolbar = Toolbar::new()
+            .class("pwt-w-100")
+            .class("pwt-overflow-hidden")
+            .class("pwt-border-bottom")
+            .with_child(self.action_button(ctx, tr!("Start"), "fa fa-play", "start", running))
+            .with_child(self.action_button(ctx, tr!("Stop"), "fa fa-stop", "stop", !running))
+            .with_child(self.action_button(
+                ctx,
+                tr!("Restart"),
+                "fa fa-refresh",
+                "restart",
+                !running,
+            ))
+            .with_child(self.action_button(ctx, tr!("Reload"), "fa fa-retweet", "reload", !running))
+            .with_spacer()
+            .with_child(
+                Button::new(tr!("Syslog"))
+                    .icon_class("fa fa-list")
+                    .disabled(selected_key.is_none())
+                    .onclick({
+                        let link = ctx.link().clone();
+                        move |_| {
+                            if let Some(key) = &selected_key {
+                                link.change_view(Some(ViewState::Syslog(key.clone())));
+                            }
+                        }
+                    }),
+            )
+            .with_flex_spacer()
+            .with_child({
+                let loading = self.loading();
+                let link = ctx.link().clone();
+                Button::refresh(loading).onclick(move |_| link.send_reload())
+            });
+
+        Some(toolbar.into())
+    }
+
+    fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        DataTable::new(self.columns.clone(), self.store.clone())
+            .class("pwt-flex-fit")
+            .selection(self.selection.clone())
+            .on_row_dblclick({
+                let selection = self.selection.clone();
+                let link = ctx.link().clone();
+                move |_: &mut _| {
+                    if let Some(key) = selection.selected_key() {
+                        link.change_view(Some(ViewState::Syslog(key)));
+                    }
+                }
+            })
+            .into()
+    }
+
+    fn dialog_view(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+        view_state: &Self::ViewState,
+    ) -> Option<Html> {
+        let props = ctx.props();
+        match view_state {
+            ViewState::Syslog(service) => Some(
+                Dialog::new(tr!("Syslog") + ": " + &**service)
+                    .resizable(true)
+                    .width(840)
+                    .height(600)
+                    .on_close(ctx.link().change_view_callback(|_| None))
+                    .with_child(
+                        Syslog::new()
+                            .base_url(props.syslog_base_url.clone())
+                            .journal_base_url(props.journal_base_url.clone())
+                            .service(service.to_string()),
+                    )
+                    .into(),
+            ),
+        }
+    }
+
+    fn changed(
+        &mut self,
+        ctx: &LoadableComponentContext<Self>,
+        old_props: &Self::Properties,
+    ) -> bool {
+        let props = ctx.props();
+        self.set_task_base_url(props.task_base_url.clone());
+        if props.base_url != old_props.base_url {
+            ctx.link().send_reload();
+        }
+        true
+    }
+}
+
+impl From<ServicesPanel> for VNode {
+    fn from(val: ServicesPanel) -> Self {
+        let comp = VComp::new::<LoadableComponentMaster<ProxmoxServicesPanel>>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,147 @@
+use std::rc::Rc;
+
+use yew::html::IntoEventCallback;
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt_macros::builder;
+
+use crate::{
+    register_auth_observer, register_login_observer, start_ticket_refresh_loop, AuthObserver,
+    LoginObserver,
+};
+
+/// The state of the user session, see [SessionKeeper].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionState {
+    /// The session is valid.
+    Active,
+    /// The server rejected a request (401), the user needs to login again.
+    Expired,
+    /// The user logged out (possibly in another browser tab) or the ticket expired.
+    LoggedOut,
+}
+
+/// Session information provided by the [SessionKeeper].
+#[derive(Clone, PartialEq)]
+pub struct SessionContext {
+    state: SessionState,
+    on_reset: Callback<()>,
+}
+
+impl SessionContext {
+    /// The current session state.
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Returns true if the user needs to login again.
+    pub fn is_expired(&self) -> bool {
+        self.state != SessionState::Active
+    }
+
+    /// Mark the session as active again, for example after a successful re-login.
+    pub fn reset(&self) {
+        self.on_reset.emit(());
+    }
+}
+
+/// Keeps the user session alive and reports when it ends.
+///
+/// The authentication ticket is renewed periodically in the background. When a request fails
+/// with 401 (unauthorized), the ticket expires or the user logs out in another tab, the
+/// [SessionContext] provided to the children changes, so applications can show a re-login
+/// dialog instead of silently failing requests.
+///
+/// Logins from other tabs mark the session as active again, after a login in the same tab
+/// call [SessionContext::reset].
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct SessionKeeper {
+    #[prop_or_default]
+    pub children: Html,
+
+    /// Called when the session expired (but not on explicit logouts).
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_expired: Option<Callback<()>>,
+}
+
+impl SessionKeeper {
+    /// Create a new instance.
+    pub fn new(children: impl Into<Html>) -> Self {
+        yew::props!(Self {
+            children: children.into(),
+        })
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    AuthChange(bool), // logout
+    Login,
+    Reset,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxSessionKeeper {
+    state: SessionState,
+    on_reset: Callback<()>,
+    _auth_observer: AuthObserver,
+    _login_observer: LoginObserver,
+}
+
+impl Component for ProxmoxSessionKeeper {
+    type Message = Msg;
+    type Properties = SessionKeeper;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        // make sure the ticket gets renewed, this replaces an already running loop
+        start_ticket_refresh_loop();
+
+        Self {
+            state: SessionState::Active,
+            on_reset: ctx.link().callback(|_| Msg::Reset),
+            _auth_observer: register_auth_observer(ctx.link().callback(Msg::AuthChange)),
+            _login_observer: register_login_observer(ctx.link().callback(|_| Msg::Login)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let state = match msg {
+            Msg::AuthChange(true) => SessionState::LoggedOut,
+            Msg::AuthChange(false) => SessionState::Expired,
+            Msg::Login | Msg::Reset => SessionState::Active,
+        };
+        if state == self.state {
+            return false;
+        }
+        if state == SessionState::Expired {
+            if let Some(on_expired) = &ctx.props().on_expired {
+                on_expired.emit(());
+            }
+        }
+        self.state = state;
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let context = SessionContext {
+            state: self.state,
+            on_reset: self.on_reset.clone(),
+        };
+
+        html! {
+            <ContextProvider<SessionContext> {context}>
+                {ctx.props().children.clone()}
+            </ContextProvider<SessionContext>>
+        }
+    }
+}
+
+impl From<SessionKeeper> for VNode {
+    fn from(val: SessionKeeper) -> Self {
+        let comp = VComp::new::<ProxmoxSessionKeeper>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
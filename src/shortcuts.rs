@@ -0,0 +1,382 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use gloo_events::{EventListener, EventListenerOptions};
+use wasm_bindgen::JsCast;
+
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::{Column, Container, Dialog, Row};
+
+use pwt_macros::builder;
+
+/// A key combination, like `Ctrl+K` or `R`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyCombo {
+    /// The key, as reported by `KeyboardEvent.key` (for example `r`, `?` or `Escape`).
+    pub key: String,
+    /// Control (or the Command key on macOS).
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    /// Builder style method to require the control key.
+    pub fn ctrl(mut self, ctrl: bool) -> Self {
+        self.ctrl = ctrl;
+        self
+    }
+
+    /// Builder style method to require the alt key.
+    pub fn alt(mut self, alt: bool) -> Self {
+        self.alt = alt;
+        self
+    }
+
+    /// Builder style method to require the shift key.
+    pub fn shift(mut self, shift: bool) -> Self {
+        self.shift = shift;
+        self
+    }
+
+    /// Returns true if the combination matches the pressed keys.
+    ///
+    /// Letters are compared case insensitive. The shift state is ignored for other
+    /// single characters, because it is part of the character (`?` is `Shift+/` on
+    /// most layouts).
+    pub fn matches(&self, key: &str, ctrl: bool, alt: bool, shift: bool) -> bool {
+        let mut chars = self.key.chars();
+        let ignore_shift =
+            matches!((chars.next(), chars.next()), (Some(c), None) if !c.is_alphabetic());
+
+        self.key.eq_ignore_ascii_case(key)
+            && self.ctrl == ctrl
+            && self.alt == alt
+            && (ignore_shift || self.shift == shift)
+    }
+
+    /// Text representation for the help dialog, for example `Ctrl+K`.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("Ctrl".to_string());
+        }
+        if self.alt {
+            parts.push("Alt".to_string());
+        }
+        if self.shift {
+            parts.push("Shift".to_string());
+        }
+        parts.push(match self.key.as_str() {
+            " " => "Space".to_string(),
+            key if key.chars().count() == 1 => key.to_uppercase(),
+            key => key.to_string(),
+        });
+        parts.join("+")
+    }
+}
+
+/// A key binding.
+///
+/// The handler returns `true` if the key press was handled. Bindings are tried from the
+/// most recently registered to the oldest, so that nested components (and open
+/// dialogs) take precedence.
+#[derive(Clone, PartialEq)]
+pub struct Shortcut {
+    pub combo: KeyCombo,
+    /// Scope shown in the help dialog, for example "Global" or "Panel".
+    pub scope: AttrValue,
+    pub description: AttrValue,
+    pub handler: Callback<(), bool>,
+}
+
+impl Shortcut {
+    pub fn new(
+        combo: KeyCombo,
+        description: impl Into<AttrValue>,
+        handler: impl Into<Callback<(), bool>>,
+    ) -> Self {
+        Self {
+            combo,
+            scope: tr!("Global").into(),
+            description: description.into(),
+            handler: handler.into(),
+        }
+    }
+
+    /// Builder style method to set the scope.
+    pub fn scope(mut self, scope: impl Into<AttrValue>) -> Self {
+        self.scope = scope.into();
+        self
+    }
+}
+
+#[derive(Default)]
+struct ShortcutsInner {
+    next_id: usize,
+    bindings: Vec<(usize, Shortcut)>,
+}
+
+/// Removes the binding when dropped.
+pub struct ShortcutGuard {
+    inner: Weak<RefCell<ShortcutsInner>>,
+    id: usize,
+}
+
+impl Drop for ShortcutGuard {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.borrow_mut().bindings.retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+/// Keyboard shortcut registry, provided as context by [ShortcutsProvider].
+#[derive(Clone)]
+pub struct Shortcuts {
+    inner: Rc<RefCell<ShortcutsInner>>,
+}
+
+impl PartialEq for Shortcuts {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Shortcuts {
+    /// Register a key binding. The binding is active until the guard is dropped.
+    pub fn register(&self, shortcut: Shortcut) -> ShortcutGuard {
+        let mut inner = self.inner.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.bindings.push((id, shortcut));
+        ShortcutGuard {
+            inner: Rc::downgrade(&self.inner),
+            id,
+        }
+    }
+
+    /// The registered bindings, grouped by scope (without duplicates).
+    pub fn bindings(&self) -> Vec<(AttrValue, Vec<(String, AttrValue)>)> {
+        let mut groups: Vec<(AttrValue, Vec<(String, AttrValue)>)> = Vec::new();
+        for (_, shortcut) in self.inner.borrow().bindings.iter() {
+            let entry = (shortcut.combo.label(), shortcut.description.clone());
+            match groups
+                .iter_mut()
+                .find(|(scope, _)| *scope == shortcut.scope)
+            {
+                Some((_, list)) if list.contains(&entry) => {}
+                Some((_, list)) => list.push(entry),
+                None => groups.push((shortcut.scope.clone(), vec![entry])),
+            }
+        }
+        groups
+    }
+
+    fn dispatch(&self, event: &KeyboardEvent) -> bool {
+        let ctrl = event.ctrl_key() || event.meta_key();
+        let alt = event.alt_key();
+        let key = event.key();
+
+        // do not steal plain keys while the user is typing
+        if !ctrl && !alt && key != "Escape" && is_text_input(event) {
+            return false;
+        }
+
+        let handlers: Vec<Callback<(), bool>> = self
+            .inner
+            .borrow()
+            .bindings
+            .iter()
+            .rev()
+            .filter(|(_, shortcut)| shortcut.combo.matches(&key, ctrl, alt, event.shift_key()))
+            .map(|(_, shortcut)| shortcut.handler.clone())
+            .collect();
+
+        // the handlers may register or remove bindings, so we must not hold the borrow
+        handlers.into_iter().any(|handler| handler.emit(()))
+    }
+}
+
+fn is_text_input(event: &KeyboardEvent) -> bool {
+    let Some(element) = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+    else {
+        return false;
+    };
+    matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        || element.has_attribute("contenteditable")
+}
+
+/// Provides the [Shortcuts] context and the keyboard shortcuts help dialog (`?`).
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct ShortcutsProvider {
+    #[prop_or_default]
+    pub children: Html,
+}
+
+impl ShortcutsProvider {
+    /// Create a new instance.
+    pub fn new(children: impl Into<Html>) -> Self {
+        yew::props!(Self {
+            children: children.into(),
+        })
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    ShowHelp(bool),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxShortcutsProvider {
+    shortcuts: Shortcuts,
+    show_help: bool,
+    _help_shortcut: ShortcutGuard,
+    _listener: EventListener,
+}
+
+impl ProxmoxShortcutsProvider {
+    fn help_dialog(&self, ctx: &Context<Self>) -> Html {
+        let mut panel = Column::new().padding(2).gap(1).style("min-width", "350px");
+
+        for (scope, bindings) in self.shortcuts.bindings() {
+            panel.add_child(
+                Container::new()
+                    .padding_top(2)
+                    .class("pwt-font-title-small")
+                    .with_child(scope),
+            );
+            for (label, description) in bindings {
+                panel.add_child(
+                    Row::new()
+                        .gap(4)
+                        .with_child(
+                            Container::new()
+                                .class("pwt-font-monospace")
+                                .style("min-width", "100px")
+                                .with_child(label),
+                        )
+                        .with_child(description),
+                );
+            }
+        }
+
+        Dialog::new(tr!("Keyboard Shortcuts"))
+            .on_close(ctx.link().callback(|_| Msg::ShowHelp(false)))
+            .with_child(panel)
+            .into()
+    }
+}
+
+impl Component for ProxmoxShortcutsProvider {
+    type Message = Msg;
+    type Properties = ShortcutsProvider;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let shortcuts = Shortcuts {
+            inner: Rc::new(RefCell::new(ShortcutsInner::default())),
+        };
+
+        let help_shortcut = shortcuts.register(Shortcut::new(
+            KeyCombo::new("?"),
+            tr!("Show keyboard shortcuts"),
+            {
+                let link = ctx.link().clone();
+                move |_| {
+                    link.send_message(Msg::ShowHelp(true));
+                    true
+                }
+            },
+        ));
+
+        let listener = EventListener::new_with_options(
+            &gloo_utils::document(),
+            "keydown",
+            EventListenerOptions::enable_prevent_default(),
+            {
+                let shortcuts = shortcuts.clone();
+                move |event| {
+                    let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                        return;
+                    };
+                    // already handled by a widget (for example a dialog or menu)
+                    if event.default_prevented() {
+                        return;
+                    }
+                    if shortcuts.dispatch(event) {
+                        event.prevent_default();
+                    }
+                }
+            },
+        );
+
+        Self {
+            shortcuts,
+            show_help: false,
+            _help_shortcut: help_shortcut,
+            _listener: listener,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ShowHelp(show) => {
+                let changed = self.show_help != show;
+                self.show_help = show;
+                changed
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let help = self.show_help.then(|| self.help_dialog(ctx));
+        html! {
+            <ContextProvider<Shortcuts> context={self.shortcuts.clone()}>
+                {ctx.props().children.clone()}
+                {help}
+            </ContextProvider<Shortcuts>>
+        }
+    }
+}
+
+impl From<ShortcutsProvider> for VNode {
+    fn from(val: ShortcutsProvider) -> Self {
+        let comp = VComp::new::<ProxmoxShortcutsProvider>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_key_combo() {
+        let reload = KeyCombo::new("r");
+        assert!(reload.matches("r", false, false, false));
+        assert!(reload.matches("R", false, false, false));
+        assert!(!reload.matches("R", false, false, true));
+        assert!(!reload.matches("r", true, false, false));
+
+        // shift is part of the character
+        assert!(KeyCombo::new("?").matches("?", false, false, true));
+
+        assert_eq!(KeyCombo::new("k").ctrl(true).label(), "Ctrl+K");
+        assert_eq!(KeyCombo::new("Escape").label(), "Escape");
+    }
+}
@@ -1,7 +1,7 @@
 use pwt::prelude::*;
 use pwt::{
     css::{FontColor, Opacity},
-    widget::Fa,
+    widget::{Fa, Row, Tooltip},
 };
 
 /// Used to represent a Status of some resource or component, e.g., if a PVE node is online or not.
@@ -53,9 +53,12 @@ impl From<Status> for Fa {
 
 /// Used to represent the state of a Node, being PVE or PBS
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum NodeState {
     Online,
     Offline,
+    /// Online, but in maintenance mode.
+    Maintenance,
     Unknown,
 }
 
@@ -70,9 +73,24 @@ impl NodeState {
         match self {
             NodeState::Online => ("check-circle", FontColor::Success),
             NodeState::Offline => ("times-circle", FontColor::Error),
+            NodeState::Maintenance => ("wrench", FontColor::Warning),
             NodeState::Unknown => ("question-circle", FontColor::Surface),
         }
     }
+
+    pub fn text(&self) -> String {
+        match self {
+            NodeState::Online => tr!("Online"),
+            NodeState::Offline => tr!("Offline"),
+            NodeState::Maintenance => tr!("Maintenance mode"),
+            NodeState::Unknown => tr!("Unknown"),
+        }
+    }
+
+    /// Icon and text.
+    pub fn render(&self) -> Html {
+        render_icon_text((*self).into(), self.text())
+    }
 }
 
 impl From<NodeState> for Fa {
@@ -108,6 +126,34 @@ impl GuestState {
             GuestState::Unknown => ("question-circle", Opacity::Quarter.into()),
         }
     }
+
+    /// Map the `status` and `template` properties returned by the API.
+    pub fn from_status(status: &str, template: bool) -> Self {
+        if template {
+            return GuestState::Template;
+        }
+        match status {
+            "running" => GuestState::Running,
+            "paused" | "suspended" => GuestState::Paused,
+            "stopped" => GuestState::Stopped,
+            _ => GuestState::Unknown,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            GuestState::Running => tr!("Running"),
+            GuestState::Paused => tr!("Paused"),
+            GuestState::Stopped => tr!("Stopped"),
+            GuestState::Template => tr!("Template"),
+            GuestState::Unknown => tr!("Unknown"),
+        }
+    }
+
+    /// Icon and text.
+    pub fn render(&self) -> Html {
+        render_icon_text((*self).into(), self.text())
+    }
 }
 
 impl From<GuestState> for Fa {
@@ -147,3 +193,168 @@ impl From<StorageState> for Fa {
         Fa::new(icon).class(class)
     }
 }
+
+/// The state of a HA managed resource.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum HaState {
+    Started,
+    Stopped,
+    /// Migrating or relocating to another node.
+    Migrating,
+    /// Frozen, for example while the node shuts down.
+    Frozen,
+    Error,
+    Unknown,
+}
+
+impl HaState {
+    /// Map the HA state returned by the API (for example the `hastate` of a guest).
+    pub fn from_state(state: &str) -> Self {
+        match state {
+            "started" => HaState::Started,
+            "stopped" | "disabled" | "ignored" | "request_stop" => HaState::Stopped,
+            "migrate" | "relocate" => HaState::Migrating,
+            "freeze" => HaState::Frozen,
+            "error" | "fence" | "recovery" => HaState::Error,
+            _ => HaState::Unknown,
+        }
+    }
+
+    fn get_icon_classes(&self) -> (&str, Classes) {
+        match self {
+            HaState::Started => ("heartbeat", FontColor::Success.into()),
+            HaState::Stopped => ("heartbeat", Opacity::Quarter.into()),
+            HaState::Migrating => ("paper-plane", FontColor::Warning.into()),
+            HaState::Frozen => ("snowflake-o", FontColor::Warning.into()),
+            HaState::Error => ("heartbeat", FontColor::Error.into()),
+            HaState::Unknown => ("question-circle", Opacity::Quarter.into()),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            HaState::Started => tr!("HA started"),
+            HaState::Stopped => tr!("HA stopped"),
+            HaState::Migrating => tr!("HA migrating"),
+            HaState::Frozen => tr!("HA frozen"),
+            HaState::Error => tr!("HA error"),
+            HaState::Unknown => tr!("Unknown"),
+        }
+    }
+
+    /// Icon and text.
+    pub fn render(&self) -> Html {
+        render_icon_text((*self).into(), self.text())
+    }
+}
+
+impl From<HaState> for Fa {
+    fn from(value: HaState) -> Self {
+        let (icon, class) = value.get_icon_classes();
+        Fa::new(icon).class(class)
+    }
+}
+
+/// The lock of a guest, if an operation is in progress.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum GuestLock {
+    Backup,
+    Migrate,
+    Snapshot,
+    Other(String),
+}
+
+impl GuestLock {
+    /// Map the `lock` property returned by the API.
+    pub fn from_lock(lock: &str) -> Self {
+        match lock {
+            "backup" => GuestLock::Backup,
+            "migrate" => GuestLock::Migrate,
+            "snapshot" | "snapshot-delete" | "rollback" => GuestLock::Snapshot,
+            other => GuestLock::Other(other.to_string()),
+        }
+    }
+
+    fn get_icon(&self) -> &str {
+        match self {
+            GuestLock::Backup => "floppy-o",
+            GuestLock::Migrate => "paper-plane",
+            GuestLock::Snapshot => "history",
+            GuestLock::Other(_) => "lock",
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            GuestLock::Backup => tr!("Locked (backup)"),
+            GuestLock::Migrate => tr!("Locked (migrate)"),
+            GuestLock::Snapshot => tr!("Locked (snapshot)"),
+            GuestLock::Other(lock) => tr!("Locked ({0})", lock),
+        }
+    }
+
+    /// Icon and text.
+    pub fn render(&self) -> Html {
+        render_icon_text(self.into(), self.text())
+    }
+}
+
+impl From<&GuestLock> for Fa {
+    fn from(value: &GuestLock) -> Self {
+        Fa::new(value.get_icon()).class(FontColor::Warning)
+    }
+}
+
+fn render_icon_text(icon: Fa, text: String) -> Html {
+    Row::new()
+        .class(pwt::css::AlignItems::Baseline)
+        .gap(1)
+        .with_child(icon)
+        .with_child(text)
+        .into()
+}
+
+/// Render the status icons of a guest: the state, followed by the HA state and lock,
+/// if any. The texts are shown as tooltips.
+pub fn render_guest_status_icons(
+    state: GuestState,
+    ha_state: Option<HaState>,
+    lock: Option<&GuestLock>,
+) -> Html {
+    let icon = |icon: Fa, text: String| Tooltip::new(icon).tip(text);
+
+    Row::new()
+        .class(pwt::css::AlignItems::Center)
+        .gap(1)
+        .with_child(icon(state.into(), state.text()))
+        .with_optional_child(ha_state.map(|ha_state| icon(ha_state.into(), ha_state.text())))
+        .with_optional_child(lock.map(|lock| icon(lock.into(), lock.text())))
+        .into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_guest_status_mapping() {
+        assert_eq!(
+            GuestState::from_status("running", false),
+            GuestState::Running
+        );
+        assert_eq!(
+            GuestState::from_status("stopped", true),
+            GuestState::Template
+        );
+        assert_eq!(HaState::from_state("fence"), HaState::Error);
+        assert_eq!(HaState::from_state("started"), HaState::Started);
+        assert_eq!(HaState::from_state("relocate"), HaState::Migrating);
+        assert_eq!(HaState::from_state("freeze"), HaState::Frozen);
+        assert_eq!(GuestLock::from_lock("rollback"), GuestLock::Snapshot);
+        assert_eq!(
+            GuestLock::from_lock("create"),
+            GuestLock::Other("create".into())
+        );
+    }
+}
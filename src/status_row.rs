@@ -1,10 +1,14 @@
 use pwt::css::Display;
 
+use yew::html::IntoEventCallback;
+
 use pwt::prelude::*;
 use pwt::widget::{Container, Row};
 
 use pwt_macros::widget;
 
+use crate::utils::activatable;
+
 #[widget(comp=ProxmoxStatusRow, @element)]
 #[derive(Properties, Clone, PartialEq)]
 pub struct StatusRow {
@@ -18,6 +22,10 @@ pub struct StatusRow {
 
     #[prop_or_default]
     pub icon_right: bool,
+
+    /// Activation callback, called when the row is clicked or activated by keyboard.
+    #[prop_or_default]
+    pub on_activate: Option<Callback<()>>,
 }
 
 impl StatusRow {
@@ -55,6 +63,12 @@ impl StatusRow {
         self.set_icon_right(icon_right);
         self
     }
+
+    /// Builder style method to set the activation callback.
+    pub fn on_activate(mut self, on_activate: impl IntoEventCallback<()>) -> Self {
+        self.on_activate = on_activate.into_event_callback();
+        self
+    }
 }
 
 #[doc(hidden)]
@@ -88,7 +102,7 @@ impl Component for ProxmoxStatusRow {
             None => html! {"-"},
         };
 
-        Row::new()
+        let row = Row::new()
             .with_std_props(&props.std_props)
             .class(Display::Flex) // we need to set this again
             .listeners(&props.listeners)
@@ -97,7 +111,8 @@ impl Component for ProxmoxStatusRow {
             .with_child(html! {<div class="pwt-white-space-nowrap">{props.title.clone()}</div>})
             .with_flex_spacer()
             .with_child(html! {<div class="pwt-white-space-nowrap">{status}</div>})
-            .with_optional_child(right_icon)
-            .into()
+            .with_optional_child(right_icon);
+
+        activatable(row, props.on_activate.as_ref()).into()
     }
 }
@@ -0,0 +1,439 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_events::EventListener;
+use serde_json::Value;
+
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::css::{ColorScheme, FlexFit};
+use pwt::prelude::*;
+use pwt::widget::{Column, Container, Panel, Row};
+use pwt_macros::builder;
+
+use proxmox_human_byte::HumanByte;
+
+use crate::pve_api_types::StorageEntry;
+use crate::rrd_value_renderer::render_bytes;
+use crate::{
+    http_get, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    LoadableComponentScopeExt, LoadableComponentState, MeterLabel, RRDGraph, RRDTimeframe,
+    RRDTimeframeSelector, RrdData, RrdDataSource, StatusRow,
+};
+
+/// Storage status as returned by the `status` API endpoint.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StorageStatus {
+    pub ty: String,
+    pub active: bool,
+    pub enabled: bool,
+    pub shared: bool,
+    pub total: u64,
+    pub used: u64,
+    pub avail: u64,
+    pub content: Vec<String>,
+}
+
+// the API returns booleans as integers
+fn parse_bool(value: &Value) -> bool {
+    value
+        .as_bool()
+        .or_else(|| value.as_u64().map(|v| v != 0))
+        .unwrap_or(false)
+}
+
+impl StorageStatus {
+    /// Parse the data returned by the API.
+    pub fn from_value(data: &Value) -> Self {
+        Self {
+            ty: data["type"].as_str().unwrap_or("").to_string(),
+            active: parse_bool(&data["active"]),
+            enabled: data.get("enabled").map(parse_bool).unwrap_or(true),
+            shared: parse_bool(&data["shared"]),
+            total: data["total"].as_u64().unwrap_or(0),
+            used: data["used"].as_u64().unwrap_or(0),
+            avail: data["avail"].as_u64().unwrap_or(0),
+            content: data["content"]
+                .as_str()
+                .unwrap_or("")
+                .split(',')
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// Display text for a storage content type.
+pub fn storage_content_text(content: &str) -> String {
+    match content {
+        "images" => tr!("Disk image"),
+        "rootdir" => tr!("Container"),
+        "vztmpl" => tr!("Container template"),
+        "iso" => tr!("ISO image"),
+        "backup" => tr!("Backup"),
+        "snippets" => tr!("Snippets"),
+        "import" => tr!("Import"),
+        _ => content.to_string(),
+    }
+}
+
+/// Sum up the used space per content type, sorted by usage (largest first).
+///
+/// Thin provisioned volumes only count with their used space.
+fn content_usage(entries: &[StorageEntry]) -> Vec<(String, u64)> {
+    let mut usage: BTreeMap<&str, u64> = BTreeMap::new();
+    for entry in entries {
+        let used = entry.used.unwrap_or(entry.size);
+        *usage.entry(entry.content.as_str()).or_default() += used.max(0) as u64;
+    }
+    let mut usage: Vec<(String, u64)> = usage
+        .into_iter()
+        .map(|(content, size)| (content.to_string(), size))
+        .collect();
+    usage.sort_by(|a, b| b.1.cmp(&a.1));
+    usage
+}
+
+/// Storage status panel with usage graph.
+///
+/// Loads the `status`, `content` and `rrddata` endpoints below `base_url`, for example
+/// `/nodes/{node}/storage/{storage}`.
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct StorageStatusPanel {
+    /// The URL path of the storage.
+    pub base_url: AttrValue,
+
+    /// Show the space used per content type.
+    ///
+    /// This lists the whole storage content, so it is only reloaded every few minutes.
+    #[builder]
+    #[prop_or_default]
+    pub content_breakdown: bool,
+
+    /// Reload interval in milliseconds.
+    #[builder]
+    #[prop_or(10_000)]
+    pub interval: u32,
+}
+
+impl StorageStatusPanel {
+    /// Create a new instance.
+    pub fn new(base_url: impl Into<AttrValue>) -> Self {
+        yew::props!(Self {
+            base_url: base_url.into(),
+        })
+    }
+}
+
+// listing the storage content can be expensive, so reload it less often (milliseconds)
+const CONTENT_RELOAD_INTERVAL: f64 = 5.0 * 60.0 * 1000.0;
+
+pub enum Msg {
+    Loaded(StorageStatus, RrdData),
+    ContentLoaded(Option<Vec<(String, u64)>>),
+    TimeframeChange(RRDTimeframe),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxStorageStatusPanel {
+    state: LoadableComponentState<()>,
+    status: Option<StorageStatus>,
+    content: Option<Vec<(String, u64)>>,
+    // time of the last content load
+    content_loaded: Rc<Cell<Option<f64>>>,
+    rrd_data: RrdData,
+    timeframe: RRDTimeframe,
+    _timeframe_listener: EventListener,
+}
+
+pwt::impl_deref_mut_property!(ProxmoxStorageStatusPanel, state, LoadableComponentState<()>);
+
+impl ProxmoxStorageStatusPanel {
+    fn status_view(&self, status: &StorageStatus) -> Html {
+        let yes_no = |v: bool| if v { tr!("Yes") } else { tr!("No") };
+
+        let fraction = (status.total > 0).then(|| status.used as f64 / status.total as f64);
+
+        let content = status
+            .content
+            .iter()
+            .map(|c| storage_content_text(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Container::new()
+            .class("pwt-d-grid pwt-gap-2 pwt-align-items-center")
+            .style("grid-template-columns", "1fr 20px 1fr")
+            .style("height", "fit-content")
+            .padding(4)
+            .with_child(
+                StatusRow::new(tr!("Enabled"))
+                    .icon_class("fa fa-fw fa-toggle-on")
+                    .status(yes_no(status.enabled)),
+            )
+            .with_child(
+                StatusRow::new(tr!("Active"))
+                    .style("grid-column", "3")
+                    .icon_class(classes!(
+                        "fa",
+                        "fa-fw",
+                        "fa-check-circle",
+                        (!status.active).then_some("pwt-color-error")
+                    ))
+                    .status(yes_no(status.active)),
+            )
+            .with_child(
+                StatusRow::new(tr!("Type"))
+                    .icon_class("fa fa-fw fa-database")
+                    .status(status.ty.clone()),
+            )
+            .with_child(
+                StatusRow::new(tr!("Shared"))
+                    .style("grid-column", "3")
+                    .icon_class("fa fa-fw fa-share-alt")
+                    .status(yes_no(status.shared)),
+            )
+            .with_child(
+                StatusRow::new(tr!("Content"))
+                    .style("grid-column", "1/-1")
+                    .icon_class("fa fa-fw fa-th")
+                    .status(content),
+            )
+            .with_child(Container::new().padding(2).style("grid-column", "1/-1"))
+            .with_child(
+                MeterLabel::with_zero_optimum(tr!("Usage"))
+                    .style("grid-column", "1/-1")
+                    .animated(true)
+                    .icon_class("fa fa-fw fa-hdd-o")
+                    .value(fraction.map(|f| f as f32))
+                    .status(match fraction {
+                        Some(fraction) => format!(
+                            "{:.2}% ({} of {}, {} available)",
+                            fraction * 100.0,
+                            HumanByte::from(status.used),
+                            HumanByte::from(status.total),
+                            HumanByte::from(status.avail),
+                        ),
+                        None => tr!("N/A"),
+                    }),
+            )
+            .into()
+    }
+
+    fn content_view(&self, status: &StorageStatus, content: &[(String, u64)]) -> Html {
+        let colors = [
+            ColorScheme::Primary,
+            ColorScheme::Secondary,
+            ColorScheme::Tertiary,
+            ColorScheme::Success,
+            ColorScheme::Warning,
+            ColorScheme::Neutral,
+        ];
+
+        let total = status.total.max(1) as f64;
+
+        let mut bar = Row::new()
+            .class("pwt-border pwt-overflow-hidden")
+            .style("height", "1.5em")
+            .style("border-radius", "var(--pwt-border-radius, 4px)");
+        let mut legend = Row::new().gap(4).style("flex-wrap", "wrap");
+
+        for (idx, (ty, size)) in content.iter().enumerate() {
+            let color = colors[idx % colors.len()];
+            let percent = (*size as f64 * 100.0 / total).min(100.0);
+            let text = format!("{}: {}", storage_content_text(ty), HumanByte::from(*size));
+            bar.add_child(
+                Container::new()
+                    .class(color)
+                    .style("width", format!("{percent:.2}%"))
+                    .attribute("title", text.clone()),
+            );
+            legend.add_child(
+                Row::new()
+                    .gap(1)
+                    .class(pwt::css::AlignItems::Center)
+                    .with_child(
+                        Container::new()
+                            .class(color)
+                            .style("width", "1em")
+                            .style("height", "1em"),
+                    )
+                    .with_child(text),
+            );
+        }
+
+        Column::new()
+            .padding_x(4)
+            .gap(2)
+            .with_child(
+                html! {<div class="pwt-font-title-small">{tr!("Usage by content type")}</div>},
+            )
+            .with_child(bar)
+            .with_child(legend)
+            .into()
+    }
+
+    fn graph_view(&self) -> Html {
+        let data = &self.rrd_data;
+        RRDGraph::new(data.time_data.clone())
+            .title(tr!("Usage"))
+            .binary(true)
+            .render_value(render_bytes)
+            .series(
+                ["total", "used"]
+                    .into_iter()
+                    .filter_map(|field| data.get(field))
+                    .collect(),
+            )
+            .into()
+    }
+}
+
+impl LoadableComponent for ProxmoxStorageStatusPanel {
+    type Message = Msg;
+    type ViewState = ();
+    type Properties = StorageStatusPanel;
+
+    fn create(ctx: &LoadableComponentContext<Self>) -> Self {
+        ctx.link().repeated_load(ctx.props().interval);
+        Self {
+            state: LoadableComponentState::new(),
+            status: None,
+            content: None,
+            content_loaded: Rc::new(Cell::new(None)),
+            rrd_data: RrdData::default(),
+            timeframe: RRDTimeframe::load(),
+            _timeframe_listener: RRDTimeframe::observe(ctx.link().callback(Msg::TimeframeChange)),
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &LoadableComponentContext<Self>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+        let props = ctx.props();
+        let base_url = props.base_url.clone();
+        let now = js_sys::Date::now();
+        let load_content = props.content_breakdown
+            && self
+                .content_loaded
+                .get()
+                .is_none_or(|last| now - last >= CONTENT_RELOAD_INTERVAL);
+        if load_content {
+            self.content_loaded.set(Some(now));
+        }
+        let timeframe = self.timeframe;
+        let link = ctx.link().clone();
+
+        Box::pin(async move {
+            let status: Value = http_get(format!("{base_url}/status"), None).await?;
+            let status = StorageStatus::from_value(&status);
+
+            // listing the content needs more privileges, so simply omit the breakdown
+            if load_content {
+                let content = http_get::<Vec<StorageEntry>>(format!("{base_url}/content"), None)
+                    .await
+                    .ok()
+                    .map(|entries| content_usage(&entries));
+                link.send_message(Msg::ContentLoaded(content));
+            }
+
+            let rrd_data = RrdDataSource::new(format!("{base_url}/rrddata"))
+                .field("total", tr!("Total"))
+                .field("used", tr!("Used"))
+                .load(timeframe)
+                .await?;
+
+            link.send_message(Msg::Loaded(status, rrd_data));
+            Ok(())
+        })
+    }
+
+    fn update(&mut self, ctx: &LoadableComponentContext<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Loaded(status, rrd_data) => {
+                self.status = Some(status);
+                self.rrd_data = rrd_data;
+            }
+            Msg::ContentLoaded(content) => {
+                self.content = content;
+            }
+            Msg::TimeframeChange(timeframe) => {
+                self.timeframe = timeframe;
+                ctx.link().send_reload();
+            }
+        }
+        true
+    }
+
+    fn changed(
+        &mut self,
+        ctx: &LoadableComponentContext<Self>,
+        old_props: &Self::Properties,
+    ) -> bool {
+        let props = ctx.props();
+        if props.base_url != old_props.base_url
+            || props.content_breakdown != old_props.content_breakdown
+        {
+            self.content = None;
+            self.content_loaded.set(None);
+            ctx.link().send_reload();
+        }
+        true
+    }
+
+    fn main_view(&self, _ctx: &LoadableComponentContext<Self>) -> Html {
+        let mut panel = Panel::new()
+            .border(false)
+            .class(FlexFit)
+            .class("pwt-overflow-auto")
+            .title(tr!("Storage Status"))
+            .with_tool(RRDTimeframeSelector::new());
+
+        if let Some(status) = &self.status {
+            panel.add_child(self.status_view(status));
+            if let Some(content) = self.content.as_ref().filter(|c| !c.is_empty()) {
+                panel.add_child(self.content_view(status, content));
+            }
+        }
+
+        panel
+            .with_child(Container::new().padding(4).with_child(self.graph_view()))
+            .into()
+    }
+}
+
+impl From<StorageStatusPanel> for VNode {
+    fn from(value: StorageStatusPanel) -> Self {
+        VComp::new::<LoadableComponentMaster<ProxmoxStorageStatusPanel>>(Rc::new(value), None)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_storage_status() {
+        let status = StorageStatus::from_value(&json!({
+            "type": "dir",
+            "active": 1,
+            "shared": 0,
+            "total": 100,
+            "used": 40,
+            "avail": 60,
+            "content": "iso,images",
+        }));
+        assert!(status.active && status.enabled && !status.shared);
+        assert_eq!(status.content, ["iso", "images"]);
+    }
+}
@@ -12,6 +12,7 @@ use pwt::widget::{Column, Fa, Panel, Row};
 
 use pwt_macros::builder;
 
+use crate::utils::activatable;
 use crate::{HelpButton, ProjectInfo};
 
 pub fn subscription_status_text(status: &str) -> String {
@@ -75,6 +76,11 @@ pub struct SubscriptionInfo {
     #[builder_cb(IntoEventCallback, into_event_callback, String)]
     #[prop_or_default]
     pub on_status_change: Option<Callback<String>>,
+
+    /// Activation callback, for example to open the subscription panel.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_activate: Option<Callback<()>>,
 }
 
 impl SubscriptionInfo {
@@ -124,17 +130,19 @@ impl Component for ProxmoxSubscriptionInfo {
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        let main_view = self.loader.render(|data| {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_activate = ctx.props().on_activate.clone();
+        let main_view = self.loader.render(move |data| {
             let status = data["status"].as_str().unwrap_or("").to_owned();
             let url = data["url"].as_str();
             let msg = subscription_status_message(&status, url);
-            Row::new()
+            let row = Row::new()
                 .padding(2)
                 .class(Flex::Fill)
                 .class(AlignItems::Center)
                 .with_child(subscription_icon(&status).large_3x().padding(6))
-                .with_child(msg)
+                .with_child(msg);
+            activatable(row, on_activate.as_ref())
         });
 
         Panel::new()
@@ -3,15 +3,17 @@ use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
 
+use anyhow::{bail, Error};
+use regex::Regex;
 use serde_json::{json, Value};
 
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::widget::form::{Field, FormContext};
-use pwt::widget::{Button, Container, InputPanel, Toolbar};
+use pwt::widget::{Button, Column, Container, InputPanel, Toolbar};
 
-use crate::utils::render_epoch;
+use crate::utils::{copy_text_to_clipboard, render_boolean_badge, render_epoch};
 use crate::{
     ConfirmButton, DataViewWindow, EditWindow, KVGrid, KVGridRow, LoadableComponentState,
     ProjectInfo,
@@ -39,10 +41,28 @@ impl SubscriptionPanel {
 
 #[derive(PartialEq)]
 pub enum ViewState {
-    UploadSubscriptionKey,
+    SetSubscriptionKey,
     SystemReport,
 }
 
+/// Validate the format of a subscription key, e.g. `pve4c-0123456789` or `pbsc-0123456789`.
+fn validate_subscription_key(key: &str) -> Result<(), Error> {
+    thread_local! {
+        static KEY_MATCH: Regex = Regex::new(r#"^[a-z]{3}\d?[cbsp]-[0-9a-f]{10}$"#).unwrap();
+    }
+    if !KEY_MATCH.with(|r| r.is_match(key.trim())) {
+        bail!(tr!("Invalid subscription key format."));
+    }
+    Ok(())
+}
+
+/// Upload the subscription key and check it against the shop server.
+async fn set_subscription_key(base_url: String, data: Value) -> Result<(), Error> {
+    let key = data["key"].as_str().unwrap_or("").trim();
+    crate::http_put(&base_url, Some(json!({ "key": key }))).await?;
+    crate::http_post(base_url, Some(json!({"force": true}))).await
+}
+
 pub struct ProxmoxSubscriptionPanel {
     state: LoadableComponentState<ViewState>,
     rows: Rc<Vec<KVGridRow>>,
@@ -85,11 +105,11 @@ impl LoadableComponent for ProxmoxSubscriptionPanel {
         let toolbar = Toolbar::new()
             .class("pwt-overflow-hidden")
             .with_child(
-                Button::new(tr!("Upload Subscription Key"))
+                Button::new(tr!("Set Subscription Key"))
                     .icon_class("fa fa-ticket")
                     .onclick(
                         ctx.link()
-                            .change_view_callback(|_| Some(ViewState::UploadSubscriptionKey)),
+                            .change_view_callback(|_| Some(ViewState::SetSubscriptionKey)),
                     ),
             )
             .with_child(
@@ -132,7 +152,7 @@ impl LoadableComponent for ProxmoxSubscriptionPanel {
                                 let base_url = base_url.clone();
                                 async move {
                                     match crate::http_delete(base_url, None).await {
-                                        Ok(()) => link.change_view(None),
+                                        Ok(()) => link.send_reload(),
                                         Err(err) => {
                                             link.show_error(tr!("Error"), err.to_string(), true)
                                         }
@@ -175,7 +195,7 @@ impl LoadableComponent for ProxmoxSubscriptionPanel {
         view_state: &Self::ViewState,
     ) -> Option<Html> {
         match view_state {
-            ViewState::UploadSubscriptionKey => Some(self.create_upload_subscription_dialog(ctx)),
+            ViewState::SetSubscriptionKey => Some(self.create_set_subscription_dialog(ctx)),
             ViewState::SystemReport => Some(self.create_system_report_dialog(ctx)),
         }
     }
@@ -191,8 +211,6 @@ impl From<SubscriptionPanel> for VNode {
 
 fn rows() -> Vec<KVGridRow> {
     let unknown_text = tr!("unknown");
-    let yes_text = tr!("Yes");
-    let no_text = tr!("No");
     vec![
         KVGridRow::new("productname", tr!("Type")),
         KVGridRow::new("key", tr!("Subscription Key")),
@@ -213,12 +231,8 @@ fn rows() -> Vec<KVGridRow> {
             }
         }),
         KVGridRow::new("nextduedata", tr!("Next due date")),
-        KVGridRow::new("signature", tr!("Signed/Offline")).renderer(
-            move |_name, value, _record| match value.as_str() {
-                Some(_) => html! {&yes_text},
-                _ => html! {&no_text},
-            },
-        ),
+        KVGridRow::new("signature", tr!("Signed/Offline"))
+            .renderer(|_name, value, _record| render_boolean_badge(Some(value.as_str().is_some()))),
         KVGridRow::new("url", tr!("Info URL")).renderer(|_name, value, _record| {
             let url = value.as_str().unwrap().to_string();
             html! { <a target="_blank" href={url.clone()}>{url}</a> }
@@ -233,41 +247,73 @@ impl ProxmoxSubscriptionPanel {
             .height(600)
             .loader("/nodes/localhost/report")
             .renderer(|report: &String| {
-                Container::from_tag("pre")
-                    .class("pwt-flex-fit pwt-font-monospace")
-                    .padding(2)
-                    .style("line-height", "normal")
-                    .with_child(report)
+                let text = report.clone();
+                Column::new()
+                    .class("pwt-flex-fit")
+                    .with_child(
+                        Toolbar::new().class("pwt-border-bottom").with_child(
+                            Button::new(tr!("Copy"))
+                                .icon_class("fa fa-clipboard")
+                                .on_activate(move |_| copy_text_to_clipboard(&text)),
+                        ),
+                    )
+                    .with_child(
+                        Container::from_tag("pre")
+                            .class("pwt-flex-fit pwt-font-monospace")
+                            .padding(2)
+                            .style("line-height", "normal")
+                            .with_child(report),
+                    )
                     .into()
             })
             .on_done(ctx.link().change_view_callback(|_| None))
             .into()
     }
 
-    fn create_upload_subscription_dialog(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+    fn create_set_subscription_dialog(&self, ctx: &LoadableComponentContext<Self>) -> Html {
         let input_panel = |_form_state: &FormContext| -> Html {
             InputPanel::new()
                 .padding(4)
+                .min_width(400)
                 .with_field(
                     tr!("Subscription Key"),
-                    Field::new().name("key").required(true).autofocus(true),
+                    Field::new()
+                        .name("key")
+                        .required(true)
+                        .autofocus(true)
+                        .validate(|key: &String| validate_subscription_key(key)),
                 )
                 .into()
         };
 
-        EditWindow::new(tr!("Upload Subscription Key"))
+        EditWindow::new(tr!("Set Subscription Key"))
             .renderer(input_panel)
             .on_submit({
                 let base_url = ctx.props().base_url.to_string();
                 move |form_state: FormContext| {
-                    let base_url = base_url.clone();
-                    async move {
-                        let data = form_state.get_submit_data();
-                        crate::http_put(base_url, Some(data)).await
-                    }
+                    set_subscription_key(base_url.clone(), form_state.get_submit_data())
+                }
+            })
+            .on_done({
+                let link = ctx.link().clone();
+                move |_| {
+                    link.change_view(None);
+                    link.send_reload();
                 }
             })
-            .on_done(ctx.link().change_view_callback(|_| None))
             .into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_subscription_key() {
+        assert!(validate_subscription_key("pve4c-0123456789").is_ok());
+        assert!(validate_subscription_key("pbsc-abcdef0123").is_ok());
+        assert!(validate_subscription_key("pve4c-012345678").is_err());
+        assert!(validate_subscription_key("invalid").is_err());
+    }
+}
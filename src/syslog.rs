@@ -1,6 +1,8 @@
 use std::rc::Rc;
 
-use pwt::widget::form::{DateField, InputType, PlainDate};
+use gloo_timers::callback::Timeout;
+
+use pwt::widget::form::{Combobox, DateField, InputType, PlainDate};
 use pwt::widget::{Button, Container, Row, SegmentedButton};
 use yew::html::IntoPropValue;
 use yew::virtual_dom::{VComp, VNode};
@@ -8,10 +10,13 @@ use yew::virtual_dom::{VComp, VNode};
 use pwt::prelude::*;
 use pwt::widget::{form::Field, Column, Toolbar};
 
-use crate::{JournalView, LogView};
+use crate::{JournalView, LogView, ServiceEntry};
 
 use pwt_macros::builder;
 
+/// debounce before the typed service filter reloads the log
+const SERVICE_FILTER_DEBOUNCE_MS: u32 = 400;
+
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
 pub struct Syslog {
@@ -30,6 +35,11 @@ pub struct Syslog {
     #[prop_or_default]
     pub service: Option<AttrValue>,
 
+    /// Services API, used to offer the services in the service filter.
+    #[prop_or(Some("/nodes/localhost/services".into()))]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub services_url: Option<AttrValue>,
+
     /// Render the live journal in structured mode: priority coloring and the filter row. Needs a
     /// backend that serves the journal endpoint's structured output.
     #[builder]
@@ -57,6 +67,9 @@ pub enum Msg {
     SinceTime(String),
     UntilDate(Option<PlainDate>),
     UntilTime(String),
+    ServiceFilter(Option<String>),
+    ApplyServiceFilter,
+    ServicesLoaded(Vec<AttrValue>),
 }
 
 pub struct ProxmoxSyslog {
@@ -69,9 +82,15 @@ pub struct ProxmoxSyslog {
     until_label_id: AttrValue,
     pending: bool,
     show_filters: bool,
+    /// the service filter applied to the syslog view
+    service: Option<String>,
+    /// the service filter as typed, applied after a typing pause
+    service_input: Option<String>,
+    service_timeout: Option<Timeout>,
+    services: Rc<Vec<AttrValue>>,
 }
 
-fn date_time_to_epoch(date: &PlainDate, time: &str) -> Option<i64> {
+pub(crate) fn date_time_to_epoch(date: &PlainDate, time: &str) -> Option<i64> {
     let (hours, minutes) = time.split_once(':')?;
     let d = date.to_date();
     d.set_hours(hours.parse().ok()?);
@@ -167,6 +186,27 @@ impl ProxmoxSyslog {
                         .value(self.until_time.to_string()),
                 ),
             )
+            .with_child(
+                Container::from_tag("label")
+                    .padding_start(2)
+                    .class("pwt-align-self-center")
+                    .class(self.active.then_some("pwt-label-disabled"))
+                    .with_child(tr!("Service") + ":"),
+            )
+            .with_child(
+                // the syslog API filters by service server-side, the live journal has its own
+                // unit filter in the structured filter row
+                Combobox::new()
+                    .editable(true)
+                    .width(200)
+                    .disabled(self.active)
+                    .placeholder(tr!("All"))
+                    .items(self.services.clone())
+                    .value(self.service_input.clone().unwrap_or_default())
+                    .on_change(ctx.link().callback(|value: String| {
+                        Msg::ServiceFilter((!value.is_empty()).then_some(value))
+                    })),
+            )
             // the structured journal view's filters only apply in live mode; keep the toggle in
             // this shared toolbar rather than stacking a second one just for it
             .with_optional_child((self.active && ctx.props().structured).then(|| {
@@ -184,7 +224,9 @@ impl ProxmoxSyslog {
         if self.active {
             JournalView::new(props.journal_base_url.clone())
                 .structured(props.structured)
+                .unit(props.service.clone())
                 .show_filters(self.show_filters)
+                .show_navigation(true)
                 .on_loading_change(ctx.link().callback(|(loading, tailview)| {
                     Msg::LoadingChange((if loading { 1 } else { 0 }, tailview))
                 }))
@@ -193,7 +235,7 @@ impl ProxmoxSyslog {
             LogView::new(props.base_url.clone())
                 .margin(2)
                 .class("pwt-flex-fill")
-                .service(props.service.clone())
+                .service(self.service.clone())
                 .since(date_time_to_epoch(&self.since, &self.since_time))
                 .until(date_time_to_epoch(&self.until, &self.until_time))
                 .active(false)
@@ -207,7 +249,24 @@ impl Component for ProxmoxSyslog {
     type Message = Msg;
     type Properties = Syslog;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        if let Some(url) = ctx.props().services_url.clone() {
+            ctx.link().send_future(async move {
+                let services = match crate::http_get::<Vec<ServiceEntry>>(&*url, None).await {
+                    Ok(list) => list
+                        .into_iter()
+                        .map(|entry| AttrValue::from(entry.service))
+                        .collect(),
+                    Err(err) => {
+                        // the service filter stays usable as free text
+                        log::error!("loading services from {url} failed: {err}");
+                        Vec::new()
+                    }
+                };
+                Msg::ServicesLoaded(services)
+            });
+        }
+
         Self {
             active: true,
             since: PlainDate::today(),
@@ -218,10 +277,22 @@ impl Component for ProxmoxSyslog {
             until_label_id: AttrValue::from(pwt::widget::get_unique_element_id()),
             pending: false,
             show_filters: false,
+            service: ctx
+                .props()
+                .service
+                .as_ref()
+                .map(|service| service.to_string()),
+            service_input: ctx
+                .props()
+                .service
+                .as_ref()
+                .map(|service| service.to_string()),
+            service_timeout: None,
+            services: Rc::new(Vec::new()),
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::SinceDate(Some(date)) => {
                 self.since = date;
@@ -256,6 +327,25 @@ impl Component for ProxmoxSyslog {
                 self.show_filters = !self.show_filters;
                 true
             }
+            Msg::ServiceFilter(service) => {
+                self.service_input = service;
+                let link = ctx.link().clone();
+                self.service_timeout = Some(Timeout::new(SERVICE_FILTER_DEBOUNCE_MS, move || {
+                    link.send_message(Msg::ApplyServiceFilter)
+                }));
+                true
+            }
+            Msg::ApplyServiceFilter => {
+                self.service_timeout = None;
+                let changed = self.service != self.service_input;
+                self.service = self.service_input.clone();
+                changed
+            }
+            Msg::ServicesLoaded(mut services) => {
+                services.sort();
+                self.services = Rc::new(services);
+                true
+            }
         }
     }
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -0,0 +1,140 @@
+//! Extract progress information and sections from task logs.
+
+/// A section of a task log.
+///
+/// Backup tasks covering several guests get one section per guest, all other lines end
+/// up in sections without title.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaskLogSection {
+    /// The section title, for example `VM 100 (qemu)`.
+    pub title: Option<String>,
+    /// The log lines of the section.
+    pub lines: Vec<String>,
+    /// The last progress (in percent) reported in the section.
+    pub progress: Option<f64>,
+    /// The section was completed.
+    pub finished: bool,
+    /// The section reported an error.
+    pub failed: bool,
+}
+
+/// Parse the progress (in percent) from a task log line.
+///
+/// Known formats are `progress 45% ...`, the vzdump style `INFO:  45% (1.2 GiB of 3.0 GiB)
+/// ...` and `transferred 1.2 GiB of 3.0 GiB (40.00%)`.
+pub fn parse_task_progress(line: &str) -> Option<f64> {
+    let line = line.trim();
+    let line = line.strip_prefix("INFO:").unwrap_or(line).trim_start();
+
+    let percent = if let Some(rest) = line.strip_prefix("progress ") {
+        rest.split_once('%')?.0
+    } else if line.starts_with(|c: char| c.is_ascii_digit()) {
+        let (number, _) = line.split_once('%')?;
+        if number.contains(char::is_whitespace) {
+            return None;
+        }
+        number
+    } else if line.starts_with("transferred ") && line.ends_with("%)") {
+        let (_, rest) = line.rsplit_once('(')?;
+        rest.strip_suffix("%)")?
+    } else {
+        return None;
+    };
+
+    let percent: f64 = percent.trim().parse().ok()?;
+    (0.0..=100.0).contains(&percent).then_some(percent)
+}
+
+fn section_start(line: &str) -> Option<&str> {
+    line.strip_prefix("INFO: Starting Backup of ")
+}
+
+fn section_end(line: &str) -> Option<bool> {
+    if line.starts_with("INFO: Finished Backup of ") {
+        Some(false)
+    } else if line.starts_with("ERROR: Backup of ") && line.contains(" failed") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Split a task log into sections and collect the reported progress.
+pub fn parse_task_log<S: AsRef<str>>(lines: &[S]) -> Vec<TaskLogSection> {
+    let mut sections = vec![TaskLogSection::default()];
+
+    for line in lines {
+        let line = line.as_ref();
+        if let Some(title) = section_start(line) {
+            sections.push(TaskLogSection {
+                title: Some(title.to_string()),
+                ..Default::default()
+            });
+        }
+
+        // always non-empty
+        let current = sections.last_mut().unwrap();
+        current.lines.push(line.to_string());
+        if let Some(progress) = parse_task_progress(line) {
+            current.progress = Some(progress);
+        }
+        if current.title.is_some() {
+            if let Some(failed) = section_end(line) {
+                current.finished = true;
+                current.failed = failed;
+                // lines after the guest section belong to the task again
+                sections.push(TaskLogSection::default());
+            }
+        }
+    }
+
+    sections.retain(|section| !section.lines.is_empty());
+    sections
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_task_progress() {
+        assert_eq!(
+            parse_task_progress("progress 45% (read 1 of 2 bytes)"),
+            Some(45.0)
+        );
+        assert_eq!(
+            parse_task_progress("INFO:  12% (1.0 GiB of 8.0 GiB) in 3s, read: 341.3 MiB/s"),
+            Some(12.0)
+        );
+        assert_eq!(
+            parse_task_progress("transferred 1.0 GiB of 4.0 GiB (25.00%)"),
+            Some(25.0)
+        );
+        assert_eq!(parse_task_progress("INFO: status = running"), None);
+        assert_eq!(
+            parse_task_progress("100 files in 5s (3% compression)"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_task_log() {
+        let log = [
+            "INFO: starting new backup job: vzdump 100 101",
+            "INFO: Starting Backup of VM 100 (qemu)",
+            "INFO:  50% (1.0 GiB of 2.0 GiB) in 1s",
+            "INFO: Finished Backup of VM 100 (00:00:02)",
+            "INFO: Starting Backup of VM 101 (lxc)",
+            "ERROR: Backup of VM 101 failed - no space left",
+            "INFO: Backup job finished with errors",
+        ];
+        let sections = parse_task_log(&log);
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[0].title, None);
+        assert_eq!(sections[1].title.as_deref(), Some("VM 100 (qemu)"));
+        assert_eq!(sections[1].progress, Some(50.0));
+        assert!(sections[1].finished && !sections[1].failed);
+        assert!(sections[2].finished && sections[2].failed);
+        assert_eq!(sections[3].lines, ["INFO: Backup job finished with errors"]);
+    }
+}
@@ -27,6 +27,10 @@ pub struct TaskProgress {
     #[prop_or_default]
     pub on_close: Option<Callback<()>>,
 
+    /// Called once when the task stops, with the information whether it was successful.
+    #[prop_or_default]
+    pub on_finish: Option<Callback<bool>>,
+
     #[prop_or("/nodes/localhost/tasks".into())]
     #[builder(IntoPropValue, into_prop_value)]
     /// The base url for
@@ -46,6 +50,11 @@ impl TaskProgress {
         self.on_close = cb.into_event_callback();
         self
     }
+
+    pub fn on_finish(mut self, cb: impl IntoEventCallback<bool>) -> Self {
+        self.on_finish = cb.into_event_callback();
+        self
+    }
 }
 
 pub enum Msg {
@@ -106,6 +115,9 @@ impl Component for PwtTaskProgress {
                 } else {
                     if self.endtime.is_none() {
                         self.endtime = Some(proxmox_time::epoch_f64());
+                        if let Some(on_finish) = &props.on_finish {
+                            on_finish.emit(task_ok);
+                        }
                     }
                     if !self.show_details && task_ok {
                         if let Some(on_close) = &props.on_close {
@@ -1,6 +1,9 @@
+use std::collections::HashSet;
 use std::rc::Rc;
 
-use serde_json::Value;
+use anyhow::Error;
+use serde::Deserialize;
+use serde_json::{json, Value};
 
 use gloo_timers::callback::Timeout;
 
@@ -8,13 +11,17 @@ use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::prelude::*;
 use yew::virtual_dom::{Key, VComp, VNode};
 
+use pwt::css::{AlignItems, FontColor};
 use pwt::state::Loader;
-use pwt::widget::{Button, Column, Dialog, TabBarItem, TabPanel, Toolbar};
+use pwt::widget::{
+    ActionIcon, Button, Column, Container, Dialog, Fa, Progress, Row, TabBarItem, TabPanel, Toolbar,
+};
 use pwt::{prelude::*, AsyncPool};
 
+use crate::common_api_types::Upid;
 use crate::percent_encoding::percent_encode_component;
-use crate::utils::{format_duration_human, format_upid, render_epoch};
-use crate::{KVGrid, KVGridRow, LogView};
+use crate::utils::{download_text_file, format_duration_human, format_upid, render_epoch};
+use crate::{parse_task_log, CopyField, KVGrid, KVGridRow, LogView, TaskLogSection};
 
 use pwt_macros::builder;
 
@@ -51,17 +58,34 @@ impl TaskViewer {
     pwt::impl_yew_std_props_builder!();
 }
 
+// number of log lines loaded per request for the summary
+const LOG_PAGE_SIZE: usize = 500;
+
+#[derive(Deserialize)]
+struct LogEntry {
+    t: String,
+}
+
 pub enum Msg {
     DataChange,
+    LoadLog,
+    LogLoaded(usize, Result<Vec<String>, Error>),
     Reload,
     StopTask,
+    Download,
+    ToggleSection(usize),
 }
 
 pub struct PwtTaskViewer {
     loader: Loader<Value>,
+    // the log is loaded incrementally, only new lines are fetched
+    log_lines: Vec<String>,
+    log_loading: bool,
+    log_error: Option<String>,
     reload_timeout: Option<Timeout>,
     active: bool,
     endtime: Option<i64>,
+    toggled_sections: HashSet<usize>,
     async_pool: AsyncPool,
 }
 
@@ -93,11 +117,16 @@ impl Component for PwtTaskViewer {
             .on_change(ctx.link().callback(|_| Msg::DataChange));
 
         loader.load();
+        ctx.link().send_message(Msg::LoadLog);
         Self {
             loader,
+            log_lines: Vec::new(),
+            log_loading: false,
+            log_error: None,
             reload_timeout: None,
             active: props.endtime.is_none(),
             endtime: props.endtime,
+            toggled_sections: HashSet::new(),
             async_pool: AsyncPool::new(),
         }
     }
@@ -107,7 +136,12 @@ impl Component for PwtTaskViewer {
         match msg {
             Msg::DataChange => {
                 let link = ctx.link().clone();
+                let was_active = self.active;
                 self.active = self.task_is_active();
+                if was_active && !self.active {
+                    // make sure the summary includes the final lines
+                    ctx.link().send_message(Msg::LoadLog);
+                }
                 if self.active {
                     self.reload_timeout = Some(Timeout::new(1_000, move || {
                         link.send_message(Msg::Reload);
@@ -135,6 +169,77 @@ impl Component for PwtTaskViewer {
                 self.active = self.task_is_active();
                 if self.active {
                     self.loader.load();
+                    ctx.link().send_message(Msg::LoadLog);
+                }
+                true
+            }
+            Msg::LoadLog => {
+                if self.log_loading {
+                    return false;
+                }
+                self.log_loading = true;
+                let url = log_url(props);
+                let start = self.log_lines.len();
+                let link = ctx.link().clone();
+                self.async_pool.spawn(async move {
+                    let param = json!({ "start": start, "limit": LOG_PAGE_SIZE });
+                    let result = crate::http_get::<Vec<LogEntry>>(&url, Some(param))
+                        .await
+                        .map(|data| data.into_iter().map(|entry| entry.t).collect());
+                    link.send_message(Msg::LogLoaded(start, result));
+                });
+                false
+            }
+            Msg::LogLoaded(start, result) => {
+                self.log_loading = false;
+                match result {
+                    // ignore stale results
+                    Ok(_) if start != self.log_lines.len() => false,
+                    Ok(lines) => {
+                        self.log_error = None;
+                        let full_page = lines.len() >= LOG_PAGE_SIZE;
+                        // PVE reports an empty range as a single "no content" line
+                        if !(lines.len() == 1 && lines[0] == "no content" && start > 0) {
+                            self.log_lines.extend(lines);
+                        }
+                        if full_page {
+                            ctx.link().send_message(Msg::LoadLog);
+                        }
+                        true
+                    }
+                    Err(err) => {
+                        self.log_error = Some(err.to_string());
+                        true
+                    }
+                }
+            }
+            Msg::Download => {
+                let url = log_url(props);
+                let filename = match props.task_id.parse::<Upid>() {
+                    Ok(upid) => format!("{}.log", upid.file_name_stem()),
+                    Err(_) => String::from("task.log"),
+                };
+                self.async_pool.spawn(async move {
+                    let result =
+                        crate::http_get::<Vec<LogEntry>>(&url, Some(json!({ "limit": 0 })))
+                            .await
+                            .and_then(|data| {
+                                let mut text = String::new();
+                                for entry in data {
+                                    text.push_str(&entry.t);
+                                    text.push('\n');
+                                }
+                                download_text_file(&filename, &text)
+                            });
+                    if let Err(err) = result {
+                        log::error!("unable to download task log: {err}");
+                    }
+                });
+                false
+            }
+            Msg::ToggleSection(index) => {
+                if !self.toggled_sections.remove(&index) {
+                    self.toggled_sections.insert(index);
                 }
                 true
             }
@@ -151,6 +256,10 @@ impl Component for PwtTaskViewer {
                     TabBarItem::new().label(tr!("Output")),
                     self.view_output(ctx),
                 )
+                .with_item(
+                    TabBarItem::new().label(tr!("Summary")),
+                    self.view_summary(ctx),
+                )
                 .with_item(
                     TabBarItem::new().label(tr!("Status")),
                     self.view_status(ctx, data.clone()),
@@ -177,6 +286,34 @@ impl From<TaskViewer> for VNode {
     }
 }
 
+fn log_url(props: &TaskViewer) -> String {
+    format!(
+        "{}/{}/log",
+        props.base_url,
+        percent_encode_component(&props.task_id),
+    )
+}
+
+fn render_log_lines(lines: &[String]) -> Html {
+    html! {
+        <pre class="pwt-font-monospace" style="margin:0;white-space:pre-wrap;">
+            {lines.join("\n")}
+        </pre>
+    }
+}
+
+fn render_progress(progress: f64) -> Row {
+    Row::new()
+        .gap(2)
+        .class(AlignItems::Center)
+        .with_child(
+            Progress::new()
+                .class("pwt-flex-fill")
+                .value((progress / 100.0) as f32),
+        )
+        .with_child(format!("{progress:.0}%"))
+}
+
 impl PwtTaskViewer {
     fn task_is_active(&self) -> bool {
         if let Some(Ok(data)) = self.loader.read().data.as_ref() {
@@ -257,7 +394,9 @@ impl PwtTaskViewer {
                         html! {"-"}
                     })
                     .required(true),
-                KVGridRow::new("upid", tr!("Unique task ID")),
+                KVGridRow::new("upid", tr!("Unique task ID")).renderer(|_name, value, _record| {
+                    CopyField::new(value.as_str().unwrap_or("").to_string()).into()
+                }),
             ]));
 
         Column::new()
@@ -269,21 +408,24 @@ impl PwtTaskViewer {
 
     fn view_output(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
-        let task_id = props.task_id.clone();
         let active = self.active;
         let link = ctx.link();
 
-        let toolbar = Toolbar::new().class("pwt-border-bottom").with_child(
-            Button::new(tr!("Stop"))
-                .disabled(!active)
-                .onclick(link.callback(|_| Msg::StopTask)),
-        );
+        let toolbar = Toolbar::new()
+            .class("pwt-border-bottom")
+            .with_child(
+                Button::new(tr!("Stop"))
+                    .disabled(!active)
+                    .onclick(link.callback(|_| Msg::StopTask)),
+            )
+            .with_flex_spacer()
+            .with_child(
+                Button::new(tr!("Download"))
+                    .icon_class("fa fa-download")
+                    .onclick(link.callback(|_| Msg::Download)),
+            );
 
-        let url = format!(
-            "{}/{}/log",
-            props.base_url,
-            percent_encode_component(&task_id),
-        );
+        let url = log_url(props);
 
         Column::new()
             .class("pwt-flex-fit")
@@ -296,4 +438,74 @@ impl PwtTaskViewer {
             )
             .into()
     }
+
+    fn view_summary(&self, ctx: &Context<Self>) -> Html {
+        let sections = parse_task_log(&self.log_lines);
+        let progress = sections.iter().rev().find_map(|section| section.progress);
+
+        let mut column = Column::new()
+            .class("pwt-flex-fit")
+            .class("pwt-overflow-auto")
+            .padding(2)
+            .gap(2);
+
+        if let Some(progress) = progress.filter(|_| self.active) {
+            column.add_child(render_progress(progress));
+        }
+
+        for (index, section) in sections.iter().enumerate() {
+            column.add_child(self.render_section(ctx, index, section));
+        }
+
+        if let Some(err) = &self.log_error {
+            column.add_child(pwt::widget::error_message(err));
+        }
+
+        column.into()
+    }
+
+    fn render_section(&self, ctx: &Context<Self>, index: usize, section: &TaskLogSection) -> Html {
+        let title = match &section.title {
+            Some(title) => title,
+            None => return render_log_lines(&section.lines),
+        };
+
+        // unfinished and failed sections are expanded by default
+        let expanded =
+            (section.failed || !section.finished) != self.toggled_sections.contains(&index);
+
+        let status_icon = if section.failed {
+            Fa::new("times-circle").class(FontColor::Error)
+        } else if section.finished {
+            Fa::new("check").class(FontColor::Success)
+        } else {
+            Fa::new("").class("pwt-loading-icon")
+        };
+
+        let header = Row::new()
+            .gap(2)
+            .class(AlignItems::Center)
+            .with_child(
+                ActionIcon::new(if expanded {
+                    "fa fa-chevron-down"
+                } else {
+                    "fa fa-chevron-right"
+                })
+                .on_activate(ctx.link().callback(move |_| Msg::ToggleSection(index))),
+            )
+            .with_child(status_icon)
+            .with_child(Container::new().class("pwt-flex-fill").with_child(title))
+            .with_optional_child(
+                section
+                    .progress
+                    .filter(|_| !section.finished)
+                    .map(|progress| render_progress(progress).style("width", "200px")),
+            );
+
+        Column::new()
+            .gap(1)
+            .with_child(header)
+            .with_optional_child(expanded.then(|| render_log_lines(&section.lines)))
+            .into()
+    }
 }
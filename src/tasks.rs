@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
@@ -5,7 +6,7 @@ use std::rc::Rc;
 use anyhow::Error;
 
 use pwt::css::JustifyContent;
-use pwt::widget::form::{Field, Form, FormContext, InputType};
+use pwt::widget::form::{Field, Form, FormContext, InputType, Number};
 
 use gloo_timers::callback::Timeout;
 use html::IntoEventCallback;
@@ -18,17 +19,21 @@ use pwt::state::{PersistentState, Selection, Store};
 use pwt::widget::data_table::{
     DataTable, DataTableColumn, DataTableHeader, DataTableRowRenderCallback,
 };
-use pwt::widget::{Button, Column, Fa, Row, Toolbar};
+use pwt::widget::menu::{Menu, MenuButton, MenuItem};
+use pwt::widget::{ActionIcon, Button, Column, Fa, List, ListTile, Row, Toolbar, Tooltip};
 
-use crate::utils::{format_upid, render_epoch_short};
+use crate::grid_state::{GridState, PersistentGridState};
+use crate::layout::list_tile::icon_list_tile;
+use crate::utils::{copy_text_to_clipboard, format_upid, render_epoch_short};
 
 use pbs_api_types::TaskListItem;
 
 use pwt_macros::builder;
 
+use crate::common_api_types::{TaskState, TaskStatusClass};
 use crate::{
     LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
-    LoadableComponentScopeExt, LoadableComponentState, TaskViewer,
+    LoadableComponentScopeExt, LoadableComponentState, MiniTaskLog, TaskViewer,
 };
 
 use super::{TaskStatusSelector, TaskTypeSelector};
@@ -37,6 +42,61 @@ const FILTER_UPDATE_BUFFER_MS: u32 = 150;
 const BATCH_LIMIT: u64 = 500;
 const LOAD_BUFFER_ROWS: usize = 20;
 
+/// Returns the guest ID of tasks started for a guest, for example `qmstart` or `vzdump` tasks.
+pub fn task_guest_id(item: &TaskListItem) -> Option<u32> {
+    item.worker_id.as_deref()?.parse().ok()
+}
+
+// the parsed task status, unparsable states are shown as error
+fn task_state(item: &TaskListItem) -> TaskState {
+    TaskState::parse(item.status.as_deref())
+        .unwrap_or_else(|_| TaskState::Error(item.status.clone().unwrap_or_default()))
+}
+
+/// Menu with the actions for a single task: open it, copy its UPID and (for guest tasks)
+/// filter the list by the task's guest.
+pub(crate) fn task_action_menu(
+    item: &TaskListItem,
+    on_open: Callback<String>,
+    on_filter_guest: Option<Callback<u32>>,
+) -> Menu {
+    let upid = item.upid.clone();
+    let mut menu = Menu::new()
+        .with_item({
+            let upid = upid.clone();
+            MenuItem::new(tr!("Open Task"))
+                .icon_class("fa fa-fw fa-external-link")
+                .on_select(move |_| on_open.emit(upid.clone()))
+        })
+        .with_item(
+            MenuItem::new(tr!("Copy UPID"))
+                .icon_class("fa fa-fw fa-clipboard")
+                .on_select(move |_| copy_text_to_clipboard(&upid)),
+        );
+
+    if let (Some(vmid), Some(on_filter_guest)) = (task_guest_id(item), on_filter_guest) {
+        menu.add_item(
+            MenuItem::new(tr!("Filter by this Guest"))
+                .icon_class("fa fa-fw fa-filter")
+                .on_select(move |_| on_filter_guest.emit(vmid)),
+        );
+    }
+
+    menu
+}
+
+/// Set the time of day of a date from a "HH:MM" value, using `default` if the value is empty or
+/// invalid.
+fn set_time_of_day(date: &js_sys::Date, time: &str, default: (u32, u32, u32)) {
+    let (hours, minutes, seconds) = time
+        .split_once(':')
+        .and_then(|(hours, minutes)| Some((hours.parse().ok()?, minutes.parse().ok()?, default.2)))
+        .unwrap_or(default);
+    date.set_hours(hours);
+    date.set_minutes(minutes);
+    date.set_seconds(seconds);
+}
+
 #[derive(PartialEq, Properties)]
 #[builder]
 pub struct Tasks {
@@ -77,7 +137,35 @@ pub struct Tasks {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or_default]
     /// An optional column configuration that overwrites the default one.
+    ///
+    /// Note: The inline task log expander and the task action menu are only part of the
+    /// default columns.
     pub columns: Option<Rc<Vec<DataTableHeader<TaskListItem>>>>,
+
+    /// Only show the tasks of this guest.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub vmid: Option<u32>,
+
+    /// Show a "Guest ID" filter field (the `vmid` parameter is only supported by Proxmox VE).
+    #[prop_or_default]
+    #[builder]
+    pub guest_filter: bool,
+
+    /// Called with the UPID whenever a task gets opened.
+    #[builder_cb(IntoEventCallback, into_event_callback, String)]
+    #[prop_or_default]
+    pub on_open_task: Option<Callback<String>>,
+
+    /// Show the tasks as a list of tiles instead of a table (for small screens).
+    #[prop_or_default]
+    #[builder]
+    pub mobile: bool,
+
+    /// Save column adjustments in the browser's local storage, using this id.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub state_id: Option<AttrValue>,
 }
 
 impl Default for Tasks {
@@ -100,6 +188,13 @@ impl Tasks {
     fn get_nodename(&self) -> String {
         self.nodename.as_deref().unwrap_or("localhost").to_string()
     }
+
+    fn get_base_url(&self) -> String {
+        match &self.base_url {
+            Some(url) => url.to_string(),
+            None => format!("/nodes/{}/tasks", self.get_nodename()),
+        }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -114,6 +209,10 @@ pub enum Msg {
     LoadFinished(bool), // no more tasks available
     UpdateFilter,
     ShowTask,
+    OpenTask(String),
+    FilterGuest(u32),
+    ToggleLog(String),
+    UpdateGridState(GridState),
 }
 pub struct ProxmoxTasks {
     state: LoadableComponentState<ViewDialog>,
@@ -127,21 +226,55 @@ pub struct ProxmoxTasks {
     last_filter: serde_json::Value,
     load_timeout: Option<Timeout>,
     columns: Rc<Vec<DataTableHeader<TaskListItem>>>,
+    expanded: HashSet<String>,
+    grid_state: Option<PersistentGridState<TaskListItem>>,
 }
 
 pwt::impl_deref_mut_property!(ProxmoxTasks, state, LoadableComponentState<ViewDialog>);
 
 impl ProxmoxTasks {
-    fn columns(ctx: &LoadableComponentContext<Self>) -> Rc<Vec<DataTableHeader<TaskListItem>>> {
+    fn update_columns(&mut self, ctx: &LoadableComponentContext<Self>) {
+        self.columns = Self::columns(ctx, &self.expanded);
+        if let Some(grid_state) = &mut self.grid_state {
+            grid_state.set_columns(self.columns.clone());
+        }
+    }
+
+    fn columns(
+        ctx: &LoadableComponentContext<Self>,
+        expanded: &HashSet<String>,
+    ) -> Rc<Vec<DataTableHeader<TaskListItem>>> {
         if let Some(columns) = ctx.props().columns.clone() {
             columns
         } else {
+            let base_url: AttrValue = ctx.props().get_base_url().into();
             Rc::new(vec![
+                DataTableColumn::new("")
+                    .width("30px")
+                    .show_menu(false)
+                    .render({
+                        let link = ctx.link().clone();
+                        let expanded = expanded.clone();
+                        move |item: &TaskListItem| {
+                            let (icon_class, tip) = if expanded.contains(&item.upid) {
+                                ("fa fa-chevron-down", tr!("Hide Log"))
+                            } else {
+                                ("fa fa-chevron-right", tr!("Show Log"))
+                            };
+                            let upid = item.upid.clone();
+                            let icon = ActionIcon::new(icon_class)
+                                .on_activate(link.callback(move |_| Msg::ToggleLog(upid.clone())));
+                            Tooltip::new(icon).tip(tip).into()
+                        }
+                    })
+                    .into(),
                 DataTableColumn::new(tr!("Start Time"))
+                    .key("starttime")
                     .width("130px")
                     .render(|item: &TaskListItem| render_epoch_short(item.starttime).into())
                     .into(),
                 DataTableColumn::new(tr!("End Time"))
+                    .key("endtime")
                     .width("130px")
                     .render(|item: &TaskListItem| match item.endtime {
                         Some(endtime) => render_epoch_short(endtime).into(),
@@ -152,28 +285,123 @@ impl ProxmoxTasks {
                     })
                     .into(),
                 DataTableColumn::new(tr!("User name"))
+                    .key("user")
                     .width("150px")
                     .render(|item: &TaskListItem| {
                         html! {&item.user}
                     })
                     .into(),
                 DataTableColumn::new(tr!("Description"))
+                    .key("upid")
                     .flex(1)
-                    .render(move |item: &TaskListItem| html! {format_upid(&item.upid)})
+                    .render({
+                        let expanded = expanded.clone();
+                        move |item: &TaskListItem| {
+                            if expanded.contains(&item.upid) {
+                                Column::new()
+                                    .gap(1)
+                                    .with_child(format_upid(&item.upid))
+                                    .with_child(
+                                        MiniTaskLog::new(item.upid.clone())
+                                            .base_url(base_url.clone()),
+                                    )
+                                    .into()
+                            } else {
+                                html! {format_upid(&item.upid)}
+                            }
+                        }
+                    })
                     .into(),
                 DataTableColumn::new(tr!("Status"))
+                    .key("status")
                     .width("200px")
-                    .render(|item: &TaskListItem| match item.status.as_deref() {
-                        Some("RUNNING") | None => Row::new()
+                    .render(|item: &TaskListItem| match task_state(item) {
+                        TaskState::Running => Row::new()
                             .class(JustifyContent::Center)
                             .with_child(Fa::new("").class("pwt-loading-icon"))
                             .into(),
-                        Some(text) => html! {text},
+                        _ => html! {item.status.as_deref().unwrap_or_default()},
+                    })
+                    .into(),
+                DataTableColumn::new("")
+                    .width("50px")
+                    .show_menu(false)
+                    .render({
+                        let link = ctx.link().clone();
+                        let filter_guest = ctx.props().guest_filter && ctx.props().vmid.is_none();
+                        move |item: &TaskListItem| {
+                            let on_filter_guest =
+                                filter_guest.then(|| link.callback(Msg::FilterGuest));
+                            let menu = task_action_menu(
+                                item,
+                                link.callback(Msg::OpenTask),
+                                on_filter_guest,
+                            );
+                            MenuButton::new("")
+                                .icon_class("fa fa-ellipsis-v")
+                                .menu(menu)
+                                .into()
+                        }
                     })
                     .into(),
             ])
         }
     }
+
+    fn render_list(&self, ctx: &LoadableComponentContext<Self>) -> Html {
+        let tiles: Vec<ListTile> = self
+            .store
+            .read()
+            .iter()
+            .map(|item| {
+                let icon = match task_state(item) {
+                    TaskState::Running => Fa::new("").class("pwt-loading-icon"),
+                    TaskState::Ok => Fa::new("check"),
+                    TaskState::Warnings(_) => {
+                        Fa::new("exclamation-triangle").class("pwt-color-warning")
+                    }
+                    TaskState::Error(_) => Fa::new("times-circle").class("pwt-color-error"),
+                };
+                let subtitle = format!("{} - {}", render_epoch_short(item.starttime), item.user);
+
+                let mut tile = icon_list_tile(icon, format_upid(&item.upid), subtitle, html! {});
+                tile.set_key(item.upid.clone());
+                tile.set_interactive(true);
+                tile.set_on_activate({
+                    let link = ctx.link().clone();
+                    let upid = item.upid.clone();
+                    move |_| link.send_message(Msg::OpenTask(upid.clone()))
+                });
+                tile
+            })
+            .collect();
+
+        List::from_tiles(tiles)
+            .virtual_scroll(Some(false))
+            .grid_template_columns("auto 1fr auto")
+            .class(pwt::css::FlexFit)
+            .into()
+    }
+
+    /// Button to fetch the next batch of older tasks, hidden once all tasks are loaded.
+    fn render_load_more(&self, ctx: &LoadableComponentContext<Self>) -> Option<Html> {
+        if self.no_more_tasks || self.store.data_len() == 0 {
+            return None;
+        }
+        Some(
+            Row::new()
+                .class(JustifyContent::Center)
+                .class("pwt-border-top")
+                .padding(1)
+                .with_child(
+                    Button::new(tr!("Load More"))
+                        .icon_class("fa fa-angle-double-down")
+                        .disabled(self.loading())
+                        .onclick(ctx.link().callback(|_| Msg::LoadBatch(false))),
+                )
+                .into(),
+        )
+    }
 }
 
 impl LoadableComponent for ProxmoxTasks {
@@ -200,16 +428,21 @@ impl LoadableComponent for ProxmoxTasks {
                     link.send_message(Msg::LoadBatch(false));
                 }
                 let record: &TaskListItem = args.record();
-                match record.status.as_deref() {
-                    Some("RUNNING" | "OK") | None => {}
-                    Some(status) if status.starts_with("WARNINGS:") => {
-                        args.add_class("pwt-color-warning")
-                    }
-                    _ => args.add_class("pwt-color-error"),
+                match task_state(record).class() {
+                    None | Some(TaskStatusClass::Ok) => {}
+                    Some(TaskStatusClass::Warning) => args.add_class("pwt-color-warning"),
+                    Some(TaskStatusClass::Error) => args.add_class("pwt-color-error"),
                 }
             }
         });
 
+        let columns = Self::columns(ctx, &HashSet::new());
+        let grid_state = ctx
+            .props()
+            .state_id
+            .as_deref()
+            .map(|state_id| PersistentGridState::new(state_id, columns.clone()));
+
         Self {
             state: LoadableComponentState::new(),
             selection,
@@ -221,7 +454,9 @@ impl LoadableComponent for ProxmoxTasks {
             fresh_load: true,
             no_more_tasks: false,
             load_timeout: None,
-            columns: Self::columns(ctx),
+            columns,
+            grid_state,
+            expanded: HashSet::new(),
         }
     }
 
@@ -230,45 +465,44 @@ impl LoadableComponent for ProxmoxTasks {
         ctx: &LoadableComponentContext<Self>,
     ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
         let props = ctx.props();
-        let nodename = props.get_nodename();
-        let path = match &props.base_url {
-            Some(url) => url.to_string(),
-            None => format!("/nodes/{nodename}/tasks"),
-        };
+        let path = props.get_base_url();
 
         let store = self.store.clone();
 
         let form_context = self.filter_form_context.read();
         let mut filter = form_context.get_submit_data();
 
+        // the time of day is only a refinement of the dates, not an API parameter
+        let mut take_time = |name: &str| match filter.as_object_mut().and_then(|m| m.remove(name)) {
+            Some(serde_json::Value::String(time)) => time,
+            _ => String::new(),
+        };
+        let since_time = take_time("_since_time");
+        let until_time = take_time("_until_time");
+
         // Transform Date values
         if let Some(since) = filter.get("since").and_then(|v| v.as_str()) {
             let since = js_sys::Date::new(&wasm_bindgen::JsValue::from_str(since));
-            since.set_hours(0);
-            since.set_minutes(0);
-            since.set_seconds(0);
+            set_time_of_day(&since, &since_time, (0, 0, 0));
             let since = (since.get_time() / 1000.0) as u64;
             filter["since"] = since.into();
         }
 
         if let Some(until) = filter.get("until").and_then(|v| v.as_str()) {
             let until = js_sys::Date::new(&wasm_bindgen::JsValue::from_str(until));
-            until.set_hours(23);
-            until.set_minutes(59);
-            until.set_seconds(59);
+            set_time_of_day(&until, &until_time, (23, 59, 59));
             let until = (until.get_time() / 1000.0) as u64;
             filter["until"] = until.into();
         }
 
-        let until = match (self.fresh_load, self.store.read().last()) {
-            (false, Some(last)) => Some(last.starttime),
-            _ => None,
+        // continue after the already loaded rows
+        let start = match self.fresh_load {
+            true => None,
+            false => Some(self.store.data_len() as u64),
         };
 
-        if let Some(until) = until {
-            if filter["until"].as_i64().unwrap_or(i64::MAX) > until {
-                filter["until"] = until.into();
-            }
+        if let Some(start) = start {
+            filter["start"] = start.into();
         }
 
         filter["limit"] = BATCH_LIMIT.into();
@@ -278,6 +512,10 @@ impl LoadableComponent for ProxmoxTasks {
             filter[key] = value.into();
         }
 
+        if let Some(vmid) = props.vmid {
+            filter["vmid"] = vmid.into();
+        }
+
         let link = ctx.link().clone();
         Box::pin(async move {
             let mut data: Vec<_> = crate::http_get(&path, Some(filter)).await?;
@@ -285,29 +523,15 @@ impl LoadableComponent for ProxmoxTasks {
             // trigger a LoadBatch for the next batch, which must already see
             // the updated no_more_tasks state
             link.send_message(Msg::LoadFinished((data.len() as u64) < BATCH_LIMIT));
-            if until.is_none() {
+            if start.is_none() {
                 store.write().set_data(data);
             } else {
-                // since we used the starttime from the last existing element,
-                // we know there should be an overlap, so find it
-                let mut store = store.write();
-                if let Some(last) = store.last() {
-                    let mut cutoff = None;
-                    for (idx, new) in data.iter().enumerate() {
-                        if new.upid == last.upid {
-                            cutoff = Some(idx);
-                            break;
-                        }
-                        if new.starttime < last.starttime {
-                            // we did not find the last element, append all
-                            break;
-                        }
-                    }
-                    if let Some(cutoff) = cutoff {
-                        data.drain(0..=cutoff);
-                    }
-                }
-                store.append(&mut data);
+                // tasks started since the last load shift the offsets, so the
+                // new batch can overlap with the loaded rows - skip those
+                let loaded: HashSet<String> =
+                    store.read().iter().map(|item| item.upid.clone()).collect();
+                data.retain(|item| !loaded.contains(&item.upid));
+                store.write().append(&mut data);
             }
             Ok(())
         })
@@ -355,7 +579,7 @@ impl LoadableComponent for ProxmoxTasks {
             Msg::LoadFinished(no_more_tasks) => {
                 self.fresh_load = false;
                 self.no_more_tasks = no_more_tasks;
-                false
+                true
             }
             Msg::RefreshClicked => {
                 let link = ctx.link().clone();
@@ -368,7 +592,24 @@ impl LoadableComponent for ProxmoxTasks {
 
                 false
             }
+            Msg::OpenTask(upid) => {
+                self.selection.select(Key::from(upid));
+                ctx.link().send_message(Msg::ShowTask);
+                false
+            }
+            Msg::FilterGuest(vmid) => {
+                self.filter_form_context
+                    .write()
+                    .set_field_value("vmid", vmid.into());
+                self.show_filter.update(true);
+                true
+            }
             Msg::ShowTask => {
+                if let (Some(on_open_task), Some(key)) =
+                    (&ctx.props().on_open_task, self.selection.selected_key())
+                {
+                    on_open_task.emit(key.to_string());
+                }
                 if let Some(on_show_task) = &ctx.props().on_show_task {
                     let selected_item = self
                         .selection
@@ -384,6 +625,19 @@ impl LoadableComponent for ProxmoxTasks {
                 }
                 false
             }
+            Msg::ToggleLog(upid) => {
+                if !self.expanded.remove(&upid) {
+                    self.expanded.insert(upid);
+                }
+                self.update_columns(ctx);
+                true
+            }
+            Msg::UpdateGridState(state) => {
+                if let Some(grid_state) = &mut self.grid_state {
+                    grid_state.update(state);
+                }
+                true
+            }
         }
     }
 
@@ -417,6 +671,9 @@ impl LoadableComponent for ProxmoxTasks {
                     .disabled(dirty_count == 0)
                     .onclick(move |_| form_context.write().reset_form())
             })
+            .with_optional_child(self.grid_state.as_ref().filter(|_| !props.mobile).map(
+                |grid_state| grid_state.columns_menu(ctx.link().callback(Msg::UpdateGridState)),
+            ))
             .with_child(
                 Button::new(tr!("Filter"))
                     .icon_class(filter_icon_class)
@@ -447,9 +704,10 @@ impl LoadableComponent for ProxmoxTasks {
             .style("grid-template-columns","minmax(100px,auto) auto minmax(100px,auto) auto minmax(100px,auto) auto 1fr" )
             .with_child(html!{<div>{tr!("Since")}</div>})
             .with_child(
-                Field::new()
-                    .name("since")
-                    .input_type(InputType::Date)
+                Row::new()
+                    .gap(2)
+                    .with_child(Field::new().name("since").input_type(InputType::Date))
+                    .with_child(Field::new().name("_since_time").input_type(InputType::Time))
                 )
             .with_child(html!{<div class="pwt-text-align-end">{tr!("Task Type")}</div>})
             .with_child(TaskTypeSelector::new().name("typefilter"))
@@ -461,13 +719,19 @@ impl LoadableComponent for ProxmoxTasks {
             // second row
             .with_child(html!{<div>{tr!("Until:")}</div>})
             .with_child(
-                Field::new()
-                    .name("until")
-                    .input_type(InputType::Date)
+                Row::new()
+                    .gap(2)
+                    .with_child(Field::new().name("until").input_type(InputType::Date))
+                    .with_child(Field::new().name("_until_time").input_type(InputType::Time))
             )
             .with_child(html!{<div class="pwt-text-align-end">{tr!("User name")}</div>})
             .with_child(Field::new().name("userfilter"));
 
+        if props.guest_filter && props.vmid.is_none() {
+            filter.add_child(html! {<div class="pwt-text-align-end">{tr!("Guest ID")}</div>});
+            filter.add_child(Number::<u32>::new().name("vmid"));
+        }
+
         if let Some((label, input)) = &props.extra_filter {
             filter.add_child(html! {<div class="pwt-text-align-end">{label}</div>});
             filter.add_child(input.clone());
@@ -479,16 +743,35 @@ impl LoadableComponent for ProxmoxTasks {
     }
 
     fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        let columns = self.columns.clone();
-        let link = ctx.link().clone();
+        let view: Html = if ctx.props().mobile {
+            self.render_list(ctx)
+        } else {
+            let columns = match &self.grid_state {
+                Some(grid_state) => grid_state.columns(),
+                None => self.columns.clone(),
+            };
+            let link = ctx.link().clone();
 
-        DataTable::new(columns, self.store.clone())
+            let table = DataTable::new(columns, self.store.clone())
+                .class("pwt-flex-fit")
+                .selection(self.selection.clone())
+                .on_row_dblclick(move |_: &mut _| {
+                    link.send_message(Msg::ShowTask);
+                })
+                .row_render_callback(self.row_render_callback.clone());
+
+            match &self.grid_state {
+                Some(grid_state) => grid_state
+                    .track(table, ctx.link().callback(Msg::UpdateGridState))
+                    .into(),
+                None => table.into(),
+            }
+        };
+
+        Column::new()
             .class("pwt-flex-fit")
-            .selection(self.selection.clone())
-            .on_row_dblclick(move |_: &mut _| {
-                link.send_message(Msg::ShowTask);
-            })
-            .row_render_callback(self.row_render_callback.clone())
+            .with_child(view)
+            .with_optional_child(self.render_load_more(ctx))
             .into()
     }
 
@@ -520,8 +803,23 @@ impl LoadableComponent for ProxmoxTasks {
         ctx: &LoadableComponentContext<Self>,
         old_props: &Self::Properties,
     ) -> bool {
-        if old_props.columns != ctx.props().columns {
-            self.columns = Self::columns(ctx);
+        let props = ctx.props();
+        if old_props.columns != props.columns
+            || old_props.base_url != props.base_url
+            || old_props.nodename != props.nodename
+            || old_props.vmid != props.vmid
+            || old_props.guest_filter != props.guest_filter
+        {
+            self.update_columns(ctx);
+        }
+        if old_props.state_id != props.state_id {
+            self.grid_state = props
+                .state_id
+                .as_deref()
+                .map(|state_id| PersistentGridState::new(state_id, self.columns.clone()));
+        }
+        if old_props.vmid != props.vmid {
+            ctx.link().send_message(Msg::LoadBatch(true));
         }
         true
     }
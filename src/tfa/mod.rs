@@ -1,8 +1,12 @@
+use anyhow::Error;
+use serde_json::Value;
+
 use pwt::props::FieldBuilder as _;
 use pwt::tr;
 use pwt::widget::form::Field;
 use pwt::widget::InputPanel;
 
+use crate::percent_encoding::percent_encode_component;
 use crate::SchemaValidation as _;
 
 mod webauthn;
@@ -26,6 +30,9 @@ pub use tfa_add_webauthn::{ProxmoxTfaAddWebauthn, TfaAddWebauthn};
 mod tfa_add_recovery;
 pub use tfa_add_recovery::{ProxmoxTfaAddRecovery, TfaAddRecovery};
 
+mod webauthn_config_panel;
+pub use webauthn_config_panel::{ProxmoxWebauthnConfigPanel, WebauthnConfigPanel};
+
 mod tfa_confirm_remove;
 
 /// If we're not logged in as `root@pam`, the user needs to provide their password as a
@@ -51,3 +58,37 @@ pub fn add_password_field(panel: InputPanel, large: bool) -> InputPanel {
         panel
     }
 }
+
+/// Unlock the second factors of a user after too many failed attempts.
+///
+/// Calls `PUT /access/users/{userid}/unlock-tfa`.
+pub async fn unlock_tfa(userid: &str) -> Result<(), Error> {
+    let url = format!(
+        "/access/users/{}/unlock-tfa",
+        percent_encode_component(userid)
+    );
+    let _: Value = crate::http_put(&url, None).await?;
+    Ok(())
+}
+
+/// Render the TFA lock status, or `None` if the user is not locked.
+///
+/// `tfa_locked_until` locks out all second factors until the given epoch, while
+/// `totp_locked` only locks TOTP until it gets unlocked by an admin.
+pub fn render_tfa_lock_status(tfa_locked_until: Option<i64>, totp_locked: bool) -> Option<String> {
+    let now = proxmox_time::epoch_i64();
+    match tfa_locked_until {
+        Some(until) if until > now => Some(tr!(
+            "Locked until {0}",
+            crate::utils::render_epoch_short(until)
+        )),
+        _ if totp_locked => Some(tr!("TOTP locked")),
+        _ => None,
+    }
+}
+
+/// Returns true if the user has any kind of TFA lock.
+pub fn tfa_is_locked(tfa_locked_until: Option<i64>, totp_locked: bool) -> bool {
+    let now = proxmox_time::epoch_i64();
+    totp_locked || tfa_locked_until.is_some_and(|until| until > now)
+}
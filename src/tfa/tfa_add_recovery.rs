@@ -14,7 +14,7 @@ use crate::percent_encoding::percent_encode_component;
 
 use pwt_macros::builder;
 
-use crate::utils::copy_text_to_clipboard;
+use crate::utils::{copy_text_to_clipboard, download_text_file};
 use crate::{AuthidSelector, EditWindow};
 
 #[derive(Debug, Deserialize)]
@@ -76,6 +76,7 @@ pub enum Msg {
     RecoveryKeys(RecoveryKeyInfo),
     ShowKeys,
     PrintKeys,
+    DownloadKeys,
 }
 
 #[doc(hidden)]
@@ -105,16 +106,7 @@ fn render_input_form(_form_ctx: FormContext) -> Html {
 
 impl ProxmoxTfaAddRecovery {
     fn recovery_keys_dialog(&self, ctx: &Context<Self>, data: &RecoveryKeyInfo) -> Html {
-        use std::fmt::Write;
-        let text: AttrValue = data
-            .keys
-            .iter()
-            .enumerate()
-            .fold(String::new(), |mut acc, (i, key)| {
-                let _ = writeln!(acc, "{i}: {key}\n");
-                acc
-            })
-            .into();
+        let text: AttrValue = format_recovery_keys(&data.keys).into();
 
         Dialog::new(tr!("Recovery Keys for user '{}'", data.userid))
             .on_close(ctx.props().on_close.clone())
@@ -148,6 +140,11 @@ impl ProxmoxTfaAddRecovery {
                                     .class("pwt-scheme-primary")
                                     .on_activate(move |_| copy_text_to_clipboard(&text)),
                             )
+                            .with_child(
+                                Button::new(tr!("Download"))
+                                    .icon_class("fa fa-download")
+                                    .onclick(ctx.link().callback(|_| Msg::DownloadKeys)),
+                            )
                             .with_child(
                                 Button::new(tr!("Print Recovery Keys"))
                                     .icon_class("fa fa-print")
@@ -196,6 +193,21 @@ impl Component for ProxmoxTfaAddRecovery {
                 }
                 true
             }
+            Msg::DownloadKeys => {
+                if let Some(data) = &self.recovery_keys {
+                    let filename = format!("recovery-keys-{}.txt", data.userid);
+                    let text = format!(
+                        "Recovery Keys for '{}' ({})\n\n{}",
+                        data.userid,
+                        current_host(),
+                        format_recovery_keys(&data.keys),
+                    );
+                    if let Err(err) = download_text_file(&filename, &text) {
+                        log::error!("unable to download recovery keys: {err}");
+                    }
+                }
+                false
+            }
         }
     }
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -235,22 +247,31 @@ impl From<TfaAddRecovery> for VNode {
     }
 }
 
-fn create_paperkey_page(data: &RecoveryKeyInfo, print_counter: usize) -> Html {
-    let document = gloo_utils::document();
-
-    let userid = data.userid.clone();
-    let title = document.title();
-    let host = document.location().unwrap().host().unwrap();
-
+/// Format the keys as numbered list, one key per line.
+fn format_recovery_keys(keys: &[String]) -> String {
     use std::fmt::Write;
-    let key_text: String = data
-        .keys
-        .iter()
+    keys.iter()
         .enumerate()
         .fold(String::new(), |mut acc, (i, key)| {
             let _ = writeln!(acc, "{i}: {key}");
             acc
-        });
+        })
+}
+
+fn current_host() -> String {
+    gloo_utils::document()
+        .location()
+        .and_then(|location| location.host().ok())
+        .unwrap_or_default()
+}
+
+fn create_paperkey_page(data: &RecoveryKeyInfo, print_counter: usize) -> Html {
+    let userid = &data.userid;
+    let title = gloo_utils::document().title();
+    let host = current_host();
+    let date = crate::utils::render_epoch_short(proxmox_time::epoch_i64());
+    let key_text = format_recovery_keys(&data.keys);
+    let hint = tr!("Each key can only be used once. Store this sheet in a safe place.");
 
     let html = format!(
         r###"
@@ -259,17 +280,33 @@ fn create_paperkey_page(data: &RecoveryKeyInfo, print_counter: usize) -> Html {
             <script>
                 window.addEventListener('DOMContentLoaded', (ev) => window.print());
             </script>
-            <style>@media print and (max-height: 150mm) {{
-                h4, p  {{ margin: 0; font-size: 1em; }}
-            }}</style>
+            <style>
+                @page {{ margin: 20mm; }}
+                body {{ font-family: sans-serif; padding: 5px; }}
+                .keys {{
+                    font-size: 1.5em;
+                    line-height: 1.5em;
+                    font-family: monospace;
+                    white-space: pre-wrap;
+                    overflow-wrap: break-word;
+                    border: 1px solid #000;
+                    padding: 0.5em 1em;
+                }}
+                @media print {{
+                    body {{ padding: 0; color: #000; background: #fff; }}
+                }}
+                @media print and (max-height: 150mm) {{
+                    h4, p {{ margin: 0; font-size: 1em; }}
+                }}
+            </style>
         </head>
-        <body style="padding: 5px;">
+        <body>
             <h4>Recovery Keys for '{userid}' - {title} ({host})</h4>
-            <p style="font-size:1.5em;line-height:1.5em;font-family:monospace;white-space:pre-wrap;overflow-wrap:break-word;">
-{key_text}
-            </p>
+            <p>{date}</p>
+            <p class="keys">{key_text}</p>
+            <p>{hint}</p>
         </body>
-    </html>`;
+    </html>
 "###
     );
 
@@ -277,3 +314,15 @@ fn create_paperkey_page(data: &RecoveryKeyInfo, print_counter: usize) -> Html {
 
     html! {<iframe key={print_counter} src={data_url}/>}
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_recovery_keys() {
+        let keys = vec!["aaaa-bbbb".to_string(), "cccc-dddd".to_string()];
+        assert_eq!(format_recovery_keys(&keys), "0: aaaa-bbbb\n1: cccc-dddd\n");
+        assert_eq!(format_recovery_keys(&[]), "");
+    }
+}
@@ -7,25 +7,79 @@ use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
-use pwt::widget::form::{Field, FormContext};
+use pwt::widget::form::{Combobox, Field, FormContext, Number};
 use pwt::widget::{Button, InputPanel, Row};
 
+use proxmox_schema::{IntegerSchema, Schema};
+
 use crate::percent_encoding::percent_encode_component;
 
 use pwt_macros::builder;
 
-use crate::{AuthidSelector, EditWindow};
+use crate::{AuthidSelector, EditWindow, QrCode};
+
+const DEFAULT_PERIOD: u64 = 30;
+const DEFAULT_DIGITS: u64 = 6;
+const DEFAULT_ALGORITHM: &str = "SHA1";
+
+const TOTP_PERIOD_SCHEMA: Schema = IntegerSchema::new("TOTP period in seconds.")
+    .minimum(10)
+    .default(DEFAULT_PERIOD as isize)
+    .schema();
+
+const TOTP_DIGITS_SCHEMA: Schema = IntegerSchema::new("Number of digits of the TOTP codes.")
+    .minimum(6)
+    .maximum(8)
+    .default(DEFAULT_DIGITS as isize)
+    .schema();
+
+/// The TOTP parameters, falling back to the defaults for empty fields.
+fn totp_options(form_ctx: &FormContext) -> (u64, u64, String) {
+    let form = form_ctx.read();
+    let period = form.get_field_value("period").and_then(|v| v.as_u64());
+    let digits = form.get_field_value("digits").and_then(|v| v.as_u64());
+    let algorithm = form.get_field_text("algorithm");
+    (
+        period.unwrap_or(DEFAULT_PERIOD),
+        digits.unwrap_or(DEFAULT_DIGITS),
+        if algorithm.is_empty() {
+            DEFAULT_ALGORITHM.to_string()
+        } else {
+            algorithm
+        },
+    )
+}
+
+fn totp_link(
+    issuer: &str,
+    userid: &str,
+    secret: &str,
+    period: u64,
+    digits: u64,
+    algorithm: &str,
+) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={secret}&period={period}&digits={digits}&algorithm={algorithm}&issuer={0}",
+        percent_encode_component(issuer),
+        percent_encode_component(userid),
+    )
+}
 
 fn extract_totp_link(form_ctx: &FormContext) -> String {
     let userid = form_ctx.read().get_field_text("userid");
     let issuer = form_ctx.read().get_field_text("issuer");
     let secret = form_ctx.read().get_field_text("secret");
+    let (period, digits, algorithm) = totp_options(form_ctx);
 
-    format!(
-        "otpauth://totp/{}:{}?secret={secret}&period=30&digits=6&algorithm=SHA1&issuer={0}",
-        percent_encode_component(&issuer),
-        percent_encode_component(&userid),
-    )
+    totp_link(&issuer, &userid, &secret, period, digits, &algorithm)
+}
+
+/// Check that the verification code looks like a code generated with the configured digits.
+fn validate_totp_code(code: &str, digits: u64) -> Result<(), Error> {
+    if code.len() as u64 != digits || !code.chars().all(|c| c.is_ascii_digit()) {
+        bail!(tr!("Expected a code with {0} digits.", digits));
+    }
+    Ok(())
 }
 
 async fn create_item(form_ctx: FormContext, base_url: String) -> Result<(), Error> {
@@ -78,6 +132,7 @@ pub struct ProxmoxTfaAddTotp {
 
 fn render_input_form(form_ctx: FormContext, secret: AttrValue) -> Html {
     let totp_link = extract_totp_link(&form_ctx);
+    let (_, digits, _) = totp_options(&form_ctx);
 
     let panel = InputPanel::new()
         .min_width(600)
@@ -128,13 +183,45 @@ fn render_input_form(form_ctx: FormContext, secret: AttrValue) -> Html {
             ),
         )
         .with_custom_child(
-            html! {<div key="qrcode" style="text-align:center;">{render_qrcode(&totp_link)}</div>},
+            html! {<div key="qrcode" style="text-align:center;">{QrCode::new(totp_link)}</div>},
         )
         .with_field(
             tr!("Verify Code"),
-            Field::new().name("value").required(true).placeholder(tr!(
-                "Scan QR code in a TOTP app and enter an auth. code here"
-            )),
+            Field::new()
+                .name("value")
+                .required(true)
+                .validate(move |code: &String| validate_totp_code(code.trim(), digits))
+                .placeholder(tr!(
+                    "Scan QR code in a TOTP app and enter an auth. code here"
+                )),
+        )
+        .with_advanced_spacer()
+        .with_advanced_field(
+            tr!("Algorithm"),
+            Combobox::from_key_value_pairs([
+                ("SHA1", "SHA1"),
+                ("SHA256", "SHA256"),
+                ("SHA512", "SHA512"),
+            ])
+            .name("algorithm")
+            .submit(false)
+            .placeholder(DEFAULT_ALGORITHM),
+        )
+        .with_advanced_field(
+            tr!("Digits"),
+            Number::<u64>::new()
+                .name("digits")
+                .submit(false)
+                .schema(&TOTP_DIGITS_SCHEMA)
+                .placeholder(DEFAULT_DIGITS.to_string()),
+        )
+        .with_advanced_field(
+            tr!("Period"),
+            Number::<u64>::new()
+                .name("period")
+                .submit(false)
+                .schema(&TOTP_PERIOD_SCHEMA)
+                .placeholder(DEFAULT_PERIOD.to_string()),
         );
 
     super::add_password_field(panel, false).into()
@@ -163,6 +250,7 @@ impl Component for ProxmoxTfaAddTotp {
         };
 
         EditWindow::new(tr!("Add a TOTP login factor"))
+            .advanced_checkbox(true)
             .renderer({
                 let secret = self.default_secret.clone();
                 move |form_ctx: &FormContext| render_input_form(form_ctx.clone(), secret.clone())
@@ -193,13 +281,6 @@ fn validate_secret(secret: &String) -> Result<(), Error> {
     Ok(())
 }
 
-fn render_qrcode(text: &str) -> Html {
-    let code = qrcode::QrCode::new(text).unwrap();
-    let svg_xml = code.render::<qrcode::render::svg::Color>().build();
-
-    Html::from_html_unchecked(AttrValue::from(svg_xml))
-}
-
 fn randomize_secret() -> String {
     let mut rnd: [u8; 32] = [0u8; 32];
 
@@ -219,3 +300,24 @@ fn randomize_secret() -> String {
     }
     data
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_totp_link() {
+        assert_eq!(
+            totp_link("Proxmox", "root@pam", "ABCD", 60, 8, "SHA256"),
+            "otpauth://totp/Proxmox:root%40pam?secret=ABCD&period=60&digits=8&algorithm=SHA256&issuer=Proxmox"
+        );
+    }
+
+    #[test]
+    fn test_validate_totp_code() {
+        assert!(validate_totp_code("123456", 6).is_ok());
+        assert!(validate_totp_code("12345678", 8).is_ok());
+        assert!(validate_totp_code("123456", 8).is_err());
+        assert!(validate_totp_code("12a456", 6).is_err());
+    }
+}
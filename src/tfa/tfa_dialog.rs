@@ -15,6 +15,8 @@ use pwt_macros::builder;
 
 use proxmox_login::SecondFactorChallenge;
 
+use crate::{Status, StatusRow};
+
 use super::WebAuthn;
 
 #[derive(Derivative)]
@@ -46,6 +48,11 @@ pub struct TfaDialog {
     #[prop_or_default]
     pub on_webauthn: Option<Callback<String>>,
 
+    /// Warn if less than this number of unused recovery keys are left.
+    #[prop_or(4)]
+    #[builder]
+    pub recovery_warning_threshold: usize,
+
     /// Mobile Layout
     ///
     /// - do not set dialog min-width and min-height
@@ -127,14 +134,6 @@ fn render_recovery(callback: Option<Callback<String>>, available_keys: &[usize])
         .with_child(html! {<div>{"Please enter one of your single-use recovery keys"}</div>})
         .with_child(html! {<div>{format!{"Available recovery keys: {:?}", available_keys}}</div>})
         .with_child(Field::new().name("data").required(true).autofocus(true))
-        .with_optional_child((available_keys.len() <= 13).then(|| {
-            html! {<div>{
-                format!(
-                    "Less than {0} recovery keys available. Please generate a new set after login!",
-                    available_keys.len() + 1,
-                )
-            }</div>}
-        }))
         .with_child(html! {<div style="flex: 1 1 auto;"/>})
         .with_child(
             SubmitButton::new()
@@ -241,8 +240,24 @@ impl Component for ProxmoxTfaDialog {
             });
         }
 
+        // the challenge lists the unused recovery keys
+        let recovery_keys_left = props.challenge.challenge.recovery.0.len();
+        let recovery_warning = (props.challenge.challenge.recovery.is_available()
+            && recovery_keys_left < props.recovery_warning_threshold)
+            .then(|| {
+                StatusRow::new(tr!("Recovery Keys"))
+                    .class("pwt-border-bottom")
+                    .padding(2)
+                    .icon_class(classes!("fa", "fa-fw", Status::Warning))
+                    .status(tr!(
+                        "Only {0} unused keys left, please generate a new set after login.",
+                        recovery_keys_left
+                    ))
+            });
+
         let mut dialog = Dialog::new("Second login factor required")
             .resizable(true)
+            .with_optional_child(recovery_warning)
             .with_child(panel)
             .on_close(props.on_close.clone());
 
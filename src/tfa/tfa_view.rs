@@ -17,7 +17,7 @@ use pwt::widget::{Button, Mask, Toolbar};
 use pwt_macros::builder;
 
 use crate::{
-    LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
+    ConfirmButton, LoadableComponent, LoadableComponentContext, LoadableComponentMaster,
     LoadableComponentScopeExt, LoadableComponentState,
 };
 
@@ -59,6 +59,8 @@ pub(super) struct TfaEntry {
     pub created: i64,
     pub enable: bool,
     pub locked: bool,
+    pub tfa_locked_until: Option<i64>,
+    pub totp_locked: bool,
 }
 
 impl ExtractPrimaryKey for TfaEntry {
@@ -93,6 +95,8 @@ pub enum Msg {
     Edit,
     Remove(Option<String>),
     RemoveResult(Result<(), Error>),
+    Unlock,
+    UnlockResult(Result<(), Error>),
 }
 
 #[derive(PartialEq)]
@@ -169,6 +173,8 @@ impl LoadableComponent for ProxmoxTfaView {
                         created: typed_tfa_info.info.created,
                         enable: typed_tfa_info.info.enable,
                         locked: tfa_locked || typed_tfa_info.ty == TfaType::Totp && totp_locked,
+                        tfa_locked_until: tfa_user.tfa_locked_until,
+                        totp_locked,
                     });
                 }
             }
@@ -235,6 +241,25 @@ impl LoadableComponent for ProxmoxTfaView {
                 ctx.link().send_reload();
                 false
             }
+            Msg::Unlock => {
+                let info = match self.get_selected_record() {
+                    Some(info) => info,
+                    None => return true,
+                };
+                let link = ctx.link().clone();
+                self.spawn(async move {
+                    link.send_message(Msg::UnlockResult(super::unlock_tfa(&info.user_id).await))
+                });
+                false
+            }
+            Msg::UnlockResult(res) => {
+                if let Err(err) = res {
+                    ctx.link()
+                        .show_error(tr!("Unable to unlock TFA"), err, true);
+                }
+                ctx.link().send_reload();
+                false
+            }
         }
     }
 
@@ -245,6 +270,9 @@ impl LoadableComponent for ProxmoxTfaView {
             .as_ref()
             .map(|item| item.tfa_type == TfaType::Recovery)
             .unwrap_or(true);
+        let unlock_disabled = !selected_record
+            .as_ref()
+            .is_some_and(|item| super::tfa_is_locked(item.tfa_locked_until, item.totp_locked));
 
         let add_menu = Menu::new()
             .with_item(
@@ -288,6 +316,19 @@ impl LoadableComponent for ProxmoxTfaView {
                     .disabled(remove_disabled)
                     .onclick(ctx.link().change_view_callback(|_| Some(ViewState::Remove))),
             )
+            .with_spacer()
+            .with_child(
+                ConfirmButton::new(tr!("Unlock TFA"))
+                    .disabled(unlock_disabled)
+                    .confirm_message(match &selected_record {
+                        Some(item) => tr!(
+                            "Are you sure you want to unlock TFA for '{0}'?",
+                            item.user_id
+                        ),
+                        None => String::new(),
+                    })
+                    .on_activate(ctx.link().callback(|_| Msg::Unlock)),
+            )
             .with_flex_spacer()
             .with_child({
                 let loading = self.loading();
@@ -392,6 +433,16 @@ thread_local! {
                 a.enable.cmp(&b.enable)
             })
             .into(),
+        DataTableColumn::new(tr!("Lock Status"))
+            .width("170px")
+            .render(|item: &TfaEntry| html!{
+                super::render_tfa_lock_status(item.tfa_locked_until, item.totp_locked)
+                    .unwrap_or_default()
+            })
+            .sorter(|a: &TfaEntry, b: &TfaEntry| {
+                a.locked.cmp(&b.locked)
+            })
+            .into(),
         DataTableColumn::new(tr!("TFA Type"))
             .width("100px")
             .render(|item: &TfaEntry| html!{
@@ -0,0 +1,213 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Error};
+use serde_json::Value;
+
+use yew::html::IntoPropValue;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::prelude::*;
+use pwt::widget::form::{Checkbox, Field, FormContext};
+use pwt::widget::{Button, Column, Fa, InputPanel, Row};
+
+use pwt_macros::builder;
+
+use crate::form::delete_empty_values;
+use crate::{ObjectGrid, ObjectGridRow, Status};
+
+/// WebAuthn configuration panel.
+///
+/// Shows and edits the relying party, origin and ID used for WebAuthn. This needs to be
+/// configured before users can add WebAuthn authenticators.
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct WebauthnConfigPanel {
+    /// The URL of the WebAuthn configuration endpoint.
+    #[prop_or("/config/access/tfa/webauthn".into())]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub base_url: AttrValue,
+}
+
+impl Default for WebauthnConfigPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebauthnConfigPanel {
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+/// Validate the origin (`scheme://host[:port]`).
+///
+/// WebAuthn only works if the origin matches the URL used to access the web interface, so
+/// it has to match `current` (if known).
+fn validate_origin(origin: &str, current: Option<&str>) -> Result<(), Error> {
+    let Some((scheme, host)) = origin.split_once("://") else {
+        bail!(tr!("Expected an origin like 'https://host.example.com'."));
+    };
+    if !matches!(scheme, "http" | "https") || host.is_empty() || host.contains('/') {
+        bail!(tr!("Expected an origin like 'https://host.example.com'."));
+    }
+    if let Some(current) = current {
+        if origin != current {
+            bail!(tr!(
+                "The origin does not match the current location '{0}'.",
+                current
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn current_origin() -> Option<String> {
+    gloo_utils::window().location().origin().ok()
+}
+
+fn render_warning(text: String) -> Html {
+    Row::new()
+        .class(pwt::css::AlignItems::Center)
+        .gap(2)
+        .with_child(Fa::from(Status::Warning))
+        .with_child(text)
+        .into()
+}
+
+fn webauthn_editor(form_ctx: &FormContext, _name: &str, _value: &Value, record: &Value) -> Html {
+    let configured_id = record["id"].as_str().unwrap_or("");
+    let id_changed =
+        !configured_id.is_empty() && form_ctx.read().get_field_text("id").trim() != configured_id;
+
+    let auto_fill = Button::new(tr!("Auto-fill"))
+        .icon_class("fa fa-fw fa-pencil-square-o")
+        .onclick({
+            let form_ctx = form_ctx.clone();
+            move |_| {
+                let location = gloo_utils::window().location();
+                let (Ok(origin), Ok(hostname)) = (location.origin(), location.hostname()) else {
+                    return;
+                };
+                let mut form = form_ctx.write();
+                form.set_field_value("rp", hostname.clone().into());
+                form.set_field_value("origin", origin.into());
+                form.set_field_value("id", hostname.into());
+            }
+        });
+
+    Column::new()
+        .padding(4)
+        .gap(2)
+        .with_child(
+            InputPanel::new()
+                .min_width(500)
+                .with_large_field(
+                    tr!("Relying Party"),
+                    Field::new()
+                        .name("rp")
+                        .required(true)
+                        .autofocus(true)
+                        .placeholder("host.example.com"),
+                )
+                .with_large_field(
+                    tr!("Origin"),
+                    Field::new()
+                        .name("origin")
+                        .submit_empty(true)
+                        .placeholder(current_origin().unwrap_or_default())
+                        .validate(|origin: &String| {
+                            validate_origin(origin, current_origin().as_deref())
+                        }),
+                )
+                .with_large_field(
+                    tr!("ID"),
+                    Field::new()
+                        .name("id")
+                        .required(true)
+                        .placeholder("host.example.com"),
+                )
+                .with_large_field(
+                    tr!("Allow Subdomains"),
+                    Checkbox::new().name("allow-subdomains"),
+                ),
+        )
+        .with_child(Row::new().with_flex_spacer().with_child(auto_fill))
+        .with_optional_child(id_changed.then(|| {
+            render_warning(tr!(
+                "Changing the ID breaks existing WebAuthn TFA entries, users need to register them again."
+            ))
+        }))
+        .into()
+}
+
+async fn store_webauthn_config(base_url: AttrValue, form_ctx: FormContext) -> Result<(), Error> {
+    let data = form_ctx.get_submit_data();
+    let data = delete_empty_values(&data, &["origin", "allow-subdomains"], false);
+    crate::http_put(base_url.as_str(), Some(data)).await
+}
+
+#[doc(hidden)]
+pub struct ProxmoxWebauthnConfigPanel {
+    rows: Rc<Vec<ObjectGridRow>>,
+}
+
+impl Component for ProxmoxWebauthnConfigPanel {
+    type Message = ();
+    type Properties = WebauthnConfigPanel;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let rows = Rc::new(vec![
+            ObjectGridRow::new("rp", tr!("Relying Party"))
+                .editor(webauthn_editor)
+                .placeholder(tr!("Not configured"))
+                .required(true),
+            ObjectGridRow::new("origin", tr!("Origin"))
+                .editor(webauthn_editor)
+                .placeholder(tr!("Not configured"))
+                .required(true),
+            ObjectGridRow::new("id", tr!("ID"))
+                .editor(webauthn_editor)
+                .placeholder(tr!("Not configured"))
+                .required(true),
+            ObjectGridRow::new("allow-subdomains", tr!("Allow Subdomains"))
+                .editor(webauthn_editor)
+                .boolean(Some(false))
+                .required(true),
+        ]);
+        Self { rows }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let base_url = ctx.props().base_url.clone();
+
+        ObjectGrid::new()
+            .editable(true)
+            .loader(base_url.to_string())
+            .on_submit(move |form_ctx| store_webauthn_config(base_url.clone(), form_ctx))
+            .rows(Rc::clone(&self.rows))
+            .into()
+    }
+}
+
+impl From<WebauthnConfigPanel> for VNode {
+    fn from(val: WebauthnConfigPanel) -> Self {
+        let comp = VComp::new::<ProxmoxWebauthnConfigPanel>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_origin() {
+        let current = Some("https://pbs.example.com:8007");
+        assert!(validate_origin("https://pbs.example.com:8007", current).is_ok());
+        assert!(validate_origin("https://other.example.com:8007", current).is_err());
+        assert!(validate_origin("https://pbs.example.com:8007/", None).is_err());
+        assert!(validate_origin("pbs.example.com", None).is_err());
+        assert!(validate_origin("ftp://pbs.example.com", None).is_err());
+    }
+}
@@ -1,19 +1,83 @@
 use std::rc::Rc;
 
+use wasm_bindgen::JsCast;
+
 use yew::html::IntoEventCallback;
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
+use pwt::state::PersistentState;
+use pwt::widget::form::Combobox;
 use pwt::widget::{Container, Dialog, ThemeDensitySelector, ThemeModeSelector, ThemeNameSelector};
 
 use pwt_macros::builder;
 
+/// State key used to store the accent color.
+///
+/// The local storage is separate for each origin, so each product has its own setting.
+const ACCENT_COLOR_STATE: &str = "ProxmoxThemeAccentColor";
+
+/// CSS variable overwritten by the accent color.
+const ACCENT_COLOR_VARIABLE: &str = "--pwt-color-primary";
+
+/// Selectable accent colors (name, CSS color).
+const ACCENT_COLORS: &[(&str, &str)] = &[
+    ("blue", "#1565c0"),
+    ("green", "#2e7d32"),
+    ("orange", "#e65100"),
+    ("purple", "#6a1b9a"),
+    ("red", "#c62828"),
+    ("teal", "#00796b"),
+];
+
+fn accent_color_value(name: &str) -> Option<&'static str> {
+    ACCENT_COLORS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, color)| *color)
+}
+
+fn accent_color_text(name: &str) -> String {
+    match name {
+        "blue" => tr!("Blue"),
+        "green" => tr!("Green"),
+        "orange" => tr!("Orange"),
+        "purple" => tr!("Purple"),
+        "red" => tr!("Red"),
+        "teal" => tr!("Teal"),
+        _ => tr!("Default"),
+    }
+}
+
+/// Apply the accent color with the given name, unknown names restore the theme default.
+fn apply_accent_color(name: &str) {
+    let Some(root) = gloo_utils::document()
+        .document_element()
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return;
+    };
+    let style = root.style();
+    let _ = match accent_color_value(name) {
+        Some(color) => style.set_property(ACCENT_COLOR_VARIABLE, color),
+        None => style.remove_property(ACCENT_COLOR_VARIABLE).map(drop),
+    };
+}
+
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
 pub struct ThemeDialog {
     #[builder_cb(IntoEventCallback, into_event_callback, ())]
     #[prop_or_default]
     pub on_close: Option<Callback<()>>,
+
+    /// Called when the theme settings may have changed.
+    ///
+    /// This is the case when the accent color changes, and when the dialog gets closed
+    /// (theme name, density and mode are applied directly by their selectors).
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    pub on_theme_change: Option<Callback<()>>,
 }
 
 impl Default for ThemeDialog {
@@ -26,25 +90,75 @@ impl ThemeDialog {
     pub fn new() -> Self {
         yew::props!(Self {})
     }
+
+    /// Apply the stored accent color.
+    ///
+    /// Should be called once on application startup.
+    pub fn apply_saved_accent_color() {
+        apply_accent_color(&PersistentState::<String>::new(ACCENT_COLOR_STATE));
+    }
 }
 
 #[doc(hidden)]
-pub struct ProxmoxThemeDialog {}
+pub enum Msg {
+    AccentColor(String),
+    Close,
+}
+
+#[doc(hidden)]
+pub struct ProxmoxThemeDialog {
+    accent_color: PersistentState<String>,
+}
 
 impl Component for ProxmoxThemeDialog {
-    type Message = ();
+    type Message = Msg;
     type Properties = ThemeDialog;
 
     fn create(_ctx: &Context<Self>) -> Self {
-        Self {}
+        Self {
+            accent_color: PersistentState::new(ACCENT_COLOR_STATE),
+        }
     }
 
-    fn view(&self, ctx: &Context<Self>) -> Html {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         let props = ctx.props();
+        match msg {
+            Msg::AccentColor(name) => {
+                apply_accent_color(&name);
+                self.accent_color.update(name);
+                if let Some(on_theme_change) = &props.on_theme_change {
+                    on_theme_change.emit(());
+                }
+                true
+            }
+            Msg::Close => {
+                if let Some(on_theme_change) = &props.on_theme_change {
+                    on_theme_change.emit(());
+                }
+                if let Some(on_close) = &props.on_close {
+                    on_close.emit(());
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let accent_colors = std::iter::once(("", accent_color_text("")))
+            .chain(
+                ACCENT_COLORS
+                    .iter()
+                    .map(|(name, _)| (*name, accent_color_text(name))),
+            )
+            .collect::<Vec<_>>();
+
+        let accent_color = accent_color_value(&self.accent_color)
+            .map(|_| self.accent_color.to_string())
+            .unwrap_or_default();
 
         Dialog::new(tr!("Theme"))
             .min_width(400)
-            .on_close(props.on_close.clone())
+            .on_close(ctx.link().callback(|_| Msg::Close))
             .with_child(
                 Container::new()
                     .padding(4)
@@ -55,7 +169,13 @@ impl Component for ProxmoxThemeDialog {
                     .with_child(tr!("Density"))
                     .with_child(ThemeDensitySelector::new())
                     .with_child(tr!("Theme mode"))
-                    .with_child(ThemeModeSelector::new()),
+                    .with_child(ThemeModeSelector::new())
+                    .with_child(tr!("Accent color"))
+                    .with_child(
+                        Combobox::from_key_value_pairs(accent_colors)
+                            .value(accent_color)
+                            .on_change(ctx.link().callback(Msg::AccentColor)),
+                    ),
             )
             .into()
     }
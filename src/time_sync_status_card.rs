@@ -0,0 +1,227 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde::Deserialize;
+
+use yew::html::{IntoEventCallback, IntoPropValue};
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::css::{AlignItems, FontColor};
+use pwt::prelude::*;
+use pwt::widget::{error_message, Button, Column, Container, Fa, Panel, Progress, Row};
+use pwt::AsyncAbortGuard;
+
+use pwt_macros::builder;
+
+use crate::utils::activatable;
+use crate::{Status, StatusRow};
+
+/// Node time status (`GET /nodes/{node}/time`).
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct NodeTimeStatus {
+    /// Seconds since epoch (UTC).
+    pub time: i64,
+    /// Seconds since epoch, in the node's local time zone.
+    #[serde(default)]
+    pub localtime: Option<i64>,
+    /// Time zone of the node.
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Severity of a clock offset (in seconds), given the warning and critical thresholds.
+pub fn time_offset_status(offset: f64, warning_threshold: f64, critical_threshold: f64) -> Status {
+    let offset = offset.abs();
+    if offset.is_nan() {
+        Status::Unknown
+    } else if offset >= critical_threshold {
+        Status::Error
+    } else if offset >= warning_threshold {
+        Status::Warning
+    } else {
+        Status::Success
+    }
+}
+
+/// Format a clock offset (in seconds) with sign and a sensible unit.
+pub fn format_time_offset(offset: f64) -> String {
+    if offset.abs() < 1.0 {
+        format!("{:+.0} ms", offset * 1000.0)
+    } else {
+        format!("{offset:+.1} s")
+    }
+}
+
+/// Card showing the time synchronization state of a node.
+///
+/// The node time API does not report the state of the time synchronization daemon, so this
+/// shows the offset between the node's clock and the clock of the browser. A drifting clock breaks ticket validation and
+/// cluster communication, so offsets above the thresholds are flagged as warning or error.
+#[derive(Properties, Clone, PartialEq)]
+#[builder]
+pub struct TimeSyncStatusCard {
+    /// The time API endpoint of the node.
+    #[prop_or(AttrValue::Static("/nodes/localhost/time"))]
+    #[builder(IntoPropValue, into_prop_value)]
+    pub base_url: AttrValue,
+
+    /// Offset (in seconds) at which a warning is shown.
+    #[prop_or(2.0)]
+    #[builder]
+    pub warning_threshold: f64,
+
+    /// Offset (in seconds) at which an error is shown.
+    #[prop_or(30.0)]
+    #[builder]
+    pub critical_threshold: f64,
+
+    /// Reload interval in milliseconds.
+    #[prop_or(60_000)]
+    #[builder]
+    pub reload_interval: u32,
+
+    /// Activation callback, for example to open the time configuration.
+    #[prop_or_default]
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    pub on_activate: Option<Callback<()>>,
+}
+
+impl Default for TimeSyncStatusCard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSyncStatusCard {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        yew::props!(Self {})
+    }
+}
+
+#[doc(hidden)]
+pub enum Msg {
+    Load,
+    LoadResult(Result<(NodeTimeStatus, f64), Error>),
+}
+
+#[doc(hidden)]
+pub struct ProxmoxTimeSyncStatusCard {
+    data: Option<Result<(NodeTimeStatus, f64), String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    reload_timeout: Option<Timeout>,
+}
+
+impl ProxmoxTimeSyncStatusCard {
+    fn render_status(&self, ctx: &Context<Self>, offset: f64) -> Html {
+        let props = ctx.props();
+        let offset_status =
+            |offset| time_offset_status(offset, props.warning_threshold, props.critical_threshold);
+        let offset_icon = |offset| status_icon_class(offset_status(offset));
+
+        let mut column = Column::new().padding(2).gap(1);
+
+        column.add_child(
+            StatusRow::new(tr!("Offset to Browser"))
+                .icon_class(offset_icon(offset))
+                .status(format_time_offset(offset)),
+        );
+
+        if offset.abs() >= props.warning_threshold {
+            let color = match offset_status(offset) {
+                Status::Error => FontColor::Error,
+                _ => FontColor::Warning,
+            };
+            column.add_child(Container::new().class(color).with_child(tr!(
+                "The clock of the server differs from the clock of this browser. \
+                     Please check the time synchronization, as clock drift can break \
+                     logins and cluster communication."
+            )));
+        }
+
+        activatable(column, props.on_activate.as_ref()).into()
+    }
+}
+
+fn status_icon_class(status: Status) -> Classes {
+    classes!("fa", "fa-fw", Classes::from(status))
+}
+
+impl Component for ProxmoxTimeSyncStatusCard {
+    type Message = Msg;
+    type Properties = TimeSyncStatusCard;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            reload_timeout: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                self.reload_timeout = None;
+                let link = ctx.link().clone();
+                let url = props.base_url.clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let start = js_sys::Date::now();
+                    let result = crate::http_get::<NodeTimeStatus>(&*url, None).await;
+                    // assume the server time was taken halfway through the request
+                    let client_time = (start + js_sys::Date::now()) / 2000.0;
+                    let result = result.map(|status| {
+                        let offset = status.time as f64 - client_time;
+                        (status, offset)
+                    });
+                    link.send_message(Msg::LoadResult(result));
+                }));
+                false
+            }
+            Msg::LoadResult(result) => {
+                self.data = Some(result.map_err(|err| err.to_string()));
+                let link = ctx.link().clone();
+                self.reload_timeout = Some(Timeout::new(props.reload_interval, move || {
+                    link.send_message(Msg::Load);
+                }));
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = match &self.data {
+            None => Progress::new().class("pwt-delay-visibility").into(),
+            Some(Err(err)) => error_message(err).padding(2).into(),
+            Some(Ok((_status, offset))) => self.render_status(ctx, *offset),
+        };
+
+        Panel::new()
+            .border(true)
+            .title(
+                Row::new()
+                    .class(AlignItems::Center)
+                    .gap(2)
+                    .with_child(Fa::new("clock-o"))
+                    .with_child(tr!("Time Synchronization"))
+                    .into_html(),
+            )
+            .with_tool(
+                Button::refresh(self.data.is_none())
+                    .on_activate(ctx.link().callback(|_| Msg::Load)),
+            )
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<TimeSyncStatusCard> for VNode {
+    fn from(val: TimeSyncStatusCard) -> Self {
+        let comp = VComp::new::<ProxmoxTimeSyncStatusCard>(Rc::new(val), None);
+        VNode::from(comp)
+    }
+}
@@ -9,31 +9,37 @@ use proxmox_client::ApiResponseData;
 use pwt::css::ColorScheme;
 use serde_json::{json, Value};
 
+use yew::html::IntoPropValue;
 use yew::virtual_dom::{Key, VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::state::{Selection, Store};
 use pwt::widget::data_table::{DataTable, DataTableColumn, DataTableHeader};
 use pwt::widget::form::{Checkbox, DisplayField, Field, FormContext, InputType};
-use pwt::widget::{
-    Button, Column, Container, Dialog, FieldLabel, InputPanel, Row, Toolbar, Tooltip,
-};
+use pwt::widget::{Button, Column, Container, Dialog, FieldLabel, InputPanel, Toolbar};
 
 use crate::percent_encoding::percent_encode_component;
-use crate::utils::{
-    copy_text_to_clipboard, epoch_to_input_value, render_boolean, render_epoch_short,
-};
+use crate::utils::{epoch_to_input_value, render_boolean, render_epoch_short};
 use crate::{
-    AuthidSelector, ConfirmButton, EditWindow, LoadableComponent, LoadableComponentContext,
-    LoadableComponentMaster, LoadableComponentScope, LoadableComponentScopeExt,
-    LoadableComponentState, PermissionPanel,
+    AuthidSelector, ConfirmButton, CopyField, EditWindow, LoadableComponent,
+    LoadableComponentContext, LoadableComponentMaster, LoadableComponentScope,
+    LoadableComponentScopeExt, LoadableComponentState, PermissionPanel,
 };
 
-async fn load_api_tokens() -> Result<Vec<ApiToken>, Error> {
+use pwt_macros::builder;
+
+async fn load_api_tokens(userid: Option<AttrValue>) -> Result<Vec<ApiToken>, Error> {
     let url = "/access/users/?include_tokens=1";
     let users: Vec<UserWithTokens> = crate::http_get(url, None).await?;
 
-    Ok(users.into_iter().flat_map(|user| user.tokens).collect())
+    Ok(users
+        .into_iter()
+        .filter(|user| match &userid {
+            Some(userid) => user.userid.as_str() == userid.as_str(),
+            None => true,
+        })
+        .flat_map(|user| user.tokens)
+        .collect())
 }
 
 async fn create_token(
@@ -96,7 +102,18 @@ async fn update_token(form_ctx: FormContext) -> Result<(), Error> {
 }
 
 #[derive(PartialEq, Properties)]
-pub struct TokenPanel {}
+#[builder]
+pub struct TokenPanel {
+    /// Read-only view - hide all buttons/menus to edit content.
+    #[builder]
+    #[prop_or_default]
+    pub readonly: bool,
+
+    /// Only show and manage the API tokens of this user.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub userid: Option<AttrValue>,
+}
 
 impl TokenPanel {
     pub fn new() -> Self {
@@ -166,11 +183,12 @@ impl LoadableComponent for ProxmoxTokenView {
 
     fn load(
         &self,
-        _ctx: &LoadableComponentContext<Self>,
+        ctx: &LoadableComponentContext<Self>,
     ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
         let store = self.store.clone();
+        let userid = ctx.props().userid.clone();
         Box::pin(async move {
-            let data = load_api_tokens().await?;
+            let data = load_api_tokens(userid).await?;
             store.write().set_data(data);
             Ok(())
         })
@@ -181,6 +199,19 @@ impl LoadableComponent for ProxmoxTokenView {
         let disabled = selected_id.is_none();
         let link = ctx.link();
 
+        let permissions_button = Button::new(tr!("Show Permissions"))
+            .disabled(disabled)
+            .on_activate(link.change_view_callback(|_| Some(ViewState::ShowPermissions)));
+
+        if ctx.props().readonly {
+            let toolbar = Toolbar::new()
+                .class("pwt-w-100")
+                .class("pwt-overflow-hidden")
+                .class("pwt-border-bottom")
+                .with_child(permissions_button);
+            return Some(toolbar.into());
+        }
+
         let toolbar = Toolbar::new()
             .class("pwt-w-100")
             .class("pwt-overflow-hidden")
@@ -215,11 +246,7 @@ impl LoadableComponent for ProxmoxTokenView {
                     .on_activate(link.callback(|_| Msg::Regenerate)),
             )
             .with_spacer()
-            .with_child(
-                Button::new(tr!("Show Permissions"))
-                    .disabled(disabled)
-                    .on_activate(link.change_view_callback(|_| Some(ViewState::ShowPermissions))),
-            );
+            .with_child(permissions_button);
 
         Some(toolbar.into())
     }
@@ -279,15 +306,18 @@ impl LoadableComponent for ProxmoxTokenView {
     }
 
     fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        let link = ctx.link().clone();
-
-        DataTable::new(self.columns.clone(), self.store.clone())
+        let mut table = DataTable::new(self.columns.clone(), self.store.clone())
             .class("pwt-flex-fit")
-            .selection(self.selection.clone())
-            .on_row_dblclick(move |_: &mut _| {
+            .selection(self.selection.clone());
+
+        if !ctx.props().readonly {
+            let link = ctx.link().clone();
+            table = table.on_row_dblclick(move |_: &mut _| {
                 link.change_view(Some(ViewState::EditToken));
-            })
-            .into()
+            });
+        }
+
+        table.into()
     }
 
     fn dialog_view(
@@ -346,27 +376,10 @@ impl ProxmoxTokenView {
                                 .class("pwt-form-grid-col4")
                                 .with_child(FieldLabel::new(tr!("Secret")))
                                 .with_child(
-                                    Row::new()
+                                    CopyField::new(secret)
                                         .class("pwt-fill-grid-row")
-                                        .gap(2)
-                                        .with_child(
-                                            Field::new()
-                                                .input_type(InputType::Password)
-                                                .class(pwt::css::FlexFit)
-                                                .value(secret.clone())
-                                                .name("secret")
-                                                .read_only(true),
-                                        )
-                                        .with_child(
-                                            Tooltip::new(
-                                                Button::new_icon("fa fa-clipboard")
-                                                    .class(ColorScheme::Primary)
-                                                    .on_activate(move |_| {
-                                                        copy_text_to_clipboard(&secret)
-                                                    }),
-                                            )
-                                            .tip(tr!("Copy token secret to clipboard.")),
-                                        ),
+                                        .password(true)
+                                        .tip(tr!("Copy token secret to clipboard.")),
                                 ),
                         ),
                 ),
@@ -387,8 +400,9 @@ impl ProxmoxTokenView {
 
     fn create_add_dialog(&self, ctx: &LoadableComponentContext<Self>) -> Html {
         let link = ctx.link().clone();
+        let userid = ctx.props().userid.clone();
         EditWindow::new(tr!("Add") + ": " + &tr!("Token"))
-            .renderer(add_input_panel)
+            .renderer(move |form_ctx: &FormContext| add_input_panel(form_ctx, userid.clone()))
             .on_submit(move |form_ctx| {
                 let link = link.clone();
                 create_token(form_ctx, link)
@@ -441,17 +455,26 @@ fn edit_input_panel(_form_ctx: &FormContext) -> Html {
         .into()
 }
 
-fn add_input_panel(_form_ctx: &FormContext) -> Html {
+fn add_input_panel(_form_ctx: &FormContext, userid: Option<AttrValue>) -> Html {
+    let user_field: Html = match userid {
+        Some(userid) => Field::new()
+            .name("userid")
+            .default(userid)
+            .required(true)
+            .disabled(true)
+            .submit(false)
+            .into(),
+        None => AuthidSelector::new()
+            .name("userid")
+            .required(true)
+            .submit(false)
+            .include_tokens(false)
+            .into(),
+    };
+
     InputPanel::new()
         .padding(4)
-        .with_field(
-            tr!("User"),
-            AuthidSelector::new()
-                .name("userid")
-                .required(true)
-                .submit(false)
-                .include_tokens(false),
-        )
+        .with_field(tr!("User"), user_field)
         .with_right_field(
             tr!("Expire"),
             Field::new()
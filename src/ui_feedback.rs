@@ -0,0 +1,216 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gloo_timers::callback::Timeout;
+
+use pwt::css::ColorScheme;
+use pwt::prelude::*;
+use pwt::widget::{ActionIcon, Column, Container, Progress, Row};
+
+thread_local! {
+    static NEXT_TOAST_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A toast notification.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub id: usize,
+    pub title: String,
+    pub message: String,
+    pub error: bool,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct UiFeedbackState {
+    busy: usize,
+    toasts: Vec<Toast>,
+}
+
+pub enum UiFeedbackAction {
+    BeginBusy,
+    EndBusy,
+    Toast(Toast),
+    Dismiss(usize),
+}
+
+impl Reducible for UiFeedbackState {
+    type Action = UiFeedbackAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        match action {
+            UiFeedbackAction::BeginBusy => state.busy += 1,
+            UiFeedbackAction::EndBusy => state.busy = state.busy.saturating_sub(1),
+            UiFeedbackAction::Toast(toast) => state.toasts.push(toast),
+            UiFeedbackAction::Dismiss(id) => state.toasts.retain(|toast| toast.id != id),
+        }
+        Rc::new(state)
+    }
+}
+
+/// Application wide busy indicator and toast notifications.
+///
+/// Provided by [UiFeedbackProvider]. If present, [LoadableComponent](crate::LoadableComponent)s
+/// report their loads as busy, and (if configured) show errors as toasts instead of
+/// dialogs.
+#[derive(Clone)]
+pub struct UiFeedback {
+    state: UseReducerHandle<UiFeedbackState>,
+    errors_as_toast: bool,
+    toast_timeout: u32,
+}
+
+impl PartialEq for UiFeedback {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+            && self.errors_as_toast == other.errors_as_toast
+            && self.toast_timeout == other.toast_timeout
+    }
+}
+
+impl UiFeedback {
+    /// Returns true if errors should be shown as toasts.
+    pub fn errors_as_toast(&self) -> bool {
+        self.errors_as_toast
+    }
+
+    /// Returns true if there are outstanding operations.
+    pub fn busy(&self) -> bool {
+        self.state.busy > 0
+    }
+
+    /// Mark the begin of an operation, each call must be followed by [Self::end_busy].
+    pub fn begin_busy(&self) {
+        self.state.dispatch(UiFeedbackAction::BeginBusy);
+    }
+
+    pub fn end_busy(&self) {
+        self.state.dispatch(UiFeedbackAction::EndBusy);
+    }
+
+    /// Show an informational toast.
+    pub fn show_info(&self, title: impl Into<String>, message: impl Into<String>) {
+        self.show_toast(title.into(), message.into(), false);
+    }
+
+    /// Show an error toast.
+    pub fn show_error(&self, title: impl Into<String>, message: impl std::fmt::Display) {
+        self.show_toast(title.into(), message.to_string(), true);
+    }
+
+    pub fn dismiss(&self, id: usize) {
+        self.state.dispatch(UiFeedbackAction::Dismiss(id));
+    }
+
+    fn show_toast(&self, title: String, message: String, error: bool) {
+        let id = NEXT_TOAST_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        });
+        self.state.dispatch(UiFeedbackAction::Toast(Toast {
+            id,
+            title,
+            message,
+            error,
+        }));
+
+        if self.toast_timeout > 0 {
+            let state = self.state.clone();
+            Timeout::new(self.toast_timeout, move || {
+                state.dispatch(UiFeedbackAction::Dismiss(id));
+            })
+            .forget();
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct UiFeedbackProviderProps {
+    #[prop_or_default]
+    pub children: Html,
+
+    /// Show errors of loadable components as toasts instead of dialogs.
+    #[prop_or_default]
+    pub errors_as_toast: bool,
+
+    /// Time in milliseconds until toasts disappear, `0` keeps them until dismissed.
+    #[prop_or(8000)]
+    pub toast_timeout: u32,
+}
+
+/// Provides the [UiFeedback] context and renders the progress bar and toasts.
+#[function_component]
+pub fn UiFeedbackProvider(props: &UiFeedbackProviderProps) -> Html {
+    let state = use_reducer_eq(UiFeedbackState::default);
+
+    let context = UiFeedback {
+        state: state.clone(),
+        errors_as_toast: props.errors_as_toast,
+        toast_timeout: props.toast_timeout,
+    };
+
+    // delay visibility, so that short loads do not flicker
+    let progress = (state.busy > 0).then(|| {
+        Progress::new()
+            .class("pwt-delay-visibility")
+            .style("position", "fixed")
+            .style("top", "0")
+            .style("left", "0")
+            .style("right", "0")
+            .style("z-index", "1000")
+    });
+
+    let toasts = (!state.toasts.is_empty()).then(|| {
+        Column::new()
+            .gap(2)
+            .style("position", "fixed")
+            .style("bottom", "1em")
+            .style("right", "1em")
+            .style("max-width", "400px")
+            .style("z-index", "1000")
+            .children(
+                state
+                    .toasts
+                    .iter()
+                    .map(|toast| render_toast(&context, toast))
+                    .collect::<Vec<Html>>(),
+            )
+    });
+
+    html! {
+        <ContextProvider<UiFeedback> context={context.clone()}>
+            {props.children.clone()}
+            {progress}
+            {toasts}
+        </ContextProvider<UiFeedback>>
+    }
+}
+
+fn render_toast(context: &UiFeedback, toast: &Toast) -> Html {
+    let id = toast.id;
+    let context = context.clone();
+
+    Row::new()
+        .key(id.to_string())
+        .class(if toast.error {
+            ColorScheme::ErrorContainer
+        } else {
+            ColorScheme::Neutral
+        })
+        .class("pwt-shadow2")
+        .padding(2)
+        .gap(2)
+        .with_child(
+            Column::new()
+                .class("pwt-flex-fill")
+                .with_child(
+                    Container::new()
+                        .class("pwt-font-title-small")
+                        .with_child(&toast.title),
+                )
+                .with_child(Container::new().with_child(&toast.message)),
+        )
+        .with_child(ActionIcon::new("fa fa-times").on_activate(move |_| context.dismiss(id)))
+        .into()
+}
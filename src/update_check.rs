@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use pwt::prelude::*;
+
+use crate::{ProjectInfo, Status, StatusRow};
+
+/// Result of [check_for_updates].
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateCheck {
+    /// The running version, as reported by the `/version` API call.
+    pub running_version: String,
+    /// Version of the product package in the list of available updates, if any.
+    pub available_version: Option<String>,
+}
+
+impl UpdateCheck {
+    /// Returns true if a newer product version is available.
+    pub fn update_available(&self) -> bool {
+        match &self.available_version {
+            Some(available) => {
+                compare_versions(upstream_version(available), &self.running_version)
+                    == Ordering::Greater
+            }
+            None => false,
+        }
+    }
+
+    /// Status row showing the running version, and whether an update is available.
+    pub fn status_row(&self) -> StatusRow {
+        let row = StatusRow::new(tr!("Version"));
+        let icon_class = |status: Status| classes!("fa", "fa-fw", status);
+        match &self.available_version {
+            Some(available) if self.update_available() => {
+                row.icon_class(icon_class(Status::Warning)).status(tr!(
+                    "{0} (update to {1} available)",
+                    &self.running_version,
+                    upstream_version(available)
+                ))
+            }
+            _ => row
+                .icon_class(icon_class(Status::Success))
+                .status(self.running_version.clone()),
+        }
+    }
+}
+
+// strip the Debian revision, the running version does not contain it
+fn upstream_version(version: &str) -> &str {
+    match version.rsplit_once('-') {
+        Some((upstream, _)) => upstream,
+        None => version,
+    }
+}
+
+/// Compare two version strings.
+///
+/// Numeric parts are compared as numbers, other parts as text.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |v: &str| -> Vec<String> {
+        v.split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    let (a, b) = (split(a), split(b));
+
+    for (a, b) in a.iter().zip(b.iter()) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Extract the running version from the `/version` API result.
+///
+/// Some products report the last component as `release` (for example `3.2` and `7`).
+pub fn running_version(data: &Value) -> Option<String> {
+    let version = data["version"].as_str()?;
+    Some(match data["release"].as_str() {
+        Some(release) if !release.is_empty() && !version.starts_with(release) => {
+            format!("{version}.{release}")
+        }
+        _ => version.to_string(),
+    })
+}
+
+/// Check if a newer version of the product is available.
+///
+/// Compares the running version against the product package in the list of available
+/// updates of the node (`{apt_url}/update`, for example `/nodes/localhost/apt/update`).
+pub async fn check_for_updates(
+    product: &dyn ProjectInfo,
+    apt_url: &str,
+) -> Result<UpdateCheck, Error> {
+    let data: Value = crate::http_get("/version", None).await?;
+    let running_version = running_version(&data)
+        .ok_or_else(|| Error::msg(tr!("Unable to determine the running version")))?;
+
+    let updates: Vec<Value> = crate::http_get(format!("{apt_url}/update"), None).await?;
+    let available_version = updates
+        .iter()
+        .find(|update| update["Package"].as_str() == Some(product.package_name()))
+        .and_then(|update| update["Version"].as_str())
+        .map(String::from);
+
+    Ok(UpdateCheck {
+        running_version,
+        available_version,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("8.2.4", "8.2.4"), Ordering::Equal);
+        assert_eq!(compare_versions("8.2.10", "8.2.9"), Ordering::Greater);
+        assert_eq!(compare_versions("8.2", "8.2.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_update_available() {
+        let mut check = UpdateCheck {
+            running_version: "3.2.7".into(),
+            available_version: Some("3.2.7-1".into()),
+        };
+        assert!(!check.update_available());
+        check.available_version = Some("3.2.8-1".into());
+        assert!(check.update_available());
+    }
+
+    #[test]
+    fn test_running_version() {
+        let pve = json!({ "version": "8.2.4", "release": "8.2" });
+        assert_eq!(running_version(&pve).as_deref(), Some("8.2.4"));
+        let pbs = json!({ "version": "3.2", "release": "7" });
+        assert_eq!(running_version(&pbs).as_deref(), Some("3.2.7"));
+    }
+}
@@ -22,7 +22,9 @@ use pwt::widget::form::{Checkbox, DateField, Field, FormContext, InputType};
 use pwt::widget::{Button, Dialog, InputPanel, Toolbar};
 
 use crate::form::delete_empty_values;
+use crate::grid_state::{GridState, PersistentGridState};
 use crate::percent_encoding::percent_encode_component;
+use crate::tfa::{render_tfa_lock_status, tfa_is_locked};
 use crate::utils::{
     epoch_to_input_date, epoch_to_input_time, parse_input_datetime, render_epoch_short,
 };
@@ -145,6 +147,16 @@ pub struct UserPanel {
     #[builder(IntoPropValue, into_prop_value)]
     #[prop_or(AttrValue::from("Y-m-d"))]
     date_format: AttrValue,
+
+    /// Read-only view - hide all buttons/menus to edit content.
+    #[builder]
+    #[prop_or_default]
+    readonly: bool,
+
+    /// Save column adjustments in the browser's local storage, using this id.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    state_id: Option<AttrValue>,
 }
 
 impl Default for UserPanel {
@@ -169,6 +181,8 @@ pub enum ViewState {
 
 pub enum Msg {
     RemoveItem,
+    UnlockTfa,
+    UpdateGridState(GridState),
 }
 
 pub struct ProxmoxUserPanel {
@@ -176,6 +190,7 @@ pub struct ProxmoxUserPanel {
     store: Store<UserWithTokens>,
     selection: Selection,
     product_realm: Option<AttrValue>,
+    grid_state: Option<PersistentGridState<UserWithTokens>>,
 }
 
 pwt::impl_deref_mut_property!(ProxmoxUserPanel, state, LoadableComponentState<ViewState>);
@@ -212,6 +227,11 @@ impl LoadableComponent for ProxmoxUserPanel {
             store,
             selection,
             product_realm: ctx.props().product_realm.clone(),
+            grid_state: ctx
+                .props()
+                .state_id
+                .as_deref()
+                .map(|state_id| PersistentGridState::new(state_id, columns())),
         }
     }
 
@@ -229,6 +249,24 @@ impl LoadableComponent for ProxmoxUserPanel {
                 }
                 false
             }
+            Msg::UnlockTfa => {
+                if let Some(key) = self.selection.selected_key() {
+                    let link = ctx.link().clone();
+                    link.clone().spawn(async move {
+                        if let Err(err) = crate::tfa::unlock_tfa(&key).await {
+                            link.show_error(tr!("Unable to unlock TFA"), err, true);
+                        }
+                        link.send_reload();
+                    })
+                }
+                false
+            }
+            Msg::UpdateGridState(state) => {
+                if let Some(grid_state) = &mut self.grid_state {
+                    grid_state.update(state);
+                }
+                true
+            }
         }
     }
 
@@ -236,6 +274,32 @@ impl LoadableComponent for ProxmoxUserPanel {
         let link = ctx.link();
 
         let no_selection = self.selection.is_empty();
+
+        let permissions_button = Button::new(tr!("Show Permissions"))
+            .disabled(no_selection)
+            .onclick(link.change_view_callback(|_| Some(ViewState::ShowPermissions)));
+        let columns_menu = self
+            .grid_state
+            .as_ref()
+            .map(|grid_state| grid_state.columns_menu(link.callback(Msg::UpdateGridState)));
+        let refresh_button = {
+            let loading = self.loading();
+            let link = ctx.link().clone();
+            Button::refresh(loading).onclick(move |_| link.send_reload())
+        };
+
+        if ctx.props().readonly {
+            let toolbar = Toolbar::new()
+                .class("pwt-w-100")
+                .class("pwt-overflow-hidden")
+                .class("pwt-border-bottom")
+                .with_child(permissions_button)
+                .with_flex_spacer()
+                .with_optional_child(columns_menu)
+                .with_child(refresh_button);
+            return Some(toolbar.into());
+        }
+
         let selected_user = self.get_selected_user().and_then(|user| Some(user));
         let disable_change_password = selected_user
             .as_ref()
@@ -248,6 +312,9 @@ impl LoadableComponent for ProxmoxUserPanel {
         let is_root_user = selected_user
             .as_ref()
             .is_some_and(|user| user.user.userid.as_str() == "root@pam");
+        let tfa_locked = selected_user
+            .as_ref()
+            .is_some_and(|user| tfa_is_locked(user.tfa_locked_until, user.totp_locked));
 
         let toolbar = Toolbar::new()
             .class("pwt-w-100")
@@ -283,31 +350,65 @@ impl LoadableComponent for ProxmoxUserPanel {
                     .disabled(disable_change_password)
                     .onclick(link.change_view_callback(|_| Some(ViewState::ChangePassword))),
             )
+            .with_child(permissions_button)
             .with_child(
-                Button::new(tr!("Show Permissions"))
-                    .disabled(no_selection)
-                    .onclick(link.change_view_callback(|_| Some(ViewState::ShowPermissions))),
+                ConfirmButton::new(tr!("Unlock TFA"))
+                    .disabled(!tfa_locked)
+                    .confirm_message(match &selected_user {
+                        Some(user) => tr!(
+                            "Are you sure you want to unlock TFA for '{0}'?",
+                            user.user.userid.as_str()
+                        ),
+                        None => String::new(),
+                    })
+                    .on_activate(link.callback(|_| Msg::UnlockTfa)),
             )
             .with_flex_spacer()
-            .with_child({
-                let loading = self.loading();
-                let link = ctx.link().clone();
-                Button::refresh(loading).onclick(move |_| link.send_reload())
-            });
+            .with_optional_child(columns_menu)
+            .with_child(refresh_button);
 
         Some(toolbar.into())
     }
 
     fn main_view(&self, ctx: &LoadableComponentContext<Self>) -> Html {
-        let link = ctx.link().clone();
-        DataTable::new(columns(), self.store.clone())
+        let columns = match &self.grid_state {
+            Some(grid_state) => grid_state.columns(),
+            None => columns(),
+        };
+
+        let mut table = DataTable::new(columns, self.store.clone())
             .class("pwt-flex-fill pwt-overflow-auto")
             .selection(self.selection.clone())
-            .striped(true)
-            .on_row_dblclick(move |_: &mut _| {
+            .striped(true);
+
+        if !ctx.props().readonly {
+            let link = ctx.link().clone();
+            table = table.on_row_dblclick(move |_: &mut _| {
                 link.change_view(Some(ViewState::Edit));
-            })
-            .into()
+            });
+        }
+
+        match &self.grid_state {
+            Some(grid_state) => grid_state
+                .track(table, ctx.link().callback(Msg::UpdateGridState))
+                .into(),
+            None => table.into(),
+        }
+    }
+
+    fn changed(
+        &mut self,
+        ctx: &LoadableComponentContext<Self>,
+        old_props: &Self::Properties,
+    ) -> bool {
+        let props = ctx.props();
+        if old_props.state_id != props.state_id {
+            self.grid_state = props
+                .state_id
+                .as_deref()
+                .map(|state_id| PersistentGridState::new(state_id, columns()));
+        }
+        true
     }
 
     fn dialog_view(
@@ -379,7 +480,7 @@ fn validate_time(time: &String) -> Result<(), Error> {
     bail!(tr!("not a valid time of day (24h HH:MM)"));
 }
 
-fn check_confirm_password(form_ctx: FormContext) {
+pub(crate) fn check_confirm_password(form_ctx: FormContext) {
     let pw = form_ctx.read().get_field_text("password");
     let confirm = form_ctx.read().get_field_text("confirm_password");
     if !confirm.is_empty() {
@@ -459,6 +560,7 @@ impl From<UserPanel> for VNode {
 thread_local! {
     static COLUMNS: Rc<Vec<DataTableHeader<UserWithTokens>>> = Rc::new(vec![
         DataTableColumn::new(tr!("Username"))
+            .key("userid")
             .flex(1)
             .justify("flex-start")
             .render(|item: &UserWithTokens| {
@@ -471,6 +573,7 @@ thread_local! {
             .into(),
 
         DataTableColumn::new(tr!("Realm"))
+            .key("realm")
             .render(|item: &UserWithTokens| {
                 html!{item.user.userid.realm().as_str()}
             })
@@ -480,6 +583,7 @@ thread_local! {
             .into(),
 
         DataTableColumn::new(tr!("Enabled"))
+            .key("enable")
             .justify("center")
             .render({
                 let yes_text = tr!("Yes");
@@ -501,6 +605,7 @@ thread_local! {
             .into(),
 
         DataTableColumn::new(tr!("Expire"))
+            .key("expire")
             .render({
                 let never_text = tr!("never");
                 move |item: &UserWithTokens| html!{
@@ -519,7 +624,25 @@ thread_local! {
             })
             .into(),
 
+        DataTableColumn::new(tr!("TFA Lock"))
+            .key("tfa-lock")
+            .render(|item: &UserWithTokens| {
+                html!{
+                    {
+                        render_tfa_lock_status(item.tfa_locked_until, item.totp_locked)
+                            .unwrap_or_default()
+                    }
+                }
+            })
+            .sorter(|a: &UserWithTokens, b: &UserWithTokens| {
+                let a = tfa_is_locked(a.tfa_locked_until, a.totp_locked);
+                let b = tfa_is_locked(b.tfa_locked_until, b.totp_locked);
+                a.cmp(&b)
+            })
+            .into(),
+
         DataTableColumn::new(tr!("Name"))
+            .key("name")
             .flex(1)
             .render(|item: &UserWithTokens| {
                 html!{
@@ -551,6 +674,7 @@ thread_local! {
             .into(),
 
         DataTableColumn::new(tr!("Email"))
+            .key("email")
             .flex(1)
             .render(|item: &UserWithTokens| {
                 html!{ { item.user.email.clone().unwrap_or_default() } }
@@ -561,6 +685,7 @@ thread_local! {
             .into(),
 
         DataTableColumn::new(tr!("Comment"))
+            .key("comment")
             .flex(1)
             .render(|item: &UserWithTokens| {
                 html!{ { item.user.comment.clone().unwrap_or_default() } }
@@ -0,0 +1,112 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use proxmox_human_byte::HumanByte;
+
+use pwt::prelude::*;
+use pwt::widget::data_table::DataTableColumn;
+use pwt::widget::{Column, Meter};
+
+/// Render a byte count (for example `1.5 GiB`), or `-` if unknown.
+pub fn render_bytes_cell(bytes: Option<u64>) -> Html {
+    match bytes {
+        Some(bytes) => html! { HumanByte::from(bytes).to_string() },
+        None => html! { "-" },
+    }
+}
+
+/// Format a transfer rate in bytes per second (for example `12 MiB/s`).
+pub fn format_bandwidth(bytes_per_second: f64) -> String {
+    format!("{}/s", HumanByte::from(bytes_per_second.max(0.0) as u64))
+}
+
+/// Render a transfer rate in bytes per second, or `-` if unknown.
+pub fn render_bandwidth_cell(bytes_per_second: Option<f64>) -> Html {
+    match bytes_per_second {
+        Some(bps) if bps.is_finite() => html! { format_bandwidth(bps) },
+        _ => html! { "-" },
+    }
+}
+
+/// Render a usage fraction (`0.0` - `1.0`) as meter with the percentage as text.
+pub fn render_percent_bar_cell(fraction: Option<f64>) -> Html {
+    let Some(fraction) = fraction.filter(|f| f.is_finite()) else {
+        return html! { "-" };
+    };
+
+    Column::new()
+        .gap(1)
+        .with_child(format!("{:.2} %", fraction * 100.0))
+        .with_child(
+            Meter::new()
+                .value(fraction.clamp(0.0, 1.0) as f32)
+                .low(0.8)
+                .high(0.9)
+                .optimum(0.0),
+        )
+        .into()
+}
+
+/// Compare optional floats, unknown values sort first.
+pub fn cmp_optional_f64(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    }
+}
+
+/// Column showing a byte count, sorted by the raw value.
+pub fn bytes_column<T: 'static>(
+    name: impl Into<AttrValue>,
+    get: impl 'static + Fn(&T) -> Option<u64>,
+) -> DataTableColumn<T> {
+    let get = Rc::new(get);
+    DataTableColumn::new(name)
+        .justify("right")
+        .render({
+            let get = Rc::clone(&get);
+            move |record: &T| render_bytes_cell(get(record))
+        })
+        .sorter(move |a: &T, b: &T| get(a).cmp(&get(b)))
+}
+
+/// Column showing a transfer rate in bytes per second, sorted by the raw value.
+pub fn bandwidth_column<T: 'static>(
+    name: impl Into<AttrValue>,
+    get: impl 'static + Fn(&T) -> Option<f64>,
+) -> DataTableColumn<T> {
+    let get = Rc::new(get);
+    DataTableColumn::new(name)
+        .justify("right")
+        .render({
+            let get = Rc::clone(&get);
+            move |record: &T| render_bandwidth_cell(get(record))
+        })
+        .sorter(move |a: &T, b: &T| cmp_optional_f64(get(a), get(b)))
+}
+
+/// Column showing a usage fraction (`0.0` - `1.0`) as meter, sorted by the raw value.
+pub fn percent_bar_column<T: 'static>(
+    name: impl Into<AttrValue>,
+    get: impl 'static + Fn(&T) -> Option<f64>,
+) -> DataTableColumn<T> {
+    let get = Rc::new(get);
+    DataTableColumn::new(name)
+        .render({
+            let get = Rc::clone(&get);
+            move |record: &T| render_percent_bar_cell(get(record))
+        })
+        .sorter(move |a: &T, b: &T| cmp_optional_f64(get(a), get(b)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cmp_optional_f64() {
+        let mut list = vec![Some(2.0), None, Some(-1.0), Some(0.5)];
+        list.sort_by(|a, b| cmp_optional_f64(*a, *b));
+        assert_eq!(list, [None, Some(-1.0), Some(0.5), Some(2.0)]);
+    }
+}
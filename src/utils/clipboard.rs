@@ -1,3 +1,4 @@
+use anyhow::Error;
 use wasm_bindgen::JsCast;
 use yew::NodeRef;
 
@@ -26,20 +27,28 @@ pub fn copy_to_clipboard(node_ref: &NodeRef) {
     }
 }
 
+/// Copies `text` to a user's clipboard via the `Clipboard` API and waits for the result.
+///
+/// Fails if the browser denies access to the clipboard, e.g. in non-secure contexts.
+pub async fn write_text_to_clipboard(text: &str) -> Result<(), Error> {
+    let future: wasm_bindgen_futures::JsFuture = gloo_utils::window()
+        .navigator()
+        .clipboard()
+        .write_text(text)
+        .into();
+
+    future.await.map_err(convert_js_error)?;
+    Ok(())
+}
+
 /// Copies `text` to a user's clipboard via the `Clipboard` API.
+///
+/// Errors are only logged, use [write_text_to_clipboard] to handle them.
 pub fn copy_text_to_clipboard(text: &str) {
     let text = text.to_owned();
 
     wasm_bindgen_futures::spawn_local(async move {
-        let future: wasm_bindgen_futures::JsFuture = gloo_utils::window()
-            .navigator()
-            .clipboard()
-            .write_text(&text)
-            .into();
-
-        let res = future.await.map_err(convert_js_error);
-
-        if let Err(e) = res {
+        if let Err(e) = write_text_to_clipboard(&text).await {
             log::error!("could not copy to clipboard: {e:#}");
         }
     });
@@ -0,0 +1,38 @@
+use anyhow::{format_err, Error};
+use wasm_bindgen::JsCast;
+
+use pwt::convert_js_error;
+
+/// Offer `text` as file download to the user.
+///
+/// Creates a temporary object URL and clicks a hidden link, so no server round trip is
+/// needed.
+pub fn download_text_file(filename: &str, text: &str) -> Result<(), Error> {
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(text));
+    let blob = web_sys::Blob::new_with_str_sequence(&parts).map_err(convert_js_error)?;
+    download_blob(filename, &blob)
+}
+
+/// Offer the content of `blob` as file download to the user.
+pub fn download_blob(filename: &str, blob: &web_sys::Blob) -> Result<(), Error> {
+    let url = web_sys::Url::create_object_url_with_blob(blob).map_err(convert_js_error)?;
+
+    let document = gloo_utils::document();
+    let link = document
+        .create_element("a")
+        .map_err(convert_js_error)?
+        .dyn_into::<web_sys::HtmlElement>()
+        .map_err(|_| format_err!("unable to create download link"))?;
+    let _ = link.set_attribute("href", &url);
+    let _ = link.set_attribute("download", filename);
+    let _ = link.style().set_property("display", "none");
+
+    if let Some(body) = document.body() {
+        let _ = body.append_child(&link);
+        link.click();
+        let _ = body.remove_child(&link);
+    }
+
+    let _ = web_sys::Url::revoke_object_url(&url);
+    Ok(())
+}
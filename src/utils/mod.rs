@@ -6,13 +6,20 @@ use yew::prelude::*;
 
 use crate::common_api_types::ProxmoxUpid;
 
+use pwt::props::{EventSubscriber, WidgetBuilder};
 use pwt::tr;
 
+mod cell_renderers;
 mod clipboard;
+mod download;
 mod task_descriptions;
 
+pub use cell_renderers::*;
+
 #[allow(deprecated)]
-pub use clipboard::{copy_text_to_clipboard, copy_to_clipboard};
+pub use clipboard::{copy_text_to_clipboard, copy_to_clipboard, write_text_to_clipboard};
+
+pub use download::{download_blob, download_text_file};
 
 pub use task_descriptions::*;
 
@@ -129,6 +136,82 @@ pub fn render_boolean(v: bool) -> String {
     }
 }
 
+/// Like [render_boolean], but renders unset values as "Unknown".
+pub fn render_optional_boolean(v: Option<bool>) -> String {
+    match v {
+        Some(v) => render_boolean(v),
+        None => tr!("Unknown"),
+    }
+}
+
+/// Interpret a JSON value as boolean.
+///
+/// Besides JSON booleans, this accepts the integers `0`/`1` and the strings used by the
+/// APIs (`0`/`1`, `yes`/`no`, `true`/`false`, `on`/`off`). Anything else is unknown.
+pub fn value_to_boolean(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(v) => Some(*v),
+        Value::Number(n) => match n.as_u64() {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            _ => None,
+        },
+        Value::String(s) => match s.to_lowercase().as_str() {
+            "1" | "yes" | "true" | "on" => Some(true),
+            "0" | "no" | "false" | "off" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Icon badge for a boolean, a check for yes, a minus for no and a question mark for unknown.
+pub fn render_boolean_icon(v: Option<bool>) -> Html {
+    let icon_class = match v {
+        Some(true) => "fa fa-check",
+        Some(false) => "fa fa-minus",
+        None => "fa fa-question pwt-opacity-50",
+    };
+    html! {<i class={icon_class}/>}
+}
+
+/// Icon badge followed by the text for a boolean.
+pub fn render_boolean_badge(v: Option<bool>) -> Html {
+    html! {
+        <span class="pwt-white-space-nowrap">
+            {render_boolean_icon(v)}
+            {" "}
+            {render_optional_boolean(v)}
+        </span>
+    }
+}
+
+/// Make a widget activatable by click or by pressing Enter/Space, if a callback is set.
+///
+/// Used by the status widgets, so product shells can link them to the matching detail page.
+pub fn activatable<W: WidgetBuilder + EventSubscriber>(
+    widget: W,
+    on_activate: Option<&Callback<()>>,
+) -> W {
+    let Some(on_activate) = on_activate.cloned() else {
+        return widget;
+    };
+    widget
+        .attribute("role", "button")
+        .attribute("tabindex", "0")
+        .style("cursor", "pointer")
+        .onclick({
+            let on_activate = on_activate.clone();
+            move |_| on_activate.emit(())
+        })
+        .onkeydown(move |event: KeyboardEvent| {
+            if matches!(event.key().as_str(), "Enter" | " ") {
+                event.prevent_default();
+                on_activate.emit(());
+            }
+        })
+}
+
 pub fn render_url(url: &str) -> Html {
     if url.starts_with("http://") || url.starts_with("https://") {
         html! {<a target="_blank" href={url.to_owned()}>{url}</a>}
@@ -306,9 +389,7 @@ pub fn set_location_href(href: &str) {
 pub fn format_upid(upid: &str) -> String {
     match upid.parse::<ProxmoxUpid>() {
         Err(_) => upid.to_string(),
-        Ok(upid) => {
-            task_descriptions::format_task_description(&upid.worker_type, upid.worker_id.as_deref())
-        }
+        Ok(upid) => upid.description(),
     }
 }
 
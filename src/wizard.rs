@@ -26,6 +26,7 @@ use yew::virtual_dom::{Key, VComp, VNode};
 use yew::{Callback, Component, Html, Properties};
 
 use super::{IntoSubmitValueCallback, SubmitValueCallback};
+use crate::{ApiError, KVGrid, KVGridRow};
 
 use pwt_macros::builder;
 
@@ -99,6 +100,51 @@ impl WizardPageRenderInfo {
             .insert(self.key.clone(), callback.into());
     }
 
+    /// Sets an async validator that will be called when a later page wants to be selected.
+    ///
+    /// The validator gets the submit data of all pages. The wizard is masked while it runs
+    /// and only advances if it succeeds, so this can be used for checks which need the API
+    /// (e.g. whether a VMID is still available). Parameter errors of an [ApiError] are shown
+    /// on the fields of the page.
+    ///
+    /// Like [Self::on_next], this is not used by [Self::go_to_next_page].
+    pub fn on_next_async(&self, validator: impl IntoSubmitValueCallback) {
+        let mut controller = self.controller.write();
+        match validator.into_submit_value_callback() {
+            Some(validator) => {
+                controller
+                    .async_validators
+                    .insert(self.key.clone(), validator);
+            }
+            None => {
+                controller.async_validators.remove(&self.key);
+            }
+        }
+    }
+
+    /// Renders the submit data of all pages as [KVGrid], useful for a final summary page.
+    ///
+    /// If `rows` is empty, all submitted properties are shown with their name as header.
+    pub fn summary_grid(&self, rows: Vec<KVGridRow>) -> KVGrid {
+        let rows = if rows.is_empty() {
+            self.valid_data
+                .as_object()
+                .map(|data| {
+                    data.keys()
+                        .map(|name| KVGridRow::new(name.clone(), name.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            rows
+        };
+
+        KVGrid::new()
+            .class(FlexFit)
+            .data(Rc::clone(&self.valid_data))
+            .rows(Rc::new(rows))
+    }
+
     /// Navigates the wizard to the next page (if possible)
     ///
     /// Note that callbacks setup with `on_next` will not be called,
@@ -121,6 +167,7 @@ impl WizardPageRenderInfo {
 struct PageConfig {
     tab_bar_item: TabBarItem,
     renderer: RenderFn<WizardPageRenderInfo>,
+    condition: Option<Callback<Rc<Value>, bool>>,
 }
 
 #[derive(Clone, Properties, PartialEq)]
@@ -218,12 +265,34 @@ impl Wizard {
 
     /// Method to add a wizard page.
     pub fn with_page(
-        mut self,
+        self,
         item: impl Into<TabBarItem>,
         renderer: impl 'static + Fn(&WizardPageRenderInfo) -> Html,
     ) -> Self {
-        let mut item = item.into();
+        self.add_page(item.into(), None, RenderFn::new(renderer))
+    }
 
+    /// Method to add a wizard page which is only shown if `condition` returns true.
+    ///
+    /// The condition gets the submit data of the previous pages (see
+    /// [WizardPageRenderInfo::valid_data]), so pages can be skipped depending on earlier
+    /// selections, for example the disk page when importing a guest.
+    pub fn with_conditional_page(
+        self,
+        item: impl Into<TabBarItem>,
+        condition: impl 'static + Fn(&Value) -> bool,
+        renderer: impl 'static + Fn(&WizardPageRenderInfo) -> Html,
+    ) -> Self {
+        let condition = Callback::from(move |data: Rc<Value>| condition(&data));
+        self.add_page(item.into(), Some(condition), RenderFn::new(renderer))
+    }
+
+    fn add_page(
+        mut self,
+        mut item: TabBarItem,
+        condition: Option<Callback<Rc<Value>, bool>>,
+        renderer: RenderFn<WizardPageRenderInfo>,
+    ) -> Self {
         if item.key.is_none() {
             item.key = Some(Key::from(format!("__wizard_page{}", self.pages.len())));
         }
@@ -231,8 +300,9 @@ impl Wizard {
         let key = item.key.clone().unwrap();
 
         let page = PageConfig {
-            renderer: RenderFn::new(renderer),
+            renderer,
             tab_bar_item: item,
+            condition,
         };
 
         self.pages.insert(key, page);
@@ -256,10 +326,11 @@ struct WizardState {
     link: Scope<PwtWizard>,
     page: Option<Key>,
     page_data: HashMap<Key, FormContext>,
-    page_list: Vec<Key>,
+    page_list: Vec<Key>, // visible pages
     pages_valid: HashSet<Key>,
     pages_lock: HashSet<Key>,
     submit_callbacks: HashMap<Key, Callback<(), bool>>,
+    async_validators: HashMap<Key, SubmitValueCallback>,
 }
 
 impl WizardState {
@@ -323,6 +394,7 @@ impl WizardController {
             pages_valid: HashSet::new(),
             pages_lock: HashSet::new(),
             submit_callbacks: HashMap::new(),
+            async_validators: HashMap::new(),
         };
         Self {
             state: Rc::new(RefCell::new(state)),
@@ -370,6 +442,7 @@ pub enum Msg {
     CloseDialog,
     Submit,
     SubmitResult(Result<(), Error>),
+    ValidationResult(Key, Key, Result<(), Error>), // validated page, target page
     ClearError,
     ShowAdvanced(bool),
 }
@@ -392,7 +465,7 @@ impl Component for PwtWizard {
             controller.insert_page(key, *show_advanced);
         }
 
-        Self {
+        let mut this = Self {
             loading: false,
             submit_error: None,
             selection,
@@ -400,7 +473,9 @@ impl Component for PwtWizard {
             show_advanced,
             controller,
             async_pool: AsyncPool::new(),
-        }
+        };
+        this.update_valid_data(ctx);
+        this
     }
 
     fn update(&mut self, ctx: &yew::Context<Self>, msg: Self::Message) -> bool {
@@ -422,6 +497,14 @@ impl Component for PwtWizard {
                                     return true;
                                 }
                             }
+                            let current = state.page.clone().unwrap();
+                            if let Some(validator) = state.async_validators.get(&current).cloned() {
+                                // stay on the current page until the validator succeeded
+                                self.selection.select(current.clone());
+                                drop(state);
+                                self.validate_page(ctx, current, page, validator);
+                                return true;
+                            }
                         }
                         _ => {}
                     }
@@ -485,6 +568,36 @@ impl Component for PwtWizard {
                     }
                 }
             }
+            Msg::ValidationResult(validated_page, target, result) => {
+                self.loading = false;
+                let current = self.controller.read().page.clone();
+                if current.as_ref() != Some(&validated_page) {
+                    return true; // page changed meanwhile
+                }
+                match result {
+                    Ok(()) => {
+                        return <Self as yew::Component>::update(
+                            self,
+                            ctx,
+                            Msg::SelectPage(target, false),
+                        );
+                    }
+                    Err(err) => {
+                        if let Some(api_err) = ApiError::from_error(&err) {
+                            let form_ctx = self
+                                .controller
+                                .read()
+                                .page_data
+                                .get(&validated_page)
+                                .cloned();
+                            if let Some(form_ctx) = form_ctx {
+                                api_err.mark_form_fields(&form_ctx);
+                            }
+                        }
+                        self.submit_error = Some(err.to_string());
+                    }
+                }
+            }
             Msg::ClearError => {
                 self.submit_error = None;
             }
@@ -519,7 +632,10 @@ impl Component for PwtWizard {
         let state = self.controller.read();
 
         let mut disabled = false;
-        for (page_num, (key, page)) in props.pages.iter().enumerate() {
+        for (key, page) in props.pages.iter() {
+            let Some(page_num) = state.get_index(key) else {
+                continue; // hidden page
+            };
             let active = Some(key) == state.page.as_ref();
             let form_ctx = state.page_data.get(key).unwrap();
 
@@ -531,10 +647,7 @@ impl Component for PwtWizard {
                 controller: self.controller.clone(),
             });
 
-            let next_page = props
-                .pages
-                .get_index(page_num + 1)
-                .map(|(key, _)| key.clone());
+            let next_page = state.page_list.get(page_num + 1).cloned();
 
             let page_content = Form::new()
                 .class(Overflow::Auto)
@@ -560,10 +673,10 @@ impl Component for PwtWizard {
             let tab_bar_item = page.tab_bar_item.clone().disabled(disabled);
 
             if !disabled {
-                if !self.controller.read().page_valid(key) {
+                if !state.page_valid(key) {
                     disabled = true;
                 }
-                if self.controller.read().page_locked(key) {
+                if state.page_locked(key) {
                     disabled = true;
                 }
             }
@@ -614,10 +727,13 @@ impl PwtWizard {
     fn update_valid_data(&mut self, ctx: &yew::Context<Self>) {
         let props = ctx.props();
 
-        let state = self.controller.read();
+        let mut state = self.controller.write();
 
         let mut valid_data = serde_json::Map::new();
         for (key, _) in props.pages.iter() {
+            if !state.page_list.contains(key) {
+                continue; // hidden pages do not contribute
+            }
             if let Some(form_ctx) = state.page_data.get(key) {
                 let mut data = form_ctx.read().get_submit_data();
                 valid_data.append(data.as_object_mut().unwrap());
@@ -628,6 +744,36 @@ impl PwtWizard {
         }
 
         self.valid_data = Rc::new(Value::Object(valid_data));
+
+        // the current page always stays visible
+        state.page_list = props
+            .pages
+            .iter()
+            .filter(|(key, page)| {
+                Some(*key) == state.page.as_ref()
+                    || page
+                        .condition
+                        .as_ref()
+                        .is_none_or(|condition| condition.emit(Rc::clone(&self.valid_data)))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+    }
+
+    fn validate_page(
+        &mut self,
+        ctx: &yew::Context<Self>,
+        page: Key,
+        target: Key,
+        validator: SubmitValueCallback,
+    ) {
+        let link = ctx.link().clone();
+        let data = self.valid_data.as_ref().clone();
+        self.loading = true;
+        self.async_pool.spawn(async move {
+            let result = validator.apply(data).await;
+            link.send_message(Msg::ValidationResult(page, target, result));
+        });
     }
 
     fn create_bottom_bar(&self, ctx: &yew::Context<Self>) -> Row {
@@ -635,36 +781,27 @@ impl PwtWizard {
 
         let state = self.controller.read();
 
-        let first_page = props.pages.first().map(|(key, _value)| key.clone());
+        let first_page = state.page_list.first().cloned();
 
         let is_first = match &state.page {
             None => true,
             Some(key) => Some(key) == first_page.as_ref(),
         };
 
-        let last_page = props.pages.last().map(|(key, _value)| key.clone());
+        let last_page = state.page_list.last().cloned();
 
         let is_last = match &state.page {
             None => false,
             Some(key) => Some(key) == last_page.as_ref(),
         };
 
-        let page_num = match &state.page {
-            None => 0,
-            Some(key) => props.pages.get_index_of(key).unwrap_or(0),
-        };
+        let page_num = state.get_current_index().unwrap_or(0);
 
         let next_is_enabled = !self.loading && state.can_progress();
 
-        let next_page = props
-            .pages
-            .get_index(page_num + 1)
-            .map(|(key, _)| key.clone());
+        let next_page = state.page_list.get(page_num + 1).cloned();
 
-        let prev_page = props
-            .pages
-            .get_index(page_num.saturating_sub(1))
-            .map(|(key, _)| key.clone());
+        let prev_page = state.page_list.get(page_num.saturating_sub(1)).cloned();
 
         let next_button_text = if is_last {
             props